@@ -0,0 +1,81 @@
+//! Reconnect backoff with full jitter
+//!
+//! Pure exponential backoff makes every stream that lost its connection at the
+//! same moment (e.g. a Binance-side disconnect) retry at the same moment too.
+//! "Full jitter" keeps the exponentially growing ceiling but sleeps for a
+//! uniformly random duration below it, spreading reconnects across the window.
+
+use rand::Rng;
+use std::time::Duration;
+
+/// Exponentially growing backoff ceiling with randomized delays
+#[derive(Debug, Clone)]
+pub struct JitteredBackoff {
+    initial: Duration,
+    max: Duration,
+    ceiling: Duration,
+}
+
+impl JitteredBackoff {
+    /// Create a backoff whose ceiling starts at `initial` and doubles up to `max`
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        let initial = initial.clamp(Duration::from_millis(1), max.max(Duration::from_millis(1)));
+        Self {
+            initial,
+            max,
+            ceiling: initial,
+        }
+    }
+
+    /// Next delay to sleep: uniform in `[1ms, ceiling]`, then the ceiling doubles
+    ///
+    /// Never returns zero and never exceeds `max`.
+    pub fn next_delay(&mut self) -> Duration {
+        let ceiling_ms = (self.ceiling.as_millis() as u64).max(1);
+        let delay = Duration::from_millis(rand::thread_rng().gen_range(1..=ceiling_ms));
+        self.ceiling = std::cmp::min(self.ceiling.saturating_mul(2), self.max);
+        delay
+    }
+
+    /// Current ceiling (upper bound of the next delay)
+    pub fn ceiling(&self) -> Duration {
+        self.ceiling
+    }
+
+    /// Reset the ceiling after a successful connection
+    pub fn reset(&mut self) {
+        self.ceiling = self.initial;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_bounded_and_positive() {
+        let max = Duration::from_secs(30);
+        let mut backoff = JitteredBackoff::new(Duration::from_millis(100), max);
+
+        for _ in 0..10_000 {
+            let delay = backoff.next_delay();
+            assert!(delay > Duration::ZERO);
+            assert!(delay <= max);
+            assert!(backoff.ceiling() <= max);
+        }
+        assert_eq!(backoff.ceiling(), max);
+    }
+
+    #[test]
+    fn test_reset_restores_initial_ceiling() {
+        let mut backoff = JitteredBackoff::new(Duration::from_secs(1), Duration::from_secs(30));
+        for _ in 0..5 {
+            backoff.next_delay();
+        }
+        assert_eq!(backoff.ceiling(), Duration::from_secs(30));
+
+        backoff.reset();
+        assert_eq!(backoff.ceiling(), Duration::from_secs(1));
+        assert!(backoff.next_delay() <= Duration::from_secs(1));
+    }
+}
@@ -0,0 +1,424 @@
+//! Circuit breaker for upstream Binance failures
+//!
+//! Repeated 429/418 (rate limit / IP ban), 5xx or connection failures mean more
+//! requests only extend the ban or pile onto an outage. After
+//! `failure_threshold` consecutive failures within `failure_window` the circuit
+//! opens: calls fail fast without touching the network until the cooldown (or
+//! a longer `Retry-After`) has elapsed. The first call after that is a probe
+//! (half-open) whose outcome closes the circuit again or re-opens it. A probe
+//! that never reports back (its request future was dropped by a timeout or
+//! cancellation) expires after another cooldown, letting the next call probe.
+
+use crate::error::McpError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Time source for the breaker (injectable for tests)
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// Wall clock ([`Instant::now`])
+#[derive(Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Breaker thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit
+    pub failure_threshold: u32,
+
+    /// Failures further apart than this do not count as consecutive
+    pub failure_window: Duration,
+
+    /// How long the circuit stays open (unless `Retry-After` asks for longer)
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            failure_window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Breaker state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests fail fast without calling Binance
+    Open,
+    /// Cooldown elapsed; a single probe request is allowed through
+    HalfOpen,
+}
+
+/// Kind of upstream failure, deciding the fail-fast error code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpstreamFailure {
+    /// HTTP 429 / 418
+    RateLimited,
+    /// HTTP 5xx, connection failure or timeout
+    Unavailable,
+}
+
+/// Breaker state as reported by health checks
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CircuitStatus {
+    pub state: CircuitState,
+
+    /// Consecutive upstream failures counted so far
+    pub consecutive_failures: u32,
+
+    /// Milliseconds until a probe is allowed (open circuit only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_in_ms: Option<u64>,
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: CircuitState,
+    consecutive_failures: u32,
+    last_failure_at: Option<Instant>,
+    open_until: Option<Instant>,
+    last_failure: UpstreamFailure,
+    /// When the running half-open probe was admitted
+    probe_started_at: Option<Instant>,
+}
+
+/// Shared circuit breaker guarding Binance REST calls
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    clock: Arc<dyn Clock>,
+    inner: Mutex<BreakerInner>,
+}
+
+impl std::fmt::Debug for CircuitBreaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CircuitBreaker")
+            .field("config", &self.config)
+            .field("status", &self.status())
+            .finish()
+    }
+}
+
+impl CircuitBreaker {
+    /// Create a closed breaker on the wall clock
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self::with_clock(config, Arc::new(SystemClock))
+    }
+
+    /// Create a closed breaker reading time from `clock`
+    pub fn with_clock(config: CircuitBreakerConfig, clock: Arc<dyn Clock>) -> Self {
+        Self {
+            config,
+            clock,
+            inner: Mutex::new(BreakerInner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                last_failure_at: None,
+                open_until: None,
+                last_failure: UpstreamFailure::Unavailable,
+                probe_started_at: None,
+            }),
+        }
+    }
+
+    /// Current state (an open circuit past its cooldown reports `HalfOpen`)
+    pub fn state(&self) -> CircuitState {
+        self.status().state
+    }
+
+    /// Current state with failure count and remaining cooldown
+    pub fn status(&self) -> CircuitStatus {
+        let now = self.clock.now();
+        let inner = self.inner.lock().expect("circuit breaker lock poisoned");
+        let remaining = inner
+            .open_until
+            .map(|until| until.saturating_duration_since(now));
+        let state = match (inner.state, remaining) {
+            (CircuitState::Open, Some(Duration::ZERO)) => CircuitState::HalfOpen,
+            (state, _) => state,
+        };
+        CircuitStatus {
+            state,
+            consecutive_failures: inner.consecutive_failures,
+            retry_in_ms: remaining
+                .filter(|_| state == CircuitState::Open)
+                .map(|d| d.as_millis() as u64),
+        }
+    }
+
+    /// Admit a request, or fail fast while the circuit is open
+    ///
+    /// # Errors
+    /// `RateLimitError` (after 429/418) or `ConnectionError` (after 5xx or
+    /// connection failures) while open or while the half-open probe is running
+    pub fn check(&self) -> Result<(), McpError> {
+        let now = self.clock.now();
+        let mut inner = self.inner.lock().expect("circuit breaker lock poisoned");
+
+        if inner.state == CircuitState::Open {
+            match inner.open_until {
+                Some(until) if now < until => {
+                    return Err(fail_fast_error(inner.last_failure, until - now));
+                }
+                _ => {
+                    tracing::info!("Binance circuit half-open, sending probe request");
+                    inner.state = CircuitState::HalfOpen;
+                    inner.probe_started_at = None;
+                }
+            }
+        }
+
+        if inner.state == CircuitState::HalfOpen {
+            // A probe outstanding for a whole cooldown was abandoned unrecorded
+            let probe_running = inner.probe_started_at.is_some_and(|started| {
+                now.saturating_duration_since(started) < self.config.cooldown
+            });
+            if probe_running {
+                return Err(fail_fast_error(inner.last_failure, Duration::ZERO));
+            }
+            inner.probe_started_at = Some(now);
+        }
+
+        Ok(())
+    }
+
+    /// Record an answer from Binance that was not an upstream failure
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().expect("circuit breaker lock poisoned");
+        if inner.state != CircuitState::Closed {
+            tracing::info!("Binance circuit closed");
+        }
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.last_failure_at = None;
+        inner.open_until = None;
+        inner.probe_started_at = None;
+    }
+
+    /// Record an upstream failure
+    ///
+    /// `retry_after` (from the `Retry-After` header) opens the circuit right away
+    /// for at least that long, since Binance has told us when to come back.
+    pub fn record_failure(&self, failure: UpstreamFailure, retry_after: Option<Duration>) {
+        let now = self.clock.now();
+        let mut inner = self.inner.lock().expect("circuit breaker lock poisoned");
+
+        let within_window = inner
+            .last_failure_at
+            .is_some_and(|last| now.saturating_duration_since(last) <= self.config.failure_window);
+        inner.consecutive_failures = if within_window {
+            inner.consecutive_failures + 1
+        } else {
+            1
+        };
+        inner.last_failure_at = Some(now);
+        inner.last_failure = failure;
+
+        let should_open = inner.state == CircuitState::HalfOpen
+            || retry_after.is_some()
+            || inner.consecutive_failures >= self.config.failure_threshold;
+        if should_open {
+            let cooldown = retry_after.map_or(self.config.cooldown, |retry_after| {
+                retry_after.max(self.config.cooldown)
+            });
+            tracing::warn!(
+                failures = inner.consecutive_failures,
+                cooldown_secs = cooldown.as_secs(),
+                failure = ?failure,
+                "Binance circuit opened, failing fast"
+            );
+            inner.state = CircuitState::Open;
+            inner.open_until = Some(now + cooldown);
+            inner.probe_started_at = None;
+        }
+    }
+
+    /// Record the outcome of a completed HTTP exchange
+    pub fn record_response(&self, response: &reqwest::Response) {
+        let status = response.status().as_u16();
+        let failure = match status {
+            429 | 418 => UpstreamFailure::RateLimited,
+            500..=599 => UpstreamFailure::Unavailable,
+            _ => return self.record_success(),
+        };
+        self.record_failure(failure, super::retry::retry_after(response));
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self::new(CircuitBreakerConfig::default())
+    }
+}
+
+fn fail_fast_error(failure: UpstreamFailure, retry_in: Duration) -> McpError {
+    let message = format!(
+        "Binance circuit open after repeated upstream failures; retry in {}s",
+        retry_in.as_secs().max(1)
+    );
+    match failure {
+        UpstreamFailure::RateLimited => McpError::RateLimitError(message),
+        UpstreamFailure::Unavailable => McpError::ConnectionError(message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Clock advanced by hand
+    struct ManualClock {
+        start: Instant,
+        offset: Mutex<Duration>,
+    }
+
+    impl ManualClock {
+        fn new() -> Arc<Self> {
+            Arc::new(Self {
+                start: Instant::now(),
+                offset: Mutex::new(Duration::ZERO),
+            })
+        }
+
+        fn advance(&self, by: Duration) {
+            *self.offset.lock().unwrap() += by;
+        }
+    }
+
+    impl Clock for ManualClock {
+        fn now(&self) -> Instant {
+            self.start + *self.offset.lock().unwrap()
+        }
+    }
+
+    fn breaker(clock: &Arc<ManualClock>) -> CircuitBreaker {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 3,
+            failure_window: Duration::from_secs(60),
+            cooldown: Duration::from_secs(30),
+        };
+        CircuitBreaker::with_clock(config, clock.clone())
+    }
+
+    #[test]
+    fn test_opens_after_threshold_then_half_open_then_closed() {
+        let clock = ManualClock::new();
+        let breaker = breaker(&clock);
+
+        for _ in 0..2 {
+            assert!(breaker.check().is_ok());
+            breaker.record_failure(UpstreamFailure::Unavailable, None);
+        }
+        assert_eq!(breaker.state(), CircuitState::Closed);
+
+        breaker.record_failure(UpstreamFailure::Unavailable, None);
+        assert_eq!(breaker.state(), CircuitState::Open);
+        let err = breaker.check().unwrap_err();
+        assert_eq!(err.error_code(), "UPSTREAM_UNAVAILABLE");
+
+        clock.advance(Duration::from_secs(30));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+
+        // One probe at a time
+        assert!(breaker.check().is_ok());
+        assert!(breaker.check().is_err());
+
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert!(breaker.check().is_ok());
+        assert_eq!(breaker.status().consecutive_failures, 0);
+    }
+
+    #[test]
+    fn test_failed_probe_reopens() {
+        let clock = ManualClock::new();
+        let breaker = breaker(&clock);
+        for _ in 0..3 {
+            breaker.record_failure(UpstreamFailure::RateLimited, None);
+        }
+
+        clock.advance(Duration::from_secs(31));
+        assert!(breaker.check().is_ok());
+        breaker.record_failure(UpstreamFailure::RateLimited, None);
+
+        assert_eq!(breaker.state(), CircuitState::Open);
+        let err = breaker.check().unwrap_err();
+        assert_eq!(err.error_code(), "RATE_LIMITED");
+        assert_eq!(breaker.status().retry_in_ms, Some(30_000));
+    }
+
+    #[tokio::test]
+    async fn test_dropped_probe_expires_after_cooldown() {
+        let clock = ManualClock::new();
+        let breaker = breaker(&clock);
+        for _ in 0..3 {
+            breaker.record_failure(UpstreamFailure::Unavailable, None);
+        }
+        clock.advance(Duration::from_secs(30));
+
+        // The probe request is dropped (e.g. by a caller's timeout) before it
+        // records an outcome
+        let probe = async {
+            breaker.check().unwrap();
+            std::future::pending::<()>().await;
+        };
+        assert!(tokio::time::timeout(Duration::from_millis(10), probe)
+            .await
+            .is_err());
+
+        assert!(breaker.check().is_err());
+        clock.advance(Duration::from_secs(29));
+        assert!(breaker.check().is_err());
+
+        // Abandoned for a whole cooldown: the next call probes again
+        clock.advance(Duration::from_secs(1));
+        assert!(breaker.check().is_ok());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_failures_outside_window_are_not_consecutive() {
+        let clock = ManualClock::new();
+        let breaker = breaker(&clock);
+
+        for _ in 0..5 {
+            breaker.record_failure(UpstreamFailure::Unavailable, None);
+            clock.advance(Duration::from_secs(61));
+        }
+
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.status().consecutive_failures, 1);
+    }
+
+    #[test]
+    fn test_retry_after_opens_immediately_for_longer_cooldown() {
+        let clock = ManualClock::new();
+        let breaker = breaker(&clock);
+
+        breaker.record_failure(UpstreamFailure::RateLimited, Some(Duration::from_secs(120)));
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(breaker.status().retry_in_ms, Some(60_000));
+
+        clock.advance(Duration::from_secs(60));
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+    }
+}
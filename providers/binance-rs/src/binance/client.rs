@@ -2,15 +2,36 @@
 //!
 //! HTTP client wrapper for making requests to Binance REST API.
 //! Provides timeout configuration, user-agent headers, and request signing.
+//! Every request passes through a shared [`CircuitBreaker`] that fails fast while
+//! Binance is rate limiting us or unavailable. Public GET requests are retried
+//! on transient failures according to the client's [`RetryPolicy`].
+//! Signed timestamps are corrected by the [`ClockOffset`] measured against
+//! Binance server time.
 
+use crate::binance::circuit::{CircuitBreaker, CircuitState, CircuitStatus, UpstreamFailure};
+use crate::binance::headers::{RequestHeaders, API_KEY_HEADER};
+use crate::binance::market::Market;
+use crate::binance::retry::{self, RetryPolicy};
+use crate::binance::time_sync::{clock_offset_ms, ClockOffset, MAX_CLOCK_SKEW_MS};
 use crate::binance::types::{
-    KlineData, OrderBook, ServerTimeResponse, Ticker24hr, TickerPrice, Trade,
+    AccountInfo, AggTradeResponse, BookTicker, ExchangeInfo, KlineData, LongShortRatio,
+    OpenInterest, OpenInterestHist, OrderBook, ServerTimeResponse, Ticker24hr, TickerPrice, Trade,
 };
 use crate::error::McpError;
 use hmac::{Hmac, Mac};
 use reqwest::Client;
 use sha2::Sha256;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Milliseconds after `timestamp` during which Binance accepts a signed request
+const RECV_WINDOW_MS: u64 = 5000;
+
+/// How long an all-symbols 24hr ticker response is reused (the request weighs 80)
+pub const ALL_TICKERS_CACHE_TTL: Duration = Duration::from_secs(10);
+
+/// Most recent all-symbols 24hr ticker response and when it was fetched
+type AllTickersCache = Mutex<Option<(Instant, Arc<Vec<Ticker24hr>>)>>;
 
 type HmacSha256 = Hmac<Sha256>;
 
@@ -24,18 +45,33 @@ pub struct BinanceClient {
     pub(crate) client: Client,
     /// Base URL for Binance API (default: https://api.binance.com)
     pub(crate) base_url: String,
+    /// Base URL for USDⓈ-M futures data (default: https://fapi.binance.com)
+    pub(crate) futures_base_url: String,
     /// Optional API key for authenticated requests
     pub(crate) api_key: Option<String>,
     /// Optional API secret for request signing
     pub(crate) api_secret: Option<String>,
+    /// Upstream failure breaker, shared by clones of this client
+    pub(crate) circuit: Arc<CircuitBreaker>,
+    /// Binance server time minus local time, shared by clones of this client
+    pub(crate) clock_offset: Arc<ClockOffset>,
+    /// Retries for public GET requests
+    pub(crate) retry: RetryPolicy,
+    /// Cached [`get_all_24hr_tickers`](Self::get_all_24hr_tickers) response,
+    /// shared by clones of this client
+    pub(crate) all_tickers: Arc<AllTickersCache>,
 }
 
 impl std::fmt::Debug for BinanceClient {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BinanceClient")
             .field("base_url", &self.base_url)
+            .field("futures_base_url", &self.futures_base_url)
             .field("api_key", &self.api_key.as_ref().map(|_| "***"))
             .field("api_secret", &self.api_secret.as_ref().map(|_| "***"))
+            .field("circuit", &self.circuit)
+            .field("clock_offset_ms", &self.clock_offset.get())
+            .field("retry", &self.retry)
             .finish()
     }
 }
@@ -46,7 +82,7 @@ impl BinanceClient {
     /// Default configuration:
     /// - Base URL: https://api.binance.com
     /// - Timeout: 10 seconds
-    /// - User-Agent: mcp-binance-server/0.1.0
+    /// - User-Agent: forgetrade-mcp/<version>, no extra headers
     /// - No API credentials (public endpoints only)
     pub fn new() -> Self {
         Self::with_timeout(Duration::from_secs(10))
@@ -58,21 +94,36 @@ impl BinanceClient {
     /// - `BINANCE_API_KEY` - API key for authenticated requests
     /// - `BINANCE_API_SECRET` - API secret for signing requests
     ///
+    /// The retry policy is read from `BINANCE_RETRY_*` (see [`RetryPolicy`]) and
+    /// the User-Agent and extra headers from `BINANCE_USER_AGENT` /
+    /// `BINANCE_EXTRA_HEADERS` (see [`RequestHeaders`]); invalid values are
+    /// logged and the defaults are used.
+    ///
     /// # Returns
     /// Client with credentials if both env vars are set, otherwise no credentials
     pub fn with_credentials() -> Self {
-        let api_key = std::env::var("BINANCE_API_KEY").ok();
-        let api_secret = std::env::var("BINANCE_API_SECRET").ok();
+        let read = |var| {
+            std::env::var(var)
+                .ok()
+                .map(|v: String| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let api_key = read("BINANCE_API_KEY");
+        let api_secret = read("BINANCE_API_SECRET");
+        let retry = RetryPolicy::from_env().unwrap_or_else(|e| {
+            tracing::warn!("{}; using the default retry policy", e);
+            RetryPolicy::default()
+        });
+        let headers = RequestHeaders::from_env().unwrap_or_else(|e| {
+            tracing::warn!("{}; using the default request headers", e);
+            RequestHeaders::default()
+        });
 
         Self {
-            client: Client::builder()
-                .timeout(Duration::from_secs(10))
-                .user_agent("mcp-binance-server/0.1.0")
-                .build()
-                .expect("Failed to create HTTP client"),
-            base_url: "https://api.binance.com".to_string(),
             api_key,
             api_secret,
+            retry,
+            ..Self::with_timeout_and_headers(Duration::from_secs(10), &headers)
         }
     }
 
@@ -89,25 +140,219 @@ impl BinanceClient {
     /// let client = BinanceClient::with_timeout(Duration::from_secs(5));
     /// ```
     pub fn with_timeout(timeout: Duration) -> Self {
+        Self::with_timeout_and_headers(timeout, &RequestHeaders::default())
+    }
+
+    /// Creates a new Binance client with custom timeout and request headers
+    ///
+    /// `headers` sets the User-Agent and extra headers sent with every request.
+    pub fn with_timeout_and_headers(timeout: Duration, headers: &RequestHeaders) -> Self {
         let client = Client::builder()
             .timeout(timeout)
-            .user_agent("mcp-binance-server/0.1.0")
+            .user_agent(headers.user_agent.as_str())
+            .default_headers(headers.extra_header_map())
             .build()
             .expect("Failed to create HTTP client");
 
         Self {
             client,
             base_url: "https://api.binance.com".to_string(),
+            futures_base_url: Market::UsdmFutures.rest_base_url().to_string(),
             api_key: None,
             api_secret: None,
+            circuit: Arc::new(CircuitBreaker::default()),
+            clock_offset: Arc::new(ClockOffset::default()),
+            retry: RetryPolicy::default(),
+            all_tickers: Arc::new(Mutex::new(None)),
         }
     }
 
+    /// Replace the retry policy for public GET requests
+    pub fn with_retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
     /// Returns the configured base URL
     pub fn base_url(&self) -> &str {
         &self.base_url
     }
 
+    /// REST base URL for `market` (the configured spot or futures base URL)
+    pub fn market_base_url(&self, market: Market) -> &str {
+        match market {
+            Market::Spot => &self.base_url,
+            Market::UsdmFutures => &self.futures_base_url,
+        }
+    }
+
+    /// Whether both an API key and secret are configured (signed endpoints usable)
+    pub fn has_credentials(&self) -> bool {
+        self.api_key.is_some() && self.api_secret.is_some()
+    }
+
+    /// Current upstream circuit breaker state
+    pub fn circuit_status(&self) -> CircuitStatus {
+        self.circuit.status()
+    }
+
+    /// Latest measured offset of Binance server time from local time in ms
+    /// (None until [`check_clock_drift`](Self::check_clock_drift) succeeded)
+    pub fn clock_offset_ms(&self) -> Option<i64> {
+        self.clock_offset.get()
+    }
+
+    /// Measures the local clock offset against `GET /api/v3/time` and stores it
+    ///
+    /// Logs a warning when the clock is more than 1000ms off, since signed
+    /// requests would otherwise risk rejection outside `recvWindow`.
+    ///
+    /// # Returns
+    /// Offset in milliseconds (positive = local clock behind Binance)
+    ///
+    /// # Errors
+    /// Same as [`get_server_time`](Self::get_server_time)
+    pub async fn check_clock_drift(&self) -> Result<i64, McpError> {
+        let sent_at = chrono::Utc::now().timestamp_millis();
+        let server_time = self.get_server_time().await?;
+        let received_at = chrono::Utc::now().timestamp_millis();
+
+        let offset = clock_offset_ms(server_time, sent_at, received_at);
+        self.clock_offset.record(offset);
+
+        if offset.abs() > MAX_CLOCK_SKEW_MS {
+            tracing::warn!(
+                offset_ms = offset,
+                "Local clock is {}ms {} Binance server time; signed requests are corrected by the offset",
+                offset.abs(),
+                if offset > 0 { "behind" } else { "ahead of" }
+            );
+        } else {
+            tracing::debug!(offset_ms = offset, "Clock drift within tolerance");
+        }
+        Ok(offset)
+    }
+
+    /// Send a public GET request through the circuit breaker, retrying transient
+    /// failures according to the [`RetryPolicy`]
+    ///
+    /// Connection errors, timeouts, 5xx and 429 are retried; any other response
+    /// (including 4xx) is returned as-is, as is the last response once attempts
+    /// run out. Retries also stop once the circuit has opened, since the next
+    /// attempt would only fail fast.
+    ///
+    /// Only the final outcome is recorded with the breaker, so a 429 whose
+    /// `Retry-After` fits the retry budget is waited out rather than opening
+    /// the circuit, and a half-open probe keeps its slot across retries.
+    ///
+    /// # Errors
+    /// Fails fast with `RateLimitError` / `ConnectionError` while the circuit is open
+    async fn send_get(&self, url: &str) -> Result<reqwest::Response, McpError> {
+        self.circuit.check()?;
+        let mut attempt = 1;
+        loop {
+            let delay = match self.client.get(url).send().await {
+                Ok(response) => {
+                    let status = response.status();
+                    let delay =
+                        self.retry
+                            .delay_for_status(status, retry::retry_after(&response), attempt);
+                    match delay {
+                        Some(delay) if self.can_retry(attempt) => {
+                            tracing::warn!(
+                                "Binance returned {} (attempt {} of {}), retrying in {:?}",
+                                status,
+                                attempt,
+                                self.retry.max_attempts,
+                                delay
+                            );
+                            delay
+                        }
+                        _ => {
+                            self.circuit.record_response(&response);
+                            return Ok(response);
+                        }
+                    }
+                }
+                Err(err) => {
+                    if !(retry::is_retryable_transport_error(&err) && self.can_retry(attempt)) {
+                        self.circuit
+                            .record_failure(UpstreamFailure::Unavailable, None);
+                        return Err(McpError::from(err));
+                    }
+                    let delay = self.retry.backoff_delay(attempt);
+                    tracing::warn!(
+                        "Binance request failed (attempt {} of {}): {}. Retrying in {:?}",
+                        attempt,
+                        self.retry.max_attempts,
+                        err,
+                        delay
+                    );
+                    delay
+                }
+            };
+
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// Whether a failed `attempt` may be followed by another one
+    fn can_retry(&self, attempt: u32) -> bool {
+        attempt < self.retry.max_attempts && self.circuit.status().state != CircuitState::Open
+    }
+
+    /// Send a signed GET request for `path` with `params` (without timestamp/signature)
+    ///
+    /// Appends `recvWindow`, the clock-corrected `timestamp` and the HMAC-SHA256
+    /// `signature`, and passes the API key in the `X-MBX-APIKEY` header.
+    ///
+    /// # Errors
+    /// Returns `AuthRequired` if the API key or secret is not configured
+    async fn send_signed_get(
+        &self,
+        path: &str,
+        params: &str,
+    ) -> Result<reqwest::Response, McpError> {
+        let api_key = self.api_key.as_ref().ok_or_else(|| {
+            McpError::AuthRequired("BINANCE_API_KEY is not configured".to_string())
+        })?;
+
+        let mut query = params.to_string();
+        if !query.is_empty() {
+            query.push('&');
+        }
+        query.push_str(&format!(
+            "recvWindow={}&timestamp={}",
+            RECV_WINDOW_MS,
+            self.get_timestamp()?
+        ));
+        let signature = self.sign_request(&query)?;
+
+        let url = format!(
+            "{}{}?{}&signature={}",
+            self.base_url, path, query, signature
+        );
+        self.send(self.client.get(url).header(API_KEY_HEADER, api_key))
+            .await
+    }
+
+    /// Send a request through the circuit breaker and record its outcome
+    async fn send(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response, McpError> {
+        self.circuit.check()?;
+        match request.send().await {
+            Ok(response) => {
+                self.circuit.record_response(&response);
+                Ok(response)
+            }
+            Err(err) => {
+                self.circuit
+                    .record_failure(UpstreamFailure::Unavailable, None);
+                Err(McpError::from(err))
+            }
+        }
+    }
+
     /// Generates HMAC-SHA256 signature for request parameters
     ///
     /// # Arguments
@@ -117,12 +362,11 @@ impl BinanceClient {
     /// Hexadecimal signature string
     ///
     /// # Errors
-    /// Returns error if API secret is not configured
+    /// Returns `AuthRequired` if API secret is not configured
     fn sign_request(&self, query_string: &str) -> Result<String, McpError> {
-        let secret = self
-            .api_secret
-            .as_ref()
-            .ok_or_else(|| McpError::InvalidRequest("API secret not configured".to_string()))?;
+        let secret = self.api_secret.as_ref().ok_or_else(|| {
+            McpError::AuthRequired("BINANCE_API_SECRET is not configured".to_string())
+        })?;
 
         let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
             .map_err(|e| McpError::ParseError(format!("Invalid secret key: {}", e)))?;
@@ -134,20 +378,22 @@ impl BinanceClient {
         Ok(signature)
     }
 
-    /// Gets current timestamp in milliseconds
+    /// Gets current timestamp in milliseconds for signed requests
     ///
-    /// Uses system time as milliseconds since Unix epoch
-    fn get_timestamp() -> Result<u64, McpError> {
-        SystemTime::now()
+    /// Uses system time as milliseconds since Unix epoch, shifted by the last
+    /// measured offset to Binance server time
+    fn get_timestamp(&self) -> Result<u64, McpError> {
+        let local = SystemTime::now()
             .duration_since(UNIX_EPOCH)
-            .map(|d| d.as_millis() as u64)
-            .map_err(|e| McpError::ParseError(format!("System time error: {}", e)))
+            .map(|d| d.as_millis() as i64)
+            .map_err(|e| McpError::ParseError(format!("System time error: {}", e)))?;
+        Ok((local + self.clock_offset.get().unwrap_or(0)) as u64)
     }
 
     /// Fetches current Binance server time
     ///
     /// Calls GET /api/v3/time endpoint and returns the server timestamp in milliseconds.
-    /// Implements exponential backoff for rate limit (429) responses with up to 3 retries.
+    /// Rate limit (429) and transient failures are retried per the [`RetryPolicy`].
     ///
     /// # Returns
     /// * `Ok(i64)` - Server time in milliseconds since Unix epoch
@@ -155,7 +401,7 @@ impl BinanceClient {
     ///
     /// # Errors
     /// * `ConnectionError` - Network failures, timeouts, 5xx server errors
-    /// * `RateLimitError` - HTTP 429 after max retries (3 attempts)
+    /// * `RateLimitError` - HTTP 429 once retries are exhausted, or circuit open
     /// * `ParseError` - Invalid JSON response or unexpected format
     ///
     /// # Example
@@ -171,69 +417,21 @@ impl BinanceClient {
     /// ```
     pub async fn get_server_time(&self) -> Result<i64, McpError> {
         let url = format!("{}/api/v3/time", self.base_url);
-        let max_retries = 3;
-        let mut retry_count = 0;
-
-        loop {
-            let response = self.client.get(&url).send().await;
-
-            match response {
-                Ok(resp) => {
-                    let status = resp.status();
-
-                    // Handle 429 rate limit with exponential backoff
-                    if status.as_u16() == 429 {
-                        if retry_count >= max_retries {
-                            return Err(McpError::RateLimitError(format!(
-                                "Rate limit exceeded after {} retries. Wait 60 seconds before retrying.",
-                                max_retries
-                            )));
-                        }
-
-                        // Parse Retry-After header if present, otherwise use exponential backoff
-                        let retry_after = resp
-                            .headers()
-                            .get("retry-after")
-                            .and_then(|h| h.to_str().ok())
-                            .and_then(|s| s.parse::<u64>().ok())
-                            .unwrap_or_else(|| 2_u64.pow(retry_count)); // 1s, 2s, 4s
-
-                        tracing::warn!(
-                            "Rate limit hit (429). Retry {} of {}. Waiting {}s before retry.",
-                            retry_count + 1,
-                            max_retries,
-                            retry_after
-                        );
-
-                        tokio::time::sleep(Duration::from_secs(retry_after)).await;
-                        retry_count += 1;
-                        continue;
-                    }
-
-                    // Check for other HTTP errors
-                    if !status.is_success() {
-                        return Err(McpError::from(resp.error_for_status().unwrap_err()));
-                    }
-
-                    // Parse successful response
-                    let server_time_response: ServerTimeResponse = resp.json().await?;
+        let response = self.send_get(&url).await?;
 
-                    // Validate response
-                    if !server_time_response.is_valid() {
-                        return Err(McpError::ParseError(format!(
-                            "Invalid server time received: {}",
-                            server_time_response.server_time
-                        )));
-                    }
+        if !response.status().is_success() {
+            return Err(McpError::from_response(response).await);
+        }
 
-                    return Ok(server_time_response.time_ms());
-                }
-                Err(err) => {
-                    // Network errors are not retryable in this simple implementation
-                    return Err(McpError::from(err));
-                }
-            }
+        let server_time_response: ServerTimeResponse = response.json().await?;
+        if !server_time_response.is_valid() {
+            return Err(McpError::ParseError(format!(
+                "Invalid server time received: {}",
+                server_time_response.server_time
+            )));
         }
+
+        Ok(server_time_response.time_ms())
     }
 
     /// Get latest price for a symbol
@@ -248,16 +446,42 @@ impl BinanceClient {
     /// * `Err(McpError)` - Network error or API error
     pub async fn get_ticker_price(&self, symbol: &str) -> Result<TickerPrice, McpError> {
         let url = format!("{}/api/v3/ticker/price?symbol={}", self.base_url, symbol);
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_get(&url).await?;
 
         if !response.status().is_success() {
-            return Err(McpError::from(response.error_for_status().unwrap_err()));
+            return Err(McpError::from_response(response).await);
         }
 
         let ticker: TickerPrice = response.json().await?;
         Ok(ticker)
     }
 
+    /// Get best bid/ask price and quantity
+    ///
+    /// Calls GET /api/v3/ticker/bookTicker (weight 2), a lighter alternative to
+    /// subscribing to the full depth stream when only top-of-book is needed.
+    ///
+    /// # Arguments
+    /// * `symbol` - Trading pair symbol (e.g., "BTCUSDT")
+    ///
+    /// # Returns
+    /// * `Ok(BookTicker)` - Best bid/ask
+    /// * `Err(McpError)` - Network error or API error
+    pub async fn get_book_ticker(&self, symbol: &str) -> Result<BookTicker, McpError> {
+        let url = format!(
+            "{}/api/v3/ticker/bookTicker?symbol={}",
+            self.base_url, symbol
+        );
+        let response = self.send_get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(McpError::from_response(response).await);
+        }
+
+        let ticker: BookTicker = response.json().await?;
+        Ok(ticker)
+    }
+
     /// Get 24-hour ticker price statistics
     ///
     /// Calls GET /api/v3/ticker/24hr
@@ -270,16 +494,76 @@ impl BinanceClient {
     /// * `Err(McpError)` - Network error or API error
     pub async fn get_24hr_ticker(&self, symbol: &str) -> Result<Ticker24hr, McpError> {
         let url = format!("{}/api/v3/ticker/24hr?symbol={}", self.base_url, symbol);
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_get(&url).await?;
 
         if !response.status().is_success() {
-            return Err(McpError::from(response.error_for_status().unwrap_err()));
+            return Err(McpError::from_response(response).await);
         }
 
         let ticker: Ticker24hr = response.json().await?;
         Ok(ticker)
     }
 
+    /// Get 24-hour ticker statistics for every listed symbol
+    ///
+    /// Calls GET /api/v3/ticker/24hr without a symbol (weight 80). The response
+    /// is reused for [`ALL_TICKERS_CACHE_TTL`] so repeated scans don't spend the
+    /// request weight again; failures are never cached.
+    ///
+    /// # Returns
+    /// * `Ok(tickers)` - One entry per symbol, including halted ones
+    /// * `Err(McpError)` - Network error or API error
+    pub async fn get_all_24hr_tickers(&self) -> Result<Arc<Vec<Ticker24hr>>, McpError> {
+        let cached = self
+            .all_tickers
+            .lock()
+            .unwrap()
+            .as_ref()
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < ALL_TICKERS_CACHE_TTL)
+            .map(|(_, tickers)| Arc::clone(tickers));
+        if let Some(tickers) = cached {
+            return Ok(tickers);
+        }
+
+        let url = format!("{}/api/v3/ticker/24hr", self.base_url);
+        let response = self.send_get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(McpError::from_response(response).await);
+        }
+
+        let tickers: Arc<Vec<Ticker24hr>> = Arc::new(response.json().await?);
+        *self.all_tickers.lock().unwrap() = Some((Instant::now(), Arc::clone(&tickers)));
+        Ok(tickers)
+    }
+
+    /// Get exchange trading rules and symbol information
+    ///
+    /// Calls GET /api/v3/exchangeInfo (weight 20, or 2 for a single symbol)
+    ///
+    /// # Arguments
+    /// * `symbol` - Trading pair symbol (None = every listed symbol, a large response)
+    ///
+    /// # Returns
+    /// * `Ok(ExchangeInfo)` - Symbol rules including price/lot filters
+    /// * `Err(McpError)` - Network error or API error
+    pub async fn get_exchange_info(&self, symbol: Option<&str>) -> Result<ExchangeInfo, McpError> {
+        let mut url = format!("{}/api/v3/exchangeInfo", self.base_url);
+
+        if let Some(symbol) = symbol {
+            url.push_str(&format!("?symbol={}", symbol));
+        }
+
+        let response = self.send_get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(McpError::from_response(response).await);
+        }
+
+        let info: ExchangeInfo = response.json().await?;
+        Ok(info)
+    }
+
     /// Get candlestick/kline data
     ///
     /// Calls GET /api/v3/klines
@@ -290,8 +574,9 @@ impl BinanceClient {
     /// * `limit` - Number of klines to return (default 500, max 1000)
     ///
     /// # Returns
-    /// * `Ok(KlineData)` - Array of kline data
-    /// * `Err(McpError)` - Network error or API error
+    /// * `Ok(KlineData)` - Array of kline data; empty when Binance has no candles
+    ///   for the symbol yet (e.g. newly listed) or answers with an empty body
+    /// * `Err(McpError)` - Network error or API error (`RegionBlocked` on HTTP 451)
     pub async fn get_klines(
         &self,
         symbol: &str,
@@ -307,42 +592,65 @@ impl BinanceClient {
             url.push_str(&format!("&limit={}", lim));
         }
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_get(&url).await?;
 
         if !response.status().is_success() {
-            return Err(McpError::from(response.error_for_status().unwrap_err()));
+            return Err(McpError::from_response(response).await);
         }
 
-        let klines: KlineData = response.json().await?;
+        let body = response.text().await?;
+        if body.trim().is_empty() {
+            return Ok(KlineData::new());
+        }
+        let klines: KlineData = serde_json::from_str(&body)?;
         Ok(klines)
     }
 
-    /// Get order book depth
+    /// Get spot order book depth
     ///
-    /// Calls GET /api/v3/depth
+    /// Calls GET /api/v3/depth; see [`get_market_order_book`](Self::get_market_order_book)
+    pub async fn get_order_book(
+        &self,
+        symbol: &str,
+        limit: Option<u32>,
+    ) -> Result<OrderBook, McpError> {
+        self.get_market_order_book(Market::Spot, symbol, limit)
+            .await
+    }
+
+    /// Get order book depth on `market`
+    ///
+    /// Calls GET /api/v3/depth (spot) or /fapi/v1/depth (USDⓈ-M futures)
     ///
     /// # Arguments
+    /// * `market` - Spot or USDⓈ-M futures
     /// * `symbol` - Trading pair symbol (e.g., "BTCUSDT")
     /// * `limit` - Number of levels to return (default 100, valid: 5, 10, 20, 50, 100, 500, 1000, 5000)
     ///
     /// # Returns
     /// * `Ok(OrderBook)` - Order book with bids and asks
     /// * `Err(McpError)` - Network error or API error
-    pub async fn get_order_book(
+    pub async fn get_market_order_book(
         &self,
+        market: Market,
         symbol: &str,
         limit: Option<u32>,
     ) -> Result<OrderBook, McpError> {
-        let mut url = format!("{}/api/v3/depth?symbol={}", self.base_url, symbol);
+        let mut url = format!(
+            "{}{}?symbol={}",
+            self.market_base_url(market),
+            market.depth_path(),
+            symbol
+        );
 
         if let Some(lim) = limit {
             url.push_str(&format!("&limit={}", lim));
         }
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_get(&url).await?;
 
         if !response.status().is_success() {
-            return Err(McpError::from(response.error_for_status().unwrap_err()));
+            return Err(McpError::from_response(response).await);
         }
 
         let order_book: OrderBook = response.json().await?;
@@ -371,15 +679,203 @@ impl BinanceClient {
             url.push_str(&format!("&limit={}", lim));
         }
 
-        let response = self.client.get(&url).send().await?;
+        let response = self.send_get(&url).await?;
 
         if !response.status().is_success() {
-            return Err(McpError::from(response.error_for_status().unwrap_err()));
+            return Err(McpError::from_response(response).await);
         }
 
         let trades: Vec<Trade> = response.json().await?;
         Ok(trades)
     }
+
+    /// Get spot compressed/aggregate trades
+    ///
+    /// Calls GET /api/v3/aggTrades; see [`get_market_agg_trades`](Self::get_market_agg_trades)
+    pub async fn get_agg_trades(
+        &self,
+        symbol: &str,
+        limit: Option<u32>,
+        from_id: Option<i64>,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+    ) -> Result<Vec<AggTradeResponse>, McpError> {
+        self.get_market_agg_trades(Market::Spot, symbol, limit, from_id, start_time, end_time)
+            .await
+    }
+
+    /// Get compressed/aggregate trades on `market`
+    ///
+    /// Calls GET /api/v3/aggTrades (spot) or /fapi/v1/aggTrades (USDⓈ-M futures)
+    ///
+    /// # Arguments
+    /// * `market` - Spot or USDⓈ-M futures
+    /// * `symbol` - Trading pair symbol (e.g., "BTCUSDT")
+    /// * `limit` - Number of trades to return (default 500, max 1000)
+    /// * `from_id` - Aggregate trade ID to start from (inclusive)
+    /// * `start_time` - Start of time range (milliseconds since epoch, inclusive)
+    /// * `end_time` - End of time range (milliseconds since epoch, inclusive)
+    ///
+    /// `from_id` cannot be combined with a time range; Binance also caps
+    /// `end_time - start_time` at one hour.
+    ///
+    /// # Returns
+    /// * `Ok(Vec<AggTradeResponse>)` - Aggregate trades, oldest first
+    /// * `Err(McpError)` - Network error or API error
+    pub async fn get_market_agg_trades(
+        &self,
+        market: Market,
+        symbol: &str,
+        limit: Option<u32>,
+        from_id: Option<i64>,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+    ) -> Result<Vec<AggTradeResponse>, McpError> {
+        let mut url = format!(
+            "{}{}?symbol={}",
+            self.market_base_url(market),
+            market.agg_trades_path(),
+            symbol
+        );
+
+        if let Some(lim) = limit {
+            url.push_str(&format!("&limit={}", lim));
+        }
+        if let Some(id) = from_id {
+            url.push_str(&format!("&fromId={}", id));
+        }
+        if let Some(start) = start_time {
+            url.push_str(&format!("&startTime={}", start));
+        }
+        if let Some(end) = end_time {
+            url.push_str(&format!("&endTime={}", end));
+        }
+
+        let response = self.send_get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(McpError::from_response(response).await);
+        }
+
+        let trades: Vec<AggTradeResponse> = response.json().await?;
+        Ok(trades)
+    }
+
+    /// Get spot account information and balances (signed)
+    ///
+    /// Calls GET /api/v3/account with `omitZeroBalances` (request weight 20)
+    ///
+    /// # Arguments
+    /// * `omit_zero_balances` - Leave out assets with zero free and locked quantity
+    ///
+    /// # Returns
+    /// * `Ok(AccountInfo)` - Account permissions and balances
+    /// * `Err(McpError)` - `AuthRequired` when credentials are missing or rejected,
+    ///   otherwise network or API error
+    pub async fn get_account(&self, omit_zero_balances: bool) -> Result<AccountInfo, McpError> {
+        let params = format!("omitZeroBalances={}", omit_zero_balances);
+        let response = self.send_signed_get("/api/v3/account", &params).await?;
+
+        if !response.status().is_success() {
+            return Err(McpError::from_response(response).await);
+        }
+
+        let account: AccountInfo = response.json().await?;
+        Ok(account)
+    }
+
+    /// Get current open interest of a USDⓈ-M perpetual
+    ///
+    /// Calls GET /fapi/v1/openInterest on the futures base URL
+    ///
+    /// # Arguments
+    /// * `symbol` - Futures symbol (e.g., "BTCUSDT")
+    ///
+    /// # Returns
+    /// * `Ok(OpenInterest)` - Open contracts in base asset units
+    /// * `Err(McpError)` - `SymbolNotFound` when the symbol has no futures
+    ///   contract (spot-only), otherwise network or API error
+    pub async fn get_open_interest(&self, symbol: &str) -> Result<OpenInterest, McpError> {
+        let url = format!(
+            "{}/fapi/v1/openInterest?symbol={}",
+            self.futures_base_url, symbol
+        );
+        let response = self.send_get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(McpError::from_response(response).await);
+        }
+
+        let open_interest: OpenInterest = response.json().await?;
+        Ok(open_interest)
+    }
+
+    /// Get open interest history of a USDⓈ-M perpetual
+    ///
+    /// Calls GET /futures/data/openInterestHist (last 30 days only)
+    ///
+    /// # Arguments
+    /// * `symbol` - Futures symbol (e.g., "BTCUSDT")
+    /// * `period` - Bucket size ("5m", "15m", "30m", "1h", "2h", "4h", "6h", "12h", "1d")
+    /// * `limit` - Number of buckets (default 30, max 500)
+    ///
+    /// # Returns
+    /// * `Ok(Vec<OpenInterestHist>)` - Buckets, oldest first
+    /// * `Err(McpError)` - Network error or API error
+    pub async fn get_open_interest_hist(
+        &self,
+        symbol: &str,
+        period: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<OpenInterestHist>, McpError> {
+        let mut url = format!(
+            "{}/futures/data/openInterestHist?symbol={}&period={}",
+            self.futures_base_url, symbol, period
+        );
+        if let Some(lim) = limit {
+            url.push_str(&format!("&limit={}", lim));
+        }
+
+        let response = self.send_get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(McpError::from_response(response).await);
+        }
+
+        let history: Vec<OpenInterestHist> = response.json().await?;
+        Ok(history)
+    }
+
+    /// Get the global long/short account ratio of a USDⓈ-M perpetual
+    ///
+    /// Calls GET /futures/data/globalLongShortAccountRatio for the two most
+    /// recent buckets, so callers can compare the latest ratio to the previous one
+    ///
+    /// # Arguments
+    /// * `symbol` - Futures symbol (e.g., "BTCUSDT")
+    /// * `period` - Bucket size ("5m", "15m", "30m", "1h", "2h", "4h", "6h", "12h", "1d")
+    ///
+    /// # Returns
+    /// * `Ok(Vec<LongShortRatio>)` - Ratios per bucket, oldest first
+    /// * `Err(McpError)` - Network error or API error
+    pub async fn get_long_short_ratio(
+        &self,
+        symbol: &str,
+        period: &str,
+    ) -> Result<Vec<LongShortRatio>, McpError> {
+        let url = format!(
+            "{}/futures/data/globalLongShortAccountRatio?symbol={}&period={}&limit=2",
+            self.futures_base_url, symbol, period
+        );
+        let response = self.send_get(&url).await?;
+
+        if !response.status().is_success() {
+            return Err(McpError::from_response(response).await);
+        }
+
+        let ratios: Vec<LongShortRatio> = response.json().await?;
+        Ok(ratios)
+    }
 }
 
 impl Default for BinanceClient {
@@ -387,3 +883,249 @@ impl Default for BinanceClient {
         Self::new()
     }
 }
+
+/// Local stand-in for the Binance REST API, shared by tests across the crate
+#[cfg(test)]
+pub(crate) mod test_server {
+    use super::BinanceClient;
+    use std::sync::Arc;
+
+    /// Raw HTTP/1.1 response with a JSON `body`
+    pub(crate) fn http_response(status: &str, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    }
+
+    /// Client pointed at a local server that answers successive requests with
+    /// `responses` (None drops the connection without answering), and the number
+    /// of requests the server has received
+    pub(crate) async fn client_with_responses(
+        responses: Vec<Option<String>>,
+    ) -> (BinanceClient, Arc<std::sync::atomic::AtomicUsize>) {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let mut client = BinanceClient::new();
+        client.base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicUsize::new(0));
+
+        let received = Arc::clone(&requests);
+        tokio::spawn(async move {
+            for response in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut request = [0u8; 4096];
+                let _ = socket.read(&mut request).await;
+                received.fetch_add(1, Ordering::SeqCst);
+                if let Some(response) = response {
+                    socket.write_all(response.as_bytes()).await.unwrap();
+                }
+            }
+        });
+        (client, requests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_server::{client_with_responses, http_response};
+    use super::*;
+    use crate::binance::headers::DEFAULT_USER_AGENT;
+
+    #[test]
+    fn test_sign_request_matches_binance_example() {
+        // HMAC-SHA256 example from the Binance spot API documentation
+        let mut client = BinanceClient::new();
+        client.api_secret =
+            Some("NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j".to_string());
+
+        let query = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1\
+                     &recvWindow=5000&timestamp=1499827319559";
+        assert_eq!(
+            client.sign_request(query).unwrap(),
+            "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_account_requires_credentials() {
+        let client = BinanceClient::new();
+        assert!(!client.has_credentials());
+
+        let err = client.get_account(true).await.unwrap_err();
+        assert_eq!(err.error_code(), "AUTH_REQUIRED");
+    }
+
+    /// Client pointed at a local server that answers one request with `status` and `body`
+    async fn client_with_canned_response(status: &str, body: &str) -> BinanceClient {
+        client_with_responses(vec![Some(http_response(status, body))])
+            .await
+            .0
+    }
+
+    fn fast_retries() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(1),
+            max_delay: Duration::from_millis(10),
+            jitter: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_client_sends_user_agent_and_extra_headers() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let headers = RequestHeaders::from_values(None, Some("X-Deployment: eu-1")).unwrap();
+        let mut client = BinanceClient::with_timeout_and_headers(Duration::from_secs(5), &headers);
+        client.base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 4096];
+            let len = socket.read(&mut request).await.unwrap();
+            socket
+                .write_all(http_response("200 OK", r#"{"serverTime":1499827319559}"#).as_bytes())
+                .await
+                .unwrap();
+            String::from_utf8_lossy(&request[..len]).to_lowercase()
+        });
+
+        client.get_server_time().await.unwrap();
+        let request = server.await.unwrap();
+        assert!(
+            request.contains(&format!("user-agent: {}", DEFAULT_USER_AGENT).to_lowercase()),
+            "{}",
+            request
+        );
+        assert!(request.contains("x-deployment: eu-1"), "{}", request);
+        // Public endpoints never carry the API key
+        assert!(!request.contains("x-mbx-apikey"), "{}", request);
+    }
+
+    #[tokio::test]
+    async fn test_transient_failures_are_retried() {
+        let (client, requests) = client_with_responses(vec![
+            None,
+            Some(http_response("503 Service Unavailable", "")),
+            Some(http_response(
+                "200 OK",
+                r#"{"symbol":"BTCUSDT","price":"67650.00"}"#,
+            )),
+        ])
+        .await;
+        let client = client.with_retry_policy(fast_retries());
+
+        let ticker = client.get_ticker_price("BTCUSDT").await.unwrap();
+        assert_eq!(ticker.price, "67650.00");
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_waits_for_retry_after() {
+        let (client, requests) = client_with_responses(vec![
+            Some(
+                "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 1\r\nContent-Length: 0\r\nConnection: close\r\n\r\n"
+                    .to_string(),
+            ),
+            Some(http_response(
+                "200 OK",
+                r#"{"symbol":"BTCUSDT","price":"67650.00"}"#,
+            )),
+        ])
+        .await;
+        let client = client.with_retry_policy(RetryPolicy {
+            max_delay: Duration::from_secs(2),
+            ..fast_retries()
+        });
+
+        let started = std::time::Instant::now();
+        let ticker = client.get_ticker_price("BTCUSDT").await.unwrap();
+        assert_eq!(ticker.price, "67650.00");
+        assert!(started.elapsed() >= Duration::from_secs(1));
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 2);
+        // The answered retry leaves the breaker closed
+        assert_eq!(client.circuit_status().state, CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let (client, requests) = client_with_responses(vec![
+            Some(http_response(
+                "400 Bad Request",
+                r#"{"code":-1121,"msg":"Invalid symbol."}"#,
+            )),
+            Some(http_response(
+                "200 OK",
+                r#"{"symbol":"BTCUSDT","price":"67650.00"}"#,
+            )),
+        ])
+        .await;
+        let client = client.with_retry_policy(fast_retries());
+
+        assert!(client.get_ticker_price("NOPE").await.is_err());
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_all_tickers_response_is_cached() {
+        let body = r#"[{"symbol":"BTCUSDT","priceChange":"100.00","priceChangePercent":"0.15","weightedAvgPrice":"67600.00","prevClosePrice":"67550.00","lastPrice":"67650.00","lastQty":"0.01","bidPrice":"67649.99","askPrice":"67650.00","openPrice":"67550.00","highPrice":"68000.00","lowPrice":"67000.00","volume":"1000.0","quoteVolume":"67600000.0","openTime":0,"closeTime":86400000,"firstId":1,"lastId":2,"count":2}]"#;
+        let (client, requests) =
+            client_with_responses(vec![Some(http_response("200 OK", body))]).await;
+
+        let first = client.get_all_24hr_tickers().await.unwrap();
+        let second = client.clone().get_all_24hr_tickers().await.unwrap();
+        assert_eq!(first.len(), 1);
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(requests.load(std::sync::atomic::Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_klines_empty_array_is_no_data() {
+        let client = client_with_canned_response("200 OK", "[]").await;
+        let klines = client.get_klines("NEWUSDT", "1h", Some(10)).await.unwrap();
+        assert!(klines.is_empty());
+
+        let client = client_with_canned_response("200 OK", "").await;
+        let klines = client.get_klines("NEWUSDT", "1h", None).await.unwrap();
+        assert!(klines.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_klines_region_block_is_distinct() {
+        let body = r#"{"code":0,"msg":"Service unavailable from a restricted location."}"#;
+        let client = client_with_canned_response("451 Unavailable For Legal Reasons", body).await;
+
+        let err = client.get_klines("BTCUSDT", "1h", None).await.unwrap_err();
+        assert!(matches!(err, McpError::RegionBlocked(_)), "{:?}", err);
+        assert_eq!(err.error_code(), "REGION_BLOCKED");
+        assert!(!err.is_retryable());
+        assert!(matches!(
+            crate::error::ProviderError::from(err),
+            crate::error::ProviderError::RegionBlocked(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_symbol_maps_to_symbol_not_found() {
+        let body = r#"{"code":-1121,"msg":"Invalid symbol."}"#;
+        let client = client_with_canned_response("400 Bad Request", body).await;
+
+        let err = client.get_24hr_ticker("NOPEUSDT").await.unwrap_err();
+        assert!(matches!(err, McpError::SymbolNotFound(_)), "{:?}", err);
+        assert!(!err.is_retryable());
+
+        let err = crate::error::ProviderError::from(err);
+        assert!(
+            matches!(&err, crate::error::ProviderError::SymbolNotFound(msg) if msg == "Invalid symbol."),
+            "{:?}",
+            err
+        );
+        assert_eq!(err.error_code(), "SYMBOL_NOT_FOUND");
+    }
+}
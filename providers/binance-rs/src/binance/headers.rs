@@ -0,0 +1,151 @@
+//! Default HTTP headers for Binance REST requests
+//!
+//! Every request identifies this server with a descriptive `User-Agent`, which
+//! Binance support asks for when diagnosing rate limits or bans. Operators can
+//! override it and attach extra headers (e.g. a deployment tag). The API key is
+//! never part of these defaults: `X-MBX-APIKEY` is only added to requests for
+//! endpoints that require it.
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+/// User-Agent sent when `BINANCE_USER_AGENT` is unset
+pub const DEFAULT_USER_AGENT: &str = concat!("forgetrade-mcp/", env!("CARGO_PKG_VERSION"));
+
+/// Header carrying the API key on keyed and signed endpoints
+pub const API_KEY_HEADER: &str = "X-MBX-APIKEY";
+
+/// Headers sent with every Binance REST request
+///
+/// ## Environment Variables
+///
+/// - `BINANCE_USER_AGENT`: User-Agent header (default: `forgetrade-mcp/<version>`)
+/// - `BINANCE_EXTRA_HEADERS`: Additional headers as `Name: value` pairs
+///   separated by `;` (e.g. `X-Deployment: eu-1; X-Team: quant`). `User-Agent`
+///   and `X-MBX-APIKEY` cannot be set this way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestHeaders {
+    pub user_agent: String,
+    /// Extra `(name, value)` headers, already validated
+    pub extra: Vec<(String, String)>,
+}
+
+impl Default for RequestHeaders {
+    fn default() -> Self {
+        Self {
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            extra: Vec::new(),
+        }
+    }
+}
+
+impl RequestHeaders {
+    /// Load the headers from environment variables
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a variable is set but not valid
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let var = |name| std::env::var(name).ok();
+        Self::from_values(
+            var("BINANCE_USER_AGENT").as_deref(),
+            var("BINANCE_EXTRA_HEADERS").as_deref(),
+        )
+    }
+
+    /// Build the headers from raw variable values (`None` or blank = default)
+    pub fn from_values(
+        user_agent: Option<&str>,
+        extra_headers: Option<&str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut headers = Self::default();
+
+        if let Some(raw) = user_agent.map(str::trim).filter(|raw| !raw.is_empty()) {
+            HeaderValue::from_str(raw)
+                .map_err(|_| format!("Invalid BINANCE_USER_AGENT '{}'", raw))?;
+            headers.user_agent = raw.to_string();
+        }
+
+        for pair in extra_headers
+            .unwrap_or_default()
+            .split(';')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+        {
+            let (name, value) = pair.split_once(':').ok_or_else(|| {
+                format!(
+                    "BINANCE_EXTRA_HEADERS entries must be 'Name: value', got '{}'",
+                    pair
+                )
+            })?;
+            let (name, value) = (name.trim(), value.trim());
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| format!("Invalid header name '{}' in BINANCE_EXTRA_HEADERS", name))?;
+            HeaderValue::from_str(value).map_err(|_| {
+                format!(
+                    "Invalid value for header '{}' in BINANCE_EXTRA_HEADERS",
+                    name
+                )
+            })?;
+            if header_name == reqwest::header::USER_AGENT
+                || header_name.as_str().eq_ignore_ascii_case(API_KEY_HEADER)
+            {
+                return Err(format!(
+                    "BINANCE_EXTRA_HEADERS cannot set '{}'; use BINANCE_USER_AGENT or BINANCE_API_KEY",
+                    name
+                )
+                .into());
+            }
+            headers.extra.push((name.to_string(), value.to_string()));
+        }
+
+        Ok(headers)
+    }
+
+    /// Extra headers as a map for `reqwest::ClientBuilder::default_headers`
+    pub fn extra_header_map(&self) -> HeaderMap {
+        self.extra
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((
+                    HeaderName::from_bytes(name.as_bytes()).ok()?,
+                    HeaderValue::from_str(value).ok()?,
+                ))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_and_overrides() {
+        let headers = RequestHeaders::from_values(None, Some("  ")).unwrap();
+        assert_eq!(headers, RequestHeaders::default());
+        assert!(headers.user_agent.starts_with("forgetrade-mcp/"));
+
+        let headers = RequestHeaders::from_values(
+            Some("acme-desk/2.1"),
+            Some("X-Deployment: eu-1; X-Team:quant;"),
+        )
+        .unwrap();
+        assert_eq!(headers.user_agent, "acme-desk/2.1");
+        assert_eq!(
+            headers.extra,
+            vec![
+                ("X-Deployment".to_string(), "eu-1".to_string()),
+                ("X-Team".to_string(), "quant".to_string())
+            ]
+        );
+        assert_eq!(headers.extra_header_map().len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_extra_headers_rejected() {
+        assert!(RequestHeaders::from_values(None, Some("X-Deployment")).is_err());
+        assert!(RequestHeaders::from_values(None, Some("Bad Name: x")).is_err());
+        assert!(RequestHeaders::from_values(None, Some("user-agent: other")).is_err());
+        assert!(RequestHeaders::from_values(None, Some("x-mbx-apikey: leak")).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Binance market selection (spot vs USDⓈ-M futures)
+//!
+//! Spot and futures data live on different hosts with different REST paths;
+//! stream names (`<symbol>@depth@100ms`, `<symbol>@aggTrade`) are shared.
+//! Stream and REST consumers take a [`Market`] to pick the right endpoints.
+
+/// Binance market a stream or REST request targets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Market {
+    /// Spot (`stream.binance.com`, `/api/v3/...`)
+    #[default]
+    Spot,
+    /// USDⓈ-M perpetual futures (`fstream.binance.com`, `/fapi/v1/...`)
+    UsdmFutures,
+}
+
+impl Market {
+    /// WebSocket host for raw streams
+    pub fn ws_base_url(self) -> &'static str {
+        match self {
+            Self::Spot => "wss://stream.binance.com:9443",
+            Self::UsdmFutures => "wss://fstream.binance.com",
+        }
+    }
+
+    /// Raw stream URL for `stream_name` (e.g. "btcusdt@depth@100ms")
+    pub fn stream_url(self, stream_name: &str) -> String {
+        format!("{}/ws/{}", self.ws_base_url(), stream_name)
+    }
+
+    /// Default REST host (spot can be overridden with `BINANCE_BASE_URL`)
+    pub fn rest_base_url(self) -> &'static str {
+        match self {
+            Self::Spot => "https://api.binance.com",
+            Self::UsdmFutures => "https://fapi.binance.com",
+        }
+    }
+
+    /// REST path of the order book depth snapshot
+    pub fn depth_path(self) -> &'static str {
+        match self {
+            Self::Spot => "/api/v3/depth",
+            Self::UsdmFutures => "/fapi/v1/depth",
+        }
+    }
+
+    /// REST path of compressed/aggregate trades
+    pub fn agg_trades_path(self) -> &'static str {
+        match self {
+            Self::Spot => "/api/v3/aggTrades",
+            Self::UsdmFutures => "/fapi/v1/aggTrades",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_url_per_market() {
+        assert_eq!(
+            Market::Spot.stream_url("btcusdt@depth@100ms"),
+            "wss://stream.binance.com:9443/ws/btcusdt@depth@100ms"
+        );
+        assert_eq!(
+            Market::UsdmFutures.stream_url("btcusdt@depth@100ms"),
+            "wss://fstream.binance.com/ws/btcusdt@depth@100ms"
+        );
+        assert_eq!(Market::default(), Market::Spot);
+    }
+
+    #[test]
+    fn test_rest_endpoints_per_market() {
+        assert_eq!(Market::Spot.depth_path(), "/api/v3/depth");
+        assert_eq!(Market::UsdmFutures.depth_path(), "/fapi/v1/depth");
+        assert_eq!(
+            Market::UsdmFutures.rest_base_url(),
+            "https://fapi.binance.com"
+        );
+        assert_eq!(Market::UsdmFutures.agg_trades_path(), "/fapi/v1/aggTrades");
+    }
+}
@@ -2,7 +2,15 @@
 //!
 //! This module contains the HTTP client for Binance API integration.
 
+pub mod backoff;
+pub mod circuit;
 pub mod client;
+pub mod headers;
+pub mod market;
+pub mod parse_errors;
+pub mod retry;
+pub mod symbol;
+pub mod time_sync;
 pub mod types;
 
 #[cfg(feature = "websocket")]
@@ -10,10 +18,11 @@ pub mod websocket;
 
 // Re-export commonly used types
 pub use client::BinanceClient;
+pub use market::Market;
 pub use types::ServerTimeResponse;
 
 #[cfg(feature = "websocket")]
 pub use websocket::{
-    BalanceUpdate, BinanceWebSocketClient, DepthUpdate, ExecutionReport, OutboundAccountPosition,
-    TickerUpdate, UserDataEvent,
+    BalanceUpdate, BinanceWebSocketClient, DepthUpdate, ExecutionReport, KlineData, KlineUpdate,
+    MiniTickerUpdate, OutboundAccountPosition, TickerKind, TickerUpdate, UserDataEvent,
 };
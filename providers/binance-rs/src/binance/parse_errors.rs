@@ -0,0 +1,90 @@
+//! Sampled logging of WebSocket message parse failures
+//!
+//! When Binance changes a stream's schema every message fails to parse, and a
+//! `warn` per message floods the logs. Each stream task owns a
+//! [`ParseErrorSampler`] that warns for the first [`WARN_FIRST`] failures and
+//! then for one in every [`WARN_EVERY`]; the raw offending message is always
+//! logged at `debug`. Every failure also bumps a process-wide counter reported
+//! by [`check_health`](crate::health::check_health).
+
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Failures per stream that are always logged at `warn`
+pub const WARN_FIRST: u64 = 10;
+
+/// After [`WARN_FIRST`], log one in every this many failures at `warn`
+pub const WARN_EVERY: u64 = 1000;
+
+/// Parse failures across all WebSocket streams since startup
+static PARSE_ERRORS: AtomicU64 = AtomicU64::new(0);
+
+/// WebSocket messages that failed to parse since startup, across all streams
+pub fn parse_error_count() -> u64 {
+    PARSE_ERRORS.load(Ordering::Relaxed)
+}
+
+/// Per-stream parse failure counter deciding which failures reach `warn`
+#[derive(Debug, Default)]
+pub struct ParseErrorSampler {
+    failures: u64,
+}
+
+impl ParseErrorSampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Failures recorded by this sampler
+    pub fn failures(&self) -> u64 {
+        self.failures
+    }
+
+    /// Count a failure; returns whether it should be logged at `warn`
+    pub fn record(&mut self) -> bool {
+        PARSE_ERRORS.fetch_add(1, Ordering::Relaxed);
+        self.failures += 1;
+        self.failures <= WARN_FIRST || (self.failures - WARN_FIRST) % WARN_EVERY == 0
+    }
+
+    /// Count a failure to parse `raw` from `stream` and log it, sampled
+    pub fn log(&mut self, stream: &str, error: &dyn std::fmt::Display, raw: &str) {
+        if self.record() {
+            tracing::warn!(
+                stream = %stream,
+                error = %error,
+                failures = self.failures,
+                "Failed to parse WebSocket message (sampled: first {}, then 1 in {})",
+                WARN_FIRST,
+                WARN_EVERY
+            );
+        }
+        tracing::debug!(stream = %stream, raw = %raw, "Unparseable WebSocket message");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_malformed_flood_is_sampled() {
+        let before = parse_error_count();
+        let mut sampler = ParseErrorSampler::new();
+
+        let warned = (0..5000)
+            .map(|i| serde_json::from_str::<serde_json::Value>(&format!("{{bad {}", i)))
+            .filter(|parsed| parsed.is_err())
+            .filter(|_| sampler.record())
+            .count() as u64;
+
+        assert_eq!(sampler.failures(), 5000);
+        // First 10, then failures 1010, 2010, 3010 and 4010
+        assert_eq!(warned, WARN_FIRST + 4);
+        // Other tests may bump the shared counter concurrently
+        assert!(parse_error_count() >= before + 5000);
+
+        // A fresh stream warns again from the start
+        let mut other = ParseErrorSampler::new();
+        assert!(other.record());
+    }
+}
@@ -0,0 +1,255 @@
+//! Retry policy for idempotent REST requests
+//!
+//! A dropped connection or a 502 from Binance's edge usually succeeds on the
+//! next try, so public GET requests are retried with exponential backoff
+//! (optionally full-jittered, like [`JitteredBackoff`](super::backoff::JitteredBackoff)).
+//! Client errors (4xx) are never retried, except 429 which waits for
+//! `Retry-After` when Binance sends one.
+
+use rand::Rng;
+use reqwest::StatusCode;
+use std::time::Duration;
+
+/// Default attempts per request, including the first
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// Default delay before the first retry
+pub const DEFAULT_BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// Default upper bound for a single delay
+pub const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// Retry settings for public GET requests
+///
+/// ## Environment Variables
+///
+/// - `BINANCE_RETRY_MAX_ATTEMPTS`: Attempts per request including the first
+///   (default: 3, `1` disables retries)
+/// - `BINANCE_RETRY_BASE_DELAY_MS`: Delay before the first retry, doubling for
+///   each further retry (default: 200)
+/// - `BINANCE_RETRY_MAX_DELAY_MS`: Longest single delay; a `Retry-After` above
+///   it ends the retries (default: 5000)
+/// - `BINANCE_RETRY_JITTER`: Sleep a random duration up to the delay instead of
+///   the full delay (default: true)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts per request, including the first (at least 1)
+    pub max_attempts: u32,
+
+    /// Delay before the first retry
+    pub base_delay: Duration,
+
+    /// Upper bound for a single delay
+    pub max_delay: Duration,
+
+    /// Randomize each delay in `[1ms, delay]`
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            base_delay: DEFAULT_BASE_DELAY,
+            max_delay: DEFAULT_MAX_DELAY,
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy that sends every request exactly once
+    pub fn disabled() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Load the policy from environment variables
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a variable is set but not a valid value
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let var = |name| std::env::var(name).ok();
+        Self::from_values(
+            var("BINANCE_RETRY_MAX_ATTEMPTS").as_deref(),
+            var("BINANCE_RETRY_BASE_DELAY_MS").as_deref(),
+            var("BINANCE_RETRY_MAX_DELAY_MS").as_deref(),
+            var("BINANCE_RETRY_JITTER").as_deref(),
+        )
+    }
+
+    /// Build the policy from raw variable values (`None` or blank = default)
+    pub fn from_values(
+        max_attempts: Option<&str>,
+        base_delay_ms: Option<&str>,
+        max_delay_ms: Option<&str>,
+        jitter: Option<&str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let parse_u64 = |name: &str, raw: &str| {
+            raw.parse::<u64>()
+                .map_err(|e| format!("Invalid {} '{}': {}", name, raw, e))
+        };
+        let mut policy = Self::default();
+
+        if let Some(raw) = non_blank(max_attempts) {
+            policy.max_attempts = raw
+                .parse::<u32>()
+                .ok()
+                .filter(|attempts| *attempts >= 1)
+                .ok_or_else(|| {
+                    format!(
+                        "BINANCE_RETRY_MAX_ATTEMPTS must be a positive integer, got '{}'",
+                        raw
+                    )
+                })?;
+        }
+        if let Some(raw) = non_blank(base_delay_ms) {
+            policy.base_delay =
+                Duration::from_millis(parse_u64("BINANCE_RETRY_BASE_DELAY_MS", raw)?);
+        }
+        if let Some(raw) = non_blank(max_delay_ms) {
+            policy.max_delay = Duration::from_millis(parse_u64("BINANCE_RETRY_MAX_DELAY_MS", raw)?);
+        }
+        if let Some(raw) = non_blank(jitter) {
+            policy.jitter = match raw.to_ascii_lowercase().as_str() {
+                "true" | "1" | "yes" => true,
+                "false" | "0" | "no" => false,
+                _ => {
+                    return Err(format!(
+                        "BINANCE_RETRY_JITTER must be true or false, got '{}'",
+                        raw
+                    )
+                    .into())
+                }
+            };
+        }
+
+        Ok(policy)
+    }
+
+    /// Delay before retry number `retry` (1 = first retry)
+    ///
+    /// `base_delay * 2^(retry - 1)`, capped at `max_delay` and jittered when enabled.
+    pub fn backoff_delay(&self, retry: u32) -> Duration {
+        let ceiling = self
+            .base_delay
+            .saturating_mul(2_u32.saturating_pow(retry.saturating_sub(1)))
+            .min(self.max_delay);
+        if !self.jitter || ceiling.is_zero() {
+            return ceiling;
+        }
+        let ceiling_ms = (ceiling.as_millis() as u64).max(1);
+        Duration::from_millis(rand::thread_rng().gen_range(1..=ceiling_ms))
+    }
+
+    /// Delay before retrying a request answered with `status`, or None to give up
+    ///
+    /// Retries 5xx and 429; on 429 `retry_after` (the `Retry-After` header) is
+    /// waited instead of the backoff, unless it exceeds `max_delay`.
+    pub fn delay_for_status(
+        &self,
+        status: StatusCode,
+        retry_after: Option<Duration>,
+        retry: u32,
+    ) -> Option<Duration> {
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            match retry_after {
+                Some(wait) if wait > self.max_delay => None,
+                Some(wait) => Some(wait),
+                None => Some(self.backoff_delay(retry)),
+            }
+        } else if status.is_server_error() {
+            Some(self.backoff_delay(retry))
+        } else {
+            None
+        }
+    }
+}
+
+fn non_blank(raw: Option<&str>) -> Option<&str> {
+    raw.map(str::trim).filter(|raw| !raw.is_empty())
+}
+
+/// `Retry-After` header of `response` in whole seconds, if present
+pub fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map(Duration::from_secs)
+}
+
+/// Whether a request that failed without a response is worth retrying
+///
+/// Connection failures, timeouts and connections dropped mid-request are;
+/// errors building the request or decoding a body are not.
+pub fn is_retryable_transport_error(err: &reqwest::Error) -> bool {
+    err.is_connect() || err.is_timeout() || err.is_request()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retries_5xx_and_429_but_not_other_4xx() {
+        let policy = RetryPolicy {
+            jitter: false,
+            ..RetryPolicy::default()
+        };
+
+        assert_eq!(
+            policy.delay_for_status(StatusCode::BAD_GATEWAY, None, 1),
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(
+            policy.delay_for_status(StatusCode::SERVICE_UNAVAILABLE, None, 3),
+            Some(Duration::from_millis(800))
+        );
+        assert_eq!(
+            policy.delay_for_status(
+                StatusCode::TOO_MANY_REQUESTS,
+                Some(Duration::from_secs(2)),
+                1
+            ),
+            Some(Duration::from_secs(2))
+        );
+        // Binance asks for a longer pause than this policy is willing to wait
+        assert_eq!(
+            policy.delay_for_status(
+                StatusCode::TOO_MANY_REQUESTS,
+                Some(Duration::from_secs(60)),
+                1
+            ),
+            None
+        );
+        assert_eq!(
+            policy.delay_for_status(StatusCode::BAD_REQUEST, None, 1),
+            None
+        );
+        assert_eq!(
+            policy.delay_for_status(StatusCode::IM_A_TEAPOT, None, 1),
+            None
+        );
+    }
+
+    #[test]
+    fn test_from_values() {
+        let policy =
+            RetryPolicy::from_values(Some("5"), Some("50"), Some("1000"), Some("false")).unwrap();
+        assert_eq!(policy.max_attempts, 5);
+        assert_eq!(policy.backoff_delay(10), Duration::from_secs(1));
+        assert!(!policy.jitter);
+
+        assert_eq!(
+            RetryPolicy::from_values(None, Some(" "), None, None).unwrap(),
+            RetryPolicy::default()
+        );
+        assert!(RetryPolicy::from_values(Some("0"), None, None, None).is_err());
+        assert!(RetryPolicy::from_values(None, None, None, Some("maybe")).is_err());
+    }
+}
@@ -0,0 +1,270 @@
+//! Trading pair symbol validation
+//!
+//! Normalizes user-supplied symbols before they reach the Binance API so that
+//! lowercase or mistyped input yields a clear validation error instead of an
+//! opaque upstream `-1121 Invalid symbol` response.
+//!
+//! Symbols must match `^[A-Z0-9]{6,12}$` after normalization. When a set of
+//! listed symbols has been registered via [`set_known_symbols`] (every serving
+//! mode loads it from exchangeInfo through [`spawn_known_symbols_refresh`]),
+//! symbols are also checked for membership.
+
+use crate::binance::BinanceClient;
+use crate::error::{ProviderError, Result};
+use std::collections::HashSet;
+use std::sync::{OnceLock, RwLock};
+use std::time::Duration;
+
+/// Minimum symbol length (e.g. "ETHBTC")
+pub const MIN_SYMBOL_LEN: usize = 6;
+
+/// Maximum symbol length (e.g. "1000SATSUSDT")
+pub const MAX_SYMBOL_LEN: usize = 12;
+
+/// Maximum number of near-miss suggestions included in an error message
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Quote assets used to suggest completions for bare base assets (e.g. "BTC")
+const COMMON_QUOTE_ASSETS: [&str; 3] = ["USDT", "USDC", "BTC"];
+
+/// Interval between exchangeInfo reloads after the startup load
+pub const KNOWN_SYMBOLS_REFRESH_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Listed symbols from the most recent exchange-info fetch (empty = not loaded)
+static KNOWN_SYMBOLS: OnceLock<RwLock<HashSet<String>>> = OnceLock::new();
+
+fn known_symbols() -> &'static RwLock<HashSet<String>> {
+    KNOWN_SYMBOLS.get_or_init(|| RwLock::new(HashSet::new()))
+}
+
+/// Replace the cached set of listed symbols used for membership checks
+///
+/// Passing an empty iterator disables the membership check again.
+pub fn set_known_symbols<I>(symbols: I)
+where
+    I: IntoIterator<Item = String>,
+{
+    let mut known = known_symbols().write().unwrap();
+    *known = symbols.into_iter().map(|s| s.to_uppercase()).collect();
+    tracing::debug!(count = known.len(), "Updated known symbol set");
+}
+
+/// Load the listed symbols from exchangeInfo now and then every `interval`
+///
+/// Failed loads are logged and keep the previous set; until the first load
+/// succeeds only the pattern is checked.
+pub fn spawn_known_symbols_refresh(
+    client: BinanceClient,
+    interval: Duration,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match client.get_exchange_info(None).await {
+                Ok(info) => set_known_symbols(info.symbols.into_iter().map(|s| s.symbol)),
+                Err(e) => tracing::warn!("Failed to load listed symbols: {}", e),
+            }
+        }
+    })
+}
+
+/// Validate and normalize a trading pair symbol
+///
+/// Uppercases, strips whitespace and checks the `^[A-Z0-9]{6,12}$` pattern. If a
+/// known symbol set has been registered, the symbol must also be listed.
+///
+/// # Arguments
+/// * `symbol` - Raw symbol from tool arguments (e.g. " btcusdt ")
+///
+/// # Returns
+/// Normalized symbol (e.g. "BTCUSDT"), `ProviderError::Validation` for a
+/// malformed symbol or `ProviderError::SymbolNotFound` for an unlisted one, both
+/// with near-miss suggestions
+pub fn validate_and_normalize(symbol: &str) -> Result<String> {
+    let known = known_symbols().read().unwrap();
+    let known = if known.is_empty() {
+        None
+    } else {
+        Some(&*known)
+    };
+    validate_with_known(symbol, known)
+}
+
+/// Validate `symbol` against the pattern and, if provided, a set of listed symbols
+pub fn validate_with_known(symbol: &str, known: Option<&HashSet<String>>) -> Result<String> {
+    let normalized: String = symbol
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase();
+
+    if normalized.is_empty() {
+        return Err(ProviderError::Validation(
+            "Symbol must not be empty (expected e.g. 'BTCUSDT')".to_string(),
+        ));
+    }
+
+    let pattern_ok = (MIN_SYMBOL_LEN..=MAX_SYMBOL_LEN).contains(&normalized.len())
+        && normalized.chars().all(|c| c.is_ascii_alphanumeric());
+
+    if !pattern_ok {
+        return Err(ProviderError::Validation(invalid_symbol_message(
+            symbol,
+            &format!(
+                "must be {}-{} letters or digits",
+                MIN_SYMBOL_LEN, MAX_SYMBOL_LEN
+            ),
+            &suggest(&normalized, known),
+        )));
+    }
+
+    if let Some(known) = known {
+        if !known.contains(&normalized) {
+            return Err(ProviderError::SymbolNotFound(invalid_symbol_message(
+                symbol,
+                "is not listed on Binance",
+                &suggest(&normalized, Some(known)),
+            )));
+        }
+    }
+
+    Ok(normalized)
+}
+
+fn invalid_symbol_message(input: &str, reason: &str, suggestions: &[String]) -> String {
+    let mut message = format!("Invalid symbol '{}': {}", input, reason);
+    if !suggestions.is_empty() {
+        message.push_str(&format!(". Did you mean: {}?", suggestions.join(", ")));
+    }
+    message
+}
+
+/// Build near-miss suggestions for an invalid symbol
+///
+/// Separators are dropped (`BTC/USDT` → `BTCUSDT`), bare base assets get common
+/// quote assets appended, and with a known set the closest listed symbols by
+/// edit distance are returned.
+fn suggest(normalized: &str, known: Option<&HashSet<String>>) -> Vec<String> {
+    let cleaned: String = normalized
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect();
+    if cleaned.is_empty() {
+        return Vec::new();
+    }
+
+    let mut candidates: Vec<String> = Vec::new();
+    if cleaned != normalized {
+        candidates.push(cleaned.clone());
+    }
+    if cleaned.len() < MIN_SYMBOL_LEN {
+        for quote in COMMON_QUOTE_ASSETS {
+            if cleaned != quote {
+                candidates.push(format!("{}{}", cleaned, quote));
+            }
+        }
+    }
+
+    match known {
+        Some(known) => {
+            let mut scored: Vec<(usize, &String)> = known
+                .iter()
+                .filter_map(|listed| {
+                    let distance = candidates
+                        .iter()
+                        .chain(std::iter::once(&cleaned))
+                        .map(|candidate| edit_distance(candidate, listed))
+                        .min()?;
+                    (distance <= 2).then_some((distance, listed))
+                })
+                .collect();
+            scored.sort();
+            scored
+                .into_iter()
+                .take(MAX_SUGGESTIONS)
+                .map(|(_, s)| s.clone())
+                .collect()
+        }
+        None => candidates
+            .into_iter()
+            .filter(|c| (MIN_SYMBOL_LEN..=MAX_SYMBOL_LEN).contains(&c.len()))
+            .take(MAX_SUGGESTIONS)
+            .collect(),
+    }
+}
+
+/// Levenshtein distance between two ASCII strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b_bytes = b.as_bytes();
+    let mut prev: Vec<usize> = (0..=b_bytes.len()).collect();
+    let mut curr = vec![0; b_bytes.len() + 1];
+
+    for (i, ca) in a.bytes().enumerate() {
+        curr[0] = i + 1;
+        for (j, &cb) in b_bytes.iter().enumerate() {
+            let cost = usize::from(ca != cb);
+            curr[j + 1] = (prev[j + 1] + 1).min(curr[j] + 1).min(prev[j] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b_bytes.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn known() -> HashSet<String> {
+        ["BTCUSDT", "ETHUSDT", "ETHBTC", "SOLUSDT"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_lowercase_is_normalized() {
+        assert_eq!(validate_with_known(" btcusdt ", None).unwrap(), "BTCUSDT");
+        assert_eq!(
+            validate_with_known("ethusdt", Some(&known())).unwrap(),
+            "ETHUSDT"
+        );
+    }
+
+    #[test]
+    fn test_empty_symbol_rejected() {
+        let err = validate_with_known("", None).unwrap_err();
+        assert!(matches!(err, ProviderError::Validation(_)));
+        assert!(err.to_string().contains("must not be empty"));
+
+        assert!(validate_with_known("   ", None).is_err());
+    }
+
+    #[test]
+    fn test_unknown_symbol_lists_near_misses() {
+        let err = validate_with_known("BTCUSTD", Some(&known())).unwrap_err();
+        assert_eq!(err.error_code(), "SYMBOL_NOT_FOUND");
+        let message = err.to_string();
+        assert!(message.contains("not listed"));
+        assert!(message.contains("BTCUSDT"));
+        assert!(!message.contains("SOLUSDT"));
+    }
+
+    #[test]
+    fn test_pattern_violation_suggestions() {
+        let err = validate_with_known("btc/usdt", None).unwrap_err();
+        assert!(err.to_string().contains("Did you mean: BTCUSDT"));
+
+        let err = validate_with_known("btc", None).unwrap_err();
+        assert!(err.to_string().contains("BTCUSDT"));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("BTCUSDT", "BTCUSDT"), 0);
+        assert_eq!(edit_distance("BTCUSTD", "BTCUSDT"), 2);
+        assert_eq!(edit_distance("ETHBTC", "ETHBTC"), 0);
+        assert_eq!(edit_distance("SOLUSDT", "BTCUSDT"), 3);
+    }
+}
@@ -0,0 +1,85 @@
+//! Local clock drift against Binance server time
+//!
+//! Signed requests carry a local `timestamp` that Binance rejects when it falls
+//! outside `recvWindow` of its own clock. [`spawn_drift_check`] measures the
+//! offset via `GET /api/v3/time` at startup and then periodically; the client
+//! adds the latest offset to the timestamps it signs.
+
+use crate::binance::BinanceClient;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::time::Duration;
+
+/// Offset beyond which the local clock is reported as skewed
+pub const MAX_CLOCK_SKEW_MS: i64 = 1000;
+
+/// Interval between drift checks after the startup check
+pub const DRIFT_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Offset of Binance server time from local time, in milliseconds
+///
+/// The server time is assumed to be sampled halfway through the round trip, so
+/// `offset = server_time - (sent_at + received_at) / 2`. Positive means the
+/// local clock is behind Binance.
+pub fn clock_offset_ms(server_time_ms: i64, sent_at_ms: i64, received_at_ms: i64) -> i64 {
+    server_time_ms - (sent_at_ms + (received_at_ms - sent_at_ms) / 2)
+}
+
+/// Latest measured clock offset, shared by clones of a client
+#[derive(Debug, Default)]
+pub struct ClockOffset {
+    offset_ms: AtomicI64,
+    measured: AtomicBool,
+}
+
+impl ClockOffset {
+    /// Store a new measurement
+    pub fn record(&self, offset_ms: i64) {
+        self.offset_ms.store(offset_ms, Ordering::Relaxed);
+        self.measured.store(true, Ordering::Relaxed);
+    }
+
+    /// Latest offset (None until the first check succeeded)
+    pub fn get(&self) -> Option<i64> {
+        self.measured
+            .load(Ordering::Relaxed)
+            .then(|| self.offset_ms.load(Ordering::Relaxed))
+    }
+}
+
+/// Check drift now and then every `interval` until the task is aborted
+///
+/// Failed checks are logged and keep the previous offset.
+pub fn spawn_drift_check(client: BinanceClient, interval: Duration) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = client.check_clock_drift().await {
+                tracing::warn!("Clock drift check failed: {}", e);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_uses_round_trip_midpoint() {
+        // Request sent at 1000, answered at 1200: server stamped it around 1100
+        assert_eq!(clock_offset_ms(1_100, 1_000, 1_200), 0);
+        // Local clock 1.5s behind Binance
+        assert_eq!(clock_offset_ms(2_600, 1_000, 1_200), 1_500);
+        // Local clock 2s ahead of Binance
+        assert_eq!(clock_offset_ms(-900, 1_000, 1_200), -2_000);
+    }
+
+    #[test]
+    fn test_offset_unknown_until_recorded() {
+        let offset = ClockOffset::default();
+        assert_eq!(offset.get(), None);
+        offset.record(-1_250);
+        assert_eq!(offset.get(), Some(-1_250));
+    }
+}
@@ -3,6 +3,7 @@
 //! Type definitions for Binance API responses and requests.
 //! All types include validation and proper deserialization.
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 /// Response from Binance /api/v3/time endpoint
@@ -63,6 +64,150 @@ mod tests {
         let response = ServerTimeResponse { server_time: 0 };
         assert!(!response.is_valid());
     }
+
+    #[test]
+    fn test_agg_trades_deserialization() {
+        let json = r#"[
+            {"a": 26129, "p": "0.01633102", "q": "4.70443515", "f": 27781, "l": 27781,
+             "T": 1498793709153, "m": true, "M": true},
+            {"a": 26130, "p": "0.01633200", "q": "0.50000000", "f": 27782, "l": 27784,
+             "T": 1498793709200, "m": false, "M": true}
+        ]"#;
+        let trades: Vec<AggTradeResponse> = serde_json::from_str(json).unwrap();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].agg_trade_id, 26129);
+        assert_eq!(trades[0].price, "0.01633102");
+        assert_eq!(trades[0].qty, "4.70443515");
+        assert_eq!(trades[0].time, 1498793709153);
+        assert!(trades[0].is_buyer_maker);
+        assert_eq!(trades[1].first_trade_id, 27782);
+        assert_eq!(trades[1].last_trade_id, 27784);
+        assert!(!trades[1].is_buyer_maker);
+
+        // Round-trips through the compact Binance field names
+        let encoded = serde_json::to_string(&trades[0]).unwrap();
+        assert!(encoded.contains(r#""a":26129"#));
+    }
+
+    #[test]
+    fn test_book_ticker_deserialization() {
+        let json = r#"{
+            "symbol": "LTCBTC",
+            "bidPrice": "4.00000000",
+            "bidQty": "431.00000000",
+            "askPrice": "4.00000200",
+            "askQty": "9.00000000"
+        }"#;
+        let ticker: BookTicker = serde_json::from_str(json).unwrap();
+        assert_eq!(ticker.symbol, "LTCBTC");
+        assert_eq!(ticker.bid_price, "4.00000000");
+        assert_eq!(ticker.bid_qty, "431.00000000");
+        assert_eq!(ticker.ask_price, "4.00000200");
+        assert_eq!(ticker.ask_qty, "9.00000000");
+
+        let encoded = serde_json::to_value(&ticker).unwrap();
+        assert_eq!(encoded["askQty"], "9.00000000");
+    }
+
+    #[test]
+    fn test_open_interest_deserialization() {
+        let json = r#"{"openInterest": "10659.509", "symbol": "BTCUSDT", "time": 1589437530011}"#;
+        let oi: OpenInterest = serde_json::from_str(json).unwrap();
+        assert_eq!(oi.symbol, "BTCUSDT");
+        assert_eq!(oi.open_interest, "10659.509");
+        assert_eq!(oi.time, 1589437530011);
+    }
+
+    #[test]
+    fn test_open_interest_hist_deserialization() {
+        let json = r#"[
+            {"symbol": "BTCUSDT", "sumOpenInterest": "20403.63700000",
+             "sumOpenInterestValue": "150570784.07809979", "timestamp": "1583127900000"},
+            {"symbol": "BTCUSDT", "sumOpenInterest": "20401.36700000",
+             "sumOpenInterestValue": "149940752.14464448", "timestamp": 1583128200000}
+        ]"#;
+        let history: Vec<OpenInterestHist> = serde_json::from_str(json).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].sum_open_interest, "20403.63700000");
+        assert_eq!(history[0].timestamp, 1583127900000);
+        assert_eq!(history[1].sum_open_interest_value, "149940752.14464448");
+        assert_eq!(history[1].timestamp, 1583128200000);
+    }
+
+    #[test]
+    fn test_long_short_ratio_deserialization() {
+        let json = r#"[
+            {"symbol": "BTCUSDT", "longShortRatio": "0.1960", "longAccount": "0.6622",
+             "shortAccount": "0.3378", "timestamp": "1583139600000"},
+            {"symbol": "BTCUSDT", "longShortRatio": "1.9559", "longAccount": "0.6617",
+             "shortAccount": "0.3383", "timestamp": 1583139900000}
+        ]"#;
+        let ratios: Vec<LongShortRatio> = serde_json::from_str(json).unwrap();
+        assert_eq!(ratios.len(), 2);
+        assert_eq!(ratios[0].long_short_ratio, "0.1960");
+        assert_eq!(ratios[0].long_account, "0.6622");
+        assert_eq!(ratios[0].short_account, "0.3378");
+        assert_eq!(ratios[0].timestamp, 1583139600000);
+        assert_eq!(ratios[1].timestamp, 1583139900000);
+
+        assert!(serde_json::from_str::<LongShortRatio>(
+            r#"{"symbol": "X", "longShortRatio": "1", "longAccount": "0.5",
+                "shortAccount": "0.5", "timestamp": "soon"}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_account_info_deserialization() {
+        let json = r#"{
+            "makerCommission": 15, "takerCommission": 15, "buyerCommission": 0,
+            "sellerCommission": 0, "canTrade": true, "canWithdraw": false,
+            "canDeposit": true, "brokered": false, "updateTime": 123456789,
+            "accountType": "SPOT",
+            "balances": [
+                {"asset": "BTC", "free": "4723846.89208129", "locked": "0.00000000"},
+                {"asset": "LTC", "free": "4763368.68006011", "locked": "0.00000000"}
+            ],
+            "permissions": ["SPOT"], "uid": 354937868
+        }"#;
+
+        let account: AccountInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(account.account_type, "SPOT");
+        assert!(account.can_trade);
+        assert!(!account.can_withdraw);
+        assert_eq!(account.balances.len(), 2);
+        assert_eq!(account.balances[1].asset, "LTC");
+        assert_eq!(account.balances[0].locked, "0.00000000");
+    }
+
+    #[test]
+    fn test_exchange_info_tick_size() {
+        let json = r#"{
+            "timezone": "UTC",
+            "serverTime": 1565246363776,
+            "rateLimits": [],
+            "symbols": [{
+                "symbol": "BTCUSDT",
+                "status": "TRADING",
+                "baseAsset": "BTC",
+                "quoteAsset": "USDT",
+                "filters": [
+                    {"filterType": "LOT_SIZE", "minQty": "0.00001000",
+                     "maxQty": "9000.00000000", "stepSize": "0.00001000"},
+                    {"filterType": "PRICE_FILTER", "minPrice": "0.01000000",
+                     "maxPrice": "1000000.00000000", "tickSize": "0.01000000"}
+                ]
+            }]
+        }"#;
+        let info: ExchangeInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(info.symbols.len(), 1);
+        assert_eq!(info.symbols[0].quote_asset, "USDT");
+        assert_eq!(info.symbols[0].tick_size(), Some("0.01000000"));
+
+        let mut no_filters = info.symbols[0].clone();
+        no_filters.filters.clear();
+        assert_eq!(no_filters.tick_size(), None);
+    }
 }
 
 /// Response from /api/v3/ticker/price endpoint
@@ -76,6 +221,93 @@ pub struct TickerPrice {
     pub price: String,
 }
 
+/// Response from /api/v3/ticker/bookTicker endpoint
+///
+/// Best bid/ask price and quantity on the order book.
+///
+/// # Example Response
+/// ```json
+/// {
+///   "symbol": "BTCUSDT",
+///   "bidPrice": "67012.34000000",
+///   "bidQty": "1.25000000",
+///   "askPrice": "67012.35000000",
+///   "askQty": "0.40000000"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct BookTicker {
+    /// Trading pair symbol
+    pub symbol: String,
+    /// Best bid price
+    pub bid_price: String,
+    /// Quantity at the best bid
+    pub bid_qty: String,
+    /// Best ask price
+    pub ask_price: String,
+    /// Quantity at the best ask
+    pub ask_qty: String,
+}
+
+/// Response from /api/v3/exchangeInfo endpoint
+///
+/// Trading rules per symbol. Filters are kept as raw JSON objects because their
+/// fields depend on `filterType`; see [`SymbolInfo::tick_size`].
+///
+/// # Example Response
+/// ```json
+/// {
+///   "timezone": "UTC",
+///   "serverTime": 1565246363776,
+///   "symbols": [{
+///     "symbol": "ETHBTC",
+///     "status": "TRADING",
+///     "baseAsset": "ETH",
+///     "quoteAsset": "BTC",
+///     "filters": [{"filterType": "PRICE_FILTER", "minPrice": "0.00001000",
+///                  "maxPrice": "922327.00000000", "tickSize": "0.00001000"}]
+///   }]
+/// }
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExchangeInfo {
+    /// Exchange timezone (always "UTC")
+    pub timezone: String,
+    /// Server time in milliseconds since Unix epoch
+    pub server_time: i64,
+    /// Requested symbols (all listed symbols when none was given)
+    pub symbols: Vec<SymbolInfo>,
+}
+
+/// Trading rules for one symbol in [`ExchangeInfo`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymbolInfo {
+    /// Trading pair symbol
+    pub symbol: String,
+    /// Trading status (e.g., "TRADING", "BREAK")
+    pub status: String,
+    /// Base asset (e.g., "ETH")
+    pub base_asset: String,
+    /// Quote asset (e.g., "BTC")
+    pub quote_asset: String,
+    /// Symbol filters, each tagged by `filterType`
+    #[serde(default)]
+    pub filters: Vec<serde_json::Value>,
+}
+
+impl SymbolInfo {
+    /// Price increment from the `PRICE_FILTER` filter (e.g., "0.01000000")
+    pub fn tick_size(&self) -> Option<&str> {
+        self.filters
+            .iter()
+            .find(|filter| filter["filterType"] == "PRICE_FILTER")
+            .and_then(|filter| filter["tickSize"].as_str())
+    }
+}
+
 /// Response from /api/v3/ticker/24hr endpoint
 ///
 /// Returns 24-hour rolling window price statistics.
@@ -164,6 +396,149 @@ pub struct Trade {
     pub is_best_match: bool,
 }
 
-// Phase 7: Order management types removed per FR-001
-// Removed: Balance, AccountInfo, Fill, Order, MyTrade structs
-// This system is now read-only market data analysis only
+/// Response item from /api/v3/aggTrades endpoint
+///
+/// Trades filled at the same time, price and taker side are aggregated into one entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AggTradeResponse {
+    /// Aggregate trade ID
+    #[serde(rename = "a")]
+    pub agg_trade_id: i64,
+    /// Price
+    #[serde(rename = "p")]
+    pub price: String,
+    /// Quantity
+    #[serde(rename = "q")]
+    pub qty: String,
+    /// First underlying trade ID
+    #[serde(rename = "f")]
+    pub first_trade_id: i64,
+    /// Last underlying trade ID
+    #[serde(rename = "l")]
+    pub last_trade_id: i64,
+    /// Trade time (milliseconds since epoch)
+    #[serde(rename = "T")]
+    pub time: i64,
+    /// Was the buyer the maker?
+    #[serde(rename = "m")]
+    pub is_buyer_maker: bool,
+    /// Was the trade the best price match?
+    #[serde(rename = "M")]
+    pub is_best_match: bool,
+}
+
+/// Response from USDⓈ-M futures /fapi/v1/openInterest endpoint
+///
+/// # Example Response
+/// ```json
+/// {
+///   "openInterest": "10659.509",
+///   "symbol": "BTCUSDT",
+///   "time": 1589437530011
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInterest {
+    /// Futures symbol
+    pub symbol: String,
+    /// Open contracts, in base asset units
+    pub open_interest: String,
+    /// Snapshot time (milliseconds since epoch)
+    pub time: i64,
+}
+
+/// Response item from /futures/data/openInterestHist endpoint
+///
+/// Binance has served `timestamp` both as a number and as a string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInterestHist {
+    /// Futures symbol
+    pub symbol: String,
+    /// Total open interest, in base asset units
+    pub sum_open_interest: String,
+    /// Total open interest value, in quote asset
+    pub sum_open_interest_value: String,
+    /// Period start (milliseconds since epoch)
+    #[serde(deserialize_with = "deserialize_millis")]
+    pub timestamp: i64,
+}
+
+/// Response item from /futures/data/globalLongShortAccountRatio endpoint
+///
+/// # Example Response
+/// ```json
+/// {
+///   "symbol": "BTCUSDT",
+///   "longShortRatio": "1.8105",
+///   "longAccount": "0.6442",
+///   "shortAccount": "0.3558",
+///   "timestamp": 1583139600000
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LongShortRatio {
+    /// Futures symbol
+    pub symbol: String,
+    /// Long accounts divided by short accounts
+    pub long_short_ratio: String,
+    /// Share of accounts net long (0-1)
+    pub long_account: String,
+    /// Share of accounts net short (0-1)
+    pub short_account: String,
+    /// Period start (milliseconds since epoch)
+    #[serde(deserialize_with = "deserialize_millis")]
+    pub timestamp: i64,
+}
+
+/// Accepts a millisecond timestamp encoded either as a JSON number or a string
+fn deserialize_millis<'de, D>(deserializer: D) -> Result<i64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Millis {
+        Number(i64),
+        Text(String),
+    }
+
+    match Millis::deserialize(deserializer)? {
+        Millis::Number(ms) => Ok(ms),
+        Millis::Text(text) => text.parse().map_err(serde::de::Error::custom),
+    }
+}
+
+/// Response from the signed /api/v3/account endpoint
+///
+/// Read-only account snapshot; order management types remain removed per FR-001.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountInfo {
+    /// Account type (e.g. "SPOT")
+    pub account_type: String,
+    /// Whether the account may place orders
+    pub can_trade: bool,
+    /// Whether the account may withdraw
+    pub can_withdraw: bool,
+    /// Whether the account may deposit
+    pub can_deposit: bool,
+    /// Last account update (ms since epoch)
+    pub update_time: i64,
+    /// Per-asset balances
+    pub balances: Vec<Balance>,
+}
+
+/// One asset balance of an [`AccountInfo`]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Balance {
+    /// Asset symbol (e.g. "BTC")
+    pub asset: String,
+    /// Available quantity
+    pub free: String,
+    /// Quantity locked in open orders
+    pub locked: String,
+}
@@ -4,29 +4,61 @@
 //! Handles automatic reconnection with exponential backoff and message broadcasting.
 //!
 //! ## Features
-//! - Ticker price streams (real-time price updates)
+//! - Ticker price streams (full 24hr ticker or the lighter mini ticker)
 //! - Order book depth streams (bid/ask updates)
+//! - Kline/candlestick streams (closed candles, optionally in-progress updates)
 //! - User data streams (order/balance notifications)
-//! - Automatic reconnection with exponential backoff (100ms → 30s)
+//! - Automatic reconnection with jittered exponential backoff (100ms → 30s)
+//! - Sampled parse-error logging (see [`parse_errors`](crate::binance::parse_errors))
 //! - Message broadcasting via tokio::sync::broadcast channels
 
+use crate::binance::backoff::JitteredBackoff;
+use crate::binance::market::Market;
+use crate::binance::parse_errors::ParseErrorSampler;
 use crate::error::McpError;
 use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use std::time::Duration;
 use tokio::sync::broadcast;
 use tokio::time::sleep;
 use tokio_tungstenite::{connect_async, tungstenite::protocol::Message};
 
-/// Base URL for Binance WebSocket streams
-const BINANCE_WS_URL: &str = "wss://stream.binance.com:9443/ws";
-
 /// Maximum reconnection backoff duration
 const MAX_BACKOFF: Duration = Duration::from_secs(30);
 
 /// Initial reconnection backoff duration
 const INITIAL_BACKOFF: Duration = Duration::from_millis(100);
 
+/// Which 24hr rolling ticker stream to subscribe to
+///
+/// Both streams push once per second. The mini ticker carries only OHLC and
+/// volume, roughly half the payload of the full ticker.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TickerKind {
+    /// `<symbol>@ticker`, decoded as [`TickerUpdate`]
+    #[default]
+    Full,
+    /// `<symbol>@miniTicker`, decoded as [`MiniTickerUpdate`]
+    Mini,
+}
+
+impl TickerKind {
+    /// Stream name for `symbol` (e.g. "btcusdt@miniTicker")
+    pub fn stream_name(self, symbol: &str) -> String {
+        let suffix = match self {
+            Self::Full => "ticker",
+            Self::Mini => "miniTicker",
+        };
+        format!("{}@{}", symbol.to_lowercase(), suffix)
+    }
+}
+
+/// Kline stream name for `symbol` and `interval` (e.g. "btcusdt@kline_1m")
+pub fn kline_stream_name(symbol: &str, interval: &str) -> String {
+    format!("{}@kline_{}", symbol.to_lowercase(), interval)
+}
+
 /// Binance WebSocket client for managing stream connections
 ///
 /// Handles connections to Binance WebSocket API with automatic
@@ -38,17 +70,22 @@ pub struct BinanceWebSocketClient {
 }
 
 impl BinanceWebSocketClient {
-    /// Create a new Binance WebSocket client with default URL
+    /// Create a new Binance WebSocket client for spot streams
     pub fn new() -> Self {
+        Self::for_market(Market::Spot)
+    }
+
+    /// Create a client for `market`'s streams
+    pub fn for_market(market: Market) -> Self {
         Self {
-            base_url: BINANCE_WS_URL.to_string(),
+            base_url: format!("{}/ws", market.ws_base_url()),
         }
     }
 
     /// Connect to a WebSocket stream with automatic retry and exponential backoff
     ///
-    /// Retries connection failures with exponential backoff starting at 100ms
-    /// and capping at 30 seconds between attempts.
+    /// Retries connection failures with full-jitter exponential backoff: the delay
+    /// is random below a ceiling that starts at 100ms and doubles up to 30 seconds.
     ///
     /// ## Arguments
     /// - `stream_name`: The Binance stream endpoint (e.g., "btcusdt@ticker", "btcusdt@depth")
@@ -86,7 +123,7 @@ impl BinanceWebSocketClient {
         McpError,
     > {
         let url = format!("{}/{}", self.base_url, stream_name);
-        let mut backoff = INITIAL_BACKOFF;
+        let mut backoff = JitteredBackoff::new(INITIAL_BACKOFF, MAX_BACKOFF);
 
         loop {
             tracing::info!("Connecting to Binance WebSocket: {}", url);
@@ -98,17 +135,15 @@ impl BinanceWebSocketClient {
                     return Ok((write, read));
                 }
                 Err(e) => {
+                    let delay = backoff.next_delay();
                     tracing::warn!(
                         "Failed to connect to {}: {}. Retrying in {:?}",
                         stream_name,
                         e,
-                        backoff
+                        delay
                     );
 
-                    sleep(backoff).await;
-
-                    // Exponential backoff with cap
-                    backoff = std::cmp::min(backoff * 2, MAX_BACKOFF);
+                    sleep(delay).await;
                 }
             }
         }
@@ -152,10 +187,34 @@ impl BinanceWebSocketClient {
         symbol: &str,
         tx: broadcast::Sender<TickerUpdate>,
     ) -> Result<(), McpError> {
-        let stream_name = format!("{}@ticker", symbol.to_lowercase());
+        self.run_ticker_stream(TickerKind::Full, symbol, tx).await
+    }
+
+    /// Start a mini ticker stream task (`<symbol>@miniTicker`)
+    ///
+    /// Same behaviour as [`Self::ticker_stream_task`], for callers that only
+    /// need last price and volume.
+    pub async fn mini_ticker_stream_task(
+        &self,
+        symbol: &str,
+        tx: broadcast::Sender<MiniTickerUpdate>,
+    ) -> Result<(), McpError> {
+        self.run_ticker_stream(TickerKind::Mini, symbol, tx).await
+    }
+
+    /// Read `kind` ticker messages for `symbol` into `tx`, reconnecting forever
+    async fn run_ticker_stream<T: DeserializeOwned>(
+        &self,
+        kind: TickerKind,
+        symbol: &str,
+        tx: broadcast::Sender<T>,
+    ) -> Result<(), McpError> {
+        let stream_name = kind.stream_name(symbol);
+
+        let mut parse_errors = ParseErrorSampler::new();
 
         loop {
-            tracing::info!("Starting ticker stream for {}", symbol);
+            tracing::info!("Starting {} stream", stream_name);
 
             // Connect with retry
             let (_write, mut read) = self.connect_with_retry(&stream_name).await?;
@@ -165,14 +224,14 @@ impl BinanceWebSocketClient {
                 match msg_result {
                     Ok(Message::Text(text)) => {
                         // Parse ticker update
-                        match serde_json::from_str::<TickerUpdate>(&text) {
+                        match serde_json::from_str::<T>(&text) {
                             Ok(update) => {
                                 // Broadcast to all subscribers
                                 // Ignore send errors (no active receivers)
                                 let _ = tx.send(update);
                             }
                             Err(e) => {
-                                tracing::warn!("Failed to parse ticker update: {}", e);
+                                parse_errors.log(&stream_name, &e, &text);
                             }
                         }
                     }
@@ -196,7 +255,7 @@ impl BinanceWebSocketClient {
                 }
             }
 
-            tracing::warn!("Ticker stream disconnected, reconnecting...");
+            tracing::warn!("{} stream disconnected, reconnecting...", stream_name);
             sleep(Duration::from_secs(1)).await;
         }
     }
@@ -241,6 +300,8 @@ impl BinanceWebSocketClient {
     ) -> Result<(), McpError> {
         let stream_name = format!("{}@depth", symbol.to_lowercase());
 
+        let mut parse_errors = ParseErrorSampler::new();
+
         loop {
             tracing::info!("Starting depth stream for {}", symbol);
 
@@ -259,7 +320,7 @@ impl BinanceWebSocketClient {
                                 let _ = tx.send(update);
                             }
                             Err(e) => {
-                                tracing::warn!("Failed to parse depth update: {}", e);
+                                parse_errors.log(&stream_name, &e, &text);
                             }
                         }
                     }
@@ -288,6 +349,95 @@ impl BinanceWebSocketClient {
         }
     }
 
+    /// Start a kline stream task (`<symbol>@kline_<interval>`)
+    ///
+    /// Binance pushes the current candle every ~2 seconds while it is forming
+    /// and once more when it closes. Only the closing update (`k.x == true`) is
+    /// broadcast unless `include_in_progress` is set, so subscribers see one
+    /// final candle per interval by default.
+    ///
+    /// ## Arguments
+    /// - `symbol`: Trading pair symbol (e.g., "btcusdt")
+    /// - `interval`: Kline interval (e.g., "1m", "1h", "1d")
+    /// - `include_in_progress`: Also broadcast updates of the still-open candle
+    /// - `tx`: Broadcast sender for distributing kline updates to subscribers
+    ///
+    /// ## Example
+    /// ```rust,no_run
+    /// use mcp_binance_server::binance::websocket::BinanceWebSocketClient;
+    /// use tokio::sync::broadcast;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = BinanceWebSocketClient::new();
+    /// let (tx, _rx) = broadcast::channel(100);
+    ///
+    /// tokio::spawn(async move {
+    ///     if let Err(e) = client.kline_stream_task("btcusdt", "1m", false, tx).await {
+    ///         eprintln!("Kline stream error: {}", e);
+    ///     }
+    /// });
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn kline_stream_task(
+        &self,
+        symbol: &str,
+        interval: &str,
+        include_in_progress: bool,
+        tx: broadcast::Sender<KlineUpdate>,
+    ) -> Result<(), McpError> {
+        let stream_name = kline_stream_name(symbol, interval);
+
+        let mut parse_errors = ParseErrorSampler::new();
+
+        loop {
+            tracing::info!("Starting {} stream", stream_name);
+
+            // Connect with retry
+            let (_write, mut read) = self.connect_with_retry(&stream_name).await?;
+
+            // Read messages and broadcast to subscribers
+            while let Some(msg_result) = read.next().await {
+                match msg_result {
+                    Ok(Message::Text(text)) => {
+                        // Parse kline update
+                        match serde_json::from_str::<KlineUpdate>(&text) {
+                            Ok(update) if update.kline.is_closed || include_in_progress => {
+                                // Broadcast to all subscribers
+                                // Ignore send errors (no active receivers)
+                                let _ = tx.send(update);
+                            }
+                            Ok(_) => {}
+                            Err(e) => {
+                                parse_errors.log(&stream_name, &e, &text);
+                            }
+                        }
+                    }
+                    Ok(Message::Ping(data)) => {
+                        tracing::debug!("Received ping with {} bytes", data.len());
+                    }
+                    Ok(Message::Pong(_)) => {
+                        tracing::debug!("Received pong");
+                    }
+                    Ok(Message::Close(frame)) => {
+                        tracing::info!("WebSocket closed: {:?}", frame);
+                        break;
+                    }
+                    Err(e) => {
+                        tracing::error!("WebSocket read error: {}", e);
+                        break;
+                    }
+                    _ => {
+                        tracing::debug!("Received other message type");
+                    }
+                }
+            }
+
+            tracing::warn!("{} stream disconnected, reconnecting...", stream_name);
+            sleep(Duration::from_secs(1)).await;
+        }
+    }
+
     /// Start a user data stream task that reads from Binance and broadcasts to subscribers
     ///
     /// Creates a background task that:
@@ -329,6 +479,8 @@ impl BinanceWebSocketClient {
     ) -> Result<(), McpError> {
         let stream_name = listen_key.to_string();
 
+        let mut parse_errors = ParseErrorSampler::new();
+
         loop {
             tracing::info!("Starting user data stream with listen key");
 
@@ -347,8 +499,8 @@ impl BinanceWebSocketClient {
                                 let _ = tx.send(event);
                             }
                             Err(e) => {
-                                tracing::warn!("Failed to parse user data event: {}", e);
-                                tracing::debug!("Raw message: {}", text);
+                                // The listen key is a credential; keep it out of the logs
+                                parse_errors.log("user data", &e, &text);
                             }
                         }
                     }
@@ -478,6 +630,122 @@ pub struct TickerUpdate {
     pub quote_volume: String,
 }
 
+/// Mini ticker update message from Binance WebSocket
+///
+/// Received from the `<symbol>@miniTicker` stream every 1000ms
+#[derive(Debug, Clone, Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MiniTickerUpdate {
+    /// Event type (always "24hrMiniTicker")
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    /// Event time (milliseconds since Unix epoch)
+    #[serde(rename = "E")]
+    pub event_time: i64,
+
+    /// Trading pair symbol
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    /// Last (close) price
+    #[serde(rename = "c")]
+    pub last_price: String,
+
+    /// Open price
+    #[serde(rename = "o")]
+    pub open_price: String,
+
+    /// High price
+    #[serde(rename = "h")]
+    pub high_price: String,
+
+    /// Low price
+    #[serde(rename = "l")]
+    pub low_price: String,
+
+    /// Total traded base asset volume
+    #[serde(rename = "v")]
+    pub volume: String,
+
+    /// Total traded quote asset volume
+    #[serde(rename = "q")]
+    pub quote_volume: String,
+}
+
+/// Kline (candlestick) update message from Binance WebSocket
+///
+/// Received from the `<symbol>@kline_<interval>` stream; the candle itself is
+/// nested under `k`
+#[derive(Debug, Clone, Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KlineUpdate {
+    /// Event type (always "kline")
+    #[serde(rename = "e")]
+    pub event_type: String,
+
+    /// Event time (milliseconds since Unix epoch)
+    #[serde(rename = "E")]
+    pub event_time: i64,
+
+    /// Trading pair symbol
+    #[serde(rename = "s")]
+    pub symbol: String,
+
+    /// Candle contents
+    #[serde(rename = "k")]
+    pub kline: KlineData,
+}
+
+/// Candle carried by a [`KlineUpdate`]
+#[derive(Debug, Clone, Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KlineData {
+    /// Candle open time (milliseconds since Unix epoch)
+    #[serde(rename = "t")]
+    pub open_time: i64,
+
+    /// Candle close time (milliseconds since Unix epoch)
+    #[serde(rename = "T")]
+    pub close_time: i64,
+
+    /// Kline interval (e.g. "1m")
+    #[serde(rename = "i")]
+    pub interval: String,
+
+    /// Open price
+    #[serde(rename = "o")]
+    pub open_price: String,
+
+    /// High price
+    #[serde(rename = "h")]
+    pub high_price: String,
+
+    /// Low price
+    #[serde(rename = "l")]
+    pub low_price: String,
+
+    /// Close price (last price while the candle is open)
+    #[serde(rename = "c")]
+    pub close_price: String,
+
+    /// Base asset volume
+    #[serde(rename = "v")]
+    pub volume: String,
+
+    /// Quote asset volume
+    #[serde(rename = "q")]
+    pub quote_volume: String,
+
+    /// Number of trades
+    #[serde(rename = "n")]
+    pub trade_count: i64,
+
+    /// Whether this candle is closed (final)
+    #[serde(rename = "x")]
+    pub is_closed: bool,
+}
+
 /// User data event from Binance WebSocket
 ///
 /// Received from the user data stream (authenticated with listen key)
@@ -645,7 +913,9 @@ mod tests {
     #[test]
     fn test_binance_ws_client_creation() {
         let client = BinanceWebSocketClient::new();
-        assert_eq!(client.base_url, BINANCE_WS_URL);
+        assert_eq!(client.base_url, "wss://stream.binance.com:9443/ws");
+        let futures = BinanceWebSocketClient::for_market(Market::UsdmFutures);
+        assert_eq!(futures.base_url, "wss://fstream.binance.com/ws");
     }
 
     #[test]
@@ -671,4 +941,78 @@ mod tests {
         assert_eq!(update.last_price, "45100.00");
         assert_eq!(update.price_change, "100.00");
     }
+
+    #[test]
+    fn test_mini_ticker_update_deserialization() {
+        let json = r#"{
+            "e": "24hrMiniTicker",
+            "E": 123456789,
+            "s": "BTCUSDT",
+            "c": "45100.00",
+            "o": "45000.00",
+            "h": "45200.00",
+            "l": "44900.00",
+            "v": "1000.5",
+            "q": "45000000.00"
+        }"#;
+
+        let update: MiniTickerUpdate = serde_json::from_str(json).unwrap();
+        assert_eq!(update.event_type, "24hrMiniTicker");
+        assert_eq!(update.symbol, "BTCUSDT");
+        assert_eq!(update.last_price, "45100.00");
+        assert_eq!(update.volume, "1000.5");
+        assert_eq!(update.quote_volume, "45000000.00");
+    }
+
+    #[test]
+    fn test_kline_update_deserialization() {
+        let json = r#"{
+            "e": "kline",
+            "E": 1672515782136,
+            "s": "BTCUSDT",
+            "k": {
+                "t": 1672515780000,
+                "T": 1672515839999,
+                "s": "BTCUSDT",
+                "i": "1m",
+                "f": 100,
+                "L": 200,
+                "o": "16500.10",
+                "c": "16510.00",
+                "h": "16520.50",
+                "l": "16495.00",
+                "v": "12.345",
+                "n": 101,
+                "x": true,
+                "q": "203900.12",
+                "V": "6.100",
+                "Q": "100700.00",
+                "B": "0"
+            }
+        }"#;
+
+        let update: KlineUpdate = serde_json::from_str(json).unwrap();
+        assert_eq!(update.event_type, "kline");
+        assert_eq!(update.symbol, "BTCUSDT");
+        assert_eq!(update.kline.open_time, 1672515780000);
+        assert_eq!(update.kline.close_time, 1672515839999);
+        assert_eq!(update.kline.interval, "1m");
+        assert_eq!(update.kline.open_price, "16500.10");
+        assert_eq!(update.kline.high_price, "16520.50");
+        assert_eq!(update.kline.low_price, "16495.00");
+        assert_eq!(update.kline.close_price, "16510.00");
+        assert_eq!(update.kline.volume, "12.345");
+        assert_eq!(update.kline.trade_count, 101);
+        assert!(update.kline.is_closed);
+        assert_eq!(kline_stream_name("BTCUSDT", "1m"), "btcusdt@kline_1m");
+    }
+
+    #[test]
+    fn test_ticker_kind_stream_names() {
+        assert_eq!(TickerKind::Full.stream_name("BTCUSDT"), "btcusdt@ticker");
+        assert_eq!(
+            TickerKind::Mini.stream_name("ethusdt"),
+            "ethusdt@miniTicker"
+        );
+    }
 }
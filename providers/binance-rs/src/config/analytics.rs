@@ -0,0 +1,269 @@
+//! Analytics Persistence Configuration
+//!
+//! Selects which symbols are pre-subscribed for snapshot and trade persistence,
+//! how often orderbook snapshots are captured, when streamed trades are
+//! flushed, and how long data is retained.
+
+use crate::orderbook::manager::MAX_CONCURRENT_SYMBOLS;
+use std::time::Duration;
+
+/// Symbols persisted when `ANALYTICS_SYMBOLS` is unset or empty
+pub const DEFAULT_ANALYTICS_SYMBOLS: [&str; 2] = ["BTCUSDT", "ETHUSDT"];
+
+/// Default snapshot capture interval in milliseconds
+pub const DEFAULT_SNAPSHOT_INTERVAL_MS: u64 = 1000;
+
+/// Smallest accepted snapshot capture interval in milliseconds
+pub const MIN_SNAPSHOT_INTERVAL_MS: u64 = 100;
+
+/// Default retention for persisted snapshots and trades
+pub const DEFAULT_RETENTION_DAYS: u32 = 7;
+
+/// Default time between trade batch flushes in milliseconds
+pub const DEFAULT_TRADE_FLUSH_INTERVAL_MS: u64 = 1000;
+
+/// Smallest accepted trade batch flush interval in milliseconds
+pub const MIN_TRADE_FLUSH_INTERVAL_MS: u64 = 100;
+
+/// Default buffered trades per symbol that trigger an early flush
+pub const DEFAULT_TRADE_FLUSH_MAX_TRADES: usize = 1000;
+
+/// Analytics persistence configuration
+///
+/// ## Environment Variables
+///
+/// - `ANALYTICS_SYMBOLS`: Comma-separated symbols to persist (default: BTCUSDT,ETHUSDT)
+/// - `ANALYTICS_SNAPSHOT_INTERVAL_MS`: Snapshot capture interval (default: 1000, min: 100)
+/// - `ANALYTICS_RETENTION_DAYS`: Days of snapshots/trades to keep (default: 7, min: 1)
+/// - `ANALYTICS_TRADE_FLUSH_INTERVAL_MS`: Time between trade batch writes (default: 1000, min: 100)
+/// - `ANALYTICS_TRADE_FLUSH_MAX_TRADES`: Buffered trades per symbol that force a write
+///   before the interval elapses (default: 1000, min: 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalyticsConfig {
+    /// Normalized, de-duplicated symbols to persist
+    pub symbols: Vec<String>,
+
+    /// Interval between orderbook snapshot captures
+    pub snapshot_interval: Duration,
+
+    /// Days of persisted data to keep before hourly cleanup deletes it
+    pub retention_days: u32,
+
+    /// Interval between trade batch writes
+    pub trade_flush_interval: Duration,
+
+    /// Buffered trades per symbol that trigger a write before the interval
+    pub trade_flush_max_trades: usize,
+}
+
+impl AnalyticsConfig {
+    /// Load analytics configuration from environment variables
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a symbol is malformed, more symbols are listed than the
+    /// orderbook manager can track, or an interval or threshold is invalid
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let symbols = std::env::var("ANALYTICS_SYMBOLS").ok();
+        let interval = std::env::var("ANALYTICS_SNAPSHOT_INTERVAL_MS").ok();
+        let retention = std::env::var("ANALYTICS_RETENTION_DAYS").ok();
+        let flush_interval = std::env::var("ANALYTICS_TRADE_FLUSH_INTERVAL_MS").ok();
+        let flush_max_trades = std::env::var("ANALYTICS_TRADE_FLUSH_MAX_TRADES").ok();
+
+        let mut config = Self::from_values(symbols.as_deref(), interval.as_deref())?;
+        config.retention_days = parse_retention_days(retention.as_deref())?;
+        (config.trade_flush_interval, config.trade_flush_max_trades) =
+            parse_trade_flush(flush_interval.as_deref(), flush_max_trades.as_deref())?;
+        Ok(config)
+    }
+
+    /// Retention period as a `Duration`
+    pub fn retention(&self) -> Duration {
+        Duration::from_secs(u64::from(self.retention_days) * 86_400)
+    }
+
+    /// Build configuration from raw variable values (`None` = unset)
+    pub fn from_values(
+        symbols: Option<&str>,
+        snapshot_interval_ms: Option<&str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut parsed = parse_symbol_list(symbols.unwrap_or(""))?;
+        if parsed.is_empty() {
+            parsed = DEFAULT_ANALYTICS_SYMBOLS
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+        }
+
+        if parsed.len() > MAX_CONCURRENT_SYMBOLS {
+            return Err(format!(
+                "ANALYTICS_SYMBOLS lists {} symbols, but the orderbook manager tracks at most {}",
+                parsed.len(),
+                MAX_CONCURRENT_SYMBOLS
+            )
+            .into());
+        }
+
+        let interval_ms = match snapshot_interval_ms.map(str::trim) {
+            None | Some("") => DEFAULT_SNAPSHOT_INTERVAL_MS,
+            Some(raw) => raw
+                .parse::<u64>()
+                .map_err(|e| format!("Invalid ANALYTICS_SNAPSHOT_INTERVAL_MS '{}': {}", raw, e))?,
+        };
+        if interval_ms < MIN_SNAPSHOT_INTERVAL_MS {
+            return Err(format!(
+                "ANALYTICS_SNAPSHOT_INTERVAL_MS must be >= {} (got {})",
+                MIN_SNAPSHOT_INTERVAL_MS, interval_ms
+            )
+            .into());
+        }
+
+        Ok(Self {
+            symbols: parsed,
+            snapshot_interval: Duration::from_millis(interval_ms),
+            retention_days: DEFAULT_RETENTION_DAYS,
+            trade_flush_interval: Duration::from_millis(DEFAULT_TRADE_FLUSH_INTERVAL_MS),
+            trade_flush_max_trades: DEFAULT_TRADE_FLUSH_MAX_TRADES,
+        })
+    }
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            symbols: DEFAULT_ANALYTICS_SYMBOLS
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            snapshot_interval: Duration::from_millis(DEFAULT_SNAPSHOT_INTERVAL_MS),
+            retention_days: DEFAULT_RETENTION_DAYS,
+            trade_flush_interval: Duration::from_millis(DEFAULT_TRADE_FLUSH_INTERVAL_MS),
+            trade_flush_max_trades: DEFAULT_TRADE_FLUSH_MAX_TRADES,
+        }
+    }
+}
+
+/// Parse `ANALYTICS_RETENTION_DAYS` (`None` or blank = default)
+pub fn parse_retention_days(raw: Option<&str>) -> Result<u32, Box<dyn std::error::Error>> {
+    let days = match raw.map(str::trim) {
+        None | Some("") => DEFAULT_RETENTION_DAYS,
+        Some(raw) => raw
+            .parse::<u32>()
+            .map_err(|e| format!("Invalid ANALYTICS_RETENTION_DAYS '{}': {}", raw, e))?,
+    };
+    if days == 0 {
+        return Err("ANALYTICS_RETENTION_DAYS must be at least 1".into());
+    }
+    Ok(days)
+}
+
+/// Parse `ANALYTICS_TRADE_FLUSH_INTERVAL_MS` and `ANALYTICS_TRADE_FLUSH_MAX_TRADES`
+/// (`None` or blank = default)
+pub fn parse_trade_flush(
+    interval_ms: Option<&str>,
+    max_trades: Option<&str>,
+) -> Result<(Duration, usize), Box<dyn std::error::Error>> {
+    let interval_ms = match interval_ms.map(str::trim) {
+        None | Some("") => DEFAULT_TRADE_FLUSH_INTERVAL_MS,
+        Some(raw) => raw
+            .parse::<u64>()
+            .map_err(|e| format!("Invalid ANALYTICS_TRADE_FLUSH_INTERVAL_MS '{}': {}", raw, e))?,
+    };
+    if interval_ms < MIN_TRADE_FLUSH_INTERVAL_MS {
+        return Err(format!(
+            "ANALYTICS_TRADE_FLUSH_INTERVAL_MS must be >= {} (got {})",
+            MIN_TRADE_FLUSH_INTERVAL_MS, interval_ms
+        )
+        .into());
+    }
+
+    let max_trades = match max_trades.map(str::trim) {
+        None | Some("") => DEFAULT_TRADE_FLUSH_MAX_TRADES,
+        Some(raw) => raw
+            .parse::<usize>()
+            .map_err(|e| format!("Invalid ANALYTICS_TRADE_FLUSH_MAX_TRADES '{}': {}", raw, e))?,
+    };
+    if max_trades == 0 {
+        return Err("ANALYTICS_TRADE_FLUSH_MAX_TRADES must be at least 1".into());
+    }
+
+    Ok((Duration::from_millis(interval_ms), max_trades))
+}
+
+/// Parse a comma-separated symbol list
+///
+/// Blank entries are skipped, symbols are validated and normalized to
+/// uppercase, and duplicates are dropped (first occurrence wins).
+pub fn parse_symbol_list(raw: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut symbols: Vec<String> = Vec::new();
+    for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let symbol = crate::binance::symbol::validate_with_known(entry, None)?;
+        if !symbols.contains(&symbol) {
+            symbols.push(symbol);
+        }
+    }
+    Ok(symbols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_uses_defaults() {
+        let config = AnalyticsConfig::from_values(None, None).unwrap();
+        assert_eq!(config, AnalyticsConfig::default());
+
+        let config = AnalyticsConfig::from_values(Some(" , ,"), Some("")).unwrap();
+        assert_eq!(config.symbols, vec!["BTCUSDT", "ETHUSDT"]);
+        assert_eq!(config.snapshot_interval, Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn test_whitespace_and_duplicates() {
+        let config = AnalyticsConfig::from_values(
+            Some(" btcusdt, SOLUSDT ,,BTCUSDT, ethusdt "),
+            Some("500"),
+        )
+        .unwrap();
+        assert_eq!(config.symbols, vec!["BTCUSDT", "SOLUSDT", "ETHUSDT"]);
+        assert_eq!(config.snapshot_interval, Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_invalid_values_rejected() {
+        assert!(AnalyticsConfig::from_values(Some("BTC/USDT"), None).is_err());
+        assert!(AnalyticsConfig::from_values(None, Some("fast")).is_err());
+        assert!(AnalyticsConfig::from_values(None, Some("10")).is_err());
+
+        let too_many: Vec<String> = (0..=MAX_CONCURRENT_SYMBOLS)
+            .map(|i| format!("SYM{:03}USDT", i))
+            .collect();
+        let err = AnalyticsConfig::from_values(Some(&too_many.join(",")), None).unwrap_err();
+        assert!(err.to_string().contains("at most"));
+    }
+
+    #[test]
+    fn test_parse_retention_days() {
+        assert_eq!(parse_retention_days(None).unwrap(), 7);
+        assert_eq!(parse_retention_days(Some(" ")).unwrap(), 7);
+        assert_eq!(parse_retention_days(Some("30")).unwrap(), 30);
+        assert!(parse_retention_days(Some("0")).is_err());
+        assert!(parse_retention_days(Some("-1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_trade_flush() {
+        assert_eq!(
+            parse_trade_flush(None, Some(" ")).unwrap(),
+            (Duration::from_millis(1000), 1000)
+        );
+        assert_eq!(
+            parse_trade_flush(Some("250"), Some("5000")).unwrap(),
+            (Duration::from_millis(250), 5000)
+        );
+        assert!(parse_trade_flush(Some("50"), None).is_err());
+        assert!(parse_trade_flush(None, Some("0")).is_err());
+        assert!(parse_trade_flush(None, Some("many")).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+//! Listen Address Configuration
+//!
+//! Selects the interface the gRPC, HTTP and SSE servers listen on. The port is
+//! chosen separately (`--port`, with per-mode defaults).
+
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+
+/// Interface bound when neither `--bind` nor `MCP_BIND_ADDR` is set (all interfaces)
+pub const DEFAULT_BIND_ADDR: IpAddr = IpAddr::V4(Ipv4Addr::UNSPECIFIED);
+
+/// Resolve the listen address for `port`
+///
+/// ## Environment Variables
+///
+/// - `MCP_BIND_ADDR`: IP address to bind (default: 0.0.0.0); the `--bind` flag
+///   (`flag`) takes precedence
+///
+/// # Errors
+///
+/// Returns error if the chosen value is not an IPv4 or IPv6 address
+pub fn listen_addr(
+    flag: Option<&str>,
+    port: u16,
+) -> Result<SocketAddr, Box<dyn std::error::Error>> {
+    let env = std::env::var("MCP_BIND_ADDR").ok();
+    let ip = parse_bind_addr(flag.or(env.as_deref()))?;
+    Ok(SocketAddr::new(ip, port))
+}
+
+/// Parse a raw bind address (`None` or blank = [`DEFAULT_BIND_ADDR`])
+///
+/// Accepts plain IP addresses such as `127.0.0.1` or `::1`; host names and
+/// `ip:port` pairs are rejected.
+pub fn parse_bind_addr(raw: Option<&str>) -> Result<IpAddr, Box<dyn std::error::Error>> {
+    match raw.map(str::trim) {
+        None | Some("") => Ok(DEFAULT_BIND_ADDR),
+        Some(value) => value
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse()
+            .map_err(|_| {
+                format!(
+                    "Invalid bind address '{}': expected an IP address such as 127.0.0.1 or ::1",
+                    value
+                )
+                .into()
+            }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bind_addresses_parsed() {
+        assert_eq!(parse_bind_addr(None).unwrap(), DEFAULT_BIND_ADDR);
+        assert_eq!(parse_bind_addr(Some(" ")).unwrap(), DEFAULT_BIND_ADDR);
+        assert_eq!(
+            parse_bind_addr(Some("127.0.0.1")).unwrap(),
+            IpAddr::V4(Ipv4Addr::LOCALHOST)
+        );
+        assert_eq!(parse_bind_addr(Some("[::1]")).unwrap().to_string(), "::1");
+    }
+
+    #[test]
+    fn test_invalid_bind_address_is_clear_error() {
+        for raw in ["localhost", "127.0.0.1:3000", "300.1.1.1"] {
+            let err = parse_bind_addr(Some(raw)).unwrap_err().to_string();
+            assert!(err.contains("Invalid bind address"), "{}", err);
+            assert!(err.contains(raw), "{}", err);
+        }
+        assert!(listen_addr(Some("not-an-ip"), 3000).is_err());
+    }
+
+    #[test]
+    fn test_flag_sets_listen_addr() {
+        let addr = listen_addr(Some("127.0.0.1"), 50053).unwrap();
+        assert_eq!(addr.to_string(), "127.0.0.1:50053");
+    }
+}
@@ -0,0 +1,106 @@
+//! Logging Configuration
+//!
+//! Selects the tracing output format. Logs always go to stderr, because stdout
+//! carries the MCP protocol in stdio mode.
+
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+/// Log line format
+///
+/// ## Environment Variables
+///
+/// - `LOG_FORMAT`: `pretty` (default, human-readable), `compact` (single-line
+///   human-readable) or `json` (one JSON object per line, for Loki/ELK ingestion)
+/// - `RUST_LOG`: Level filter directives (default level: info)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Multi-line human-readable text output
+    #[default]
+    Pretty,
+    /// Shorter single-line text output
+    Compact,
+    /// Newline-delimited JSON
+    Json,
+}
+
+impl LogFormat {
+    /// Load the log format from `LOG_FORMAT`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if `LOG_FORMAT` is set to an unknown format
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_value(std::env::var("LOG_FORMAT").ok().as_deref())
+    }
+
+    /// Parse a raw `LOG_FORMAT` value (`None` or blank = default, case-insensitive)
+    pub fn from_value(value: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        match value.map(|v| v.trim().to_ascii_lowercase()).as_deref() {
+            None | Some("") | Some("pretty") => Ok(Self::Pretty),
+            Some("compact") => Ok(Self::Compact),
+            Some("json") => Ok(Self::Json),
+            Some(other) => Err(format!(
+                "Invalid LOG_FORMAT '{}': expected pretty, compact or json",
+                other
+            )
+            .into()),
+        }
+    }
+
+    /// Build a stderr subscriber in this format, filtered by `RUST_LOG`
+    pub fn subscriber(self) -> Box<dyn tracing::Subscriber + Send + Sync> {
+        let filter = EnvFilter::from_default_env().add_directive(tracing::Level::INFO.into());
+        let builder = tracing_subscriber::fmt()
+            .with_target(false)
+            .with_thread_ids(false)
+            .with_level(true)
+            .with_writer(std::io::stderr) // Always write to stderr for MCP compatibility
+            .with_env_filter(filter);
+
+        match self {
+            Self::Pretty => Box::new(builder.pretty().finish()),
+            Self::Compact => Box::new(builder.compact().finish()),
+            Self::Json => Box::new(builder.json().finish()),
+        }
+    }
+
+    /// Install this format as the global subscriber
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a global subscriber was already installed
+    pub fn init(self) -> Result<(), Box<dyn std::error::Error>> {
+        self.subscriber().try_init()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_values_parsed() {
+        assert_eq!(LogFormat::from_value(None).unwrap(), LogFormat::Pretty);
+        assert_eq!(LogFormat::from_value(Some(" ")).unwrap(), LogFormat::Pretty);
+        assert_eq!(
+            LogFormat::from_value(Some("compact")).unwrap(),
+            LogFormat::Compact
+        );
+        assert_eq!(
+            LogFormat::from_value(Some("JSON")).unwrap(),
+            LogFormat::Json
+        );
+        assert!(LogFormat::from_value(Some("logfmt")).is_err());
+    }
+
+    #[test]
+    fn test_each_format_initializes() {
+        for value in ["pretty", "compact", "json"] {
+            let format = LogFormat::from_value(Some(value)).unwrap();
+            let _guard = tracing::subscriber::set_default(format.subscriber());
+            tracing::info!(format = value, "logging smoke test");
+        }
+    }
+}
@@ -2,13 +2,31 @@
 //!
 //! This module handles loading and managing configuration including API credentials.
 
+pub mod bind;
 pub mod credentials;
+pub mod logging;
+pub mod runtime;
 
 #[cfg(feature = "http-api")]
 pub mod http;
 
+#[cfg(feature = "orderbook")]
+pub mod analytics;
+
+#[cfg(feature = "orderbook")]
+pub mod orderbook;
+
 // Re-export
+pub use bind::listen_addr;
 pub use credentials::Credentials;
+pub use logging::LogFormat;
+pub use runtime::RuntimeConfig;
 
 #[cfg(feature = "http-api")]
 pub use http::HttpConfig;
+
+#[cfg(feature = "orderbook")]
+pub use analytics::AnalyticsConfig;
+
+#[cfg(feature = "orderbook")]
+pub use orderbook::StalenessConfig;
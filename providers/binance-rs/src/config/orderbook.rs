@@ -0,0 +1,155 @@
+//! Order Book Freshness Configuration
+//!
+//! Decides when a cached order book is too old to serve and must be refreshed
+//! over REST, globally and per symbol (illiquid pairs update less often).
+
+use crate::orderbook::manager::{DEFAULT_SNAPSHOT_DEPTH, STALENESS_THRESHOLD_MS};
+use std::collections::HashMap;
+
+/// Smallest accepted staleness threshold in milliseconds
+pub const MIN_STALENESS_MS: i64 = 100;
+
+/// Staleness thresholds for cached order books
+///
+/// ## Environment Variables
+///
+/// - `ORDERBOOK_STALENESS_MS`: Default threshold in ms (default: 5000, min: 100)
+/// - `ORDERBOOK_STALENESS_OVERRIDES`: Per-symbol thresholds as comma-separated
+///   `SYMBOL=ms` pairs (e.g. `XLMBTC=30000,ZECUSDT=15000`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StalenessConfig {
+    /// Threshold for symbols without an override
+    pub default_ms: i64,
+
+    /// Uppercase symbol → threshold
+    pub overrides: HashMap<String, i64>,
+}
+
+impl StalenessConfig {
+    /// Load staleness thresholds from environment variables
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a threshold is not a number of ms >= 100 or an override
+    /// entry is not a valid `SYMBOL=ms` pair
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        let default_ms = std::env::var("ORDERBOOK_STALENESS_MS").ok();
+        let overrides = std::env::var("ORDERBOOK_STALENESS_OVERRIDES").ok();
+        Self::from_values(default_ms.as_deref(), overrides.as_deref())
+    }
+
+    /// Build configuration from raw variable values (`None` or blank = unset)
+    pub fn from_values(
+        default_ms: Option<&str>,
+        overrides: Option<&str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut config = Self::default();
+
+        if let Some(raw) = default_ms.map(str::trim).filter(|raw| !raw.is_empty()) {
+            config.default_ms = parse_threshold("ORDERBOOK_STALENESS_MS", raw)?;
+        }
+
+        for entry in overrides
+            .unwrap_or("")
+            .split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+        {
+            let (symbol, ms) = entry.split_once('=').ok_or_else(|| {
+                format!(
+                    "Invalid ORDERBOOK_STALENESS_OVERRIDES entry '{}': expected SYMBOL=ms",
+                    entry
+                )
+            })?;
+            let symbol = crate::binance::symbol::validate_with_known(symbol.trim(), None)?;
+            let ms = parse_threshold("ORDERBOOK_STALENESS_OVERRIDES", ms.trim())?;
+            config.overrides.insert(symbol, ms);
+        }
+
+        Ok(config)
+    }
+
+    /// Override the threshold of one symbol
+    pub fn with_override(mut self, symbol: &str, threshold_ms: i64) -> Self {
+        self.overrides.insert(symbol.to_uppercase(), threshold_ms);
+        self
+    }
+
+    /// Threshold applying to `symbol` (uppercase)
+    pub fn threshold_ms(&self, symbol: &str) -> i64 {
+        self.overrides
+            .get(symbol)
+            .copied()
+            .unwrap_or(self.default_ms)
+    }
+}
+
+impl Default for StalenessConfig {
+    fn default() -> Self {
+        Self {
+            default_ms: STALENESS_THRESHOLD_MS,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+/// Parse `ORDERBOOK_SNAPSHOT_DEPTH` (`None` or blank = 100)
+///
+/// Only the number is parsed here; the order book manager rejects depths
+/// Binance does not accept when it is constructed.
+pub fn parse_snapshot_depth(raw: Option<&str>) -> Result<u32, Box<dyn std::error::Error>> {
+    match raw.map(str::trim) {
+        None | Some("") => Ok(DEFAULT_SNAPSHOT_DEPTH),
+        Some(raw) => Ok(raw
+            .parse::<u32>()
+            .map_err(|e| format!("Invalid ORDERBOOK_SNAPSHOT_DEPTH '{}': {}", raw, e))?),
+    }
+}
+
+fn parse_threshold(var: &str, raw: &str) -> Result<i64, Box<dyn std::error::Error>> {
+    let ms = raw
+        .parse::<i64>()
+        .map_err(|e| format!("Invalid {} '{}': {}", var, raw, e))?;
+    if ms < MIN_STALENESS_MS {
+        return Err(format!("{} must be >= {} (got {})", var, MIN_STALENESS_MS, ms).into());
+    }
+    Ok(ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unset_uses_default() {
+        let config = StalenessConfig::from_values(None, Some(" ")).unwrap();
+        assert_eq!(config, StalenessConfig::default());
+        assert_eq!(config.threshold_ms("BTCUSDT"), 5000);
+    }
+
+    #[test]
+    fn test_overrides_parsed_per_symbol() {
+        let config =
+            StalenessConfig::from_values(Some("8000"), Some("xlmbtc=30000, ZECUSDT = 15000"))
+                .unwrap();
+        assert_eq!(config.threshold_ms("BTCUSDT"), 8000);
+        assert_eq!(config.threshold_ms("XLMBTC"), 30000);
+        assert_eq!(config.threshold_ms("ZECUSDT"), 15000);
+    }
+
+    #[test]
+    fn test_invalid_values_rejected() {
+        assert!(StalenessConfig::from_values(Some("soon"), None).is_err());
+        assert!(StalenessConfig::from_values(Some("50"), None).is_err());
+        assert!(StalenessConfig::from_values(None, Some("XLMBTC")).is_err());
+        assert!(StalenessConfig::from_values(None, Some("XLM/BTC=30000")).is_err());
+        assert!(StalenessConfig::from_values(None, Some("XLMBTC=10")).is_err());
+    }
+
+    #[test]
+    fn test_snapshot_depth_parsed() {
+        assert_eq!(parse_snapshot_depth(None).unwrap(), 100);
+        assert_eq!(parse_snapshot_depth(Some(" 1000 ")).unwrap(), 1000);
+        assert!(parse_snapshot_depth(Some("deep")).is_err());
+    }
+}
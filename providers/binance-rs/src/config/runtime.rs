@@ -0,0 +1,184 @@
+//! Tokio Runtime Configuration
+//!
+//! Sizes the multi-thread runtime built in `main`. Worker threads run the
+//! async tasks (WebSocket streams, request handlers); the blocking pool runs
+//! `spawn_blocking` work, which is mostly RocksDB reads and writes for the
+//! analytics storage and report archiving.
+
+/// Upper bound on worker threads
+pub const MAX_WORKER_THREADS: usize = 1024;
+
+/// Blocking pool size when none is configured (Tokio's own default)
+pub const DEFAULT_BLOCKING_THREADS: usize = 512;
+
+/// Upper bound on blocking pool threads
+pub const MAX_BLOCKING_THREADS: usize = 8192;
+
+/// Worker and blocking pool sizes for the Tokio runtime
+///
+/// ## Environment Variables
+///
+/// - `TOKIO_WORKER_THREADS`: Worker threads (default: one per CPU core); the
+///   `--worker-threads` flag takes precedence
+/// - `TOKIO_BLOCKING_THREADS`: Maximum blocking pool threads (default: 512);
+///   the `--blocking-threads` flag takes precedence
+///
+/// One worker per core suits most deployments. Raise the blocking pool only
+/// when analytics storage queries queue up under load; threads are spawned on
+/// demand and idle ones exit, so a large limit costs nothing while unused.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuntimeConfig {
+    /// None = one per CPU core
+    pub worker_threads: Option<usize>,
+    /// None = [`DEFAULT_BLOCKING_THREADS`]
+    pub blocking_threads: Option<usize>,
+}
+
+impl RuntimeConfig {
+    /// Load sizes from the CLI flags, falling back to the environment
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the chosen value for either size is invalid
+    pub fn from_env(
+        worker_flag: Option<&str>,
+        blocking_flag: Option<&str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let worker_env = std::env::var("TOKIO_WORKER_THREADS").ok();
+        let blocking_env = std::env::var("TOKIO_BLOCKING_THREADS").ok();
+        Self::from_sources(
+            worker_flag,
+            blocking_flag,
+            worker_env.as_deref(),
+            blocking_env.as_deref(),
+        )
+    }
+
+    /// Parse the flag values, falling back to the environment values per size
+    fn from_sources(
+        worker_flag: Option<&str>,
+        blocking_flag: Option<&str>,
+        worker_env: Option<&str>,
+        blocking_env: Option<&str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_values(worker_flag.or(worker_env), blocking_flag.or(blocking_env))
+    }
+
+    /// Parse raw worker and blocking thread counts (`None` or blank = default)
+    pub fn from_values(
+        worker_threads: Option<&str>,
+        blocking_threads: Option<&str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            worker_threads: parse_thread_count(
+                "worker threads",
+                worker_threads,
+                MAX_WORKER_THREADS,
+            )?,
+            blocking_threads: parse_thread_count(
+                "blocking threads",
+                blocking_threads,
+                MAX_BLOCKING_THREADS,
+            )?,
+        })
+    }
+
+    /// Worker threads the runtime will start
+    pub fn worker_threads(&self) -> usize {
+        self.worker_threads.unwrap_or_else(default_worker_threads)
+    }
+
+    /// Maximum threads in the blocking pool
+    pub fn blocking_threads(&self) -> usize {
+        self.blocking_threads.unwrap_or(DEFAULT_BLOCKING_THREADS)
+    }
+
+    /// Build a multi-thread runtime with these sizes and all drivers enabled
+    pub fn build(&self) -> std::io::Result<tokio::runtime::Runtime> {
+        tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(self.worker_threads())
+            .max_blocking_threads(self.blocking_threads())
+            .enable_all()
+            .build()
+    }
+}
+
+/// One worker per available CPU core (1 if it can't be determined)
+pub fn default_worker_threads() -> usize {
+    std::thread::available_parallelism().map_or(1, |cores| cores.get())
+}
+
+fn parse_thread_count(
+    name: &str,
+    raw: Option<&str>,
+    max: usize,
+) -> Result<Option<usize>, Box<dyn std::error::Error>> {
+    match raw.map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(value) => match value.parse::<usize>() {
+            Ok(count) if (1..=max).contains(&count) => Ok(Some(count)),
+            _ => Err(format!(
+                "Invalid {} '{}': expected a number from 1 to {}",
+                name, value, max
+            )
+            .into()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thread_counts_parsed() {
+        let config = RuntimeConfig::from_values(Some(" 8 "), Some("1024")).unwrap();
+        assert_eq!(config.worker_threads(), 8);
+        assert_eq!(config.blocking_threads(), 1024);
+
+        let defaults = RuntimeConfig::from_values(None, Some("")).unwrap();
+        assert_eq!(defaults, RuntimeConfig::default());
+        assert_eq!(defaults.worker_threads(), default_worker_threads());
+        assert_eq!(defaults.blocking_threads(), DEFAULT_BLOCKING_THREADS);
+    }
+
+    #[test]
+    fn test_invalid_thread_counts_rejected() {
+        for raw in ["0", "-2", "four", "1025"] {
+            let err = RuntimeConfig::from_values(Some(raw), None)
+                .unwrap_err()
+                .to_string();
+            assert!(err.contains("Invalid worker threads"), "{}", err);
+            assert!(err.contains(raw), "{}", err);
+        }
+        assert!(RuntimeConfig::from_values(None, Some("0")).is_err());
+        assert!(RuntimeConfig::from_values(None, Some("8193")).is_err());
+    }
+
+    #[test]
+    fn test_flag_overrides_env() {
+        let config =
+            RuntimeConfig::from_sources(Some("3"), Some("16"), Some("8"), Some("64")).unwrap();
+        assert_eq!(config.worker_threads, Some(3));
+        assert_eq!(config.blocking_threads, Some(16));
+
+        // Each size falls back to its environment value on its own
+        let config = RuntimeConfig::from_sources(None, Some("16"), Some("8"), Some("64")).unwrap();
+        assert_eq!(config.worker_threads, Some(8));
+        assert_eq!(config.blocking_threads, Some(16));
+
+        // An invalid environment value is ignored when the flag is set
+        let config = RuntimeConfig::from_sources(Some("3"), None, Some("many"), None).unwrap();
+        assert_eq!(config, RuntimeConfig::from_values(Some("3"), None).unwrap());
+    }
+
+    #[test]
+    fn test_configured_runtime_runs_tasks() {
+        let runtime = RuntimeConfig::from_values(Some("2"), Some("4"))
+            .unwrap()
+            .build()
+            .unwrap();
+        let sum = runtime.block_on(async { tokio::task::spawn_blocking(|| 40 + 2).await.unwrap() });
+        assert_eq!(sum, 42);
+    }
+}
@@ -29,6 +29,18 @@ pub enum ProviderError {
     #[error("Validation error: {0}")]
     Validation(String),
 
+    #[error("Symbol not found: {0}")]
+    SymbolNotFound(String),
+
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    #[error("Upstream timeout: {0}")]
+    UpstreamTimeout(String),
+
+    #[error("Region blocked: {0}")]
+    RegionBlocked(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 
@@ -36,7 +48,59 @@ pub enum ProviderError {
     Initialization(String),
 
     #[error("MCP error: {0}")]
-    Mcp(#[from] McpError),
+    Mcp(#[source] McpError),
+}
+
+impl From<McpError> for ProviderError {
+    fn from(err: McpError) -> Self {
+        match err {
+            McpError::RegionBlocked(msg) => ProviderError::RegionBlocked(msg),
+            McpError::SymbolNotFound(msg) => ProviderError::SymbolNotFound(msg),
+            other => ProviderError::Mcp(other),
+        }
+    }
+}
+
+impl ProviderError {
+    /// Stable machine-readable error code for client retry logic
+    ///
+    /// | Code | Meaning |
+    /// |------|---------|
+    /// | `VALIDATION` | Bad tool arguments; fix the request |
+    /// | `SYMBOL_NOT_FOUND` | Symbol is not listed / unknown to Binance |
+    /// | `RATE_LIMITED` | Local or Binance rate limit hit; retry with backoff |
+    /// | `UPSTREAM_TIMEOUT` | Binance did not answer in time; retry |
+    /// | `UPSTREAM_UNAVAILABLE` | Connection failure or Binance 5xx; retry |
+    /// | `UPSTREAM_ERROR` | Other Binance API / data source failure |
+    /// | `REGION_BLOCKED` | Binance refuses this server's location (HTTP 451); do not retry |
+    /// | `NOT_FOUND` | Unknown tool, resource or prompt |
+    /// | `AUTH_REQUIRED` | Missing API credentials |
+    /// | `NOT_READY` | Server still initializing |
+    /// | `INTERNAL` | Anything else |
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            ProviderError::Validation(_) | ProviderError::InvalidUri(_) => "VALIDATION",
+            ProviderError::SymbolNotFound(_) => "SYMBOL_NOT_FOUND",
+            ProviderError::RateLimited(_) => "RATE_LIMITED",
+            ProviderError::UpstreamTimeout(_) => "UPSTREAM_TIMEOUT",
+            ProviderError::RegionBlocked(_) => "REGION_BLOCKED",
+            ProviderError::BinanceApi(_) => "UPSTREAM_ERROR",
+            ProviderError::ToolNotFound(_)
+            | ProviderError::ResourceNotFound(_)
+            | ProviderError::PromptNotFound(_) => "NOT_FOUND",
+            ProviderError::AuthRequired(_) => "AUTH_REQUIRED",
+            ProviderError::Initialization(_) => "NOT_READY",
+            ProviderError::Mcp(err) => err.error_code(),
+            ProviderError::Grpc(_) | ProviderError::Json(_) | ProviderError::Internal(_) => {
+                "INTERNAL"
+            }
+        }
+    }
+
+    /// Error string for `InvokeResponse.error`: `"{CODE}: {message}"`
+    pub fn to_invoke_error(&self) -> String {
+        format!("{}: {}", self.error_code(), self)
+    }
 }
 
 /// Main error type for MCP Binance Server (from mcp-binance-rs)
@@ -59,13 +123,38 @@ pub enum McpError {
 
     #[error("Internal error: {0}")]
     InternalError(String),
+
+    #[error("Request timeout: {0}")]
+    Timeout(String),
+
+    #[error("Invalid symbol: {0}")]
+    SymbolNotFound(String),
+
+    #[error("Authentication required: {0}")]
+    AuthRequired(String),
+
+    #[error("Region blocked: {0}")]
+    RegionBlocked(String),
 }
 
+/// Error body returned by Binance REST endpoints (e.g. `{"code":-1121,"msg":"Invalid symbol."}`)
+#[derive(Debug, serde::Deserialize)]
+struct BinanceErrorBody {
+    code: i64,
+    msg: String,
+}
+
+/// Binance API error code for an unknown symbol
+const BINANCE_INVALID_SYMBOL: i64 = -1121;
+
+/// Binance API error code for too many requests
+const BINANCE_TOO_MANY_REQUESTS: i64 = -1003;
+
 impl McpError {
     pub fn is_retryable(&self) -> bool {
         matches!(
             self,
-            McpError::ConnectionError(_) | McpError::RateLimitError(_)
+            McpError::ConnectionError(_) | McpError::RateLimitError(_) | McpError::Timeout(_)
         )
     }
 
@@ -77,16 +166,68 @@ impl McpError {
             McpError::InvalidRequest(_) => "invalid_request",
             McpError::NotReady(_) => "not_ready",
             McpError::InternalError(_) => "internal_error",
+            McpError::Timeout(_) => "timeout",
+            McpError::SymbolNotFound(_) => "symbol_not_found",
+            McpError::AuthRequired(_) => "auth_required",
+            McpError::RegionBlocked(_) => "region_blocked",
+        }
+    }
+
+    /// Stable machine-readable error code (see [`ProviderError::error_code`])
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            McpError::ConnectionError(_) => "UPSTREAM_UNAVAILABLE",
+            McpError::RateLimitError(_) => "RATE_LIMITED",
+            McpError::ParseError(_) => "UPSTREAM_ERROR",
+            McpError::InvalidRequest(_) => "VALIDATION",
+            McpError::NotReady(_) => "NOT_READY",
+            McpError::InternalError(_) => "INTERNAL",
+            McpError::Timeout(_) => "UPSTREAM_TIMEOUT",
+            McpError::SymbolNotFound(_) => "SYMBOL_NOT_FOUND",
+            McpError::AuthRequired(_) => "AUTH_REQUIRED",
+            McpError::RegionBlocked(_) => "REGION_BLOCKED",
+        }
+    }
+
+    /// Convert a non-success Binance response, keeping the API error code when present
+    pub async fn from_response(response: reqwest::Response) -> Self {
+        let http_error = match response.error_for_status_ref() {
+            Err(err) => err,
+            Ok(_) => {
+                return McpError::InternalError(format!(
+                    "Unexpected response status: {}",
+                    response.status()
+                ))
+            }
+        };
+
+        let body = response.text().await.unwrap_or_default();
+        if let Ok(api_error) = serde_json::from_str::<BinanceErrorBody>(&body) {
+            match api_error.code {
+                BINANCE_INVALID_SYMBOL => return McpError::SymbolNotFound(api_error.msg),
+                BINANCE_TOO_MANY_REQUESTS => return McpError::RateLimitError(api_error.msg),
+                // Rejected API key or signature on a signed endpoint
+                code if http_error.status().is_some_and(|s| s.as_u16() == 401) => {
+                    return McpError::AuthRequired(format!("{} (code {})", api_error.msg, code))
+                }
+                _ if http_error.status().is_some_and(|s| s.as_u16() == 451) => {
+                    return McpError::RegionBlocked(api_error.msg)
+                }
+                code if http_error.status().is_some_and(|s| s.as_u16() == 400) => {
+                    return McpError::InvalidRequest(format!("{} (code {})", api_error.msg, code))
+                }
+                _ => {}
+            }
         }
+
+        McpError::from(http_error)
     }
 }
 
 impl From<reqwest::Error> for McpError {
     fn from(err: reqwest::Error) -> Self {
         if err.is_timeout() {
-            McpError::ConnectionError(
-                "Request timeout. Please check your internet connection.".to_string(),
-            )
+            McpError::Timeout("Request timeout. Please check your internet connection.".to_string())
         } else if err.is_connect() {
             McpError::ConnectionError(
                 "Failed to connect to Binance API. Please check your internet connection."
@@ -103,6 +244,10 @@ impl From<reqwest::Error> for McpError {
                 403 => McpError::ConnectionError(
                     "WAF limit violated. Please reduce request frequency.".to_string(),
                 ),
+                451 => McpError::RegionBlocked(
+                    "Binance API is unavailable from this server's location (HTTP 451)."
+                        .to_string(),
+                ),
                 500..=599 => McpError::ConnectionError(format!(
                     "Binance server error (HTTP {}). Please try again later.",
                     status.as_u16()
@@ -131,6 +276,11 @@ impl From<ProviderError> for tonic::Status {
             ProviderError::PromptNotFound(msg) => tonic::Status::not_found(msg),
             ProviderError::AuthRequired(msg) => tonic::Status::unauthenticated(msg),
             ProviderError::Validation(msg) => tonic::Status::invalid_argument(msg),
+            ProviderError::InvalidUri(msg) => tonic::Status::invalid_argument(msg),
+            ProviderError::SymbolNotFound(msg) => tonic::Status::not_found(msg),
+            ProviderError::RateLimited(msg) => tonic::Status::resource_exhausted(msg),
+            ProviderError::UpstreamTimeout(msg) => tonic::Status::deadline_exceeded(msg),
+            ProviderError::RegionBlocked(msg) => tonic::Status::permission_denied(msg),
             _ => tonic::Status::internal(err.to_string()),
         }
     }
@@ -143,15 +293,20 @@ impl McpError {
     pub fn to_error_data(&self) -> rmcp::model::ErrorData {
         use rmcp::model::{ErrorCode, ErrorData};
 
+        let data = Some(serde_json::json!({ "error_code": self.error_code() }));
         match self {
-            McpError::RateLimitError(msg) => ErrorData::new(ErrorCode(429), msg.clone(), None),
-            McpError::InvalidRequest(msg) => ErrorData::new(ErrorCode(400), msg.clone(), None),
+            McpError::RateLimitError(msg) => ErrorData::new(ErrorCode(429), msg.clone(), data),
+            McpError::InvalidRequest(msg) => ErrorData::new(ErrorCode(400), msg.clone(), data),
             McpError::ParseError(msg) => {
-                ErrorData::new(ErrorCode(400), format!("Parse error: {}", msg), None)
+                ErrorData::new(ErrorCode(400), format!("Parse error: {}", msg), data)
             }
-            McpError::ConnectionError(msg) => ErrorData::new(ErrorCode(503), msg.clone(), None),
-            McpError::NotReady(msg) => ErrorData::new(ErrorCode(503), msg.clone(), None),
-            McpError::InternalError(msg) => ErrorData::new(ErrorCode(500), msg.clone(), None),
+            McpError::ConnectionError(msg) => ErrorData::new(ErrorCode(503), msg.clone(), data),
+            McpError::NotReady(msg) => ErrorData::new(ErrorCode(503), msg.clone(), data),
+            McpError::InternalError(msg) => ErrorData::new(ErrorCode(500), msg.clone(), data),
+            McpError::Timeout(msg) => ErrorData::new(ErrorCode(504), msg.clone(), data),
+            McpError::SymbolNotFound(msg) => ErrorData::new(ErrorCode(404), msg.clone(), data),
+            McpError::AuthRequired(msg) => ErrorData::new(ErrorCode(401), msg.clone(), data),
+            McpError::RegionBlocked(msg) => ErrorData::new(ErrorCode(451), msg.clone(), data),
         }
     }
 }
@@ -162,3 +317,82 @@ impl From<McpError> for rmcp::model::ErrorData {
         err.to_error_data()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_error_codes() {
+        let cases = [
+            (ProviderError::Validation("x".into()), "VALIDATION"),
+            (ProviderError::InvalidUri("x".into()), "VALIDATION"),
+            (
+                ProviderError::SymbolNotFound("x".into()),
+                "SYMBOL_NOT_FOUND",
+            ),
+            (ProviderError::RateLimited("x".into()), "RATE_LIMITED"),
+            (
+                ProviderError::UpstreamTimeout("x".into()),
+                "UPSTREAM_TIMEOUT",
+            ),
+            (ProviderError::RegionBlocked("x".into()), "REGION_BLOCKED"),
+            (ProviderError::BinanceApi("x".into()), "UPSTREAM_ERROR"),
+            (ProviderError::ToolNotFound("x".into()), "NOT_FOUND"),
+            (ProviderError::ResourceNotFound("x".into()), "NOT_FOUND"),
+            (ProviderError::PromptNotFound("x".into()), "NOT_FOUND"),
+            (ProviderError::AuthRequired("x".into()), "AUTH_REQUIRED"),
+            (ProviderError::Initialization("x".into()), "NOT_READY"),
+            (ProviderError::Internal("x".into()), "INTERNAL"),
+            (
+                ProviderError::Grpc(tonic::Status::internal("x")),
+                "INTERNAL",
+            ),
+        ];
+        for (err, code) in cases {
+            assert_eq!(err.error_code(), code, "{:?}", err);
+        }
+    }
+
+    #[test]
+    fn test_mcp_error_codes_pass_through() {
+        let cases = [
+            (
+                McpError::ConnectionError("x".into()),
+                "UPSTREAM_UNAVAILABLE",
+            ),
+            (McpError::RateLimitError("x".into()), "RATE_LIMITED"),
+            (McpError::ParseError("x".into()), "UPSTREAM_ERROR"),
+            (McpError::InvalidRequest("x".into()), "VALIDATION"),
+            (McpError::NotReady("x".into()), "NOT_READY"),
+            (McpError::InternalError("x".into()), "INTERNAL"),
+            (McpError::Timeout("x".into()), "UPSTREAM_TIMEOUT"),
+            (McpError::SymbolNotFound("x".into()), "SYMBOL_NOT_FOUND"),
+            (McpError::AuthRequired("x".into()), "AUTH_REQUIRED"),
+            (McpError::RegionBlocked("x".into()), "REGION_BLOCKED"),
+        ];
+        for (err, code) in cases {
+            assert_eq!(err.error_code(), code);
+            assert_eq!(ProviderError::from(err).error_code(), code);
+        }
+    }
+
+    #[test]
+    fn test_region_block_maps_to_provider_variant() {
+        let err = ProviderError::from(McpError::RegionBlocked("restricted".into()));
+        assert!(matches!(err, ProviderError::RegionBlocked(_)), "{:?}", err);
+        assert_eq!(
+            tonic::Status::from(err).code(),
+            tonic::Code::PermissionDenied
+        );
+    }
+
+    #[test]
+    fn test_invoke_error_prefix() {
+        let err = ProviderError::RateLimited("slow down".into());
+        assert_eq!(
+            err.to_invoke_error(),
+            "RATE_LIMITED: Rate limited: slow down"
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::error::Result;
+use crate::pagination::Page;
 use crate::pb::*;
 
 /// Builder for constructing the Capabilities response
@@ -20,6 +21,19 @@ impl CapabilityBuilder {
         // Add ONLY the unified market data report tool (per FR-002)
         builder.add_unified_report_tool();
 
+        // Add orderbook tools served from the cached WebSocket book
+        #[cfg(feature = "orderbook")]
+        builder.add_orderbook_tools();
+
+        // Add raw market data passthrough tools
+        builder.add_passthrough_tools();
+
+        // Add signed account tools (fail with AUTH_REQUIRED without credentials)
+        builder.add_account_tools();
+
+        // Add the server health tool (available on every transport)
+        builder.add_health_tool();
+
         // Add resources
         builder.add_resources();
 
@@ -46,6 +60,13 @@ impl CapabilityBuilder {
         })
     }
 
+    // Helper to create the output schema of a tool's response type
+    fn output_schema<T: schemars::JsonSchema>() -> Option<Json> {
+        serde_json::to_vec(&schemars::schema_for!(T))
+            .ok()
+            .map(|value| Json { value })
+    }
+
     // ========== Unified Market Data Report Tool (THE ONLY PUBLIC TOOL) ==========
     // Per FR-002: All market data methods consolidated into single unified method
 
@@ -70,8 +91,13 @@ impl CapabilityBuilder {
       "properties": {
         "include_sections": {
           "type": "array",
-          "description": "Section names to include (omit for all sections)",
-          "items": {"type": "string"}
+          "description": "Section names to include (omit for all sections; opt-in recent_trades must be listed)",
+          "items": {"type": "string", "enum": ["price_overview", "orderbook_metrics", "liquidity_analysis", "market_microstructure", "market_anomalies", "microstructure_health", "derivatives_sentiment", "recent_trades", "data_health"]}
+        },
+        "preset": {
+          "type": "string",
+          "enum": ["full", "quick"],
+          "description": "Predefined sections instead of include_sections: full (all default sections) or quick (header, price overview and order book metrics, with no kline, analytics or futures requests)"
         },
         "volume_window_hours": {
           "type": "integer",
@@ -82,20 +108,82 @@ impl CapabilityBuilder {
         },
         "orderbook_levels": {
           "type": "integer",
-          "description": "Number of orderbook levels for depth analysis",
+          "description": "Orderbook levels per side for volume, imbalance and microprice",
           "minimum": 1,
           "maximum": 100,
           "default": 20
+        },
+        "analytics_timeout_ms": {
+          "type": "integer",
+          "description": "Per-call timeout (ms) for analytics-backed sections; a section exceeding it renders as unavailable",
+          "minimum": 100,
+          "maximum": 10000,
+          "default": 3000
+        },
+        "max_walls_per_side": {
+          "type": "integer",
+          "description": "Largest liquidity walls listed per side",
+          "minimum": 1,
+          "maximum": 20,
+          "default": 5
+        },
+        "recent_trades_count": {
+          "type": "integer",
+          "description": "Aggregated trades listed in the opt-in recent_trades section",
+          "minimum": 1,
+          "maximum": 100,
+          "default": 20
+        },
+        "max_total_ms": {
+          "type": "integer",
+          "description": "Overall generation deadline (ms); when it passes, the finished sections are returned with partial set and the unfinished ones listed in failed_sections. Omit for no deadline",
+          "minimum": 100,
+          "maximum": 60000
+        },
+        "emoji": {
+          "type": "boolean",
+          "description": "Render emoji indicators; false emits plain ASCII markers such as [FRESH] or [BUY PRESSURE]",
+          "default": true
+        },
+        "freshness_thresholds": {
+          "type": "object",
+          "description": "Data age boundaries (ms) for the fresh/recent/aging/stale indicators in the header and data health section; must be strictly increasing",
+          "properties": {
+            "fresh_ms": {"type": "integer", "minimum": 1, "maximum": 3600000, "default": 1000},
+            "recent_ms": {"type": "integer", "minimum": 1, "maximum": 3600000, "default": 5000},
+            "stale_ms": {"type": "integer", "minimum": 1, "maximum": 3600000, "default": 30000}
+          },
+          "additionalProperties": false
+        },
+        "footer_attribution": {
+          "type": "string",
+          "description": "Attribution line closing the footer (replaces the ForgeTrade credit for white-label deployments); empty omits it",
+          "maxLength": 200
+        },
+        "show_notional": {
+          "type": "boolean",
+          "description": "Follow base-asset sizes in wall tables and slippage estimates with their approximate quote notional at the mid price, e.g. 1.5 BTC (~$97 500)",
+          "default": true
+        },
+        "show_build_info": {
+          "type": "boolean",
+          "description": "Include the build configuration (enabled features) block in the footer",
+          "default": true
         }
       },
       "additionalProperties": false
+    },
+    "no_cache": {
+      "type": "boolean",
+      "description": "Bypass the report cache (no read, no write) and force a fresh snapshot",
+      "default": false
     }
   },
   "required": ["symbol"],
   "additionalProperties": false
 }"#,
             ),
-            output_schema: None,
+            output_schema: Self::output_schema::<crate::report::MarketReport>(),
         });
     }
 
@@ -289,7 +377,12 @@ impl CapabilityBuilder {
         "include_sections": {
           "type": "array",
           "description": "Section names to include (omit for all sections)",
-          "items": {"type": "string"}
+          "items": {"type": "string", "enum": ["price_overview", "orderbook_metrics", "liquidity_analysis", "market_microstructure", "market_anomalies", "microstructure_health", "derivatives_sentiment", "recent_trades", "data_health"]}
+        },
+        "preset": {
+          "type": "string",
+          "enum": ["full", "quick"],
+          "description": "Predefined sections instead of include_sections: full (all default sections) or quick (header, price overview and order book metrics, with no kline, analytics or futures requests)"
         },
         "volume_window_hours": {
           "type": "integer",
@@ -300,7 +393,7 @@ impl CapabilityBuilder {
         },
         "orderbook_levels": {
           "type": "integer",
-          "description": "Number of orderbook levels for depth analysis",
+          "description": "Orderbook levels per side for volume, imbalance and microprice",
           "minimum": 1,
           "maximum": 100,
           "default": 20
@@ -321,10 +414,14 @@ impl CapabilityBuilder {
 
     #[cfg(feature = "orderbook")]
     fn add_orderbook_tools(&mut self) {
+        use crate::orderbook::types::{
+            OrderBookIntegrity, SlippageCurve, SlippageEstimate, SubscriptionStatus,
+        };
+
         let orderbook_tools = vec![
             (
-                "binance.orderbook_l1",
-                "Get Level 1 orderbook metrics (best bid/ask, spread)",
+                "binance.get_orderbook_integrity",
+                "Get depth update sequence integrity for a tracked symbol: last update id, gaps detected, resyncs performed and whether the book is crossed",
                 r#"{
   "$schema": "http://json-schema.org/draft-07/schema#",
   "type": "object",
@@ -334,6 +431,102 @@ impl CapabilityBuilder {
   "required": ["symbol"],
   "additionalProperties": false
 }"#,
+                Self::output_schema::<OrderBookIntegrity>(),
+            ),
+            (
+                "binance.get_slippage_estimate",
+                "Estimate average fill price and slippage (bps) for a market order of a given USD size, walking the live orderbook",
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z0-9]{6,12}$"},
+    "side": {"type": "string", "enum": ["buy", "sell"]},
+    "notional_usd": {"type": "number", "exclusiveMinimum": 0}
+  },
+  "required": ["symbol", "side", "notional_usd"],
+  "additionalProperties": false
+}"#,
+                Self::output_schema::<SlippageEstimate>(),
+            ),
+            (
+                "binance.get_slippage_curve",
+                "Estimate slippage (bps) at a set of USD order sizes for both market buys and sells, showing where orderbook liquidity thins out",
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z0-9]{6,12}$"},
+    "sizes_usd": {"type": "array", "items": {"type": "number", "exclusiveMinimum": 0}, "minItems": 1, "maxItems": 20, "default": [1000, 10000, 100000, 1000000], "description": "Order sizes in USD; sizes beyond the visible depth report a partial fill"}
+  },
+  "required": ["symbol"],
+  "additionalProperties": false
+}"#,
+                Self::output_schema::<SlippageCurve>(),
+            ),
+            (
+                "binance.subscribe_symbol",
+                "Start tracking a symbol's orderbook now (REST snapshot + depth WebSocket); returns the active symbol count against the 20-symbol limit",
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z0-9]{6,12}$"}
+  },
+  "required": ["symbol"],
+  "additionalProperties": false
+}"#,
+                Self::output_schema::<SubscriptionStatus>(),
+            ),
+            (
+                "binance.unsubscribe_symbol",
+                "Stop tracking a symbol's orderbook, closing its depth WebSocket and freeing a slot",
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z0-9]{6,12}$"}
+  },
+  "required": ["symbol"],
+  "additionalProperties": false
+}"#,
+                Self::output_schema::<SubscriptionStatus>(),
+            ),
+        ];
+
+        for (name, desc, schema, output_schema) in orderbook_tools {
+            self.tools.push(Tool {
+                name: name.to_string(),
+                description: desc.to_string(),
+                input_schema: Self::json_schema(schema),
+                output_schema,
+            });
+        }
+    }
+
+    // Individual L1/L2/health tools stay unregistered: the unified report
+    // covers them (per FR-002)
+    #[cfg(feature = "orderbook")]
+    #[allow(dead_code)]
+    fn add_orderbook_metric_tools(&mut self) {
+        use crate::orderbook::types::{OrderBookDepth, OrderBookHealth, OrderBookMetrics};
+
+        let metric_tools = vec![
+            (
+                "binance.orderbook_l1",
+                "Get Level 1 orderbook metrics (best bid/ask, spread, microprice, imbalance)",
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z0-9]{6,12}$"},
+    "depth_levels": {"type": "integer", "minimum": 1, "maximum": 100, "default": 20, "description": "Levels per side used for volume, imbalance ratio and microprice"},
+    "wall_median_multiplier": {"type": "number", "minimum": 1.0, "maximum": 100.0, "default": 2.0, "description": "Wall threshold as a multiple of the median level quantity"}
+  },
+  "required": ["symbol"],
+  "additionalProperties": false
+}"#,
+                Self::output_schema::<OrderBookMetrics>(),
             ),
             (
                 "binance.orderbook_l2",
@@ -343,11 +536,13 @@ impl CapabilityBuilder {
   "type": "object",
   "properties": {
     "symbol": {"type": "string", "pattern": "^[A-Z0-9]{6,12}$"},
-    "depth_levels": {"type": "integer", "minimum": 5, "maximum": 100, "default": 20}
+    "levels": {"type": "integer", "minimum": 1, "maximum": 100, "default": 20},
+    "bucket_bps": {"type": "number", "exclusiveMinimum": 0, "maximum": 1000, "description": "Group the returned levels into price buckets this many basis points wide, summing quantities (omit for raw levels)"}
   },
   "required": ["symbol"],
   "additionalProperties": false
 }"#,
+                Self::output_schema::<OrderBookDepth>(),
             ),
             (
                 "binance.orderbook_health",
@@ -361,15 +556,16 @@ impl CapabilityBuilder {
   "required": ["symbol"],
   "additionalProperties": false
 }"#,
+                Self::output_schema::<OrderBookHealth>(),
             ),
         ];
 
-        for (name, desc, schema) in orderbook_tools {
+        for (name, desc, schema, output_schema) in metric_tools {
             self.tools.push(Tool {
                 name: name.to_string(),
                 description: desc.to_string(),
                 input_schema: Self::json_schema(schema),
-                output_schema: None,
+                output_schema,
             });
         }
     }
@@ -378,6 +574,8 @@ impl CapabilityBuilder {
 
     #[cfg(feature = "orderbook_analytics")]
     fn add_analytics_tools(&mut self) {
+        use crate::orderbook::analytics::{tools, types};
+
         let analytics_tools = vec![
             (
                 "binance.get_order_flow",
@@ -387,11 +585,13 @@ impl CapabilityBuilder {
   "type": "object",
   "properties": {
     "symbol": {"type": "string", "pattern": "^[A-Z]+$", "description": "Trading pair (e.g., BTCUSDT)"},
-    "window_duration_secs": {"type": "integer", "minimum": 10, "maximum": 300, "default": 60, "description": "Analysis window in seconds"}
+    "window_duration_secs": {"type": "integer", "minimum": 10, "maximum": 300, "default": 60, "description": "Analysis window in seconds"},
+    "delta_half_life_secs": {"type": "number", "exclusiveMinimum": 0, "description": "Half-life in seconds for an exponentially-weighted cumulative delta (omit for the unweighted sum)"}
   },
   "required": ["symbol"],
   "additionalProperties": false
 }"#,
+                Self::output_schema::<types::OrderFlowSnapshot>(),
             ),
             (
                 "binance.get_volume_profile",
@@ -402,11 +602,16 @@ impl CapabilityBuilder {
   "properties": {
     "symbol": {"type": "string", "pattern": "^[A-Z]+$", "description": "Trading pair"},
     "duration_hours": {"type": "integer", "minimum": 1, "maximum": 168, "default": 24, "description": "Time period in hours"},
-    "tick_size": {"type": "number", "description": "Optional bin size"}
+    "tick_size": {"type": "number", "description": "Optional bin size"},
+    "value_area_pct": {"type": "number", "minimum": 0.5, "maximum": 0.95, "default": 0.7, "description": "Share of volume inside the value area (VAH/VAL)"},
+    "max_bins": {"type": "integer", "minimum": 1, "description": "Optional cap on histogram bins; adjacent bins are merged to fit"},
+    "poc_basis": {"type": "string", "enum": ["volume", "trade_count"], "default": "volume", "description": "Pick the POC by traded volume or by trade count (TPO-style)"},
+    "min_trades": {"type": "integer", "minimum": 50, "default": 1000, "description": "Trades required to build the profile; below 1000 the profile is flagged low_confidence"}
   },
   "required": ["symbol"],
   "additionalProperties": false
 }"#,
+                Self::output_schema::<types::VolumeProfile>(),
             ),
             (
                 "binance.detect_market_anomalies",
@@ -415,11 +620,15 @@ impl CapabilityBuilder {
   "$schema": "http://json-schema.org/draft-07/schema#",
   "type": "object",
   "properties": {
-    "symbol": {"type": "string", "pattern": "^[A-Z]+$", "description": "Trading pair"}
+    "symbol": {"type": "string", "pattern": "^[A-Z]+$", "description": "Trading pair"},
+    "window_secs": {"type": "integer", "minimum": 10, "maximum": 300, "default": 60, "description": "Detection window in seconds"},
+    "quote_stuffing_update_rate": {"type": "number", "exclusiveMinimum": 0, "default": 500, "description": "Flag quote stuffing above this many updates/sec"},
+    "quote_stuffing_fill_rate": {"type": "number", "minimum": 0, "maximum": 1, "default": 0.1, "description": "Flag only when the estimated fill rate (0-1) is below this"}
   },
   "required": ["symbol"],
   "additionalProperties": false
 }"#,
+                Self::output_schema::<Page<types::MarketMicrostructureAnomaly>>(),
             ),
             (
                 "binance.get_microstructure_health",
@@ -433,6 +642,7 @@ impl CapabilityBuilder {
   "required": ["symbol"],
   "additionalProperties": false
 }"#,
+                Self::output_schema::<types::MicrostructureHealth>(),
             ),
             (
                 "binance.get_liquidity_vacuums",
@@ -447,27 +657,256 @@ impl CapabilityBuilder {
   "required": ["symbol"],
   "additionalProperties": false
 }"#,
+                Self::output_schema::<Page<types::LiquidityVacuum>>(),
+            ),
+            (
+                "binance.get_execution_benchmarks",
+                "Compute VWAP and TWAP execution benchmarks from stored trades over a trailing window",
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z]+$", "description": "Trading pair"},
+    "window_secs": {"type": "integer", "minimum": 60, "maximum": 86400, "default": 3600, "description": "Trailing window in seconds"}
+  },
+  "required": ["symbol"],
+  "additionalProperties": false
+}"#,
+                Self::output_schema::<tools::ExecutionBenchmarks>(),
+            ),
+            (
+                "binance.get_imbalance_history",
+                "Get bid/ask volume imbalance bucketed over time from stored order book snapshots",
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z]+$", "description": "Trading pair"},
+    "window_secs": {"type": "integer", "minimum": 60, "maximum": 86400, "default": 3600, "description": "Trailing window in seconds"},
+    "bucket_secs": {"type": "integer", "minimum": 1, "maximum": 86400, "default": 60, "description": "Bucket width in seconds (at most window_secs, at most 1440 buckets)"}
+  },
+  "required": ["symbol"],
+  "additionalProperties": false
+}"#,
+                Self::output_schema::<tools::ImbalanceHistory>(),
+            ),
+            (
+                "binance.get_health_trend",
+                "Get the microstructure health score bucketed over time from stored order book snapshots, with a trend direction (improving/stable/degrading)",
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z]+$", "description": "Trading pair"},
+    "window_secs": {"type": "integer", "minimum": 60, "maximum": 86400, "default": 3600, "description": "Trailing window in seconds"},
+    "bucket_secs": {"type": "integer", "minimum": 1, "maximum": 86400, "default": 300, "description": "Bucket width in seconds (at most window_secs, at most 1440 buckets)"}
+  },
+  "required": ["symbol"],
+  "additionalProperties": false
+}"#,
+                Self::output_schema::<tools::HealthTrend>(),
+            ),
+            (
+                "binance.get_spread_stats",
+                "Get spread mean, standard deviation and range (bps) over a trailing window of stored order book snapshots",
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z]+$", "description": "Trading pair"},
+    "window_secs": {"type": "integer", "minimum": 60, "maximum": 86400, "default": 3600, "description": "Trailing window in seconds"}
+  },
+  "required": ["symbol"],
+  "additionalProperties": false
+}"#,
+                Self::output_schema::<tools::SpreadStatsReport>(),
+            ),
+            (
+                "binance.get_depth_heatmap",
+                "Get cumulative resting order book liquidity as a price bucket x time bucket grid from stored snapshots, for heatmap visualization",
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z]+$", "description": "Trading pair"},
+    "window_secs": {"type": "integer", "minimum": 60, "maximum": 86400, "default": 3600, "description": "Trailing window in seconds"},
+    "price_bucket_bps": {"type": "integer", "minimum": 1, "maximum": 1000, "default": 5, "description": "Price bucket width in basis points of the current mid price (at most 500 price buckets)"},
+    "time_bucket_secs": {"type": "integer", "minimum": 1, "maximum": 86400, "default": 60, "description": "Time bucket width in seconds (at most window_secs, at most 1440 buckets)"}
+  },
+  "required": ["symbol"],
+  "additionalProperties": false
+}"#,
+                Self::output_schema::<tools::DepthHeatmap>(),
             ),
         ];
 
-        for (name, desc, schema) in analytics_tools {
+        for (name, desc, schema, output_schema) in analytics_tools {
             self.tools.push(Tool {
                 name: name.to_string(),
                 description: desc.to_string(),
                 input_schema: Self::json_schema(schema),
-                output_schema: None,
+                output_schema,
             });
         }
     }
 
+    // ========== Market Data Passthrough ==========
+
+    fn add_passthrough_tools(&mut self) {
+        self.tools.push(Tool {
+            name: "binance.get_agg_trades".to_string(),
+            description: "Get compressed/aggregate trades from Binance (GET /api/v3/aggTrades), by trade ID or time range".to_string(),
+            input_schema: Self::json_schema(
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z0-9]{6,12}$", "description": "Trading pair (e.g., BTCUSDT)"},
+    "limit": {"type": "integer", "minimum": 1, "maximum": 1000, "default": 500, "description": "Number of trades to return"},
+    "from_id": {"type": "integer", "minimum": 0, "description": "Aggregate trade ID to start from (inclusive); not combinable with start_time/end_time"},
+    "cursor": {"type": "string", "description": "next_cursor of the previous page; continues after its last trade (not combinable with from_id or start_time/end_time)"},
+    "start_time": {"type": "integer", "minimum": 0, "description": "Range start (ms since epoch, inclusive)"},
+    "end_time": {"type": "integer", "minimum": 0, "description": "Range end (ms since epoch, inclusive); at most 1 hour after start_time"}
+  },
+  "required": ["symbol"],
+  "not": {
+    "anyOf": [
+      {"required": ["from_id", "start_time"]},
+      {"required": ["from_id", "end_time"]},
+      {"required": ["from_id", "cursor"]},
+      {"required": ["cursor", "start_time"]},
+      {"required": ["cursor", "end_time"]}
+    ]
+  },
+  "additionalProperties": false
+}"#,
+            ),
+            output_schema: Self::output_schema::<Page<crate::binance::types::AggTradeResponse>>(),
+        });
+
+        self.tools.push(Tool {
+            name: "binance.get_book_ticker".to_string(),
+            description: "Get best bid/ask price and quantity (GET /api/v3/ticker/bookTicker); lighter than a full order book subscription".to_string(),
+            input_schema: Self::json_schema(
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z0-9]{6,12}$", "description": "Trading pair (e.g., BTCUSDT)"}
+  },
+  "required": ["symbol"],
+  "additionalProperties": false
+}"#,
+            ),
+            output_schema: Self::output_schema::<crate::binance::types::BookTicker>(),
+        });
+
+        self.tools.push(Tool {
+            name: "binance.get_price_change".to_string(),
+            description: "Get price change over a trailing window (1h, 4h, 12h, 1d or 7d) from klines: absolute and percentage change plus the window's high and low".to_string(),
+            input_schema: Self::json_schema(
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {"type": "string", "pattern": "^[A-Z0-9]{6,12}$", "description": "Trading pair (e.g., BTCUSDT)"},
+    "window": {"type": "string", "enum": ["1h", "4h", "12h", "1d", "7d"], "description": "Trailing window ending now"}
+  },
+  "required": ["symbol", "window"],
+  "additionalProperties": false
+}"#,
+            ),
+            output_schema: Self::output_schema::<crate::price_change::PriceChange>(),
+        });
+
+        self.tools.push(Tool {
+            name: "binance.scan_market".to_string(),
+            description: "Scan every symbol's 24hr ticker for one quote asset and return the top volume leaders, gainers, losers or movers above a minimum quote volume".to_string(),
+            input_schema: Self::json_schema(
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "quote": {"type": "string", "pattern": "^[A-Za-z0-9]{2,10}$", "default": "USDT", "description": "Quote asset to scan (e.g., USDT, BTC)"},
+    "min_volume": {"type": "number", "minimum": 0, "default": 0, "description": "Minimum 24h volume in the quote asset"},
+    "top_n": {"type": "integer", "minimum": 1, "maximum": 100, "default": 10, "description": "Number of symbols to return"},
+    "sort_by": {"type": "string", "enum": ["volume", "gainers", "losers", "movers"], "default": "volume", "description": "Ranking: quote volume, largest gain, largest loss, or largest absolute change"},
+    "cursor": {"type": "string", "description": "next_cursor of the previous page; returns the next top_n symbols"}
+  },
+  "additionalProperties": false
+}"#,
+            ),
+            output_schema: Self::output_schema::<crate::market_scan::MarketScan>(),
+        });
+    }
+
+    // ========== Account (Signed) ==========
+
+    fn add_account_tools(&mut self) {
+        self.tools.push(Tool {
+            name: "binance.get_account".to_string(),
+            description: "Get spot account balances (GET /api/v3/account, signed); requires BINANCE_API_KEY and BINANCE_API_SECRET, otherwise fails with AUTH_REQUIRED".to_string(),
+            input_schema: Self::json_schema(
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "omit_zero_balances": {"type": "boolean", "default": true, "description": "Leave out assets with zero free and locked balance"}
+  },
+  "additionalProperties": false
+}"#,
+            ),
+            output_schema: Self::output_schema::<crate::binance::types::AccountInfo>(),
+        });
+    }
+
+    // ========== Server Health ==========
+
+    fn add_health_tool(&mut self) {
+        self.tools.push(Tool {
+            name: "binance.health".to_string(),
+            description: "Get overall server health: orderbook status, storage reachability, WebSocket connectivity and uptime".to_string(),
+            input_schema: Self::json_schema(
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {},
+  "additionalProperties": false
+}"#,
+            ),
+            output_schema: Self::output_schema::<crate::health::ServerHealth>(),
+        });
+
+        self.tools.push(Tool {
+            name: "binance.self_test".to_string(),
+            description: "Smoke-test REST, WebSocket, storage and report generation end-to-end for a canary symbol, with per-component pass/fail and timings".to_string(),
+            input_schema: Self::json_schema(
+                r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "type": "object",
+  "properties": {
+    "symbol": {
+      "type": "string",
+      "description": "Canary trading pair symbol (e.g., BTCUSDT)"
+    }
+  },
+  "required": ["symbol"],
+  "additionalProperties": false
+}"#,
+            ),
+            output_schema: Self::output_schema::<crate::self_test::SelfTestReport>(),
+        });
+    }
+
     // ========== Resources ==========
 
     fn add_resources(&mut self) {
         self.resources.push(Resource {
             uri_scheme: "binance".to_string(),
-            description:
-                "Binance cryptocurrency trading resources (market data, balances, trades, orders)"
-                    .to_string(),
+            description: "Binance market data: binance://market/{symbol} and \
+                binance://report/{symbol} (markdown), binance://ticker/{symbol} and \
+                binance://orderbook/{symbol} (JSON)"
+                .to_string(),
             mime_type: "text/markdown".to_string(),
         });
     }
@@ -475,23 +914,25 @@ impl CapabilityBuilder {
     // ========== Prompts ==========
 
     fn add_prompts(&mut self) {
-        self.prompts.push(Prompt {
-            name: "trading-analysis".to_string(),
-            description: "Analyze market conditions and suggest trading strategies for a symbol"
-                .to_string(),
-            args_schema: Self::json_schema(
-                r#"{
+        // Built-in prompts plus any loaded from PROMPTS_DIR share one argument schema
+        for template in super::prompts::templates().iter() {
+            self.prompts.push(Prompt {
+                name: template.name.clone(),
+                description: template.description.clone(),
+                args_schema: Self::json_schema(
+                    r#"{
   "$schema": "http://json-schema.org/draft-07/schema#",
   "type": "object",
   "properties": {
     "symbol": {"type": "string", "description": "Trading pair symbol"},
-    "timeframe": {"type": "string", "description": "Analysis timeframe (1h, 4h, 1d)"}
+    "timeframe": {"type": "string", "default": "1d", "description": "Analysis timeframe (1h, 4h, 1d)"}
   },
   "required": ["symbol"],
   "additionalProperties": false
 }"#,
-            ),
-        });
+                ),
+            });
+        }
     }
 }
 
@@ -500,3 +941,84 @@ impl Default for CapabilityBuilder {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ProviderError;
+
+    /// Every advertised tool must be routable in the same feature configuration
+    #[tokio::test]
+    async fn test_listed_tools_are_routable() {
+        let capabilities = CapabilityBuilder::new().build().unwrap();
+        // Tools that reach the network fail fast instead of calling Binance
+        let mut client = crate::binance::client::BinanceClient::new().with_retry_policy(
+            crate::binance::retry::RetryPolicy {
+                max_attempts: 1,
+                ..Default::default()
+            },
+        );
+        client.base_url = "http://127.0.0.1:1".to_string();
+
+        for tool in &capabilities.tools {
+            let request = InvokeRequest {
+                tool_name: tool.name.clone(),
+                payload: Some(Json {
+                    value: b"{}".to_vec(),
+                }),
+                correlation_id: String::new(),
+            };
+            let result = crate::grpc::tools::route_tool(
+                &client,
+                None,
+                None,
+                None,
+                None,
+                &request,
+                &tokio_util::sync::CancellationToken::new(),
+            )
+            .await;
+            assert!(
+                !matches!(result, Err(ProviderError::ToolNotFound(_))),
+                "{} is listed but not routed",
+                tool.name
+            );
+        }
+    }
+
+    #[cfg(feature = "orderbook")]
+    #[test]
+    fn test_report_output_schema_is_json_schema() {
+        let capabilities = CapabilityBuilder::new().build().unwrap();
+        let report = capabilities
+            .tools
+            .iter()
+            .find(|tool| tool.name == "binance.generate_market_report")
+            .unwrap();
+
+        let schema: schemars::Schema =
+            serde_json::from_slice(&report.output_schema.as_ref().unwrap().value).unwrap();
+        assert_eq!(schema.get("type"), Some(&serde_json::json!("object")));
+        let properties = schema.get("properties").unwrap();
+        for field in [
+            "markdown_content",
+            "symbol",
+            "generated_at",
+            "generated_at_ts",
+            "data_age_ms",
+            "failed_sections",
+            "generation_time_ms",
+            "partial",
+        ] {
+            assert!(properties.get(field).is_some(), "missing {}", field);
+        }
+
+        for tool in &capabilities.tools {
+            assert!(
+                tool.output_schema.is_some(),
+                "{} has no output schema",
+                tool.name
+            );
+        }
+    }
+}
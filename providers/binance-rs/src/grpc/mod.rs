@@ -1,8 +1,9 @@
 use crate::binance::client::BinanceClient;
-use crate::error::Result;
+use crate::error::{ProviderError, Result};
 use crate::pb::{provider_server::Provider, *};
 #[cfg(feature = "orderbook")]
 use crate::report::ReportGenerator;
+use tokio_util::sync::CancellationToken;
 use tonic::{Request, Response, Status};
 
 #[cfg(feature = "orderbook")]
@@ -13,10 +14,60 @@ use std::sync::Arc;
 pub mod capabilities;
 pub mod prompts;
 pub mod resources;
+pub mod stream;
 pub mod tools;
 
 use capabilities::CapabilityBuilder;
 
+/// Report cache TTL from `REPORT_CACHE_TTL_SECS` (default: 60 seconds)
+#[cfg(feature = "orderbook")]
+fn report_cache_ttl_secs() -> std::result::Result<u64, String> {
+    let ttl = crate::report::cache_ttl_from_env().map_err(|e| e.to_string())?;
+    tracing::info!("Report cache TTL: {}s", ttl);
+    Ok(ttl)
+}
+
+/// Concurrent analytics query limit from `REPORT_ANALYTICS_CONCURRENCY` (default: 4)
+#[cfg(feature = "orderbook")]
+fn report_analytics_concurrency() -> std::result::Result<usize, String> {
+    let limit = crate::report::analytics_concurrency_from_env().map_err(|e| e.to_string())?;
+    tracing::info!("Report analytics concurrency: {}", limit);
+    Ok(limit)
+}
+
+/// Report archive directory from `REPORT_ARCHIVE_DIR` (unset: no archiving)
+#[cfg(feature = "orderbook")]
+fn report_archive_dir() -> Option<std::path::PathBuf> {
+    let dir = crate::report::archive_dir_from_env();
+    if let Some(dir) = &dir {
+        tracing::info!("Archiving fresh reports under {}", dir.display());
+    }
+    dir
+}
+
+/// Live order book manager configured from `ORDERBOOK_STALENESS_*` and
+/// `ORDERBOOK_SNAPSHOT_DEPTH`
+#[cfg(feature = "orderbook")]
+fn live_orderbook_manager(
+    binance_client: &BinanceClient,
+) -> std::result::Result<Arc<OrderBookManager>, String> {
+    let staleness = crate::config::StalenessConfig::from_env()
+        .map_err(|e| format!("Invalid order book staleness config: {}", e))?;
+    tracing::info!(
+        "Order book staleness threshold: {}ms ({} symbol overrides)",
+        staleness.default_ms,
+        staleness.overrides.len()
+    );
+    let depth = crate::config::orderbook::parse_snapshot_depth(
+        std::env::var("ORDERBOOK_SNAPSHOT_DEPTH").ok().as_deref(),
+    )
+    .map_err(|e| e.to_string())?;
+    let manager = OrderBookManager::with_snapshot_depth(Arc::new(binance_client.clone()), depth)
+        .map_err(|e| e.to_string())?;
+    tracing::info!("Order book snapshot depth: {} levels", depth);
+    Ok(Arc::new(manager.with_staleness(staleness)))
+}
+
 /// BinanceProviderServer implements the Provider gRPC service
 #[derive(Clone)]
 pub struct BinanceProviderServer {
@@ -27,17 +78,22 @@ pub struct BinanceProviderServer {
     #[cfg(feature = "orderbook")]
     pub orderbook_manager: Arc<OrderBookManager>,
 
-    /// Analytics storage (optional, enabled with orderbook_analytics feature)
+    /// Analytics storage (enabled with orderbook_analytics feature; `None` when
+    /// RocksDB failed to open and the server runs without analytics)
     #[cfg(feature = "orderbook_analytics")]
-    pub analytics_storage: Arc<crate::orderbook::analytics::SnapshotStorage>,
+    pub analytics_storage: Option<Arc<crate::orderbook::analytics::SnapshotStorage>>,
 
-    /// Trade persistence storage (optional, enabled with orderbook_analytics feature)
+    /// Trade persistence storage (shares the analytics RocksDB, `None` with it)
     #[cfg(feature = "orderbook_analytics")]
-    pub trade_storage: Arc<crate::orderbook::analytics::TradeStorage>,
+    pub trade_storage: Option<Arc<crate::orderbook::analytics::TradeStorage>>,
 
     /// Market data report generator
     #[cfg(feature = "orderbook")]
     pub report_generator: Arc<ReportGenerator>,
+
+    /// Live ticker/depth subscriptions backing the `Stream` RPC
+    #[cfg(feature = "websocket")]
+    pub stream_hub: stream::StreamHub,
 }
 
 impl BinanceProviderServer {
@@ -49,62 +105,28 @@ impl BinanceProviderServer {
         {
             tracing::info!("OrderBook feature enabled - initializing WebSocket manager");
             let orderbook_manager =
-                Arc::new(OrderBookManager::new(Arc::new(binance_client.clone())));
-
-            tracing::info!("Analytics feature enabled - initializing RocksDB storage");
-            let data_path = std::env::var("ANALYTICS_DATA_PATH")
-                .unwrap_or_else(|_| "./data/analytics".to_string());
-
-            let analytics_storage = Arc::new(
-                crate::orderbook::analytics::SnapshotStorage::new(&data_path).map_err(|e| {
-                    crate::error::ProviderError::Initialization(format!(
-                        "Failed to initialize analytics storage: {}",
-                        e
-                    ))
-                })?,
-            );
-
-            tracing::info!("Analytics storage initialized at: {}", data_path);
-
-            // Initialize TradeStorage (shares same RocksDB as SnapshotStorage)
-            let trade_storage = Arc::new(crate::orderbook::analytics::TradeStorage::new(
-                analytics_storage.db(),
-            ));
-
-            tracing::info!("Trade persistence storage initialized (shared RocksDB)");
-
-            // Initialize ReportGenerator with analytics support
-            let report_generator = Arc::new(ReportGenerator::new_with_analytics(
-                Arc::new(binance_client.clone()),
-                orderbook_manager.clone(),
-                60, // 60 second cache TTL
-                analytics_storage.clone(),
-                trade_storage.clone(),
-            ));
-
-            tracing::info!("Market data report generator initialized with analytics support");
-
-            Ok(Self {
-                binance_client,
-                orderbook_manager,
-                analytics_storage,
-                trade_storage,
-                report_generator,
-            })
+                live_orderbook_manager(&binance_client).map_err(ProviderError::Initialization)?;
+            Self::with_orderbook_manager(binance_client, orderbook_manager)
         }
 
         #[cfg(all(feature = "orderbook", not(feature = "orderbook_analytics")))]
         {
             tracing::info!("OrderBook feature enabled - initializing WebSocket manager");
             let orderbook_manager =
-                Arc::new(OrderBookManager::new(Arc::new(binance_client.clone())));
+                live_orderbook_manager(&binance_client).map_err(ProviderError::Initialization)?;
 
             // Initialize ReportGenerator
-            let report_generator = Arc::new(ReportGenerator::new(
-                Arc::new(binance_client.clone()),
-                orderbook_manager.clone(),
-                60, // 60 second cache TTL
-            ));
+            let report_generator = Arc::new(
+                ReportGenerator::new(
+                    Arc::new(binance_client.clone()),
+                    orderbook_manager.clone(),
+                    report_cache_ttl_secs().map_err(ProviderError::Initialization)?,
+                )
+                .with_archive_dir(report_archive_dir())
+                .with_analytics_concurrency(
+                    report_analytics_concurrency().map_err(ProviderError::Initialization)?,
+                ),
+            );
 
             tracing::info!("Market data report generator initialized");
 
@@ -112,19 +134,135 @@ impl BinanceProviderServer {
                 binance_client,
                 orderbook_manager,
                 report_generator,
+                #[cfg(feature = "websocket")]
+                stream_hub: stream::StreamHub::new(),
             })
         }
 
         #[cfg(not(feature = "orderbook"))]
         {
-            Ok(Self { binance_client })
+            Ok(Self {
+                binance_client,
+                #[cfg(feature = "websocket")]
+                stream_hub: stream::StreamHub::new(),
+            })
         }
     }
+
+    /// Create a server whose order books are fed by replayed snapshots instead of
+    /// REST + WebSocket streams (see [`crate::orderbook::replay`])
+    #[cfg(feature = "orderbook_analytics")]
+    pub fn new_replay() -> Result<Self> {
+        tracing::info!("Replay mode - order books are fed from stored snapshots");
+        let orderbook_manager = Arc::new(OrderBookManager::with_source(Arc::new(
+            crate::orderbook::source::ReplaySnapshotSource,
+        )));
+        Self::with_orderbook_manager(BinanceClient::with_credentials(), orderbook_manager)
+    }
+
+    /// Open analytics storage at `ANALYTICS_DATA_PATH` and build the report
+    /// generator around `orderbook_manager`
+    #[cfg(feature = "orderbook_analytics")]
+    fn with_orderbook_manager(
+        binance_client: BinanceClient,
+        orderbook_manager: Arc<OrderBookManager>,
+    ) -> Result<Self> {
+        let data_path =
+            std::env::var("ANALYTICS_DATA_PATH").unwrap_or_else(|_| "./data/analytics".to_string());
+        Self::with_storage_path(binance_client, orderbook_manager, &data_path)
+    }
+
+    /// Open analytics storage at `data_path` and build the report generator
+    ///
+    /// A storage that fails to open (e.g. locked by another process) does not
+    /// abort startup: the server continues without analytics, whose report
+    /// sections then report the data source as unavailable.
+    #[cfg(feature = "orderbook_analytics")]
+    fn with_storage_path(
+        binance_client: BinanceClient,
+        orderbook_manager: Arc<OrderBookManager>,
+        data_path: &str,
+    ) -> Result<Self> {
+        let cache_ttl_secs = report_cache_ttl_secs().map_err(ProviderError::Initialization)?;
+        let analytics_concurrency =
+            report_analytics_concurrency().map_err(ProviderError::Initialization)?;
+        let storage_config = crate::orderbook::analytics::StorageConfig::from_env()
+            .map_err(|e| ProviderError::Initialization(format!("Invalid storage config: {}", e)))?;
+        let storage_backend = crate::orderbook::analytics::StorageBackend::from_env()
+            .map_err(|e| ProviderError::Initialization(format!("Invalid storage config: {}", e)))?;
+        tracing::info!(
+            "Analytics feature enabled - initializing {:?} storage",
+            storage_backend
+        );
+
+        let (analytics_storage, trade_storage, report_generator) =
+            match crate::orderbook::analytics::SnapshotStorage::open(
+                storage_backend,
+                data_path,
+                &storage_config,
+            ) {
+                Ok(storage) => {
+                    tracing::info!("Analytics storage initialized at: {}", data_path);
+                    let analytics_storage = Arc::new(storage);
+
+                    // Initialize TradeStorage (shares same RocksDB as SnapshotStorage);
+                    // the memory backend has no database, so trades are not persisted
+                    let trade_storage = analytics_storage.db().map(|db| {
+                        tracing::info!("Trade persistence storage initialized (shared RocksDB)");
+                        Arc::new(crate::orderbook::analytics::TradeStorage::new(db))
+                    });
+                    if trade_storage.is_none() {
+                        tracing::warn!("In-memory analytics storage: trade persistence disabled");
+                    }
+
+                    let report_generator = ReportGenerator::new_with_analytics(
+                        Arc::new(binance_client.clone()),
+                        orderbook_manager.clone(),
+                        cache_ttl_secs,
+                        analytics_storage.clone(),
+                        trade_storage.clone(),
+                    )
+                    .with_archive_dir(report_archive_dir())
+                    .with_analytics_concurrency(analytics_concurrency);
+                    tracing::info!(
+                        "Market data report generator initialized with analytics support"
+                    );
+                    (Some(analytics_storage), trade_storage, report_generator)
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to initialize analytics storage at {}: {} - continuing without analytics",
+                        data_path,
+                        e
+                    );
+                    let report_generator = ReportGenerator::new(
+                        Arc::new(binance_client.clone()),
+                        orderbook_manager.clone(),
+                        cache_ttl_secs,
+                    )
+                    .with_analytics_unavailable()
+                    .with_archive_dir(report_archive_dir())
+                    .with_analytics_concurrency(analytics_concurrency);
+                    (None, None, report_generator)
+                }
+            };
+
+        Ok(Self {
+            binance_client,
+            orderbook_manager,
+            analytics_storage,
+            trade_storage,
+            report_generator: Arc::new(report_generator),
+            #[cfg(feature = "websocket")]
+            stream_hub: stream::StreamHub::new(),
+        })
+    }
 }
 
 #[tonic::async_trait]
 impl Provider for BinanceProviderServer {
-    type StreamStream = futures::stream::Empty<std::result::Result<CloudEvent, Status>>;
+    type StreamStream =
+        tokio_stream::wrappers::ReceiverStream<std::result::Result<CloudEvent, Status>>;
 
     async fn list_capabilities(
         &self,
@@ -154,12 +292,13 @@ impl Provider for BinanceProviderServer {
         let response = tools::route_tool(
             &self.binance_client,
             Some(self.orderbook_manager.clone()),
-            Some(self.analytics_storage.clone()),
-            Some(self.trade_storage.clone()),
+            self.analytics_storage.clone(),
+            self.trade_storage.clone(),
             Some(self.report_generator.clone()),
             &req,
+            &CancellationToken::new(),
         )
-        .await?;
+        .await;
 
         #[cfg(all(feature = "orderbook", not(feature = "orderbook_analytics")))]
         let response = tools::route_tool(
@@ -169,14 +308,41 @@ impl Provider for BinanceProviderServer {
             None,
             Some(self.report_generator.clone()),
             &req,
+            &CancellationToken::new(),
         )
-        .await?;
+        .await;
 
         #[cfg(not(feature = "orderbook"))]
-        let response =
-            tools::route_tool(&self.binance_client, None, None, None, None, &req).await?;
-
-        Ok(Response::new(response))
+        let response = tools::route_tool(
+            &self.binance_client,
+            None,
+            None,
+            None,
+            None,
+            &req,
+            &CancellationToken::new(),
+        )
+        .await;
+
+        // Unknown tools are a protocol error; tool failures are reported in-band
+        // with a stable error code prefix so clients can decide whether to retry
+        match response {
+            Ok(response) => Ok(Response::new(response)),
+            Err(err @ ProviderError::ToolNotFound(_)) => Err(err.into()),
+            Err(err) => {
+                tracing::warn!(
+                    tool_name = %req.tool_name,
+                    error_code = err.error_code(),
+                    "Tool invocation failed: {}",
+                    err
+                );
+                Ok(Response::new(InvokeResponse {
+                    result: None,
+                    error: err.to_invoke_error(),
+                    correlation_id: req.correlation_id.clone(),
+                }))
+            }
+        }
     }
 
     async fn read_resource(
@@ -190,7 +356,13 @@ impl Provider for BinanceProviderServer {
             req.correlation_id
         );
 
-        let response = resources::handle_resource(&self.binance_client, &req).await?;
+        let response = resources::handle_resource(
+            &self.binance_client,
+            #[cfg(feature = "orderbook")]
+            Some(&self.report_generator),
+            &req,
+        )
+        .await?;
 
         Ok(Response::new(response))
     }
@@ -211,13 +383,26 @@ impl Provider for BinanceProviderServer {
         Ok(Response::new(response))
     }
 
+    #[cfg(feature = "websocket")]
+    async fn stream(
+        &self,
+        request: Request<StreamRequest>,
+    ) -> std::result::Result<Response<Self::StreamStream>, Status> {
+        let req = request.into_inner();
+        tracing::info!("Stream RPC called: topic={}", req.topic);
+
+        let stream = self.stream_hub.subscribe(&req.topic)?;
+
+        Ok(Response::new(stream))
+    }
+
+    #[cfg(not(feature = "websocket"))]
     async fn stream(
         &self,
         _request: Request<StreamRequest>,
     ) -> std::result::Result<Response<Self::StreamStream>, Status> {
-        // Not implemented for binance-rs provider
         Err(Status::unimplemented(
-            "Streaming not supported by binance-rs provider",
+            "Streaming requires the websocket feature",
         ))
     }
 }
@@ -227,3 +412,49 @@ impl Default for BinanceProviderServer {
         Self::new().expect("Failed to create BinanceProviderServer")
     }
 }
+
+#[cfg(all(test, feature = "orderbook_analytics"))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_storage_open_failure_keeps_server_running() {
+        // A regular file where the RocksDB directory should be makes the open fail
+        let blocker = tempfile::NamedTempFile::new().unwrap();
+        let mut client = BinanceClient::new();
+        client.base_url = "http://127.0.0.1:1".to_string();
+        let manager = Arc::new(OrderBookManager::new(Arc::new(client.clone())));
+
+        let server = BinanceProviderServer::with_storage_path(
+            client,
+            manager,
+            blocker.path().to_str().unwrap(),
+        )
+        .expect("server should start without analytics storage");
+        assert!(server.analytics_storage.is_none());
+        assert!(server.trade_storage.is_none());
+
+        let invoke = |tool_name: &str| InvokeRequest {
+            tool_name: tool_name.to_string(),
+            payload: None,
+            correlation_id: "test".to_string(),
+        };
+
+        // Tools that don't need storage keep working
+        let health = server
+            .invoke(Request::new(invoke("binance.health")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(health.error.is_empty(), "{}", health.error);
+        assert!(health.result.is_some());
+
+        // Analytics tools fail in-band instead of taking the server down
+        let benchmarks = server
+            .invoke(Request::new(invoke("binance.get_execution_benchmarks")))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(benchmarks.error.contains("Trade storage not initialized"));
+    }
+}
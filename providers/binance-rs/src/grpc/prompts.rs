@@ -1,6 +1,247 @@
+//! Prompt templates served by the `GetPrompt` RPC
+//!
+//! Every prompt renders a system and a user message from a template with
+//! `{symbol}`, `{base}`, `{timeframe}` and `{market_data}` placeholders, where
+//! `{market_data}` is a live ticker and order book summary. Built-in templates
+//! cover trading analysis, risk assessment, entry/exit planning and liquidity
+//! review. Files in `PROMPTS_DIR` named `{prompt-name}.md` replace the built-in
+//! prompt of the same name or add a new one; a line holding only `---` splits
+//! the system message from the user message (without it, the whole file is the
+//! system message and the user message is just the market data).
+
 use crate::binance::client::BinanceClient;
 use crate::error::{ProviderError, Result};
 use crate::pb::{Json, PromptMessage, PromptRequest, PromptResponse};
+use std::path::{Path, PathBuf};
+use std::sync::{OnceLock, RwLock};
+
+/// Timeframe used when a prompt request omits `timeframe`
+pub const DEFAULT_TIMEFRAME: &str = "1d";
+
+/// Line separating the system and user message in a template file
+pub const TEMPLATE_SEPARATOR: &str = "---";
+
+/// User message of templates that don't define one
+const DEFAULT_USER_TEMPLATE: &str = "{market_data}";
+
+/// Description of prompts loaded from `PROMPTS_DIR` without a built-in namesake
+const CUSTOM_PROMPT_DESCRIPTION: &str = "Custom prompt loaded from PROMPTS_DIR";
+
+/// A prompt's messages before placeholder interpolation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptTemplate {
+    /// Prompt name clients request (e.g. "risk-assessment")
+    pub name: String,
+    pub description: String,
+    /// System message template
+    pub system: String,
+    /// User message template
+    pub user: String,
+}
+
+impl PromptTemplate {
+    fn builtin(name: &str, description: &str, system: &str, user: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            system: system.to_string(),
+            user: user.to_string(),
+        }
+    }
+
+    /// Parse a template file's content, split at the first `---` line
+    fn parse(name: &str, description: &str, content: &str) -> Self {
+        let mut system = Vec::new();
+        let mut user: Option<Vec<&str>> = None;
+        for line in content.lines() {
+            match user.as_mut() {
+                Some(user) => user.push(line),
+                None if line.trim() == TEMPLATE_SEPARATOR => user = Some(Vec::new()),
+                None => system.push(line),
+            }
+        }
+
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            system: system.join("\n").trim().to_string(),
+            user: user.map_or(DEFAULT_USER_TEMPLATE.to_string(), |user| {
+                user.join("\n").trim().to_string()
+            }),
+        }
+    }
+
+    /// Interpolate the placeholders into a system and a user message
+    pub fn render(&self, symbol: &str, timeframe: &str, market_data: &str) -> Vec<PromptMessage> {
+        let base = symbol.strip_suffix("USDT").unwrap_or(symbol);
+        let fill = |template: &str| {
+            template
+                .replace("{symbol}", symbol)
+                .replace("{base}", base)
+                .replace("{timeframe}", timeframe)
+                .replace("{market_data}", market_data)
+        };
+
+        vec![
+            PromptMessage {
+                role: "system".to_string(),
+                content: fill(&self.system),
+            },
+            PromptMessage {
+                role: "user".to_string(),
+                content: fill(&self.user),
+            },
+        ]
+    }
+}
+
+/// The set of prompts offered, built-ins first in registration order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptTemplates {
+    templates: Vec<PromptTemplate>,
+}
+
+impl PromptTemplates {
+    /// The built-in prompts
+    pub fn builtin() -> Self {
+        Self {
+            templates: vec![
+                PromptTemplate::builtin(
+                    "trading-analysis",
+                    "Analyze market conditions and suggest trading strategies for a symbol",
+                    TRADING_ANALYSIS_SYSTEM,
+                    "{market_data}\n\nUse this data to provide a comprehensive trading analysis.",
+                ),
+                PromptTemplate::builtin(
+                    "risk-assessment",
+                    "Assess volatility, liquidity and microstructure risks of trading a symbol",
+                    RISK_ASSESSMENT_SYSTEM,
+                    "{market_data}\n\nUse this data to rate the risk of trading {symbol}.",
+                ),
+                PromptTemplate::builtin(
+                    "entry-exit-planning",
+                    "Plan entry, stop-loss and take-profit levels for a symbol",
+                    ENTRY_EXIT_PLANNING_SYSTEM,
+                    "{market_data}\n\nUse this data to plan entries and exits for {symbol}.",
+                ),
+                PromptTemplate::builtin(
+                    "liquidity-review",
+                    "Review order book depth, walls and execution cost for a symbol",
+                    LIQUIDITY_REVIEW_SYSTEM,
+                    "{market_data}\n\nUse this data to review the liquidity of {symbol}.",
+                ),
+            ],
+        }
+    }
+
+    /// Built-in prompts overridden or extended by the `*.md` files in `dir`
+    ///
+    /// # Errors
+    /// The directory or a template file can't be read, or a file name is not a
+    /// valid prompt name (lowercase letters, digits and `-`)
+    pub fn load_dir(dir: &Path) -> std::result::Result<Self, Box<dyn std::error::Error>> {
+        let mut templates = Self::builtin();
+
+        let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+            .map_err(|e| format!("Cannot read PROMPTS_DIR {}: {}", dir.display(), e))?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<std::io::Result<_>>()?;
+        paths.retain(|path| path.extension().is_some_and(|ext| ext == "md"));
+        paths.sort();
+
+        for path in paths {
+            let name = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .filter(|name| is_prompt_name(name))
+                .ok_or_else(|| {
+                    format!(
+                        "Invalid prompt template file name {} (expected e.g. risk-assessment.md)",
+                        path.display()
+                    )
+                })?;
+            let content = std::fs::read_to_string(&path)
+                .map_err(|e| format!("Cannot read prompt template {}: {}", path.display(), e))?;
+            templates.insert(name, &content);
+        }
+
+        Ok(templates)
+    }
+
+    /// Replace the template called `name`, keeping its description, or add it
+    fn insert(&mut self, name: &str, content: &str) {
+        match self.templates.iter_mut().find(|t| t.name == name) {
+            Some(existing) => {
+                *existing = PromptTemplate::parse(name, &existing.description, content)
+            }
+            None => self.templates.push(PromptTemplate::parse(
+                name,
+                CUSTOM_PROMPT_DESCRIPTION,
+                content,
+            )),
+        }
+    }
+
+    /// Template called `name`, if offered
+    pub fn get(&self, name: &str) -> Option<&PromptTemplate> {
+        self.templates.iter().find(|t| t.name == name)
+    }
+
+    /// Every offered template
+    pub fn iter(&self) -> impl Iterator<Item = &PromptTemplate> {
+        self.templates.iter()
+    }
+}
+
+fn is_prompt_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Templates served by [`handle_prompt`] (built-ins until [`set_templates`])
+static TEMPLATES: OnceLock<RwLock<PromptTemplates>> = OnceLock::new();
+
+fn templates_lock() -> &'static RwLock<PromptTemplates> {
+    TEMPLATES.get_or_init(|| RwLock::new(PromptTemplates::builtin()))
+}
+
+/// Prompts currently offered
+pub fn templates() -> PromptTemplates {
+    templates_lock().read().unwrap().clone()
+}
+
+/// Replace the offered prompts (e.g. with ones loaded from `PROMPTS_DIR`)
+pub fn set_templates(templates: PromptTemplates) {
+    *templates_lock().write().unwrap() = templates;
+}
+
+/// Prompt template directory from `PROMPTS_DIR` (unset or blank: built-ins only)
+pub fn prompts_dir_from_env() -> Option<PathBuf> {
+    prompts_dir_from_value(std::env::var("PROMPTS_DIR").ok().as_deref())
+}
+
+/// Parse a raw `PROMPTS_DIR` value (`None` or blank = built-ins only)
+pub fn prompts_dir_from_value(raw: Option<&str>) -> Option<PathBuf> {
+    raw.map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+}
+
+/// Load `PROMPTS_DIR` templates, if set, and offer them from now on
+pub fn load_templates_from_env() -> std::result::Result<(), Box<dyn std::error::Error>> {
+    if let Some(dir) = prompts_dir_from_env() {
+        let templates = PromptTemplates::load_dir(&dir)?;
+        tracing::info!(
+            "Loaded prompt templates from {} ({} prompts)",
+            dir.display(),
+            templates.iter().count()
+        );
+        set_templates(templates);
+    }
+    Ok(())
+}
 
 // Helper function to parse Json payload
 fn parse_json(json_opt: &Option<Json>) -> Result<serde_json::Value> {
@@ -21,52 +262,36 @@ pub async fn handle_prompt(
 ) -> Result<PromptResponse> {
     tracing::debug!("Handling prompt: {}", request.prompt_name);
 
-    let messages = match request.prompt_name.as_str() {
-        "trading-analysis" => handle_trading_analysis_prompt(client, request).await?,
-        _ => return Err(ProviderError::PromptNotFound(request.prompt_name.clone())),
-    };
+    let template = templates()
+        .get(&request.prompt_name)
+        .cloned()
+        .ok_or_else(|| ProviderError::PromptNotFound(request.prompt_name.clone()))?;
 
-    Ok(PromptResponse {
-        messages,
-        error: String::new(),
-    })
-}
-
-// ========== Prompt Handlers ==========
-
-async fn handle_trading_analysis_prompt(
-    client: &BinanceClient,
-    request: &PromptRequest,
-) -> Result<Vec<PromptMessage>> {
     let args = parse_json(&request.arguments)?;
-
     let symbol = args["symbol"].as_str().ok_or_else(|| {
         ProviderError::Validation("Missing required argument: symbol".to_string())
     })?;
-
-    let timeframe = args["timeframe"].as_str().unwrap_or("1d");
+    let symbol = crate::binance::symbol::validate_and_normalize(symbol)?;
+    let timeframe = args["timeframe"].as_str().unwrap_or(DEFAULT_TIMEFRAME);
 
     tracing::info!(
-        "Generating trading analysis prompt for symbol: {}, timeframe: {}",
+        "Generating {} prompt for symbol: {}, timeframe: {}",
+        template.name,
         symbol,
         timeframe
     );
 
-    // Fetch real market data from Binance API
-    let ticker = client
-        .get_24hr_ticker(symbol)
-        .await
-        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+    let market_data = fetch_market_data(client, &symbol).await?;
 
-    let orderbook = client
-        .get_order_book(symbol, Some(10))
-        .await
-        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+    Ok(PromptResponse {
+        messages: template.render(&symbol, timeframe, &market_data),
+        error: String::new(),
+    })
+}
 
-    let base_asset = symbol.strip_suffix("USDT").unwrap_or(symbol);
+// ========== Templates ==========
 
-    let system_message = format!(
-        r#"You are a professional cryptocurrency trading analyst. Analyze the market conditions for {} using the provided data and suggest trading strategies.
+const TRADING_ANALYSIS_SYSTEM: &str = r#"You are a professional cryptocurrency trading analyst. Analyze the market conditions for {symbol} using the provided data and suggest trading strategies.
 
 Focus on:
 1. Price action and trend analysis
@@ -75,10 +300,62 @@ Focus on:
 4. Market sentiment indicators
 5. Risk-reward ratio for potential trades
 
+For deeper context, call `binance.generate_market_report` for {symbol}; its price_overview, liquidity_analysis and derivatives_sentiment sections are the most relevant.
+
 Provide actionable insights with clear entry/exit points and risk management suggestions.
-Timeframe for analysis: {}"#,
-        symbol, timeframe
-    );
+Timeframe for analysis: {timeframe}"#;
+
+const RISK_ASSESSMENT_SYSTEM: &str = r#"You are a cryptocurrency risk analyst. Assess the risks of holding or trading {symbol} over a {timeframe} horizon using the provided data.
+
+Focus on:
+1. Volatility: the 24h range relative to the last price
+2. Liquidity risk: spread width and how thin the book is near the mid price
+3. Microstructure risk: flash crash risk, quote stuffing and iceberg orders
+4. Positioning risk: funding and open interest extremes
+
+For deeper context, call `binance.generate_market_report` for {symbol} with the market_anomalies, microstructure_health, liquidity_analysis and derivatives_sentiment sections.
+
+Rate the overall risk as low, medium or high, explain the main drivers and suggest a position size limit and stop distance in line with it."#;
+
+const ENTRY_EXIT_PLANNING_SYSTEM: &str = r#"You are a cryptocurrency execution planner. Plan entries and exits for {symbol} on the {timeframe} timeframe using the provided data.
+
+Focus on:
+1. Entry zones at support levels, the value area and large bid walls
+2. Stop-loss placement beyond the levels that invalidate the idea
+3. Take-profit targets at resistance, the value area high and large ask walls
+4. Expected slippage for the planned order size
+
+For deeper context, call `binance.generate_market_report` for {symbol}; its liquidity_analysis (volume profile POC/VAH/VAL and walls), orderbook_metrics (slippage estimates) and price_overview sections are the most relevant.
+
+Give concrete price levels for each entry, stop and target, and the resulting risk-reward ratio."#;
+
+const LIQUIDITY_REVIEW_SYSTEM: &str = r#"You are a market microstructure analyst. Review the liquidity of {symbol} using the provided data.
+
+Focus on:
+1. Spread and top-of-book depth
+2. Bid/ask imbalance and where the large walls sit
+3. Liquidity vacuums where price could move quickly
+4. The cost of executing large market orders in {base}
+
+For deeper context, call `binance.generate_market_report` for {symbol}; its orderbook_metrics, liquidity_analysis, market_microstructure and recent_trades sections are the most relevant.
+
+Summarize whether {symbol} can absorb large orders on the {timeframe} timeframe and how best to work them."#;
+
+// ========== Market Data ==========
+
+/// Ticker and top of book summary interpolated as `{market_data}`
+async fn fetch_market_data(client: &BinanceClient, symbol: &str) -> Result<String> {
+    let ticker = client
+        .get_24hr_ticker(symbol)
+        .await
+        .map_err(ProviderError::from)?;
+
+    let orderbook = client
+        .get_order_book(symbol, Some(10))
+        .await
+        .map_err(ProviderError::from)?;
+
+    let base_asset = symbol.strip_suffix("USDT").unwrap_or(symbol);
 
     // Calculate spread for order book analysis
     let (best_bid, best_ask, spread, spread_pct) =
@@ -96,7 +373,7 @@ Timeframe for analysis: {}"#,
             ("N/A".to_string(), "N/A".to_string(), 0.0, 0.0)
         };
 
-    let context_message = format!(
+    Ok(format!(
         r#"## Market Data for {}
 
 ### Price Statistics (24h)
@@ -114,9 +391,7 @@ Timeframe for analysis: {}"#,
 
 ### Top Bids & Asks
 Top 5 Bids: {}
-Top 5 Asks: {}
-
-Use this data to provide a comprehensive trading analysis."#,
+Top 5 Asks: {}"#,
         symbol,
         ticker.last_price,
         ticker.price_change,
@@ -144,16 +419,82 @@ Use this data to provide a comprehensive trading analysis."#,
             .map(|(p, q)| format!("{}@{}", q, p))
             .collect::<Vec<_>>()
             .join(", ")
-    );
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_template_overrides_builtin() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("risk-assessment.md"),
+            "Custom risk desk view of {symbol} ({timeframe})\n---\n{market_data}\nBase: {base}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("scalping.md"), "Scalp {symbol}").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "ignored").unwrap();
+
+        let templates = PromptTemplates::load_dir(dir.path()).unwrap();
 
-    Ok(vec![
-        PromptMessage {
-            role: "system".to_string(),
-            content: system_message,
-        },
-        PromptMessage {
-            role: "user".to_string(),
-            content: context_message,
-        },
-    ])
+        let risk = templates.get("risk-assessment").unwrap();
+        assert_ne!(
+            risk,
+            PromptTemplates::builtin().get("risk-assessment").unwrap()
+        );
+        // The built-in description is kept
+        assert!(risk.description.starts_with("Assess volatility"));
+        let messages = risk.render("ETHUSDT", "4h", "MARKET");
+        assert_eq!(messages[0].content, "Custom risk desk view of ETHUSDT (4h)");
+        assert_eq!(messages[1].content, "MARKET\nBase: ETH");
+
+        // New names are added after the built-ins; without `---` the user
+        // message is the market data
+        let names: Vec<&str> = templates.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "trading-analysis",
+                "risk-assessment",
+                "entry-exit-planning",
+                "liquidity-review",
+                "scalping"
+            ]
+        );
+        let scalping = templates
+            .get("scalping")
+            .unwrap()
+            .render("BTCUSDT", "1m", "MARKET");
+        assert_eq!(scalping[1].content, "MARKET");
+
+        // Untouched built-ins are unchanged
+        assert_eq!(
+            templates.get("liquidity-review"),
+            PromptTemplates::builtin().get("liquidity-review")
+        );
+    }
+
+    #[test]
+    fn test_builtin_templates_interpolate_symbol_and_timeframe() {
+        for template in PromptTemplates::builtin().iter() {
+            let messages = template.render("SOLUSDT", "4h", "MARKET");
+            assert!(messages[0].content.contains("SOLUSDT"), "{}", template.name);
+            assert!(
+                messages[1].content.starts_with("MARKET"),
+                "{}",
+                template.name
+            );
+            assert!(
+                !messages.iter().any(|m| m.content.contains('{')),
+                "{} left a placeholder",
+                template.name
+            );
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Bad Name.md"), "x").unwrap();
+        assert!(PromptTemplates::load_dir(dir.path()).is_err());
+    }
 }
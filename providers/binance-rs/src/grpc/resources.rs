@@ -1,51 +1,160 @@
+//! `binance://` resource reads
+//!
+//! A resource URI names a kind of market data and a symbol, e.g.
+//! `binance://ticker/BTCUSDT`. Each kind has a fixed MIME type: the ticker and
+//! order book are returned as JSON, the market summary and report as markdown.
+
 use crate::binance::client::BinanceClient;
 use crate::error::{ProviderError, Result};
 use crate::pb::{ResourceRequest, ResourceResponse};
 
+/// URI scheme of every resource served by this provider
+pub const RESOURCE_SCHEME: &str = "binance";
+
+/// Depth levels returned by `binance://orderbook/{symbol}`
+pub const RESOURCE_ORDERBOOK_LIMIT: u32 = 100;
+
+/// A parsed `binance://{kind}/{symbol}` URI
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResourceUri {
+    /// `binance://market/{symbol}`: 24h statistics and top of book (markdown)
+    Market(String),
+    /// `binance://ticker/{symbol}`: 24hr ticker (JSON)
+    Ticker(String),
+    /// `binance://orderbook/{symbol}`: depth snapshot (JSON)
+    OrderBook(String),
+    /// `binance://report/{symbol}`: market report (markdown)
+    Report(String),
+}
+
+impl ResourceUri {
+    /// Parse a resource URI, normalizing its symbol (e.g. "btcusdt" -> "BTCUSDT")
+    ///
+    /// Returns `ProviderError::InvalidUri` for a wrong scheme, missing symbol or
+    /// extra path segments, `ProviderError::ResourceNotFound` for an unknown kind,
+    /// and the usual symbol validation errors for a malformed symbol.
+    pub fn parse(uri: &str) -> Result<Self> {
+        let malformed = || {
+            ProviderError::InvalidUri(format!(
+                "'{}' (expected {}://{{market|ticker|orderbook|report}}/{{symbol}})",
+                uri, RESOURCE_SCHEME
+            ))
+        };
+
+        let path = uri
+            .split_once("://")
+            .filter(|(scheme, _)| *scheme == RESOURCE_SCHEME)
+            .map(|(_, path)| path)
+            .ok_or_else(malformed)?;
+        let (kind, symbol) = path.split_once('/').ok_or_else(malformed)?;
+        if symbol.is_empty() || symbol.contains('/') {
+            return Err(malformed());
+        }
+
+        let constructor: fn(String) -> Self = match kind {
+            "market" => Self::Market,
+            "ticker" => Self::Ticker,
+            "orderbook" => Self::OrderBook,
+            "report" => Self::Report,
+            _ => return Err(ProviderError::ResourceNotFound(uri.to_string())),
+        };
+        let symbol = crate::binance::symbol::validate_and_normalize(symbol)?;
+        Ok(constructor(symbol))
+    }
+
+    /// Symbol the resource is about
+    pub fn symbol(&self) -> &str {
+        match self {
+            Self::Market(symbol)
+            | Self::Ticker(symbol)
+            | Self::OrderBook(symbol)
+            | Self::Report(symbol) => symbol,
+        }
+    }
+
+    /// MIME type of the resource content
+    pub fn mime_type(&self) -> &'static str {
+        match self {
+            Self::Market(_) | Self::Report(_) => "text/markdown",
+            Self::Ticker(_) | Self::OrderBook(_) => "application/json",
+        }
+    }
+}
+
 /// Handle resource read request by routing based on URI
+///
+/// `binance://report/{symbol}` needs the report generator, which only exists
+/// with the `orderbook` feature.
 pub async fn handle_resource(
     client: &BinanceClient,
+    #[cfg(feature = "orderbook")] report_generator: Option<&crate::report::ReportGenerator>,
     request: &ResourceRequest,
 ) -> Result<ResourceResponse> {
     tracing::debug!("Handling resource URI: {}", request.uri);
 
-    // Parse URI and route to appropriate handler
-    if let Some(symbol) = parse_market_uri(&request.uri) {
-        handle_market_resource(client, &symbol).await
-    } else {
-        Err(ProviderError::ResourceNotFound(request.uri.clone()))
-    }
-}
-
-// ========== URI Parsers ==========
-
-/// Parse binance://market/{symbol} URI
-fn parse_market_uri(uri: &str) -> Option<String> {
-    if let Some(rest) = uri.strip_prefix("binance://market/") {
-        if !rest.is_empty() {
-            return Some(rest.to_uppercase());
+    let resource = ResourceUri::parse(&request.uri)?;
+    let content = match &resource {
+        ResourceUri::Market(symbol) => handle_market_resource(client, symbol).await?,
+        ResourceUri::Ticker(symbol) => {
+            let ticker = client
+                .get_24hr_ticker(symbol)
+                .await
+                .map_err(ProviderError::from)?;
+            serde_json::to_vec(&ticker)?
         }
-    }
-    None
-}
+        ResourceUri::OrderBook(symbol) => {
+            let orderbook = client
+                .get_order_book(symbol, Some(RESOURCE_ORDERBOOK_LIMIT))
+                .await
+                .map_err(ProviderError::from)?;
+            serde_json::to_vec(&orderbook)?
+        }
+        #[cfg(feature = "orderbook")]
+        ResourceUri::Report(symbol) => {
+            let generator = report_generator.ok_or_else(|| {
+                ProviderError::Validation("Report generator not initialized".to_string())
+            })?;
+            let report = generator
+                .generate_report(
+                    symbol,
+                    crate::report::ReportOptions::default(),
+                    &tokio_util::sync::CancellationToken::new(),
+                )
+                .await
+                .map_err(ProviderError::BinanceApi)?;
+            report.markdown_content.into_bytes()
+        }
+        #[cfg(not(feature = "orderbook"))]
+        ResourceUri::Report(_) => {
+            return Err(ProviderError::ResourceNotFound(format!(
+                "{} (reports require the orderbook feature)",
+                request.uri
+            )))
+        }
+    };
 
-/// Parse binance://account/trades/{symbol} URI
+    Ok(ResourceResponse {
+        content,
+        mime_type: resource.mime_type().to_string(),
+        error: String::new(),
+    })
+}
 
 // ========== Resource Handlers ==========
 
-async fn handle_market_resource(client: &BinanceClient, symbol: &str) -> Result<ResourceResponse> {
+async fn handle_market_resource(client: &BinanceClient, symbol: &str) -> Result<Vec<u8>> {
     tracing::info!("Fetching market resource for symbol: {}", symbol);
 
     // Fetch real market data from Binance API
     let ticker = client
         .get_24hr_ticker(symbol)
         .await
-        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+        .map_err(ProviderError::from)?;
 
     let orderbook = client
         .get_order_book(symbol, Some(5))
         .await
-        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+        .map_err(ProviderError::from)?;
 
     // Format bid/ask data
     let top_bids = orderbook
@@ -148,9 +257,69 @@ async fn handle_market_resource(client: &BinanceClient, symbol: &str) -> Result<
         timestamp
     );
 
-    Ok(ResourceResponse {
-        content: content.as_bytes().to_vec(),
-        mime_type: "text/markdown".to_string(),
-        error: String::new(),
-    })
+    Ok(content.into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_resource_uris() {
+        let cases = [
+            (
+                "binance://market/BTCUSDT",
+                ResourceUri::Market("BTCUSDT".to_string()),
+            ),
+            (
+                "binance://ticker/BTCUSDT",
+                ResourceUri::Ticker("BTCUSDT".to_string()),
+            ),
+            (
+                "binance://orderbook/ethusdt",
+                ResourceUri::OrderBook("ETHUSDT".to_string()),
+            ),
+            (
+                "binance://report/BTCUSDT",
+                ResourceUri::Report("BTCUSDT".to_string()),
+            ),
+        ];
+        for (uri, expected) in cases {
+            assert_eq!(ResourceUri::parse(uri).unwrap(), expected, "{}", uri);
+        }
+
+        assert_eq!(
+            ResourceUri::parse("binance://ticker/BTCUSDT")
+                .unwrap()
+                .mime_type(),
+            "application/json"
+        );
+        assert_eq!(
+            ResourceUri::parse("binance://report/BTCUSDT")
+                .unwrap()
+                .mime_type(),
+            "text/markdown"
+        );
+    }
+
+    #[test]
+    fn test_malformed_uri_is_rejected() {
+        for uri in [
+            "binance:/ticker/BTCUSDT",
+            "http://ticker/BTCUSDT",
+            "binance://ticker",
+            "binance://ticker/",
+            "binance://ticker/BTCUSDT/extra",
+        ] {
+            assert!(
+                matches!(ResourceUri::parse(uri), Err(ProviderError::InvalidUri(_))),
+                "{}",
+                uri
+            );
+        }
+        assert!(matches!(
+            ResourceUri::parse("binance://candles/BTCUSDT"),
+            Err(ProviderError::ResourceNotFound(_))
+        ));
+    }
 }
@@ -0,0 +1,307 @@
+//! Live market event streaming for the gRPC `Stream` RPC
+//!
+//! Topics use Binance stream naming: `{symbol}@ticker` or `{symbol}@depth`
+//! (e.g. `btcusdt@ticker`). Each topic is backed by a single upstream WebSocket
+//! task fanning out over a `broadcast` channel; every gRPC subscriber gets its own
+//! forwarder that wraps updates in CloudEvents 1.0 envelopes.
+//!
+//! When a client disconnects, its forwarder exits and drops its broadcast
+//! receiver. The upstream WebSocket task is aborted once the last subscriber
+//! for a topic is gone.
+
+use crate::error::{ProviderError, Result};
+use crate::pb::{CloudEvent, Json};
+use serde::Serialize;
+use tokio::sync::{broadcast, mpsc};
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::Status;
+
+#[cfg(feature = "websocket")]
+use crate::binance::websocket::{BinanceWebSocketClient, DepthUpdate, TickerUpdate};
+#[cfg(feature = "websocket")]
+use std::collections::HashMap;
+#[cfg(feature = "websocket")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "websocket")]
+use tokio::task::JoinHandle;
+
+/// CloudEvents `source` attribute for all events emitted by this provider
+pub const EVENT_SOURCE: &str = "urn:provider:binance-rs";
+
+/// Capacity of each per-topic broadcast channel
+const TOPIC_CHANNEL_CAPACITY: usize = 256;
+
+/// Capacity of each per-client outbound gRPC channel
+const CLIENT_CHANNEL_CAPACITY: usize = 64;
+
+/// Market event kinds available over `Stream`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamEventKind {
+    /// 24hr rolling ticker updates (`@ticker`)
+    Ticker,
+    /// Order book diff updates (`@depth`)
+    Depth,
+}
+
+impl StreamEventKind {
+    /// CloudEvents `type` attribute for this kind
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            StreamEventKind::Ticker => "binance.ticker.updated",
+            StreamEventKind::Depth => "binance.depth.updated",
+        }
+    }
+}
+
+/// Parse a `StreamRequest.topic` of the form `{symbol}@{ticker|depth}`
+///
+/// Symbols are normalized to uppercase.
+pub fn parse_topic(topic: &str) -> Result<(String, StreamEventKind)> {
+    let (symbol, kind) = topic.split_once('@').ok_or_else(|| {
+        ProviderError::Validation(format!(
+            "Invalid stream topic '{}': expected '{{symbol}}@ticker' or '{{symbol}}@depth'",
+            topic
+        ))
+    })?;
+
+    if symbol.is_empty() || !symbol.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(ProviderError::Validation(format!(
+            "Invalid symbol in stream topic '{}'",
+            topic
+        )));
+    }
+
+    let kind = match kind.to_ascii_lowercase().as_str() {
+        "ticker" => StreamEventKind::Ticker,
+        "depth" => StreamEventKind::Depth,
+        other => {
+            return Err(ProviderError::Validation(format!(
+                "Unsupported stream event type '{}': expected 'ticker' or 'depth'",
+                other
+            )))
+        }
+    };
+
+    Ok((symbol.to_uppercase(), kind))
+}
+
+/// Forward broadcast updates to a gRPC client as CloudEvents
+///
+/// Spawns a task that runs until either the broadcast sender closes or the
+/// client stream is dropped. `on_close` runs after the forwarder exits and its
+/// receiver has been dropped (used to release the upstream subscription).
+pub fn forward_events<T, F>(
+    mut rx: broadcast::Receiver<T>,
+    topic: String,
+    kind: StreamEventKind,
+    on_close: F,
+) -> ReceiverStream<std::result::Result<CloudEvent, Status>>
+where
+    T: Serialize + Clone + Send + 'static,
+    F: FnOnce() + Send + 'static,
+{
+    let (tx, client_rx) = mpsc::channel(CLIENT_CHANNEL_CAPACITY);
+
+    tokio::spawn(async move {
+        let mut sequence: u64 = 0;
+
+        loop {
+            let update = tokio::select! {
+                // Client went away: stop forwarding without waiting for the next update
+                _ = tx.closed() => {
+                    tracing::info!(topic = %topic, "Stream client disconnected");
+                    break;
+                }
+                received = rx.recv() => match received {
+                    Ok(update) => update,
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        tracing::warn!(topic = %topic, skipped, "Stream subscriber lagging, events dropped");
+                        continue;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => {
+                        tracing::info!(topic = %topic, "Upstream stream closed");
+                        break;
+                    }
+                },
+            };
+
+            sequence += 1;
+            let event = match to_cloud_event(&topic, kind, sequence, &update) {
+                Ok(event) => event,
+                Err(e) => {
+                    tracing::error!(topic = %topic, error = %e, "Failed to serialize stream event");
+                    continue;
+                }
+            };
+
+            if tx.send(Ok(event)).await.is_err() {
+                tracing::info!(topic = %topic, "Stream client disconnected");
+                break;
+            }
+        }
+
+        drop(rx);
+        on_close();
+    });
+
+    ReceiverStream::new(client_rx)
+}
+
+/// Wrap a market update in a CloudEvents 1.0 envelope
+fn to_cloud_event<T: Serialize>(
+    topic: &str,
+    kind: StreamEventKind,
+    sequence: u64,
+    update: &T,
+) -> std::result::Result<CloudEvent, serde_json::Error> {
+    Ok(CloudEvent {
+        id: format!("{}-{}", topic, sequence),
+        source: EVENT_SOURCE.to_string(),
+        r#type: kind.event_type().to_string(),
+        time: chrono::Utc::now().to_rfc3339(),
+        specversion: "1.0".to_string(),
+        data: Some(Json {
+            value: serde_json::to_vec(update)?,
+        }),
+    })
+}
+
+/// Upstream subscription shared by all clients of one topic
+#[cfg(feature = "websocket")]
+struct TopicChannel<T> {
+    sender: broadcast::Sender<T>,
+    task: JoinHandle<()>,
+}
+
+/// Registry of live upstream WebSocket subscriptions keyed by symbol
+#[cfg(feature = "websocket")]
+#[derive(Clone, Default)]
+pub struct StreamHub {
+    ws_client: BinanceWebSocketClient,
+    tickers: Arc<Mutex<HashMap<String, TopicChannel<TickerUpdate>>>>,
+    depths: Arc<Mutex<HashMap<String, TopicChannel<DepthUpdate>>>>,
+}
+
+#[cfg(feature = "websocket")]
+impl StreamHub {
+    /// Create an empty hub using the default Binance WebSocket endpoint
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Subscribe a gRPC client to `topic`, starting the upstream stream if needed
+    pub fn subscribe(
+        &self,
+        topic: &str,
+    ) -> Result<ReceiverStream<std::result::Result<CloudEvent, Status>>> {
+        let (symbol, kind) = parse_topic(topic)?;
+        let topic = format!("{}@{}", symbol.to_lowercase(), topic_suffix(kind));
+
+        tracing::info!(topic = %topic, "New stream subscription");
+
+        let stream = match kind {
+            StreamEventKind::Ticker => {
+                let rx = self.ticker_receiver(&symbol);
+                let tickers = self.tickers.clone();
+                forward_events(rx, topic, kind, move || release(&tickers, &symbol))
+            }
+            StreamEventKind::Depth => {
+                let rx = self.depth_receiver(&symbol);
+                let depths = self.depths.clone();
+                forward_events(rx, topic, kind, move || release(&depths, &symbol))
+            }
+        };
+
+        Ok(stream)
+    }
+
+    fn ticker_receiver(&self, symbol: &str) -> broadcast::Receiver<TickerUpdate> {
+        let mut tickers = self.tickers.lock().unwrap();
+        if let Some(channel) = tickers.get(symbol) {
+            return channel.sender.subscribe();
+        }
+
+        let (sender, rx) = broadcast::channel(TOPIC_CHANNEL_CAPACITY);
+        let client = self.ws_client.clone();
+        let upstream_tx = sender.clone();
+        let upstream_symbol = symbol.to_string();
+        let task = tokio::spawn(async move {
+            if let Err(e) = client
+                .ticker_stream_task(&upstream_symbol, upstream_tx)
+                .await
+            {
+                tracing::error!(symbol = %upstream_symbol, error = %e, "Ticker stream failed");
+            }
+        });
+
+        tickers.insert(symbol.to_string(), TopicChannel { sender, task });
+        rx
+    }
+
+    fn depth_receiver(&self, symbol: &str) -> broadcast::Receiver<DepthUpdate> {
+        let mut depths = self.depths.lock().unwrap();
+        if let Some(channel) = depths.get(symbol) {
+            return channel.sender.subscribe();
+        }
+
+        let (sender, rx) = broadcast::channel(TOPIC_CHANNEL_CAPACITY);
+        let client = self.ws_client.clone();
+        let upstream_tx = sender.clone();
+        let upstream_symbol = symbol.to_string();
+        let task = tokio::spawn(async move {
+            if let Err(e) = client
+                .depth_stream_task(&upstream_symbol, upstream_tx)
+                .await
+            {
+                tracing::error!(symbol = %upstream_symbol, error = %e, "Depth stream failed");
+            }
+        });
+
+        depths.insert(symbol.to_string(), TopicChannel { sender, task });
+        rx
+    }
+}
+
+/// Stop the upstream task for `symbol` once no subscribers remain
+#[cfg(feature = "websocket")]
+fn release<T>(channels: &Mutex<HashMap<String, TopicChannel<T>>>, symbol: &str) {
+    let mut channels = channels.lock().unwrap();
+    if let Some(channel) = channels.get(symbol) {
+        if channel.sender.receiver_count() == 0 {
+            channel.task.abort();
+            channels.remove(symbol);
+            tracing::info!(symbol = %symbol, "Last subscriber left, upstream stream stopped");
+        }
+    }
+}
+
+#[cfg(feature = "websocket")]
+fn topic_suffix(kind: StreamEventKind) -> &'static str {
+    match kind {
+        StreamEventKind::Ticker => "ticker",
+        StreamEventKind::Depth => "depth",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_topic() {
+        let (symbol, kind) = parse_topic("btcusdt@ticker").unwrap();
+        assert_eq!(symbol, "BTCUSDT");
+        assert_eq!(kind, StreamEventKind::Ticker);
+
+        let (_, kind) = parse_topic("ETHUSDT@DEPTH").unwrap();
+        assert_eq!(kind, StreamEventKind::Depth);
+    }
+
+    #[test]
+    fn test_parse_topic_invalid() {
+        assert!(parse_topic("btcusdt").is_err());
+        assert!(parse_topic("@ticker").is_err());
+        assert!(parse_topic("btcusdt@kline").is_err());
+        assert!(parse_topic("btc/usdt@ticker").is_err());
+    }
+}
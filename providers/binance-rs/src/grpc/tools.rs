@@ -1,11 +1,16 @@
 use crate::binance::client::BinanceClient;
 use crate::error::{ProviderError, Result};
+use crate::pagination::Page;
 use crate::pb::{InvokeRequest, InvokeResponse, Json};
 
 #[cfg(feature = "orderbook")]
 use crate::orderbook::OrderBookManager;
 #[cfg(feature = "orderbook")]
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+
+/// Items Binance returns from list endpoints when no `limit` is given
+const BINANCE_DEFAULT_LIMIT: u32 = 500;
 
 // Helper functions for working with Json type
 fn parse_json(json_opt: &Option<Json>) -> Result<serde_json::Value> {
@@ -19,7 +24,37 @@ fn parse_json(json_opt: &Option<Json>) -> Result<serde_json::Value> {
     serde_json::from_str(json_str).map_err(|e| ProviderError::Json(e))
 }
 
+/// Rewrite the payload's `symbol` field to its validated, normalized form
+///
+/// Returns `None` when the payload has no `symbol` field (nothing to rewrite).
+fn normalize_symbol_arg(request: &InvokeRequest) -> Result<Option<InvokeRequest>> {
+    let Ok(mut args) = parse_json(&request.payload) else {
+        // Leave missing or malformed payloads for the handler to report
+        return Ok(None);
+    };
+
+    let Some(raw) = args.get("symbol") else {
+        return Ok(None);
+    };
+    let raw = raw
+        .as_str()
+        .ok_or_else(|| ProviderError::Validation("Field 'symbol' must be a string".to_string()))?;
+
+    let symbol = crate::binance::symbol::validate_and_normalize(raw)?;
+    args["symbol"] = serde_json::Value::String(symbol);
+
+    Ok(Some(InvokeRequest {
+        payload: Some(Json {
+            value: serde_json::to_vec(&args)?,
+        }),
+        ..request.clone()
+    }))
+}
+
 /// Route tool invocation to appropriate handler
+///
+/// `cancel` aborts long-running tools (currently the market report) between
+/// steps; transports cancel it when the caller disconnects.
 pub async fn route_tool(
     client: &BinanceClient,
     #[cfg(feature = "orderbook")] orderbook_manager: Option<Arc<OrderBookManager>>,
@@ -35,14 +70,102 @@ pub async fn route_tool(
     #[cfg(feature = "orderbook")] report_generator: Option<Arc<crate::report::ReportGenerator>>,
     #[cfg(not(feature = "orderbook"))] _report_generator: Option<()>,
     request: &InvokeRequest,
+    #[cfg(feature = "orderbook")] cancel: &CancellationToken,
+    #[cfg(not(feature = "orderbook"))] _cancel: &CancellationToken,
 ) -> Result<InvokeResponse> {
     tracing::debug!("Routing tool: {}", request.tool_name);
 
+    // Validate and normalize `symbol` once here so every handler receives e.g. "BTCUSDT"
+    let normalized = normalize_symbol_arg(request)?;
+    let request = normalized.as_ref().unwrap_or(request);
+
     let result = match request.tool_name.as_str() {
         // Unified market data report - THE ONLY PUBLIC TOOL (per FR-002)
         #[cfg(feature = "orderbook")]
         "binance.generate_market_report" => {
-            handle_generate_market_report(report_generator.as_ref(), request).await?
+            handle_generate_market_report(report_generator.as_ref(), request, cancel).await?
+        }
+
+        // OrderBook tools
+        #[cfg(feature = "orderbook")]
+        "binance.get_orderbook_integrity" => {
+            handle_orderbook_integrity(orderbook_manager.as_ref(), request).await?
+        }
+        #[cfg(feature = "orderbook")]
+        "binance.get_slippage_estimate" => {
+            handle_slippage_estimate(orderbook_manager.as_ref(), request).await?
+        }
+        #[cfg(feature = "orderbook")]
+        "binance.get_slippage_curve" => {
+            handle_slippage_curve(orderbook_manager.as_ref(), request).await?
+        }
+        #[cfg(feature = "orderbook")]
+        "binance.subscribe_symbol" => {
+            handle_symbol_subscription(orderbook_manager.as_ref(), request, true).await?
+        }
+        #[cfg(feature = "orderbook")]
+        "binance.unsubscribe_symbol" => {
+            handle_symbol_subscription(orderbook_manager.as_ref(), request, false).await?
+        }
+
+        // Execution benchmarks from stored trades
+        #[cfg(feature = "orderbook_analytics")]
+        "binance.get_execution_benchmarks" => {
+            handle_get_execution_benchmarks(trade_storage.as_ref(), request).await?
+        }
+
+        // Imbalance time series from stored snapshots
+        #[cfg(feature = "orderbook_analytics")]
+        "binance.get_imbalance_history" => {
+            handle_get_imbalance_history(analytics_storage.as_ref(), request).await?
+        }
+        #[cfg(feature = "orderbook_analytics")]
+        "binance.get_health_trend" => {
+            handle_get_health_trend(analytics_storage.as_ref(), request).await?
+        }
+        #[cfg(feature = "orderbook_analytics")]
+        "binance.get_spread_stats" => {
+            handle_get_spread_stats(analytics_storage.as_ref(), request).await?
+        }
+        #[cfg(feature = "orderbook_analytics")]
+        "binance.get_depth_heatmap" => {
+            handle_get_depth_heatmap(analytics_storage.as_ref(), request).await?
+        }
+
+        // Raw market data passthrough
+        "binance.get_agg_trades" => handle_get_agg_trades(client, request).await?,
+        "binance.get_recent_trades" => handle_get_recent_trades(client, request).await?,
+        "binance.get_klines" => handle_get_klines(client, request).await?,
+        "binance.get_book_ticker" => handle_get_book_ticker(client, request).await?,
+        "binance.get_price_change" => handle_get_price_change(client, request).await?,
+        "binance.scan_market" => handle_scan_market(client, request).await?,
+
+        // Signed account data (requires BINANCE_API_KEY / BINANCE_API_SECRET)
+        "binance.get_account" => handle_get_account(client, request).await?,
+
+        // Server health
+        "binance.health" => {
+            handle_health(
+                client,
+                #[cfg(feature = "orderbook")]
+                orderbook_manager.as_deref(),
+                #[cfg(feature = "orderbook_analytics")]
+                analytics_storage.as_deref(),
+            )
+            .await?
+        }
+        "binance.self_test" => {
+            handle_self_test(
+                client,
+                #[cfg(feature = "orderbook")]
+                orderbook_manager.as_deref(),
+                #[cfg(feature = "orderbook_analytics")]
+                analytics_storage.as_deref(),
+                #[cfg(feature = "orderbook")]
+                report_generator.as_deref(),
+                request,
+            )
+            .await?
         }
 
         // Unknown tool
@@ -52,6 +175,67 @@ pub async fn route_tool(
     Ok(InvokeResponse {
         result: Some(result),
         error: String::new(),
+        correlation_id: request.correlation_id.clone(),
+    })
+}
+
+// ========== Server Health Tool Handler ==========
+
+async fn handle_health(
+    client: &BinanceClient,
+    #[cfg(feature = "orderbook")] orderbook_manager: Option<&OrderBookManager>,
+    #[cfg(feature = "orderbook_analytics")] analytics_storage: Option<
+        &crate::orderbook::analytics::SnapshotStorage,
+    >,
+) -> Result<Json> {
+    tracing::info!("Getting server health");
+
+    let health = crate::health::check_health(
+        client,
+        #[cfg(feature = "orderbook")]
+        orderbook_manager,
+        #[cfg(feature = "orderbook_analytics")]
+        analytics_storage,
+    )
+    .await;
+
+    Ok(Json {
+        value: serde_json::to_vec(&health)?,
+    })
+}
+
+async fn handle_self_test(
+    client: &BinanceClient,
+    #[cfg(feature = "orderbook")] orderbook_manager: Option<&OrderBookManager>,
+    #[cfg(feature = "orderbook_analytics")] analytics_storage: Option<
+        &crate::orderbook::analytics::SnapshotStorage,
+    >,
+    #[cfg(feature = "orderbook")] report_generator: Option<&crate::report::ReportGenerator>,
+    request: &InvokeRequest,
+) -> Result<Json> {
+    let args = parse_json(&request.payload)?;
+    let raw = args["symbol"]
+        .as_str()
+        .ok_or_else(|| ProviderError::Validation("Missing required field: symbol".to_string()))?;
+    // Every check keys on the symbol, so normalize it once up front
+    let symbol = crate::binance::symbol::validate_and_normalize(raw)?;
+
+    tracing::info!("Running self-test for canary symbol: {}", symbol);
+
+    let report = crate::self_test::run_self_test(
+        client,
+        #[cfg(feature = "orderbook")]
+        orderbook_manager,
+        #[cfg(feature = "orderbook_analytics")]
+        analytics_storage,
+        #[cfg(feature = "orderbook")]
+        report_generator,
+        &symbol,
+    )
+    .await;
+
+    Ok(Json {
+        value: serde_json::to_vec(&report)?,
     })
 }
 
@@ -69,7 +253,7 @@ async fn handle_get_ticker(client: &BinanceClient, request: &InvokeRequest) -> R
     let ticker = client
         .get_24hr_ticker(symbol)
         .await
-        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+        .map_err(ProviderError::from)?;
 
     // Serialize the response
     let result = serde_json::to_value(&ticker)?;
@@ -96,7 +280,7 @@ async fn handle_get_orderbook(client: &BinanceClient, request: &InvokeRequest) -
     let orderbook = client
         .get_order_book(symbol, limit)
         .await
-        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+        .map_err(ProviderError::from)?;
 
     let result = serde_json::to_value(&orderbook)?;
 
@@ -122,9 +306,252 @@ async fn handle_get_recent_trades(client: &BinanceClient, request: &InvokeReques
     let trades = client
         .get_recent_trades(symbol, limit)
         .await
-        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+        .map_err(ProviderError::from)?;
+
+    // Only the most recent trades are served, so a capped page has no cursor
+    let truncated = trades.len() >= limit.unwrap_or(BINANCE_DEFAULT_LIMIT) as usize;
+    let result = serde_json::to_value(Page::new(trades, truncated, None))?;
+
+    Ok(Json {
+        value: serde_json::to_string(&result)?.as_bytes().to_vec(),
+    })
+}
+
+async fn handle_get_book_ticker(client: &BinanceClient, request: &InvokeRequest) -> Result<Json> {
+    let args = parse_json(&request.payload)?;
+    let symbol = args["symbol"]
+        .as_str()
+        .ok_or_else(|| ProviderError::Validation("Missing required field: symbol".to_string()))?;
+
+    tracing::info!("Getting book ticker for symbol: {}", symbol);
+
+    let ticker = client
+        .get_book_ticker(symbol)
+        .await
+        .map_err(ProviderError::from)?;
+
+    let result = serde_json::to_value(&ticker)?;
+
+    Ok(Json {
+        value: serde_json::to_string(&result)?.as_bytes().to_vec(),
+    })
+}
+
+async fn handle_get_price_change(client: &BinanceClient, request: &InvokeRequest) -> Result<Json> {
+    use crate::price_change::{fetch_price_change, PriceWindow};
+
+    let args = parse_json(&request.payload)?;
+    let symbol = args["symbol"]
+        .as_str()
+        .ok_or_else(|| ProviderError::Validation("Missing required field: symbol".to_string()))?;
+    let raw_window = args["window"]
+        .as_str()
+        .ok_or_else(|| ProviderError::Validation("Missing required field: window".to_string()))?;
+    let window = PriceWindow::parse(raw_window).ok_or_else(|| {
+        ProviderError::Validation(format!(
+            "window must be one of 1h, 4h, 12h, 1d, 7d (got '{}')",
+            raw_window
+        ))
+    })?;
+
+    tracing::info!(
+        "Getting {} price change for symbol: {}",
+        window.label(),
+        symbol
+    );
+
+    let change = fetch_price_change(client, symbol, window)
+        .await
+        .map_err(ProviderError::from)?
+        .ok_or_else(|| {
+            ProviderError::BinanceApi(format!(
+                "No klines available for {} over {}",
+                symbol,
+                window.label()
+            ))
+        })?;
 
-    let result = serde_json::to_value(&trades)?;
+    Ok(Json {
+        value: serde_json::to_vec(&change)?,
+    })
+}
+
+async fn handle_scan_market(client: &BinanceClient, request: &InvokeRequest) -> Result<Json> {
+    use crate::market_scan::{scan_tickers, ScanSortBy, DEFAULT_SCAN_TOP_N, MAX_SCAN_TOP_N};
+    use crate::pagination::parse_offset_cursor;
+
+    let args = parse_json(&request.payload)?;
+    let quote = args["quote"].as_str().unwrap_or("USDT");
+    let min_volume = args["min_volume"].as_f64().unwrap_or(0.0);
+    let top_n = args["top_n"]
+        .as_u64()
+        .map(|n| n as usize)
+        .unwrap_or(DEFAULT_SCAN_TOP_N);
+    let raw_sort_by = args["sort_by"].as_str().unwrap_or("volume");
+    let offset = match args["cursor"].as_str() {
+        Some(raw) => parse_offset_cursor(raw).ok_or_else(|| {
+            ProviderError::Validation(format!(
+                "cursor must be a next_cursor value (got '{}')",
+                raw
+            ))
+        })?,
+        None => 0,
+    };
+
+    if quote.trim().is_empty() {
+        return Err(ProviderError::Validation(
+            "quote must not be empty".to_string(),
+        ));
+    }
+    if !min_volume.is_finite() || min_volume < 0.0 {
+        return Err(ProviderError::Validation(format!(
+            "min_volume must be >= 0 (got {})",
+            min_volume
+        )));
+    }
+    if !(1..=MAX_SCAN_TOP_N).contains(&top_n) {
+        return Err(ProviderError::Validation(format!(
+            "top_n must be between 1 and {} (got {})",
+            MAX_SCAN_TOP_N, top_n
+        )));
+    }
+    let sort_by = ScanSortBy::parse(raw_sort_by).ok_or_else(|| {
+        ProviderError::Validation(format!(
+            "sort_by must be one of volume, gainers, losers, movers (got '{}')",
+            raw_sort_by
+        ))
+    })?;
+
+    tracing::info!(
+        "Scanning {} market by {} (min volume {}, top {})",
+        quote,
+        sort_by.label(),
+        min_volume,
+        top_n
+    );
+
+    // exchangeInfo gives each symbol's quote asset; suffixes are ambiguous
+    let (tickers, exchange_info) = tokio::try_join!(
+        client.get_all_24hr_tickers(),
+        client.get_exchange_info(None)
+    )
+    .map_err(ProviderError::from)?;
+    let scan = scan_tickers(
+        &tickers,
+        &exchange_info.symbols,
+        quote,
+        min_volume,
+        top_n,
+        sort_by,
+        offset,
+    );
+
+    Ok(Json {
+        value: serde_json::to_vec(&scan)?,
+    })
+}
+
+async fn handle_get_account(client: &BinanceClient, request: &InvokeRequest) -> Result<Json> {
+    if !client.has_credentials() {
+        return Err(ProviderError::AuthRequired(
+            "binance.get_account needs BINANCE_API_KEY and BINANCE_API_SECRET to be set"
+                .to_string(),
+        ));
+    }
+
+    // Payload is optional: no arguments means the defaults
+    let args = match request.payload {
+        Some(_) => parse_json(&request.payload)?,
+        None => serde_json::Value::Null,
+    };
+    let omit_zero_balances = args["omit_zero_balances"].as_bool().unwrap_or(true);
+
+    tracing::info!(
+        "Getting account balances (omit_zero_balances: {})",
+        omit_zero_balances
+    );
+
+    let account = client
+        .get_account(omit_zero_balances)
+        .await
+        .map_err(ProviderError::from)?;
+
+    let result = serde_json::to_value(&account)?;
+
+    Ok(Json {
+        value: serde_json::to_string(&result)?.as_bytes().to_vec(),
+    })
+}
+
+async fn handle_get_agg_trades(client: &BinanceClient, request: &InvokeRequest) -> Result<Json> {
+    let args = parse_json(&request.payload)?;
+    let symbol = args["symbol"]
+        .as_str()
+        .ok_or_else(|| ProviderError::Validation("Missing required field: symbol".to_string()))?;
+    let limit = args["limit"].as_u64().map(|l| l as u32);
+    let cursor = args["cursor"]
+        .as_str()
+        .map(|raw| {
+            raw.trim().parse::<i64>().map_err(|_| {
+                ProviderError::Validation(format!(
+                    "cursor must be a next_cursor value (got '{}')",
+                    raw
+                ))
+            })
+        })
+        .transpose()?;
+    if cursor.is_some() && args["from_id"].as_i64().is_some() {
+        return Err(ProviderError::Validation(
+            "cursor cannot be combined with from_id".to_string(),
+        ));
+    }
+    // The cursor is the aggregate trade ID after the previous page's last trade
+    let from_id = cursor.or_else(|| args["from_id"].as_i64());
+    let start_time = args["start_time"].as_i64();
+    let end_time = args["end_time"].as_i64();
+
+    if let Some(lim) = limit {
+        if !(1..=1000).contains(&lim) {
+            return Err(ProviderError::Validation(format!(
+                "limit must be between 1 and 1000 (got {})",
+                lim
+            )));
+        }
+    }
+    if from_id.is_some() && (start_time.is_some() || end_time.is_some()) {
+        return Err(ProviderError::Validation(
+            "from_id/cursor cannot be combined with start_time/end_time".to_string(),
+        ));
+    }
+    if let (Some(start), Some(end)) = (start_time, end_time) {
+        if end < start {
+            return Err(ProviderError::Validation(
+                "end_time must be >= start_time".to_string(),
+            ));
+        }
+    }
+
+    tracing::info!(
+        "Getting aggregate trades for symbol: {}, limit: {:?}, from_id: {:?}, range: {:?}-{:?}",
+        symbol,
+        limit,
+        from_id,
+        start_time,
+        end_time
+    );
+
+    // Call actual Binance API
+    let trades = client
+        .get_agg_trades(symbol, limit, from_id, start_time, end_time)
+        .await
+        .map_err(ProviderError::from)?;
+
+    let page = Page::from_limited(
+        trades,
+        limit.unwrap_or(BINANCE_DEFAULT_LIMIT) as usize,
+        |last| (last.agg_trade_id + 1).to_string(),
+    );
+    let result = serde_json::to_value(&page)?;
 
     Ok(Json {
         value: serde_json::to_string(&result)?.as_bytes().to_vec(),
@@ -152,9 +579,11 @@ async fn handle_get_klines(client: &BinanceClient, request: &InvokeRequest) -> R
     let klines = client
         .get_klines(symbol, interval, limit)
         .await
-        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+        .map_err(ProviderError::from)?;
 
-    let result = serde_json::to_value(&klines)?;
+    // Only the most recent candles are served, so a capped page has no cursor
+    let truncated = klines.len() >= limit.unwrap_or(BINANCE_DEFAULT_LIMIT) as usize;
+    let result = serde_json::to_value(Page::new(klines, truncated, None))?;
 
     Ok(Json {
         value: serde_json::to_string(&result)?.as_bytes().to_vec(),
@@ -192,7 +621,7 @@ async fn handle_get_avg_price(client: &BinanceClient, request: &InvokeRequest) -
     let ticker = client
         .get_ticker_price(symbol)
         .await
-        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+        .map_err(ProviderError::from)?;
 
     let result = serde_json::json!({
         "symbol": ticker.symbol,
@@ -228,7 +657,7 @@ async fn handle_orderbook_l1(
     // Call orderbook tool
     let metrics = get_orderbook_metrics(manager.clone(), params)
         .await
-        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+        .map_err(ProviderError::from)?;
 
     let result = serde_json::to_value(&metrics)?;
 
@@ -259,7 +688,7 @@ async fn handle_orderbook_l2(
     // Call orderbook tool
     let depth = get_orderbook_depth(manager.clone(), params)
         .await
-        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+        .map_err(ProviderError::from)?;
 
     let result = serde_json::to_value(&depth)?;
 
@@ -285,7 +714,7 @@ async fn handle_orderbook_health(
     // Call orderbook tool
     let health = get_orderbook_health(manager.clone())
         .await
-        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+        .map_err(ProviderError::from)?;
 
     let result = serde_json::to_value(&health)?;
 
@@ -294,6 +723,137 @@ async fn handle_orderbook_health(
     })
 }
 
+#[cfg(feature = "orderbook")]
+async fn handle_orderbook_integrity(
+    manager: Option<&Arc<OrderBookManager>>,
+    request: &InvokeRequest,
+) -> Result<Json> {
+    use crate::orderbook::tools::{get_orderbook_integrity, GetOrderBookIntegrityParams};
+
+    // Check if manager is available
+    let manager = manager.ok_or_else(|| {
+        ProviderError::Validation("OrderBook manager not initialized".to_string())
+    })?;
+
+    // Parse parameters
+    let args = parse_json(&request.payload)?;
+    let params: GetOrderBookIntegrityParams = serde_json::from_value(args)
+        .map_err(|e| ProviderError::Validation(format!("Invalid parameters: {}", e)))?;
+
+    tracing::info!("Getting orderbook integrity for symbol: {}", params.symbol);
+
+    let integrity = get_orderbook_integrity(manager.clone(), params)
+        .await
+        .map_err(ProviderError::from)?;
+
+    let result = serde_json::to_value(&integrity)?;
+
+    Ok(Json {
+        value: serde_json::to_string(&result)?.as_bytes().to_vec(),
+    })
+}
+
+#[cfg(feature = "orderbook")]
+async fn handle_slippage_estimate(
+    manager: Option<&Arc<OrderBookManager>>,
+    request: &InvokeRequest,
+) -> Result<Json> {
+    use crate::orderbook::tools::{get_slippage_estimate, GetSlippageEstimateParams};
+
+    // Check if manager is available
+    let manager = manager.ok_or_else(|| {
+        ProviderError::Validation("OrderBook manager not initialized".to_string())
+    })?;
+
+    // Parse parameters
+    let args = parse_json(&request.payload)?;
+    let params: GetSlippageEstimateParams = serde_json::from_value(args)
+        .map_err(|e| ProviderError::Validation(format!("Invalid parameters: {}", e)))?;
+
+    tracing::info!(
+        "Estimating {:?} slippage for {} USD on symbol: {}",
+        params.side,
+        params.notional_usd,
+        params.symbol
+    );
+
+    // Call orderbook tool
+    let estimate = get_slippage_estimate(manager.clone(), params)
+        .await
+        .map_err(ProviderError::from)?;
+
+    let result = serde_json::to_value(&estimate)?;
+
+    Ok(Json {
+        value: serde_json::to_string(&result)?.as_bytes().to_vec(),
+    })
+}
+
+#[cfg(feature = "orderbook")]
+async fn handle_slippage_curve(
+    manager: Option<&Arc<OrderBookManager>>,
+    request: &InvokeRequest,
+) -> Result<Json> {
+    use crate::orderbook::tools::{get_slippage_curve, GetSlippageCurveParams};
+
+    // Check if manager is available
+    let manager = manager.ok_or_else(|| {
+        ProviderError::Validation("OrderBook manager not initialized".to_string())
+    })?;
+
+    // Parse parameters
+    let args = parse_json(&request.payload)?;
+    let params: GetSlippageCurveParams = serde_json::from_value(args)
+        .map_err(|e| ProviderError::Validation(format!("Invalid parameters: {}", e)))?;
+
+    tracing::info!("Estimating slippage curve for symbol: {}", params.symbol);
+
+    // Call orderbook tool
+    let curve = get_slippage_curve(manager.clone(), params)
+        .await
+        .map_err(ProviderError::from)?;
+
+    let result = serde_json::to_value(&curve)?;
+
+    Ok(Json {
+        value: serde_json::to_string(&result)?.as_bytes().to_vec(),
+    })
+}
+
+#[cfg(feature = "orderbook")]
+async fn handle_symbol_subscription(
+    manager: Option<&Arc<OrderBookManager>>,
+    request: &InvokeRequest,
+    subscribe: bool,
+) -> Result<Json> {
+    use crate::orderbook::tools::{subscribe_symbol, unsubscribe_symbol, SymbolSubscriptionParams};
+
+    // Check if manager is available
+    let manager = manager.ok_or_else(|| {
+        ProviderError::Validation("OrderBook manager not initialized".to_string())
+    })?;
+
+    // Parse parameters
+    let args = parse_json(&request.payload)?;
+    let params: SymbolSubscriptionParams = serde_json::from_value(args)
+        .map_err(|e| ProviderError::Validation(format!("Invalid parameters: {}", e)))?;
+
+    let status = if subscribe {
+        tracing::info!("Subscribing orderbook for symbol: {}", params.symbol);
+        subscribe_symbol(manager.clone(), params).await
+    } else {
+        tracing::info!("Unsubscribing orderbook for symbol: {}", params.symbol);
+        unsubscribe_symbol(manager.clone(), params).await
+    }
+    .map_err(ProviderError::from)?;
+
+    let result = serde_json::to_value(&status)?;
+
+    Ok(Json {
+        value: serde_json::to_string(&result)?.as_bytes().to_vec(),
+    })
+}
+
 // ========== Advanced Analytics Tool Handlers (Feature-gated) ==========
 
 #[cfg(feature = "orderbook_analytics")]
@@ -402,22 +962,21 @@ async fn handle_detect_market_anomalies(
     storage: Option<&Arc<crate::orderbook::analytics::SnapshotStorage>>,
     request: &InvokeRequest,
 ) -> Result<Json> {
-    use crate::orderbook::analytics::tools::detect_market_anomalies;
+    use crate::orderbook::analytics::tools::{detect_market_anomalies, DetectAnomaliesParams};
 
     let storage = storage.ok_or_else(|| {
         ProviderError::Validation("Analytics storage not initialized".to_string())
     })?;
 
     let args = parse_json(&request.payload)?;
-    let symbol = args["symbol"]
-        .as_str()
-        .ok_or_else(|| ProviderError::Validation("Missing symbol".to_string()))?;
+    let params: DetectAnomaliesParams = serde_json::from_value(args)
+        .map_err(|e| ProviderError::Validation(format!("Invalid parameters: {}", e)))?;
 
-    let anomalies = detect_market_anomalies(storage.clone(), symbol)
+    let anomalies = detect_market_anomalies(storage.clone(), params)
         .await
         .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
 
-    let result = serde_json::to_value(&anomalies)?;
+    let result = serde_json::to_value(Page::complete(anomalies))?;
     Ok(Json {
         value: serde_json::to_string(&result)?.as_bytes().to_vec(),
     })
@@ -439,7 +998,7 @@ async fn handle_get_microstructure_health(
         .as_str()
         .ok_or_else(|| ProviderError::Validation("Missing symbol".to_string()))?;
 
-    let health = get_microstructure_health(storage.clone(), symbol)
+    let health = get_microstructure_health(storage.clone(), symbol, None)
         .await
         .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
 
@@ -468,7 +1027,133 @@ async fn handle_get_liquidity_vacuums(
         .await
         .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
 
-    let result = serde_json::to_value(&vacuums)?;
+    let result = serde_json::to_value(Page::complete(vacuums))?;
+    Ok(Json {
+        value: serde_json::to_string(&result)?.as_bytes().to_vec(),
+    })
+}
+
+#[cfg(feature = "orderbook_analytics")]
+async fn handle_get_execution_benchmarks(
+    trade_storage: Option<&Arc<crate::orderbook::analytics::TradeStorage>>,
+    request: &InvokeRequest,
+) -> Result<Json> {
+    use crate::orderbook::analytics::tools::{
+        get_execution_benchmarks, GetExecutionBenchmarksParams,
+    };
+
+    let trade_storage = trade_storage
+        .ok_or_else(|| ProviderError::Validation("Trade storage not initialized".to_string()))?;
+
+    let args = parse_json(&request.payload)?;
+    let params: GetExecutionBenchmarksParams = serde_json::from_value(args)
+        .map_err(|e| ProviderError::Validation(format!("Invalid parameters: {}", e)))?;
+
+    let benchmarks = get_execution_benchmarks(trade_storage.clone(), params)
+        .await
+        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+
+    let result = serde_json::to_value(&benchmarks)?;
+    Ok(Json {
+        value: serde_json::to_string(&result)?.as_bytes().to_vec(),
+    })
+}
+
+#[cfg(feature = "orderbook_analytics")]
+async fn handle_get_imbalance_history(
+    storage: Option<&Arc<crate::orderbook::analytics::SnapshotStorage>>,
+    request: &InvokeRequest,
+) -> Result<Json> {
+    use crate::orderbook::analytics::tools::{get_imbalance_history, GetImbalanceHistoryParams};
+
+    let storage = storage.ok_or_else(|| {
+        ProviderError::Validation("Analytics storage not initialized".to_string())
+    })?;
+
+    let args = parse_json(&request.payload)?;
+    let params: GetImbalanceHistoryParams = serde_json::from_value(args)
+        .map_err(|e| ProviderError::Validation(format!("Invalid parameters: {}", e)))?;
+
+    let history = get_imbalance_history(storage.clone(), params)
+        .await
+        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+
+    let result = serde_json::to_value(&history)?;
+    Ok(Json {
+        value: serde_json::to_string(&result)?.as_bytes().to_vec(),
+    })
+}
+
+#[cfg(feature = "orderbook_analytics")]
+async fn handle_get_health_trend(
+    storage: Option<&Arc<crate::orderbook::analytics::SnapshotStorage>>,
+    request: &InvokeRequest,
+) -> Result<Json> {
+    use crate::orderbook::analytics::tools::{get_health_trend, GetHealthTrendParams};
+
+    let storage = storage.ok_or_else(|| {
+        ProviderError::Validation("Analytics storage not initialized".to_string())
+    })?;
+
+    let args = parse_json(&request.payload)?;
+    let params: GetHealthTrendParams = serde_json::from_value(args)
+        .map_err(|e| ProviderError::Validation(format!("Invalid parameters: {}", e)))?;
+
+    let trend = get_health_trend(storage.clone(), params)
+        .await
+        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+
+    let result = serde_json::to_value(&trend)?;
+    Ok(Json {
+        value: serde_json::to_string(&result)?.as_bytes().to_vec(),
+    })
+}
+
+#[cfg(feature = "orderbook_analytics")]
+async fn handle_get_spread_stats(
+    storage: Option<&Arc<crate::orderbook::analytics::SnapshotStorage>>,
+    request: &InvokeRequest,
+) -> Result<Json> {
+    use crate::orderbook::analytics::tools::{get_spread_stats, GetSpreadStatsParams};
+
+    let storage = storage.ok_or_else(|| {
+        ProviderError::Validation("Analytics storage not initialized".to_string())
+    })?;
+
+    let args = parse_json(&request.payload)?;
+    let params: GetSpreadStatsParams = serde_json::from_value(args)
+        .map_err(|e| ProviderError::Validation(format!("Invalid parameters: {}", e)))?;
+
+    let stats = get_spread_stats(storage.clone(), params)
+        .await
+        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+
+    let result = serde_json::to_value(&stats)?;
+    Ok(Json {
+        value: serde_json::to_string(&result)?.as_bytes().to_vec(),
+    })
+}
+
+#[cfg(feature = "orderbook_analytics")]
+async fn handle_get_depth_heatmap(
+    storage: Option<&Arc<crate::orderbook::analytics::SnapshotStorage>>,
+    request: &InvokeRequest,
+) -> Result<Json> {
+    use crate::orderbook::analytics::tools::{get_depth_heatmap, GetDepthHeatmapParams};
+
+    let storage = storage.ok_or_else(|| {
+        ProviderError::Validation("Analytics storage not initialized".to_string())
+    })?;
+
+    let args = parse_json(&request.payload)?;
+    let params: GetDepthHeatmapParams = serde_json::from_value(args)
+        .map_err(|e| ProviderError::Validation(format!("Invalid parameters: {}", e)))?;
+
+    let heatmap = get_depth_heatmap(storage.clone(), params)
+        .await
+        .map_err(|e| ProviderError::BinanceApi(e.to_string()))?;
+
+    let result = serde_json::to_value(&heatmap)?;
     Ok(Json {
         value: serde_json::to_string(&result)?.as_bytes().to_vec(),
     })
@@ -480,6 +1165,7 @@ async fn handle_get_liquidity_vacuums(
 async fn handle_generate_market_report(
     report_generator: Option<&Arc<crate::report::ReportGenerator>>,
     request: &InvokeRequest,
+    cancel: &CancellationToken,
 ) -> Result<Json> {
     let generator = report_generator
         .ok_or_else(|| ProviderError::Validation("Report generator not initialized".to_string()))?;
@@ -492,16 +1178,19 @@ async fn handle_generate_market_report(
     tracing::info!("Generating market report for symbol: {}", symbol);
 
     // Parse options if provided
-    let options = if let Some(opts) = args.get("options") {
+    let mut options: crate::report::ReportOptions = if let Some(opts) = args.get("options") {
         serde_json::from_value(opts.clone())
             .map_err(|e| ProviderError::Validation(format!("Invalid options: {}", e)))?
     } else {
         crate::report::ReportOptions::default()
     };
+    if args["no_cache"].as_bool().unwrap_or(false) {
+        options.no_cache = true;
+    }
 
     // Generate report
     let report = generator
-        .generate_report(symbol, options)
+        .generate_report(symbol, options, cancel)
         .await
         .map_err(|e| ProviderError::BinanceApi(e))?;
 
@@ -510,3 +1199,83 @@ async fn handle_generate_market_report(
         value: serde_json::to_string(&result)?.as_bytes().to_vec(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binance::client::test_server::{client_with_responses, http_response};
+    use serde_json::Value;
+
+    /// Route `tool_name` with `args` and return the decoded result
+    async fn invoke(client: &BinanceClient, tool_name: &str, args: Value) -> Value {
+        let request = InvokeRequest {
+            tool_name: tool_name.to_string(),
+            payload: Some(Json {
+                value: args.to_string().into_bytes(),
+            }),
+            correlation_id: String::new(),
+        };
+        let result = route_tool(
+            client,
+            None,
+            None,
+            None,
+            None,
+            &request,
+            &CancellationToken::new(),
+        )
+        .await
+        .unwrap();
+        serde_json::from_slice(&result.result.unwrap().value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_recent_trades_and_klines_are_paged() {
+        let trade = |id: i64| {
+            serde_json::json!({
+                "id": id, "price": "100.0", "qty": "1.0", "quoteQty": "100.0",
+                "time": 1_700_000_000_000_i64 + id, "isBuyerMaker": true, "isBestMatch": true
+            })
+        };
+        // Klines pass through untyped; open time and OHLCV are enough
+        let kline = |open_time: i64| serde_json::json!([open_time, "1", "2", "0.5", "1.5", "10"]);
+        let trades = serde_json::json!([trade(1), trade(2)]).to_string();
+        let klines = serde_json::json!([kline(0), kline(60_000)]).to_string();
+        let (client, _) = client_with_responses(vec![
+            Some(http_response("200 OK", &trades)),
+            Some(http_response("200 OK", &trades)),
+            Some(http_response("200 OK", &klines)),
+        ])
+        .await;
+
+        // The limit capped the results; only the latest data is served, so no cursor
+        let capped = invoke(
+            &client,
+            "binance.get_recent_trades",
+            serde_json::json!({"symbol": "BTCUSDT", "limit": 2}),
+        )
+        .await;
+        assert_eq!(capped["count"], 2);
+        assert_eq!(capped["truncated"], true);
+        assert!(capped["next_cursor"].is_null());
+
+        let short = invoke(
+            &client,
+            "binance.get_recent_trades",
+            serde_json::json!({"symbol": "BTCUSDT", "limit": 5}),
+        )
+        .await;
+        assert_eq!(short["items"].as_array().unwrap().len(), 2);
+        assert_eq!(short["truncated"], false);
+
+        let candles = invoke(
+            &client,
+            "binance.get_klines",
+            serde_json::json!({"symbol": "BTCUSDT", "interval": "1m", "limit": 2}),
+        )
+        .await;
+        assert_eq!(candles["count"], 2);
+        assert_eq!(candles["truncated"], true);
+        assert!(candles["next_cursor"].is_null());
+    }
+}
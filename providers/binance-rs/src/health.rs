@@ -0,0 +1,179 @@
+//! Server-wide health reporting
+//!
+//! Aggregates orderbook manager health, analytics storage reachability,
+//! WebSocket connectivity, the Binance circuit breaker, clock drift and process
+//! uptime into a single [`ServerHealth`] document. Served as `GET /health` on
+//! the HTTP transport (for load-balancer liveness/readiness probes) and as the
+//! `binance.health` tool on every transport.
+
+use crate::binance::circuit::{CircuitState, CircuitStatus};
+use crate::binance::parse_errors::parse_error_count;
+use crate::binance::BinanceClient;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use std::time::Instant;
+
+#[cfg(feature = "orderbook")]
+use crate::orderbook::types::{HealthStatus, OrderBookHealth};
+#[cfg(feature = "orderbook")]
+use crate::orderbook::OrderBookManager;
+
+/// Process start time, recorded by [`mark_started`] (or lazily on first use)
+static STARTED_AT: OnceLock<Instant> = OnceLock::new();
+
+/// Overall server status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ServerStatus {
+    /// All components healthy
+    Ok,
+    /// Serving, but some component is stale or unreachable
+    Degraded,
+    /// Not able to serve live market data
+    Error,
+}
+
+/// Aggregated server health
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ServerHealth {
+    /// Overall status
+    pub status: ServerStatus,
+
+    /// Seconds since the server started
+    pub uptime_secs: u64,
+
+    /// Whether at least one depth WebSocket is connected
+    pub websocket_connected: bool,
+
+    /// WebSocket messages that failed to parse since startup, across all streams
+    /// (a sudden climb usually means Binance changed a stream's schema)
+    pub websocket_parse_errors: u64,
+
+    /// Binance REST circuit breaker (open = calls fail fast)
+    pub upstream: CircuitStatus,
+
+    /// Binance server time minus local time in ms (None = not measured yet)
+    pub clock_offset_ms: Option<i64>,
+
+    /// Whether analytics storage answered a probe (None = analytics not enabled)
+    pub storage_reachable: Option<bool>,
+
+    /// Orderbook manager health (None = manager not initialized)
+    #[cfg(feature = "orderbook")]
+    pub orderbook: Option<OrderBookHealth>,
+
+    /// Open HTTP transport sessions (None = not served over HTTP)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active_sessions: Option<usize>,
+
+    /// Check timestamp (milliseconds since epoch)
+    pub timestamp: i64,
+
+    /// Human-readable reason when status is not `ok`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl ServerHealth {
+    /// Whether the server should receive traffic (HTTP 200 vs 503)
+    pub fn is_ready(&self) -> bool {
+        self.status != ServerStatus::Error
+    }
+}
+
+/// Record the process start time used for `uptime_secs`
+///
+/// Call once at startup; later calls are no-ops.
+pub fn mark_started() {
+    STARTED_AT.get_or_init(Instant::now);
+}
+
+/// Seconds elapsed since [`mark_started`]
+pub fn uptime_secs() -> u64 {
+    STARTED_AT.get_or_init(Instant::now).elapsed().as_secs()
+}
+
+/// Collect health from all available components
+///
+/// # Arguments
+/// * `binance_client` - Client whose circuit breaker state is reported
+/// * `orderbook_manager` - Optional orderbook manager (reuses `get_health`)
+/// * `analytics_storage` - Optional snapshot storage to probe
+///
+/// # Returns
+/// `error` when tracked symbols have zero connected WebSockets, `degraded` when
+/// the orderbook is stale/partially connected, storage is unreachable or the
+/// Binance circuit is open.
+pub async fn check_health(
+    binance_client: &BinanceClient,
+    #[cfg(feature = "orderbook")] orderbook_manager: Option<&OrderBookManager>,
+    #[cfg(feature = "orderbook_analytics")] analytics_storage: Option<
+        &crate::orderbook::analytics::SnapshotStorage,
+    >,
+) -> ServerHealth {
+    let mut status = ServerStatus::Ok;
+    let mut reason: Option<String> = None;
+    #[allow(unused_mut)]
+    let mut websocket_connected = false;
+    #[allow(unused_mut)]
+    let mut storage_reachable = None;
+
+    #[cfg(feature = "orderbook")]
+    let orderbook = match orderbook_manager {
+        Some(manager) => Some(manager.get_health().await),
+        None => None,
+    };
+
+    #[cfg(feature = "orderbook")]
+    if let Some(health) = &orderbook {
+        websocket_connected = health.websocket_connected;
+        status = match health.status {
+            HealthStatus::Ok => ServerStatus::Ok,
+            HealthStatus::Degraded => ServerStatus::Degraded,
+            HealthStatus::Error => ServerStatus::Error,
+        };
+        reason = health.reason.clone();
+    }
+
+    #[cfg(feature = "orderbook_analytics")]
+    if let Some(storage) = analytics_storage {
+        // The in-memory backend has no database to lose
+        let reachable = storage.db().map_or(true, |db| {
+            db.property_value("rocksdb.estimate-num-keys").is_ok()
+        });
+        if !reachable && status == ServerStatus::Ok {
+            status = ServerStatus::Degraded;
+            reason = Some("Analytics storage unreachable".to_string());
+        }
+        storage_reachable = Some(reachable);
+    }
+
+    let upstream = binance_client.circuit_status();
+    if upstream.state != CircuitState::Closed && status == ServerStatus::Ok {
+        status = ServerStatus::Degraded;
+        reason = Some(format!(
+            "Binance circuit {}: REST calls failing fast after repeated upstream errors",
+            if upstream.state == CircuitState::Open {
+                "open"
+            } else {
+                "half-open"
+            }
+        ));
+    }
+
+    ServerHealth {
+        status,
+        uptime_secs: uptime_secs(),
+        websocket_connected,
+        websocket_parse_errors: parse_error_count(),
+        upstream,
+        clock_offset_ms: binance_client.clock_offset_ms(),
+        storage_reachable,
+        #[cfg(feature = "orderbook")]
+        orderbook,
+        active_sessions: None,
+        timestamp: chrono::Utc::now().timestamp_millis(),
+        reason,
+    }
+}
@@ -2,7 +2,13 @@
 
 pub mod error;
 pub mod grpc;
+pub mod health; // Server-wide health checks
+pub mod pagination; // Items/count/truncated/next_cursor envelope for list tools
 pub mod pb;
+pub mod rate_limit; // Per-client tool call rate limiting
+pub mod self_test; // End-to-end liveness self-test
+pub mod shutdown; // Ctrl+C / SIGTERM graceful shutdown signals
+pub mod timestamp; // RFC 3339 + epoch millis timestamps for outputs
 
 #[cfg(feature = "http_transport")]
 pub mod transport; // MCP transport layer (HTTP)
@@ -10,6 +16,9 @@ pub mod transport; // MCP transport layer (HTTP)
 // Binance API integration modules
 pub mod binance; // Binance API client
 pub mod config; // Configuration management
+pub mod market_data; // Exchange-agnostic market data source trait
+pub mod market_scan; // Quote-asset scans over all 24hr tickers
+pub mod price_change; // Price change over trailing windows
 
 #[cfg(feature = "orderbook")]
 pub mod orderbook; // WebSocket orderbook manager
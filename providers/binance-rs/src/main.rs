@@ -3,32 +3,73 @@ use binance_provider::pb::provider_server::ProviderServer;
 use std::net::SocketAddr;
 use tonic::transport::Server;
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Parse command-line arguments first to determine mode
+/// How long shutdown waits for persistence tasks to write their final batches
+#[cfg(feature = "orderbook_analytics")]
+const SHUTDOWN_DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Record start time for health uptime reporting
+    binance_provider::health::mark_started();
+
+    // Parse command-line arguments first to determine mode and runtime sizes
     let args: Vec<String> = std::env::args().collect();
-    let (mode, port) = parse_args(&args);
+    let cli = parse_args(&args);
+    let runtime = binance_provider::config::RuntimeConfig::from_env(
+        cli.worker_threads.as_deref(),
+        cli.blocking_threads.as_deref(),
+    )?;
+
+    runtime.build()?.block_on(run(cli, runtime))
+}
 
-    // Initialize tracing/logging
+async fn run(
+    cli: CliArgs,
+    runtime: binance_provider::config::RuntimeConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let CliArgs {
+        mode,
+        port,
+        bind,
+        export,
+        replay,
+        ..
+    } = cli;
+
+    // Initialize tracing/logging in the LOG_FORMAT format
     // For stdio mode, output to stderr (stdout is reserved for MCP protocol)
-    tracing_subscriber::fmt()
-        .with_target(false)
-        .with_thread_ids(false)
-        .with_level(true)
-        .with_writer(std::io::stderr) // Always write to stderr for MCP compatibility
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
-        )
-        .init();
+    binance_provider::config::LogFormat::from_env()?.init()?;
 
     tracing::info!("Starting Binance Provider in {} mode...", mode);
+    tracing::info!(
+        "Tokio runtime: {} worker threads, up to {} blocking threads",
+        runtime.worker_threads(),
+        runtime.blocking_threads()
+    );
+
+    // Listed symbols back symbol validation in every serving mode
+    if !matches!(mode.as_str(), "export-snapshots" | "export-trades") {
+        use binance_provider::binance::symbol::{
+            spawn_known_symbols_refresh, KNOWN_SYMBOLS_REFRESH_INTERVAL,
+        };
+        spawn_known_symbols_refresh(
+            binance_provider::binance::BinanceClient::new(),
+            KNOWN_SYMBOLS_REFRESH_INTERVAL,
+        );
+    }
+
+    // Resolved only by the listening modes, so a bad MCP_BIND_ADDR can't break stdio
+    let addr = || binance_provider::config::listen_addr(bind.as_deref(), port);
 
     match mode.as_str() {
-        "grpc" => run_grpc_server(port).await?,
-        "http" => run_http_server(port).await?,
+        "grpc" => run_grpc_server(addr()?, replay).await?,
+        "http" => run_http_server(addr()?, replay).await?,
+        _ if replay.is_some() => {
+            eprintln!("--replay is only supported with --grpc or --http");
+            std::process::exit(1);
+        }
         "stdio" => run_stdio_server().await?,
-        "sse" => run_sse_server(port).await?,
+        "sse" => run_sse_server(addr()?).await?,
+        "export-snapshots" | "export-trades" => run_export(&mode, export)?,
         _ => {
             eprintln!("Invalid mode: {}", mode);
             print_usage();
@@ -39,11 +80,67 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+/// Parsed command-line arguments
+struct CliArgs {
+    mode: String,
+    port: u16,
+    /// `--bind` address (overrides `MCP_BIND_ADDR`)
+    bind: Option<String>,
+    export: ExportArgs,
+    replay: Option<ReplayArgs>,
+    /// `--worker-threads` (overrides `TOKIO_WORKER_THREADS`)
+    worker_threads: Option<String>,
+    /// `--blocking-threads` (overrides `TOKIO_BLOCKING_THREADS`)
+    blocking_threads: Option<String>,
+}
+
+/// Arguments for the `--export-snapshots` / `--export-trades` subcommands
+///
+/// `--symbol`, `--from` and `--to` also select the `--replay` window.
+#[derive(Default)]
+#[cfg_attr(not(feature = "orderbook_analytics"), allow(dead_code))]
+struct ExportArgs {
+    symbol: Option<String>,
+    /// Window start (Unix seconds)
+    from: Option<i64>,
+    /// Window end (Unix seconds)
+    to: Option<i64>,
+    out: Option<String>,
+}
+
+/// Arguments for `--replay` (serve stored snapshots instead of live streams)
+#[cfg_attr(not(feature = "orderbook_analytics"), allow(dead_code))]
+struct ReplayArgs {
+    /// Symbol to replay (default: ANALYTICS_SYMBOLS)
+    symbol: Option<String>,
+    /// Window start (Unix seconds)
+    from: i64,
+    /// Window end (Unix seconds)
+    to: i64,
+    /// Playback speed relative to wall-clock time
+    speed: f64,
+}
+
+/// Parse a CLI timestamp as Unix seconds or RFC 3339 (e.g. 2025-01-18T00:00:00Z)
+fn parse_timestamp_arg(value: &str) -> Option<i64> {
+    value.parse::<i64>().ok().or_else(|| {
+        chrono::DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|dt| dt.timestamp())
+    })
+}
+
 /// Parse command-line arguments
-fn parse_args(args: &[String]) -> (String, u16) {
+fn parse_args(args: &[String]) -> CliArgs {
     let mut mode = "grpc".to_string();
     let mut port = 0u16; // 0 means use default based on mode
     let mut port_set_explicitly = false;
+    let mut bind = None;
+    let mut worker_threads = None;
+    let mut blocking_threads = None;
+    let mut export = ExportArgs::default();
+    let mut replay = false;
+    let mut speed = 1.0;
 
     let mut i = 1;
     while i < args.len() {
@@ -58,6 +155,47 @@ fn parse_args(args: &[String]) -> (String, u16) {
             "--http" => mode = "http".to_string(),
             "--stdio" => mode = "stdio".to_string(),
             "--sse" => mode = "sse".to_string(),
+            "--export-snapshots" => mode = "export-snapshots".to_string(),
+            "--export-trades" => mode = "export-trades".to_string(),
+            "--replay" => replay = true,
+            "--speed" => {
+                if i + 1 < args.len() {
+                    speed = match args[i + 1].parse::<f64>() {
+                        Ok(value) if value.is_finite() && value > 0.0 => value,
+                        _ => {
+                            eprintln!("Invalid replay speed: {}", args[i + 1]);
+                            std::process::exit(1);
+                        }
+                    };
+                    i += 1;
+                }
+            }
+            "--symbol" => {
+                if i + 1 < args.len() {
+                    export.symbol = Some(args[i + 1].to_uppercase());
+                    i += 1;
+                }
+            }
+            "--from" | "--to" => {
+                if i + 1 < args.len() {
+                    let Some(ts) = parse_timestamp_arg(&args[i + 1]) else {
+                        eprintln!("Invalid timestamp for {}: {}", args[i], args[i + 1]);
+                        std::process::exit(1);
+                    };
+                    if args[i] == "--from" {
+                        export.from = Some(ts);
+                    } else {
+                        export.to = Some(ts);
+                    }
+                    i += 1;
+                }
+            }
+            "--out" => {
+                if i + 1 < args.len() {
+                    export.out = Some(args[i + 1].clone());
+                    i += 1;
+                }
+            }
             "--port" => {
                 if i + 1 < args.len() {
                     port = args[i + 1].parse().unwrap_or(0);
@@ -65,6 +203,24 @@ fn parse_args(args: &[String]) -> (String, u16) {
                     i += 1;
                 }
             }
+            "--bind" => {
+                if i + 1 < args.len() {
+                    bind = Some(args[i + 1].clone());
+                    i += 1;
+                }
+            }
+            "--worker-threads" => {
+                if i + 1 < args.len() {
+                    worker_threads = Some(args[i + 1].clone());
+                    i += 1;
+                }
+            }
+            "--blocking-threads" => {
+                if i + 1 < args.len() {
+                    blocking_threads = Some(args[i + 1].clone());
+                    i += 1;
+                }
+            }
             "--help" | "-h" => {
                 print_usage();
                 std::process::exit(0);
@@ -88,7 +244,28 @@ fn parse_args(args: &[String]) -> (String, u16) {
         };
     }
 
-    (mode, port)
+    let replay = replay.then(|| {
+        let Some(from) = export.from else {
+            eprintln!("--from is required for --replay");
+            std::process::exit(1);
+        };
+        ReplayArgs {
+            symbol: export.symbol.clone(),
+            from,
+            to: export.to.unwrap_or_else(|| chrono::Utc::now().timestamp()),
+            speed,
+        }
+    });
+
+    CliArgs {
+        mode,
+        port,
+        bind,
+        export,
+        replay,
+        worker_threads,
+        blocking_threads,
+    }
 }
 
 /// Print usage information
@@ -105,11 +282,31 @@ fn print_usage() {
     println!("    --stdio             Run in stdio MCP mode (shortcut for --mode stdio)");
     println!("    --sse               Run in SSE mode (shortcut for --mode sse)");
     println!("    --port <PORT>       Port to listen on (default: 50053 for gRPC, 3000 for HTTP, 8000 for SSE)");
+    println!(
+        "    --bind <ADDR>       IP address to listen on (default: 0.0.0.0, env: MCP_BIND_ADDR)"
+    );
+    println!("    --worker-threads <N>    Tokio worker threads (default: CPU cores, env: TOKIO_WORKER_THREADS)");
+    println!("    --blocking-threads <N>  Max Tokio blocking threads (default: 512, env: TOKIO_BLOCKING_THREADS)");
     println!("    --help, -h          Print this help message");
     println!();
+    println!("EXPORT (requires orderbook_analytics):");
+    println!("    --export-snapshots  Export stored orderbook snapshots as CSV and exit");
+    println!("    --export-trades     Export stored aggregate trades as CSV and exit");
+    println!("    --symbol <SYMBOL>   Symbol to export (e.g. BTCUSDT)");
+    println!("    --from <TIME>       Window start, Unix seconds or RFC 3339");
+    println!("    --to <TIME>         Window end, Unix seconds or RFC 3339 (default: now)");
+    println!("    --out <FILE>        Output file (default: stdout)");
+    println!();
+    println!("REPLAY (requires orderbook_analytics, with --grpc or --http):");
+    println!("    --replay            Serve stored snapshots instead of live WebSocket streams");
+    println!("    --from <TIME>       Replay start, Unix seconds or RFC 3339");
+    println!("    --to <TIME>         Replay end (default: now)");
+    println!("    --speed <N>         Playback speed, N x wall-clock time (default: 1)");
+    println!("    --symbol <SYMBOL>   Symbol to replay (default: ANALYTICS_SYMBOLS)");
+    println!();
     println!("ENVIRONMENT VARIABLES:");
-    println!("    BINANCE_API_KEY       Binance API key (optional, preserved for future use)");
-    println!("    BINANCE_API_SECRET    Binance API secret (optional, preserved for future use)");
+    println!("    BINANCE_API_KEY       Binance API key (optional, enables binance.get_account)");
+    println!("    BINANCE_API_SECRET    Binance API secret (optional, signs binance.get_account)");
     println!("    BINANCE_BASE_URL      Binance API base URL (default: https://api.binance.com)");
     println!("    ANALYTICS_DATA_PATH   Analytics storage path (default: ./data/analytics)");
     println!("    RUST_LOG              Logging level (default: info)");
@@ -124,19 +321,302 @@ fn print_usage() {
     println!("    # Start HTTP server on custom port");
     println!("    binance-provider --mode http --port 8080");
     println!();
+    println!("    # Only accept local connections");
+    println!("    binance-provider --http --bind 127.0.0.1");
+    println!();
     println!("    # Start gRPC server with analytics features");
     println!("    cargo run --features orderbook,orderbook_analytics -- --grpc --port 50053");
     println!();
     println!("    # Start in stdio mode");
     println!("    binance-provider --stdio");
+    println!();
+    println!("    # Export the last day of BTCUSDT snapshots for pandas");
+    println!("    binance-provider --export-snapshots --symbol BTCUSDT \\");
+    println!("        --from 2025-01-18T00:00:00Z --to 2025-01-19T00:00:00Z --out btc.csv");
+    println!();
+    println!("    # Replay an hour of stored BTCUSDT books at 10x speed over HTTP");
+    println!("    binance-provider --http --replay --symbol BTCUSDT \\");
+    println!("        --from 2025-01-18T00:00:00Z --to 2025-01-18T01:00:00Z --speed 10");
 }
 
-/// Run the provider in gRPC mode
-async fn run_grpc_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
-    let addr: SocketAddr = format!("0.0.0.0:{}", port).parse()?;
+/// Export stored snapshots or trades to CSV (read-only, safe while a server is running)
+#[cfg(feature = "orderbook_analytics")]
+fn run_export(mode: &str, args: ExportArgs) -> Result<(), Box<dyn std::error::Error>> {
+    use binance_provider::orderbook::analytics::{SnapshotStorage, TradeStorage};
+    use std::io::Write;
+
+    let symbol = args.symbol.ok_or("--symbol is required for export")?;
+    let from = args.from.ok_or("--from is required for export")?;
+    let to = args.to.unwrap_or_else(|| chrono::Utc::now().timestamp());
+
+    let storage_path =
+        std::env::var("ANALYTICS_DATA_PATH").unwrap_or_else(|_| "./data/analytics".to_string());
+    let storage = SnapshotStorage::open_read_only(&storage_path)?;
+
+    let writer: Box<dyn Write> = match &args.out {
+        Some(path) => Box::new(std::io::BufWriter::new(std::fs::File::create(path)?)),
+        None => Box::new(std::io::BufWriter::new(std::io::stdout().lock())),
+    };
+
+    let rows = if mode == "export-snapshots" {
+        storage.export_csv(&symbol, from, to, writer)?
+    } else {
+        // Trade batch keys are in milliseconds
+        let db = storage
+            .db()
+            .ok_or("Trade export requires RocksDB storage")?;
+        TradeStorage::new(db).export_csv(&symbol, from * 1000, to * 1000, writer)?
+    };
+
+    tracing::info!(
+        symbol = %symbol,
+        rows,
+        out = args.out.as_deref().unwrap_or("stdout"),
+        "CSV export complete"
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "orderbook_analytics"))]
+fn run_export(_mode: &str, _args: ExportArgs) -> Result<(), Box<dyn std::error::Error>> {
+    tracing::error!("CSV export not available - compile with 'orderbook_analytics' feature");
+    Err("CSV export not available".into())
+}
+
+/// Build the provider, starting the snapshot replay when `--replay` was given
+#[cfg(feature = "orderbook_analytics")]
+async fn build_provider(
+    replay: Option<&ReplayArgs>,
+) -> Result<BinanceProviderServer, Box<dyn std::error::Error>> {
+    use binance_provider::orderbook::replay::{load_stored_books, ReplayFeed};
+
+    let Some(replay) = replay else {
+        return Ok(BinanceProviderServer::new()?);
+    };
+
+    let provider = BinanceProviderServer::new_replay()?;
+    let symbols = match &replay.symbol {
+        Some(symbol) => vec![symbol.clone()],
+        None => binance_provider::config::AnalyticsConfig::from_env()?.symbols,
+    };
+    let storage = provider
+        .analytics_storage
+        .as_ref()
+        .ok_or("Replay mode requires analytics storage, which failed to open")?;
+    let books = load_stored_books(storage, &symbols, replay.from, replay.to).await?;
+    if books.is_empty() {
+        return Err(format!(
+            "No stored snapshots for {} between {} and {}",
+            symbols.join(", "),
+            replay.from,
+            replay.to
+        )
+        .into());
+    }
+
+    tracing::info!(
+        "Replaying {} snapshots of {} at {}x speed",
+        books.len(),
+        symbols.join(", "),
+        replay.speed
+    );
+    let feed = ReplayFeed::new(books, replay.speed)?;
+    let manager = provider.orderbook_manager.clone();
+    tokio::spawn(async move {
+        if let Err(e) = feed.run(manager).await {
+            tracing::error!("Replay stopped: {}", e);
+        }
+    });
+
+    Ok(provider)
+}
 
+#[cfg(not(feature = "orderbook_analytics"))]
+async fn build_provider(
+    replay: Option<&ReplayArgs>,
+) -> Result<BinanceProviderServer, Box<dyn std::error::Error>> {
+    if replay.is_some() {
+        tracing::error!("Replay mode not available - compile with 'orderbook_analytics' feature");
+        return Err("Replay mode not available".into());
+    }
+    Ok(BinanceProviderServer::new()?)
+}
+
+/// Check local clock drift against Binance now and every few minutes
+fn spawn_clock_drift_check(provider: &BinanceProviderServer) {
+    use binance_provider::binance::time_sync::{spawn_drift_check, DRIFT_CHECK_INTERVAL};
+    spawn_drift_check(provider.binance_client.clone(), DRIFT_CHECK_INTERVAL);
+}
+
+/// POST order book health transitions to `HEALTH_WEBHOOK_URL`, if set
+#[cfg(feature = "orderbook")]
+async fn spawn_health_webhook(
+    manager: std::sync::Arc<binance_provider::orderbook::OrderBookManager>,
+) {
+    use binance_provider::orderbook::health_webhook::{
+        self, webhook_url_from_env, HEALTH_WEBHOOK_POLL_INTERVAL,
+    };
+    if let Some(url) = webhook_url_from_env() {
+        tracing::info!(
+            "Health webhook enabled (polling every {:?})",
+            HEALTH_WEBHOOK_POLL_INTERVAL
+        );
+        if let Err(e) =
+            health_webhook::spawn_health_webhook(manager, url, HEALTH_WEBHOOK_POLL_INTERVAL).await
+        {
+            tracing::error!("Health webhook disabled, HTTP client failed: {}", e);
+        }
+    }
+}
+
+/// Pre-subscribe to `ANALYTICS_SYMBOLS` and persist their snapshots and trades (T015-T020)
+///
+/// Also starts the hourly retention task (`ANALYTICS_RETENTION_DAYS` and the 1GB cap).
+/// Skipped in replay mode (replayed books must not be persisted again) and when
+/// analytics storage failed to open (the server runs degraded). Trades are only
+/// persisted with RocksDB storage (not `ANALYTICS_STORAGE=memory`). Returns the
+/// tasks to pass to [`drain_persistence`] once the server stops.
+#[cfg(feature = "orderbook_analytics")]
+async fn spawn_persistence(
+    provider: &BinanceProviderServer,
+    replay: Option<&ReplayArgs>,
+    shutdown_tx: &tokio::sync::broadcast::Sender<()>,
+) -> Result<Vec<tokio::task::JoinHandle<()>>, Box<dyn std::error::Error>> {
+    let mut persistence_handles: Vec<tokio::task::JoinHandle<()>> = Vec::new();
+    let (None, Some(analytics_storage)) = (replay, provider.analytics_storage.clone()) else {
+        return Ok(persistence_handles);
+    };
+
+    // Symbols and capture interval from ANALYTICS_SYMBOLS / ANALYTICS_SNAPSHOT_INTERVAL_MS
+    let analytics_config = binance_provider::config::AnalyticsConfig::from_env()?;
+    let symbol_list = analytics_config.symbols.join(", ");
+
+    // T015/T016: Pre-subscribe to each persisted symbol's WebSocket
+    for symbol in &analytics_config.symbols {
+        if let Err(e) = provider.orderbook_manager.subscribe(symbol).await {
+            tracing::error!("Failed to pre-subscribe to {}: {}", symbol, e);
+        } else {
+            // T017: INFO logging for pre-subscription
+            tracing::info!("Pre-subscribed to {} for snapshot persistence", symbol);
+        }
+    }
+
+    // T018: Spawn snapshot persistence task
+    let persistence_shutdown_rx = shutdown_tx.subscribe();
+    let persisted_symbols: Vec<&str> = analytics_config
+        .symbols
+        .iter()
+        .map(String::as_str)
+        .collect();
+    persistence_handles.push(
+        binance_provider::orderbook::analytics::storage::spawn_snapshot_persistence_task(
+            analytics_storage.clone(),
+            provider.orderbook_manager.clone(),
+            &persisted_symbols, // T020: Verify correct symbol parameters
+            analytics_config.snapshot_interval,
+            persistence_shutdown_rx, // T019: Pass shutdown_rx for graceful shutdown
+        ),
+    );
+
+    tracing::info!(
+        "Snapshot persistence task spawned for {} (every {:?})",
+        symbol_list,
+        analytics_config.snapshot_interval
+    );
+
+    // Hourly retention cleanup and 1GB size cap enforcement
+    let _retention_handle = binance_provider::orderbook::analytics::storage::spawn_retention_task(
+        analytics_storage,
+        provider.trade_storage.clone(),
+        analytics_config.retention(),
+        binance_provider::orderbook::analytics::storage::MAX_STORAGE_BYTES,
+        shutdown_tx.subscribe(),
+    );
+
+    tracing::info!(
+        "Retention task spawned ({} days, hourly)",
+        analytics_config.retention_days
+    );
+
+    // Feature 008: Spawn trade stream persistence task
+    if let Some(trade_storage_handle) = provider.trade_storage.clone() {
+        let trade_shutdown_rx = shutdown_tx.subscribe();
+        let trade_symbols = analytics_config.symbols.clone();
+        let trade_flush = binance_provider::orderbook::analytics::trade_storage::TradeFlushPolicy {
+            interval: analytics_config.trade_flush_interval,
+            max_trades: analytics_config.trade_flush_max_trades,
+        };
+        let backfill_client = provider.binance_client.clone();
+
+        persistence_handles.push(tokio::spawn(async move {
+            use binance_provider::binance::Market;
+            use binance_provider::orderbook::analytics::trade_storage::spawn_trade_persistence_task;
+            use binance_provider::orderbook::analytics::trade_stream::TradeStreamHandler;
+
+            // Single unbounded channel shared by all trade streams (trades carry their symbol)
+            let (trade_tx, trade_rx) = tokio::sync::mpsc::unbounded_channel();
+
+            // Spawn one WebSocket handler per symbol; trade ID gaps are backfilled over REST
+            for symbol in &trade_symbols {
+                let mut handler = TradeStreamHandler::new(symbol, Market::Spot)
+                    .with_backfill(backfill_client.clone());
+                let tx = trade_tx.clone();
+                let symbol = symbol.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = handler.connect_with_backoff(tx).await {
+                        tracing::error!("{} trade stream failed: {}", symbol, e);
+                    }
+                });
+                tracing::info!("Starting trade stream collection for {}", symbol);
+            }
+            drop(trade_tx);
+
+            // Batches per symbol on a timer or once full; buffered trades are flushed on shutdown
+            let _ = spawn_trade_persistence_task(
+                trade_storage_handle,
+                &trade_symbols,
+                trade_rx,
+                trade_shutdown_rx,
+                trade_flush,
+            )
+            .await;
+        }));
+
+        tracing::info!("Trade persistence task spawned for {}", symbol_list);
+    }
+
+    Ok(persistence_handles)
+}
+
+/// Wait for persistence tasks to write their final batches, up to
+/// [`SHUTDOWN_DRAIN_TIMEOUT`]
+#[cfg(feature = "orderbook_analytics")]
+async fn drain_persistence(persistence_handles: Vec<tokio::task::JoinHandle<()>>) {
+    if tokio::time::timeout(
+        SHUTDOWN_DRAIN_TIMEOUT,
+        futures::future::join_all(persistence_handles),
+    )
+    .await
+    .is_err()
+    {
+        tracing::warn!(
+            "Persistence tasks did not finish within {:?}; buffered data may be lost",
+            SHUTDOWN_DRAIN_TIMEOUT
+        );
+    }
+}
+
+/// Run the provider in gRPC mode
+async fn run_grpc_server(
+    addr: SocketAddr,
+    replay: Option<ReplayArgs>,
+) -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("Initializing Binance Provider Server...");
-    let provider = BinanceProviderServer::new()?;
+    binance_provider::grpc::prompts::load_templates_from_env()?;
+    let provider = build_provider(replay.as_ref()).await?;
+    spawn_clock_drift_check(&provider);
+    #[cfg(feature = "orderbook")]
+    spawn_health_webhook(provider.orderbook_manager.clone()).await;
 
     tracing::info!("Starting gRPC server on {}", addr);
     tracing::info!("Provider capabilities:");
@@ -155,7 +635,15 @@ async fn run_grpc_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
     }
 
     tracing::info!("  - 1 resource (market data)");
-    tracing::info!("  - 1 prompt (trading-analysis)");
+    let prompt_names: Vec<String> = binance_provider::grpc::prompts::templates()
+        .iter()
+        .map(|template| template.name.clone())
+        .collect();
+    tracing::info!(
+        "  - {} prompts ({})",
+        prompt_names.len(),
+        prompt_names.join(", ")
+    );
 
     // Check for API credentials
     match (
@@ -175,130 +663,16 @@ async fn run_grpc_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
     let (shutdown_tx, _shutdown_rx) = tokio::sync::broadcast::channel::<()>(1);
     let mut server_shutdown_rx = shutdown_tx.subscribe();
 
-    // Spawn shutdown signal handler
-    let signal_tx = shutdown_tx.clone();
-    tokio::spawn(async move {
-        match tokio::signal::ctrl_c().await {
-            Ok(()) => {
-                tracing::info!("Received shutdown signal (Ctrl+C)");
-                let _ = signal_tx.send(());
-            }
-            Err(err) => {
-                tracing::error!("Failed to listen for shutdown signal: {}", err);
-            }
-        }
-    });
+    // Spawn shutdown signal handler (Ctrl+C, or SIGTERM on Unix)
+    binance_provider::shutdown::spawn_shutdown_listener(
+        binance_provider::shutdown::shutdown_signal(),
+        shutdown_tx.clone(),
+    );
 
-    // Pre-subscribe to symbols and spawn snapshot persistence task (T015-T020)
+    // Snapshot and trade persistence plus retention; awaited after the server stops
+    // so final batches land
     #[cfg(feature = "orderbook_analytics")]
-    {
-        // T015: Pre-subscribe to BTCUSDT WebSocket
-        if let Err(e) = provider.orderbook_manager.subscribe("BTCUSDT").await {
-            tracing::error!("Failed to pre-subscribe to BTCUSDT: {}", e);
-        } else {
-            // T017: INFO logging for pre-subscription
-            tracing::info!("Pre-subscribed to BTCUSDT for snapshot persistence");
-        }
-
-        // T016: Pre-subscribe to ETHUSDT WebSocket
-        if let Err(e) = provider.orderbook_manager.subscribe("ETHUSDT").await {
-            tracing::error!("Failed to pre-subscribe to ETHUSDT: {}", e);
-        } else {
-            // T017: INFO logging for pre-subscription
-            tracing::info!("Pre-subscribed to ETHUSDT for snapshot persistence");
-        }
-
-        // T018: Spawn snapshot persistence task
-        let persistence_shutdown_rx = shutdown_tx.subscribe();
-        let _persistence_handle =
-            binance_provider::orderbook::analytics::storage::spawn_snapshot_persistence_task(
-                provider.analytics_storage.clone(),
-                provider.orderbook_manager.clone(),
-                &["BTCUSDT", "ETHUSDT"], // T020: Verify correct symbol parameters
-                persistence_shutdown_rx, // T019: Pass shutdown_rx for graceful shutdown
-            );
-
-        tracing::info!("Snapshot persistence task spawned for BTCUSDT, ETHUSDT");
-
-        // Feature 008: Spawn trade stream persistence task
-        let trade_shutdown_rx = shutdown_tx.subscribe();
-        let trade_storage_handle = provider.trade_storage.clone();
-
-        tokio::spawn(async move {
-            use binance_provider::orderbook::analytics::trade_storage::AggTrade as PersistAggTrade;
-            use binance_provider::orderbook::analytics::trade_stream::TradeStreamHandler;
-            use std::time::Duration;
-            use tokio::time::interval;
-
-            // Create unbounded channels for BTC and ETH trade streams
-            let (btc_tx, mut btc_rx) = tokio::sync::mpsc::unbounded_channel();
-            let (eth_tx, mut eth_rx) = tokio::sync::mpsc::unbounded_channel();
-
-            // Spawn WebSocket handlers
-            let mut btc_handler = TradeStreamHandler::new("BTCUSDT");
-            let mut eth_handler = TradeStreamHandler::new("ETHUSDT");
-
-            tokio::spawn(async move {
-                if let Err(e) = btc_handler.connect_with_backoff(btc_tx).await {
-                    tracing::error!("BTCUSDT trade stream failed: {}", e);
-                }
-            });
-
-            tokio::spawn(async move {
-                if let Err(e) = eth_handler.connect_with_backoff(eth_tx).await {
-                    tracing::error!("ETHUSDT trade stream failed: {}", e);
-                }
-            });
-
-            tracing::info!("Starting trade stream collection for BTCUSDT");
-            tracing::info!("Starting trade stream collection for ETHUSDT");
-
-            // Buffers for 1-second batching
-            let mut btc_buffer: Vec<PersistAggTrade> = Vec::new();
-            let mut eth_buffer: Vec<PersistAggTrade> = Vec::new();
-
-            let mut flush_interval = interval(Duration::from_secs(1));
-            let mut shutdown_rx = trade_shutdown_rx;
-
-            loop {
-                tokio::select! {
-                    Some(trade) = btc_rx.recv() => {
-                        btc_buffer.push((&trade).into());
-                    }
-                    Some(trade) = eth_rx.recv() => {
-                        eth_buffer.push((&trade).into());
-                    }
-                    _ = flush_interval.tick() => {
-                        let now_ms = chrono::Utc::now().timestamp_millis();
-
-                        if !btc_buffer.is_empty() {
-                            let count = btc_buffer.len();
-                            if let Err(e) = trade_storage_handle.store_batch("BTCUSDT", now_ms, btc_buffer.drain(..).collect()) {
-                                tracing::error!("Failed to store BTCUSDT trades: {}", e);
-                            } else {
-                                tracing::info!("Stored {} trades for BTCUSDT at timestamp {}", count, now_ms);
-                            }
-                        }
-
-                        if !eth_buffer.is_empty() {
-                            let count = eth_buffer.len();
-                            if let Err(e) = trade_storage_handle.store_batch("ETHUSDT", now_ms, eth_buffer.drain(..).collect()) {
-                                tracing::error!("Failed to store ETHUSDT trades: {}", e);
-                            } else {
-                                tracing::info!("Stored {} trades for ETHUSDT at timestamp {}", count, now_ms);
-                            }
-                        }
-                    }
-                    _ = shutdown_rx.recv() => {
-                        tracing::info!("Shutting down trade persistence task...");
-                        break;
-                    }
-                }
-            }
-        });
-
-        tracing::info!("Trade persistence task spawned for BTCUSDT, ETHUSDT");
-    }
+    let persistence_handles = spawn_persistence(&provider, replay.as_ref(), &shutdown_tx).await?;
 
     // Start the gRPC server with graceful shutdown
     Server::builder()
@@ -309,34 +683,55 @@ async fn run_grpc_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
         })
         .await?;
 
+    #[cfg(feature = "orderbook_analytics")]
+    drain_persistence(persistence_handles).await;
+
     tracing::info!("Server stopped");
     Ok(())
 }
 
 /// Run the provider in HTTP mode
 #[cfg(feature = "http_transport")]
-async fn run_http_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
+async fn run_http_server(
+    addr: SocketAddr,
+    replay: Option<ReplayArgs>,
+) -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("Initializing Binance Provider (HTTP mode)...");
 
     #[cfg(all(feature = "orderbook", feature = "orderbook_analytics"))]
     {
-        let provider = BinanceProviderServer::new()?;
+        let provider = build_provider(replay.as_ref()).await?;
+        spawn_clock_drift_check(&provider);
+        spawn_health_webhook(provider.orderbook_manager.clone()).await;
+
+        // The HTTP server stops on its own signal listener; this one flushes persistence
+        let (shutdown_tx, _shutdown_rx) = tokio::sync::broadcast::channel::<()>(1);
+        binance_provider::shutdown::spawn_shutdown_listener(
+            binance_provider::shutdown::shutdown_signal(),
+            shutdown_tx.clone(),
+        );
+        let persistence_handles =
+            spawn_persistence(&provider, replay.as_ref(), &shutdown_tx).await?;
+
         binance_provider::transport::http::start_http_server(
-            port,
+            addr,
             provider.binance_client,
             Some(provider.orderbook_manager),
-            Some(provider.analytics_storage),
-            Some(provider.trade_storage),
+            provider.analytics_storage,
+            provider.trade_storage,
             Some(provider.report_generator),
         )
         .await?;
+        drain_persistence(persistence_handles).await;
     }
 
     #[cfg(all(feature = "orderbook", not(feature = "orderbook_analytics")))]
     {
-        let provider = BinanceProviderServer::new()?;
+        let provider = build_provider(replay.as_ref()).await?;
+        spawn_clock_drift_check(&provider);
+        spawn_health_webhook(provider.orderbook_manager.clone()).await;
         binance_provider::transport::http::start_http_server(
-            port,
+            addr,
             provider.binance_client,
             Some(provider.orderbook_manager),
             Some(provider.report_generator),
@@ -346,15 +741,19 @@ async fn run_http_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
 
     #[cfg(not(feature = "orderbook"))]
     {
-        let provider = BinanceProviderServer::new()?;
-        binance_provider::transport::http::start_http_server(port, provider.binance_client).await?;
+        let provider = build_provider(replay.as_ref()).await?;
+        spawn_clock_drift_check(&provider);
+        binance_provider::transport::http::start_http_server(addr, provider.binance_client).await?;
     }
 
     Ok(())
 }
 
 #[cfg(not(feature = "http_transport"))]
-async fn run_http_server(_port: u16) -> Result<(), Box<dyn std::error::Error>> {
+async fn run_http_server(
+    _addr: SocketAddr,
+    _replay: Option<ReplayArgs>,
+) -> Result<(), Box<dyn std::error::Error>> {
     eprintln!("HTTP transport not available. Build with --features http_transport");
     std::process::exit(1);
 }
@@ -374,11 +773,10 @@ async fn run_stdio_server() -> Result<(), Box<dyn std::error::Error>> {
 
 /// Run the provider in SSE mode (Server-Sent Events)
 #[cfg(feature = "mcp_server")]
-async fn run_sse_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
+async fn run_sse_server(addr: SocketAddr) -> Result<(), Box<dyn std::error::Error>> {
     use binance_provider::mcp::BinanceServer;
     use binance_provider::transport::sse::{CancellationToken, SseServer, SseServerConfig};
 
-    let addr = format!("0.0.0.0:{}", port).parse()?;
     tracing::info!("Starting SSE server on {}", addr);
 
     // Create SSE server configuration
@@ -396,19 +794,38 @@ async fn run_sse_server(port: u16) -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("  SSE endpoint: http://{}/sse", addr);
     tracing::info!("  POST endpoint: http://{}/message", addr);
 
-    // Attach MCP service
-    let shutdown_ct = sse_server.with_service(|| BinanceServer::new());
+    // Attach MCP service; sessions share one server so they also share the
+    // order book manager and its pre-subscribed books
+    let server = BinanceServer::new();
+    #[cfg(feature = "orderbook")]
+    let orderbook_manager = server.orderbook_manager.clone();
+    // Each connection gets its own MCP_TOOL_RATE_PER_MIN budget
+    let tool_rate = binance_provider::rate_limit::tool_rate_from_env()?;
+    let shutdown_ct = sse_server.with_service(move || server.clone().with_tool_rate(tool_rate));
+
+    // Warm up the books listed in ANALYTICS_SYMBOLS (default: BTCUSDT, ETHUSDT)
+    #[cfg(feature = "orderbook")]
+    {
+        let symbols = binance_provider::config::AnalyticsConfig::from_env()?.symbols;
+        tracing::info!("Pre-subscribing SSE symbols: {}", symbols.join(", "));
+        spawn_health_webhook(orderbook_manager.clone()).await;
+        binance_provider::transport::sse::spawn_presubscribe(
+            orderbook_manager,
+            symbols,
+            shutdown_ct.child_token(),
+        );
+    }
 
-    // Wait for shutdown signal
-    tokio::signal::ctrl_c().await?;
-    tracing::info!("Received shutdown signal (Ctrl+C)");
+    // Wait for shutdown signal (Ctrl+C, or SIGTERM on Unix)
+    let signal = binance_provider::shutdown::shutdown_signal().await?;
+    tracing::info!("Received shutdown signal ({})", signal);
     shutdown_ct.cancel();
 
     Ok(())
 }
 
 #[cfg(not(feature = "mcp_server"))]
-async fn run_sse_server(_port: u16) -> Result<(), Box<dyn std::error::Error>> {
+async fn run_sse_server(_addr: SocketAddr) -> Result<(), Box<dyn std::error::Error>> {
     tracing::error!("SSE mode not available - compile with 'mcp_server' feature");
     Err("SSE mode not available".into())
 }
@@ -0,0 +1,161 @@
+//! Exchange-agnostic market data source
+//!
+//! [`ReportGenerator`](crate::report::ReportGenerator) fetches REST market data
+//! through [`MarketDataSource`] rather than a concrete client, so another exchange
+//! can be plugged in by implementing the trait. Responses use the Binance types,
+//! which other providers convert into. [`BinanceClient`] is the only
+//! implementation today.
+//!
+//! Spot endpoints every exchange offers are required. Exchange info, aggregated
+//! trades and futures data have defaults for sources without them: the report
+//! then falls back to default price precision, shows the recent trades
+//! placeholder and omits the derivatives section.
+
+use crate::binance::types::{
+    AggTradeResponse, ExchangeInfo, KlineData, LongShortRatio, OpenInterest, OpenInterestHist,
+    OrderBook, Ticker24hr, Trade,
+};
+use crate::binance::BinanceClient;
+use crate::error::McpError;
+
+/// Provider of REST market data for one exchange
+#[tonic::async_trait]
+pub trait MarketDataSource: Send + Sync + 'static {
+    /// 24-hour rolling ticker statistics for `symbol`
+    async fn get_24hr_ticker(&self, symbol: &str) -> Result<Ticker24hr, McpError>;
+
+    /// Order book snapshot with up to `limit` levels per side
+    async fn get_order_book(&self, symbol: &str, limit: Option<u32>)
+        -> Result<OrderBook, McpError>;
+
+    /// Candlesticks for `interval` (e.g. "1h"), most recent `limit`
+    async fn get_klines(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: Option<u32>,
+    ) -> Result<KlineData, McpError>;
+
+    /// Most recent `limit` public trades
+    async fn get_recent_trades(
+        &self,
+        symbol: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<Trade>, McpError>;
+
+    /// Trading rules, used for price tick sizes
+    async fn get_exchange_info(&self, _symbol: Option<&str>) -> Result<ExchangeInfo, McpError> {
+        Err(McpError::InvalidRequest(
+            "exchange info is not supported by this market data source".to_string(),
+        ))
+    }
+
+    /// Aggregated trades, most recent `limit` when no range is given
+    async fn get_agg_trades(
+        &self,
+        _symbol: &str,
+        _limit: Option<u32>,
+        _from_id: Option<i64>,
+        _start_time: Option<i64>,
+        _end_time: Option<i64>,
+    ) -> Result<Vec<AggTradeResponse>, McpError> {
+        Err(McpError::InvalidRequest(
+            "aggregated trades are not supported by this market data source".to_string(),
+        ))
+    }
+
+    /// Futures open interest; `SymbolNotFound` means there is no futures contract
+    async fn get_open_interest(&self, symbol: &str) -> Result<OpenInterest, McpError> {
+        Err(McpError::SymbolNotFound(format!(
+            "{} (no futures data from this market data source)",
+            symbol
+        )))
+    }
+
+    /// Open interest history in `period` buckets
+    async fn get_open_interest_hist(
+        &self,
+        _symbol: &str,
+        _period: &str,
+        _limit: Option<u32>,
+    ) -> Result<Vec<OpenInterestHist>, McpError> {
+        Ok(Vec::new())
+    }
+
+    /// Global long/short account ratio for `period`
+    async fn get_long_short_ratio(
+        &self,
+        _symbol: &str,
+        _period: &str,
+    ) -> Result<Vec<LongShortRatio>, McpError> {
+        Ok(Vec::new())
+    }
+}
+
+#[tonic::async_trait]
+impl MarketDataSource for BinanceClient {
+    async fn get_24hr_ticker(&self, symbol: &str) -> Result<Ticker24hr, McpError> {
+        BinanceClient::get_24hr_ticker(self, symbol).await
+    }
+
+    async fn get_order_book(
+        &self,
+        symbol: &str,
+        limit: Option<u32>,
+    ) -> Result<OrderBook, McpError> {
+        BinanceClient::get_order_book(self, symbol, limit).await
+    }
+
+    async fn get_klines(
+        &self,
+        symbol: &str,
+        interval: &str,
+        limit: Option<u32>,
+    ) -> Result<KlineData, McpError> {
+        BinanceClient::get_klines(self, symbol, interval, limit).await
+    }
+
+    async fn get_recent_trades(
+        &self,
+        symbol: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<Trade>, McpError> {
+        BinanceClient::get_recent_trades(self, symbol, limit).await
+    }
+
+    async fn get_exchange_info(&self, symbol: Option<&str>) -> Result<ExchangeInfo, McpError> {
+        BinanceClient::get_exchange_info(self, symbol).await
+    }
+
+    async fn get_agg_trades(
+        &self,
+        symbol: &str,
+        limit: Option<u32>,
+        from_id: Option<i64>,
+        start_time: Option<i64>,
+        end_time: Option<i64>,
+    ) -> Result<Vec<AggTradeResponse>, McpError> {
+        BinanceClient::get_agg_trades(self, symbol, limit, from_id, start_time, end_time).await
+    }
+
+    async fn get_open_interest(&self, symbol: &str) -> Result<OpenInterest, McpError> {
+        BinanceClient::get_open_interest(self, symbol).await
+    }
+
+    async fn get_open_interest_hist(
+        &self,
+        symbol: &str,
+        period: &str,
+        limit: Option<u32>,
+    ) -> Result<Vec<OpenInterestHist>, McpError> {
+        BinanceClient::get_open_interest_hist(self, symbol, period, limit).await
+    }
+
+    async fn get_long_short_ratio(
+        &self,
+        symbol: &str,
+        period: &str,
+    ) -> Result<Vec<LongShortRatio>, McpError> {
+        BinanceClient::get_long_short_ratio(self, symbol, period).await
+    }
+}
@@ -0,0 +1,303 @@
+//! Market-wide scan of 24hr tickers
+//!
+//! Filters the all-symbols 24hr ticker list down to one quote asset (per
+//! exchangeInfo) and a minimum quote volume, then ranks what is left by volume
+//! or price change to answer "what's moving right now" without a request per
+//! symbol.
+
+use crate::binance::types::{SymbolInfo, Ticker24hr};
+use crate::pagination::Page;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::HashSet;
+
+/// Default number of symbols returned by a scan
+pub const DEFAULT_SCAN_TOP_N: usize = 10;
+
+/// Upper bound on `top_n`
+pub const MAX_SCAN_TOP_N: usize = 100;
+
+/// Ranking applied by [`scan_tickers`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanSortBy {
+    /// Highest 24h quote volume first
+    Volume,
+    /// Largest 24h percentage gain first
+    Gainers,
+    /// Largest 24h percentage loss first
+    Losers,
+    /// Largest absolute 24h percentage change first, either direction
+    Movers,
+}
+
+impl ScanSortBy {
+    /// Every ranking, in the order listed in the tool schema
+    pub const ALL: [ScanSortBy; 4] = [
+        ScanSortBy::Volume,
+        ScanSortBy::Gainers,
+        ScanSortBy::Losers,
+        ScanSortBy::Movers,
+    ];
+
+    /// Parse a ranking label ("volume", "gainers", "losers", "movers")
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|sort_by| sort_by.label().eq_ignore_ascii_case(raw.trim()))
+    }
+
+    /// Label used in tool arguments and output
+    pub fn label(&self) -> &'static str {
+        match self {
+            ScanSortBy::Volume => "volume",
+            ScanSortBy::Gainers => "gainers",
+            ScanSortBy::Losers => "losers",
+            ScanSortBy::Movers => "movers",
+        }
+    }
+}
+
+/// One symbol in a scan result
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ScanEntry {
+    pub symbol: String,
+    pub last_price: f64,
+    /// 24h change in percent
+    pub price_change_percent: f64,
+    /// 24h traded volume in the quote asset
+    pub quote_volume: f64,
+    pub high_price: f64,
+    pub low_price: f64,
+    /// 24h trade count
+    pub trade_count: i64,
+}
+
+/// Ranked scan over all symbols quoted in one asset
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct MarketScan {
+    /// Quote asset the symbols were filtered by (e.g. "USDT")
+    pub quote: String,
+    /// Ranking applied ("volume", "gainers", "losers" or "movers")
+    pub sort_by: String,
+    /// Minimum 24h quote volume a symbol needed
+    pub min_volume: f64,
+    /// Symbols passing the filters before `top_n` was applied
+    pub matched: usize,
+    /// At most `top_n` symbols, best ranked first; the cursor is the rank
+    /// offset of the next page
+    #[serde(flatten)]
+    pub page: Page<ScanEntry>,
+}
+
+/// Filter `tickers` to `quote` pairs with at least `min_volume` quote volume and
+/// return the `top_n` best ranked by `sort_by`, skipping the first `offset`
+///
+/// A symbol matches when exchangeInfo `symbols` lists `quote` as its quote
+/// asset (suffix matching would count e.g. `BTCTUSD` as a `USD` pair). Tickers
+/// with unparseable numbers are skipped; ties keep the symbol order.
+pub fn scan_tickers(
+    tickers: &[Ticker24hr],
+    symbols: &[SymbolInfo],
+    quote: &str,
+    min_volume: f64,
+    top_n: usize,
+    sort_by: ScanSortBy,
+    offset: usize,
+) -> MarketScan {
+    let quote = quote.trim().to_uppercase();
+    let quoted: HashSet<&str> = symbols
+        .iter()
+        .filter(|info| info.quote_asset.eq_ignore_ascii_case(&quote))
+        .map(|info| info.symbol.as_str())
+        .collect();
+    let mut entries: Vec<ScanEntry> = tickers
+        .iter()
+        .filter(|ticker| quoted.contains(ticker.symbol.as_str()))
+        .filter_map(scan_entry)
+        .filter(|entry| entry.quote_volume >= min_volume)
+        .collect();
+
+    entries.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+    let key = |entry: &ScanEntry| match sort_by {
+        ScanSortBy::Volume => entry.quote_volume,
+        ScanSortBy::Gainers => entry.price_change_percent,
+        ScanSortBy::Losers => -entry.price_change_percent,
+        ScanSortBy::Movers => entry.price_change_percent.abs(),
+    };
+    entries.sort_by(|a, b| key(b).total_cmp(&key(a)));
+
+    MarketScan {
+        quote,
+        sort_by: sort_by.label().to_string(),
+        min_volume,
+        matched: entries.len(),
+        page: Page::from_offset(entries, offset, top_n),
+    }
+}
+
+fn scan_entry(ticker: &Ticker24hr) -> Option<ScanEntry> {
+    Some(ScanEntry {
+        symbol: ticker.symbol.clone(),
+        last_price: ticker.last_price.parse().ok()?,
+        price_change_percent: ticker.price_change_percent.parse().ok()?,
+        quote_volume: ticker.quote_volume.parse().ok()?,
+        high_price: ticker.high_price.parse().ok()?,
+        low_price: ticker.low_price.parse().ok()?,
+        trade_count: ticker.count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticker(symbol: &str, change_percent: &str, quote_volume: &str) -> Ticker24hr {
+        Ticker24hr {
+            symbol: symbol.to_string(),
+            price_change: "0".to_string(),
+            price_change_percent: change_percent.to_string(),
+            weighted_avg_price: "1.0".to_string(),
+            prev_close_price: "1.0".to_string(),
+            last_price: "1.0".to_string(),
+            last_qty: "1.0".to_string(),
+            bid_price: "1.0".to_string(),
+            ask_price: "1.0".to_string(),
+            open_price: "1.0".to_string(),
+            high_price: "1.1".to_string(),
+            low_price: "0.9".to_string(),
+            volume: "100.0".to_string(),
+            quote_volume: quote_volume.to_string(),
+            open_time: 0,
+            close_time: 86_400_000,
+            first_id: 1,
+            last_id: 10,
+            count: 10,
+        }
+    }
+
+    fn all_tickers() -> Vec<Ticker24hr> {
+        vec![
+            ticker("BTCUSDT", "1.50", "900000000"),
+            ticker("ETHUSDT", "-4.20", "500000000"),
+            ticker("SOLUSDT", "8.75", "200000000"),
+            ticker("PEPEUSDT", "25.00", "5000"),
+            ticker("ETHBTC", "-9.00", "800000000"),
+            ticker("USDT", "50.00", "900000000"),
+            ticker("BADUSDT", "n/a", "900000000"),
+            ticker("BTCTUSD", "3.00", "900000000"),
+        ]
+    }
+
+    fn symbol_info(symbol: &str, base: &str, quote: &str) -> SymbolInfo {
+        SymbolInfo {
+            symbol: symbol.to_string(),
+            status: "TRADING".to_string(),
+            base_asset: base.to_string(),
+            quote_asset: quote.to_string(),
+            filters: Vec::new(),
+        }
+    }
+
+    fn all_symbols() -> Vec<SymbolInfo> {
+        vec![
+            symbol_info("BTCUSDT", "BTC", "USDT"),
+            symbol_info("ETHUSDT", "ETH", "USDT"),
+            symbol_info("SOLUSDT", "SOL", "USDT"),
+            symbol_info("PEPEUSDT", "PEPE", "USDT"),
+            symbol_info("ETHBTC", "ETH", "BTC"),
+            symbol_info("BADUSDT", "BAD", "USDT"),
+            symbol_info("BTCTUSD", "BTC", "TUSD"),
+        ]
+    }
+
+    fn symbols(scan: &MarketScan) -> Vec<&str> {
+        scan.page.items.iter().map(|t| t.symbol.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filters_by_quote_and_volume() {
+        let scan = scan_tickers(
+            &all_tickers(),
+            &all_symbols(),
+            "usdt",
+            100_000.0,
+            10,
+            ScanSortBy::Volume,
+            0,
+        );
+
+        assert_eq!(scan.quote, "USDT");
+        assert_eq!(scan.matched, 3);
+        assert_eq!(symbols(&scan), ["BTCUSDT", "ETHUSDT", "SOLUSDT"]);
+        assert_eq!(scan.page.items[0].quote_volume, 900_000_000.0);
+    }
+
+    #[test]
+    fn test_sorts_and_truncates() {
+        let tickers = all_tickers();
+        let infos = all_symbols();
+
+        let gainers = scan_tickers(
+            &tickers,
+            &infos,
+            "USDT",
+            100_000.0,
+            2,
+            ScanSortBy::Gainers,
+            0,
+        );
+        assert_eq!(symbols(&gainers), ["SOLUSDT", "BTCUSDT"]);
+        assert_eq!(gainers.matched, 3);
+        assert!(gainers.page.truncated);
+        assert_eq!(gainers.page.next_cursor.as_deref(), Some("2"));
+
+        let rest = scan_tickers(
+            &tickers,
+            &infos,
+            "USDT",
+            100_000.0,
+            2,
+            ScanSortBy::Gainers,
+            2,
+        );
+        assert_eq!(symbols(&rest), ["ETHUSDT"]);
+        assert!(!rest.page.truncated);
+
+        let losers = scan_tickers(
+            &tickers,
+            &infos,
+            "USDT",
+            100_000.0,
+            1,
+            ScanSortBy::Losers,
+            0,
+        );
+        assert_eq!(symbols(&losers), ["ETHUSDT"]);
+
+        let movers = scan_tickers(&tickers, &infos, "USDT", 0.0, 10, ScanSortBy::Movers, 0);
+        assert_eq!(
+            symbols(&movers),
+            ["PEPEUSDT", "SOLUSDT", "ETHUSDT", "BTCUSDT"]
+        );
+    }
+
+    #[test]
+    fn test_matches_quote_asset_not_suffix() {
+        let tickers = all_tickers();
+        let infos = all_symbols();
+
+        // BTCTUSD ends in "USD" but is quoted in TUSD
+        let usd = scan_tickers(&tickers, &infos, "USD", 0.0, 10, ScanSortBy::Volume, 0);
+        assert_eq!(usd.matched, 0);
+
+        let tusd = scan_tickers(&tickers, &infos, "tusd", 0.0, 10, ScanSortBy::Volume, 0);
+        assert_eq!(symbols(&tusd), ["BTCTUSD"]);
+    }
+
+    #[test]
+    fn test_sort_by_labels() {
+        assert_eq!(ScanSortBy::parse("Gainers"), Some(ScanSortBy::Gainers));
+        assert_eq!(ScanSortBy::parse(" movers "), Some(ScanSortBy::Movers));
+        assert_eq!(ScanSortBy::parse("price"), None);
+    }
+}
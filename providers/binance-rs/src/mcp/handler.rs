@@ -5,15 +5,17 @@
 
 use crate::mcp::server::BinanceServer;
 use crate::mcp::types::{OrderbookParam, SymbolParam};
+use rmcp::handler::server::tool::ToolCallContext;
 use rmcp::handler::server::wrapper::Parameters;
 use rmcp::handler::server::ServerHandler;
 use rmcp::model::{
-    CallToolResult, Content, ErrorData, Implementation, InitializeResult, ListResourcesResult,
-    PaginatedRequestParam, ProtocolVersion, ReadResourceRequestParam, ReadResourceResult,
-    ResourcesCapability, ServerCapabilities, ToolsCapability,
+    CallToolRequestParam, CallToolResult, Content, ErrorData, Implementation, InitializeResult,
+    ListResourcesResult, ListToolsResult, PaginatedRequestParam, ProtocolVersion,
+    ReadResourceRequestParam, ReadResourceResult, ResourcesCapability, ServerCapabilities,
+    ToolsCapability,
 };
 use rmcp::service::{RequestContext, RoleServer};
-use rmcp::{tool, tool_handler, tool_router};
+use rmcp::{tool, tool_router};
 use serde_json::json;
 
 /// MCP Tool Router for Binance operations
@@ -91,13 +93,69 @@ impl BinanceServer {
             response_json.to_string(),
         )]))
     }
+
+    /// Get overall server health
+    ///
+    /// Reports orderbook manager health, WebSocket connectivity and uptime.
+    #[tool(
+        name = "binance.health",
+        description = "Get overall server health: orderbook status, WebSocket connectivity and uptime"
+    )]
+    pub async fn health(&self) -> Result<CallToolResult, ErrorData> {
+        let health = crate::health::check_health(
+            &self.client,
+            #[cfg(feature = "orderbook")]
+            Some(&self.orderbook_manager),
+            #[cfg(feature = "orderbook_analytics")]
+            None,
+        )
+        .await;
+
+        let response_json = json!(health);
+
+        Ok(CallToolResult::success(vec![Content::text(
+            response_json.to_string(),
+        )]))
+    }
+
+    /// Run the end-to-end self-test for a canary symbol
+    ///
+    /// Exercises REST and the depth WebSocket; storage and report checks are
+    /// skipped since this server runs without them.
+    #[tool(
+        name = "binance.self_test",
+        description = "Smoke-test REST and WebSocket connectivity for a canary symbol, with per-component pass/fail and timings"
+    )]
+    pub async fn self_test(
+        &self,
+        params: Parameters<SymbolParam>,
+    ) -> Result<CallToolResult, ErrorData> {
+        let symbol = params.0.symbol.to_uppercase();
+
+        let report = crate::self_test::run_self_test(
+            &self.client,
+            #[cfg(feature = "orderbook")]
+            Some(&self.orderbook_manager),
+            #[cfg(feature = "orderbook_analytics")]
+            None,
+            #[cfg(feature = "orderbook")]
+            None,
+            &symbol,
+        )
+        .await;
+
+        let response_json = json!(report);
+
+        Ok(CallToolResult::success(vec![Content::text(
+            response_json.to_string(),
+        )]))
+    }
 }
 
 /// ServerHandler trait implementation
 ///
-/// Uses the #[tool_handler] macro to automatically wire the tool router
-/// to the ServerHandler trait.
-#[tool_handler(router = self.tool_router)]
+/// Tool calls and listings are served by the tool router; calls are checked
+/// against the connection's tool budget first.
 impl ServerHandler for BinanceServer {
     /// Returns server information and capabilities
     ///
@@ -131,6 +189,36 @@ impl ServerHandler for BinanceServer {
         }
     }
 
+    /// Routes a tool call, rejecting it with a 429 error past the rate limit
+    async fn call_tool(
+        &self,
+        request: CallToolRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<CallToolResult, ErrorData> {
+        if let Some(bucket) = &self.tool_bucket {
+            let acquired = bucket.lock().unwrap().try_acquire();
+            if let Err(retry_after) = acquired {
+                return Err(crate::error::McpError::RateLimitError(format!(
+                    "Tool call rate limit exceeded; retry in {}s",
+                    retry_after.as_secs_f64().ceil() as u64
+                ))
+                .into());
+            }
+        }
+
+        let tcc = ToolCallContext::new(self, request, context);
+        self.tool_router.call(tcc).await
+    }
+
+    /// Lists all tools registered on the tool router
+    async fn list_tools(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListToolsResult, ErrorData> {
+        Ok(ListToolsResult::with_all_items(self.tool_router.list_all()))
+    }
+
     /// Lists all available resources
     ///
     /// Returns a list of resources that can be accessed via read_resource.
@@ -3,11 +3,12 @@
 //! This module contains the BinanceServer struct which implements the MCP ServerHandler trait.
 
 use crate::binance::BinanceClient;
+use crate::rate_limit::TokenBucket;
 use rmcp::handler::server::router::tool::ToolRouter;
+use std::sync::Mutex;
 
 #[cfg(feature = "orderbook")]
 use crate::orderbook::OrderBookManager;
-#[cfg(feature = "orderbook")]
 use std::sync::Arc;
 
 /// Main Binance MCP Server struct
@@ -25,6 +26,9 @@ pub struct BinanceServer {
     /// Order book manager for WebSocket subscriptions (feature-gated)
     #[cfg(feature = "orderbook")]
     pub orderbook_manager: Arc<OrderBookManager>,
+
+    /// Tool call budget of this connection (None = unlimited); shared by clones
+    pub tool_bucket: Option<Arc<Mutex<TokenBucket>>>,
 }
 
 impl BinanceServer {
@@ -36,15 +40,14 @@ impl BinanceServer {
         let client = BinanceClient::new();
 
         #[cfg(feature = "orderbook")]
-        let client_arc = Arc::new(client.clone());
-        #[cfg(feature = "orderbook")]
-        let orderbook_manager = Arc::new(OrderBookManager::new(client_arc));
+        let orderbook_manager = Self::orderbook_manager(&client);
 
         Self {
             client,
             tool_router: Self::tool_router(),
             #[cfg(feature = "orderbook")]
             orderbook_manager,
+            tool_bucket: None,
         }
     }
 
@@ -55,17 +58,52 @@ impl BinanceServer {
         let client = BinanceClient::with_credentials();
 
         #[cfg(feature = "orderbook")]
-        let client_arc = Arc::new(client.clone());
-        #[cfg(feature = "orderbook")]
-        let orderbook_manager = Arc::new(OrderBookManager::new(client_arc));
+        let orderbook_manager = Self::orderbook_manager(&client);
 
         Self {
             client,
             tool_router: Self::tool_router(),
             #[cfg(feature = "orderbook")]
             orderbook_manager,
+            tool_bucket: None,
         }
     }
+
+    /// This server with a fresh budget of `rate_per_min` tool calls per minute
+    ///
+    /// Used per SSE connection so each client is limited separately while
+    /// still sharing the client and order book manager (None = unlimited).
+    pub fn with_tool_rate(mut self, rate_per_min: Option<u32>) -> Self {
+        self.tool_bucket =
+            rate_per_min.map(|rate| Arc::new(Mutex::new(TokenBucket::per_minute(rate))));
+        self
+    }
+
+    /// Order book manager with staleness thresholds and snapshot depth from environment
+    ///
+    /// Invalid `ORDERBOOK_STALENESS_*` values fall back to the 5s default and an
+    /// invalid `ORDERBOOK_SNAPSHOT_DEPTH` to 100 levels.
+    #[cfg(feature = "orderbook")]
+    fn orderbook_manager(client: &BinanceClient) -> Arc<OrderBookManager> {
+        let staleness = crate::config::StalenessConfig::from_env().unwrap_or_else(|e| {
+            tracing::warn!("Ignoring invalid order book staleness config: {}", e);
+            Default::default()
+        });
+        let client = Arc::new(client.clone());
+        let manager = crate::config::orderbook::parse_snapshot_depth(
+            std::env::var("ORDERBOOK_SNAPSHOT_DEPTH").ok().as_deref(),
+        )
+        .map_err(|e| e.to_string())
+        .and_then(|depth| {
+            OrderBookManager::with_snapshot_depth(Arc::clone(&client), depth)
+                .map_err(|e| e.to_string())
+        })
+        .unwrap_or_else(|e| {
+            tracing::warn!("Ignoring invalid order book snapshot depth: {}", e);
+            OrderBookManager::new(client)
+        });
+        Arc::new(manager.with_staleness(staleness))
+    }
 }
 
 impl Default for BinanceServer {
@@ -1,7 +1,8 @@
 //! Anomaly detection (quote stuffing, icebergs, flash crashes)
 //!
 //! Detects market microstructure anomalies that indicate manipulation or risk:
-//! - Quote stuffing: >500 updates/sec with <10% fill rate
+//! - Quote stuffing: >500 updates/sec with <10% fill rate (configurable per call
+//!   via [`QuoteStuffingThresholds`], since baseline update rates differ by symbol)
 //! - Iceberg orders: Refill rate >5x median with 95% confidence
 //! - Flash crash risk: >80% liquidity drain, >10x spread widening, >90% cancellation rate
 
@@ -16,17 +17,43 @@ use crate::orderbook::analytics::{
     types::{AnomalyType, MarketMicrostructureAnomaly, Severity},
 };
 
-/// Detect quote stuffing (>500 updates/sec, <10% fill rate)
+/// Default update rate (updates/sec) above which quotes count as stuffed
+pub const DEFAULT_QUOTE_STUFFING_UPDATE_RATE: f64 = 500.0;
+
+/// Default fill rate (0-1) below which high update rates count as stuffing
+pub const DEFAULT_QUOTE_STUFFING_FILL_RATE: f64 = 0.10;
+
+/// Quote stuffing detection thresholds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct QuoteStuffingThresholds {
+    /// Flag when the update rate (updates/sec) exceeds this
+    pub update_rate: f64,
+    /// ...and the fill rate (0-1) is below this
+    pub fill_rate: f64,
+}
+
+impl Default for QuoteStuffingThresholds {
+    fn default() -> Self {
+        Self {
+            update_rate: DEFAULT_QUOTE_STUFFING_UPDATE_RATE,
+            fill_rate: DEFAULT_QUOTE_STUFFING_FILL_RATE,
+        }
+    }
+}
+
+/// Detect quote stuffing (update rate above threshold, fill rate below threshold)
 ///
 /// # Arguments
 /// * `snapshots` - Recent orderbook snapshots (recommended: last 10 seconds)
-/// * `fill_rate` - Percentage of orders that resulted in trades
+/// * `fill_rate` - Share of updates that resulted in trades (0-1)
+/// * `thresholds` - Update/fill rate thresholds (default: >500 updates/sec, <10% fill)
 ///
 /// # Returns
 /// Some(anomaly) if detected, None otherwise
 pub fn detect_quote_stuffing(
     snapshots: &[OrderBookSnapshot],
     fill_rate: f64,
+    thresholds: &QuoteStuffingThresholds,
 ) -> Option<MarketMicrostructureAnomaly> {
     if snapshots.len() < 2 {
         return None;
@@ -35,28 +62,24 @@ pub fn detect_quote_stuffing(
     // Calculate update rate (snapshots per second) using actual timestamps
     let first_timestamp = snapshots.first().unwrap().timestamp;
     let last_timestamp = snapshots.last().unwrap().timestamp;
-    let duration_millis = (last_timestamp - first_timestamp) as f64;
 
-    // Handle edge case: all snapshots have same timestamp
-    // Assume 1 second total duration (for testing scenarios where timestamps are identical)
-    let duration_secs = if duration_millis < 1.0 {
-        1.0
-    } else {
-        duration_millis / 1000.0
-    };
+    // Snapshot timestamps are Unix seconds. If all snapshots share one timestamp,
+    // assume 1 second total duration (for testing scenarios where timestamps are identical)
+    let duration_secs = ((last_timestamp - first_timestamp) as f64).max(1.0);
 
     let update_count = snapshots.len() - 1;
     let update_rate = (update_count as f64) / duration_secs;
 
-    // Thresholds from FR
-    let is_quote_stuffing = update_rate > 500.0 && fill_rate < 0.10;
+    let is_quote_stuffing =
+        update_rate > thresholds.update_rate && fill_rate < thresholds.fill_rate;
 
     if is_quote_stuffing {
         // Calculate severity based on update rate
-        let severity = calculate_quote_stuffing_severity(update_rate);
+        let severity = calculate_quote_stuffing_severity(update_rate, thresholds.update_rate);
 
         // Confidence based on how far from threshold
-        let confidence_score = ((update_rate - 500.0) / 500.0).min(1.0);
+        let confidence_score =
+            ((update_rate - thresholds.update_rate) / thresholds.update_rate).min(1.0);
 
         let recommended_action = match severity {
             Severity::Critical => "Suspend trading immediately - likely market manipulation",
@@ -65,6 +88,7 @@ pub fn detect_quote_stuffing(
             Severity::Low => "Monitor closely - consider reducing position size",
         };
 
+        let detection_timestamp = Utc::now();
         Some(MarketMicrostructureAnomaly {
             anomaly_id: Uuid::new_v4(),
             symbol: snapshots[0]
@@ -76,7 +100,8 @@ pub fn detect_quote_stuffing(
                 update_rate,
                 fill_rate,
             },
-            detection_timestamp: Utc::now(),
+            detection_timestamp,
+            detection_timestamp_ts: detection_timestamp.into(),
             confidence_score,
             affected_price_levels: Vec::new(), // Not applicable for quote stuffing
             severity,
@@ -84,7 +109,9 @@ pub fn detect_quote_stuffing(
             metadata: serde_json::json!({
                 "update_count": update_count,
                 "duration_secs": duration_secs,
-                "threshold_exceeded_by": format!("{:.0}%", (update_rate / 500.0 - 1.0) * 100.0)
+                "update_rate_threshold": thresholds.update_rate,
+                "fill_rate_threshold": thresholds.fill_rate,
+                "threshold_exceeded_by": format!("{:.0}%", (update_rate / thresholds.update_rate - 1.0) * 100.0)
             }),
         })
     } else {
@@ -92,24 +119,70 @@ pub fn detect_quote_stuffing(
     }
 }
 
-/// Calculate severity based on update rate thresholds
+/// Calculate severity based on update rate relative to the detection threshold
 ///
-/// Thresholds (from T049):
-/// - Medium: 500-750 updates/sec
-/// - High: 750-1000 updates/sec
-/// - Critical: >1000 updates/sec
-fn calculate_quote_stuffing_severity(update_rate: f64) -> Severity {
-    if update_rate > 1000.0 {
+/// Bands (from T049, 500-750-1000 updates/sec at the default threshold):
+/// - Medium: 1x-1.5x threshold
+/// - High: 1.5x-2x threshold
+/// - Critical: >2x threshold
+fn calculate_quote_stuffing_severity(update_rate: f64, threshold: f64) -> Severity {
+    if update_rate > threshold * 2.0 {
         Severity::Critical
-    } else if update_rate > 750.0 {
+    } else if update_rate > threshold * 1.5 {
         Severity::High
-    } else if update_rate > 500.0 {
+    } else if update_rate > threshold {
         Severity::Medium
     } else {
         Severity::Low
     }
 }
 
+/// Estimate the share of updates that traded against the top of book
+///
+/// Stored snapshots carry no executions, so an update counts as filled when the
+/// best level on either side was consumed: same best price with less quantity,
+/// or the best bid moved down / best ask moved up. Cancellations at the touch
+/// look the same, so this over-estimates fills and errs against flagging.
+pub fn estimate_fill_rate(snapshots: &[OrderBookSnapshot]) -> f64 {
+    if snapshots.len() < 2 {
+        return 0.0;
+    }
+
+    let consumed = snapshots
+        .windows(2)
+        .filter(|pair| {
+            let (before, after) = (&pair[0], &pair[1]);
+            level_consumed(before.bids.first(), after.bids.first(), |old, new| {
+                new < old
+            }) || level_consumed(before.asks.first(), after.asks.first(), |old, new| {
+                new > old
+            })
+        })
+        .count();
+
+    consumed as f64 / (snapshots.len() - 1) as f64
+}
+
+/// Whether the best level `before` was (partly) taken by the time of `after`
+///
+/// `moved_through(old_price, new_price)` is true when the new best price lies
+/// behind the old one, i.e. the old level is gone.
+fn level_consumed(
+    before: Option<&(String, String)>,
+    after: Option<&(String, String)>,
+    moved_through: impl Fn(f64, f64) -> bool,
+) -> bool {
+    let parse = |level: Option<&(String, String)>| {
+        level.and_then(|(price, qty)| Some((price.parse::<f64>().ok()?, qty.parse::<f64>().ok()?)))
+    };
+    match (parse(before), parse(after)) {
+        (Some((old_price, old_qty)), Some((new_price, new_qty))) => {
+            (new_price == old_price && new_qty < old_qty) || moved_through(old_price, new_price)
+        }
+        _ => false,
+    }
+}
+
 /// Detect iceberg orders (refill rate >5x median, 95% confidence)
 ///
 /// Uses z-score with 95% confidence threshold (z > 1.96)
@@ -143,6 +216,7 @@ pub fn detect_iceberg_orders(
 
         let severity = Severity::from_confidence(confidence_score);
 
+        let detection_timestamp = Utc::now();
         Some(MarketMicrostructureAnomaly {
             anomaly_id: Uuid::new_v4(),
             symbol: String::new(), // Should be passed as parameter
@@ -151,7 +225,8 @@ pub fn detect_iceberg_orders(
                 refill_rate_multiplier,
                 median_refill_rate,
             },
-            detection_timestamp: Utc::now(),
+            detection_timestamp,
+            detection_timestamp_ts: detection_timestamp.into(),
             confidence_score,
             affected_price_levels: vec![price_level],
             severity,
@@ -214,6 +289,7 @@ pub fn detect_flash_crash_risk(
                 / 3.0;
         let confidence_clamped = confidence_score.min(1.0);
 
+        let detection_timestamp = Utc::now();
         Some(MarketMicrostructureAnomaly {
             anomaly_id: Uuid::new_v4(),
             symbol: String::new(), // Should be passed as parameter
@@ -222,7 +298,8 @@ pub fn detect_flash_crash_risk(
                 spread_multiplier,
                 cancellation_rate,
             },
-            detection_timestamp: Utc::now(),
+            detection_timestamp,
+            detection_timestamp_ts: detection_timestamp.into(),
             confidence_score: confidence_clamped,
             affected_price_levels: Vec::new(),
             severity: Severity::Critical, // Flash crash risk is always critical
@@ -255,16 +332,36 @@ fn calculate_spread(snapshot: &OrderBookSnapshot) -> f64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::orderbook::analytics::test_support::snapshot;
 
     #[test]
     fn test_calculate_quote_stuffing_severity() {
         assert_eq!(
-            calculate_quote_stuffing_severity(1100.0),
+            calculate_quote_stuffing_severity(1100.0, 500.0),
+            Severity::Critical
+        );
+        assert_eq!(
+            calculate_quote_stuffing_severity(850.0, 500.0),
+            Severity::High
+        );
+        assert_eq!(
+            calculate_quote_stuffing_severity(600.0, 500.0),
+            Severity::Medium
+        );
+        assert_eq!(
+            calculate_quote_stuffing_severity(400.0, 500.0),
+            Severity::Low
+        );
+
+        // Bands scale with a custom threshold
+        assert_eq!(
+            calculate_quote_stuffing_severity(250.0, 100.0),
             Severity::Critical
         );
-        assert_eq!(calculate_quote_stuffing_severity(850.0), Severity::High);
-        assert_eq!(calculate_quote_stuffing_severity(600.0), Severity::Medium);
-        assert_eq!(calculate_quote_stuffing_severity(400.0), Severity::Low);
+        assert_eq!(
+            calculate_quote_stuffing_severity(120.0, 100.0),
+            Severity::Medium
+        );
     }
 
     #[test]
@@ -279,7 +376,7 @@ mod tests {
             600
         ]; // 600 snapshots = 600 updates/sec
 
-        let result = detect_quote_stuffing(&snapshots, 0.05);
+        let result = detect_quote_stuffing(&snapshots, 0.05, &QuoteStuffingThresholds::default());
         assert!(result.is_some());
 
         let anomaly = result.unwrap();
@@ -289,4 +386,58 @@ mod tests {
         ));
         assert_eq!(anomaly.severity, Severity::Medium);
     }
+
+    #[test]
+    fn test_borderline_rate_flagged_only_at_lower_threshold() {
+        // 300 updates over one second: below the default 500/sec, above 250/sec
+        let snapshots = vec![snapshot(0, &[("100.0", "1.0")], &[("100.1", "1.0")]); 301];
+
+        assert!(
+            detect_quote_stuffing(&snapshots, 0.05, &QuoteStuffingThresholds::default()).is_none()
+        );
+
+        let lower = QuoteStuffingThresholds {
+            update_rate: 250.0,
+            ..Default::default()
+        };
+        let anomaly = detect_quote_stuffing(&snapshots, 0.05, &lower).expect("flagged at 250/sec");
+        assert_eq!(anomaly.severity, Severity::Medium);
+
+        // A stricter fill-rate threshold clears the same burst
+        let strict_fill = QuoteStuffingThresholds {
+            update_rate: 250.0,
+            fill_rate: 0.01,
+        };
+        assert!(detect_quote_stuffing(&snapshots, 0.05, &strict_fill).is_none());
+    }
+
+    #[test]
+    fn test_update_rate_uses_second_timestamps() {
+        // One snapshot per second for a minute is ~1 update/sec, never stuffing
+        let snapshots: Vec<_> = (0..60)
+            .map(|t| snapshot(t, &[("100.0", "1.0")], &[("100.1", "1.0")]))
+            .collect();
+        let sensitive = QuoteStuffingThresholds {
+            update_rate: 2.0,
+            fill_rate: 1.0,
+        };
+        assert!(detect_quote_stuffing(&snapshots, 0.0, &sensitive).is_none());
+    }
+
+    #[test]
+    fn test_estimate_fill_rate() {
+        let snapshots = vec![
+            snapshot(0, &[("100.0", "2.0")], &[("100.1", "2.0")]),
+            // Best bid partly taken
+            snapshot(1, &[("100.0", "1.0")], &[("100.1", "2.0")]),
+            // Quotes refreshed, nothing taken
+            snapshot(2, &[("100.0", "3.0")], &[("100.1", "2.5")]),
+            // Best ask swept, new best ask higher
+            snapshot(3, &[("100.0", "3.0")], &[("100.2", "1.0")]),
+            // Bid improved: not a fill
+            snapshot(4, &[("100.1", "1.0")], &[("100.2", "1.0")]),
+        ];
+        assert_eq!(estimate_fill_rate(&snapshots), 0.5);
+        assert_eq!(estimate_fill_rate(&snapshots[..1]), 0.0);
+    }
 }
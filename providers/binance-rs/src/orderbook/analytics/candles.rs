@@ -0,0 +1,239 @@
+//! OHLCV candles synthesized from persisted trades
+//!
+//! Fallback for when Binance's kline endpoint is unavailable: [`AggTrade`]s
+//! read from `TradeStorage` are bucketed into candles in the `/api/v3/klines`
+//! array format, so anything consuming [`KlineData`] rows (e.g.
+//! [`price_change_from_klines`](crate::price_change::price_change_from_klines))
+//! works unchanged.
+//!
+//! Synthesized candles only cover the trade history this server collected
+//! itself: nothing before the trade stream started, and no candle for an
+//! interval without trades (downtime included), where Binance would return one.
+
+use crate::binance::types::KlineData;
+use crate::orderbook::analytics::{tools::AnalyticsToolError, trade_storage::AggTrade};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Kline intervals that can be synthesized, with their length in milliseconds
+///
+/// Longer Binance intervals (`3d`, `1w`, `1M`) are not epoch-aligned and are
+/// not supported.
+pub const CANDLE_INTERVALS: [(&str, i64); 13] = [
+    ("1s", 1_000),
+    ("1m", 60_000),
+    ("3m", 3 * 60_000),
+    ("5m", 5 * 60_000),
+    ("15m", 15 * 60_000),
+    ("30m", 30 * 60_000),
+    ("1h", 3_600_000),
+    ("2h", 2 * 3_600_000),
+    ("4h", 4 * 3_600_000),
+    ("6h", 6 * 3_600_000),
+    ("8h", 8 * 3_600_000),
+    ("12h", 12 * 3_600_000),
+    ("1d", 24 * 3_600_000),
+];
+
+/// Length of a kline interval in milliseconds (None = unsupported)
+pub fn interval_ms(interval: &str) -> Option<i64> {
+    CANDLE_INTERVALS
+        .iter()
+        .find(|(label, _)| *label == interval)
+        .map(|(_, ms)| *ms)
+}
+
+/// One candle being accumulated
+struct Candle {
+    open_time: i64,
+    open: Decimal,
+    high: Decimal,
+    low: Decimal,
+    close: Decimal,
+    volume: Decimal,
+    quote_volume: Decimal,
+    trades: u64,
+    taker_buy_volume: Decimal,
+    taker_buy_quote_volume: Decimal,
+}
+
+impl Candle {
+    fn new(open_time: i64, price: Decimal) -> Self {
+        Self {
+            open_time,
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: Decimal::ZERO,
+            quote_volume: Decimal::ZERO,
+            trades: 0,
+            taker_buy_volume: Decimal::ZERO,
+            taker_buy_quote_volume: Decimal::ZERO,
+        }
+    }
+
+    fn add(&mut self, price: Decimal, quantity: Decimal, buyer_is_maker: bool) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume += quantity;
+        self.quote_volume += price * quantity;
+        self.trades += 1;
+        if !buyer_is_maker {
+            self.taker_buy_volume += quantity;
+            self.taker_buy_quote_volume += price * quantity;
+        }
+    }
+
+    /// `[open_time, open, high, low, close, volume, close_time, quote_volume,
+    /// trades, taker_buy_base, taker_buy_quote, ignore]`, prices as strings
+    fn into_kline(self, interval_ms: i64) -> serde_json::Value {
+        let decimal = |value: Decimal| value.normalize().to_string();
+        serde_json::json!([
+            self.open_time,
+            decimal(self.open),
+            decimal(self.high),
+            decimal(self.low),
+            decimal(self.close),
+            decimal(self.volume),
+            self.open_time + interval_ms - 1,
+            decimal(self.quote_volume),
+            self.trades,
+            decimal(self.taker_buy_volume),
+            decimal(self.taker_buy_quote_volume),
+            "0"
+        ])
+    }
+}
+
+/// Aggregate `trades` into `interval` candles, oldest first
+///
+/// Candles open on multiples of the interval since the Unix epoch, like
+/// Binance's. The last candle is still forming if its interval hasn't ended.
+/// Trades with an unparseable or non-positive price, or a negative quantity,
+/// are skipped; no trades yields no candles.
+///
+/// # Errors
+/// `CalculationFailed` for an interval not in [`CANDLE_INTERVALS`]
+pub fn aggregate_klines(
+    trades: &[AggTrade],
+    interval: &str,
+) -> Result<KlineData, AnalyticsToolError> {
+    let interval_ms = interval_ms(interval).ok_or_else(|| {
+        AnalyticsToolError::CalculationFailed(format!("Unsupported candle interval '{}'", interval))
+    })?;
+
+    let mut parsed: Vec<(i64, Decimal, Decimal, bool)> = trades
+        .iter()
+        .filter_map(|trade| {
+            let price = Decimal::from_str(&trade.price).ok()?;
+            let quantity = Decimal::from_str(&trade.quantity).ok()?;
+            (price > Decimal::ZERO && quantity >= Decimal::ZERO).then_some((
+                trade.timestamp,
+                price,
+                quantity,
+                trade.buyer_is_maker,
+            ))
+        })
+        .collect();
+    // Stable sort keeps storage order for trades sharing a millisecond
+    parsed.sort_by_key(|(timestamp, ..)| *timestamp);
+
+    let mut candles: Vec<Candle> = Vec::new();
+    for (timestamp, price, quantity, buyer_is_maker) in parsed {
+        let open_time = timestamp - timestamp.rem_euclid(interval_ms);
+        match candles.last_mut() {
+            Some(candle) if candle.open_time == open_time => {
+                candle.add(price, quantity, buyer_is_maker)
+            }
+            _ => {
+                let mut candle = Candle::new(open_time, price);
+                candle.add(price, quantity, buyer_is_maker);
+                candles.push(candle);
+            }
+        }
+    }
+
+    Ok(candles
+        .into_iter()
+        .map(|candle| candle.into_kline(interval_ms))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(timestamp: i64, price: &str, quantity: &str, buyer_is_maker: bool) -> AggTrade {
+        AggTrade {
+            price: price.to_string(),
+            quantity: quantity.to_string(),
+            timestamp,
+            trade_id: timestamp,
+            buyer_is_maker,
+        }
+    }
+
+    #[test]
+    fn test_trades_aggregate_into_one_minute_candles() {
+        let minute = 1_700_000_040_000; // 2023-11-14T22:14:00Z, a minute boundary
+        let trades = vec![
+            trade(minute + 5_000, "100.0", "1.0", false),
+            trade(minute + 20_000, "103.5", "0.5", true),
+            trade(minute + 40_000, "99.0", "2.0", false),
+            trade(minute + 59_999, "101.0", "1.5", true),
+            // Out of order, belongs to the next minute
+            trade(minute + 90_000, "102.0", "1.0", false),
+            trade(minute + 61_000, "101.5", "1.0", true),
+            trade(minute + 70_000, "bad", "1.0", false),
+        ];
+
+        let klines = aggregate_klines(&trades, "1m").unwrap();
+        assert_eq!(klines.len(), 2);
+
+        let first = &klines[0];
+        assert_eq!(first[0], minute);
+        assert_eq!(first[1], "100");
+        assert_eq!(first[2], "103.5");
+        assert_eq!(first[3], "99");
+        assert_eq!(first[4], "101");
+        assert_eq!(first[5], "5");
+        assert_eq!(first[6], minute + 59_999);
+        // 100 + 51.75 + 198 + 151.5
+        assert_eq!(first[7], "501.25");
+        assert_eq!(first[8], 4);
+        assert_eq!(first[9], "3");
+        assert_eq!(first[10], "298");
+
+        let second = &klines[1];
+        assert_eq!(second[0], minute + 60_000);
+        assert_eq!(second[1], "101.5");
+        assert_eq!(second[2], "102");
+        assert_eq!(second[3], "101.5");
+        assert_eq!(second[4], "102");
+        assert_eq!(second[8], 2);
+
+        // Consumable wherever Binance klines are
+        let change = crate::price_change::price_change_from_klines(
+            "BTCUSDT",
+            crate::price_change::PriceWindow::OneHour,
+            &klines,
+        )
+        .unwrap();
+        assert_eq!(change.open_price, 100.0);
+        assert_eq!(change.current_price, 102.0);
+        assert_eq!(change.high_price, 103.5);
+        assert_eq!(change.low_price, 99.0);
+    }
+
+    #[test]
+    fn test_empty_trades_and_unsupported_interval() {
+        assert!(aggregate_klines(&[], "5m").unwrap().is_empty());
+        assert!(matches!(
+            aggregate_klines(&[trade(0, "1", "1", false)], "1w"),
+            Err(AnalyticsToolError::CalculationFailed(_))
+        ));
+        assert_eq!(interval_ms("4h"), Some(14_400_000));
+    }
+}
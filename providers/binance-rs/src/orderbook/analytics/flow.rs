@@ -2,7 +2,9 @@
 //!
 //! Provides bid/ask pressure tracking over configurable time windows (10-300s).
 //! Analyzes buying vs selling pressure through order flow rates, cumulative delta,
-//! and categorical flow direction indicators.
+//! and categorical flow direction indicators, plus absorption events (levels
+//! taking heavy flow without the price moving). Order counts and volume are
+//! blended into a single composite flow score.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
@@ -16,15 +18,28 @@ use std::collections::HashMap;
 use std::str::FromStr;
 use uuid::Uuid;
 
+/// Weight of the order-count imbalance in [`calculate_flow_score`]
+pub const ORDER_FLOW_WEIGHT: f64 = 0.4;
+
+/// Weight of the volume imbalance in [`calculate_flow_score`]
+///
+/// Volume gets the larger share: a burst of small orders moves the order
+/// count without committing much size, which is the usual cause of order flow
+/// and cumulative delta disagreeing.
+pub const VOLUME_DELTA_WEIGHT: f64 = 0.6;
+
 /// Calculate order flow metrics over a time window
 ///
 /// # Arguments
 /// * `storage` - RocksDB storage instance with historical snapshots
 /// * `symbol` - Trading pair (e.g., "BTCUSDT")
 /// * `window_duration_secs` - Time window in seconds (10-300, from clarifications)
+/// * `delta_half_life_secs` - Exponential-decay half-life for the cumulative
+///   delta (None = every interval weighs the same)
 ///
 /// # Returns
-/// OrderFlowSnapshot with bid/ask flow rates, net flow, and flow direction
+/// OrderFlowSnapshot with bid/ask flow rates, net flow, flow direction and
+/// absorption events detected in the window
 ///
 /// # Errors
 /// * `insufficient_historical_data` - Need at least 2 snapshots for window
@@ -33,12 +48,17 @@ pub async fn calculate_order_flow(
     storage: &SnapshotStorage,
     symbol: &str,
     window_duration_secs: u32,
+    delta_half_life_secs: Option<f64>,
 ) -> Result<OrderFlowSnapshot> {
     // Validate window duration (from clarifications: min 10s, max 300s)
     anyhow::ensure!(
         (10..=300).contains(&window_duration_secs),
         "window_duration_secs must be between 10 and 300 seconds"
     );
+    anyhow::ensure!(
+        delta_half_life_secs.map_or(true, |half_life| half_life.is_finite() && half_life > 0.0),
+        "delta_half_life_secs must be a positive number of seconds"
+    );
 
     let end = Utc::now();
     let start = end - Duration::seconds(window_duration_secs as i64);
@@ -85,20 +105,34 @@ pub async fn calculate_order_flow(
     let flow_direction = determine_flow_direction(bid_flow_rate, ask_flow_rate);
 
     // Calculate cumulative delta (running buy - sell volume)
-    let cumulative_delta = calculate_cumulative_delta(&snapshots)?;
+    let (cumulative_delta, gross_volume_change) =
+        calculate_cumulative_delta(&snapshots, delta_half_life_secs)?;
 
     let net_flow = bid_flow_rate - ask_flow_rate;
+    let flow_score = calculate_flow_score(
+        bid_flow_rate,
+        ask_flow_rate,
+        cumulative_delta,
+        gross_volume_change,
+    );
+
+    let absorption_events = detect_absorption_events(&snapshots, symbol)?;
 
     Ok(OrderFlowSnapshot {
         symbol: symbol.to_string(),
         time_window_start: start,
+        time_window_start_ts: start.into(),
         time_window_end: end,
+        time_window_end_ts: end.into(),
         window_duration_secs,
         bid_flow_rate,
         ask_flow_rate,
         net_flow,
         flow_direction,
         cumulative_delta,
+        delta_half_life_secs,
+        flow_score,
+        absorption_events,
     })
 }
 
@@ -174,6 +208,19 @@ fn calculate_flow_rates(bid_count: u64, ask_count: u64, duration_secs: u32) -> (
     (bid_flow_rate, ask_flow_rate)
 }
 
+/// Bid/ask flow rates (orders/sec) over already-loaded snapshots
+///
+/// Same counting as [`calculate_order_flow`], for callers that slice stored
+/// snapshots themselves (e.g. per bucket).
+pub fn flow_rates_from_snapshots(
+    snapshots: &[OrderBookSnapshot],
+    duration_secs: u32,
+) -> (f64, f64) {
+    // Counting never fails; the Result is kept for the storage-backed caller
+    let (bid_count, ask_count) = aggregate_bid_ask_counts(snapshots).unwrap_or((0, 0));
+    calculate_flow_rates(bid_count, ask_count, duration_secs.max(1))
+}
+
 /// Determine categorical flow direction from bid/ask ratio
 ///
 /// Thresholds (from FR-003, types.rs):
@@ -192,10 +239,22 @@ fn determine_flow_direction(bid_flow_rate: f64, ask_flow_rate: f64) -> FlowDirec
 /// Sums the net volume difference across all snapshots in the window.
 /// Positive values indicate accumulation, negative indicate distribution.
 ///
+/// With `half_life_secs`, each interval is weighted by
+/// `0.5^(age / half_life)`, where `age` is how long before the last snapshot
+/// the interval ended, so recent flow dominates. Without it every interval
+/// weighs 1.
+///
 /// # Returns
-/// Cumulative delta in base asset units
-fn calculate_cumulative_delta(snapshots: &[OrderBookSnapshot]) -> Result<f64> {
+/// `(cumulative_delta, gross_volume_change)` in base asset units, where the
+/// gross change sums both sides' absolute volume changes (weighted the same
+/// way) and bounds the delta
+fn calculate_cumulative_delta(
+    snapshots: &[OrderBookSnapshot],
+    half_life_secs: Option<f64>,
+) -> Result<(f64, f64)> {
     let mut cumulative_delta = 0.0;
+    let mut gross_volume_change = 0.0;
+    let latest = snapshots.last().map_or(0, |snapshot| snapshot.timestamp);
 
     for window in snapshots.windows(2) {
         let prev = &window[0];
@@ -227,32 +286,84 @@ fn calculate_cumulative_delta(snapshots: &[OrderBookSnapshot]) -> Result<f64> {
             .sum();
         let ask_delta = ask_volume - prev_ask_volume;
 
-        // Accumulate net delta (buy - sell)
-        cumulative_delta += bid_delta.abs() - ask_delta.abs();
+        // Accumulate net delta (buy - sell), decayed by the interval's age
+        let weight = half_life_secs.map_or(1.0, |half_life| {
+            let age_secs = (latest - curr.timestamp).max(0) as f64;
+            0.5_f64.powf(age_secs / half_life)
+        });
+        cumulative_delta += weight * (bid_delta.abs() - ask_delta.abs());
+        gross_volume_change += weight * (bid_delta.abs() + ask_delta.abs());
     }
 
-    Ok(cumulative_delta)
+    Ok((cumulative_delta, gross_volume_change))
+}
+
+/// Composite flow score in [-100, 100] (positive = buying pressure)
+///
+/// Each component is normalized to [-1, 1] before weighting:
+/// - Orders: `(bid_rate - ask_rate) / (bid_rate + ask_rate)`, weight
+///   [`ORDER_FLOW_WEIGHT`]
+/// - Volume: `cumulative_delta / gross_volume_change`, weight
+///   [`VOLUME_DELTA_WEIGHT`]
+///
+/// A component without activity (zero denominator) contributes 0.
+pub fn calculate_flow_score(
+    bid_flow_rate: f64,
+    ask_flow_rate: f64,
+    cumulative_delta: f64,
+    gross_volume_change: f64,
+) -> f64 {
+    let ratio = |net: f64, total: f64| {
+        if total > 0.0 {
+            (net / total).clamp(-1.0, 1.0)
+        } else {
+            0.0
+        }
+    };
+    let order_imbalance = ratio(bid_flow_rate - ask_flow_rate, bid_flow_rate + ask_flow_rate);
+    let volume_imbalance = ratio(cumulative_delta, gross_volume_change);
+
+    100.0 * (ORDER_FLOW_WEIGHT * order_imbalance + VOLUME_DELTA_WEIGHT * volume_imbalance)
 }
 
-/// Detect absorption events where large hidden orders absorb market pressure
+/// Intervals in which a level must be hit before it counts as absorbing
+pub const ABSORPTION_MIN_HITS: usize = 3;
+
+/// A level is "hit" when it loses more than this share (20%) of its resting size
+/// between two snapshots while staying in the book
+pub const ABSORPTION_MIN_DEPLETION: Decimal = Decimal::from_parts(20, 0, 0, false, 2);
+
+/// Volume absorbed at the level must exceed this multiple of the median level
+/// size on its side of the book (relative, so it works for any symbol)
+pub const ABSORPTION_VOLUME_MULTIPLIER: Decimal = Decimal::from_parts(5, 0, 0, false, 0);
+
+/// Largest mid-price range (0.1% of the lowest mid) while the level absorbs;
+/// beyond that the flow moved price and was not absorbed
+pub const ABSORPTION_MAX_PRICE_MOVE: Decimal = Decimal::from_parts(1, 0, 0, false, 3);
+
+/// Levels hit more often than this are attributed to a market maker, otherwise a whale
+pub const ABSORPTION_MARKET_MAKER_HITS: usize = 5;
+
+/// Detect absorption events: price levels that take heavy flow while price barely moves
 ///
-/// Identifies whale/market maker activity by tracking price level refills:
-/// - Monitors consecutive refills at the same price (>5x median volume)
-/// - Classifies as accumulation (bid absorption) or distribution (ask absorption)
-/// - Detects iceberg orders and layered liquidity provision
+/// Identifies whale/market maker activity defending a price:
+/// - Bid levels absorbing sell flow are classified as accumulation
+/// - Ask levels absorbing buy flow are classified as distribution
+/// - Frequently refilled levels (iceberg-like) are attributed to market makers
 ///
 /// # Arguments
-/// * `snapshots` - OrderBook snapshots over analysis window
+/// * `snapshots` - OrderBook snapshots over analysis window, oldest first
 /// * `symbol` - Trading pair (e.g., "BTCUSDT")
 ///
 /// # Returns
-/// Vector of AbsorptionEvent with detection time, price, volume, and direction
+/// Absorption events, largest absorbed volume first
 ///
 /// # Algorithm
-/// 1. Track each price level across snapshots
-/// 2. Detect refills: volume increases after partial/full depletion (>20% reduction)
-/// 3. Calculate median volume per side
-/// 4. Flag absorption: >5x median volume with >3 consecutive refills
+/// 1. Track each price level across consecutive snapshots
+/// 2. Count a hit when the level loses >`ABSORPTION_MIN_DEPLETION` of its size but stays in the book
+/// 3. Flag absorption: at least `ABSORPTION_MIN_HITS` hits, absorbed volume above
+///    `ABSORPTION_VOLUME_MULTIPLIER` x median level size, and a mid price range
+///    within `ABSORPTION_MAX_PRICE_MOVE` between the first and last hit
 pub fn detect_absorption_events(
     snapshots: &[OrderBookSnapshot],
     symbol: &str,
@@ -261,142 +372,127 @@ pub fn detect_absorption_events(
         return Ok(Vec::new());
     }
 
-    let mut events = Vec::new();
-
-    // Track refill events per price level
-    let mut bid_refills: HashMap<String, Vec<(DateTime<Utc>, Decimal)>> = HashMap::new();
-    let mut ask_refills: HashMap<String, Vec<(DateTime<Utc>, Decimal)>> = HashMap::new();
+    let mut events =
+        detect_side_absorption(snapshots, symbol, |s| &s.bids, Direction::Accumulation);
+    events.extend(detect_side_absorption(
+        snapshots,
+        symbol,
+        |s| &s.asks,
+        Direction::Distribution,
+    ));
+    events.sort_by_key(|event| std::cmp::Reverse(event.absorbed_volume));
 
-    // Analyze consecutive snapshot pairs for refill detection
-    for window in snapshots.windows(2) {
-        let prev = &window[0];
-        let curr = &window[1];
+    Ok(events)
+}
 
-        // Detect bid refills
-        for (price_str, curr_qty_str) in &curr.bids {
-            let curr_qty = Decimal::from_str(curr_qty_str).unwrap_or(Decimal::ZERO);
-
-            if let Some((_, prev_qty_str)) = prev.bids.iter().find(|(p, _)| p == price_str) {
-                let prev_qty = Decimal::from_str(prev_qty_str).unwrap_or(Decimal::ZERO);
-
-                // Detect refill: volume decreased by >20% then increased
-                if prev_qty > Decimal::ZERO {
-                    let reduction_pct = (prev_qty - curr_qty) / prev_qty;
-                    if reduction_pct > Decimal::from_str("0.20").unwrap() {
-                        // Mark potential absorption zone
-                        let timestamp =
-                            DateTime::from_timestamp(curr.timestamp, 0).unwrap_or(Utc::now());
-                        bid_refills
-                            .entry(price_str.clone())
-                            .or_insert_with(Vec::new)
-                            .push((timestamp, curr_qty));
-                    }
-                }
-            }
-        }
+/// Absorption on one side of the book (see [`detect_absorption_events`])
+fn detect_side_absorption(
+    snapshots: &[OrderBookSnapshot],
+    symbol: &str,
+    side: fn(&OrderBookSnapshot) -> &Vec<(String, String)>,
+    direction: Direction,
+) -> Vec<AbsorptionEvent> {
+    // Price level → (snapshot index, volume taken) per hit
+    let mut hits: HashMap<&str, Vec<(usize, Decimal)>> = HashMap::new();
+
+    for (idx, window) in snapshots.windows(2).enumerate() {
+        let prev_levels: HashMap<&str, Decimal> = side(&window[0])
+            .iter()
+            .filter_map(|(p, q)| Decimal::from_str(q).ok().map(|qty| (p.as_str(), qty)))
+            .collect();
 
-        // Detect ask refills
-        for (price_str, curr_qty_str) in &curr.asks {
-            let curr_qty = Decimal::from_str(curr_qty_str).unwrap_or(Decimal::ZERO);
-
-            if let Some((_, prev_qty_str)) = prev.asks.iter().find(|(p, _)| p == price_str) {
-                let prev_qty = Decimal::from_str(prev_qty_str).unwrap_or(Decimal::ZERO);
-
-                if prev_qty > Decimal::ZERO {
-                    let reduction_pct = (prev_qty - curr_qty) / prev_qty;
-                    if reduction_pct > Decimal::from_str("0.20").unwrap() {
-                        let timestamp =
-                            DateTime::from_timestamp(curr.timestamp, 0).unwrap_or(Utc::now());
-                        ask_refills
-                            .entry(price_str.clone())
-                            .or_insert_with(Vec::new)
-                            .push((timestamp, curr_qty));
-                    }
-                }
+        for (price_str, curr_qty_str) in side(&window[1]) {
+            let Ok(curr_qty) = Decimal::from_str(curr_qty_str) else {
+                continue;
+            };
+            let Some(&prev_qty) = prev_levels.get(price_str.as_str()) else {
+                continue;
+            };
+            if prev_qty > Decimal::ZERO
+                && (prev_qty - curr_qty) / prev_qty > ABSORPTION_MIN_DEPLETION
+            {
+                hits.entry(price_str.as_str())
+                    .or_default()
+                    .push((idx + 1, prev_qty - curr_qty));
             }
         }
     }
 
-    // Calculate median volumes
-    let all_bid_volumes: Vec<Decimal> = snapshots
+    let level_sizes: Vec<Decimal> = snapshots
         .iter()
-        .flat_map(|s| &s.bids)
+        .flat_map(side)
         .filter_map(|(_, qty)| Decimal::from_str(qty).ok())
         .collect();
-    let median_bid_volume = calculate_median(&all_bid_volumes);
+    let volume_threshold = calculate_median(&level_sizes) * ABSORPTION_VOLUME_MULTIPLIER;
 
-    let all_ask_volumes: Vec<Decimal> = snapshots
-        .iter()
-        .flat_map(|s| &s.asks)
-        .filter_map(|(_, qty)| Decimal::from_str(qty).ok())
-        .collect();
-    let median_ask_volume = calculate_median(&all_ask_volumes);
-
-    // Identify absorption events: >3 refills AND >5x median volume
-    let threshold_refills = 3;
-    let volume_multiplier = Decimal::from(5);
-
-    // Process bid absorptions (accumulation)
-    for (price_str, refills) in bid_refills {
-        if refills.len() >= threshold_refills {
-            let total_absorbed: Decimal = refills.iter().map(|(_, vol)| *vol).sum();
-            let avg_absorbed = total_absorbed / Decimal::from(refills.len());
-
-            if avg_absorbed > median_bid_volume * volume_multiplier {
-                let first_time = refills.first().unwrap().0;
-                let last_time = refills.last().unwrap().0;
-                let entity_type = if refills.len() > 5 {
-                    EntityType::MarketMaker
-                } else {
-                    EntityType::Whale
-                };
-
-                events.push(AbsorptionEvent {
-                    event_id: Uuid::new_v4(),
-                    symbol: symbol.to_string(),
-                    first_detected: first_time,
-                    last_updated: last_time,
-                    price_level: Decimal::from_str(&price_str).unwrap_or(Decimal::ZERO),
-                    absorbed_volume: total_absorbed,
-                    refill_count: refills.len() as u32,
-                    direction: Direction::Accumulation,
-                    suspected_entity_type: entity_type,
-                });
-            }
+    let mut events = Vec::new();
+    for (price_str, level_hits) in hits {
+        if level_hits.len() < ABSORPTION_MIN_HITS {
+            continue;
         }
-    }
 
-    // Process ask absorptions (distribution)
-    for (price_str, refills) in ask_refills {
-        if refills.len() >= threshold_refills {
-            let total_absorbed: Decimal = refills.iter().map(|(_, vol)| *vol).sum();
-            let avg_absorbed = total_absorbed / Decimal::from(refills.len());
-
-            if avg_absorbed > median_ask_volume * volume_multiplier {
-                let first_time = refills.first().unwrap().0;
-                let last_time = refills.last().unwrap().0;
-                let entity_type = if refills.len() > 5 {
-                    EntityType::MarketMaker
-                } else {
-                    EntityType::Whale
-                };
-
-                events.push(AbsorptionEvent {
-                    event_id: Uuid::new_v4(),
-                    symbol: symbol.to_string(),
-                    first_detected: first_time,
-                    last_updated: last_time,
-                    price_level: Decimal::from_str(&price_str).unwrap_or(Decimal::ZERO),
-                    absorbed_volume: total_absorbed,
-                    refill_count: refills.len() as u32,
-                    direction: Direction::Distribution,
-                    suspected_entity_type: entity_type,
-                });
-            }
+        let absorbed_volume: Decimal = level_hits.iter().map(|(_, taken)| *taken).sum();
+        if absorbed_volume <= volume_threshold {
+            continue;
         }
+
+        // Price must hold from just before the first hit through the last one
+        let first_idx = level_hits[0].0;
+        let last_idx = level_hits[level_hits.len() - 1].0;
+        let mids: Vec<Decimal> = snapshots[first_idx - 1..=last_idx]
+            .iter()
+            .filter_map(mid_price)
+            .collect();
+        let (Some(low), Some(high)) = (mids.iter().min(), mids.iter().max()) else {
+            continue;
+        };
+        if *low <= Decimal::ZERO || (*high - *low) / *low > ABSORPTION_MAX_PRICE_MOVE {
+            continue;
+        }
+
+        let timestamp_of = |idx: usize| {
+            DateTime::from_timestamp(snapshots[idx].timestamp, 0).unwrap_or(Utc::now())
+        };
+        let first_detected = timestamp_of(first_idx);
+        let last_updated = timestamp_of(last_idx);
+        let suspected_entity_type = if level_hits.len() > ABSORPTION_MARKET_MAKER_HITS {
+            EntityType::MarketMaker
+        } else {
+            EntityType::Whale
+        };
+
+        events.push(AbsorptionEvent {
+            event_id: Uuid::new_v4(),
+            symbol: symbol.to_string(),
+            price_level: Decimal::from_str(price_str).unwrap_or(Decimal::ZERO),
+            absorbed_volume,
+            refill_count: level_hits.len() as u32,
+            first_detected,
+            first_detected_ts: first_detected.into(),
+            last_updated,
+            last_updated_ts: last_updated.into(),
+            suspected_entity_type,
+            direction,
+        });
     }
 
-    Ok(events)
+    events
+}
+
+/// Mid price of a snapshot from its highest bid and lowest ask, whatever the
+/// level order (None when a side is empty)
+pub(crate) fn mid_price(snapshot: &OrderBookSnapshot) -> Option<Decimal> {
+    let best_bid = snapshot
+        .bids
+        .iter()
+        .filter_map(|(p, _)| Decimal::from_str(p).ok())
+        .max()?;
+    let best_ask = snapshot
+        .asks
+        .iter()
+        .filter_map(|(p, _)| Decimal::from_str(p).ok())
+        .min()?;
+    Some((best_bid + best_ask) / Decimal::from(2))
 }
 
 /// Calculate median from a sorted vector of Decimals
@@ -419,6 +515,7 @@ fn calculate_median(values: &[Decimal]) -> Decimal {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::orderbook::analytics::test_support::snapshot;
 
     #[test]
     fn test_calculate_flow_rates() {
@@ -427,6 +524,23 @@ mod tests {
         assert!((ask_rate - 1.0).abs() < f64::EPSILON);
     }
 
+    #[test]
+    fn test_flow_score_sign_follows_pressure() {
+        // 3:1 bid orders, 90 of 100 units of volume change on the bid side
+        let strong_buy = calculate_flow_score(3.0, 1.0, 80.0, 100.0);
+        let strong_sell = calculate_flow_score(1.0, 3.0, -80.0, 100.0);
+        assert!(strong_buy > 50.0, "{}", strong_buy);
+        assert!(strong_sell < -50.0, "{}", strong_sell);
+        assert!((strong_buy + strong_sell).abs() < 1e-9);
+
+        let balanced = calculate_flow_score(2.0, 2.1, 1.0, 100.0);
+        assert!(balanced.abs() < 5.0, "{}", balanced);
+
+        // Saturates at the bounds; no activity scores zero
+        assert!((calculate_flow_score(5.0, 0.0, 10.0, 10.0) - 100.0).abs() < 1e-9);
+        assert_eq!(calculate_flow_score(0.0, 0.0, 0.0, 0.0), 0.0);
+    }
+
     #[test]
     fn test_determine_flow_direction() {
         assert_eq!(
@@ -440,6 +554,71 @@ mod tests {
         assert_eq!(determine_flow_direction(50.0, 50.0), FlowDirection::Neutral);
     }
 
+    #[test]
+    fn test_decayed_delta_favours_recent_flow() {
+        // 5.0 bought two minutes ago, 4.9 sold in the last second
+        let snapshots = vec![
+            snapshot(1_700_000_000, &[("100.0", "10")], &[("100.1", "10")]),
+            snapshot(1_700_000_001, &[("100.0", "15")], &[("100.1", "10")]),
+            snapshot(1_700_000_120, &[("100.0", "15")], &[("100.1", "10")]),
+            snapshot(1_700_000_121, &[("100.0", "15")], &[("100.1", "14.9")]),
+        ];
+
+        let (unweighted, _) = calculate_cumulative_delta(&snapshots, None).unwrap();
+        assert!((unweighted - 0.1).abs() < 1e-9, "{}", unweighted);
+
+        // The buy is 4 half-lives old and keeps 1/16 of its weight
+        let (weighted, gross) = calculate_cumulative_delta(&snapshots, Some(30.0)).unwrap();
+        assert!((weighted - (5.0 / 16.0 - 4.9)).abs() < 1e-9, "{}", weighted);
+        assert!((gross - (5.0 / 16.0 + 4.9)).abs() < 1e-9, "{}", gross);
+        assert!(calculate_flow_score(1.0, 1.0, weighted, gross) < -50.0);
+    }
+
+    /// Book whose 100.0 bid alternates 50 → 20 (30 taken) and back; other levels
+    /// hold 1.0. The best ask moves up by `ask_step` per snapshot.
+    fn absorbing_snapshots(ask_step: i64) -> Vec<OrderBookSnapshot> {
+        let level = |price: Decimal, qty: &str| (price.to_string(), qty.to_string());
+        (0..6)
+            .map(|i| {
+                let defended = if i % 2 == 0 { "50" } else { "20" };
+                let mut bids = vec![level(Decimal::new(1000, 1), defended)];
+                bids.extend((1..5).map(|n| level(Decimal::new(1000 - n, 1), "1")));
+                let best_ask = Decimal::new(1001 + ask_step * i, 1);
+                let asks = (0..5)
+                    .map(|n| level(best_ask + Decimal::new(n, 1), "1"))
+                    .collect();
+                OrderBookSnapshot {
+                    bids,
+                    asks,
+                    update_id: i as u64,
+                    timestamp: 1_700_000_000 + i,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_detect_absorption_at_defended_level() {
+        let events = detect_absorption_events(&absorbing_snapshots(0), "BTCUSDT").unwrap();
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.price_level, Decimal::new(1000, 1));
+        assert_eq!(event.direction, Direction::Accumulation);
+        assert_eq!(event.refill_count, 3);
+        assert_eq!(event.absorbed_volume, Decimal::from(90));
+        assert_eq!(event.suspected_entity_type, EntityType::Whale);
+        assert_eq!(event.first_detected.timestamp(), 1_700_000_001);
+        assert_eq!(event.last_updated.timestamp(), 1_700_000_005);
+    }
+
+    #[test]
+    fn test_no_absorption_when_price_moves() {
+        // Ask walks up 1.0 per snapshot: the mid moves far more than 0.1%
+        let events = detect_absorption_events(&absorbing_snapshots(10), "BTCUSDT").unwrap();
+        assert!(events.is_empty());
+    }
+
     #[test]
     fn test_aggregate_bid_ask_counts_empty() {
         let snapshots: Vec<OrderBookSnapshot> = vec![];
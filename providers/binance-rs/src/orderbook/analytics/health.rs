@@ -5,13 +5,32 @@
 //! - Liquidity depth (35% weight)
 //! - Flow balance (25% weight)
 //! - Update rate (15% weight)
+//!
+//! ## Liquidity depth with live metrics
+//!
+//! Without live input, the latest stored snapshot's depth is compared to the
+//! plain average over the window. When the live `OrderBookMetrics` are passed
+//! in, current depth is `bid_volume + ask_volume` from the live book and the
+//! baseline is a recency-weighted average of snapshot depths (weight halves
+//! every [`DEPTH_HALF_LIFE_SNAPSHOTS`] snapshots back), so the score tracks the
+//! book the report shows rather than a possibly lagging snapshot.
+//!
+//! Snapshot depth is summed over the same `depth_levels` the live volumes
+//! cover. Snapshots only keep [`SNAPSHOT_LEVELS`] per side, so live metrics
+//! over more levels than that fall back to the stored-snapshot score.
 
 use anyhow::Result;
 use chrono::Utc;
 
 use crate::orderbook::analytics::{
-    storage::snapshot::OrderBookSnapshot, types::MicrostructureHealth,
+    spread::spread_stats,
+    storage::snapshot::{OrderBookSnapshot, SNAPSHOT_LEVELS},
+    types::MicrostructureHealth,
 };
+use crate::orderbook::types::OrderBookMetrics;
+
+/// Snapshots after which a snapshot's weight in the live depth baseline halves
+pub const DEPTH_HALF_LIFE_SNAPSHOTS: f64 = 10.0;
 
 /// Calculate market microstructure health score
 ///
@@ -20,6 +39,8 @@ use crate::orderbook::analytics::{
 /// * `snapshots` - Recent orderbook snapshots (recommended: last 60 seconds)
 /// * `bid_flow_rate` - Bid orders per second
 /// * `ask_flow_rate` - Ask orders per second
+/// * `live_metrics` - Current book metrics; when present the depth score uses live
+///   depth against a recency-weighted baseline
 ///
 /// # Returns
 /// MicrostructureHealth with composite score and component breakdowns
@@ -28,6 +49,7 @@ pub fn calculate_microstructure_health(
     snapshots: &[OrderBookSnapshot],
     bid_flow_rate: f64,
     ask_flow_rate: f64,
+    live_metrics: Option<&OrderBookMetrics>,
 ) -> Result<MicrostructureHealth> {
     anyhow::ensure!(
         !snapshots.is_empty(),
@@ -36,7 +58,12 @@ pub fn calculate_microstructure_health(
 
     // Component scoring (weights from FR-012, T051)
     let spread_stability_score = calculate_spread_stability_score(snapshots);
-    let liquidity_depth_score = calculate_liquidity_depth_score(snapshots);
+    let liquidity_depth_score = match live_metrics {
+        Some(live) if live.depth_levels <= SNAPSHOT_LEVELS => {
+            calculate_live_liquidity_depth_score(snapshots, live)
+        }
+        _ => calculate_liquidity_depth_score(snapshots),
+    };
     let flow_balance_score = calculate_flow_balance_score(bid_flow_rate, ask_flow_rate);
     let update_rate_score = calculate_update_rate_score(snapshots);
 
@@ -66,9 +93,11 @@ pub fn calculate_microstructure_health(
         liquidity_depth_score,
     );
 
+    let timestamp = Utc::now();
     Ok(MicrostructureHealth {
         symbol: symbol.to_string(),
-        timestamp: Utc::now(),
+        timestamp,
+        timestamp_ts: timestamp.into(),
         overall_score,
         spread_stability_score,
         liquidity_depth_score,
@@ -81,40 +110,22 @@ pub fn calculate_microstructure_health(
 
 /// Calculate spread stability score (0-100)
 ///
-/// Measures coefficient of variation of spread over time window.
+/// Measures the coefficient of variation of the spread (bps) over the time
+/// window, from the same [`spread_stats`] that `get_spread_stats` reports.
 /// Lower CV = higher stability = higher score
 fn calculate_spread_stability_score(snapshots: &[OrderBookSnapshot]) -> f64 {
     if snapshots.len() < 2 {
         return 50.0; // Neutral score
     }
 
-    let spreads: Vec<f64> = snapshots
-        .iter()
-        .filter_map(|s| {
-            if s.bids.is_empty() || s.asks.is_empty() {
-                return None;
-            }
-            let best_bid: f64 = s.bids[0].0.parse().ok()?;
-            let best_ask: f64 = s.asks[0].0.parse().ok()?;
-            Some(best_ask - best_bid)
-        })
-        .collect();
-
-    if spreads.is_empty() {
+    let Some(stats) = spread_stats(snapshots) else {
         return 0.0;
-    }
-
-    let mean: f64 = spreads.iter().sum::<f64>() / spreads.len() as f64;
-    let variance: f64 =
-        spreads.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / spreads.len() as f64;
-    let std_dev = variance.sqrt();
-
-    let cv = if mean > 0.0 {
-        (std_dev / mean) * 100.0
-    } else {
-        100.0
     };
 
+    let cv = stats
+        .coefficient_of_variation()
+        .map_or(100.0, |cv| cv * 100.0);
+
     // Convert CV to score: lower CV = higher score
     // CV < 5% = 100, CV > 50% = 0
     (100.0 - cv.min(50.0) * 2.0).max(0.0)
@@ -155,9 +166,57 @@ fn calculate_liquidity_depth_score(snapshots: &[OrderBookSnapshot]) -> f64 {
         return 0.0;
     }
 
-    // Score based on ratio to average
-    // depth = avg → 50, depth = 2x avg → 100, depth = 0.5x avg → 0
-    let ratio = current_depth / avg_depth;
+    depth_ratio_score(current_depth, avg_depth)
+}
+
+/// Calculate liquidity depth score (0-100) from the live book
+///
+/// Live bid + ask volume relative to the recency-weighted snapshot depth over
+/// the same number of levels per side (`live.depth_levels`, at most
+/// [`SNAPSHOT_LEVELS`]).
+fn calculate_live_liquidity_depth_score(
+    snapshots: &[OrderBookSnapshot],
+    live: &OrderBookMetrics,
+) -> f64 {
+    let current_depth = live.bid_volume + live.ask_volume;
+    let levels = live.depth_levels.min(SNAPSHOT_LEVELS);
+
+    let newest = snapshots.len().saturating_sub(1);
+    let mut weighted_depth = 0.0;
+    let mut total_weight = 0.0;
+    for (i, snapshot) in snapshots.iter().enumerate() {
+        let weight = 0.5_f64.powf((newest - i) as f64 / DEPTH_HALF_LIFE_SNAPSHOTS);
+        weighted_depth += snapshot_depth(snapshot, levels) * weight;
+        total_weight += weight;
+    }
+
+    if total_weight == 0.0 {
+        return 0.0;
+    }
+
+    depth_ratio_score(current_depth, weighted_depth / total_weight)
+}
+
+/// Total bid + ask quantity over the top `levels` of each side of a snapshot
+fn snapshot_depth(snapshot: &OrderBookSnapshot, levels: usize) -> f64 {
+    snapshot
+        .bids
+        .iter()
+        .take(levels)
+        .chain(snapshot.asks.iter().take(levels))
+        .filter_map(|(_, qty)| qty.parse::<f64>().ok())
+        .sum()
+}
+
+/// Score based on ratio to average
+///
+/// depth = avg → 50, depth = 2x avg → 100, depth = 0.5x avg → 25, no depth → 0
+fn depth_ratio_score(current_depth: f64, baseline_depth: f64) -> f64 {
+    if baseline_depth == 0.0 {
+        return 0.0;
+    }
+
+    let ratio = current_depth / baseline_depth;
     (ratio * 50.0).min(100.0).max(0.0)
 }
 
@@ -256,6 +315,7 @@ fn generate_recommendation(score: f64, level: &str, liquidity_depth_score: f64)
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::orderbook::analytics::test_support::snapshot;
 
     #[test]
     fn test_classify_health_level() {
@@ -302,4 +362,103 @@ mod tests {
         assert!(calculate_update_rate_score(&snapshots_low) < 100.0);
         assert_eq!(calculate_update_rate_score(&snapshots_optimal), 100.0);
     }
+
+    fn live_metrics(bid_volume: f64, ask_volume: f64) -> OrderBookMetrics {
+        serde_json::from_value(serde_json::json!({
+            "symbol": "BTCUSDT",
+            "timestamp": 0,
+            "last_update_id": 0,
+            "spread_bps": 1.0,
+            "microprice": 100.005,
+            "mid_price": 100.005,
+            "bid_volume": bid_volume,
+            "ask_volume": ask_volume,
+            "imbalance_ratio": bid_volume / ask_volume,
+            "best_bid": "100.00",
+            "best_ask": "100.01",
+            "best_bid_size": bid_volume,
+            "best_ask_size": ask_volume,
+            "walls": {"bids": [], "asks": []},
+            "slippage_estimates": {}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_live_metrics_change_liquidity_depth_score() {
+        // Stored depth is flat at 10 per snapshot; the live book has tripled
+        let snapshots: Vec<OrderBookSnapshot> = (0..20)
+            .map(|t| snapshot(t, &[("100.00", "5")], &[("100.01", "5")]))
+            .collect();
+
+        let historical =
+            calculate_microstructure_health("BTCUSDT", &snapshots, 1.0, 1.0, None).unwrap();
+        let live = live_metrics(15.0, 15.0);
+        let with_live =
+            calculate_microstructure_health("BTCUSDT", &snapshots, 1.0, 1.0, Some(&live)).unwrap();
+
+        assert_eq!(historical.liquidity_depth_score, 50.0);
+        assert_eq!(with_live.liquidity_depth_score, 100.0);
+        assert!(with_live.overall_score > historical.overall_score);
+        // Other components do not depend on live metrics
+        assert_eq!(
+            with_live.spread_stability_score,
+            historical.spread_stability_score
+        );
+    }
+
+    #[test]
+    fn test_live_depth_baseline_weights_recent_snapshots() {
+        // Old snapshots were deep (100), the last ten are thin (10)
+        let snapshots: Vec<OrderBookSnapshot> = (0..30)
+            .map(|t| {
+                let qty = if t < 20 { "50" } else { "5" };
+                snapshot(t, &[("100.00", qty)], &[("100.01", qty)])
+            })
+            .collect();
+        let live = live_metrics(5.0, 5.0);
+
+        let plain_average = snapshots
+            .iter()
+            .map(|s| snapshot_depth(s, SNAPSHOT_LEVELS))
+            .sum::<f64>()
+            / 30.0;
+        let unweighted = depth_ratio_score(10.0, plain_average);
+        let weighted = calculate_live_liquidity_depth_score(&snapshots, &live);
+
+        // Matching the recent regime scores closer to neutral than a flat average would
+        assert!(weighted > unweighted);
+        assert!(weighted < 50.0);
+    }
+
+    #[test]
+    fn test_live_depth_compared_over_matching_levels() {
+        // Every snapshot holds 20 levels of 1.0 per side
+        let levels: Vec<(String, String)> = (0..20)
+            .map(|i| (format!("{}.00", 100 + i), "1".to_string()))
+            .collect();
+        let snapshots: Vec<OrderBookSnapshot> = (0..10)
+            .map(|t| OrderBookSnapshot {
+                bids: levels.clone(),
+                asks: levels.clone(),
+                update_id: t as u64,
+                timestamp: t,
+            })
+            .collect();
+
+        // 5 live levels of 1.0 per side match the top 5 stored levels
+        let mut shallow = live_metrics(5.0, 5.0);
+        shallow.depth_levels = 5;
+        let health =
+            calculate_microstructure_health("BTCUSDT", &snapshots, 1.0, 1.0, Some(&shallow))
+                .unwrap();
+        assert_eq!(health.liquidity_depth_score, 50.0);
+
+        // Deeper than the snapshots: scored from stored snapshots alone
+        let mut deep = live_metrics(100.0, 100.0);
+        deep.depth_levels = 100;
+        let health =
+            calculate_microstructure_health("BTCUSDT", &snapshots, 1.0, 1.0, Some(&deep)).unwrap();
+        assert_eq!(health.liquidity_depth_score, 50.0);
+    }
 }
@@ -0,0 +1,247 @@
+//! Microstructure health trend from stored snapshots
+//!
+//! Splits a time window into fixed-width buckets and scores each bucket with
+//! [`calculate_microstructure_health`] over the snapshots captured in it (flow
+//! rates are taken from the same snapshots). A least-squares line through the
+//! bucket scores gives the trend: the fitted change across the window decides
+//! whether health is improving, stable or degrading.
+
+use crate::orderbook::analytics::{
+    flow::flow_rates_from_snapshots, health::calculate_microstructure_health,
+    storage::snapshot::OrderBookSnapshot, tools::AnalyticsToolError,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Upper bound on buckets per request (one day at 1-minute resolution)
+pub const MAX_HEALTH_TREND_BUCKETS: i64 = 1440;
+
+/// Snapshots a bucket needs before it is scored
+pub const MIN_HEALTH_BUCKET_SNAPSHOTS: usize = 2;
+
+/// Scored buckets required before a trend is returned
+pub const MIN_SCORED_BUCKETS: usize = 2;
+
+/// Fitted score change (points over the window) below which the trend is stable
+pub const STABLE_SCORE_CHANGE: f64 = 5.0;
+
+/// Direction of the health score over the window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthTrendDirection {
+    Improving,
+    Stable,
+    Degrading,
+}
+
+/// Health over one bucket `[start_time, start_time + bucket_secs)`
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct HealthBucket {
+    /// Bucket start (Unix seconds)
+    pub start_time: i64,
+    /// Composite 0-100 score (None when the bucket had too few snapshots)
+    pub overall_score: Option<f64>,
+    /// Excellent, Good, Fair, Poor or Critical (None when unscored)
+    pub health_level: Option<String>,
+    /// Snapshots in this bucket
+    pub snapshot_count: usize,
+}
+
+/// Least-squares trend through the scored buckets
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, JsonSchema)]
+pub struct HealthTrendFit {
+    /// Score change per bucket
+    pub slope_per_bucket: f64,
+    /// Fitted score change from the first to the last scored bucket
+    pub score_change: f64,
+    pub direction: HealthTrendDirection,
+}
+
+/// Score snapshots in `[start_sec, end_sec]` per `bucket_secs`-wide bucket
+///
+/// The last bucket may be shorter than `bucket_secs`; snapshots stamped exactly
+/// at `end_sec` belong to it. Snapshots outside the window are ignored, and
+/// buckets with fewer than [`MIN_HEALTH_BUCKET_SNAPSHOTS`] are left unscored.
+///
+/// # Errors
+/// - CalculationFailed: `bucket_secs` is zero or the window is empty
+/// - InsufficientData: fewer than [`MIN_SCORED_BUCKETS`] buckets could be scored
+pub fn bucket_health(
+    symbol: &str,
+    snapshots: &[OrderBookSnapshot],
+    start_sec: i64,
+    end_sec: i64,
+    bucket_secs: i64,
+) -> Result<Vec<HealthBucket>, AnalyticsToolError> {
+    if bucket_secs <= 0 || end_sec <= start_sec {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "Invalid bucketing: window {}..{} with {}s buckets",
+            start_sec, end_sec, bucket_secs
+        )));
+    }
+
+    let bucket_count = (end_sec - start_sec + bucket_secs - 1) / bucket_secs;
+    let mut grouped: Vec<Vec<OrderBookSnapshot>> = vec![Vec::new(); bucket_count as usize];
+    for snapshot in snapshots {
+        if !(start_sec..=end_sec).contains(&snapshot.timestamp) {
+            continue;
+        }
+        let index = ((snapshot.timestamp - start_sec) / bucket_secs).min(bucket_count - 1);
+        grouped[index as usize].push(snapshot.clone());
+    }
+
+    let mut buckets = Vec::with_capacity(grouped.len());
+    for (i, mut bucket) in grouped.into_iter().enumerate() {
+        bucket.sort_by_key(|snapshot| snapshot.timestamp);
+        let health = if bucket.len() >= MIN_HEALTH_BUCKET_SNAPSHOTS {
+            let (bid_flow_rate, ask_flow_rate) =
+                flow_rates_from_snapshots(&bucket, bucket_secs as u32);
+            Some(
+                calculate_microstructure_health(
+                    symbol,
+                    &bucket,
+                    bid_flow_rate,
+                    ask_flow_rate,
+                    None,
+                )
+                .map_err(|e| AnalyticsToolError::CalculationFailed(e.to_string()))?,
+            )
+        } else {
+            None
+        };
+
+        buckets.push(HealthBucket {
+            start_time: start_sec + i as i64 * bucket_secs,
+            overall_score: health.as_ref().map(|h| h.overall_score),
+            health_level: health.map(|h| h.health_level),
+            snapshot_count: bucket.len(),
+        });
+    }
+
+    let scored = buckets.iter().filter(|b| b.overall_score.is_some()).count();
+    if scored < MIN_SCORED_BUCKETS {
+        return Err(AnalyticsToolError::InsufficientData(format!(
+            "Need at least {} buckets with {}+ snapshots between {} and {}, found {}",
+            MIN_SCORED_BUCKETS, MIN_HEALTH_BUCKET_SNAPSHOTS, start_sec, end_sec, scored
+        )));
+    }
+
+    Ok(buckets)
+}
+
+/// Fit a line through the scored buckets and classify its direction
+///
+/// Buckets are placed at their index, so gaps left by unscored buckets keep
+/// their width. Returns None with fewer than [`MIN_SCORED_BUCKETS`] scores.
+pub fn fit_health_trend(buckets: &[HealthBucket]) -> Option<HealthTrendFit> {
+    let points: Vec<(f64, f64)> = buckets
+        .iter()
+        .enumerate()
+        .filter_map(|(i, b)| b.overall_score.map(|score| (i as f64, score)))
+        .collect();
+    if points.len() < MIN_SCORED_BUCKETS {
+        return None;
+    }
+
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let covariance: f64 = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum();
+    let variance: f64 = points.iter().map(|(x, _)| (x - mean_x).powi(2)).sum();
+    let slope_per_bucket = covariance / variance;
+
+    let span = points[points.len() - 1].0 - points[0].0;
+    let score_change = slope_per_bucket * span;
+    let direction = if score_change >= STABLE_SCORE_CHANGE {
+        HealthTrendDirection::Improving
+    } else if score_change <= -STABLE_SCORE_CHANGE {
+        HealthTrendDirection::Degrading
+    } else {
+        HealthTrendDirection::Stable
+    };
+
+    Some(HealthTrendFit {
+        slope_per_bucket,
+        score_change,
+        direction,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::analytics::test_support::snapshot;
+
+    /// Every test book is the same single level per side
+    const BIDS: &[(&str, &str)] = &[("100.00", "5")];
+    const ASKS: &[(&str, &str)] = &[("100.10", "5")];
+
+    fn bucket(score: Option<f64>) -> HealthBucket {
+        HealthBucket {
+            start_time: 0,
+            overall_score: score,
+            health_level: None,
+            snapshot_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_improving_series() {
+        // Identical books, but each minute records more updates than the last
+        // (2, 4, 6, 8, 10 snapshots), lifting the update rate component
+        let snapshots: Vec<OrderBookSnapshot> = (0..5_i64)
+            .flat_map(|minute| {
+                (0..2 * (minute + 1)).map(move |i| snapshot(minute * 60 + i, BIDS, ASKS))
+            })
+            .collect();
+
+        let buckets = bucket_health("BTCUSDT", &snapshots, 0, 300, 60).unwrap();
+        assert_eq!(buckets.len(), 5);
+        let scores: Vec<f64> = buckets.iter().map(|b| b.overall_score.unwrap()).collect();
+        assert!(scores.windows(2).all(|pair| pair[1] > pair[0]));
+
+        let fit = fit_health_trend(&buckets).unwrap();
+        assert!(fit.slope_per_bucket > 0.0);
+        assert_eq!(fit.direction, HealthTrendDirection::Improving);
+    }
+
+    #[test]
+    fn test_direction_thresholds() {
+        let flat = [bucket(Some(70.0)), bucket(None), bucket(Some(72.0))];
+        assert_eq!(
+            fit_health_trend(&flat).unwrap().direction,
+            HealthTrendDirection::Stable
+        );
+
+        let falling = [bucket(Some(80.0)), bucket(Some(70.0)), bucket(Some(60.0))];
+        let fit = fit_health_trend(&falling).unwrap();
+        assert_eq!(fit.direction, HealthTrendDirection::Degrading);
+        assert!((fit.score_change + 20.0).abs() < 1e-9);
+
+        assert_eq!(fit_health_trend(&[bucket(Some(50.0)), bucket(None)]), None);
+    }
+
+    #[test]
+    fn test_sparse_buckets_are_unscored() {
+        let snapshots = vec![
+            snapshot(0, BIDS, ASKS),
+            snapshot(10, BIDS, ASKS),
+            snapshot(70, BIDS, ASKS),
+            snapshot(130, BIDS, ASKS),
+            snapshot(140, BIDS, ASKS),
+        ];
+
+        let buckets = bucket_health("BTCUSDT", &snapshots, 0, 180, 60).unwrap();
+        assert_eq!(buckets[1].overall_score, None);
+        assert_eq!(buckets[1].snapshot_count, 1);
+        assert!(buckets[0].overall_score.is_some());
+
+        assert!(matches!(
+            bucket_health("BTCUSDT", &snapshots[..3], 0, 180, 60),
+            Err(AnalyticsToolError::InsufficientData(_))
+        ));
+    }
+}
@@ -0,0 +1,257 @@
+//! Resting-liquidity depth heatmap from stored snapshots
+//!
+//! Unlike the volume profile (traded volume per price), this grids the order
+//! book itself: time buckets × price buckets, each cell holding the cumulative
+//! resting quantity out to that price. Price buckets are `price_bucket_bps`
+//! wide relative to the mid of the newest snapshot and aligned to multiples of
+//! that width, so a level always lands in the same bucket across time. Bids
+//! accumulate downward from the best bid (a bid bucket holds every bid at or
+//! above it) and asks upward from the best ask, as in a depth chart. Cells are
+//! averaged over the time bucket's snapshots, keeping them comparable between
+//! buckets with different snapshot counts.
+
+use crate::orderbook::analytics::{
+    flow::mid_price, storage::snapshot::OrderBookSnapshot, tools::AnalyticsToolError,
+};
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::str::FromStr;
+
+/// Upper bound on time buckets per request (one day at 1-minute resolution)
+pub const MAX_HEATMAP_TIME_BUCKETS: i64 = 1440;
+
+/// Upper bound on price buckets; raise `price_bucket_bps` for wider ranges
+pub const MAX_HEATMAP_PRICE_BUCKETS: usize = 500;
+
+/// Time × price grid of average cumulative resting quantity
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct DepthHeatmapGrid {
+    /// Mid price of the newest snapshot, the reference for the bucket width
+    #[schemars(with = "String")]
+    pub reference_price: Decimal,
+    /// Price bucket width in quote units (`reference_price * price_bucket_bps / 10000`)
+    #[schemars(with = "String")]
+    pub price_bucket_width: Decimal,
+    /// Lower bound of each price bucket, ascending (columns)
+    #[schemars(with = "Vec<String>")]
+    pub price_axis: Vec<Decimal>,
+    /// Start of each time bucket in Unix seconds, ascending (rows)
+    pub time_axis: Vec<i64>,
+    /// Snapshots averaged into each row (0 = no data, row is all zeros)
+    pub snapshot_counts: Vec<usize>,
+    /// `quantities[row][column]`: average cumulative base quantity resting
+    /// from the touch out to the price bucket (bids at or above it plus asks at
+    /// or below it) during the time bucket
+    pub quantities: Vec<Vec<f64>>,
+    /// Largest cell value, for scaling a color map
+    pub max_quantity: f64,
+}
+
+/// Grid snapshots in `[start_sec, end_sec]` into time × price buckets
+///
+/// The last time bucket may be shorter than `time_bucket_secs`; snapshots
+/// stamped exactly at `end_sec` belong to it. Snapshots outside the window and
+/// unparseable levels are ignored.
+///
+/// # Errors
+/// - CalculationFailed: `time_bucket_secs` or `price_bucket_bps` is not
+///   positive, the window is empty, or the price range needs more than
+///   [`MAX_HEATMAP_PRICE_BUCKETS`] buckets
+/// - InsufficientData: no snapshot in the window has both a bid and an ask
+pub fn build_depth_heatmap(
+    snapshots: &[OrderBookSnapshot],
+    start_sec: i64,
+    end_sec: i64,
+    time_bucket_secs: i64,
+    price_bucket_bps: u32,
+) -> Result<DepthHeatmapGrid, AnalyticsToolError> {
+    if time_bucket_secs <= 0 || price_bucket_bps == 0 || end_sec <= start_sec {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "Invalid bucketing: window {}..{} with {}s x {}bps buckets",
+            start_sec, end_sec, time_bucket_secs, price_bucket_bps
+        )));
+    }
+
+    let mut in_window: Vec<&OrderBookSnapshot> = snapshots
+        .iter()
+        .filter(|s| (start_sec..=end_sec).contains(&s.timestamp))
+        .collect();
+    in_window.sort_by_key(|s| s.timestamp);
+
+    let reference_price = in_window
+        .iter()
+        .copied()
+        .rev()
+        .find_map(mid_price)
+        .ok_or_else(|| {
+            AnalyticsToolError::InsufficientData(format!(
+                "Need a snapshot with both sides between {} and {}, found {} snapshots",
+                start_sec,
+                end_sec,
+                in_window.len()
+            ))
+        })?;
+    let price_bucket_width =
+        reference_price * Decimal::from(price_bucket_bps) / Decimal::from(10_000);
+    if price_bucket_width.is_zero() {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "Price bucket of {}bps at {} rounds to zero",
+            price_bucket_bps, reference_price
+        )));
+    }
+
+    // Parse every level once: (time bucket, price bucket index, quantity, is bid)
+    let time_bucket_count = (end_sec - start_sec + time_bucket_secs - 1) / time_bucket_secs;
+    let mut cells: Vec<(usize, i64, f64, bool)> = Vec::new();
+    let mut snapshot_counts = vec![0_usize; time_bucket_count as usize];
+    for snapshot in &in_window {
+        let row = ((snapshot.timestamp - start_sec) / time_bucket_secs).min(time_bucket_count - 1)
+            as usize;
+        snapshot_counts[row] += 1;
+        let bids = snapshot.bids.iter().map(|level| (level, true));
+        let asks = snapshot.asks.iter().map(|level| (level, false));
+        for ((price, qty), is_bid) in bids.chain(asks) {
+            let (Ok(price), Ok(qty)) = (Decimal::from_str(price), qty.parse::<f64>()) else {
+                continue;
+            };
+            let Some(index) = (price / price_bucket_width).floor().to_i64() else {
+                continue;
+            };
+            cells.push((row, index, qty, is_bid));
+        }
+    }
+
+    let (Some(low), Some(high)) = (
+        cells.iter().map(|(_, index, _, _)| *index).min(),
+        cells.iter().map(|(_, index, _, _)| *index).max(),
+    ) else {
+        return Err(AnalyticsToolError::InsufficientData(format!(
+            "No parseable levels between {} and {}",
+            start_sec, end_sec
+        )));
+    };
+    let price_bucket_count = (high - low + 1) as usize;
+    if price_bucket_count > MAX_HEATMAP_PRICE_BUCKETS {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "Price range needs {} buckets of {}bps (max {}); use a larger price_bucket_bps",
+            price_bucket_count, price_bucket_bps, MAX_HEATMAP_PRICE_BUCKETS
+        )));
+    }
+
+    let empty = vec![vec![0.0_f64; price_bucket_count]; time_bucket_count as usize];
+    let (mut bid_depth, mut ask_depth) = (empty.clone(), empty);
+    for (row, index, qty, is_bid) in cells {
+        let side = if is_bid {
+            &mut bid_depth
+        } else {
+            &mut ask_depth
+        };
+        side[row][(index - low) as usize] += qty;
+    }
+
+    // Accumulate bids downward and asks upward, then average per snapshot
+    let quantities: Vec<Vec<f64>> = bid_depth
+        .iter_mut()
+        .zip(ask_depth.iter_mut())
+        .zip(&snapshot_counts)
+        .map(|((bids, asks), count)| {
+            cumulate(bids.iter_mut().rev());
+            cumulate(asks.iter_mut());
+            let count = (*count).max(1) as f64;
+            bids.iter()
+                .zip(asks.iter())
+                .map(|(b, a)| (b + a) / count)
+                .collect()
+        })
+        .collect();
+    let max_quantity = quantities.iter().flatten().copied().fold(0.0, f64::max);
+
+    Ok(DepthHeatmapGrid {
+        reference_price,
+        price_bucket_width,
+        price_axis: (low..=high)
+            .map(|index| Decimal::from(index) * price_bucket_width)
+            .collect(),
+        time_axis: (0..time_bucket_count)
+            .map(|i| start_sec + i * time_bucket_secs)
+            .collect(),
+        snapshot_counts,
+        quantities,
+        max_quantity,
+    })
+}
+
+/// Replace each quantity with the running total up to it
+fn cumulate<'a>(quantities: impl Iterator<Item = &'a mut f64>) {
+    let mut total = 0.0;
+    for qty in quantities {
+        total += *qty;
+        *qty = total;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::analytics::test_support::snapshot;
+
+    #[test]
+    fn test_grid_dimensions_and_cell_values() {
+        let snapshots = vec![
+            // Row 0 [0, 60): two snapshots averaged
+            snapshot(0, &[("99.95", "2"), ("99.85", "1")], &[("100.05", "3")]),
+            snapshot(30, &[("99.95", "4")], &[("100.05", "1"), ("100.15", "6")]),
+            // Row 1 [60, 120): empty
+            // Row 2 [120, 180]: mid 100.00, so 10bps buckets are 0.1 wide
+            snapshot(180, &[("99.95", "5")], &[("100.05", "5")]),
+            // Outside the window
+            snapshot(181, &[("50.00", "100")], &[("150.00", "100")]),
+        ];
+
+        let grid = build_depth_heatmap(&snapshots, 0, 180, 60, 10).unwrap();
+
+        assert_eq!(grid.reference_price, Decimal::from_str("100.00").unwrap());
+        assert_eq!(grid.price_bucket_width, Decimal::from_str("0.1").unwrap());
+        // Buckets 99.8, 99.9, 100.0, 100.1
+        assert_eq!(grid.price_axis.len(), 4);
+        assert_eq!(grid.price_axis[0], Decimal::from_str("99.8").unwrap());
+        assert_eq!(grid.time_axis, vec![0, 60, 120]);
+        assert_eq!(grid.snapshot_counts, vec![2, 0, 1]);
+        assert_eq!(grid.quantities.len(), 3);
+        assert!(grid.quantities.iter().all(|row| row.len() == 4));
+
+        // 99.9 bucket in row 0: bids at or above it, (2 + 4) / 2 snapshots
+        assert_eq!(grid.quantities[0][1], 3.0);
+        // 99.8 bucket adds the 99.85 bid: (2 + 1 + 4) / 2
+        assert_eq!(grid.quantities[0][0], 3.5);
+        // 100.0 bucket: asks at or below it, (3 + 1) / 2
+        assert_eq!(grid.quantities[0][2], 2.0);
+        // 100.1 bucket adds the 100.15 ask: (3 + 1 + 6) / 2
+        assert_eq!(grid.quantities[0][3], 5.0);
+        assert_eq!(grid.quantities[1], vec![0.0; 4]);
+        assert_eq!(grid.quantities[2], vec![5.0; 4]);
+        assert_eq!(grid.max_quantity, 5.0);
+    }
+
+    #[test]
+    fn test_empty_window_is_insufficient_data() {
+        assert!(matches!(
+            build_depth_heatmap(&[], 0, 600, 60, 10),
+            Err(AnalyticsToolError::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn test_too_many_price_buckets_rejected() {
+        let snapshots = vec![snapshot(
+            0,
+            &[("100.00", "1"), ("1.00", "1")],
+            &[("100.02", "1")],
+        )];
+        assert!(matches!(
+            build_depth_heatmap(&snapshots, 0, 60, 60, 1),
+            Err(AnalyticsToolError::CalculationFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,176 @@
+//! Bid/ask imbalance history from stored snapshots
+//!
+//! Splits a time window into fixed-width buckets and computes one imbalance
+//! ratio per bucket from the snapshots captured in it. Bid and ask volume are
+//! summed over all levels of every snapshot in the bucket before dividing, so
+//! a bucket's ratio is the volume-weighted mean of its snapshots.
+
+use crate::orderbook::analytics::{
+    storage::snapshot::OrderBookSnapshot, tools::AnalyticsToolError,
+};
+use crate::orderbook::metrics::calculate_imbalance_ratio;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Upper bound on buckets per request (one day at 1-minute resolution)
+pub const MAX_IMBALANCE_BUCKETS: i64 = 1440;
+
+/// Snapshots required in the window before a series is returned
+pub const MIN_IMBALANCE_SNAPSHOTS: usize = 2;
+
+/// Imbalance over one bucket `[start_time, start_time + bucket_secs)`
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct ImbalanceBucket {
+    /// Bucket start (Unix seconds)
+    pub start_time: i64,
+    /// Bid volume / ask volume (None when no snapshot fell in the bucket)
+    pub imbalance_ratio: Option<f64>,
+    /// Snapshots aggregated into this bucket
+    pub snapshot_count: usize,
+}
+
+/// Bucket snapshots in `[start_sec, end_sec]` into `bucket_secs`-wide buckets
+///
+/// The last bucket may be shorter than `bucket_secs`; snapshots stamped exactly
+/// at `end_sec` belong to it. Snapshots outside the window are ignored.
+///
+/// # Errors
+/// - CalculationFailed: `bucket_secs` is zero or the window is empty
+/// - InsufficientData: fewer than [`MIN_IMBALANCE_SNAPSHOTS`] snapshots in the window
+pub fn bucket_imbalance(
+    snapshots: &[OrderBookSnapshot],
+    start_sec: i64,
+    end_sec: i64,
+    bucket_secs: i64,
+) -> Result<Vec<ImbalanceBucket>, AnalyticsToolError> {
+    if bucket_secs <= 0 || end_sec <= start_sec {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "Invalid bucketing: window {}..{} with {}s buckets",
+            start_sec, end_sec, bucket_secs
+        )));
+    }
+
+    let bucket_count = (end_sec - start_sec + bucket_secs - 1) / bucket_secs;
+    let mut volumes = vec![(0.0_f64, 0.0_f64, 0_usize); bucket_count as usize];
+    let mut in_window = 0;
+
+    for snapshot in snapshots {
+        if !(start_sec..=end_sec).contains(&snapshot.timestamp) {
+            continue;
+        }
+        let index = ((snapshot.timestamp - start_sec) / bucket_secs).min(bucket_count - 1);
+        let (bid_volume, ask_volume, count) = &mut volumes[index as usize];
+        *bid_volume += side_volume(&snapshot.bids);
+        *ask_volume += side_volume(&snapshot.asks);
+        *count += 1;
+        in_window += 1;
+    }
+
+    if in_window < MIN_IMBALANCE_SNAPSHOTS {
+        return Err(AnalyticsToolError::InsufficientData(format!(
+            "Need at least {} snapshots between {} and {}, found {}",
+            MIN_IMBALANCE_SNAPSHOTS, start_sec, end_sec, in_window
+        )));
+    }
+
+    Ok(volumes
+        .into_iter()
+        .enumerate()
+        .map(
+            |(i, (bid_volume, ask_volume, snapshot_count))| ImbalanceBucket {
+                start_time: start_sec + i as i64 * bucket_secs,
+                imbalance_ratio: (snapshot_count > 0)
+                    .then(|| calculate_imbalance_ratio(bid_volume, ask_volume)),
+                snapshot_count,
+            },
+        )
+        .collect())
+}
+
+/// Total quantity across the stored levels of one side (unparseable levels skipped)
+fn side_volume(levels: &[(String, String)]) -> f64 {
+    levels
+        .iter()
+        .filter_map(|(_, qty)| qty.parse::<f64>().ok())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::analytics::test_support::snapshot;
+
+    #[test]
+    fn test_bucketed_series_length_and_values() {
+        let snapshots = vec![
+            // Bucket 0 [1000, 1060): (2 + 4) / (1 + 2) = 2.0
+            snapshot(1_000, &[("100.00", "2")], &[("100.10", "1")]),
+            snapshot(1_030, &[("100.00", "4")], &[("100.10", "2")]),
+            // Bucket 1 [1060, 1120): empty
+            // Bucket 2 [1120, 1180): 1 / 4 = 0.25
+            snapshot(1_150, &[("100.00", "1")], &[("100.10", "4")]),
+            // Window end lands in the last bucket [1180, 1200]: 3 / 3 = 1.0
+            snapshot(1_200, &[("100.00", "3")], &[("100.10", "3")]),
+            // Outside the window
+            snapshot(1_201, &[("100.00", "100")], &[("100.10", "1")]),
+        ];
+
+        let series = bucket_imbalance(&snapshots, 1_000, 1_200, 60).unwrap();
+
+        assert_eq!(series.len(), 4);
+        let starts: Vec<i64> = series.iter().map(|b| b.start_time).collect();
+        assert_eq!(starts, vec![1_000, 1_060, 1_120, 1_180]);
+        let ratios: Vec<Option<f64>> = series.iter().map(|b| b.imbalance_ratio).collect();
+        assert_eq!(ratios, vec![Some(2.0), None, Some(0.25), Some(1.0)]);
+        let counts: Vec<usize> = series.iter().map(|b| b.snapshot_count).collect();
+        assert_eq!(counts, vec![2, 0, 1, 1]);
+    }
+
+    #[test]
+    fn test_sums_all_levels_per_side() {
+        let snapshots = vec![
+            snapshot(
+                10,
+                &[("100.00", "1"), ("99.90", "2")],
+                &[("100.10", "1"), ("100.20", "bad")],
+            ),
+            snapshot(20, &[("100.00", "0")], &[("100.10", "0")]),
+        ];
+
+        let series = bucket_imbalance(&snapshots, 0, 60, 60).unwrap();
+        assert_eq!(series.len(), 1);
+        assert_eq!(series[0].imbalance_ratio, Some(3.0));
+    }
+
+    #[test]
+    fn test_too_few_snapshots_is_insufficient_data() {
+        assert!(matches!(
+            bucket_imbalance(&[], 0, 600, 60),
+            Err(AnalyticsToolError::InsufficientData(_))
+        ));
+        assert!(matches!(
+            bucket_imbalance(
+                &[
+                    snapshot(30, &[("100.00", "1")], &[("100.10", "1")]),
+                    snapshot(900, &[("100.00", "1")], &[("100.10", "1")])
+                ],
+                0,
+                600,
+                60
+            ),
+            Err(AnalyticsToolError::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn test_invalid_bucketing_rejected() {
+        assert!(matches!(
+            bucket_imbalance(&[], 0, 600, 0),
+            Err(AnalyticsToolError::CalculationFailed(_))
+        ));
+        assert!(matches!(
+            bucket_imbalance(&[], 600, 600, 60),
+            Err(AnalyticsToolError::CalculationFailed(_))
+        ));
+    }
+}
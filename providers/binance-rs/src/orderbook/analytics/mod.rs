@@ -5,7 +5,12 @@
 //! - Volume profile generation (POC/VAH/VAL indicators)
 //! - Anomaly detection (quote stuffing, icebergs, flash crashes)
 //! - Liquidity vacuum mapping
-//! - Microstructure health scoring
+//! - Microstructure health scoring and its trend over time
+//! - VWAP/TWAP execution benchmarks
+//! - Bid/ask imbalance history
+//! - Spread statistics (mean, volatility, range)
+//! - Resting-liquidity depth heatmaps
+//! - OHLCV candles from stored trades (kline fallback)
 
 #[cfg(feature = "orderbook_analytics")]
 pub mod storage;
@@ -28,6 +33,9 @@ pub mod anomaly;
 #[cfg(feature = "orderbook_analytics")]
 pub mod health;
 
+#[cfg(feature = "orderbook_analytics")]
+pub mod health_trend;
+
 #[cfg(feature = "orderbook_analytics")]
 pub mod tools;
 
@@ -38,7 +46,25 @@ pub mod trade_storage;
 pub mod trade_websocket;
 
 #[cfg(feature = "orderbook_analytics")]
-pub use storage::SnapshotStorage;
+pub mod vwap;
+
+#[cfg(feature = "orderbook_analytics")]
+pub mod imbalance;
+
+#[cfg(feature = "orderbook_analytics")]
+pub mod spread;
+
+#[cfg(feature = "orderbook_analytics")]
+pub mod heatmap;
+
+#[cfg(feature = "orderbook_analytics")]
+pub mod candles;
+
+#[cfg(all(test, feature = "orderbook_analytics"))]
+pub(crate) mod test_support;
+
+#[cfg(feature = "orderbook_analytics")]
+pub use storage::{SnapshotStorage, SnapshotStore, StorageBackend, StorageConfig};
 
 #[cfg(feature = "orderbook_analytics")]
 pub use trade_storage::TradeStorage;
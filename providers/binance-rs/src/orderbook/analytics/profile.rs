@@ -2,11 +2,12 @@
 //!
 //! Generates volume distribution histograms across price levels using adaptive
 //! tick-based binning. Identifies Point of Control (POC), Value Area High/Low
-//! (VAH/VAL - 70% volume boundaries), and liquidity vacuum zones.
+//! (VAH/VAL - 70% volume boundaries by default), and liquidity vacuum zones.
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
-use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::{Decimal, RoundingStrategy};
 use std::collections::HashMap;
 use std::str::FromStr;
 use uuid::Uuid;
@@ -14,9 +15,45 @@ use uuid::Uuid;
 use crate::orderbook::analytics::{
     storage::snapshot::OrderBookSnapshot,
     trade_stream::AggTrade,
-    types::{ImpactLevel, LiquidityVacuum, VolumeBin, VolumeProfile},
+    types::{ImpactLevel, LiquidityVacuum, PocBasis, VolumeBin, VolumeProfile},
 };
 
+/// Default share of total volume inside the value area (70%)
+pub const DEFAULT_VALUE_AREA_PCT: f64 = 0.70;
+
+/// Trades needed for a full-confidence profile, and the default minimum
+pub const DEFAULT_MIN_TRADES: usize = 1000;
+
+/// Lowest accepted `min_trades`; profiles below [`DEFAULT_MIN_TRADES`] trades
+/// are flagged `low_confidence`
+pub const MIN_TRADES_FLOOR: usize = 50;
+
+/// Accepted value area percentages (50%-95%)
+pub const VALUE_AREA_PCT_RANGE: std::ops::RangeInclusive<f64> = 0.5..=0.95;
+
+/// Decimal places the value area share is rounded to before use (0.7000 = 70%)
+pub const VALUE_AREA_PCT_DECIMALS: u32 = 4;
+
+/// Decimal places of `LiquidityVacuum::volume_deficit_pct` (e.g. 66.67)
+pub const DEFICIT_PCT_DECIMALS: u32 = 2;
+
+/// Rounding used when analytics decimals are cut to a documented precision
+///
+/// Half away from zero, so a 12.345% deficit reads 12.35% instead of the
+/// banker's rounding of plain `Decimal::round_dp` (12.34%).
+pub const ANALYTICS_ROUNDING: RoundingStrategy = RoundingStrategy::MidpointAwayFromZero;
+
+/// Round `value` to `decimals` places with [`ANALYTICS_ROUNDING`] and convert to f64
+///
+/// Converts via `ToPrimitive` rather than a string round trip; values outside
+/// the f64 range (not reachable for percentages) become 0.0.
+pub fn round_to_f64(value: Decimal, decimals: u32) -> f64 {
+    value
+        .round_dp_with_strategy(decimals, ANALYTICS_ROUNDING)
+        .to_f64()
+        .unwrap_or(0.0)
+}
+
 /// Generate volume profile histogram from aggregated trade data
 ///
 /// # Arguments
@@ -24,43 +61,71 @@ use crate::orderbook::analytics::{
 /// * `trades` - Aggregated trade events from @aggTrade stream
 /// * `duration_hours` - Analysis time period (1-168 hours)
 /// * `custom_tick_size` - Optional custom bin size (if None, auto-calculated)
+/// * `value_area_pct` - Share of volume inside VAH/VAL (default: 0.70, range 0.5-0.95)
+/// * `max_bins` - Optional cap on returned bins; adjacent bins are merged to fit
+///   (see [`merge_bins`]), POC/VAH/VAL still come from the full histogram
+/// * `poc_basis` - Whether the POC is the highest-volume or most-traded bin
+/// * `min_trades` - Trades required to build a profile (default: 1000, at least 50)
 ///
 /// # Returns
 /// VolumeProfile with histogram, POC, VAH, VAL, and liquidity vacuums
 ///
 /// # Errors
-/// * `insufficient_historical_data` - Need at least 1000 trades for reliable profile
+/// * `insufficient_historical_data` - Fewer than `min_trades` trades
+/// * `min_trades` below 50
+/// * `value_area_pct` outside 0.5-0.95
+/// * `max_bins` of 0
 pub async fn generate_volume_profile(
     symbol: &str,
     trades: Vec<AggTrade>,
     duration_hours: u32,
     custom_tick_size: Option<Decimal>,
+    value_area_pct: Option<f64>,
+    max_bins: Option<usize>,
+    poc_basis: PocBasis,
+    min_trades: Option<usize>,
 ) -> Result<VolumeProfile> {
+    let min_trades = min_trades.unwrap_or(DEFAULT_MIN_TRADES);
     anyhow::ensure!(
-        trades.len() >= 1000,
-        "insufficient_historical_data: Minimum 1000 trades required for {}h profile, only {} trades available",
+        min_trades >= MIN_TRADES_FLOOR,
+        "min_trades must be at least {}, got {}",
+        MIN_TRADES_FLOOR,
+        min_trades
+    );
+    anyhow::ensure!(
+        trades.len() >= min_trades,
+        "insufficient_historical_data: Minimum {} trades required for {}h profile, only {} trades available",
+        min_trades,
         duration_hours,
         trades.len()
     );
+    let trade_count = trades.len();
 
     anyhow::ensure!(
         (1..=168).contains(&duration_hours),
         "duration_hours must be between 1 and 168"
     );
 
+    let value_area_pct = value_area_pct.unwrap_or(DEFAULT_VALUE_AREA_PCT);
+    anyhow::ensure!(
+        VALUE_AREA_PCT_RANGE.contains(&value_area_pct),
+        "value_area_pct must be between 0.5 and 0.95, got {}",
+        value_area_pct
+    );
+    anyhow::ensure!(max_bins != Some(0), "max_bins must be at least 1");
+
     // Find price range
     let (price_min, price_max) = find_price_range(&trades)?;
 
-    // Calculate adaptive bin size
-    let bin_size = custom_tick_size.unwrap_or_else(|| {
-        adaptive_bin_size(price_min, price_max, Decimal::from_str("0.01").unwrap())
-    });
+    // Calculate adaptive bin size (no exchange info here, so the tick is inferred)
+    let bin_size =
+        custom_tick_size.unwrap_or_else(|| adaptive_bin_size(price_min, price_max, None));
 
     // Bin trades by price level
     let bins = bin_trades_by_price(&trades, price_min, price_max, bin_size)?;
 
     // Find POC, VAH, VAL
-    let (poc, vah, val) = find_poc_vah_val(&bins)?;
+    let (poc, vah, val) = find_poc_vah_val(&bins, value_area_pct, poc_basis)?;
 
     // Note: Liquidity vacuums are calculated separately via get_liquidity_vacuums tool
 
@@ -70,6 +135,10 @@ pub async fn generate_volume_profile(
     // Build histogram sorted by price
     let mut histogram = bins.clone();
     histogram.sort_by(|a, b| a.price_level.cmp(&b.price_level));
+    let original_bin_count = histogram.len();
+    if let Some(max_bins) = max_bins {
+        histogram = merge_bins(&histogram, max_bins, &[poc, vah, val]);
+    }
 
     let now = Utc::now();
     let start = now - Duration::hours(duration_hours as i64);
@@ -77,36 +146,90 @@ pub async fn generate_volume_profile(
     Ok(VolumeProfile {
         symbol: symbol.to_string(),
         time_period_start: start,
+        time_period_start_ts: start.into(),
         time_period_end: now,
+        time_period_end_ts: now.into(),
         price_range_low: price_min,
         price_range_high: price_max,
         bin_size,
         bin_count: histogram.len(),
+        original_bin_count,
         histogram,
         total_volume,
         point_of_control: poc,
+        poc_basis,
         value_area_high: vah,
         value_area_low: val,
+        value_area_pct,
+        trade_count,
+        low_confidence: trade_count < DEFAULT_MIN_TRADES,
     })
 }
 
+/// Inferred tick sizes by price magnitude, highest threshold first
+///
+/// Mirrors typical Binance spot PRICE_FILTER ticks: 0.01 for BTC/ETH-sized
+/// prices down to 1e-8 for sub-cent tokens.
+const TICK_BY_MAGNITUDE: [(Decimal, Decimal); 3] = [
+    (
+        Decimal::from_parts(100, 0, 0, false, 0),
+        Decimal::from_parts(1, 0, 0, false, 2),
+    ),
+    (Decimal::ONE, Decimal::from_parts(1, 0, 0, false, 4)),
+    (
+        Decimal::from_parts(1, 0, 0, false, 2),
+        Decimal::from_parts(1, 0, 0, false, 6),
+    ),
+];
+
+/// Tick used for prices below every [`TICK_BY_MAGNITUDE`] threshold (1e-8)
+const MIN_INFERRED_TICK: Decimal = Decimal::from_parts(1, 0, 0, false, 8);
+
+/// Guess the exchange tick size from the magnitude of traded prices
+///
+/// Fallback for when exchange info can't be fetched (offline, region block).
+/// Uses `price_max` so a stray low print doesn't shrink the tick.
+///
+/// # Arguments
+/// * `price_min` - Lowest price in data
+/// * `price_max` - Highest price in data
+pub fn infer_tick_size(price_min: Decimal, price_max: Decimal) -> Decimal {
+    let reference = std::cmp::max(price_min, price_max);
+    TICK_BY_MAGNITUDE
+        .iter()
+        .find(|(threshold, _)| reference >= *threshold)
+        .map_or(MIN_INFERRED_TICK, |(_, tick)| *tick)
+}
+
 /// Calculate adaptive bin size using formula: max(tick_size × 10, price_range / 100)
 ///
 /// # Arguments
 /// * `price_min` - Lowest price in data
 /// * `price_max` - Highest price in data
-/// * `exchange_tick_size` - Exchange's minimum price increment
+/// * `exchange_tick_size` - Exchange's minimum price increment; inferred with
+///   [`infer_tick_size`] when None
 ///
 /// # Returns
 /// Adaptive bin size (Decimal)
 fn adaptive_bin_size(
     price_min: Decimal,
     price_max: Decimal,
-    exchange_tick_size: Decimal,
+    exchange_tick_size: Option<Decimal>,
 ) -> Decimal {
+    let tick_size = exchange_tick_size.unwrap_or_else(|| {
+        let inferred = infer_tick_size(price_min, price_max);
+        tracing::debug!(
+            %price_min,
+            %price_max,
+            tick_size = %inferred,
+            "Exchange tick size unavailable, using inferred tick"
+        );
+        inferred
+    });
+
     let price_range = price_max - price_min;
     let range_based = price_range / Decimal::from(100);
-    let tick_based = exchange_tick_size * Decimal::from(10);
+    let tick_based = tick_size * Decimal::from(10);
 
     std::cmp::max(range_based, tick_based)
 }
@@ -160,29 +283,39 @@ fn bin_trades_by_price(
 /// Find Point of Control (POC), Value Area High (VAH), and Value Area Low (VAL)
 ///
 /// # POC
-/// Price level with highest volume (max volume bin)
+/// Price level with highest volume (max volume bin), or with the most trades
+/// when `poc_basis` is [`PocBasis::TradeCount`]
 ///
 /// # VAH/VAL
-/// Upper and lower boundaries containing 70% of total volume, centered around POC
+/// Upper and lower boundaries containing `value_area_pct` of total volume
+/// (e.g. 0.70), expanded outward from POC whichever basis picked it
 ///
 /// # Returns
 /// (POC price, VAH price, VAL price)
-fn find_poc_vah_val(bins: &[VolumeBin]) -> Result<(Decimal, Decimal, Decimal)> {
+fn find_poc_vah_val(
+    bins: &[VolumeBin],
+    value_area_pct: f64,
+    poc_basis: PocBasis,
+) -> Result<(Decimal, Decimal, Decimal)> {
     anyhow::ensure!(
         !bins.is_empty(),
         "Cannot calculate POC/VAH/VAL from empty bins"
     );
 
-    // Find POC (max volume bin)
-    let poc_bin = bins
-        .iter()
-        .max_by_key(|b| b.volume)
-        .context("No bins available")?;
+    // Find POC (max volume or max trade count bin)
+    let poc_bin = match poc_basis {
+        PocBasis::Volume => bins.iter().max_by_key(|b| b.volume),
+        PocBasis::TradeCount => bins.iter().max_by_key(|b| b.trade_count),
+    }
+    .context("No bins available")?;
     let poc = poc_bin.price_level;
 
     // Calculate total volume
     let total_volume: Decimal = bins.iter().map(|b| b.volume).sum();
-    let target_volume = total_volume * Decimal::from_str("0.70").unwrap();
+    let value_area_share = Decimal::try_from(value_area_pct)
+        .context("Invalid value area percentage")?
+        .round_dp_with_strategy(VALUE_AREA_PCT_DECIMALS, ANALYTICS_ROUNDING);
+    let target_volume = total_volume * value_area_share;
 
     // Sort bins by price
     let mut sorted_bins = bins.to_vec();
@@ -194,7 +327,7 @@ fn find_poc_vah_val(bins: &[VolumeBin]) -> Result<(Decimal, Decimal, Decimal)> {
         .position(|b| b.price_level == poc)
         .unwrap_or(sorted_bins.len() / 2);
 
-    // Expand around POC until we reach 70% volume
+    // Expand around POC until the value area holds the target volume
     let mut accumulated_volume = poc_bin.volume;
     let mut low_idx = poc_idx;
     let mut high_idx = poc_idx;
@@ -229,7 +362,43 @@ fn find_poc_vah_val(bins: &[VolumeBin]) -> Result<(Decimal, Decimal, Decimal)> {
     Ok((poc, vah, val))
 }
 
+/// Merge adjacent bins of a price-sorted histogram down to `max_bins`
+///
+/// Splits the bins into `max_bins` runs of near-equal length, summing volume and
+/// trade counts per run. A merged bin is labelled with the first of `anchors`
+/// (POC, VAH, VAL in priority order) that falls in its run, so those levels
+/// still appear in the histogram; other runs take the midpoint of their first
+/// and last price level. Histograms already within `max_bins` are returned as is.
+pub fn merge_bins(histogram: &[VolumeBin], max_bins: usize, anchors: &[Decimal]) -> Vec<VolumeBin> {
+    let natural = histogram.len();
+    if max_bins == 0 || natural <= max_bins {
+        return histogram.to_vec();
+    }
+
+    (0..max_bins)
+        .map(|group| {
+            let run = &histogram[group * natural / max_bins..(group + 1) * natural / max_bins];
+            let first = run[0].price_level;
+            let last = run[run.len() - 1].price_level;
+            let price_level = anchors
+                .iter()
+                .find(|anchor| (first..=last).contains(anchor))
+                .copied()
+                .unwrap_or((first + last) / Decimal::from(2));
+
+            VolumeBin {
+                price_level,
+                volume: run.iter().map(|b| b.volume).sum(),
+                trade_count: run.iter().map(|b| b.trade_count).sum(),
+            }
+        })
+        .collect()
+}
+
 /// Create liquidity vacuum with severity classification
+///
+/// The deficit is rounded to [`DEFICIT_PCT_DECIMALS`] before the impact level
+/// is classified, so the reported percentage and the level always agree.
 fn create_vacuum(
     symbol: &str,
     low_bin: &VolumeBin,
@@ -238,9 +407,10 @@ fn create_vacuum(
 ) -> LiquidityVacuum {
     let actual_volume = (low_bin.volume + high_bin.volume) / Decimal::from(2);
     let deficit = (median_volume - actual_volume) / median_volume * Decimal::from(100);
-    let volume_deficit_pct = deficit.to_string().parse::<f64>().unwrap_or(0.0);
+    let volume_deficit_pct = round_to_f64(deficit, DEFICIT_PCT_DECIMALS);
 
     let expected_impact = ImpactLevel::from_deficit_pct(volume_deficit_pct);
+    let detection_timestamp = Utc::now();
 
     LiquidityVacuum {
         vacuum_id: Uuid::new_v4(),
@@ -251,7 +421,8 @@ fn create_vacuum(
         median_volume,
         actual_volume,
         expected_impact,
-        detection_timestamp: Utc::now(),
+        detection_timestamp,
+        detection_timestamp_ts: detection_timestamp.into(),
     }
 }
 
@@ -603,7 +774,7 @@ mod tests {
         let price_max = Decimal::from(200);
         let tick_size = Decimal::from_str("0.01").unwrap();
 
-        let bin_size = adaptive_bin_size(price_min, price_max, tick_size);
+        let bin_size = adaptive_bin_size(price_min, price_max, Some(tick_size));
 
         // price_range / 100 = 100 / 100 = 1.0
         // tick_size × 10 = 0.01 × 10 = 0.1
@@ -611,6 +782,39 @@ mod tests {
         assert_eq!(bin_size, Decimal::from(1));
     }
 
+    #[test]
+    fn test_infer_tick_size_by_magnitude() {
+        let cases = [
+            ("95000", "105000", "0.01"),          // BTC
+            ("3500", "3600", "0.01"),             // ETH
+            ("100", "140", "0.01"),               // SOL
+            ("0.5", "1.2", "0.0001"),             // XRP
+            ("0.08", "0.09", "0.000001"),         // DOGE
+            ("0.00001", "0.00002", "0.00000001"), // SHIB
+        ];
+
+        for (min, max, expected) in cases {
+            let tick = infer_tick_size(
+                Decimal::from_str(min).unwrap(),
+                Decimal::from_str(max).unwrap(),
+            );
+            assert_eq!(
+                tick,
+                Decimal::from_str(expected).unwrap(),
+                "{}-{}",
+                min,
+                max
+            );
+        }
+
+        // Flat sub-cent range: bin falls back to 10 inferred ticks
+        let price = Decimal::from_str("0.00001").unwrap();
+        assert_eq!(
+            adaptive_bin_size(price, price, None),
+            Decimal::from_str("0.0000001").unwrap()
+        );
+    }
+
     #[test]
     fn test_find_price_range() {
         let trades = vec![
@@ -646,4 +850,179 @@ mod tests {
         assert_eq!(min, Decimal::from(50000));
         assert_eq!(max, Decimal::from(51000));
     }
+
+    #[test]
+    fn test_vacuum_deficit_pct_rounds_half_away_from_zero() {
+        let bin = |price: i64, volume: &str| VolumeBin {
+            price_level: Decimal::from(price),
+            volume: Decimal::from_str(volume).unwrap(),
+            trade_count: 1,
+        };
+
+        // (8 - 7.0124) / 8 = 12.345% exactly: the midpoint rounds up
+        let vacuum = create_vacuum(
+            "BTCUSDT",
+            &bin(100, "7.0124"),
+            &bin(101, "7.0124"),
+            Decimal::from(8),
+        );
+        assert_eq!(vacuum.volume_deficit_pct, 12.35);
+
+        // (3 - 1) / 3 = 66.666...% is cut to two places
+        let vacuum = create_vacuum(
+            "BTCUSDT",
+            &bin(100, "0.5"),
+            &bin(101, "1.5"),
+            Decimal::from(3),
+        );
+        assert_eq!(vacuum.volume_deficit_pct, 66.67);
+        assert_eq!(vacuum.expected_impact, ImpactLevel::ModerateMovement);
+
+        // (10 - 1.99) / 10 = 80.1%, kept as is and classified from the rounded value
+        let vacuum = create_vacuum(
+            "BTCUSDT",
+            &bin(100, "1.99"),
+            &bin(101, "1.99"),
+            Decimal::from(10),
+        );
+        assert_eq!(vacuum.volume_deficit_pct, 80.1);
+        assert_eq!(vacuum.expected_impact, ImpactLevel::FastMovement);
+    }
+
+    #[test]
+    fn test_value_area_widens_with_percentage() {
+        // Bell-shaped histogram centered on 105
+        let bins: Vec<VolumeBin> = [1, 2, 4, 8, 12, 20, 12, 8, 4, 2, 1]
+            .iter()
+            .enumerate()
+            .map(|(i, volume)| VolumeBin {
+                price_level: Decimal::from(100 + i as i64),
+                volume: Decimal::from(*volume),
+                trade_count: 1,
+            })
+            .collect();
+
+        let (poc_70, vah_70, val_70) = find_poc_vah_val(&bins, 0.70, PocBasis::Volume).unwrap();
+        let (poc_90, vah_90, val_90) = find_poc_vah_val(&bins, 0.90, PocBasis::Volume).unwrap();
+
+        assert_eq!(poc_70, Decimal::from(105));
+        assert_eq!(poc_90, poc_70);
+        assert!(vah_90 >= vah_70);
+        assert!(val_90 <= val_70);
+        assert!(vah_90 - val_90 > vah_70 - val_70);
+    }
+
+    #[test]
+    fn test_poc_follows_basis() {
+        // Volume peaks at 103 (a few large prints), trade count at 101 (many small ones)
+        let bins: Vec<VolumeBin> = [(2, 5), (6, 40), (4, 10), (20, 3), (3, 6)]
+            .iter()
+            .enumerate()
+            .map(|(i, (volume, trade_count))| VolumeBin {
+                price_level: Decimal::from(100 + i as i64),
+                volume: Decimal::from(*volume),
+                trade_count: *trade_count,
+            })
+            .collect();
+
+        let (poc_volume, _, _) = find_poc_vah_val(&bins, 0.70, PocBasis::Volume).unwrap();
+        let (poc_count, vah_count, val_count) =
+            find_poc_vah_val(&bins, 0.70, PocBasis::TradeCount).unwrap();
+
+        assert_eq!(poc_volume, Decimal::from(103));
+        assert_eq!(poc_count, Decimal::from(101));
+        // The value area still expands by volume from the chosen POC
+        assert!(val_count <= poc_count && poc_count <= vah_count);
+    }
+
+    #[tokio::test]
+    async fn test_low_min_trades_profiles_thin_markets() {
+        let trades: Vec<AggTrade> = (0..100u64)
+            .map(|i| AggTrade {
+                event_type: "aggTrade".to_string(),
+                event_time: 0,
+                symbol: "THINUSDT".to_string(),
+                agg_trade_id: i,
+                price: format!("{}.00", 100 + i % 10),
+                quantity: "1.0".to_string(),
+                first_trade_id: i,
+                last_trade_id: i,
+                trade_time: 0,
+                is_buyer_maker: i % 2 == 0,
+                is_best_match: true,
+            })
+            .collect();
+
+        let profile = generate_volume_profile(
+            "THINUSDT",
+            trades.clone(),
+            24,
+            None,
+            None,
+            None,
+            PocBasis::Volume,
+            Some(50),
+        )
+        .await
+        .unwrap();
+        assert!(profile.low_confidence);
+        assert_eq!(profile.trade_count, 100);
+
+        // The default minimum still refuses, and the floor can't be undercut
+        let default_min = generate_volume_profile(
+            "THINUSDT",
+            trades.clone(),
+            24,
+            None,
+            None,
+            None,
+            PocBasis::Volume,
+            None,
+        )
+        .await;
+        assert!(default_min
+            .unwrap_err()
+            .to_string()
+            .contains("insufficient_historical_data"));
+        let below_floor = generate_volume_profile(
+            "THINUSDT",
+            trades,
+            24,
+            None,
+            None,
+            None,
+            PocBasis::Volume,
+            Some(10),
+        )
+        .await;
+        assert!(below_floor.is_err());
+    }
+
+    #[test]
+    fn test_merge_bins_fits_max_bins_and_keeps_volume() {
+        let bins: Vec<VolumeBin> = [1, 2, 4, 8, 12, 20, 12, 8, 4, 2, 1]
+            .iter()
+            .enumerate()
+            .map(|(i, volume)| VolumeBin {
+                price_level: Decimal::from(100 + i as i64),
+                volume: Decimal::from(*volume),
+                trade_count: 1,
+            })
+            .collect();
+        let (poc, vah, val) = find_poc_vah_val(&bins, 0.70, PocBasis::Volume).unwrap();
+
+        let merged = merge_bins(&bins, 4, &[poc, vah, val]);
+
+        assert_eq!(merged.len(), 4);
+        let total = |bins: &[VolumeBin]| bins.iter().map(|b| b.volume).sum::<Decimal>();
+        assert_eq!(total(&merged), total(&bins));
+        assert_eq!(merged.iter().map(|b| b.trade_count).sum::<u64>(), 11);
+        assert!(merged
+            .windows(2)
+            .all(|w| w[0].price_level < w[1].price_level));
+        assert!(merged.iter().any(|b| b.price_level == poc));
+
+        // Already within the cap: unchanged
+        assert_eq!(merge_bins(&bins, 20, &[poc]).len(), bins.len());
+    }
 }
@@ -0,0 +1,114 @@
+//! Spread statistics from stored snapshots
+//!
+//! Converts each snapshot's top of book into a spread in basis points, using the
+//! same formula as the live L1 metrics, and summarizes the series. Market makers
+//! care about how stable the spread is over a window, not only where it is now;
+//! the health score's spread stability component is derived from these stats.
+
+use crate::orderbook::analytics::storage::snapshot::OrderBookSnapshot;
+use crate::orderbook::metrics::calculate_spread_bps;
+use rust_decimal::Decimal;
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::str::FromStr;
+
+/// Snapshots with a usable top of book required by `get_spread_stats`
+pub const MIN_SPREAD_SNAPSHOTS: usize = 2;
+
+/// Spread summary (basis points) over a set of snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct SpreadStats {
+    /// Snapshots with both sides present
+    pub sample_count: usize,
+    /// Mean spread
+    pub mean_bps: f64,
+    /// Population standard deviation of the spread
+    pub stddev_bps: f64,
+    /// Narrowest spread (negative when the book was crossed)
+    pub min_bps: f64,
+    /// Widest spread
+    pub max_bps: f64,
+}
+
+impl SpreadStats {
+    /// Standard deviation relative to the mean (None unless the mean is positive)
+    pub fn coefficient_of_variation(&self) -> Option<f64> {
+        (self.mean_bps > 0.0).then(|| self.stddev_bps / self.mean_bps)
+    }
+}
+
+/// Spread of one snapshot in basis points of the best bid
+///
+/// None when a side is empty, a price does not parse, or the best bid is zero.
+pub fn snapshot_spread_bps(snapshot: &OrderBookSnapshot) -> Option<f64> {
+    let best_bid = Decimal::from_str(&snapshot.bids.first()?.0).ok()?;
+    let best_ask = Decimal::from_str(&snapshot.asks.first()?.0).ok()?;
+    calculate_spread_bps(best_bid, best_ask)
+}
+
+/// Mean, standard deviation and range of the spread across `snapshots`
+///
+/// Snapshots without a usable top of book are skipped; returns None when none
+/// is left.
+pub fn spread_stats(snapshots: &[OrderBookSnapshot]) -> Option<SpreadStats> {
+    let spreads: Vec<f64> = snapshots.iter().filter_map(snapshot_spread_bps).collect();
+    if spreads.is_empty() {
+        return None;
+    }
+
+    let count = spreads.len() as f64;
+    let mean_bps = spreads.iter().sum::<f64>() / count;
+    let variance = spreads.iter().map(|s| (s - mean_bps).powi(2)).sum::<f64>() / count;
+
+    Some(SpreadStats {
+        sample_count: spreads.len(),
+        mean_bps,
+        stddev_bps: variance.sqrt(),
+        min_bps: spreads.iter().copied().fold(f64::INFINITY, f64::min),
+        max_bps: spreads.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::analytics::test_support::snapshot;
+
+    #[test]
+    fn test_spread_stats_match_hand_computed_values() {
+        // Spreads of 1, 2, 3 and 6 bps on a 10000 bid
+        let snapshots = vec![
+            snapshot(1, &[("10000", "1.0")], &[("10001", "1.0")]),
+            snapshot(2, &[("10000", "1.0")], &[("10002", "1.0")]),
+            snapshot(3, &[("10000", "1.0")], &[("10003", "1.0")]),
+            snapshot(4, &[("10000", "1.0")], &[("10006", "1.0")]),
+        ];
+
+        let stats = spread_stats(&snapshots).unwrap();
+
+        // mean = 12 / 4 = 3; variance = (4 + 1 + 0 + 9) / 4 = 3.5
+        assert_eq!(stats.sample_count, 4);
+        assert!((stats.mean_bps - 3.0).abs() < 1e-9);
+        assert!((stats.stddev_bps - 3.5_f64.sqrt()).abs() < 1e-9);
+        assert!((stats.min_bps - 1.0).abs() < 1e-9);
+        assert!((stats.max_bps - 6.0).abs() < 1e-9);
+        let cv = stats.coefficient_of_variation().unwrap();
+        assert!((cv - 3.5_f64.sqrt() / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unusable_snapshots_skipped() {
+        let snapshots = vec![
+            snapshot(1, &[("100", "1.0")], &[]),
+            snapshot(2, &[("0", "1.0")], &[("1", "1.0")]),
+            snapshot(3, &[("100", "1.0")], &[("100.5", "1.0")]),
+        ];
+
+        let stats = spread_stats(&snapshots).unwrap();
+        assert_eq!(stats.sample_count, 1);
+        assert!((stats.mean_bps - 50.0).abs() < 1e-9);
+        assert_eq!(stats.stddev_bps, 0.0);
+
+        assert!(spread_stats(&snapshots[..2]).is_none());
+    }
+}
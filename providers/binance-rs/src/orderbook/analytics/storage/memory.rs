@@ -0,0 +1,197 @@
+//! In-memory snapshot store for deployments without RocksDB
+//!
+//! Selected with `ANALYTICS_STORAGE=memory`. Each symbol keeps a ring buffer of
+//! its newest snapshots ordered by timestamp; once a buffer holds
+//! `max_snapshots_per_symbol` entries, every write evicts the oldest one. Nothing
+//! survives a restart, and trade persistence (which needs RocksDB) is disabled.
+
+use super::SnapshotStore;
+use anyhow::Result;
+use std::collections::{HashMap, VecDeque};
+use std::sync::RwLock;
+
+/// Snapshots kept per symbol when `ANALYTICS_MEMORY_SNAPSHOTS` is unset
+/// (one hour at the default 1-second capture interval)
+pub const DEFAULT_MEMORY_SNAPSHOTS_PER_SYMBOL: usize = 3600;
+
+/// Bounded per-symbol ring buffers of serialized snapshots
+#[derive(Debug)]
+pub struct MemorySnapshotStore {
+    max_snapshots_per_symbol: usize,
+    /// `(timestamp_ms, value)` per symbol, oldest first
+    symbols: RwLock<HashMap<String, VecDeque<(i64, Vec<u8>)>>>,
+}
+
+impl MemorySnapshotStore {
+    /// Create an empty store keeping at most `max_snapshots_per_symbol` (min 1)
+    pub fn new(max_snapshots_per_symbol: usize) -> Self {
+        Self {
+            max_snapshots_per_symbol: max_snapshots_per_symbol.max(1),
+            symbols: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Retention cap per symbol
+    pub fn max_snapshots_per_symbol(&self) -> usize {
+        self.max_snapshots_per_symbol
+    }
+
+    /// Number of snapshots currently held for `symbol`
+    pub fn len(&self, symbol: &str) -> usize {
+        self.symbols
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(symbol)
+            .map_or(0, VecDeque::len)
+    }
+}
+
+impl SnapshotStore for MemorySnapshotStore {
+    fn put(&self, symbol: &str, timestamp_ms: i64, value: &[u8]) -> Result<()> {
+        let mut symbols = self.symbols.write().unwrap_or_else(|e| e.into_inner());
+        let buffer = symbols.entry(symbol.to_string()).or_default();
+
+        // Captures arrive in order, so this is almost always a push to the back
+        let index = buffer.partition_point(|(ts, _)| *ts < timestamp_ms);
+        match buffer.get_mut(index) {
+            Some((ts, existing)) if *ts == timestamp_ms => *existing = value.to_vec(),
+            _ => buffer.insert(index, (timestamp_ms, value.to_vec())),
+        }
+
+        while buffer.len() > self.max_snapshots_per_symbol {
+            buffer.pop_front();
+        }
+        Ok(())
+    }
+
+    fn get(&self, symbol: &str, timestamp_ms: i64) -> Result<Option<Vec<u8>>> {
+        let symbols = self.symbols.read().unwrap_or_else(|e| e.into_inner());
+        Ok(symbols.get(symbol).and_then(|buffer| {
+            let index = buffer.partition_point(|(ts, _)| *ts < timestamp_ms);
+            buffer
+                .get(index)
+                .filter(|(ts, _)| *ts == timestamp_ms)
+                .map(|(_, value)| value.clone())
+        }))
+    }
+
+    fn delete(&self, symbol: &str, timestamp_ms: i64) -> Result<()> {
+        let mut symbols = self.symbols.write().unwrap_or_else(|e| e.into_inner());
+        if let Some(buffer) = symbols.get_mut(symbol) {
+            buffer.retain(|(ts, _)| *ts != timestamp_ms);
+        }
+        Ok(())
+    }
+
+    fn query_range(&self, symbol: &str, start_ms: i64, end_ms: i64) -> Result<Vec<Vec<u8>>> {
+        let symbols = self.symbols.read().unwrap_or_else(|e| e.into_inner());
+        let Some(buffer) = symbols.get(symbol) else {
+            return Ok(Vec::new());
+        };
+
+        let start = buffer.partition_point(|(ts, _)| *ts < start_ms);
+        Ok(buffer
+            .range(start..)
+            .take_while(|(ts, _)| *ts <= end_ms)
+            .map(|(_, value)| value.clone())
+            .collect())
+    }
+
+    fn delete_before(&self, cutoff_ms: i64) -> Result<usize> {
+        let mut symbols = self.symbols.write().unwrap_or_else(|e| e.into_inner());
+        let mut deleted = 0;
+        for buffer in symbols.values_mut() {
+            let expired = buffer.partition_point(|(ts, _)| *ts < cutoff_ms);
+            buffer.drain(..expired);
+            deleted += expired;
+        }
+        Ok(deleted)
+    }
+
+    fn size_bytes(&self) -> Result<u64> {
+        let symbols = self.symbols.read().unwrap_or_else(|e| e.into_inner());
+        Ok(symbols
+            .iter()
+            .map(|(symbol, buffer)| {
+                buffer
+                    .iter()
+                    .map(|(_, value)| (symbol.len() + 8 + value.len()) as u64)
+                    .sum::<u64>()
+            })
+            .sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::analytics::storage::{
+        query::query_snapshots_in_window, snapshot::OrderBookSnapshot, SnapshotStorage,
+    };
+
+    fn snapshot(update_id: u64, timestamp: i64) -> OrderBookSnapshot {
+        OrderBookSnapshot {
+            bids: vec![("100.0".to_string(), "1.0".to_string())],
+            asks: vec![("101.0".to_string(), "1.0".to_string())],
+            update_id,
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn test_retention_cap_evicts_oldest() -> Result<()> {
+        let store = MemorySnapshotStore::new(3);
+
+        for ts in 1..=5 {
+            store.put("BTCUSDT", ts * 1000, &[ts as u8])?;
+        }
+        store.put("ETHUSDT", 1000, b"eth")?;
+
+        assert_eq!(store.len("BTCUSDT"), 3);
+        assert_eq!(store.len("ETHUSDT"), 1);
+        assert!(store.get("BTCUSDT", 2000)?.is_none());
+        assert_eq!(store.get("BTCUSDT", 3000)?, Some(vec![3]));
+
+        // A late write older than everything kept is evicted straight away
+        store.put("BTCUSDT", 500, b"late")?;
+        assert_eq!(store.len("BTCUSDT"), 3);
+        assert!(store.get("BTCUSDT", 500)?.is_none());
+
+        // Rewriting a timestamp replaces it rather than growing the buffer
+        store.put("BTCUSDT", 5000, b"again")?;
+        assert_eq!(store.get("BTCUSDT", 5000)?, Some(b"again".to_vec()));
+        assert_eq!(store.len("BTCUSDT"), 3);
+
+        assert_eq!(store.delete_before(4000)?, 2);
+        assert_eq!(store.len("BTCUSDT"), 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_memory_backend_serves_window_queries() -> Result<()> {
+        let storage = SnapshotStorage::in_memory(100);
+
+        // Inserted out of order, two snapshots within the same second
+        for (update_id, key_ms) in [
+            (3, 1737158402000),
+            (1, 1737158400100),
+            (2, 1737158400600),
+            (4, 1737158403000),
+        ] {
+            let bytes = snapshot(update_id, key_ms / 1000).to_bytes()?;
+            storage.put("BTCUSDT", key_ms, &bytes).await?;
+        }
+
+        let results =
+            query_snapshots_in_window(&storage, "BTCUSDT", 1737158400, 1737158402).await?;
+        let update_ids: Vec<u64> = results.iter().map(|s| s.update_id).collect();
+        assert_eq!(update_ids, vec![1, 2, 3]);
+
+        let results =
+            query_snapshots_in_window(&storage, "ETHUSDT", 1737158400, 1737158402).await?;
+        assert!(results.is_empty());
+
+        assert!(storage.db().is_none());
+        Ok(())
+    }
+}
@@ -1,78 +1,480 @@
 //! Time-series storage for orderbook snapshots using RocksDB
 //!
 //! Storage design:
-//! - **Key format**: `{symbol}:{unix_timestamp_sec}` (e.g., "BTCUSDT:1737158400")
+//! - **Key format**: `{symbol}:{unix_timestamp_ms}` (e.g., "BTCUSDT:1737158400123")
 //! - **Value format**: MessagePack-serialized OrderBookSnapshot
-//! - **Retention**: 7 days (background cleanup task deletes keys older than 7 days)
-//! - **Compression**: Zstd for ~500MB-1GB storage (12M snapshots for 20 pairs)
-//! - **Query pattern**: Prefix scan for time-range queries (<200ms target)
+//! - **Retention**: 7 days by default (`ANALYTICS_RETENTION_DAYS`), enforced hourly
+//! - **Size cap**: 1GB; oldest keys are purged first when exceeded, regardless of age
+//! - **Compression**: Zstd by default for ~500MB-1GB storage (12M snapshots for 20 pairs)
+//! - **Query pattern**: Prefix scan for time-range queries (<200ms target); the
+//!   prefix is the key up to its last `:` (`BTCUSDT:`, `trades:BTCUSDT:`)
+//! - **Tuning**: write buffer, block cache and compression via [`StorageConfig`]
+//! - **Backends**: RocksDB by default; `ANALYTICS_STORAGE=memory` keeps a bounded
+//!   ring buffer per symbol instead (see [`memory`]). Both implement [`SnapshotStore`]
+//!
+//! ## Migrating from second keys
+//!
+//! Databases written before millisecond keys hold `{symbol}:{unix_timestamp_sec}`
+//! keys, which collided when snapshots were captured more than once a second.
+//! No migration step is needed: legacy keys (10 digits) still sort before and
+//! among the 13-digit millisecond keys of the same symbol, readers scale them to
+//! milliseconds (see [`snapshot_key_millis`]) and retention expires them.
 
+pub mod export;
+pub mod memory;
 pub mod query;
 pub mod snapshot;
 
 use anyhow::{Context, Result};
-use rocksdb::{Options, WriteBatch, DB};
+use rocksdb::{
+    BlockBasedOptions, Cache, DBCompressionType, Options, SliceTransform, WriteBatch, DB,
+};
+use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
+
+/// Hard cap on RocksDB size (SST files + memtables)
+pub const MAX_STORAGE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Interval between retention / size-cap enforcement runs
+pub const CLEANUP_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Default memtable size in MiB
+pub const DEFAULT_WRITE_BUFFER_MB: usize = 64;
+
+/// Default block cache size in MiB
+pub const DEFAULT_BLOCK_CACHE_MB: usize = 32;
+
+/// Name recorded for the prefix extractor (must stay stable across releases)
+const PREFIX_EXTRACTOR_NAME: &str = "symbol_prefix";
+
+/// Bloom filter bits per prefix
+const BLOOM_BITS_PER_KEY: f64 = 10.0;
+
+/// RocksDB tuning for analytics storage
+///
+/// ## Environment Variables
+///
+/// - `ANALYTICS_WRITE_BUFFER_MB`: Memtable size (default: 64)
+/// - `ANALYTICS_BLOCK_CACHE_MB`: LRU block cache size (default: 32)
+/// - `ANALYTICS_COMPRESSION`: `none`, `snappy`, `lz4`, `zstd` (default) or `zlib`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StorageConfig {
+    /// Memtable size in bytes; up to 3 memtables are kept
+    pub write_buffer_bytes: usize,
+
+    /// Block cache size in bytes, shared by reads of all symbols
+    pub block_cache_bytes: usize,
+
+    /// SST block compression
+    pub compression: DBCompressionType,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            write_buffer_bytes: DEFAULT_WRITE_BUFFER_MB * 1024 * 1024,
+            block_cache_bytes: DEFAULT_BLOCK_CACHE_MB * 1024 * 1024,
+            compression: DBCompressionType::Zstd,
+        }
+    }
+}
+
+impl StorageConfig {
+    /// Load tuning from `ANALYTICS_WRITE_BUFFER_MB`, `ANALYTICS_BLOCK_CACHE_MB`
+    /// and `ANALYTICS_COMPRESSION`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a size is not a positive integer or the compression is unknown
+    pub fn from_env() -> std::result::Result<Self, Box<dyn std::error::Error>> {
+        Self::from_values(
+            std::env::var("ANALYTICS_WRITE_BUFFER_MB").ok().as_deref(),
+            std::env::var("ANALYTICS_BLOCK_CACHE_MB").ok().as_deref(),
+            std::env::var("ANALYTICS_COMPRESSION").ok().as_deref(),
+        )
+    }
+
+    /// Build the config from raw variable values (`None` or blank = default)
+    pub fn from_values(
+        write_buffer_mb: Option<&str>,
+        block_cache_mb: Option<&str>,
+        compression: Option<&str>,
+    ) -> std::result::Result<Self, Box<dyn std::error::Error>> {
+        let defaults = Self::default();
+
+        let write_buffer_bytes = match write_buffer_mb.map(str::trim) {
+            None | Some("") => defaults.write_buffer_bytes,
+            Some(raw) => parse_megabytes("ANALYTICS_WRITE_BUFFER_MB", raw)?,
+        };
+        let block_cache_bytes = match block_cache_mb.map(str::trim) {
+            None | Some("") => defaults.block_cache_bytes,
+            Some(raw) => parse_megabytes("ANALYTICS_BLOCK_CACHE_MB", raw)?,
+        };
+        let compression = match compression.map(str::trim) {
+            None | Some("") => defaults.compression,
+            Some(raw) => parse_compression(raw)?,
+        };
+
+        Ok(Self {
+            write_buffer_bytes,
+            block_cache_bytes,
+            compression,
+        })
+    }
+}
+
+fn parse_megabytes(
+    name: &str,
+    raw: &str,
+) -> std::result::Result<usize, Box<dyn std::error::Error>> {
+    match raw.parse::<usize>() {
+        Ok(mb) if mb > 0 => Ok(mb * 1024 * 1024),
+        _ => Err(format!("{} must be a positive integer, got '{}'", name, raw).into()),
+    }
+}
+
+fn parse_compression(
+    raw: &str,
+) -> std::result::Result<DBCompressionType, Box<dyn std::error::Error>> {
+    match raw.to_ascii_lowercase().as_str() {
+        "none" => Ok(DBCompressionType::None),
+        "snappy" => Ok(DBCompressionType::Snappy),
+        "lz4" => Ok(DBCompressionType::Lz4),
+        "zstd" => Ok(DBCompressionType::Zstd),
+        "zlib" => Ok(DBCompressionType::Zlib),
+        _ => Err(format!(
+            "ANALYTICS_COMPRESSION must be none, snappy, lz4, zstd or zlib, got '{}'",
+            raw
+        )
+        .into()),
+    }
+}
+
+/// Where snapshots are kept
+///
+/// ## Environment Variables
+///
+/// - `ANALYTICS_STORAGE`: `rocksdb` (default) or `memory`
+/// - `ANALYTICS_MEMORY_SNAPSHOTS`: Snapshots kept per symbol by the memory
+///   backend (default: 3600)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum StorageBackend {
+    /// Persistent RocksDB database at `ANALYTICS_DATA_PATH`
+    #[default]
+    RocksDb,
+    /// Non-persistent ring buffer of the newest snapshots per symbol
+    Memory { max_snapshots_per_symbol: usize },
+}
+
+impl StorageBackend {
+    /// Load the backend from `ANALYTICS_STORAGE` and `ANALYTICS_MEMORY_SNAPSHOTS`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the backend is unknown or the capacity is not a positive integer
+    pub fn from_env() -> std::result::Result<Self, Box<dyn std::error::Error>> {
+        Self::from_values(
+            std::env::var("ANALYTICS_STORAGE").ok().as_deref(),
+            std::env::var("ANALYTICS_MEMORY_SNAPSHOTS").ok().as_deref(),
+        )
+    }
+
+    /// Build the backend from raw variable values (`None` or blank = default)
+    pub fn from_values(
+        backend: Option<&str>,
+        memory_snapshots: Option<&str>,
+    ) -> std::result::Result<Self, Box<dyn std::error::Error>> {
+        let backend = backend.map(str::trim).unwrap_or_default();
+        match backend.to_ascii_lowercase().as_str() {
+            "" | "rocksdb" => Ok(Self::RocksDb),
+            "memory" => {
+                let max_snapshots_per_symbol = match memory_snapshots.map(str::trim) {
+                    None | Some("") => memory::DEFAULT_MEMORY_SNAPSHOTS_PER_SYMBOL,
+                    Some(raw) => match raw.parse::<usize>() {
+                        Ok(count) if count > 0 => count,
+                        _ => {
+                            return Err(format!(
+                                "ANALYTICS_MEMORY_SNAPSHOTS must be a positive integer, got '{}'",
+                                raw
+                            )
+                            .into())
+                        }
+                    },
+                };
+                Ok(Self::Memory {
+                    max_snapshots_per_symbol,
+                })
+            }
+            _ => Err(format!(
+                "ANALYTICS_STORAGE must be rocksdb or memory, got '{}'",
+                backend
+            )
+            .into()),
+        }
+    }
+}
+
+/// Snapshot persistence operations shared by the RocksDB and in-memory backends
+///
+/// Keys are `(symbol, timestamp_ms)` and values are serialized snapshots. Calls
+/// are blocking; [`SnapshotStorage`] runs them on the blocking thread pool.
+pub trait SnapshotStore: Send + Sync {
+    /// Store `value`, replacing any snapshot with the same key
+    fn put(&self, symbol: &str, timestamp_ms: i64, value: &[u8]) -> Result<()>;
 
-/// RocksDB storage handle for orderbook snapshots
+    /// Snapshot stored under exactly this key
+    fn get(&self, symbol: &str, timestamp_ms: i64) -> Result<Option<Vec<u8>>>;
+
+    /// Remove the snapshot stored under this key, if any
+    fn delete(&self, symbol: &str, timestamp_ms: i64) -> Result<()>;
+
+    /// Snapshots of `symbol` keyed within `[start_ms, end_ms]`, oldest first
+    fn query_range(&self, symbol: &str, start_ms: i64, end_ms: i64) -> Result<Vec<Vec<u8>>>;
+
+    /// Delete every snapshot keyed before `cutoff_ms`
+    ///
+    /// # Returns
+    /// Number of snapshots deleted
+    fn delete_before(&self, cutoff_ms: i64) -> Result<usize>;
+
+    /// Approximate storage footprint in bytes
+    fn size_bytes(&self) -> Result<u64>;
+}
+
+/// Prefix of a key for bloom filters and prefix scans: everything up to and
+/// including the last `:`
+///
+/// Snapshot keys `BTCUSDT:1737158400` map to `BTCUSDT:` and trade keys
+/// `trades:BTCUSDT:1737158400123` to `trades:BTCUSDT:`, whatever the symbol length.
+fn symbol_prefix(key: &[u8]) -> &[u8] {
+    match key.iter().rposition(|b| *b == b':') {
+        Some(delimiter) => &key[..=delimiter],
+        None => key,
+    }
+}
+
+/// Keys without a `:` delimiter have no symbol prefix
+fn has_symbol_prefix(key: &[u8]) -> bool {
+    key.contains(&b':')
+}
+
+/// Options shared by read-write and read-only opens (the prefix extractor must match)
+fn base_options() -> Options {
+    let mut opts = Options::default();
+    opts.set_prefix_extractor(SliceTransform::create(
+        PREFIX_EXTRACTOR_NAME,
+        symbol_prefix,
+        Some(has_symbol_prefix),
+    ));
+    opts
+}
+
+/// [`SnapshotStore`] over the RocksDB database shared with trade storage
+struct RocksDbStore {
+    db: Arc<DB>,
+}
+
+impl SnapshotStore for RocksDbStore {
+    fn put(&self, symbol: &str, timestamp_ms: i64, value: &[u8]) -> Result<()> {
+        let key = format!("{}:{}", symbol, timestamp_ms);
+        self.db
+            .put(key.as_bytes(), value)
+            .context("Failed to write snapshot to RocksDB")
+    }
+
+    fn get(&self, symbol: &str, timestamp_ms: i64) -> Result<Option<Vec<u8>>> {
+        let key = format!("{}:{}", symbol, timestamp_ms);
+        self.db
+            .get(key.as_bytes())
+            .context("Failed to read snapshot from RocksDB")
+    }
+
+    fn delete(&self, symbol: &str, timestamp_ms: i64) -> Result<()> {
+        let key = format!("{}:{}", symbol, timestamp_ms);
+        self.db
+            .delete(key.as_bytes())
+            .context("Failed to delete snapshot from RocksDB")
+    }
+
+    fn query_range(&self, symbol: &str, start_ms: i64, end_ms: i64) -> Result<Vec<Vec<u8>>> {
+        query::scan_snapshot_range(&self.db, symbol, start_ms, end_ms)
+    }
+
+    fn delete_before(&self, cutoff_ms: i64) -> Result<usize> {
+        let mut batch = WriteBatch::default();
+        let mut deleted_count = 0;
+
+        // Iterate all keys (no prefix filter - global cleanup)
+        let iter = self.db.iterator(rocksdb::IteratorMode::Start);
+
+        for item in iter {
+            let (key, _) = item?;
+            let key_str = String::from_utf8_lossy(&key);
+
+            // Parse timestamp from key format "{symbol}:{timestamp}"
+            if let Some(timestamp_str) = key_str.split(':').nth(1) {
+                if let Some(timestamp_ms) = snapshot_key_millis(timestamp_str) {
+                    if timestamp_ms < cutoff_ms {
+                        batch.delete(&key);
+                        deleted_count += 1;
+                    }
+                }
+            }
+        }
+
+        if deleted_count > 0 {
+            self.db
+                .write(batch)
+                .context("Failed to delete old snapshots")?;
+        }
+
+        Ok(deleted_count)
+    }
+
+    fn size_bytes(&self) -> Result<u64> {
+        let sst = self
+            .db
+            .property_int_value("rocksdb.total-sst-files-size")
+            .context("Failed to read RocksDB SST size")?
+            .unwrap_or(0);
+        let memtables = self
+            .db
+            .property_int_value("rocksdb.cur-size-all-mem-tables")
+            .context("Failed to read RocksDB memtable size")?
+            .unwrap_or(0);
+        Ok(sst + memtables)
+    }
+}
+
+/// Storage handle for orderbook snapshots
+///
+/// Backed by RocksDB (see [`Self::with_config`]) or by in-memory ring buffers
+/// (see [`Self::in_memory`]); callers use the same async interface either way.
 #[derive(Clone)]
 pub struct SnapshotStorage {
-    db: Arc<DB>,
+    store: Arc<dyn SnapshotStore>,
+    /// RocksDB handle, `None` for the memory backend
+    db: Option<Arc<DB>>,
 }
 
 impl SnapshotStorage {
+    /// Initialize RocksDB with the default [`StorageConfig`]
+    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::with_config(path, &StorageConfig::default())
+    }
+
     /// Initialize RocksDB with optimized settings for time-series workload
     ///
     /// Configuration:
     /// - LSM-tree optimized for write-heavy workload (1 snapshot/sec × 20 pairs)
-    /// - Zstd compression for storage efficiency
-    /// - Prefix bloom filter for fast time-range scans
-    pub fn new<P: AsRef<Path>>(path: P) -> Result<Self> {
-        let mut opts = Options::default();
+    /// - Compression, write buffer and block cache from `config`
+    /// - Prefix bloom filter on the symbol prefix for fast time-range scans
+    pub fn with_config<P: AsRef<Path>>(path: P, config: &StorageConfig) -> Result<Self> {
+        let mut opts = base_options();
         opts.create_if_missing(true);
 
         // Write optimization (20 writes/sec is light, but prepare for bursts)
-        opts.set_write_buffer_size(64 * 1024 * 1024); // 64MB write buffer
+        opts.set_write_buffer_size(config.write_buffer_bytes);
         opts.set_max_write_buffer_number(3);
 
-        // Compression (Zstd for best ratio)
-        opts.set_compression_type(rocksdb::DBCompressionType::Zstd);
+        opts.set_compression_type(config.compression);
 
-        // Prefix bloom filter for symbol-based scans
-        opts.set_prefix_extractor(rocksdb::SliceTransform::create_fixed_prefix(10));
+        // Block cache and whole-key-prefix bloom filters for symbol-based scans
+        let mut table_opts = BlockBasedOptions::default();
+        table_opts.set_block_cache(&Cache::new_lru_cache(config.block_cache_bytes));
+        table_opts.set_bloom_filter(BLOOM_BITS_PER_KEY, false);
+        opts.set_block_based_table_factory(&table_opts);
 
         let db = DB::open(&opts, path).context("Failed to open RocksDB for snapshot storage")?;
 
-        Ok(Self { db: Arc::new(db) })
+        Ok(Self::from_db(Arc::new(db)))
     }
 
-    /// Store a snapshot with key format `{symbol}:{unix_timestamp_sec}`
-    pub async fn put(&self, symbol: &str, timestamp_sec: i64, value: &[u8]) -> Result<()> {
-        let key = format!("{}:{}", symbol, timestamp_sec);
-        let db = self.db.clone();
+    /// Open storage on `backend`; `path` is only used by RocksDB
+    pub fn open<P: AsRef<Path>>(
+        backend: StorageBackend,
+        path: P,
+        config: &StorageConfig,
+    ) -> Result<Self> {
+        match backend {
+            StorageBackend::RocksDb => Self::with_config(path, config),
+            StorageBackend::Memory {
+                max_snapshots_per_symbol,
+            } => Ok(Self::in_memory(max_snapshots_per_symbol)),
+        }
+    }
+
+    /// Non-persistent storage keeping the newest `max_snapshots_per_symbol`
+    /// snapshots of each symbol
+    pub fn in_memory(max_snapshots_per_symbol: usize) -> Self {
+        Self {
+            store: Arc::new(memory::MemorySnapshotStore::new(max_snapshots_per_symbol)),
+            db: None,
+        }
+    }
+
+    fn from_db(db: Arc<DB>) -> Self {
+        Self {
+            store: Arc::new(RocksDbStore { db: db.clone() }),
+            db: Some(db),
+        }
+    }
+
+    /// Open an existing database read-only (e.g., for CSV export while the server runs)
+    ///
+    /// Does not take the RocksDB write lock, so it can be used alongside a live
+    /// persistence task. Data written after opening is not visible.
+    pub fn open_read_only<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let opts = base_options();
+
+        let db = DB::open_for_read_only(&opts, path, false)
+            .context("Failed to open RocksDB read-only for snapshot storage")?;
+
+        Ok(Self::from_db(Arc::new(db)))
+    }
+
+    /// Store a snapshot with key format `{symbol}:{unix_timestamp_ms}`
+    pub async fn put(&self, symbol: &str, timestamp_ms: i64, value: &[u8]) -> Result<()> {
+        let symbol = symbol.to_string();
+        let store = self.store.clone();
         let value_owned = value.to_vec(); // Convert to owned Vec<u8> for 'static
 
         // Spawn blocking to avoid blocking async runtime
-        tokio::task::spawn_blocking(move || {
-            db.put(key.as_bytes(), &value_owned)
-                .context("Failed to write snapshot to RocksDB")
-        })
-        .await??;
+        tokio::task::spawn_blocking(move || store.put(&symbol, timestamp_ms, &value_owned))
+            .await??;
 
         Ok(())
     }
 
     /// Retrieve a snapshot by exact key
-    pub async fn get(&self, symbol: &str, timestamp_sec: i64) -> Result<Option<Vec<u8>>> {
-        let key = format!("{}:{}", symbol, timestamp_sec);
-        let db = self.db.clone();
+    pub async fn get(&self, symbol: &str, timestamp_ms: i64) -> Result<Option<Vec<u8>>> {
+        let symbol = symbol.to_string();
+        let store = self.store.clone();
 
-        tokio::task::spawn_blocking(move || {
-            db.get(key.as_bytes())
-                .context("Failed to read snapshot from RocksDB")
-        })
-        .await?
+        tokio::task::spawn_blocking(move || store.get(&symbol, timestamp_ms)).await?
+    }
+
+    /// Delete a snapshot by exact key
+    pub async fn delete(&self, symbol: &str, timestamp_ms: i64) -> Result<()> {
+        let symbol = symbol.to_string();
+        let store = self.store.clone();
+
+        tokio::task::spawn_blocking(move || store.delete(&symbol, timestamp_ms)).await?
+    }
+
+    /// Serialized snapshots of `symbol` keyed within `[start_ms, end_ms]`, oldest first
+    pub async fn query_range(
+        &self,
+        symbol: &str,
+        start_ms: i64,
+        end_ms: i64,
+    ) -> Result<Vec<Vec<u8>>> {
+        let symbol = symbol.to_string();
+        let store = self.store.clone();
+
+        tokio::task::spawn_blocking(move || store.query_range(&symbol, start_ms, end_ms)).await?
     }
 
     /// Delete keys older than retention period (7 days)
@@ -80,33 +482,85 @@ impl SnapshotStorage {
     /// Called by background cleanup task (hourly).
     /// Expected cleanup: ~1.7M keys/day (86,400 snapshots/day/pair × 20 pairs)
     pub async fn cleanup_old_snapshots(&self, retention_secs: i64) -> Result<usize> {
-        let cutoff_timestamp = chrono::Utc::now().timestamp() - retention_secs;
-        let db = self.db.clone();
+        let cutoff_ms = (chrono::Utc::now().timestamp() - retention_secs) * 1000;
+        let store = self.store.clone();
+
+        tokio::task::spawn_blocking(move || store.delete_before(cutoff_ms)).await?
+    }
+
+    /// Approximate size in bytes (RocksDB: SST files + memtables; memory: keys + values)
+    pub fn size_bytes(&self) -> Result<u64> {
+        self.store.size_bytes()
+    }
+
+    /// Check the storage size cap
+    ///
+    /// # Returns
+    /// Bytes over `max_bytes` (0 when within the cap)
+    pub fn check_size_limit(&self, max_bytes: u64) -> Result<u64> {
+        Ok(self.size_bytes()?.saturating_sub(max_bytes))
+    }
+
+    /// Delete the oldest keys until at least `bytes_to_free` bytes are removed
+    ///
+    /// Covers both snapshot (`{symbol}:{ms}`) and trade (`trades:{symbol}:{ms}`)
+    /// keys, ordered by timestamp across all symbols. Sizes are logical
+    /// (key + value), so with compression a single pass may free less on disk
+    /// than requested; the next enforcement run purges the remainder.
+    ///
+    /// The memory backend is bounded by its per-symbol count instead and
+    /// never purges here.
+    ///
+    /// # Returns
+    /// Number of keys deleted
+    pub async fn purge_oldest(&self, bytes_to_free: u64) -> Result<usize> {
+        let Some(db) = self.db.clone() else {
+            return Ok(0);
+        };
+        if bytes_to_free == 0 {
+            return Ok(0);
+        }
 
         tokio::task::spawn_blocking(move || {
-            let mut batch = WriteBatch::default();
-            let mut deleted_count = 0;
+            // Pass 1: bytes stored per second, oldest first
+            let mut bytes_per_sec: BTreeMap<i64, u64> = BTreeMap::new();
+            for item in db.iterator(rocksdb::IteratorMode::Start) {
+                let (key, value) = item?;
+                if let Some(ts) = key_timestamp_secs(&key) {
+                    *bytes_per_sec.entry(ts).or_default() += (key.len() + value.len()) as u64;
+                }
+            }
 
-            // Iterate all keys (no prefix filter - global cleanup)
-            let iter = db.iterator(rocksdb::IteratorMode::Start);
+            // Smallest cutoff whose cumulative size covers the request
+            let mut freed = 0u64;
+            let mut cutoff = None;
+            for (ts, bytes) in &bytes_per_sec {
+                freed += bytes;
+                cutoff = Some(*ts);
+                if freed >= bytes_to_free {
+                    break;
+                }
+            }
+            let Some(cutoff) = cutoff else {
+                return Ok(0);
+            };
 
-            for item in iter {
+            // Pass 2: delete everything at or before the cutoff
+            let mut batch = WriteBatch::default();
+            let mut deleted_count = 0;
+            for item in db.iterator(rocksdb::IteratorMode::Start) {
                 let (key, _) = item?;
-                let key_str = String::from_utf8_lossy(&key);
-
-                // Parse timestamp from key format "{symbol}:{timestamp}"
-                if let Some(timestamp_str) = key_str.split(':').nth(1) {
-                    if let Ok(timestamp) = timestamp_str.parse::<i64>() {
-                        if timestamp < cutoff_timestamp {
-                            batch.delete(&key);
-                            deleted_count += 1;
-                        }
-                    }
+                if key_timestamp_secs(&key).is_some_and(|ts| ts <= cutoff) {
+                    batch.delete(&key);
+                    deleted_count += 1;
                 }
             }
 
             if deleted_count > 0 {
-                db.write(batch).context("Failed to delete old snapshots")?;
+                db.write(batch)
+                    .context("Failed to purge oldest keys for size cap")?;
+                // Reclaim disk space now rather than at the next natural compaction
+                db.compact_range::<&[u8], &[u8]>(None, None);
             }
 
             Ok(deleted_count)
@@ -114,24 +568,45 @@ impl SnapshotStorage {
         .await?
     }
 
+    /// Purge oldest keys if the database exceeds `max_bytes`
+    ///
+    /// # Returns
+    /// Number of keys deleted (0 when within the cap)
+    pub async fn enforce_size_limit(&self, max_bytes: u64) -> Result<usize> {
+        let excess = self.check_size_limit(max_bytes)?;
+        if excess == 0 {
+            return Ok(0);
+        }
+
+        tracing::warn!(
+            size_bytes = excess + max_bytes,
+            max_bytes,
+            "Analytics storage over size cap, purging oldest keys"
+        );
+        self.purge_oldest(excess).await
+    }
+
     /// Get database handle for advanced queries (prefix scans)
     ///
     /// This allows sharing the same DB across multiple storage modules
-    /// (e.g., SnapshotStorage for orderbook snapshots, TradeStorage for trade persistence)
-    pub fn db(&self) -> Arc<DB> {
+    /// (e.g., SnapshotStorage for orderbook snapshots, TradeStorage for trade persistence).
+    /// `None` for the memory backend.
+    pub fn db(&self) -> Option<Arc<DB>> {
         self.db.clone()
     }
 }
 
 /// Spawn background task for periodic snapshot persistence
 ///
-/// Captures orderbook snapshots every 1 second and persists to RocksDB.
-/// Gracefully shuts down when shutdown signal is received.
+/// Captures orderbook snapshots every `interval` (1 second by default) and persists to RocksDB.
+/// On shutdown one final snapshot per symbol is captured before the task exits,
+/// so the stored history runs right up to the restart.
 ///
 /// # Arguments
 /// * `storage` - RocksDB snapshot storage handle
 /// * `manager` - OrderBook manager for accessing current orderbook state
 /// * `symbols` - List of symbols to persist (e.g., ["BTCUSDT", "ETHUSDT"])
+/// * `interval` - Time between snapshot captures
 /// * `shutdown_rx` - Broadcast receiver for shutdown signal
 ///
 /// # Returns
@@ -140,106 +615,232 @@ pub fn spawn_snapshot_persistence_task(
     storage: Arc<SnapshotStorage>,
     manager: Arc<crate::orderbook::OrderBookManager>,
     symbols: &[&str],
+    interval: std::time::Duration,
     mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
 ) -> tokio::task::JoinHandle<()> {
     let symbols_owned: Vec<String> = symbols.iter().map(|s| s.to_string()).collect();
 
     tokio::spawn(async move {
-        use snapshot::OrderBookSnapshot;
-
-        // T008: 1-second interval loop
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(1));
+        // T008: Capture loop (1-second interval by default)
+        let mut interval = tokio::time::interval(interval);
         interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
 
         tracing::info!(
-            "Snapshot persistence task started for {} symbols",
-            symbols_owned.len()
+            "Snapshot persistence task started for {} symbols (every {:?})",
+            symbols_owned.len(),
+            interval.period()
         );
 
         loop {
             tokio::select! {
                 // T009: Graceful shutdown handling
                 _ = shutdown_rx.recv() => {
-                    tracing::info!("Snapshot persistence task shutting down");
+                    tracing::info!("Snapshot persistence task shutting down, capturing final snapshots");
+                    capture_snapshots(&storage, &manager, &symbols_owned).await;
+                    break;
+                }
+
+                // T008: Capture tick
+                _ = interval.tick() => {
+                    capture_snapshots(&storage, &manager, &symbols_owned).await;
+                }
+            }
+        }
+
+        tracing::info!("Snapshot persistence task stopped");
+    })
+}
+
+/// Capture and persist the current orderbook of each symbol
+///
+/// Failures are logged per symbol and never stop the remaining captures.
+async fn capture_snapshots(
+    storage: &SnapshotStorage,
+    manager: &crate::orderbook::OrderBookManager,
+    symbols: &[String],
+) {
+    use snapshot::OrderBookSnapshot;
+
+    for symbol in symbols {
+        // T010: Capture snapshot from OrderBookManager
+        let orderbook = match manager.get_order_book(symbol).await {
+            Ok(ob) => ob,
+            Err(e) => {
+                // T014: ERROR-level logging for failures
+                tracing::error!(
+                    symbol = %symbol,
+                    error = %e,
+                    "Failed to get orderbook for snapshot"
+                );
+                continue;
+            }
+        };
+
+        // Skip empty orderbooks
+        if orderbook.bids.is_empty() && orderbook.asks.is_empty() {
+            tracing::warn!(
+                symbol = %symbol,
+                "Skipping snapshot: empty orderbook"
+            );
+            continue;
+        }
+
+        // T011: Serialize to MessagePack (one clock read for value and key)
+        let captured_at_ms = chrono::Utc::now().timestamp_millis();
+        let snapshot = OrderBookSnapshot::from_orderbook_at(&orderbook, captured_at_ms);
+
+        // T021: DEBUG-level logging for snapshot capture details
+        tracing::debug!(
+            symbol = %symbol,
+            timestamp = %snapshot.timestamp,
+            update_id = %snapshot.update_id,
+            bid_levels = %snapshot.bids.len(),
+            ask_levels = %snapshot.asks.len(),
+            "Captured orderbook snapshot"
+        );
+
+        let bytes = match snapshot.to_bytes() {
+            Ok(b) => b,
+            Err(e) => {
+                // T014: ERROR-level logging for serialization failures
+                tracing::error!(
+                    symbol = %symbol,
+                    timestamp = %snapshot.timestamp,
+                    error = %e,
+                    "Failed to serialize snapshot to MessagePack"
+                );
+                continue;
+            }
+        };
+
+        // T012: Store in RocksDB
+        if let Err(e) = storage.put(symbol, captured_at_ms, &bytes).await {
+            // T014: ERROR-level logging for storage failures
+            tracing::error!(
+                symbol = %symbol,
+                timestamp = %snapshot.timestamp,
+                error = %e,
+                "Failed to persist snapshot to RocksDB"
+            );
+        } else {
+            // T013: INFO-level logging for successful persistence
+            tracing::info!(
+                symbol = %symbol,
+                timestamp = %snapshot.timestamp,
+                "Stored snapshot"
+            );
+        }
+    }
+}
+
+/// Snapshot keys below this are legacy Unix seconds (ms keys passed it in 1973)
+const LEGACY_SECONDS_KEY_LIMIT: i64 = 100_000_000_000;
+
+/// Timestamp field of a snapshot key in Unix milliseconds
+///
+/// Accepts both the current millisecond keys and legacy second keys.
+pub(crate) fn snapshot_key_millis(field: &str) -> Option<i64> {
+    let timestamp = field.parse::<i64>().ok()?;
+    if timestamp < LEGACY_SECONDS_KEY_LIMIT {
+        Some(timestamp * 1000)
+    } else {
+        Some(timestamp)
+    }
+}
+
+/// Timestamp of a snapshot or trade key, normalized to Unix seconds
+fn key_timestamp_secs(key: &[u8]) -> Option<i64> {
+    let key_str = std::str::from_utf8(key).ok()?;
+    match key_str.strip_prefix("trades:") {
+        Some(rest) => rest
+            .split(':')
+            .nth(1)?
+            .parse::<i64>()
+            .ok()
+            .map(|ms| ms / 1000),
+        None => snapshot_key_millis(key_str.split(':').nth(1)?).map(|ms| ms / 1000),
+    }
+}
+
+/// Spawn background task enforcing retention and the storage size cap
+///
+/// Runs every `CLEANUP_INTERVAL` (hourly): deletes snapshots and trades older
+/// than `retention`, then purges oldest keys while the DB exceeds `max_bytes`.
+///
+/// # Arguments
+/// * `storage` - RocksDB snapshot storage handle
+/// * `trade_storage` - Trade storage sharing the same DB (optional)
+/// * `retention` - Maximum age of persisted data
+/// * `max_bytes` - Storage size cap (e.g., `MAX_STORAGE_BYTES`)
+/// * `shutdown_rx` - Broadcast receiver for shutdown signal
+pub fn spawn_retention_task(
+    storage: Arc<SnapshotStorage>,
+    trade_storage: Option<Arc<super::TradeStorage>>,
+    retention: Duration,
+    max_bytes: u64,
+    mut shutdown_rx: tokio::sync::broadcast::Receiver<()>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        tracing::info!(
+            retention_days = retention.as_secs() / 86_400,
+            max_bytes,
+            "Analytics retention task started"
+        );
+
+        loop {
+            tokio::select! {
+                _ = shutdown_rx.recv() => {
+                    tracing::info!("Analytics retention task shutting down");
                     break;
                 }
 
-                // T008: 1-second tick
                 _ = interval.tick() => {
-                    for symbol in &symbols_owned {
-                        // T010: Capture snapshot from OrderBookManager
-                        let orderbook = match manager.get_order_book(symbol).await {
-                            Ok(ob) => ob,
-                            Err(e) => {
-                                // T014: ERROR-level logging for failures
-                                tracing::error!(
-                                    symbol = %symbol,
-                                    error = %e,
-                                    "Failed to get orderbook for snapshot"
-                                );
-                                continue;
-                            }
-                        };
-
-                        // Skip empty orderbooks
-                        if orderbook.bids.is_empty() && orderbook.asks.is_empty() {
-                            tracing::warn!(
-                                symbol = %symbol,
-                                "Skipping snapshot: empty orderbook"
-                            );
-                            continue;
+                    let retention_secs = retention.as_secs() as i64;
+
+                    let expired_snapshots = match storage.cleanup_old_snapshots(retention_secs).await {
+                        Ok(count) => count,
+                        Err(e) => {
+                            tracing::error!(error = %e, "Snapshot retention cleanup failed");
+                            0
                         }
+                    };
 
-                        // T011: Serialize to MessagePack
-                        let snapshot = OrderBookSnapshot::from_orderbook(&orderbook);
-
-                        // T021: DEBUG-level logging for snapshot capture details
-                        tracing::debug!(
-                            symbol = %symbol,
-                            timestamp = %snapshot.timestamp,
-                            update_id = %snapshot.update_id,
-                            bid_levels = %snapshot.bids.len(),
-                            ask_levels = %snapshot.asks.len(),
-                            "Captured orderbook snapshot"
-                        );
-
-                        let bytes = match snapshot.to_bytes() {
-                            Ok(b) => b,
-                            Err(e) => {
-                                // T014: ERROR-level logging for serialization failures
-                                tracing::error!(
-                                    symbol = %symbol,
-                                    timestamp = %snapshot.timestamp,
-                                    error = %e,
-                                    "Failed to serialize snapshot to MessagePack"
-                                );
-                                continue;
-                            }
-                        };
-
-                        // T012: Store in RocksDB
-                        if let Err(e) = storage.put(symbol, snapshot.timestamp, &bytes).await {
-                            // T014: ERROR-level logging for storage failures
-                            tracing::error!(
-                                symbol = %symbol,
-                                timestamp = %snapshot.timestamp,
-                                error = %e,
-                                "Failed to persist snapshot to RocksDB"
-                            );
-                        } else {
-                            // T013: INFO-level logging for successful persistence
-                            tracing::info!(
-                                symbol = %symbol,
-                                timestamp = %snapshot.timestamp,
-                                "Stored snapshot"
-                            );
+                    let mut expired_trades = 0;
+                    if let Some(trades) = &trade_storage {
+                        let trades = trades.clone();
+                        let cutoff_ms =
+                            (chrono::Utc::now().timestamp() - retention_secs) * 1000;
+                        match tokio::task::spawn_blocking(move || trades.cleanup_old_trades(cutoff_ms)).await {
+                            Ok(Ok(count)) => expired_trades = count,
+                            Ok(Err(e)) => tracing::error!(error = %e, "Trade retention cleanup failed"),
+                            Err(e) => tracing::error!(error = %e, "Trade retention cleanup panicked"),
                         }
                     }
+
+                    let purged = match storage.enforce_size_limit(max_bytes).await {
+                        Ok(count) => count,
+                        Err(e) => {
+                            tracing::error!(error = %e, "Storage size cap enforcement failed");
+                            0
+                        }
+                    };
+
+                    let size_bytes = storage.size_bytes().unwrap_or(0);
+                    tracing::info!(
+                        expired_snapshots,
+                        expired_trades,
+                        purged_for_size_cap = purged,
+                        size_bytes,
+                        "Analytics storage cleanup completed"
+                    );
                 }
             }
         }
 
-        tracing::info!("Snapshot persistence task stopped");
+        tracing::info!("Analytics retention task stopped");
     })
 }
 
@@ -254,9 +855,9 @@ mod tests {
         let storage = SnapshotStorage::new(temp_dir.path())?;
 
         let test_data = b"test_snapshot_data";
-        storage.put("BTCUSDT", 1737158400, test_data).await?;
+        storage.put("BTCUSDT", 1737158400123, test_data).await?;
 
-        let retrieved = storage.get("BTCUSDT", 1737158400).await?;
+        let retrieved = storage.get("BTCUSDT", 1737158400123).await?;
         assert_eq!(retrieved.as_deref(), Some(test_data.as_ref()));
 
         Ok(())
@@ -268,11 +869,11 @@ mod tests {
         let storage = SnapshotStorage::new(temp_dir.path())?;
 
         // Insert old snapshot (8 days ago)
-        let old_timestamp = chrono::Utc::now().timestamp() - (8 * 24 * 3600);
+        let old_timestamp = chrono::Utc::now().timestamp_millis() - (8 * 24 * 3600 * 1000);
         storage.put("BTCUSDT", old_timestamp, b"old_data").await?;
 
         // Insert recent snapshot (1 day ago)
-        let recent_timestamp = chrono::Utc::now().timestamp() - (1 * 24 * 3600);
+        let recent_timestamp = chrono::Utc::now().timestamp_millis() - (1 * 24 * 3600 * 1000);
         storage
             .put("ETHUSDT", recent_timestamp, b"recent_data")
             .await?;
@@ -287,4 +888,149 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_cleanup_expires_legacy_second_keys() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let storage = SnapshotStorage::new(temp_dir.path())?;
+
+        let old_sec = chrono::Utc::now().timestamp() - (8 * 24 * 3600);
+        let recent_sec = chrono::Utc::now().timestamp() - 3600;
+        storage.put("BTCUSDT", old_sec, b"legacy_old").await?;
+        storage.put("BTCUSDT", recent_sec, b"legacy_recent").await?;
+
+        assert_eq!(storage.cleanup_old_snapshots(7 * 24 * 3600).await?, 1);
+        assert!(storage.get("BTCUSDT", recent_sec).await?.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_timestamp_secs() {
+        assert_eq!(
+            key_timestamp_secs(b"BTCUSDT:1737158400999"),
+            Some(1737158400)
+        );
+        // Legacy second keys
+        assert_eq!(key_timestamp_secs(b"BTCUSDT:1737158400"), Some(1737158400));
+        assert_eq!(
+            key_timestamp_secs(b"trades:BTCUSDT:1737158400123"),
+            Some(1737158400)
+        );
+        assert_eq!(key_timestamp_secs(b"garbage"), None);
+    }
+
+    #[test]
+    fn test_symbol_prefix_extends_to_last_delimiter() {
+        assert_eq!(symbol_prefix(b"BTC:1737158400"), b"BTC:");
+        assert_eq!(symbol_prefix(b"BTCUSDT:1737158400"), b"BTCUSDT:");
+        assert_eq!(
+            symbol_prefix(b"trades:BTCUSDT:1737158400123"),
+            b"trades:BTCUSDT:"
+        );
+        assert!(!has_symbol_prefix(b"garbage"));
+    }
+
+    #[tokio::test]
+    async fn test_prefix_scan_returns_only_target_symbol() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let storage = SnapshotStorage::new(temp_dir.path())?;
+
+        // Symbols sharing leading bytes, shorter and longer than the old fixed prefix
+        for ts in 1000..1005 {
+            storage.put("BTC", ts, b"short").await?;
+            storage.put("BTCUSDT", ts, b"long").await?;
+            storage.put("BTCUSDTM", ts, b"longer").await?;
+        }
+        let db = storage.db().unwrap();
+        db.put(b"trades:BTC:1000000", b"trade")?;
+        // Scan SST files (and their prefix blooms) as well as the memtable
+        db.flush()?;
+        storage.put("BTC", 1005, b"short").await?;
+
+        for (prefix, expected) in [("BTC:", 6), ("BTCUSDT:", 5), ("trades:BTC:", 1)] {
+            let keys: Vec<String> = db
+                .prefix_iterator(prefix.as_bytes())
+                .map(|item| String::from_utf8(item.unwrap().0.to_vec()).unwrap())
+                .collect();
+            assert_eq!(keys.len(), expected, "keys for {}: {:?}", prefix, keys);
+            assert!(keys.iter().all(|key| key.starts_with(prefix)));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_storage_backend_from_values() {
+        assert_eq!(
+            StorageBackend::from_values(None, None).unwrap(),
+            StorageBackend::RocksDb
+        );
+        assert_eq!(
+            StorageBackend::from_values(Some("Memory"), None).unwrap(),
+            StorageBackend::Memory {
+                max_snapshots_per_symbol: memory::DEFAULT_MEMORY_SNAPSHOTS_PER_SYMBOL
+            }
+        );
+        assert_eq!(
+            StorageBackend::from_values(Some("memory"), Some("600")).unwrap(),
+            StorageBackend::Memory {
+                max_snapshots_per_symbol: 600
+            }
+        );
+        assert!(StorageBackend::from_values(Some("memory"), Some("0")).is_err());
+        assert!(StorageBackend::from_values(Some("sqlite"), None).is_err());
+    }
+
+    #[test]
+    fn test_storage_config_from_values() {
+        assert_eq!(
+            StorageConfig::from_values(None, Some(""), None).unwrap(),
+            StorageConfig::default()
+        );
+
+        let config = StorageConfig::from_values(Some("16"), Some("128"), Some("LZ4")).unwrap();
+        assert_eq!(config.write_buffer_bytes, 16 * 1024 * 1024);
+        assert_eq!(config.block_cache_bytes, 128 * 1024 * 1024);
+        assert_eq!(config.compression, DBCompressionType::Lz4);
+
+        assert!(StorageConfig::from_values(Some("0"), None, None).is_err());
+        assert!(StorageConfig::from_values(None, None, Some("brotli")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_purge_oldest_first_beyond_tiny_cap() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let storage = SnapshotStorage::new(temp_dir.path())?;
+
+        // Interleave symbols so key order (symbol-first) differs from age order
+        let value = [0u8; 100];
+        let base_ms = 1_737_158_400_000;
+        for ts in 1000..1010 {
+            let symbol = if ts % 2 == 0 { "BTCUSDT" } else { "ETHUSDT" };
+            storage.put(symbol, base_ms + ts * 1000, &value).await?;
+        }
+
+        // Simulated cap exceeded by the size of three entries
+        let entry_bytes = ("BTCUSDT:1737158400000".len() + value.len()) as u64;
+        let deleted = storage.purge_oldest(3 * entry_bytes).await?;
+        assert_eq!(deleted, 3);
+
+        for ts in 1000..1010 {
+            let symbol = if ts % 2 == 0 { "BTCUSDT" } else { "ETHUSDT" };
+            let present = storage.get(symbol, base_ms + ts * 1000).await?.is_some();
+            assert_eq!(
+                present,
+                ts >= 1003,
+                "unexpected state for {}:{}",
+                symbol,
+                ts
+            );
+        }
+
+        // Within a generous cap nothing is purged
+        assert_eq!(storage.enforce_size_limit(MAX_STORAGE_BYTES).await?, 0);
+
+        Ok(())
+    }
 }
@@ -3,13 +3,16 @@
 //! Implements efficient time-range queries using RocksDB prefix scans.
 //! Target latency: <200ms for typical 60-second window queries.
 
-use super::{snapshot::OrderBookSnapshot, SnapshotStorage};
+use super::{snapshot::OrderBookSnapshot, snapshot_key_millis, SnapshotStorage};
 use anyhow::{Context, Result};
-use rocksdb::IteratorMode;
+use rocksdb::{IteratorMode, DB};
 
 /// Query snapshots within a time range for a symbol
 ///
-/// Uses RocksDB prefix scan with key format `{symbol}:{timestamp}`.
+/// Uses RocksDB prefix scan with key format `{symbol}:{timestamp_ms}` (legacy
+/// `{symbol}:{timestamp_sec}` keys are included), or the in-memory ring buffer
+/// when that backend is selected. The window is inclusive of every
+/// millisecond in `end_timestamp_sec`.
 /// Target performance: <200ms for 60-second window (60 snapshots).
 pub async fn query_snapshots_in_window(
     storage: &SnapshotStorage,
@@ -17,66 +20,86 @@ pub async fn query_snapshots_in_window(
     start_timestamp_sec: i64,
     end_timestamp_sec: i64,
 ) -> Result<Vec<OrderBookSnapshot>> {
-    let symbol_owned = symbol.to_string();
-    let db = storage.db().clone();
-
-    // Spawn blocking to avoid blocking async runtime
-    tokio::task::spawn_blocking(move || {
-        let start_instant = std::time::Instant::now();
-        let mut snapshots = Vec::new();
-
-        // PERF FIX: Start iteration at start_timestamp instead of beginning of symbol
-        // Key format: "{symbol}:{timestamp}" - start at exact timestamp
-        let start_key = format!("{}:{}", symbol_owned, start_timestamp_sec);
-        let prefix = format!("{}:", symbol_owned);
-        let mode = IteratorMode::From(start_key.as_bytes(), rocksdb::Direction::Forward);
-
-        let mut keys_scanned = 0;
-        let mut keys_matched = 0;
-
-        for item in db.iterator(mode) {
-            let (key, value) = item?;
-            keys_scanned += 1;
-            let key_str = String::from_utf8_lossy(&key);
-
-            // Check if key still matches symbol prefix
-            if !key_str.starts_with(&prefix) {
-                break; // Moved past our symbol, stop iteration
-            }
+    let start_instant = std::time::Instant::now();
+    let values = storage
+        .query_range(
+            symbol,
+            start_timestamp_sec * 1000,
+            end_timestamp_sec * 1000 + 999,
+        )
+        .await?;
+
+    let snapshots = values
+        .iter()
+        .map(|value| OrderBookSnapshot::from_bytes(value).context("Failed to deserialize snapshot"))
+        .collect::<Result<Vec<_>>>()?;
+
+    tracing::info!(
+        "Snapshot query completed: symbol={} window={}s duration={:?} snapshots={}",
+        symbol,
+        end_timestamp_sec - start_timestamp_sec,
+        start_instant.elapsed(),
+        snapshots.len()
+    );
+
+    Ok(snapshots)
+}
 
-            // Parse timestamp from key "{symbol}:{timestamp}"
-            if let Some(timestamp_str) = key_str.split(':').nth(1) {
-                if let Ok(timestamp) = timestamp_str.parse::<i64>() {
-                    // PERF: Early exit if we've passed end timestamp
-                    if timestamp > end_timestamp_sec {
-                        break;
-                    }
-
-                    // Filter by time range
-                    if timestamp >= start_timestamp_sec && timestamp <= end_timestamp_sec {
-                        let snapshot = OrderBookSnapshot::from_bytes(&value)
-                            .context("Failed to deserialize snapshot")?;
-                        snapshots.push(snapshot);
-                        keys_matched += 1;
-                    }
+/// Serialized snapshots of `symbol` keyed within `[start_ms, end_ms]` (blocking)
+///
+/// Seeks straight to the start of the window and stops at the first key past
+/// it, so the cost is proportional to the window rather than the symbol's history.
+pub(crate) fn scan_snapshot_range(
+    db: &DB,
+    symbol: &str,
+    start_ms: i64,
+    end_ms: i64,
+) -> Result<Vec<Vec<u8>>> {
+    let mut values = Vec::new();
+
+    // PERF FIX: Start iteration at start_timestamp instead of beginning of symbol
+    // Key format: "{symbol}:{timestamp_ms}" - the seconds key sorts at or before
+    // both the first millisecond key and a legacy seconds key of that second
+    let start_key = format!("{}:{}", symbol, start_ms.div_euclid(1000));
+    let prefix = format!("{}:", symbol);
+    let mode = IteratorMode::From(start_key.as_bytes(), rocksdb::Direction::Forward);
+
+    let mut keys_scanned = 0;
+
+    for item in db.iterator(mode) {
+        let (key, value) = item?;
+        keys_scanned += 1;
+        let key_str = String::from_utf8_lossy(&key);
+
+        // Check if key still matches symbol prefix
+        if !key_str.starts_with(&prefix) {
+            break; // Moved past our symbol, stop iteration
+        }
+
+        // Parse timestamp from key "{symbol}:{timestamp_ms}"
+        if let Some(timestamp_str) = key_str.split(':').nth(1) {
+            if let Some(timestamp_ms) = snapshot_key_millis(timestamp_str) {
+                // PERF: Early exit if we've passed end timestamp
+                if timestamp_ms > end_ms {
+                    break;
+                }
+
+                // Filter by time range
+                if timestamp_ms >= start_ms {
+                    values.push(value.to_vec());
                 }
             }
         }
+    }
 
-        let elapsed = start_instant.elapsed();
-        tracing::info!(
-            "Snapshot query completed: symbol={} window={}s duration={:?} keys_scanned={} keys_matched={} snapshots={}",
-            symbol_owned,
-            end_timestamp_sec - start_timestamp_sec,
-            elapsed,
-            keys_scanned,
-            keys_matched,
-            snapshots.len()
-        );
-
-        Ok(snapshots)
-    })
-    .await?
+    tracing::debug!(
+        "Snapshot scan: symbol={} keys_scanned={} keys_matched={}",
+        symbol,
+        keys_scanned,
+        values.len()
+    );
+
+    Ok(values)
 }
 
 /// Count orderbook updates in time window (used for flow rate calculation)
@@ -125,41 +148,82 @@ mod tests {
         let storage = SnapshotStorage::new(temp_dir.path())?;
 
         // Insert 3 snapshots at different timestamps
-        let snapshot1 = OrderBookSnapshot {
-            bids: vec![("100.0".to_string(), "1.0".to_string())],
-            asks: vec![("101.0".to_string(), "1.0".to_string())],
-            update_id: 1,
-            timestamp: 1000,
-        };
-        let snapshot2 = OrderBookSnapshot {
-            bids: vec![("100.1".to_string(), "1.1".to_string())],
-            asks: vec![("101.1".to_string(), "1.1".to_string())],
-            update_id: 2,
-            timestamp: 1001,
-        };
-        let snapshot3 = OrderBookSnapshot {
-            bids: vec![("100.2".to_string(), "1.2".to_string())],
-            asks: vec![("101.2".to_string(), "1.2".to_string())],
-            update_id: 3,
-            timestamp: 1002,
-        };
-
-        storage.put("BTCUSDT", 1000, &snapshot1.to_bytes()?).await?;
-        storage.put("BTCUSDT", 1001, &snapshot2.to_bytes()?).await?;
-        storage.put("BTCUSDT", 1002, &snapshot3.to_bytes()?).await?;
+        let snapshot1 = snapshot(1, 1737158400);
+        let snapshot2 = snapshot(2, 1737158401);
+        let snapshot3 = snapshot(3, 1737158402);
+
+        storage
+            .put("BTCUSDT", 1737158400000, &snapshot1.to_bytes()?)
+            .await?;
+        storage
+            .put("BTCUSDT", 1737158401000, &snapshot2.to_bytes()?)
+            .await?;
+        storage
+            .put("BTCUSDT", 1737158402000, &snapshot3.to_bytes()?)
+            .await?;
 
         // Query window covering all 3 snapshots
-        let results = query_snapshots_in_window(&storage, "BTCUSDT", 1000, 1002).await?;
+        let results =
+            query_snapshots_in_window(&storage, "BTCUSDT", 1737158400, 1737158402).await?;
         assert_eq!(results.len(), 3);
 
         // Query partial window (only first 2)
-        let results = query_snapshots_in_window(&storage, "BTCUSDT", 1000, 1001).await?;
+        let results =
+            query_snapshots_in_window(&storage, "BTCUSDT", 1737158400, 1737158401).await?;
         assert_eq!(results.len(), 2);
 
         // Query outside window
-        let results = query_snapshots_in_window(&storage, "BTCUSDT", 2000, 2010).await?;
+        let results =
+            query_snapshots_in_window(&storage, "BTCUSDT", 1737159000, 1737159010).await?;
         assert_eq!(results.len(), 0);
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_snapshots_within_same_second_do_not_collide() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let storage = SnapshotStorage::new(temp_dir.path())?;
+
+        for (update_id, key_ms) in [(1, 1737158400100), (2, 1737158400600)] {
+            let bytes = snapshot(update_id, 1737158400).to_bytes()?;
+            storage.put("BTCUSDT", key_ms, &bytes).await?;
+        }
+
+        let results =
+            query_snapshots_in_window(&storage, "BTCUSDT", 1737158400, 1737158400).await?;
+        let update_ids: Vec<u64> = results.iter().map(|s| s.update_id).collect();
+        assert_eq!(update_ids, vec![1, 2]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_legacy_second_keys_are_still_queried() -> Result<()> {
+        let temp_dir = TempDir::new()?;
+        let storage = SnapshotStorage::new(temp_dir.path())?;
+
+        // Second keys written before the upgrade, mixed with a millisecond key
+        for (update_id, key) in [(1, 1737158400), (2, 1737158401500), (3, 1737158402)] {
+            let bytes = snapshot(update_id, 1737158400 + update_id as i64 - 1).to_bytes()?;
+            storage.put("BTCUSDT", key, &bytes).await?;
+        }
+
+        let results =
+            query_snapshots_in_window(&storage, "BTCUSDT", 1737158400, 1737158401).await?;
+        let update_ids: Vec<u64> = results.iter().map(|s| s.update_id).collect();
+        assert_eq!(update_ids, vec![1, 2]);
+
+        Ok(())
+    }
+
+    fn snapshot(update_id: u64, timestamp: i64) -> OrderBookSnapshot {
+        let offset = update_id as f64 / 10.0;
+        OrderBookSnapshot {
+            bids: vec![(format!("{:.1}", 100.0 + offset), "1.0".to_string())],
+            asks: vec![(format!("{:.1}", 101.0 + offset), "1.0".to_string())],
+            update_id,
+            timestamp,
+        }
+    }
 }
@@ -9,6 +9,9 @@ use anyhow::{Context, Result};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 
+/// Levels per side kept in each snapshot
+pub const SNAPSHOT_LEVELS: usize = 20;
+
 /// Simplified orderbook snapshot for storage (top 20 levels per side)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderBookSnapshot {
@@ -25,7 +28,12 @@ pub struct OrderBookSnapshot {
 impl OrderBookSnapshot {
     /// Create snapshot from full OrderBook (take top 20 levels)
     pub fn from_orderbook(orderbook: &OrderBook) -> Self {
-        let timestamp = Utc::now().timestamp();
+        Self::from_orderbook_at(orderbook, Utc::now().timestamp_millis())
+    }
+
+    /// Create snapshot captured at `captured_at_ms`, the millisecond storage key
+    pub fn from_orderbook_at(orderbook: &OrderBook, captured_at_ms: i64) -> Self {
+        let timestamp = captured_at_ms.div_euclid(1000);
 
         // Convert top 20 levels to string tuples for MessagePack
         // Bug #2 Fix: Bids are sorted ascending in BTreeMap, but we need HIGHEST bids
@@ -34,14 +42,14 @@ impl OrderBookSnapshot {
             .bids
             .iter()
             .rev()  // ← Fix: reverse to get highest bids first
-            .take(20)
+            .take(SNAPSHOT_LEVELS)
             .map(|(price, qty)| (price.to_string(), qty.to_string()))
             .collect();
 
         let asks: Vec<(String, String)> = orderbook
             .asks
             .iter()
-            .take(20)
+            .take(SNAPSHOT_LEVELS)
             .map(|(price, qty)| (price.to_string(), qty.to_string()))
             .collect();
 
@@ -53,6 +61,26 @@ impl OrderBookSnapshot {
         }
     }
 
+    /// Rebuild an OrderBook for `symbol` (timestamp converted to milliseconds)
+    pub fn to_orderbook(&self, symbol: &str) -> Result<OrderBook> {
+        use rust_decimal::Decimal;
+        use std::str::FromStr;
+
+        let parse = |(price, qty): &(String, String)| -> Result<(Decimal, Decimal)> {
+            Ok((
+                Decimal::from_str(price).with_context(|| format!("Invalid price: {}", price))?,
+                Decimal::from_str(qty).with_context(|| format!("Invalid quantity: {}", qty))?,
+            ))
+        };
+
+        let mut orderbook = OrderBook::new(symbol.to_string());
+        orderbook.bids = self.bids.iter().map(parse).collect::<Result<_>>()?;
+        orderbook.asks = self.asks.iter().map(parse).collect::<Result<_>>()?;
+        orderbook.last_update_id = self.update_id as i64;
+        orderbook.timestamp = self.timestamp * 1000;
+        Ok(orderbook)
+    }
+
     /// Serialize to MessagePack bytes
     pub fn to_bytes(&self) -> Result<Vec<u8>> {
         rmp_serde::to_vec(self).context("Failed to serialize snapshot to MessagePack")
@@ -87,19 +115,19 @@ pub async fn capture_snapshot_task(
         }
 
         // Create and store snapshot
-        let snapshot = OrderBookSnapshot::from_orderbook(&orderbook);
-        let timestamp_sec = snapshot.timestamp;
+        let timestamp_ms = Utc::now().timestamp_millis();
+        let snapshot = OrderBookSnapshot::from_orderbook_at(&orderbook, timestamp_ms);
         let bytes = snapshot.to_bytes()?;
 
         storage
-            .put(&symbol, timestamp_sec, &bytes)
+            .put(&symbol, timestamp_ms, &bytes)
             .await
             .context("Failed to store snapshot")?;
 
         tracing::debug!(
             "Captured snapshot for {} at timestamp {}",
             symbol,
-            timestamp_sec
+            timestamp_ms
         );
     }
 }
@@ -126,4 +154,23 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_snapshot_to_orderbook() -> Result<()> {
+        let snapshot = OrderBookSnapshot {
+            bids: vec![("100.50".to_string(), "1.5".to_string())],
+            asks: vec![("100.60".to_string(), "2.0".to_string())],
+            update_id: 12345,
+            timestamp: 1737158400,
+        };
+
+        let orderbook = snapshot.to_orderbook("BTCUSDT")?;
+        assert_eq!(orderbook.symbol, "BTCUSDT");
+        assert_eq!(orderbook.last_update_id, 12345);
+        assert_eq!(orderbook.timestamp, 1737158400000);
+        assert_eq!(orderbook.best_bid().unwrap().to_string(), "100.50");
+        assert_eq!(orderbook.best_ask().unwrap().to_string(), "100.60");
+
+        Ok(())
+    }
 }
@@ -0,0 +1,23 @@
+//! Snapshot builder shared by the analytics unit tests
+
+use super::storage::snapshot::OrderBookSnapshot;
+
+/// Snapshot at `timestamp` (also its update ID) with `(price, quantity)` levels
+pub(crate) fn snapshot(
+    timestamp: i64,
+    bids: &[(&str, &str)],
+    asks: &[(&str, &str)],
+) -> OrderBookSnapshot {
+    let levels = |levels: &[(&str, &str)]| {
+        levels
+            .iter()
+            .map(|(price, qty)| (price.to_string(), qty.to_string()))
+            .collect()
+    };
+    OrderBookSnapshot {
+        bids: levels(bids),
+        asks: levels(asks),
+        update_id: timestamp as u64,
+        timestamp,
+    }
+}
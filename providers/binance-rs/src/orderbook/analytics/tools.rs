@@ -5,19 +5,39 @@
 //! - get_volume_profile: Volume distribution histogram (POC/VAH/VAL)
 //! - detect_market_anomalies: Quote stuffing, icebergs, flash crash risk
 //! - get_microstructure_health: Composite market health scoring
+//! - get_health_trend: Bucketed health score over stored snapshots with trend direction
 //! - get_liquidity_vacuums: Low-volume price zones for SL placement
+//! - get_execution_benchmarks: VWAP/TWAP over stored trades
+//! - get_imbalance_history: Bucketed bid/ask imbalance over stored snapshots
+//! - get_spread_stats: Spread mean, volatility and range over stored snapshots
+//! - get_depth_heatmap: Resting liquidity gridded by price and time over stored snapshots
 
 use crate::orderbook::analytics::{
-    anomaly::{detect_flash_crash_risk, detect_iceberg_orders, detect_quote_stuffing},
+    anomaly::{
+        detect_flash_crash_risk, detect_iceberg_orders, detect_quote_stuffing, estimate_fill_rate,
+        QuoteStuffingThresholds, DEFAULT_QUOTE_STUFFING_FILL_RATE,
+        DEFAULT_QUOTE_STUFFING_UPDATE_RATE,
+    },
     flow::calculate_order_flow,
     health::calculate_microstructure_health,
-    profile::{generate_volume_profile, identify_liquidity_vacuums},
+    health_trend::{
+        bucket_health, fit_health_trend, HealthBucket, HealthTrendDirection,
+        MAX_HEALTH_TREND_BUCKETS,
+    },
+    heatmap::{build_depth_heatmap, DepthHeatmapGrid, MAX_HEATMAP_TIME_BUCKETS},
+    imbalance::{bucket_imbalance, ImbalanceBucket, MAX_IMBALANCE_BUCKETS},
+    profile::{
+        generate_volume_profile, identify_liquidity_vacuums, MIN_TRADES_FLOOR, VALUE_AREA_PCT_RANGE,
+    },
+    spread::{spread_stats, SpreadStats, MIN_SPREAD_SNAPSHOTS},
     storage::{query::query_snapshots_in_window, SnapshotStorage},
+    trade_storage::TradeStorage,
     trade_stream::AggTrade,
     types::{
         LiquidityVacuum, MarketMicrostructureAnomaly, MicrostructureHealth, OrderFlowSnapshot,
-        VolumeProfile,
+        PocBasis, VolumeProfile,
     },
+    vwap::{compute_twap, compute_vwap, TradeWindow},
 };
 use rust_decimal::Decimal;
 use schemars::JsonSchema;
@@ -37,6 +57,9 @@ pub enum AnalyticsToolError {
     #[error("Invalid window duration: {0}. Must be between 10 and 300 seconds")]
     InvalidWindowDuration(u32),
 
+    #[error("Invalid delta half-life: {0}. Must be a positive number of seconds")]
+    InvalidHalfLife(f64),
+
     #[error("Analytics calculation failed: {0}")]
     CalculationFailed(String),
 }
@@ -78,6 +101,17 @@ pub struct GetOrderFlowParams {
     )]
     #[serde(default = "default_window_duration")]
     pub window_duration_secs: u32,
+
+    /// Half-life in seconds for exponentially weighting the cumulative delta
+    /// toward recent flow
+    ///
+    /// Default: None (every interval in the window weighs the same)
+    #[schemars(
+        description = "Half-life in seconds for an exponentially-weighted cumulative delta. Omit for the unweighted sum.",
+        range(min = 0.001)
+    )]
+    #[serde(default)]
+    pub delta_half_life_secs: Option<f64>,
 }
 
 fn default_window_duration() -> u32 {
@@ -88,6 +122,70 @@ fn get_order_flow_symbol_examples() -> Vec<&'static str> {
     vec!["BTCUSDT", "ETHUSDT", "SOLUSDT"]
 }
 
+/// Parameters for detect_market_anomalies tool
+///
+/// Quote stuffing thresholds are per call because baseline update rates differ
+/// widely between symbols.
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct DetectAnomaliesParams {
+    /// Trading pair symbol (e.g., "BTCUSDT")
+    #[schemars(
+        description = "Trading pair (e.g., BTCUSDT). Must be uppercase.",
+        regex(pattern = r"^[A-Z]+$")
+    )]
+    pub symbol: String,
+
+    /// Detection window in seconds, ending now (10-300)
+    ///
+    /// Default: 60 seconds
+    #[schemars(
+        description = "Detection window in seconds ending now. Defaults to 60. Range: 10-300 seconds.",
+        range(min = 10, max = 300)
+    )]
+    #[serde(default = "default_window_duration")]
+    pub window_secs: u32,
+
+    /// Flag quote stuffing above this many order book updates per second
+    ///
+    /// Default: 500
+    #[schemars(
+        description = "Quote stuffing update rate threshold (updates/sec). Defaults to 500. Must be positive.",
+        range(min = 0.0)
+    )]
+    #[serde(default = "default_quote_stuffing_update_rate")]
+    pub quote_stuffing_update_rate: f64,
+
+    /// ...when the fill rate (0-1) is below this
+    ///
+    /// Default: 0.10
+    #[schemars(
+        description = "Quote stuffing fill rate threshold (0-1). Defaults to 0.10.",
+        range(min = 0.0, max = 1.0)
+    )]
+    #[serde(default = "default_quote_stuffing_fill_rate")]
+    pub quote_stuffing_fill_rate: f64,
+}
+
+impl DetectAnomaliesParams {
+    /// Default window and thresholds for `symbol`
+    pub fn new(symbol: &str) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            window_secs: default_window_duration(),
+            quote_stuffing_update_rate: default_quote_stuffing_update_rate(),
+            quote_stuffing_fill_rate: default_quote_stuffing_fill_rate(),
+        }
+    }
+}
+
+fn default_quote_stuffing_update_rate() -> f64 {
+    DEFAULT_QUOTE_STUFFING_UPDATE_RATE
+}
+
+fn default_quote_stuffing_fill_rate() -> f64 {
+    DEFAULT_QUOTE_STUFFING_FILL_RATE
+}
+
 /// Parameters for get_volume_profile tool
 ///
 /// JSON schema matches contract: specs/003-specify-scripts-bash/contracts/get_volume_profile.json
@@ -122,6 +220,46 @@ pub struct GetVolumeProfileParams {
     )]
     #[serde(default, deserialize_with = "deserialize_optional_string_or_number")]
     pub tick_size: Option<String>,
+
+    /// Share of total volume inside the value area (VAH/VAL)
+    ///
+    /// Default: 0.70. Range: 0.5-0.95
+    #[schemars(
+        description = "Optional: Share of total volume inside the value area (VAH/VAL). Defaults to 0.70. Range: 0.5-0.95.",
+        range(min = 0.5, max = 0.95)
+    )]
+    #[serde(default)]
+    pub value_area_pct: Option<f64>,
+
+    /// Maximum number of histogram bins to return
+    ///
+    /// Adjacent bins are merged when the natural bin count is larger.
+    /// Default: no limit
+    #[schemars(
+        description = "Optional: Maximum number of histogram bins. Adjacent bins are merged to fit; POC/VAH/VAL are unchanged. Must be at least 1.",
+        range(min = 1)
+    )]
+    #[serde(default)]
+    pub max_bins: Option<usize>,
+
+    /// Measure that picks the Point of Control
+    ///
+    /// Default: volume
+    #[schemars(
+        description = "Optional: Pick the POC by \"volume\" (default) or \"trade_count\" (TPO-style)."
+    )]
+    #[serde(default)]
+    pub poc_basis: PocBasis,
+
+    /// Trades required to build the profile
+    ///
+    /// Default: 1000. Minimum: 50; below 1000 the profile is flagged `low_confidence`
+    #[schemars(
+        description = "Optional: Trades required to build the profile. Defaults to 1000, minimum 50. Profiles from fewer than 1000 trades are flagged low_confidence.",
+        range(min = 50)
+    )]
+    #[serde(default)]
+    pub min_trades: Option<usize>,
 }
 
 /// Custom deserializer that accepts both string and number for tick_size
@@ -184,6 +322,252 @@ fn get_liquidity_vacuums_symbol_examples() -> Vec<&'static str> {
     vec!["BTCUSDT", "ETHUSDT", "SOLUSDT"]
 }
 
+/// Parameters for get_execution_benchmarks tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct GetExecutionBenchmarksParams {
+    /// Trading pair symbol (e.g., "BTCUSDT")
+    #[schemars(
+        description = "Trading pair (e.g., BTCUSDT). Must be uppercase.",
+        regex(pattern = r"^[A-Z]+$")
+    )]
+    pub symbol: String,
+
+    /// Benchmark window in seconds, ending now (60-86400)
+    ///
+    /// Default: 3600 seconds (1 hour)
+    #[schemars(
+        description = "Benchmark window in seconds ending now. Defaults to 3600. Range: 60-86400 seconds.",
+        range(min = 60, max = 86400)
+    )]
+    #[serde(default = "default_benchmark_window_secs")]
+    pub window_secs: u32,
+}
+
+fn default_benchmark_window_secs() -> u32 {
+    3600
+}
+
+/// VWAP/TWAP execution benchmarks for one symbol and window
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ExecutionBenchmarks {
+    pub symbol: String,
+    pub window_secs: u32,
+    /// Volume-weighted average price
+    #[schemars(with = "String")]
+    pub vwap: Decimal,
+    /// Time-weighted average price
+    #[schemars(with = "String")]
+    pub twap: Decimal,
+    /// Trades inside the window
+    pub trade_count: usize,
+    /// Window start (Unix milliseconds)
+    pub start_time: i64,
+    /// Window end (Unix milliseconds)
+    pub end_time: i64,
+}
+
+/// Parameters for get_imbalance_history tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct GetImbalanceHistoryParams {
+    /// Trading pair symbol (e.g., "BTCUSDT")
+    #[schemars(
+        description = "Trading pair (e.g., BTCUSDT). Must be uppercase.",
+        regex(pattern = r"^[A-Z]+$")
+    )]
+    pub symbol: String,
+
+    /// History window in seconds, ending now (60-86400)
+    ///
+    /// Default: 3600 seconds (1 hour)
+    #[schemars(
+        description = "History window in seconds ending now. Defaults to 3600. Range: 60-86400 seconds.",
+        range(min = 60, max = 86400)
+    )]
+    #[serde(default = "default_imbalance_window_secs")]
+    pub window_secs: u32,
+
+    /// Bucket width in seconds (at most window_secs, at most 1440 buckets)
+    ///
+    /// Default: 60 seconds
+    #[schemars(
+        description = "Bucket width in seconds. Defaults to 60. Must not exceed window_secs or yield more than 1440 buckets.",
+        range(min = 1, max = 86400)
+    )]
+    #[serde(default = "default_imbalance_bucket_secs")]
+    pub bucket_secs: u32,
+}
+
+fn default_imbalance_window_secs() -> u32 {
+    3600
+}
+
+fn default_imbalance_bucket_secs() -> u32 {
+    60
+}
+
+/// Bucketed bid/ask imbalance time series for one symbol
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ImbalanceHistory {
+    pub symbol: String,
+    pub window_secs: u32,
+    pub bucket_secs: u32,
+    /// Window start (Unix seconds)
+    pub start_time: i64,
+    /// Window end (Unix seconds)
+    pub end_time: i64,
+    /// Buckets in chronological order
+    pub buckets: Vec<ImbalanceBucket>,
+}
+
+/// Parameters for get_health_trend tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct GetHealthTrendParams {
+    /// Trading pair symbol (e.g., "BTCUSDT")
+    #[schemars(
+        description = "Trading pair (e.g., BTCUSDT). Must be uppercase.",
+        regex(pattern = r"^[A-Z]+$")
+    )]
+    pub symbol: String,
+
+    /// History window in seconds, ending now (60-86400)
+    ///
+    /// Default: 3600 seconds (1 hour)
+    #[schemars(
+        description = "History window in seconds ending now. Defaults to 3600. Range: 60-86400 seconds.",
+        range(min = 60, max = 86400)
+    )]
+    #[serde(default = "default_imbalance_window_secs")]
+    pub window_secs: u32,
+
+    /// Bucket width in seconds (at most window_secs, at most 1440 buckets)
+    ///
+    /// Default: 300 seconds
+    #[schemars(
+        description = "Bucket width in seconds. Defaults to 300. Must not exceed window_secs or yield more than 1440 buckets.",
+        range(min = 1, max = 86400)
+    )]
+    #[serde(default = "default_health_trend_bucket_secs")]
+    pub bucket_secs: u32,
+}
+
+fn default_health_trend_bucket_secs() -> u32 {
+    300
+}
+
+/// Bucketed microstructure health time series with its trend
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct HealthTrend {
+    pub symbol: String,
+    pub window_secs: u32,
+    pub bucket_secs: u32,
+    /// Window start (Unix seconds)
+    pub start_time: i64,
+    /// Window end (Unix seconds)
+    pub end_time: i64,
+    /// Buckets in chronological order
+    pub buckets: Vec<HealthBucket>,
+    /// Least-squares score change per bucket
+    pub slope_per_bucket: f64,
+    /// Fitted score change from the first to the last scored bucket
+    pub score_change: f64,
+    /// improving, stable or degrading
+    pub trend: HealthTrendDirection,
+}
+
+/// Parameters for get_spread_stats tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct GetSpreadStatsParams {
+    /// Trading pair symbol (e.g., "BTCUSDT")
+    #[schemars(
+        description = "Trading pair (e.g., BTCUSDT). Must be uppercase.",
+        regex(pattern = r"^[A-Z]+$")
+    )]
+    pub symbol: String,
+
+    /// Window in seconds, ending now (60-86400)
+    ///
+    /// Default: 3600 seconds (1 hour)
+    #[schemars(
+        description = "Window in seconds ending now. Defaults to 3600. Range: 60-86400 seconds.",
+        range(min = 60, max = 86400)
+    )]
+    #[serde(default = "default_imbalance_window_secs")]
+    pub window_secs: u32,
+}
+
+/// Spread statistics for one symbol over a trailing window
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SpreadStatsReport {
+    pub symbol: String,
+    pub window_secs: u32,
+    /// Window start (Unix seconds)
+    pub start_time: i64,
+    /// Window end (Unix seconds)
+    pub end_time: i64,
+    #[serde(flatten)]
+    pub stats: SpreadStats,
+}
+
+/// Parameters for get_depth_heatmap tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct GetDepthHeatmapParams {
+    /// Trading pair symbol (e.g., "BTCUSDT")
+    #[schemars(
+        description = "Trading pair (e.g., BTCUSDT). Must be uppercase.",
+        regex(pattern = r"^[A-Z]+$")
+    )]
+    pub symbol: String,
+
+    /// Window in seconds, ending now (60-86400)
+    ///
+    /// Default: 3600 seconds (1 hour)
+    #[schemars(
+        description = "Window in seconds ending now. Defaults to 3600. Range: 60-86400 seconds.",
+        range(min = 60, max = 86400)
+    )]
+    #[serde(default = "default_imbalance_window_secs")]
+    pub window_secs: u32,
+
+    /// Price bucket width in basis points of the current mid price (1-1000)
+    ///
+    /// Default: 5 bps
+    #[schemars(
+        description = "Price bucket width in basis points of the current mid price. Defaults to 5. Range: 1-1000.",
+        range(min = 1, max = 1000)
+    )]
+    #[serde(default = "default_price_bucket_bps")]
+    pub price_bucket_bps: u32,
+
+    /// Time bucket width in seconds (at most window_secs, at most 1440 buckets)
+    ///
+    /// Default: 60 seconds
+    #[schemars(
+        description = "Time bucket width in seconds. Defaults to 60. Must not exceed window_secs or yield more than 1440 buckets.",
+        range(min = 1, max = 86400)
+    )]
+    #[serde(default = "default_imbalance_bucket_secs")]
+    pub time_bucket_secs: u32,
+}
+
+fn default_price_bucket_bps() -> u32 {
+    5
+}
+
+/// Resting-liquidity heatmap for one symbol over a trailing window
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DepthHeatmap {
+    pub symbol: String,
+    pub window_secs: u32,
+    pub price_bucket_bps: u32,
+    pub time_bucket_secs: u32,
+    /// Window start (Unix seconds)
+    pub start_time: i64,
+    /// Window end (Unix seconds)
+    pub end_time: i64,
+    #[serde(flatten)]
+    pub grid: DepthHeatmapGrid,
+}
+
 /// Get order flow analysis (bid/ask pressure tracking)
 ///
 /// Calculates order flow dynamics over configurable time window (10-300 seconds)
@@ -203,11 +587,15 @@ fn get_liquidity_vacuums_symbol_examples() -> Vec<&'static str> {
 /// - ask_flow_rate: Ask orders per second (≥ 0.0)
 /// - net_flow: Bid flow - ask flow (can be negative)
 /// - flow_direction: STRONG_BUY, MODERATE_BUY, NEUTRAL, MODERATE_SELL, STRONG_SELL
-/// - cumulative_delta: Running sum of buy volume - sell volume
+/// - cumulative_delta: Running sum of buy volume - sell volume (decayed with
+///   `delta_half_life_secs` when given)
+/// - flow_score: Composite of order-count (40%) and volume (60%) imbalance, -100..100
+/// - absorption_events: Levels absorbing heavy flow while price held
 ///
 /// # Errors
 /// - InsufficientData: Need at least 2 snapshots for window analysis
 /// - InvalidWindowDuration: Window must be 10-300 seconds
+/// - InvalidHalfLife: `delta_half_life_secs` must be positive
 /// - StorageError: RocksDB query failed or timeout exceeded
 ///
 /// # Performance
@@ -224,6 +612,12 @@ pub async fn get_order_flow(
     if !(10..=300).contains(&window_duration) {
         return Err(AnalyticsToolError::InvalidWindowDuration(window_duration));
     }
+    if let Some(half_life) = params
+        .delta_half_life_secs
+        .filter(|half_life| !(half_life.is_finite() && *half_life > 0.0))
+    {
+        return Err(AnalyticsToolError::InvalidHalfLife(half_life));
+    }
 
     info!(
         symbol = %symbol_upper,
@@ -232,12 +626,17 @@ pub async fn get_order_flow(
     );
 
     // Call flow calculation logic
-    let snapshot = calculate_order_flow(&storage, &symbol_upper, window_duration)
-        .await
-        .map_err(|e| {
-            debug!(error = %e, "Order flow calculation failed");
-            AnalyticsToolError::from(e)
-        })?;
+    let snapshot = calculate_order_flow(
+        &storage,
+        &symbol_upper,
+        window_duration,
+        params.delta_half_life_secs,
+    )
+    .await
+    .map_err(|e| {
+        debug!(error = %e, "Order flow calculation failed");
+        AnalyticsToolError::from(e)
+    })?;
 
     debug!(
         symbol = %symbol_upper,
@@ -258,12 +657,13 @@ pub async fn get_order_flow(
 ///
 /// # Arguments
 /// * `trades` - Aggregated trade events from @aggTrade stream
-/// * `params` - Tool parameters (symbol, duration_hours, tick_size)
+/// * `params` - Tool parameters (symbol, duration_hours, tick_size, value_area_pct, max_bins)
 ///
 /// # Returns
 /// VolumeProfile with:
 /// - symbol: Trading pair
-/// - histogram: Volume bins sorted by price level
+/// - histogram: Volume bins sorted by price level (at most `max_bins`)
+/// - original_bin_count: Bin count before merging
 /// - bin_size: Price increment per bin (adaptive or custom)
 /// - point_of_control: Price with highest volume (POC)
 /// - value_area_high/low: 70% volume boundaries (VAH/VAL)
@@ -271,7 +671,7 @@ pub async fn get_order_flow(
 /// - liquidity_vacuums: Low-volume zones (<20% of median)
 ///
 /// # Errors
-/// - InsufficientData: Need at least 1000 trades for reliable profile
+/// - InsufficientData: Fewer than `min_trades` (default 1000) trades
 /// - CalculationFailed: Profile generation failed
 ///
 /// # Performance
@@ -291,6 +691,31 @@ pub async fn get_volume_profile(
         )));
     }
 
+    // Validate value area percentage
+    if let Some(pct) = params.value_area_pct {
+        if !VALUE_AREA_PCT_RANGE.contains(&pct) {
+            return Err(AnalyticsToolError::CalculationFailed(format!(
+                "value_area_pct must be between 0.5 and 0.95, got {}",
+                pct
+            )));
+        }
+    }
+
+    if params.max_bins == Some(0) {
+        return Err(AnalyticsToolError::CalculationFailed(
+            "max_bins must be at least 1".to_string(),
+        ));
+    }
+
+    if let Some(min_trades) = params.min_trades {
+        if min_trades < MIN_TRADES_FLOOR {
+            return Err(AnalyticsToolError::CalculationFailed(format!(
+                "min_trades must be at least {}, got {}",
+                MIN_TRADES_FLOOR, min_trades
+            )));
+        }
+    }
+
     // Parse custom tick size if provided
     let tick_size = params
         .tick_size
@@ -301,21 +726,34 @@ pub async fn get_volume_profile(
         symbol = %symbol_upper,
         duration_hours,
         tick_size = ?tick_size,
+        value_area_pct = ?params.value_area_pct,
+        max_bins = ?params.max_bins,
+        poc_basis = ?params.poc_basis,
+        min_trades = ?params.min_trades,
         trade_count = trades.len(),
         "Generating volume profile"
     );
 
     // Call profile generation logic
-    let profile = generate_volume_profile(&symbol_upper, trades, duration_hours, tick_size)
-        .await
-        .map_err(|e| {
-            debug!(error = %e, "Volume profile generation failed");
-            if e.to_string().contains("insufficient_historical_data") {
-                AnalyticsToolError::InsufficientData(e.to_string())
-            } else {
-                AnalyticsToolError::CalculationFailed(e.to_string())
-            }
-        })?;
+    let profile = generate_volume_profile(
+        &symbol_upper,
+        trades,
+        duration_hours,
+        tick_size,
+        params.value_area_pct,
+        params.max_bins,
+        params.poc_basis,
+        params.min_trades,
+    )
+    .await
+    .map_err(|e| {
+        debug!(error = %e, "Volume profile generation failed");
+        if e.to_string().contains("insufficient_historical_data") {
+            AnalyticsToolError::InsufficientData(e.to_string())
+        } else {
+            AnalyticsToolError::CalculationFailed(e.to_string())
+        }
+    })?;
 
     debug!(
         symbol = %symbol_upper,
@@ -336,7 +774,7 @@ pub async fn get_volume_profile(
 ///
 /// # Arguments
 /// * `storage` - RocksDB snapshot storage
-/// * `symbol` - Trading pair (e.g., "BTCUSDT")
+/// * `params` - Tool parameters (symbol, window_secs, quote stuffing thresholds)
 ///
 /// # Returns
 /// Vector of MarketMicrostructureAnomaly with:
@@ -348,16 +786,39 @@ pub async fn get_volume_profile(
 /// - recommendation: Suggested action
 pub async fn detect_market_anomalies(
     storage: Arc<SnapshotStorage>,
-    symbol: &str,
+    params: DetectAnomaliesParams,
 ) -> Result<Vec<MarketMicrostructureAnomaly>, AnalyticsToolError> {
     use chrono::{Duration, Utc};
 
+    let window_secs = params.window_secs;
+    if !(10..=300).contains(&window_secs) {
+        return Err(AnalyticsToolError::InvalidWindowDuration(window_secs));
+    }
+    let update_rate = params.quote_stuffing_update_rate;
+    if !(update_rate.is_finite() && update_rate > 0.0) {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "quote_stuffing_update_rate must be positive, got {}",
+            update_rate
+        )));
+    }
+    if !(0.0..=1.0).contains(&params.quote_stuffing_fill_rate) {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "quote_stuffing_fill_rate must be between 0 and 1, got {}",
+            params.quote_stuffing_fill_rate
+        )));
+    }
+    let thresholds = QuoteStuffingThresholds {
+        update_rate,
+        fill_rate: params.quote_stuffing_fill_rate,
+    };
+
     let end = Utc::now();
-    let start = end - Duration::seconds(60); // Last 60 seconds
+    let start = end - Duration::seconds(window_secs as i64);
 
-    let snapshots = query_snapshots_in_window(&storage, symbol, start.timestamp(), end.timestamp())
-        .await
-        .map_err(|e| AnalyticsToolError::StorageError(e.to_string()))?;
+    let snapshots =
+        query_snapshots_in_window(&storage, &params.symbol, start.timestamp(), end.timestamp())
+            .await
+            .map_err(|e| AnalyticsToolError::StorageError(e.to_string()))?;
 
     if snapshots.len() < 2 {
         return Ok(Vec::new());
@@ -365,11 +826,9 @@ pub async fn detect_market_anomalies(
 
     let mut anomalies = Vec::new();
 
-    // Calculate update rate for quote stuffing detection
-    let update_rate = snapshots.len() as f64 / 60.0; // updates per second
-
-    // Detect quote stuffing
-    if let Some(anomaly) = detect_quote_stuffing(&snapshots, update_rate) {
+    // Detect quote stuffing (update rate is measured from snapshot timestamps)
+    let fill_rate = estimate_fill_rate(&snapshots);
+    if let Some(anomaly) = detect_quote_stuffing(&snapshots, fill_rate, &thresholds) {
         anomalies.push(anomaly);
     }
 
@@ -390,6 +849,8 @@ pub async fn detect_market_anomalies(
 /// # Arguments
 /// * `storage` - RocksDB snapshot storage
 /// * `symbol` - Trading pair (e.g., "BTCUSDT")
+/// * `live_metrics` - Current book metrics; when provided the liquidity depth score
+///   reflects the live book (see `health` module docs)
 ///
 /// # Returns
 /// MicrostructureHealth with:
@@ -403,6 +864,7 @@ pub async fn detect_market_anomalies(
 pub async fn get_microstructure_health(
     storage: Arc<SnapshotStorage>,
     symbol: &str,
+    live_metrics: Option<&crate::orderbook::types::OrderBookMetrics>,
 ) -> Result<MicrostructureHealth, AnalyticsToolError> {
     use chrono::{Duration, Utc};
 
@@ -421,7 +883,7 @@ pub async fn get_microstructure_health(
     }
 
     // Calculate order flow for flow balance component
-    let flow_snapshot = calculate_order_flow(&storage, symbol, 60)
+    let flow_snapshot = calculate_order_flow(&storage, symbol, 60, None)
         .await
         .map_err(|e| AnalyticsToolError::CalculationFailed(e.to_string()))?;
 
@@ -430,6 +892,7 @@ pub async fn get_microstructure_health(
         &snapshots,
         flow_snapshot.bid_flow_rate,
         flow_snapshot.ask_flow_rate,
+        live_metrics,
     )
     .map_err(|e| AnalyticsToolError::CalculationFailed(e.to_string()))?;
 
@@ -506,8 +969,12 @@ fn merge_adjacent_vacuums(mut vacuums: Vec<LiquidityVacuum>) -> Vec<LiquidityVac
             if !total_range.is_zero() {
                 let current_weight = (current_range / total_range).to_f64().unwrap_or(0.5);
                 let next_weight = (next_range / total_range).to_f64().unwrap_or(0.5);
-                current.volume_deficit_pct =
-                    current.volume_deficit_pct * current_weight + next.volume_deficit_pct * next_weight;
+                let weighted = current.volume_deficit_pct * current_weight
+                    + next.volume_deficit_pct * next_weight;
+                current.volume_deficit_pct = rust_decimal::Decimal::try_from(weighted)
+                    .map_or(weighted, |pct| {
+                        super::profile::round_to_f64(pct, super::profile::DEFICIT_PCT_DECIMALS)
+                    });
             }
             // Sum actual volumes
             current.actual_volume += next.actual_volume;
@@ -587,6 +1054,325 @@ pub async fn get_liquidity_vacuums(
     Ok(merged_vacuums)
 }
 
+/// Get VWAP and TWAP execution benchmarks from stored trades
+///
+/// # Arguments
+/// * `trade_storage` - RocksDB aggTrade storage
+/// * `params` - Tool parameters (symbol, window_secs)
+///
+/// # Errors
+/// - CalculationFailed: window_secs outside 60-86400
+/// - StorageError: Trade query failed
+/// - InsufficientData: No trades recorded in the window
+pub async fn get_execution_benchmarks(
+    trade_storage: Arc<TradeStorage>,
+    params: GetExecutionBenchmarksParams,
+) -> Result<ExecutionBenchmarks, AnalyticsToolError> {
+    let symbol_upper = params.symbol.to_uppercase();
+
+    if !(60..=86_400).contains(&params.window_secs) {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "window_secs must be between 60 and 86400, got {}",
+            params.window_secs
+        )));
+    }
+
+    let window = TradeWindow::trailing(
+        chrono::Utc::now().timestamp_millis(),
+        std::time::Duration::from_secs(params.window_secs as u64),
+    );
+    // Up to a day of trade batches; read and deserialize off the runtime
+    let query_symbol = symbol_upper.clone();
+    let trades = tokio::task::spawn_blocking(move || {
+        trade_storage.query_trades(&query_symbol, window.start_ms, window.end_ms)
+    })
+    .await
+    .map_err(|e| AnalyticsToolError::StorageError(e.to_string()))?
+    .map_err(|e| AnalyticsToolError::StorageError(e.to_string()))?;
+
+    info!(
+        symbol = %symbol_upper,
+        window_secs = params.window_secs,
+        trade_count = trades.len(),
+        "Computing execution benchmarks"
+    );
+
+    let vwap = compute_vwap(&trades, window)?;
+    let twap = compute_twap(&trades, window)?;
+
+    Ok(ExecutionBenchmarks {
+        symbol: symbol_upper,
+        window_secs: params.window_secs,
+        vwap,
+        twap,
+        trade_count: trades.len(),
+        start_time: window.start_ms,
+        end_time: window.end_ms,
+    })
+}
+
+/// Get bid/ask imbalance sampled over time from stored snapshots
+///
+/// # Arguments
+/// * `storage` - RocksDB snapshot storage with historical orderbook data
+/// * `params` - Tool parameters (symbol, window_secs, bucket_secs)
+///
+/// # Errors
+/// - CalculationFailed: window_secs outside 60-86400, bucket_secs of zero, larger
+///   than the window, or producing more than 1440 buckets
+/// - StorageError: Snapshot query failed
+/// - InsufficientData: Fewer than 2 snapshots recorded in the window
+pub async fn get_imbalance_history(
+    storage: Arc<SnapshotStorage>,
+    params: GetImbalanceHistoryParams,
+) -> Result<ImbalanceHistory, AnalyticsToolError> {
+    let symbol_upper = params.symbol.to_uppercase();
+    let window_secs = params.window_secs as i64;
+    let bucket_secs = params.bucket_secs as i64;
+
+    if !(60..=86_400).contains(&params.window_secs) {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "window_secs must be between 60 and 86400, got {}",
+            params.window_secs
+        )));
+    }
+    if !(1..=window_secs).contains(&bucket_secs)
+        || (window_secs + bucket_secs - 1) / bucket_secs > MAX_IMBALANCE_BUCKETS
+    {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "bucket_secs must be between 1 and window_secs ({}) and yield at most {} buckets, got {}",
+            window_secs, MAX_IMBALANCE_BUCKETS, bucket_secs
+        )));
+    }
+
+    let end_time = chrono::Utc::now().timestamp();
+    let start_time = end_time - window_secs;
+    let snapshots = query_snapshots_in_window(&storage, &symbol_upper, start_time, end_time)
+        .await
+        .map_err(|e| AnalyticsToolError::StorageError(e.to_string()))?;
+
+    info!(
+        symbol = %symbol_upper,
+        window_secs,
+        bucket_secs,
+        snapshot_count = snapshots.len(),
+        "Computing imbalance history"
+    );
+
+    let buckets = bucket_imbalance(&snapshots, start_time, end_time, bucket_secs)?;
+
+    Ok(ImbalanceHistory {
+        symbol: symbol_upper,
+        window_secs: params.window_secs,
+        bucket_secs: params.bucket_secs,
+        start_time,
+        end_time,
+        buckets,
+    })
+}
+
+/// Get the microstructure health score per bucket and its trend from stored snapshots
+///
+/// Each bucket is scored with the same composite as `get_microstructure_health`
+/// (without live metrics); the trend is improving or degrading when the fitted
+/// score change across the window reaches 5 points.
+///
+/// # Arguments
+/// * `storage` - RocksDB snapshot storage with historical orderbook data
+/// * `params` - Tool parameters (symbol, window_secs, bucket_secs)
+///
+/// # Errors
+/// - CalculationFailed: window_secs outside 60-86400, bucket_secs of zero, larger
+///   than the window, or producing more than 1440 buckets
+/// - StorageError: Snapshot query failed
+/// - InsufficientData: Fewer than 2 buckets with at least 2 snapshots each
+pub async fn get_health_trend(
+    storage: Arc<SnapshotStorage>,
+    params: GetHealthTrendParams,
+) -> Result<HealthTrend, AnalyticsToolError> {
+    let symbol_upper = params.symbol.to_uppercase();
+    let window_secs = params.window_secs as i64;
+    let bucket_secs = params.bucket_secs as i64;
+
+    if !(60..=86_400).contains(&params.window_secs) {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "window_secs must be between 60 and 86400, got {}",
+            params.window_secs
+        )));
+    }
+    if !(1..=window_secs).contains(&bucket_secs)
+        || (window_secs + bucket_secs - 1) / bucket_secs > MAX_HEALTH_TREND_BUCKETS
+    {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "bucket_secs must be between 1 and window_secs ({}) and yield at most {} buckets, got {}",
+            window_secs, MAX_HEALTH_TREND_BUCKETS, bucket_secs
+        )));
+    }
+
+    let end_time = chrono::Utc::now().timestamp();
+    let start_time = end_time - window_secs;
+    let snapshots = query_snapshots_in_window(&storage, &symbol_upper, start_time, end_time)
+        .await
+        .map_err(|e| AnalyticsToolError::StorageError(e.to_string()))?;
+
+    info!(
+        symbol = %symbol_upper,
+        window_secs,
+        bucket_secs,
+        snapshot_count = snapshots.len(),
+        "Computing health trend"
+    );
+
+    let buckets = bucket_health(&symbol_upper, &snapshots, start_time, end_time, bucket_secs)?;
+    let fit = fit_health_trend(&buckets).ok_or_else(|| {
+        AnalyticsToolError::InsufficientData("Need at least 2 scored buckets".to_string())
+    })?;
+
+    Ok(HealthTrend {
+        symbol: symbol_upper,
+        window_secs: params.window_secs,
+        bucket_secs: params.bucket_secs,
+        start_time,
+        end_time,
+        buckets,
+        slope_per_bucket: fit.slope_per_bucket,
+        score_change: fit.score_change,
+        trend: fit.direction,
+    })
+}
+
+/// Get spread mean, standard deviation and range from stored snapshots
+///
+/// # Arguments
+/// * `storage` - RocksDB snapshot storage with historical orderbook data
+/// * `params` - Tool parameters (symbol, window_secs)
+///
+/// # Errors
+/// - CalculationFailed: window_secs outside 60-86400
+/// - StorageError: Snapshot query failed
+/// - InsufficientData: Fewer than 2 snapshots with both sides in the window
+pub async fn get_spread_stats(
+    storage: Arc<SnapshotStorage>,
+    params: GetSpreadStatsParams,
+) -> Result<SpreadStatsReport, AnalyticsToolError> {
+    let symbol_upper = params.symbol.to_uppercase();
+
+    if !(60..=86_400).contains(&params.window_secs) {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "window_secs must be between 60 and 86400, got {}",
+            params.window_secs
+        )));
+    }
+
+    let end_time = chrono::Utc::now().timestamp();
+    let start_time = end_time - params.window_secs as i64;
+    let snapshots = query_snapshots_in_window(&storage, &symbol_upper, start_time, end_time)
+        .await
+        .map_err(|e| AnalyticsToolError::StorageError(e.to_string()))?;
+
+    debug!(
+        symbol = %symbol_upper,
+        window_secs = params.window_secs,
+        snapshot_count = snapshots.len(),
+        "Computing spread stats"
+    );
+
+    let stats = spread_stats(&snapshots)
+        .filter(|stats| stats.sample_count >= MIN_SPREAD_SNAPSHOTS)
+        .ok_or_else(|| {
+            AnalyticsToolError::InsufficientData(format!(
+                "Need at least {} snapshots with both sides between {} and {}, found {}",
+                MIN_SPREAD_SNAPSHOTS,
+                start_time,
+                end_time,
+                snapshots.len()
+            ))
+        })?;
+
+    Ok(SpreadStatsReport {
+        symbol: symbol_upper,
+        window_secs: params.window_secs,
+        start_time,
+        end_time,
+        stats,
+    })
+}
+
+/// Get resting liquidity per price bucket and time bucket from stored snapshots
+///
+/// # Arguments
+/// * `storage` - RocksDB snapshot storage with historical orderbook data
+/// * `params` - Tool parameters (symbol, window_secs, price_bucket_bps, time_bucket_secs)
+///
+/// # Errors
+/// - CalculationFailed: window_secs outside 60-86400, price_bucket_bps outside
+///   1-1000, time_bucket_secs of zero, larger than the window or producing more
+///   than 1440 buckets, or a price range wider than 500 price buckets
+/// - StorageError: Snapshot query failed
+/// - InsufficientData: No snapshot with both sides in the window
+pub async fn get_depth_heatmap(
+    storage: Arc<SnapshotStorage>,
+    params: GetDepthHeatmapParams,
+) -> Result<DepthHeatmap, AnalyticsToolError> {
+    let symbol_upper = params.symbol.to_uppercase();
+    let window_secs = params.window_secs as i64;
+    let time_bucket_secs = params.time_bucket_secs as i64;
+
+    if !(60..=86_400).contains(&params.window_secs) {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "window_secs must be between 60 and 86400, got {}",
+            params.window_secs
+        )));
+    }
+    if !(1..=1_000).contains(&params.price_bucket_bps) {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "price_bucket_bps must be between 1 and 1000, got {}",
+            params.price_bucket_bps
+        )));
+    }
+    if !(1..=window_secs).contains(&time_bucket_secs)
+        || (window_secs + time_bucket_secs - 1) / time_bucket_secs > MAX_HEATMAP_TIME_BUCKETS
+    {
+        return Err(AnalyticsToolError::CalculationFailed(format!(
+            "time_bucket_secs must be between 1 and window_secs ({}) and yield at most {} buckets, got {}",
+            window_secs, MAX_HEATMAP_TIME_BUCKETS, time_bucket_secs
+        )));
+    }
+
+    let end_time = chrono::Utc::now().timestamp();
+    let start_time = end_time - window_secs;
+    let snapshots = query_snapshots_in_window(&storage, &symbol_upper, start_time, end_time)
+        .await
+        .map_err(|e| AnalyticsToolError::StorageError(e.to_string()))?;
+
+    debug!(
+        symbol = %symbol_upper,
+        window_secs,
+        time_bucket_secs,
+        price_bucket_bps = params.price_bucket_bps,
+        snapshot_count = snapshots.len(),
+        "Computing depth heatmap"
+    );
+
+    let grid = build_depth_heatmap(
+        &snapshots,
+        start_time,
+        end_time,
+        time_bucket_secs,
+        params.price_bucket_bps,
+    )?;
+
+    Ok(DepthHeatmap {
+        symbol: symbol_upper,
+        window_secs: params.window_secs,
+        price_bucket_bps: params.price_bucket_bps,
+        time_bucket_secs: params.time_bucket_secs,
+        start_time,
+        end_time,
+        grid,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -605,6 +1391,25 @@ mod tests {
         assert!(err.to_string().contains("between 10 and 300"));
     }
 
+    #[test]
+    fn test_detect_anomalies_params_defaults() {
+        let params: DetectAnomaliesParams =
+            serde_json::from_value(serde_json::json!({"symbol": "BTCUSDT"})).unwrap();
+        assert_eq!(params.window_secs, 60);
+        assert_eq!(params.quote_stuffing_update_rate, 500.0);
+        assert_eq!(params.quote_stuffing_fill_rate, 0.10);
+
+        let custom: DetectAnomaliesParams = serde_json::from_value(serde_json::json!({
+            "symbol": "BTCUSDT",
+            "window_secs": 120,
+            "quote_stuffing_update_rate": 50.0
+        }))
+        .unwrap();
+        assert_eq!(custom.window_secs, 120);
+        assert_eq!(custom.quote_stuffing_update_rate, 50.0);
+        assert_eq!(custom.quote_stuffing_fill_rate, 0.10);
+    }
+
     #[test]
     fn test_get_order_flow_params_schema() {
         // Verify schema can be generated (compile-time check)
@@ -2,13 +2,49 @@
 // Handles RocksDB operations for storing and querying aggregate trade batches
 
 use anyhow::{Context, Result};
-use rocksdb::{WriteBatch, DB};
+use rocksdb::{IteratorMode, WriteBatch, DB};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
 
 /// RocksDB key prefix for trade batches
 const TRADES_KEY_PREFIX: &str = "trades:";
 
+/// Widest range accepted by [`TradeStorage::query_trades`] (7 days)
+///
+/// Matches the default retention window. Ranges are checked in milliseconds,
+/// so 168h plus a few minutes is rejected too.
+/// Use [`TradeStorage::query_trades_page`] with a `limit` to bound memory for
+/// long ranges on busy symbols.
+pub const MAX_TRADE_QUERY_RANGE_MS: i64 = 168 * 3600 * 1000;
+
+/// When buffered stream trades are written to storage
+///
+/// A symbol's buffer is flushed when it reaches `max_trades` trades, and every
+/// buffer is flushed each `interval`, whichever comes first. Configured from
+/// `ANALYTICS_TRADE_FLUSH_*` (see [`AnalyticsConfig`](crate::config::AnalyticsConfig)).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradeFlushPolicy {
+    pub interval: Duration,
+    pub max_trades: usize,
+}
+
+impl Default for TradeFlushPolicy {
+    fn default() -> Self {
+        use crate::config::analytics::{
+            DEFAULT_TRADE_FLUSH_INTERVAL_MS, DEFAULT_TRADE_FLUSH_MAX_TRADES,
+        };
+
+        Self {
+            interval: Duration::from_millis(DEFAULT_TRADE_FLUSH_INTERVAL_MS),
+            max_trades: DEFAULT_TRADE_FLUSH_MAX_TRADES,
+        }
+    }
+}
+
 /// Simplified aggregate trade for persistence (minimal fields needed for analytics)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AggTrade {
@@ -40,6 +76,27 @@ impl From<&super::trade_stream::AggTrade> for AggTrade {
     }
 }
 
+/// Resume position for [`TradeStorage::query_trades_page`]
+///
+/// Trades are stored in batches keyed by flush time, so a page boundary is a
+/// batch key plus the number of that batch's trades already returned (counted
+/// in the page's direction).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradeCursor {
+    pub batch_timestamp: i64,
+    pub offset: usize,
+}
+
+/// One page of stored trades
+#[derive(Debug, Clone)]
+pub struct TradePage {
+    pub trades: Vec<AggTrade>,
+    /// Where the next page starts; None once the range is exhausted (a page
+    /// that ends exactly on the last trade may still return a cursor to an
+    /// empty page)
+    pub next: Option<TradeCursor>,
+}
+
 /// Trade persistence storage
 pub struct TradeStorage {
     db: Arc<DB>,
@@ -77,55 +134,118 @@ impl TradeStorage {
 
     /// Query trades for a symbol within a time range
     ///
-    /// Returns all trades where start_time <= timestamp <= end_time
+    /// Returns all trades in batches stamped start_time <= timestamp <= end_time,
+    /// oldest first. See [`query_trades_page`](Self::query_trades_page) for
+    /// range limits and bounded reads.
     pub fn query_trades(
         &self,
         symbol: &str,
         start_time: i64,
         end_time: i64,
     ) -> Result<Vec<AggTrade>> {
+        Ok(self
+            .query_trades_page(symbol, start_time, end_time, None, false, None)?
+            .trades)
+    }
+
+    /// Query at most `limit` trades for a symbol within a time range
+    ///
+    /// With `reverse` the newest trades are returned first, so `limit` keeps the
+    /// most recent ones; otherwise the oldest come first. To page, pass the
+    /// returned [`TradePage::next`] back as `after` with the same range and
+    /// direction; every trade is returned exactly once across pages. Iteration
+    /// stops as soon as `limit` trades are collected.
+    ///
+    /// # Errors
+    /// Fails if `end_time < start_time` or the range is wider than
+    /// [`MAX_TRADE_QUERY_RANGE_MS`]
+    pub fn query_trades_page(
+        &self,
+        symbol: &str,
+        start_time: i64,
+        end_time: i64,
+        limit: Option<usize>,
+        reverse: bool,
+        after: Option<TradeCursor>,
+    ) -> Result<TradePage> {
         // Validate query parameters
         if end_time < start_time {
             anyhow::bail!("end_time must be >= start_time");
         }
 
-        let window_hours = (end_time - start_time) / (3600 * 1000);
-        if window_hours > 168 {
-            anyhow::bail!("Query window exceeds maximum 7 days (168 hours)");
+        if end_time - start_time > MAX_TRADE_QUERY_RANGE_MS {
+            anyhow::bail!(
+                "Query window of {}ms exceeds maximum 7 days ({}ms)",
+                end_time - start_time,
+                MAX_TRADE_QUERY_RANGE_MS
+            );
         }
 
+        let limit = limit.unwrap_or(usize::MAX);
         let prefix = format!("{}{}:", TRADES_KEY_PREFIX, symbol);
         tracing::info!(
-            "Querying trades: symbol={} prefix='{}' start_time={} end_time={} window_hours={}",
+            "Querying trades: symbol={} prefix='{}' start_time={} end_time={} limit={} reverse={}",
             symbol,
             prefix,
             start_time,
             end_time,
-            window_hours
+            limit,
+            reverse
         );
         let mut all_trades = Vec::new();
-
-        let iter = self.db.prefix_iterator(prefix.as_bytes());
+        let mut next = None;
+
+        let direction = if reverse {
+            rocksdb::Direction::Reverse
+        } else {
+            rocksdb::Direction::Forward
+        };
+        let seek_key = match after {
+            Some(cursor) => format!("{}{}", prefix, cursor.batch_timestamp),
+            None if reverse => format!("{}{}", prefix, end_time),
+            None => format!("{}{}", prefix, start_time),
+        };
+        let iter = self
+            .db
+            .iterator(IteratorMode::From(seek_key.as_bytes(), direction));
         let mut key_count = 0;
 
         for item in iter {
             let (key, value) = item.context("Failed to read from RocksDB iterator")?;
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
             key_count += 1;
 
             // Parse timestamp from key (format: "trades:SYMBOL:TIMESTAMP")
-            if let Some(timestamp) = parse_timestamp_from_key(&key) {
-                // Filter by time range
-                if timestamp >= start_time && timestamp <= end_time {
-                    // Deserialize batch
-                    let batch: Vec<AggTrade> = rmp_serde::from_slice(&value)
-                        .context("Failed to deserialize trade batch from MessagePack")?;
-                    all_trades.extend(batch);
-                }
+            let Some(timestamp) = parse_timestamp_from_key(&key) else {
+                continue;
+            };
 
-                // Early termination if beyond end_time (keys are ordered chronologically)
-                if timestamp > end_time {
-                    break;
-                }
+            // Keys are ordered chronologically, so leaving the range ends the scan
+            if timestamp > end_time || timestamp < start_time {
+                break;
+            }
+
+            let mut batch: Vec<AggTrade> = rmp_serde::from_slice(&value)
+                .context("Failed to deserialize trade batch from MessagePack")?;
+            if reverse {
+                batch.reverse();
+            }
+            let skip = match after {
+                Some(cursor) if cursor.batch_timestamp == timestamp => cursor.offset,
+                _ => 0,
+            };
+            let remaining = limit - all_trades.len();
+            let taken = batch.len().saturating_sub(skip).min(remaining);
+            all_trades.extend(batch.into_iter().skip(skip).take(taken));
+
+            if all_trades.len() >= limit {
+                next = Some(TradeCursor {
+                    batch_timestamp: timestamp,
+                    offset: skip + taken,
+                });
+                break;
             }
         }
 
@@ -135,7 +255,85 @@ impl TradeStorage {
             key_count,
             symbol
         );
-        Ok(all_trades)
+        Ok(TradePage {
+            trades: all_trades,
+            next,
+        })
+    }
+
+    /// Export trades for a symbol within a time range as CSV
+    ///
+    /// Columns: `timestamp,trade_id,price,quantity,buyer_is_maker`
+    /// Streams one stored batch at a time, so memory use is bounded by the
+    /// largest 1-second batch rather than the export window.
+    ///
+    /// # Returns
+    /// Number of trade rows written (excluding the header)
+    pub fn export_csv<W: Write>(
+        &self,
+        symbol: &str,
+        start_time: i64,
+        end_time: i64,
+        mut writer: W,
+    ) -> Result<usize> {
+        if end_time < start_time {
+            anyhow::bail!("end_time must be >= start_time");
+        }
+
+        writer
+            .write_all(b"timestamp,trade_id,price,quantity,buyer_is_maker\n")
+            .context("Failed to write CSV header")?;
+
+        let prefix = format!("{}{}:", TRADES_KEY_PREFIX, symbol);
+        let mut rows = 0;
+
+        for item in self.db.prefix_iterator(prefix.as_bytes()) {
+            let (key, value) = item.context("Failed to read from RocksDB iterator")?;
+
+            if !key.starts_with(prefix.as_bytes()) {
+                break;
+            }
+
+            let Some(timestamp) = parse_timestamp_from_key(&key) else {
+                continue;
+            };
+
+            if timestamp > end_time {
+                break;
+            }
+            if timestamp < start_time {
+                continue;
+            }
+
+            let batch: Vec<AggTrade> = rmp_serde::from_slice(&value)
+                .context("Failed to deserialize trade batch from MessagePack")?;
+
+            for trade in batch {
+                writeln!(
+                    writer,
+                    "{},{},{},{},{}",
+                    trade.timestamp,
+                    trade.trade_id,
+                    trade.price,
+                    trade.quantity,
+                    trade.buyer_is_maker
+                )
+                .context("Failed to write CSV row")?;
+                rows += 1;
+            }
+        }
+
+        writer.flush().context("Failed to flush CSV writer")?;
+
+        tracing::info!(
+            symbol = %symbol,
+            start_time,
+            end_time,
+            rows,
+            "Exported trades to CSV"
+        );
+
+        Ok(rows)
     }
 
     /// Delete trades older than the retention period (7 days)
@@ -186,6 +384,102 @@ fn parse_timestamp_from_key(key: &[u8]) -> Option<i64> {
     }
 }
 
+/// Spawn the task that batches streamed trades into [`TradeStorage`]
+///
+/// Trades are buffered per symbol (upper-cased) and written as one batch per
+/// symbol according to `flush` (see [`TradeFlushPolicy`]). On shutdown, trades
+/// still queued in `trade_rx` are drained into the buffers and flushed one
+/// final time before the task exits, so a restart loses nothing that had
+/// already been received.
+pub fn spawn_trade_persistence_task(
+    storage: Arc<TradeStorage>,
+    symbols: &[String],
+    mut trade_rx: mpsc::UnboundedReceiver<super::trade_stream::AggTrade>,
+    mut shutdown_rx: broadcast::Receiver<()>,
+    flush: TradeFlushPolicy,
+) -> tokio::task::JoinHandle<()> {
+    let mut buffers: HashMap<String, TradeBuffer> = symbols
+        .iter()
+        .map(|symbol| (symbol.to_uppercase(), TradeBuffer::default()))
+        .collect();
+    let max_trades = flush.max_trades.max(1);
+
+    tokio::spawn(async move {
+        let mut timer =
+            tokio::time::interval_at(tokio::time::Instant::now() + flush.interval, flush.interval);
+
+        loop {
+            tokio::select! {
+                Some(trade) = trade_rx.recv() => {
+                    let symbol = trade.symbol.to_uppercase();
+                    let buffer = buffers.entry(symbol.clone()).or_default();
+                    buffer.trades.push((&trade).into());
+                    if buffer.trades.len() >= max_trades {
+                        buffer.flush(&storage, &symbol);
+                    }
+                }
+                _ = timer.tick() => {
+                    flush_trade_buffers(&storage, &mut buffers);
+                }
+                _ = shutdown_rx.recv() => {
+                    tracing::info!("Shutting down trade persistence task, flushing buffered trades");
+                    while let Ok(trade) = trade_rx.try_recv() {
+                        buffers
+                            .entry(trade.symbol.to_uppercase())
+                            .or_default()
+                            .trades
+                            .push((&trade).into());
+                    }
+                    flush_trade_buffers(&storage, &mut buffers);
+                    break;
+                }
+            }
+        }
+    })
+}
+
+/// Trades of one symbol waiting to be written
+#[derive(Default)]
+struct TradeBuffer {
+    trades: Vec<AggTrade>,
+    /// Key timestamp of the last batch written for the symbol
+    last_batch_ms: i64,
+}
+
+impl TradeBuffer {
+    /// Write the buffered trades as one batch keyed at the current time
+    ///
+    /// Size-triggered flushes can happen more than once per millisecond, so the
+    /// key timestamp is bumped past the previous batch's instead of overwriting it.
+    fn flush(&mut self, storage: &TradeStorage, symbol: &str) {
+        if self.trades.is_empty() {
+            return;
+        }
+        let batch_ms = chrono::Utc::now()
+            .timestamp_millis()
+            .max(self.last_batch_ms + 1);
+        let count = self.trades.len();
+        if let Err(e) = storage.store_batch(symbol, batch_ms, std::mem::take(&mut self.trades)) {
+            tracing::error!("Failed to store {} trades: {}", symbol, e);
+        } else {
+            self.last_batch_ms = batch_ms;
+            tracing::info!(
+                "Stored {} trades for {} at timestamp {}",
+                count,
+                symbol,
+                batch_ms
+            );
+        }
+    }
+}
+
+/// Flush every non-empty buffer
+fn flush_trade_buffers(storage: &TradeStorage, buffers: &mut HashMap<String, TradeBuffer>) {
+    for (symbol, buffer) in buffers.iter_mut() {
+        buffer.flush(storage, symbol);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -200,6 +494,96 @@ mod tests {
         assert_eq!(parse_timestamp_from_key(invalid_key), None);
     }
 
+    #[tokio::test]
+    async fn test_shutdown_flushes_buffered_trades() {
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(DB::open_default(temp_dir.path()).unwrap());
+        let storage = Arc::new(TradeStorage::new(db));
+        let (trade_tx, trade_rx) = mpsc::unbounded_channel();
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+
+        // Neither the timer nor the size threshold fires, so only the shutdown flush can store
+        let handle = spawn_trade_persistence_task(
+            Arc::clone(&storage),
+            &["BTCUSDT".to_string()],
+            trade_rx,
+            shutdown_rx,
+            TradeFlushPolicy {
+                interval: Duration::from_secs(3600),
+                max_trades: 100,
+            },
+        );
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        trade_tx.send(stream_trade("btcusdt", 42, now_ms)).unwrap();
+        shutdown_tx.send(()).unwrap();
+        handle.await.unwrap();
+
+        let stored = storage
+            .query_trades("BTCUSDT", now_ms - 60_000, now_ms + 60_000)
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+        assert_eq!(stored[0].trade_id, 42);
+    }
+
+    #[tokio::test]
+    async fn test_full_buffer_flushes_before_timer() {
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(DB::open_default(temp_dir.path()).unwrap());
+        let storage = Arc::new(TradeStorage::new(db));
+        let (trade_tx, trade_rx) = mpsc::unbounded_channel();
+        let (shutdown_tx, shutdown_rx) = broadcast::channel(1);
+
+        let handle = spawn_trade_persistence_task(
+            Arc::clone(&storage),
+            &["BTCUSDT".to_string()],
+            trade_rx,
+            shutdown_rx,
+            TradeFlushPolicy {
+                interval: Duration::from_secs(3600),
+                max_trades: 3,
+            },
+        );
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let stored = || {
+            storage
+                .query_trades("BTCUSDT", now_ms - 60_000, now_ms + 60_000)
+                .unwrap()
+        };
+
+        // Two full batches back to back get distinct keys; the 7th trade stays buffered
+        for id in 0..7 {
+            trade_tx.send(stream_trade("BTCUSDT", id, now_ms)).unwrap();
+        }
+        for _ in 0..100 {
+            if stored().len() == 6 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(stored().len(), 6);
+
+        shutdown_tx.send(()).unwrap();
+        handle.await.unwrap();
+        let ids: Vec<i64> = stored().iter().map(|t| t.trade_id).collect();
+        assert_eq!(ids, (0..7).collect::<Vec<_>>());
+    }
+
+    fn stream_trade(symbol: &str, id: u64, time_ms: i64) -> super::super::trade_stream::AggTrade {
+        super::super::trade_stream::AggTrade {
+            event_type: "aggTrade".to_string(),
+            event_time: time_ms,
+            symbol: symbol.to_string(),
+            agg_trade_id: id,
+            price: "67650.00".to_string(),
+            quantity: "0.5".to_string(),
+            first_trade_id: id,
+            last_trade_id: id,
+            trade_time: time_ms,
+            is_buyer_maker: false,
+            is_best_match: true,
+        }
+    }
+
     #[test]
     fn test_store_and_query_trades() {
         let temp_dir = tempdir().unwrap();
@@ -249,4 +633,120 @@ mod tests {
         assert_eq!(queried[0].price, "43250.0");
         assert_eq!(queried[99].price, "43251.99");
     }
+
+    #[test]
+    fn test_query_rejects_invalid_ranges() {
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(DB::open_default(temp_dir.path()).unwrap());
+        let storage = TradeStorage::new(db);
+
+        let base_timestamp = 1760903627000;
+        assert!(storage
+            .query_trades("BTCUSDT", base_timestamp, base_timestamp - 1)
+            .is_err());
+        assert!(storage
+            .query_trades(
+                "BTCUSDT",
+                base_timestamp,
+                base_timestamp + MAX_TRADE_QUERY_RANGE_MS + 60_000
+            )
+            .is_err());
+        assert!(storage
+            .query_trades(
+                "BTCUSDT",
+                base_timestamp,
+                base_timestamp + MAX_TRADE_QUERY_RANGE_MS
+            )
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_query_page_honors_limit_and_order() {
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(DB::open_default(temp_dir.path()).unwrap());
+        let storage = TradeStorage::new(db);
+
+        // Three 1-second batches of 3 trades each, trade ids 0..9
+        let base_timestamp = 1760903627000;
+        for batch in 0..3 {
+            let trades = (0..3)
+                .map(|i| AggTrade {
+                    price: "43250.0".to_string(),
+                    quantity: "0.5".to_string(),
+                    timestamp: base_timestamp + batch * 1000 + i,
+                    trade_id: batch * 3 + i,
+                    buyer_is_maker: false,
+                })
+                .collect();
+            storage
+                .store_batch("BTCUSDT", base_timestamp + batch * 1000, trades)
+                .unwrap();
+        }
+        let ids = |trades: &[AggTrade]| -> Vec<i64> { trades.iter().map(|t| t.trade_id).collect() };
+        let end = base_timestamp + 3600 * 1000;
+
+        let oldest = storage
+            .query_trades_page("BTCUSDT", base_timestamp, end, Some(4), false, None)
+            .unwrap();
+        assert_eq!(ids(&oldest.trades), vec![0, 1, 2, 3]);
+
+        let newest = storage
+            .query_trades_page("BTCUSDT", base_timestamp, end, Some(4), true, None)
+            .unwrap();
+        assert_eq!(ids(&newest.trades), vec![8, 7, 6, 5]);
+
+        // Walking the cursor in either direction returns every trade exactly once
+        for (reverse, expected) in [
+            (false, (0..9).collect::<Vec<i64>>()),
+            (true, (0..9).rev().collect()),
+        ] {
+            let mut seen = Vec::new();
+            let mut after = None;
+            loop {
+                let page = storage
+                    .query_trades_page("BTCUSDT", base_timestamp, end, Some(4), reverse, after)
+                    .unwrap();
+                seen.extend(ids(&page.trades));
+                match page.next {
+                    Some(cursor) => after = Some(cursor),
+                    None => break,
+                }
+            }
+            assert_eq!(seen, expected);
+        }
+    }
+
+    #[test]
+    fn test_export_csv() {
+        let temp_dir = tempdir().unwrap();
+        let db = Arc::new(DB::open_default(temp_dir.path()).unwrap());
+        let storage = TradeStorage::new(db);
+
+        let base_timestamp = 1760903627000;
+        let trades: Vec<AggTrade> = (0..3)
+            .map(|i| AggTrade {
+                price: format!("43250.{}", i),
+                quantity: "0.5".to_string(),
+                timestamp: base_timestamp + i,
+                trade_id: i,
+                buyer_is_maker: i == 1,
+            })
+            .collect();
+        storage
+            .store_batch("BTCUSDT", base_timestamp, trades)
+            .unwrap();
+
+        let mut out = Vec::new();
+        let rows = storage
+            .export_csv("BTCUSDT", base_timestamp, base_timestamp + 1000, &mut out)
+            .unwrap();
+        assert_eq!(rows, 3);
+
+        let csv = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "timestamp,trade_id,price,quantity,buyer_is_maker");
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[2], "1760903627001,1,43250.1,0.5,true");
+    }
 }
@@ -1,11 +1,24 @@
 //! Binance @aggTrade WebSocket stream for volume profile data
 //!
-//! Connects to wss://stream.binance.com:9443/ws/<symbol>@aggTrade for real-time
+//! Connects to `<symbol>@aggTrade` on the spot (wss://stream.binance.com:9443) or
+//! USDⓈ-M futures (wss://fstream.binance.com) stream host for real-time
 //! aggregated trade data. Supports exponential backoff reconnection (1s, 2s, 4s, 8s, max 60s).
+//!
+//! Aggregate trade IDs are consecutive, so a jump in `a` means trades were missed
+//! (typically while reconnecting). With a REST client attached the handler
+//! backfills the missing IDs from the market's `aggTrades?fromId=` endpoint before passing
+//! on the live trade, keeping persisted volume complete.
 
+use crate::binance::market::Market;
+use crate::binance::parse_errors::ParseErrorSampler;
+use crate::binance::types::AggTradeResponse;
+use crate::binance::BinanceClient;
+use crate::error::McpError;
 use anyhow::{Context, Result};
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::ops::RangeInclusive;
 use tokio::time::{sleep, Duration};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{debug, error, info, warn};
@@ -75,11 +88,52 @@ pub struct AggTrade {
     pub is_best_match: bool,
 }
 
+/// Trades per `aggTrades` backfill request (Binance maximum)
+const BACKFILL_PAGE_SIZE: u32 = 1000;
+
+/// Largest gap that is backfilled; older missed trades are skipped with a warning
+pub const MAX_BACKFILL_TRADES: u64 = 10_000;
+
+impl AggTrade {
+    /// Convert a REST `aggTrades` entry into a stream event for `symbol`
+    pub fn from_rest(symbol: &str, trade: &AggTradeResponse) -> Self {
+        Self {
+            event_type: "aggTrade".to_string(),
+            event_time: trade.time,
+            symbol: symbol.to_string(),
+            agg_trade_id: trade.agg_trade_id as u64,
+            price: trade.price.clone(),
+            quantity: trade.qty.clone(),
+            first_trade_id: trade.first_trade_id as u64,
+            last_trade_id: trade.last_trade_id as u64,
+            trade_time: trade.time,
+            is_buyer_maker: trade.is_buyer_maker,
+            is_best_match: trade.is_best_match,
+        }
+    }
+}
+
+/// Aggregate trade IDs missing between the last delivered trade and `next_id`
+///
+/// None when nothing was delivered yet or `next_id` directly follows `last_id`.
+pub fn gap_range(last_id: Option<u64>, next_id: u64) -> Option<RangeInclusive<u64>> {
+    let last_id = last_id?;
+    (next_id > last_id + 1).then(|| last_id + 1..=next_id - 1)
+}
+
 /// Trade stream handler for volume profile collection
 pub struct TradeStreamHandler {
     symbol: String,
+    /// Selects the stream host and the REST backfill endpoint
+    market: Market,
     url: String,
     trade_buffer: Vec<AggTrade>,
+    /// Last aggregate trade ID passed on (live or backfilled)
+    last_agg_trade_id: Option<u64>,
+    /// REST client used to backfill gaps (gaps are only logged without one)
+    client: Option<BinanceClient>,
+    /// Samples aggTrade parse failures across reconnects
+    parse_errors: ParseErrorSampler,
 }
 
 impl TradeStreamHandler {
@@ -87,23 +141,33 @@ impl TradeStreamHandler {
     ///
     /// # Arguments
     /// * `symbol` - Trading pair (e.g., "BTCUSDT")
+    /// * `market` - Spot or USDⓈ-M futures stream
     ///
     /// # Example
     /// ```no_run
-    /// let handler = TradeStreamHandler::new("BTCUSDT");
+    /// let handler = TradeStreamHandler::new("BTCUSDT", Market::Spot);
     /// handler.connect_with_backoff().await?;
     /// ```
-    pub fn new(symbol: &str) -> Self {
-        let symbol_lower = symbol.to_lowercase();
-        let url = format!("wss://stream.binance.com:9443/ws/{}@aggTrade", symbol_lower);
+    pub fn new(symbol: &str, market: Market) -> Self {
+        let url = market.stream_url(&format!("{}@aggTrade", symbol.to_lowercase()));
 
         Self {
             symbol: symbol.to_uppercase(),
+            market,
             url,
             trade_buffer: Vec::new(),
+            last_agg_trade_id: None,
+            client: None,
+            parse_errors: ParseErrorSampler::new(),
         }
     }
 
+    /// Backfill trade ID gaps through `client` instead of only logging them
+    pub fn with_backfill(mut self, client: BinanceClient) -> Self {
+        self.client = Some(client);
+        self
+    }
+
     /// Connect to WebSocket stream with exponential backoff
     ///
     /// Retry delays: 1s, 2s, 4s, 8s, 16s, 32s, capped at 60s max
@@ -173,14 +237,30 @@ impl TradeStreamHandler {
                                 "Received aggTrade event"
                             );
 
+                            // Already delivered (e.g. by a backfill)
+                            if self
+                                .last_agg_trade_id
+                                .is_some_and(|last| trade.agg_trade_id <= last)
+                            {
+                                continue;
+                            }
+
+                            if let Some(gap) = gap_range(self.last_agg_trade_id, trade.agg_trade_id)
+                            {
+                                self.handle_gap(gap, trade_tx).await?;
+                            }
+
                             // Send to processing channel
+                            let agg_trade_id = trade.agg_trade_id;
                             if let Err(e) = trade_tx.send(trade) {
                                 error!(error = %e, "Failed to send trade to channel");
                                 return Err(e.into());
                             }
+                            self.last_agg_trade_id = Some(agg_trade_id);
                         }
                         Err(e) => {
-                            warn!(error = %e, text = %text, "Failed to parse aggTrade event");
+                            let stream = format!("{}@aggTrade", self.symbol.to_lowercase());
+                            self.parse_errors.log(&stream, &e, &text);
                         }
                     }
                 }
@@ -208,21 +288,156 @@ impl TradeStreamHandler {
         Ok(())
     }
 
+    /// Backfill (or, without a client, report) the trades missing in `gap`
+    ///
+    /// Backfill failures are logged and the live stream resumes; only a closed
+    /// trade channel is an error.
+    async fn handle_gap(
+        &mut self,
+        gap: RangeInclusive<u64>,
+        trade_tx: &tokio::sync::mpsc::UnboundedSender<AggTrade>,
+    ) -> Result<()> {
+        let missed = gap.end() - gap.start() + 1;
+        let Some(client) = self.client.clone() else {
+            warn!(
+                symbol = %self.symbol,
+                from_id = gap.start(),
+                to_id = gap.end(),
+                missed,
+                "Trade stream gap detected, no backfill client configured"
+            );
+            return Ok(());
+        };
+
+        // Only the most recent trades of a very large gap are backfilled
+        let gap = if missed > MAX_BACKFILL_TRADES {
+            warn!(
+                symbol = %self.symbol,
+                missed,
+                skipped = missed - MAX_BACKFILL_TRADES,
+                "Trade stream gap exceeds backfill limit, oldest trades skipped"
+            );
+            gap.end() - MAX_BACKFILL_TRADES + 1..=*gap.end()
+        } else {
+            gap
+        };
+
+        let symbol = self.symbol.clone();
+        let market = self.market;
+        let result = self
+            .backfill_gap(gap.clone(), trade_tx, |from_id| {
+                let client = client.clone();
+                let symbol = symbol.clone();
+                async move {
+                    client
+                        .get_market_agg_trades(
+                            market,
+                            &symbol,
+                            Some(BACKFILL_PAGE_SIZE),
+                            Some(from_id as i64),
+                            None,
+                            None,
+                        )
+                        .await
+                }
+            })
+            .await;
+
+        match result {
+            Ok(count) => info!(
+                symbol = %self.symbol,
+                from_id = gap.start(),
+                to_id = gap.end(),
+                backfilled = count,
+                "Backfilled trade stream gap"
+            ),
+            Err(BackfillError::Closed) => anyhow::bail!("Trade channel closed during backfill"),
+            Err(BackfillError::Fetch(e)) => warn!(
+                symbol = %self.symbol,
+                from_id = gap.start(),
+                to_id = gap.end(),
+                error = %e,
+                "Trade stream backfill failed, resuming live"
+            ),
+        }
+        Ok(())
+    }
+
+    /// Send every trade with an ID in `gap`, fetching pages with `fetch_page(from_id)`
+    ///
+    /// Stops at the end of the gap or when a page comes back empty.
+    ///
+    /// # Returns
+    /// Number of trades sent
+    async fn backfill_gap<F, Fut>(
+        &mut self,
+        gap: RangeInclusive<u64>,
+        trade_tx: &tokio::sync::mpsc::UnboundedSender<AggTrade>,
+        mut fetch_page: F,
+    ) -> std::result::Result<usize, BackfillError>
+    where
+        F: FnMut(u64) -> Fut,
+        Fut: Future<Output = std::result::Result<Vec<AggTradeResponse>, McpError>>,
+    {
+        let mut from_id = *gap.start();
+        let mut sent = 0;
+
+        while from_id <= *gap.end() {
+            let page = fetch_page(from_id).await.map_err(BackfillError::Fetch)?;
+            let Some(last) = page.last().map(|t| t.agg_trade_id as u64) else {
+                break;
+            };
+
+            for trade in page
+                .iter()
+                .filter(|t| gap.contains(&(t.agg_trade_id as u64)))
+            {
+                trade_tx
+                    .send(AggTrade::from_rest(&self.symbol, trade))
+                    .map_err(|_| BackfillError::Closed)?;
+                self.last_agg_trade_id = Some(trade.agg_trade_id as u64);
+                sent += 1;
+            }
+
+            if last < from_id {
+                break;
+            }
+            from_id = last + 1;
+        }
+
+        Ok(sent)
+    }
+
     /// Get buffered trades for aggregation
     pub fn drain_buffer(&mut self) -> Vec<AggTrade> {
         std::mem::take(&mut self.trade_buffer)
     }
 }
 
+/// Why a backfill stopped early
+#[derive(Debug)]
+enum BackfillError {
+    /// REST request failed
+    Fetch(McpError),
+    /// Trade channel receiver dropped
+    Closed,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_trade_stream_handler_new() {
-        let handler = TradeStreamHandler::new("BTCUSDT");
+        let handler = TradeStreamHandler::new("BTCUSDT", Market::Spot);
         assert_eq!(handler.symbol, "BTCUSDT");
-        assert!(handler.url.contains("btcusdt@aggTrade"));
+        assert_eq!(
+            handler.url,
+            "wss://stream.binance.com:9443/ws/btcusdt@aggTrade"
+        );
+
+        let futures = TradeStreamHandler::new("btcusdt", Market::UsdmFutures);
+        assert_eq!(futures.url, "wss://fstream.binance.com/ws/btcusdt@aggTrade");
     }
 
     #[test]
@@ -247,4 +462,82 @@ mod tests {
         assert_eq!(trade.quantity, "1.25");
         assert_eq!(trade.is_buyer_maker, true);
     }
+
+    fn rest_trade(id: i64) -> AggTradeResponse {
+        AggTradeResponse {
+            agg_trade_id: id,
+            price: "100.00".to_string(),
+            qty: "1.0".to_string(),
+            first_trade_id: id,
+            last_trade_id: id,
+            time: 1_700_000_000_000 + id,
+            is_buyer_maker: false,
+            is_best_match: true,
+        }
+    }
+
+    #[test]
+    fn test_gap_range() {
+        assert_eq!(gap_range(None, 10), None);
+        assert_eq!(gap_range(Some(9), 10), None);
+        assert_eq!(gap_range(Some(10), 10), None);
+        assert_eq!(gap_range(Some(5), 10), Some(6..=9));
+    }
+
+    #[tokio::test]
+    async fn test_gap_backfilled_from_missing_id() {
+        let mut handler = TradeStreamHandler::new("BTCUSDT", Market::Spot);
+        handler.last_agg_trade_id = Some(100);
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+
+        // Live stream resumed at 2051: trades 101..=2050 were missed
+        let gap = gap_range(handler.last_agg_trade_id, 2051).unwrap();
+        assert_eq!(gap, 101..=2050);
+
+        let mut requested = Vec::new();
+        let sent = handler
+            .backfill_gap(gap, &tx, |from_id| {
+                requested.push(from_id);
+                // Pages of up to 1000 trades; the REST API may already include live ids
+                let page: Vec<_> = (from_id..from_id + 1000)
+                    .map(|id| rest_trade(id as i64))
+                    .collect();
+                async move { Ok(page) }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(requested, vec![101, 1101]);
+        assert_eq!(sent, 1950);
+        assert_eq!(handler.last_agg_trade_id, Some(2050));
+
+        drop(tx);
+        let mut ids = Vec::new();
+        while let Some(trade) = rx.recv().await {
+            assert_eq!(trade.symbol, "BTCUSDT");
+            ids.push(trade.agg_trade_id);
+        }
+        assert_eq!(ids, (101..=2050).collect::<Vec<_>>());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_stops_on_empty_page() {
+        let mut handler = TradeStreamHandler::new("BTCUSDT", Market::Spot);
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let sent = handler
+            .backfill_gap(1..=50, &tx, |from_id| {
+                let page = if from_id == 1 {
+                    (1..=20).map(rest_trade).collect()
+                } else {
+                    Vec::new()
+                };
+                async move { Ok(page) }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(sent, 20);
+        assert_eq!(handler.last_agg_trade_id, Some(20));
+    }
 }
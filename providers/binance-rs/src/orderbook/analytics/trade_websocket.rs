@@ -1,6 +1,7 @@
 // WebSocket client for Binance aggTrade stream
 // Handles connection lifecycle, reconnection, and message parsing
 
+use crate::binance::market::Market;
 use anyhow::Result;
 use std::time::Duration;
 
@@ -11,9 +12,8 @@ pub struct TradeStreamClient {
 }
 
 impl TradeStreamClient {
-    pub fn new(symbol: &str) -> Self {
-        let symbol_lower = symbol.to_lowercase();
-        let websocket_url = format!("wss://stream.binance.com/ws/{}@aggTrade", symbol_lower);
+    pub fn new(symbol: &str, market: Market) -> Self {
+        let websocket_url = market.stream_url(&format!("{}@aggTrade", symbol.to_lowercase()));
 
         Self {
             symbol: symbol.to_string(),
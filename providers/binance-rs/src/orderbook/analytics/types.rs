@@ -2,6 +2,7 @@
 //!
 //! This module defines all entities and enums used across the analytics system.
 
+use crate::timestamp::Timestamp;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use schemars::JsonSchema;
@@ -144,9 +145,15 @@ pub struct OrderFlowSnapshot {
     /// Window start timestamp
     pub time_window_start: DateTime<Utc>,
 
+    /// `time_window_start` as RFC 3339 and epoch milliseconds
+    pub time_window_start_ts: Timestamp,
+
     /// Window end timestamp (must be ≤ now)
     pub time_window_end: DateTime<Utc>,
 
+    /// `time_window_end` as RFC 3339 and epoch milliseconds
+    pub time_window_end_ts: Timestamp,
+
     /// Duration in seconds (10, 30, 60, 300)
     #[schemars(range(min = 10, max = 300))]
     pub window_duration_secs: u32,
@@ -165,8 +172,37 @@ pub struct OrderFlowSnapshot {
     /// Categorical pressure indicator
     pub flow_direction: FlowDirection,
 
-    /// Running sum of (buy volume - sell volume)
+    /// Running sum of (buy volume - sell volume), exponentially decayed when
+    /// `delta_half_life_secs` is set
     pub cumulative_delta: f64,
+
+    /// Half-life (seconds) weighting `cumulative_delta` toward recent flow
+    /// (absent = unweighted)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delta_half_life_secs: Option<f64>,
+
+    /// Composite pressure score blending order-count and volume imbalance
+    /// (-100 = all selling, 0 = balanced, 100 = all buying); see
+    /// [`calculate_flow_score`](crate::orderbook::analytics::flow::calculate_flow_score)
+    #[schemars(range(min = -100.0, max = 100.0))]
+    pub flow_score: f64,
+
+    /// Levels that absorbed heavy flow without the price moving
+    pub absorption_events: Vec<AbsorptionEvent>,
+}
+
+/// Which per-bin measure picks the Point of Control
+///
+/// `Volume` is the classic volume profile; `TradeCount` favours the price
+/// where trading happened most often, regardless of size (TPO-style).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PocBasis {
+    /// Bin with the highest traded volume
+    #[default]
+    Volume,
+    /// Bin with the most trades
+    TradeCount,
 }
 
 /// Volume profile histogram with POC/VAH/VAL
@@ -181,9 +217,15 @@ pub struct VolumeProfile {
     /// Analysis period start
     pub time_period_start: DateTime<Utc>,
 
+    /// `time_period_start` as RFC 3339 and epoch milliseconds
+    pub time_period_start_ts: Timestamp,
+
     /// Analysis period end (must be ≤ now)
     pub time_period_end: DateTime<Utc>,
 
+    /// `time_period_end` as RFC 3339 and epoch milliseconds
+    pub time_period_end_ts: Timestamp,
+
     /// Lowest price in histogram
     #[schemars(with = "String")]
     pub price_range_low: Decimal,
@@ -192,7 +234,7 @@ pub struct VolumeProfile {
     #[schemars(with = "String")]
     pub price_range_high: Decimal,
 
-    /// Price bin width (adaptive tick-based)
+    /// Price bin width (adaptive tick-based), before any `max_bins` merging
     #[schemars(with = "String")]
     pub bin_size: Decimal,
 
@@ -200,6 +242,10 @@ pub struct VolumeProfile {
     #[schemars(range(min = 1, max = 200))]
     pub bin_count: usize,
 
+    /// Number of bins before merging down to `max_bins` (equals `bin_count` when
+    /// nothing was merged)
+    pub original_bin_count: usize,
+
     /// Volume distribution histogram
     pub histogram: Vec<VolumeBin>,
 
@@ -207,17 +253,35 @@ pub struct VolumeProfile {
     #[schemars(with = "String")]
     pub total_volume: Decimal,
 
-    /// Price level with highest volume (POC)
+    /// Price level with highest volume, or most trades with
+    /// `poc_basis: trade_count` (POC)
     #[schemars(with = "String")]
     pub point_of_control: Decimal,
 
-    /// Upper boundary of value area (70% volume)
+    /// Measure the POC was picked by
+    #[serde(default)]
+    pub poc_basis: PocBasis,
+
+    /// Upper boundary of value area (`value_area_pct` of volume)
     #[schemars(with = "String")]
     pub value_area_high: Decimal,
 
-    /// Lower boundary of value area (70% volume)
+    /// Lower boundary of value area (`value_area_pct` of volume)
     #[schemars(with = "String")]
     pub value_area_low: Decimal,
+
+    /// Share of total volume inside VAH/VAL (e.g. 0.70)
+    #[schemars(range(min = 0.5, max = 0.95))]
+    pub value_area_pct: f64,
+
+    /// Trades the profile was built from
+    #[serde(default)]
+    pub trade_count: usize,
+
+    /// Built from fewer than 1000 trades (allowed by a lower `min_trades`);
+    /// levels may shift as more trades arrive
+    #[serde(default)]
+    pub low_confidence: bool,
 }
 
 /// Single bin in volume profile histogram
@@ -257,6 +321,9 @@ pub struct MarketMicrostructureAnomaly {
     /// When anomaly was detected
     pub detection_timestamp: DateTime<Utc>,
 
+    /// `detection_timestamp` as RFC 3339 and epoch milliseconds
+    pub detection_timestamp_ts: Timestamp,
+
     /// Detection confidence (0.0-1.0)
     #[schemars(range(min = 0.0, max = 1.0))]
     pub confidence_score: f64,
@@ -281,9 +348,9 @@ pub struct MarketMicrostructureAnomaly {
 #[serde(tag = "type")]
 pub enum AnomalyType {
     QuoteStuffing {
-        /// Updates/sec (>500 triggers)
+        /// Updates/sec (>500 triggers by default)
         update_rate: f64,
-        /// Percentage (<10% triggers)
+        /// Share of updates that traded, 0-1 (<10% triggers by default)
         fill_rate: f64,
     },
     IcebergOrder {
@@ -342,6 +409,9 @@ pub struct LiquidityVacuum {
 
     /// When detected
     pub detection_timestamp: DateTime<Utc>,
+
+    /// `detection_timestamp` as RFC 3339 and epoch milliseconds
+    pub detection_timestamp_ts: Timestamp,
 }
 
 /// Absorption event
@@ -372,9 +442,15 @@ pub struct AbsorptionEvent {
     /// First refill timestamp
     pub first_detected: DateTime<Utc>,
 
+    /// `first_detected` as RFC 3339 and epoch milliseconds
+    pub first_detected_ts: Timestamp,
+
     /// Most recent refill (≤ now)
     pub last_updated: DateTime<Utc>,
 
+    /// `last_updated` as RFC 3339 and epoch milliseconds
+    pub last_updated_ts: Timestamp,
+
     /// Heuristic entity classification
     pub suspected_entity_type: EntityType,
 
@@ -406,6 +482,9 @@ pub struct MicrostructureHealth {
     /// When health was calculated
     pub timestamp: DateTime<Utc>,
 
+    /// `timestamp` as RFC 3339 and epoch milliseconds
+    pub timestamp_ts: Timestamp,
+
     /// Composite health score (0-100)
     #[schemars(range(min = 0.0, max = 100.0))]
     pub overall_score: f64,
@@ -0,0 +1,213 @@
+//! Execution benchmarks (VWAP/TWAP) from persisted trades
+//!
+//! - VWAP: Σ(price × quantity) / Σ(quantity) over trades in the window
+//! - TWAP: each trade's price is held until the next trade (the last one until
+//!   the window end) and averaged by the time it was in effect
+//!
+//! Both operate on [`AggTrade`] batches read from `TradeStorage` and return
+//! `Decimal` to avoid float drift on large notional sums.
+
+use crate::orderbook::analytics::{tools::AnalyticsToolError, trade_storage::AggTrade};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Closed time range `[start_ms, end_ms]` (milliseconds since epoch)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TradeWindow {
+    pub start_ms: i64,
+    pub end_ms: i64,
+}
+
+impl TradeWindow {
+    /// Window of length `duration` ending at `end_ms`
+    pub fn trailing(end_ms: i64, duration: Duration) -> Self {
+        Self {
+            start_ms: end_ms - duration.as_millis() as i64,
+            end_ms,
+        }
+    }
+
+    fn contains(&self, timestamp_ms: i64) -> bool {
+        (self.start_ms..=self.end_ms).contains(&timestamp_ms)
+    }
+}
+
+/// Volume-weighted average price of trades inside `window`
+///
+/// # Errors
+/// `InsufficientData` when no trade with a positive quantity falls in the window
+pub fn compute_vwap(
+    trades: &[AggTrade],
+    window: TradeWindow,
+) -> Result<Decimal, AnalyticsToolError> {
+    let mut notional = Decimal::ZERO;
+    let mut volume = Decimal::ZERO;
+
+    for (_, price, quantity) in parsed_trades(trades, window) {
+        notional += price * quantity;
+        volume += quantity;
+    }
+
+    if volume.is_zero() {
+        return Err(insufficient(trades.len(), window));
+    }
+
+    Ok(notional / volume)
+}
+
+/// Time-weighted average price of trades inside `window`
+///
+/// If all trades share one timestamp at the window end (zero elapsed time), the
+/// plain average of their prices is returned.
+///
+/// # Errors
+/// `InsufficientData` when no trade falls in the window
+pub fn compute_twap(
+    trades: &[AggTrade],
+    window: TradeWindow,
+) -> Result<Decimal, AnalyticsToolError> {
+    let mut points: Vec<(i64, Decimal)> = parsed_trades(trades, window)
+        .map(|(timestamp, price, _)| (timestamp, price))
+        .collect();
+    if points.is_empty() {
+        return Err(insufficient(trades.len(), window));
+    }
+
+    // Stable sort keeps storage order for trades sharing a millisecond
+    points.sort_by_key(|(timestamp, _)| *timestamp);
+
+    let mut weighted = Decimal::ZERO;
+    let mut elapsed_ms: i64 = 0;
+    for (i, (timestamp, price)) in points.iter().enumerate() {
+        let until = points
+            .get(i + 1)
+            .map(|(next, _)| *next)
+            .unwrap_or(window.end_ms);
+        let held_ms = until - timestamp;
+        weighted += *price * Decimal::from(held_ms);
+        elapsed_ms += held_ms;
+    }
+
+    if elapsed_ms == 0 {
+        let sum: Decimal = points.iter().map(|(_, price)| *price).sum();
+        return Ok(sum / Decimal::from(points.len()));
+    }
+
+    Ok(weighted / Decimal::from(elapsed_ms))
+}
+
+/// Trades inside `window` with parseable, positive price and non-negative quantity
+fn parsed_trades(
+    trades: &[AggTrade],
+    window: TradeWindow,
+) -> impl Iterator<Item = (i64, Decimal, Decimal)> + '_ {
+    trades
+        .iter()
+        .filter(move |trade| window.contains(trade.timestamp))
+        .filter_map(|trade| {
+            let price = Decimal::from_str(&trade.price).ok()?;
+            let quantity = Decimal::from_str(&trade.quantity).ok()?;
+            (price > Decimal::ZERO && quantity >= Decimal::ZERO).then_some((
+                trade.timestamp,
+                price,
+                quantity,
+            ))
+        })
+}
+
+fn insufficient(trade_count: usize, window: TradeWindow) -> AnalyticsToolError {
+    AnalyticsToolError::InsufficientData(format!(
+        "No trades with volume between {} and {} ({} trades scanned)",
+        window.start_ms, window.end_ms, trade_count
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trade(timestamp: i64, price: &str, quantity: &str) -> AggTrade {
+        AggTrade {
+            price: price.to_string(),
+            quantity: quantity.to_string(),
+            timestamp,
+            trade_id: timestamp,
+            buyer_is_maker: false,
+        }
+    }
+
+    fn window() -> TradeWindow {
+        TradeWindow {
+            start_ms: 1_000,
+            end_ms: 5_000,
+        }
+    }
+
+    #[test]
+    fn test_vwap_matches_hand_computed_value() {
+        // (100×1 + 102×3 + 101×2) / (1+3+2) = 608 / 6 = 101.333...
+        let trades = vec![
+            trade(1_000, "100", "1"),
+            trade(2_000, "102", "3"),
+            trade(3_000, "101", "2"),
+        ];
+
+        let vwap = compute_vwap(&trades, window()).unwrap();
+        let expected = Decimal::from(608) / Decimal::from(6);
+        assert_eq!(vwap, expected);
+        assert_eq!(vwap.round_dp(4), Decimal::from_str("101.3333").unwrap());
+    }
+
+    #[test]
+    fn test_vwap_ignores_trades_outside_window() {
+        let trades = vec![
+            trade(500, "1", "1000"),
+            trade(2_000, "100", "2"),
+            trade(9_000, "1", "1000"),
+        ];
+        assert_eq!(compute_vwap(&trades, window()).unwrap(), Decimal::from(100));
+    }
+
+    #[test]
+    fn test_twap_weights_by_time_in_effect() {
+        // 100 held 1s, 102 held 1s, 101 held 2s (until window end) → (100+102+202)/4 = 101
+        let trades = vec![
+            trade(3_000, "101", "2"),
+            trade(1_000, "100", "1"),
+            trade(2_000, "102", "3"),
+        ];
+        assert_eq!(compute_twap(&trades, window()).unwrap(), Decimal::from(101));
+    }
+
+    #[test]
+    fn test_twap_single_instant_falls_back_to_mean() {
+        let trades = vec![trade(5_000, "100", "1"), trade(5_000, "104", "1")];
+        assert_eq!(compute_twap(&trades, window()).unwrap(), Decimal::from(102));
+    }
+
+    #[test]
+    fn test_empty_trades_are_insufficient_data() {
+        assert!(matches!(
+            compute_vwap(&[], window()),
+            Err(AnalyticsToolError::InsufficientData(_))
+        ));
+        assert!(matches!(
+            compute_twap(&[], window()),
+            Err(AnalyticsToolError::InsufficientData(_))
+        ));
+
+        let zero_volume = vec![trade(2_000, "100", "0")];
+        assert!(matches!(
+            compute_vwap(&zero_volume, window()),
+            Err(AnalyticsToolError::InsufficientData(_))
+        ));
+    }
+
+    #[test]
+    fn test_trailing_window() {
+        let window = TradeWindow::trailing(10_000, Duration::from_secs(3));
+        assert_eq!(window.start_ms, 7_000);
+        assert_eq!(window.end_ms, 10_000);
+    }
+}
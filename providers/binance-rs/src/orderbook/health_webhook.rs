@@ -0,0 +1,229 @@
+//! Webhook notifications for order book health transitions
+//!
+//! When `HEALTH_WEBHOOK_URL` is set, [`spawn_health_webhook`] polls
+//! [`OrderBookManager::get_health`] and POSTs a [`HealthTransition`] whenever the
+//! status changes (e.g. `ok` → `degraded`). A new status must be seen on
+//! [`HEALTH_WEBHOOK_CONFIRMATIONS`] consecutive polls before it is reported, so
+//! a single slow update doesn't page anyone. Failed POSTs are logged and the
+//! transition is not retried.
+
+use super::types::{HealthStatus, OrderBookHealth};
+use super::OrderBookManager;
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Interval between health polls
+pub const HEALTH_WEBHOOK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Consecutive polls a new status must hold before it is reported
+pub const HEALTH_WEBHOOK_CONFIRMATIONS: u32 = 2;
+
+/// Timeout for each webhook POST
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Webhook URL from `HEALTH_WEBHOOK_URL` (None when unset or blank)
+pub fn webhook_url_from_env() -> Option<String> {
+    std::env::var("HEALTH_WEBHOOK_URL")
+        .ok()
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+}
+
+/// JSON body POSTed on a status change
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthTransition {
+    /// Status reported before this change
+    pub previous_status: HealthStatus,
+
+    /// Newly confirmed status
+    pub status: HealthStatus,
+
+    /// Reason for the new status, if not `ok`
+    pub reason: Option<String>,
+
+    /// Full health report at the time of the change
+    pub health: OrderBookHealth,
+}
+
+/// Debounces polled statuses into confirmed transitions
+#[derive(Debug, Clone)]
+pub struct TransitionTracker {
+    confirmations: u32,
+    reported: Option<HealthStatus>,
+    candidate: Option<(HealthStatus, u32)>,
+}
+
+impl TransitionTracker {
+    /// Tracker requiring `confirmations` consecutive polls (at least 1)
+    pub fn new(confirmations: u32) -> Self {
+        Self {
+            confirmations: confirmations.max(1),
+            reported: None,
+            candidate: None,
+        }
+    }
+
+    /// Record a polled status
+    ///
+    /// The first status observed becomes the baseline without a transition.
+    ///
+    /// # Returns
+    /// `Some(previous)` once `status` differs from the last reported status and
+    /// has held for the required number of polls
+    pub fn observe(&mut self, status: HealthStatus) -> Option<HealthStatus> {
+        let Some(reported) = self.reported else {
+            self.reported = Some(status);
+            return None;
+        };
+        if status == reported {
+            self.candidate = None;
+            return None;
+        }
+
+        let seen = match self.candidate {
+            Some((candidate, seen)) if candidate == status => seen + 1,
+            _ => 1,
+        };
+        if seen < self.confirmations {
+            self.candidate = Some((status, seen));
+            return None;
+        }
+
+        self.reported = Some(status);
+        self.candidate = None;
+        Some(reported)
+    }
+}
+
+/// Poll manager health every `interval` and POST transitions to `url`
+///
+/// The current status is recorded as the baseline before the task is spawned,
+/// so only changes after this call are reported. Runs until aborted.
+///
+/// # Errors
+/// Returns error if the HTTP client can't be built
+pub async fn spawn_health_webhook(
+    manager: Arc<OrderBookManager>,
+    url: String,
+    interval: Duration,
+) -> Result<tokio::task::JoinHandle<()>, reqwest::Error> {
+    let client = reqwest::Client::builder()
+        .timeout(WEBHOOK_TIMEOUT)
+        .build()?;
+    let mut tracker = TransitionTracker::new(HEALTH_WEBHOOK_CONFIRMATIONS);
+    tracker.observe(manager.get_health().await.status);
+
+    Ok(tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        loop {
+            ticker.tick().await;
+            let health = manager.get_health().await;
+            let Some(previous_status) = tracker.observe(health.status) else {
+                continue;
+            };
+
+            let transition = HealthTransition {
+                previous_status,
+                status: health.status,
+                reason: health.reason.clone(),
+                health,
+            };
+            tracing::info!(
+                previous = ?transition.previous_status,
+                status = ?transition.status,
+                "Order book health changed, notifying webhook"
+            );
+
+            match client.post(&url).json(&transition).send().await {
+                Ok(response) if response.status().is_success() => {}
+                Ok(response) => tracing::warn!(
+                    status = %response.status(),
+                    "Health webhook rejected notification"
+                ),
+                Err(e) => tracing::warn!(error = %e, "Health webhook POST failed"),
+            }
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binance::BinanceClient;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_tracker_reports_confirmed_changes_only() {
+        let mut tracker = TransitionTracker::new(2);
+
+        assert_eq!(tracker.observe(HealthStatus::Ok), None);
+        // A single degraded poll is not confirmed
+        assert_eq!(tracker.observe(HealthStatus::Degraded), None);
+        assert_eq!(tracker.observe(HealthStatus::Ok), None);
+
+        assert_eq!(tracker.observe(HealthStatus::Error), None);
+        assert_eq!(tracker.observe(HealthStatus::Error), Some(HealthStatus::Ok));
+        // No repeat while the status holds
+        assert_eq!(tracker.observe(HealthStatus::Error), None);
+    }
+
+    /// Accept one HTTP request, answer 200 and return its body
+    async fn receive_one_post(listener: TcpListener) -> String {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+            let text = String::from_utf8_lossy(&request);
+            if let Some((headers, body)) = text.split_once("\r\n\r\n") {
+                let length = headers
+                    .lines()
+                    .find_map(|line| {
+                        let (name, value) = line.split_once(':')?;
+                        name.eq_ignore_ascii_case("content-length")
+                            .then(|| value.trim().parse::<usize>().ok())?
+                    })
+                    .unwrap_or(0);
+                if body.len() >= length {
+                    socket
+                        .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                        .await
+                        .unwrap();
+                    return body.to_string();
+                }
+            }
+            assert!(n > 0, "connection closed before the body arrived");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_webhook_receives_transition_when_health_flips() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let received = tokio::spawn(receive_one_post(listener));
+
+        let manager = Arc::new(OrderBookManager::new(Arc::new(BinanceClient::new())));
+        manager.insert_test_state("BTCUSDT", true).await;
+        // Baseline is ok; then drop the only connection
+        let task = spawn_health_webhook(Arc::clone(&manager), url, Duration::from_millis(20))
+            .await
+            .unwrap();
+        manager.insert_test_state("BTCUSDT", false).await;
+
+        let body = tokio::time::timeout(Duration::from_secs(5), received)
+            .await
+            .expect("no webhook notification")
+            .unwrap();
+        task.abort();
+
+        let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+        assert_eq!(payload["previous_status"], "ok");
+        assert_eq!(payload["status"], "error");
+        assert_eq!(payload["health"]["websocket_connected"], false);
+    }
+}
@@ -2,14 +2,22 @@
 //!
 //! Implements lazy initialization, WebSocket streaming, REST API fallback,
 //! and symbol limit enforcement (max 20 concurrent symbols).
+//!
+//! Snapshots come from an injected [`SnapshotSource`]; in replay mode books are
+//! pushed with [`OrderBookManager::apply_snapshot`] instead of being streamed.
 
 use crate::binance::client::BinanceClient;
+use crate::config::StalenessConfig;
 use crate::orderbook::rate_limiter::{RateLimiter, RateLimiterError};
-use crate::orderbook::types::{HealthStatus, OrderBook, OrderBookHealth};
-use crate::orderbook::websocket::{DepthUpdateEvent, DepthWebSocketClient};
+use crate::orderbook::source::{RestSnapshotSource, SnapshotSource};
+use crate::orderbook::types::{
+    HealthStatus, OrderBook, OrderBookHealth, OrderBookIntegrity, StreamHealth,
+};
+use crate::orderbook::websocket::{DepthUpdateEvent, DepthWebSocketClient, StreamConnectionStats};
 use rust_decimal::Decimal;
 use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use thiserror::Error;
 use tokio::sync::RwLock;
@@ -17,10 +25,21 @@ use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 
 /// Maximum number of concurrent symbols that can be tracked
-const MAX_CONCURRENT_SYMBOLS: usize = 20;
+pub const MAX_CONCURRENT_SYMBOLS: usize = 20;
+
+/// Default staleness threshold in milliseconds (5 seconds)
+pub const STALENESS_THRESHOLD_MS: i64 = 5000;
 
-/// Staleness threshold in milliseconds (5 seconds)
-const STALENESS_THRESHOLD_MS: i64 = 5000;
+/// Default levels per side fetched for REST snapshots
+pub const DEFAULT_SNAPSHOT_DEPTH: u32 = 100;
+
+/// Snapshot depths accepted by Binance `/api/v3/depth`
+///
+/// Request weight grows with depth: 5 up to 100 levels, 25 up to 500, 50 at
+/// 1000 and 250 at 5000. Deep books help wall and liquidity analytics but use
+/// up the 6000/min IP weight budget faster on every initial fetch, resync and
+/// staleness refresh; shallow books are enough for L1 quotes.
+pub const SNAPSHOT_DEPTHS: [u32; 8] = [5, 10, 20, 50, 100, 500, 1000, 5000];
 
 /// Order book manager errors
 #[derive(Debug, Error)]
@@ -45,6 +64,9 @@ pub enum ManagerError {
 
     #[error("WebSocket error: {0}")]
     WebSocketError(String),
+
+    #[error("Invalid snapshot depth {0}: expected one of 5, 10, 20, 50, 100, 500, 1000, 5000")]
+    InvalidSnapshotDepth(u32),
 }
 
 /// Internal state for a tracked order book
@@ -52,8 +74,7 @@ struct OrderBookState {
     /// Current order book snapshot
     order_book: OrderBook,
 
-    /// WebSocket client task handle (kept alive to prevent task cancellation)
-    #[allow(dead_code)]
+    /// WebSocket client task handle, aborted when the symbol is dropped or re-initialized
     websocket_handle: Option<JoinHandle<()>>,
 
     /// Last successful update timestamp
@@ -64,6 +85,41 @@ struct OrderBookState {
 
     /// CROSSED FIX: Flag indicating orderbook needs re-sync due to gap
     needs_resync: bool,
+
+    /// Depth updates rejected for a sequence gap since the symbol was tracked
+    gaps_detected: u64,
+
+    /// REST resyncs performed after a gap or crossed book
+    resyncs: u64,
+
+    /// Reconnect count and last connect time of this symbol's depth stream
+    stream_stats: Arc<StreamConnectionStats>,
+}
+
+impl OrderBookState {
+    /// Abort the depth WebSocket task, if any
+    ///
+    /// The update-processing task ends on its own once the aborted task drops
+    /// its sender.
+    fn stop_stream(&self) {
+        if let Some(handle) = &self.websocket_handle {
+            handle.abort();
+        }
+    }
+
+    /// State for a book without a depth stream (replayed or test books)
+    fn detached(order_book: OrderBook) -> Self {
+        Self {
+            order_book,
+            websocket_handle: None,
+            last_update_time: chrono::Utc::now().timestamp_millis(),
+            websocket_connected: false,
+            needs_resync: false,
+            gaps_detected: 0,
+            resyncs: 0,
+            stream_stats: Arc::new(StreamConnectionStats::default()),
+        }
+    }
 }
 
 /// Manager for multiple order book subscriptions
@@ -71,7 +127,7 @@ struct OrderBookState {
 /// Tracks up to 20 symbols with lazy initialization:
 /// 1. First request triggers REST API snapshot + WebSocket subscription
 /// 2. Subsequent requests use cached data (updated via WebSocket)
-/// 3. REST API fallback when data is stale (>5s old)
+/// 3. REST API fallback when data is stale (>5s old by default, see [`StalenessConfig`])
 pub struct OrderBookManager {
     /// Map of symbol → order book state
     states: Arc<RwLock<HashMap<String, OrderBookState>>>,
@@ -79,20 +135,69 @@ pub struct OrderBookManager {
     /// Rate limiter for REST API requests
     rate_limiter: Arc<RateLimiter>,
 
-    /// Binance API client (for REST fallback)
-    binance_client: Arc<BinanceClient>,
+    /// Snapshot source (REST + WebSocket when live, replay otherwise)
+    source: Arc<dyn SnapshotSource>,
+
+    /// Depth WebSocket reconnect attempts across all symbols
+    websocket_reconnects: Arc<AtomicU64>,
+
+    /// Age after which a cached book is refreshed, per symbol
+    staleness: StalenessConfig,
 }
 
 impl OrderBookManager {
-    /// Create a new order book manager
+    /// Create a new order book manager fed by the REST API and depth WebSockets
     pub fn new(binance_client: Arc<BinanceClient>) -> Self {
+        Self::with_source(Arc::new(RestSnapshotSource::new(binance_client)))
+    }
+
+    /// Create a live manager fetching `depth` levels per side for REST snapshots
+    ///
+    /// See [`SNAPSHOT_DEPTHS`] for the request weight of each depth.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManagerError::InvalidSnapshotDepth`] if Binance does not accept `depth`
+    pub fn with_snapshot_depth(
+        binance_client: Arc<BinanceClient>,
+        depth: u32,
+    ) -> Result<Self, ManagerError> {
+        let source = RestSnapshotSource::with_depth(binance_client, depth)?;
+        Ok(Self::with_source(Arc::new(source)))
+    }
+
+    /// Create an order book manager fed by a custom snapshot source (e.g. replay)
+    pub fn with_source(source: Arc<dyn SnapshotSource>) -> Self {
         Self {
             states: Arc::new(RwLock::new(HashMap::new())),
             rate_limiter: Arc::new(RateLimiter::new()),
-            binance_client,
+            source,
+            websocket_reconnects: Arc::new(AtomicU64::new(0)),
+            staleness: StalenessConfig::default(),
         }
     }
 
+    /// Use `staleness` thresholds instead of the 5s default
+    pub fn with_staleness(mut self, staleness: StalenessConfig) -> Self {
+        self.staleness = staleness;
+        self
+    }
+
+    /// Staleness thresholds applied to cached books
+    pub fn staleness(&self) -> &StalenessConfig {
+        &self.staleness
+    }
+
+    /// Whether books are streamed live (false in replay mode)
+    pub fn is_live(&self) -> bool {
+        self.source.is_live()
+    }
+
+    /// Total depth WebSocket reconnect attempts since startup (all symbols)
+    pub fn websocket_reconnect_count(&self) -> u64 {
+        self.websocket_reconnects.load(Ordering::Relaxed)
+    }
+
     /// Subscribe to order book updates for a symbol (eager initialization)
     ///
     /// Initiates WebSocket subscription and fetches initial REST API snapshot.
@@ -132,6 +237,36 @@ impl OrderBookManager {
         Ok(())
     }
 
+    /// Stop tracking a symbol: abort its depth WebSocket task and drop its book
+    ///
+    /// Returns false if the symbol was not tracked. A later query or
+    /// [`subscribe`](Self::subscribe) starts it again from a fresh snapshot.
+    pub async fn unsubscribe(&self, symbol: &str) -> bool {
+        let symbol_upper = symbol.to_uppercase();
+
+        let Some(state) = self.states.write().await.remove(&symbol_upper) else {
+            debug!(symbol = %symbol_upper, "Not subscribed");
+            return false;
+        };
+        state.stop_stream();
+
+        info!(symbol = %symbol_upper, "Unsubscribed order book");
+        true
+    }
+
+    /// Number of symbols currently tracked (at most [`MAX_CONCURRENT_SYMBOLS`])
+    pub async fn active_symbol_count(&self) -> usize {
+        self.states.read().await.len()
+    }
+
+    /// Whether `symbol` is currently tracked
+    pub async fn is_subscribed(&self, symbol: &str) -> bool {
+        self.states
+            .read()
+            .await
+            .contains_key(&symbol.to_uppercase())
+    }
+
     /// Get order book for a symbol (lazy initialization)
     ///
     /// On first request:
@@ -149,6 +284,11 @@ impl OrderBookManager {
         {
             let states = self.states.read().await;
             if let Some(state) = states.get(&symbol_upper) {
+                // Replayed books are historical: serve them as last applied
+                if !self.source.is_live() {
+                    return Ok(state.order_book.clone());
+                }
+
                 // AUTO-RESYNC FIX: Check if resync is needed due to gap detection
                 if state.needs_resync {
                     warn!(
@@ -165,7 +305,7 @@ impl OrderBookManager {
                 let now = chrono::Utc::now().timestamp_millis();
                 let age_ms = now - state.last_update_time;
 
-                if age_ms < STALENESS_THRESHOLD_MS {
+                if age_ms < self.staleness.threshold_ms(&symbol_upper) {
                     debug!(
                         symbol = %symbol_upper,
                         age_ms,
@@ -213,22 +353,41 @@ impl OrderBookManager {
         self.rate_limiter.wait().await?;
 
         // Fetch initial snapshot from REST API
-        let order_book = self.fetch_snapshot(symbol).await?;
+        let order_book = self.source.fetch_snapshot(symbol).await?;
+
+        if !self.source.is_live() {
+            states.insert(symbol.to_string(), OrderBookState::detached(order_book));
+            return Ok(());
+        }
 
         // Start WebSocket subscription
-        let (ws_client, mut update_receiver) = DepthWebSocketClient::new(symbol.to_string());
+        let (ws_client, mut update_receiver) = DepthWebSocketClient::new(
+            symbol.to_string(),
+            self.source.market(),
+            Arc::clone(&self.websocket_reconnects),
+        );
+        let stream_stats = ws_client.stats();
         let websocket_handle = ws_client.start();
 
-        // Store initial state
+        // Store initial state; a stale refresh keeps the integrity counters
+        let (gaps_detected, resyncs) = states.get(symbol).map_or((0, 0), |previous| {
+            (previous.gaps_detected, previous.resyncs)
+        });
         let state = OrderBookState {
             order_book: order_book.clone(),
             websocket_handle: Some(websocket_handle),
             last_update_time: chrono::Utc::now().timestamp_millis(),
             websocket_connected: true,
             needs_resync: false, // CROSSED FIX: Initialize resync flag
+            gaps_detected,
+            resyncs,
+            stream_stats,
         };
 
-        states.insert(symbol.to_string(), state);
+        // A stale refresh replaces the previous stream
+        if let Some(previous) = states.insert(symbol.to_string(), state) {
+            previous.stop_stream();
+        }
 
         // Spawn task to process WebSocket updates
         let states_clone = Arc::clone(&self.states);
@@ -277,12 +436,13 @@ impl OrderBookManager {
         self.rate_limiter.wait().await?;
 
         // Fetch fresh snapshot
-        let fresh_snapshot = self.fetch_snapshot(symbol).await?;
+        let fresh_snapshot = self.source.fetch_snapshot(symbol).await?;
 
         // Update orderbook in-place (WebSocket handle remains untouched)
         state.order_book = fresh_snapshot.clone();
         state.last_update_time = chrono::Utc::now().timestamp_millis();
         state.needs_resync = false; // Clear resync flag
+        state.resyncs += 1;
 
         info!(
             symbol = %symbol,
@@ -293,48 +453,29 @@ impl OrderBookManager {
         Ok(fresh_snapshot)
     }
 
-    /// Fetch order book snapshot from REST API
-    async fn fetch_snapshot(&self, symbol: &str) -> Result<OrderBook, ManagerError> {
-        debug!(symbol = %symbol, "Fetching order book snapshot from REST API");
-
-        // Use BinanceClient to fetch depth
-        let snapshot = self
-            .binance_client
-            .get_order_book(symbol, Some(100))
-            .await
-            .map_err(|e| ManagerError::RestApiError(e.to_string()))?;
-
-        // Convert response to OrderBook
-        let mut order_book = OrderBook::new(symbol.to_string());
-        order_book.last_update_id = snapshot.last_update_id;
-        order_book.timestamp = chrono::Utc::now().timestamp_millis();
+    /// Replace the tracked book for `order_book.symbol` (replay mode)
+    ///
+    /// Starts tracking the symbol if needed (subject to the symbol limit) without
+    /// a WebSocket subscription; the book is served as-is until the next call.
+    pub async fn apply_snapshot(&self, order_book: OrderBook) -> Result<(), ManagerError> {
+        let mut states = self.states.write().await;
 
-        // Parse bids - Binance API returns Vec<(String, String)>
-        for (price_str, qty_str) in &snapshot.bids {
-            let price = Decimal::from_str(price_str)
-                .map_err(|e| ManagerError::RestApiError(format!("Invalid bid price: {}", e)))?;
-            let qty = Decimal::from_str(qty_str)
-                .map_err(|e| ManagerError::RestApiError(format!("Invalid bid qty: {}", e)))?;
-            order_book.bids.insert(price, qty);
+        if let Some(state) = states.get_mut(&order_book.symbol) {
+            state.order_book = order_book;
+            state.last_update_time = chrono::Utc::now().timestamp_millis();
+            state.needs_resync = false;
+            return Ok(());
         }
 
-        // Parse asks - Binance API returns Vec<(String, String)>
-        for (price_str, qty_str) in &snapshot.asks {
-            let price = Decimal::from_str(price_str)
-                .map_err(|e| ManagerError::RestApiError(format!("Invalid ask price: {}", e)))?;
-            let qty = Decimal::from_str(qty_str)
-                .map_err(|e| ManagerError::RestApiError(format!("Invalid ask qty: {}", e)))?;
-            order_book.asks.insert(price, qty);
+        if states.len() >= MAX_CONCURRENT_SYMBOLS {
+            return Err(ManagerError::SymbolLimitReached);
         }
 
-        debug!(
-            symbol = %symbol,
-            bid_levels = order_book.bids.len(),
-            ask_levels = order_book.asks.len(),
-            "Fetched order book snapshot"
+        states.insert(
+            order_book.symbol.clone(),
+            OrderBookState::detached(order_book),
         );
-
-        Ok(order_book)
+        Ok(())
     }
 
     /// Process a depth update from WebSocket
@@ -343,6 +484,9 @@ impl OrderBookManager {
     /// - If u <= lastUpdateId: ignore (stale event)
     /// - If U > lastUpdateId + 1: gap detected, skip update to prevent corruption
     /// - If U <= lastUpdateId + 1 <= u: normal case, apply update
+    ///
+    /// Futures update IDs are not contiguous, so a futures event (carrying `pu`)
+    /// whose `pu` equals lastUpdateId continues the book even when U > lastUpdateId + 1.
     async fn process_depth_update(
         states: &Arc<RwLock<HashMap<String, OrderBookState>>>,
         symbol: &str,
@@ -367,8 +511,9 @@ impl OrderBookManager {
             return Ok(());
         }
 
-        // Case 2: Gap detected (U > lastUpdateId + 1) - skip to prevent corruption
-        if update.first_update_id > last_id + 1 {
+        // Case 2: Gap detected (U > lastUpdateId + 1, and for futures pu != lastUpdateId)
+        // - skip to prevent corruption
+        if update.first_update_id > last_id + 1 && update.prev_final_update_id != Some(last_id) {
             error!(
                 symbol = %symbol,
                 gap = update.first_update_id - last_id - 1,
@@ -379,6 +524,7 @@ impl OrderBookManager {
             );
             // Mark as needing resync
             state.needs_resync = true;
+            state.gaps_detected += 1;
             return Err(ManagerError::WebSocketError(
                 format!("Gap detected: expected U={}, got U={}", last_id + 1, update.first_update_id)
             ));
@@ -467,20 +613,34 @@ impl OrderBookManager {
             .max()
             .unwrap_or(0);
 
+        // Oldest book past its own symbol's threshold
+        let stalest = states
+            .iter()
+            .map(|(symbol, s)| (symbol, now - s.last_update_time))
+            .filter(|(symbol, age_ms)| *age_ms > self.staleness.threshold_ms(symbol))
+            .max_by_key(|(_, age_ms)| *age_ms);
+
         // Determine status
         let (status, reason) = if active_count == 0 {
             (HealthStatus::Ok, None)
+        } else if !self.source.is_live() {
+            (
+                HealthStatus::Ok,
+                Some("Replay mode: serving stored snapshots".to_string()),
+            )
         } else if connected_count == 0 {
             (
                 HealthStatus::Error,
                 Some("All WebSocket connections down".to_string()),
             )
-        } else if max_age_ms > STALENESS_THRESHOLD_MS {
+        } else if let Some((symbol, age_ms)) = stalest {
             (
                 HealthStatus::Degraded,
                 Some(format!(
-                    "Data is stale ({}ms old), may need refresh",
-                    max_age_ms
+                    "{} data is stale ({}ms old, threshold {}ms), may need refresh",
+                    symbol,
+                    age_ms,
+                    self.staleness.threshold_ms(symbol)
                 )),
             )
         } else if connected_count < active_count {
@@ -495,6 +655,17 @@ impl OrderBookManager {
             (HealthStatus::Ok, None)
         };
 
+        let mut streams: Vec<StreamHealth> = states
+            .iter()
+            .map(|(symbol, state)| StreamHealth {
+                symbol: symbol.clone(),
+                connected: state.websocket_connected,
+                reconnects: state.stream_stats.reconnects(),
+                last_connected_at: state.stream_stats.last_connected_at(),
+            })
+            .collect();
+        streams.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+
         OrderBookHealth {
             status,
             orderbook_symbols_active: active_count,
@@ -502,6 +673,58 @@ impl OrderBookManager {
             websocket_connected: connected_count > 0,
             timestamp: now,
             reason,
+            websocket_reconnects: self.websocket_reconnect_count(),
+            streams,
+        }
+    }
+
+    /// Sequence integrity of a tracked symbol's book, `None` if not tracked
+    ///
+    /// Reads the cached state only; never subscribes or resyncs.
+    pub async fn get_integrity(&self, symbol: &str) -> Option<OrderBookIntegrity> {
+        let symbol_upper = symbol.to_uppercase();
+        let states = self.states.read().await;
+        let state = states.get(&symbol_upper)?;
+
+        let crossed = matches!(
+            (state.order_book.best_bid(), state.order_book.best_ask()),
+            (Some(bid), Some(ask)) if ask <= bid
+        );
+
+        Some(OrderBookIntegrity {
+            symbol: symbol_upper,
+            last_update_id: state.order_book.last_update_id,
+            gaps_detected: state.gaps_detected,
+            resyncs: state.resyncs,
+            crossed,
+            resync_pending: state.needs_resync,
+            timestamp: chrono::Utc::now().timestamp_millis(),
+        })
+    }
+
+    /// Track `symbol` with an empty book and the given connection state (tests only)
+    #[cfg(test)]
+    pub(crate) async fn insert_test_state(&self, symbol: &str, websocket_connected: bool) {
+        let state = OrderBookState {
+            websocket_connected,
+            ..OrderBookState::detached(OrderBook::new(symbol.to_string()))
+        };
+        self.states.write().await.insert(symbol.to_string(), state);
+    }
+
+    /// Attach `handle` as the depth stream task of a tracked symbol (tests only)
+    #[cfg(test)]
+    pub(crate) async fn attach_test_stream(&self, symbol: &str, handle: JoinHandle<()>) {
+        if let Some(state) = self.states.write().await.get_mut(symbol) {
+            state.websocket_handle = Some(handle);
+        }
+    }
+
+    /// Backdate the last update of a tracked symbol by `age_ms` (tests only)
+    #[cfg(test)]
+    pub(crate) async fn age_test_state(&self, symbol: &str, age_ms: i64) {
+        if let Some(state) = self.states.write().await.get_mut(symbol) {
+            state.last_update_time = chrono::Utc::now().timestamp_millis() - age_ms;
         }
     }
 }
@@ -519,4 +742,158 @@ mod tests {
     fn test_staleness_threshold() {
         assert_eq!(STALENESS_THRESHOLD_MS, 5000);
     }
+
+    #[test]
+    fn test_invalid_snapshot_depth_rejected() {
+        let client = Arc::new(BinanceClient::new());
+        for depth in SNAPSHOT_DEPTHS {
+            assert!(OrderBookManager::with_snapshot_depth(Arc::clone(&client), depth).is_ok());
+        }
+        for depth in [0, 1, 99, 200, 10_000] {
+            assert!(matches!(
+                OrderBookManager::with_snapshot_depth(Arc::clone(&client), depth),
+                Err(ManagerError::InvalidSnapshotDepth(d)) if d == depth
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unsubscribe_aborts_stream_and_drops_state() {
+        let manager = OrderBookManager::new(Arc::new(BinanceClient::new()));
+        manager.insert_test_state("BTCUSDT", true).await;
+        manager.insert_test_state("ETHUSDT", true).await;
+
+        // Stand-in stream task; the sender is dropped when the task is aborted
+        let (stream_alive, stream_stopped) = tokio::sync::oneshot::channel::<()>();
+        let stream = tokio::spawn(async move {
+            let _stream_alive = stream_alive;
+            std::future::pending::<()>().await
+        });
+        manager.attach_test_stream("BTCUSDT", stream).await;
+
+        assert!(manager.unsubscribe("btcusdt").await);
+        assert_eq!(manager.active_symbol_count().await, 1);
+        assert!(!manager.is_subscribed("BTCUSDT").await);
+        assert!(stream_stopped.await.is_err(), "stream task still running");
+
+        assert!(!manager.unsubscribe("BTCUSDT").await);
+        assert!(manager.is_subscribed("ETHUSDT").await);
+    }
+
+    /// Live source always serving the same book
+    struct FixedSnapshotSource(OrderBook);
+
+    #[tonic::async_trait]
+    impl SnapshotSource for FixedSnapshotSource {
+        async fn fetch_snapshot(&self, _symbol: &str) -> Result<OrderBook, ManagerError> {
+            Ok(self.0.clone())
+        }
+    }
+
+    fn depth_update(first_update_id: i64, final_update_id: i64) -> DepthUpdateEvent {
+        DepthUpdateEvent {
+            event_type: "depthUpdate".to_string(),
+            event_time: 0,
+            symbol: "BTCUSDT".to_string(),
+            first_update_id,
+            final_update_id,
+            prev_final_update_id: None,
+            bids: vec![["100.0".to_string(), "1.0".to_string()]],
+            asks: vec![["101.0".to_string(), "1.0".to_string()]],
+        }
+    }
+
+    #[tokio::test]
+    async fn test_gap_and_resync_are_counted() {
+        let mut snapshot = OrderBook::new("BTCUSDT".to_string());
+        snapshot.last_update_id = 500;
+        let manager = OrderBookManager::with_source(Arc::new(FixedSnapshotSource(snapshot)));
+        manager.insert_test_state("BTCUSDT", true).await;
+
+        // Contiguous update from the empty book (last id 0)
+        OrderBookManager::process_depth_update(&manager.states, "BTCUSDT", depth_update(1, 10))
+            .await
+            .unwrap();
+        let integrity = manager.get_integrity("BTCUSDT").await.unwrap();
+        assert_eq!(integrity.last_update_id, 10);
+        assert_eq!((integrity.gaps_detected, integrity.resyncs), (0, 0));
+        assert!(!integrity.crossed);
+
+        // U=20 skips ids 11-19
+        assert!(OrderBookManager::process_depth_update(
+            &manager.states,
+            "BTCUSDT",
+            depth_update(20, 25)
+        )
+        .await
+        .is_err());
+        let integrity = manager.get_integrity("BTCUSDT").await.unwrap();
+        assert_eq!(integrity.gaps_detected, 1);
+        assert_eq!(integrity.resyncs, 0);
+        assert!(integrity.resync_pending);
+        assert_eq!(integrity.last_update_id, 10);
+
+        // The next read recovers from a fresh snapshot
+        let book = manager.get_order_book("BTCUSDT").await.unwrap();
+        assert_eq!(book.last_update_id, 500);
+        let integrity = manager.get_integrity("BTCUSDT").await.unwrap();
+        assert_eq!((integrity.gaps_detected, integrity.resyncs), (1, 1));
+        assert!(!integrity.resync_pending);
+
+        assert!(manager.get_integrity("ETHUSDT").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_futures_update_continues_on_matching_pu() {
+        let manager = OrderBookManager::new(Arc::new(BinanceClient::new()));
+        manager.insert_test_state("BTCUSDT", true).await;
+        OrderBookManager::process_depth_update(&manager.states, "BTCUSDT", depth_update(1, 10))
+            .await
+            .unwrap();
+
+        // Futures ids are not contiguous: U=20 follows u=10 because pu=10
+        let mut update = depth_update(20, 25);
+        update.prev_final_update_id = Some(10);
+        OrderBookManager::process_depth_update(&manager.states, "BTCUSDT", update)
+            .await
+            .unwrap();
+        let integrity = manager.get_integrity("BTCUSDT").await.unwrap();
+        assert_eq!(integrity.last_update_id, 25);
+        assert_eq!(integrity.gaps_detected, 0);
+        assert!(!integrity.resync_pending);
+
+        // A pu that doesn't match the last applied u is still a gap
+        let mut update = depth_update(40, 45);
+        update.prev_final_update_id = Some(30);
+        assert!(OrderBookManager::process_depth_update(&manager.states, "BTCUSDT", update)
+            .await
+            .is_err());
+        let integrity = manager.get_integrity("BTCUSDT").await.unwrap();
+        assert_eq!(integrity.last_update_id, 25);
+        assert_eq!(integrity.gaps_detected, 1);
+    }
+
+    #[tokio::test]
+    async fn test_per_symbol_staleness_threshold() {
+        let mut client = BinanceClient::new();
+        client.base_url = "http://127.0.0.1:1".to_string();
+        let manager = OrderBookManager::new(Arc::new(client))
+            .with_staleness(StalenessConfig::default().with_override("XLMBTC", 60_000));
+
+        manager.insert_test_state("BTCUSDT", true).await;
+        manager.insert_test_state("XLMBTC", true).await;
+        manager.age_test_state("BTCUSDT", 10_000).await;
+        manager.age_test_state("XLMBTC", 10_000).await;
+
+        // Same age: only the symbol on the default 5s threshold is stale
+        let health = manager.get_health().await;
+        assert!(matches!(health.status, HealthStatus::Degraded));
+        let reason = health.reason.unwrap();
+        assert!(reason.starts_with("BTCUSDT data is stale"), "{}", reason);
+
+        // The illiquid symbol is served from cache; the stale one falls back to
+        // REST, which is unreachable here
+        assert!(manager.get_order_book("XLMBTC").await.is_ok());
+        assert!(manager.get_order_book("BTCUSDT").await.is_err());
+    }
 }
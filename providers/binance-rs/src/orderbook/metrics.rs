@@ -12,27 +12,68 @@ use crate::orderbook::types::{
     OrderBook, OrderBookDepth, OrderBookMetrics, SlippageEstimate, SlippageEstimates, Wall,
     WallSide, Walls,
 };
-use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
 use rust_decimal::Decimal;
 use std::collections::BTreeMap;
 
-/// Number of top levels to analyze for volume calculations
-const TOP_LEVELS: usize = 20;
+/// Default number of top levels feeding volume, imbalance and microprice
+pub const DEFAULT_DEPTH_LEVELS: usize = 20;
+
+/// Maximum number of levels accepted for `depth_levels`
+pub const MAX_DEPTH_LEVELS: usize = 100;
+
+/// Number of top levels used for wall detection (median baseline)
+const WALL_LEVELS: usize = 20;
 
 /// Target USD amounts for slippage estimates
 const SLIPPAGE_TARGETS_USD: [f64; 3] = [10_000.0, 25_000.0, 50_000.0];
 
+/// Order sizes in USD for a slippage curve when none are given
+pub const DEFAULT_SLIPPAGE_CURVE_USD: [f64; 4] = [1_000.0, 10_000.0, 100_000.0, 1_000_000.0];
+
+/// Maximum number of order sizes in one slippage curve
+pub const MAX_SLIPPAGE_CURVE_POINTS: usize = 20;
+
+/// Default wall threshold for live books as a multiple of the median level quantity
+pub const DEFAULT_WALL_MEDIAN_MULTIPLIER: f64 = 2.0;
+
 /// Scaling factor for prices (fixed at 100)
 const PRICE_SCALE: i32 = 100;
 
 /// Scaling factor for quantities (fixed at 100000)
 const QTY_SCALE: i32 = 100_000;
 
+/// Widest accepted depth bucket in basis points (10%)
+pub const MAX_BUCKET_BPS: f64 = 1000.0;
+
 /// Calculate L1 aggregated metrics for an order book
 ///
 /// Returns comprehensive metrics including spread, microprice, imbalance,
 /// walls, and slippage estimates.
 pub fn calculate_metrics(order_book: &OrderBook) -> Option<OrderBookMetrics> {
+    calculate_metrics_with_wall_multiplier(order_book, DEFAULT_WALL_MEDIAN_MULTIPLIER)
+}
+
+/// Calculate L1 metrics, flagging walls above `wall_multiplier` x median level quantity
+pub fn calculate_metrics_with_wall_multiplier(
+    order_book: &OrderBook,
+    wall_multiplier: f64,
+) -> Option<OrderBookMetrics> {
+    calculate_metrics_at_depth(order_book, DEFAULT_DEPTH_LEVELS, wall_multiplier)
+}
+
+/// Calculate L1 metrics with volume, imbalance and microprice over the top `depth_levels`
+///
+/// # Arguments
+/// * `order_book` - Book to analyze
+/// * `depth_levels` - Levels per side summed into `bid_volume`/`ask_volume` (clamped to 1-100)
+/// * `wall_multiplier` - Wall threshold as a multiple of the top-20 median level quantity
+pub fn calculate_metrics_at_depth(
+    order_book: &OrderBook,
+    depth_levels: usize,
+    wall_multiplier: f64,
+) -> Option<OrderBookMetrics> {
+    let depth_levels = depth_levels.clamp(1, MAX_DEPTH_LEVELS);
     let best_bid = order_book.best_bid()?;
     let best_ask = order_book.best_ask()?;
 
@@ -47,24 +88,30 @@ pub fn calculate_metrics(order_book: &OrderBook) -> Option<OrderBookMetrics> {
     // Calculate spread in basis points: ((ask - bid) / bid) * 10000
     let spread_bps = calculate_spread_bps(*best_bid, *best_ask)?;
 
-    // Get top 20 levels for volume calculations
+    // Get top levels for volume and wall calculations
     let top_bids: Vec<(&Decimal, &Decimal)> = order_book
         .bids
         .iter()
         .rev() // BTreeMap is ascending, we want highest bids first
-        .take(TOP_LEVELS)
+        .take(depth_levels.max(WALL_LEVELS))
         .collect();
 
-    let top_asks: Vec<(&Decimal, &Decimal)> = order_book.asks.iter().take(TOP_LEVELS).collect();
+    let top_asks: Vec<(&Decimal, &Decimal)> = order_book
+        .asks
+        .iter()
+        .take(depth_levels.max(WALL_LEVELS))
+        .collect();
 
-    // Calculate total volumes
+    // Calculate total volumes over the requested depth
     let bid_volume = top_bids
         .iter()
+        .take(depth_levels)
         .map(|(_, qty)| qty.to_f64().unwrap_or(0.0))
         .sum::<f64>();
 
     let ask_volume = top_asks
         .iter()
+        .take(depth_levels)
         .map(|(_, qty)| qty.to_f64().unwrap_or(0.0))
         .sum::<f64>();
 
@@ -75,14 +122,12 @@ pub fn calculate_metrics(order_book: &OrderBook) -> Option<OrderBookMetrics> {
     let mid_price = (best_bid.to_f64()? + best_ask.to_f64()?) / 2.0;
 
     // Calculate imbalance ratio
-    let imbalance_ratio = if ask_volume > 0.0 {
-        bid_volume / ask_volume
-    } else {
-        0.0
-    };
+    let imbalance_ratio = calculate_imbalance_ratio(bid_volume, ask_volume);
 
     // Detect walls
-    let walls = detect_walls(&top_bids, &top_asks);
+    let wall_bids = &top_bids[..top_bids.len().min(WALL_LEVELS)];
+    let wall_asks = &top_asks[..top_asks.len().min(WALL_LEVELS)];
+    let walls = detect_walls(wall_bids, wall_asks, wall_multiplier);
 
     // Calculate slippage estimates
     let slippage_estimates =
@@ -98,6 +143,7 @@ pub fn calculate_metrics(order_book: &OrderBook) -> Option<OrderBookMetrics> {
         bid_volume,
         ask_volume,
         imbalance_ratio,
+        depth_levels,
         // OrderBook data is correct - no swap needed
         best_bid: best_bid.to_string(),
         best_ask: best_ask.to_string(),
@@ -108,11 +154,23 @@ pub fn calculate_metrics(order_book: &OrderBook) -> Option<OrderBookMetrics> {
     })
 }
 
+/// Bid/ask volume imbalance ratio
+///
+/// Formula: bid_volume / ask_volume (0.0 when there is no ask volume).
+/// Above 1.0 means more resting bid volume than ask volume.
+pub fn calculate_imbalance_ratio(bid_volume: f64, ask_volume: f64) -> f64 {
+    if ask_volume > 0.0 {
+        bid_volume / ask_volume
+    } else {
+        0.0
+    }
+}
+
 /// Calculate spread in basis points
 ///
 /// Formula: ((best_ask - best_bid) / best_bid) * 10000
 /// Accuracy: within 0.01 bps
-fn calculate_spread_bps(best_bid: Decimal, best_ask: Decimal) -> Option<f64> {
+pub(crate) fn calculate_spread_bps(best_bid: Decimal, best_ask: Decimal) -> Option<f64> {
     if best_bid.is_zero() {
         return None;
     }
@@ -147,8 +205,12 @@ fn calculate_microprice(
     Some(microprice)
 }
 
-/// Detect walls (levels with qty > 2x median of top 20 levels)
-fn detect_walls(top_bids: &[(&Decimal, &Decimal)], top_asks: &[(&Decimal, &Decimal)]) -> Walls {
+/// Detect walls (levels with qty > `wall_multiplier` x median of top 20 levels)
+fn detect_walls(
+    top_bids: &[(&Decimal, &Decimal)],
+    top_asks: &[(&Decimal, &Decimal)],
+    wall_multiplier: f64,
+) -> Walls {
     // Calculate median quantity across all top levels
     let mut all_qtys: Vec<f64> = top_bids
         .iter()
@@ -169,7 +231,8 @@ fn detect_walls(top_bids: &[(&Decimal, &Decimal)], top_asks: &[(&Decimal, &Decim
         }
     };
 
-    let threshold = median_qty * 2.0;
+    let threshold = median_qty * wall_multiplier;
+    let median_multiple = |qty: f64| if median_qty > 0.0 { qty / median_qty } else { 0.0 };
 
     // Find bid walls
     let bid_walls: Vec<Wall> = top_bids
@@ -180,6 +243,7 @@ fn detect_walls(top_bids: &[(&Decimal, &Decimal)], top_asks: &[(&Decimal, &Decim
                 Some(Wall {
                     price: price.to_string(),
                     qty: qty.to_string(),
+                    median_multiple: median_multiple(qty_f64),
                     side: WallSide::Bid,
                 })
             } else {
@@ -197,6 +261,7 @@ fn detect_walls(top_bids: &[(&Decimal, &Decimal)], top_asks: &[(&Decimal, &Decim
                 Some(Wall {
                     price: price.to_string(),
                     qty: qty.to_string(),
+                    median_multiple: median_multiple(qty_f64),
                     side: WallSide::Ask,
                 })
             } else {
@@ -241,6 +306,42 @@ fn calculate_slippage_estimates(
     }
 }
 
+/// Estimate slippage for an arbitrary USD order size against the cached book
+///
+/// Walks asks for buys and bids for sells, starting from the best level.
+/// `filled_usd` in the result is lower than `notional_usd` when the book is too
+/// thin to absorb the whole order.
+///
+/// Returns `None` if the relevant side of the book is empty.
+pub fn estimate_slippage(
+    order_book: &OrderBook,
+    notional_usd: f64,
+    is_sell: bool,
+) -> Option<SlippageEstimate> {
+    let (levels, best_price) = if is_sell {
+        (&order_book.bids, order_book.best_bid()?)
+    } else {
+        (&order_book.asks, order_book.best_ask()?)
+    };
+
+    calculate_slippage_for_amount(levels, notional_usd, best_price.to_f64()?, is_sell)
+}
+
+/// Estimate slippage at each of `sizes_usd` on one side of the book
+///
+/// Points keep the order of `sizes_usd`, including sizes the book is too thin
+/// to fill. Returns an empty curve if the relevant side of the book is empty.
+pub fn slippage_curve(
+    order_book: &OrderBook,
+    sizes_usd: &[f64],
+    is_sell: bool,
+) -> Vec<SlippageEstimate> {
+    sizes_usd
+        .iter()
+        .filter_map(|&size| estimate_slippage(order_book, size, is_sell))
+        .collect()
+}
+
 /// Calculate slippage for a target USD amount
 ///
 /// For sells: iterate bids from best (highest) to worst (lowest)
@@ -347,9 +448,68 @@ pub fn extract_depth(order_book: &OrderBook, levels: usize) -> OrderBookDepth {
         qty_scale: QTY_SCALE,
         bids,
         asks,
+        bucket_bps: None,
     }
 }
 
+/// Extract L2 depth with adjacent levels grouped into `bucket_bps`-wide buckets
+///
+/// The top `levels` levels per side are bucketed by distance from that side's
+/// best price (bucket width = best price × `bucket_bps` / 10000), summing their
+/// quantities. Each bucket is reported at its level nearest the touch, so the
+/// total quantity per side matches [`extract_depth`] with the same `levels`.
+pub fn extract_bucketed_depth(
+    order_book: &OrderBook,
+    levels: usize,
+    bucket_bps: f64,
+) -> OrderBookDepth {
+    let bids = bucket_levels(order_book.bids.iter().rev().take(levels), bucket_bps);
+    let asks = bucket_levels(order_book.asks.iter().take(levels), bucket_bps);
+
+    OrderBookDepth {
+        symbol: order_book.symbol.clone(),
+        timestamp: order_book.timestamp,
+        price_scale: PRICE_SCALE,
+        qty_scale: QTY_SCALE,
+        bids,
+        asks,
+        bucket_bps: Some(bucket_bps),
+    }
+}
+
+/// Group levels (ordered from the touch outward) into compact-encoded buckets
+fn bucket_levels<'a>(
+    levels: impl Iterator<Item = (&'a Decimal, &'a Decimal)>,
+    bucket_bps: f64,
+) -> Vec<[i64; 2]> {
+    let bucket_fraction =
+        Decimal::from_f64(bucket_bps).unwrap_or(Decimal::ZERO) / Decimal::from(10_000);
+
+    // (bucket index, reported price, summed quantity)
+    let mut buckets: Vec<(Decimal, Decimal, Decimal)> = Vec::new();
+    let mut anchor: Option<(Decimal, Decimal)> = None;
+
+    for (price, qty) in levels {
+        let (touch, width) = *anchor.get_or_insert((*price, *price * bucket_fraction));
+        let distance = (*price - touch).abs();
+        let index = if width > Decimal::ZERO {
+            (distance / width).floor()
+        } else {
+            distance
+        };
+
+        match buckets.last_mut() {
+            Some((last_index, _, total)) if *last_index == index => *total += *qty,
+            _ => buckets.push((index, *price, *qty)),
+        }
+    }
+
+    buckets
+        .into_iter()
+        .filter_map(|(_, price, qty)| encode_level(price, qty))
+        .collect()
+}
+
 /// Encode a price level as compact integers
 fn encode_level(price: Decimal, qty: Decimal) -> Option<[i64; 2]> {
     // Scale price by 100: 67650.00 → 6765000
@@ -402,6 +562,35 @@ mod tests {
         assert_eq!(encoded[1], 123400); // qty * 100000
     }
 
+    #[test]
+    fn test_bucketed_depth_conserves_quantity() {
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        for i in 0..10 {
+            let offset = Decimal::new(i, 2); // 0.00 - 0.09
+            book.update_bid(Decimal::from(100) - offset, Decimal::from(i + 1));
+            book.update_ask(Decimal::new(10001, 2) + offset, Decimal::from(i + 1));
+        }
+
+        let raw = extract_depth(&book, 10);
+        // 5 bps of 100 = 0.05 wide buckets: five levels each
+        let bucketed = extract_bucketed_depth(&book, 10, 5.0);
+
+        let total = |levels: &[[i64; 2]]| levels.iter().map(|level| level[1]).sum::<i64>();
+        let sides = [(&raw.bids, &bucketed.bids), (&raw.asks, &bucketed.asks)];
+        for (raw_side, bucketed_side) in sides {
+            assert_eq!(raw_side.len(), 10);
+            assert_eq!(bucketed_side.len(), 2);
+            assert_eq!(total(bucketed_side), total(raw_side));
+        }
+
+        // Buckets are reported at their level nearest the touch
+        assert_eq!(bucketed.bids[0], [10000, 15 * QTY_SCALE as i64]);
+        assert_eq!(bucketed.bids[1][0], 9995);
+        assert_eq!(bucketed.asks[0][0], 10001);
+        assert_eq!(bucketed.bucket_bps, Some(5.0));
+        assert_eq!(raw.bucket_bps, None);
+    }
+
     #[test]
     fn test_walls_detection() {
         // Create test data with owned values
@@ -431,7 +620,70 @@ mod tests {
         let asks: Vec<(&Decimal, &Decimal)> =
             ask_data.iter().map(|(price, qty)| (price, qty)).collect();
 
-        let walls = detect_walls(&bids, &asks);
+        let walls = detect_walls(&bids, &asks, DEFAULT_WALL_MEDIAN_MULTIPLIER);
         assert!(!walls.bids.is_empty(), "Should detect bid wall");
+        assert!((walls.bids[0].median_multiple - 10.0).abs() < 1e-9);
+
+        let walls = detect_walls(&bids, &asks, 12.0);
+        assert!(walls.bids.is_empty(), "10x level is below a 12x threshold");
+    }
+
+    #[test]
+    fn test_wall_multiplier_controls_classification() {
+        let bid_data: Vec<(Decimal, Decimal)> = (0..10)
+            .map(|i| {
+                let price = Decimal::from(67650 - i);
+                let qty = if i == 3 {
+                    Decimal::from_str("2.5").unwrap()
+                } else {
+                    Decimal::ONE
+                };
+                (price, qty)
+            })
+            .collect();
+        let ask_data: Vec<(Decimal, Decimal)> = (0..10)
+            .map(|i| (Decimal::from(67651 + i), Decimal::ONE))
+            .collect();
+
+        let bids: Vec<(&Decimal, &Decimal)> = bid_data.iter().map(|(p, q)| (p, q)).collect();
+        let asks: Vec<(&Decimal, &Decimal)> = ask_data.iter().map(|(p, q)| (p, q)).collect();
+
+        // 2.5x the median: a wall at 2x, not at 3x
+        let walls = detect_walls(&bids, &asks, 2.0);
+        assert_eq!(walls.bids.len(), 1);
+        assert_eq!(walls.bids[0].price, "67647");
+
+        let walls = detect_walls(&bids, &asks, 3.0);
+        assert!(walls.bids.is_empty());
+    }
+
+    #[test]
+    fn test_imbalance_depends_on_depth_levels() {
+        // Top 5 bids are heavy, deeper bids are thin; asks are uniform
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        for i in 0..20 {
+            let bid_qty = if i < 5 { "4.0" } else { "0.5" };
+            book.bids.insert(
+                Decimal::from(67650 - i),
+                Decimal::from_str(bid_qty).unwrap(),
+            );
+            book.asks
+                .insert(Decimal::from(67651 + i), Decimal::from_str("1.0").unwrap());
+        }
+
+        let shallow = calculate_metrics_at_depth(&book, 5, DEFAULT_WALL_MEDIAN_MULTIPLIER).unwrap();
+        let deep = calculate_metrics_at_depth(&book, 20, DEFAULT_WALL_MEDIAN_MULTIPLIER).unwrap();
+
+        assert_eq!(shallow.depth_levels, 5);
+        assert_eq!(deep.depth_levels, 20);
+        assert!((shallow.bid_volume - 20.0).abs() < 1e-9);
+        assert!((shallow.imbalance_ratio - 4.0).abs() < 1e-9);
+        assert!((deep.imbalance_ratio - 27.5 / 20.0).abs() < 1e-9);
+        assert!(shallow.imbalance_ratio != deep.imbalance_ratio);
+        assert!(shallow.microprice != deep.microprice);
+
+        // Default entry point keeps the 20-level behavior
+        let default = calculate_metrics(&book).unwrap();
+        assert_eq!(default.imbalance_ratio, deep.imbalance_ratio);
     }
 }
@@ -9,6 +9,7 @@
 //! - Sub-100ms latency for warm requests via local cache
 //! - Lazy initialization: subscribe on first request per symbol
 //! - Up to 20 concurrent symbols with client-side rate limiting
+//! - Replay mode: stored snapshots served in place of live streams
 
 #[cfg(feature = "orderbook")]
 pub mod types;
@@ -25,6 +26,15 @@ pub mod websocket;
 #[cfg(feature = "orderbook")]
 pub mod rate_limiter;
 
+#[cfg(feature = "orderbook")]
+pub mod source;
+
+#[cfg(feature = "orderbook")]
+pub mod replay;
+
+#[cfg(feature = "orderbook")]
+pub mod health_webhook;
+
 #[cfg(feature = "orderbook")]
 pub mod tools;
 
@@ -33,8 +43,8 @@ pub mod analytics;
 
 #[cfg(feature = "orderbook")]
 pub use types::{
-    OrderBook, OrderBookDepth, OrderBookHealth, OrderBookMetrics, SlippageEstimate,
-    SlippageEstimates, Wall,
+    OrderBook, OrderBookDepth, OrderBookHealth, OrderBookIntegrity, OrderBookMetrics,
+    SlippageCurve, SlippageEstimate, SlippageEstimates, StreamHealth, Wall,
 };
 
 #[cfg(feature = "orderbook")]
@@ -0,0 +1,197 @@
+//! Replay of stored order book snapshots
+//!
+//! [`ReplayFeed`] pushes historical books into an [`OrderBookManager`] built with
+//! [`ReplaySnapshotSource`](super::source::ReplaySnapshotSource). Books are applied
+//! in capture order, spaced by their recorded timestamps divided by the speed
+//! factor, so tools and reports read the books exactly as they were captured.
+//!
+//! Only the books are replayed: analytics queries and report windows still end
+//! at `Utc::now()`, so they see replayed data only for a recent replay window.
+
+use crate::orderbook::manager::{ManagerError, OrderBookManager};
+use crate::orderbook::types::OrderBook;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::info;
+
+/// Ordered queue of historical books replayed at `speed`× wall-clock time
+pub struct ReplayFeed {
+    books: VecDeque<OrderBook>,
+    speed: f64,
+    /// Capture time (ms) of the last applied book
+    last_timestamp: Option<i64>,
+}
+
+impl ReplayFeed {
+    /// Create a feed from books of any symbols, sorted by capture time
+    ///
+    /// # Errors
+    /// Returns error if `speed` is not a positive finite number
+    pub fn new(mut books: Vec<OrderBook>, speed: f64) -> Result<Self, String> {
+        if !speed.is_finite() || speed <= 0.0 {
+            return Err(format!("Replay speed must be positive, got {}", speed));
+        }
+
+        // Stable sort keeps storage order for books captured in the same millisecond
+        books.sort_by_key(|book| (book.timestamp, book.last_update_id));
+
+        Ok(Self {
+            books: books.into(),
+            speed,
+            last_timestamp: None,
+        })
+    }
+
+    /// Books not yet applied
+    pub fn remaining(&self) -> usize {
+        self.books.len()
+    }
+
+    /// Wall-clock delay before the next book is due (zero for the first one)
+    pub fn next_delay(&self) -> Duration {
+        match (self.last_timestamp, self.books.front()) {
+            (Some(last), Some(next)) => {
+                let gap_ms = (next.timestamp - last).max(0) as f64;
+                Duration::from_secs_f64(gap_ms / 1000.0 / self.speed)
+            }
+            _ => Duration::ZERO,
+        }
+    }
+
+    /// Apply the next book to `manager` immediately
+    ///
+    /// # Returns
+    /// Capture time (ms) of the applied book, or None once the feed is exhausted
+    pub async fn step(&mut self, manager: &OrderBookManager) -> Result<Option<i64>, ManagerError> {
+        let Some(book) = self.books.pop_front() else {
+            return Ok(None);
+        };
+        let timestamp = book.timestamp;
+        manager.apply_snapshot(book).await?;
+        self.last_timestamp = Some(timestamp);
+        Ok(Some(timestamp))
+    }
+
+    /// Apply all books, sleeping [`next_delay`](Self::next_delay) between them
+    ///
+    /// # Returns
+    /// Number of books applied
+    pub async fn run(mut self, manager: Arc<OrderBookManager>) -> Result<usize, ManagerError> {
+        let total = self.remaining();
+        info!(
+            books = total,
+            speed = self.speed,
+            "Starting order book replay"
+        );
+
+        while self.remaining() > 0 {
+            tokio::time::sleep(self.next_delay()).await;
+            self.step(&manager).await?;
+        }
+
+        info!(books = total, "Order book replay finished");
+        Ok(total)
+    }
+}
+
+/// Load stored snapshots of `symbols` captured in `[from_sec, to_sec]` as books
+#[cfg(feature = "orderbook_analytics")]
+pub async fn load_stored_books(
+    storage: &crate::orderbook::analytics::SnapshotStorage,
+    symbols: &[String],
+    from_sec: i64,
+    to_sec: i64,
+) -> anyhow::Result<Vec<OrderBook>> {
+    use crate::orderbook::analytics::storage::query::query_snapshots_in_window;
+
+    let mut books = Vec::new();
+    for symbol in symbols {
+        let snapshots = query_snapshots_in_window(storage, symbol, from_sec, to_sec).await?;
+        for snapshot in &snapshots {
+            books.push(snapshot.to_orderbook(symbol)?);
+        }
+    }
+    Ok(books)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::orderbook::source::ReplaySnapshotSource;
+    use crate::orderbook::types::HealthStatus;
+    use rust_decimal::Decimal;
+
+    fn book(timestamp: i64, update_id: i64, best_bid: i64) -> OrderBook {
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        book.timestamp = timestamp;
+        book.last_update_id = update_id;
+        book.bids.insert(Decimal::from(best_bid), Decimal::ONE);
+        book.asks.insert(Decimal::from(best_bid + 1), Decimal::ONE);
+        book
+    }
+
+    fn replay_manager() -> OrderBookManager {
+        OrderBookManager::with_source(Arc::new(ReplaySnapshotSource))
+    }
+
+    #[tokio::test]
+    async fn test_replay_serves_snapshots_in_order() {
+        let manager = replay_manager();
+        let mut feed = ReplayFeed::new(
+            vec![
+                book(3_000, 3, 102),
+                book(1_000, 1, 100),
+                book(2_000, 2, 101),
+            ],
+            1.0,
+        )
+        .unwrap();
+
+        // Nothing replayed yet: no REST fallback
+        assert!(matches!(
+            manager.get_order_book("BTCUSDT").await,
+            Err(ManagerError::SymbolNotFound(_))
+        ));
+
+        for (expected_ts, expected_bid) in [(1_000, 100), (2_000, 101), (3_000, 102)] {
+            assert_eq!(feed.step(&manager).await.unwrap(), Some(expected_ts));
+            let served = manager.get_order_book("btcusdt").await.unwrap();
+            assert_eq!(served.timestamp, expected_ts);
+            assert_eq!(served.best_bid(), Some(&Decimal::from(expected_bid)));
+        }
+        assert_eq!(feed.step(&manager).await.unwrap(), None);
+
+        // Replayed books have no depth stream, which is not a health error
+        let health = manager.get_health().await;
+        assert_eq!(health.orderbook_symbols_active, 1);
+        assert!(matches!(health.status, HealthStatus::Ok));
+        assert!(!manager.is_live());
+    }
+
+    #[tokio::test]
+    async fn test_replay_spacing_scales_with_speed() {
+        let manager = replay_manager();
+        let mut feed = ReplayFeed::new(vec![book(0, 1, 100), book(2_000, 2, 101)], 4.0).unwrap();
+
+        assert_eq!(feed.next_delay(), Duration::ZERO);
+        feed.step(&manager).await.unwrap();
+        assert_eq!(feed.next_delay(), Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_run_applies_every_book() {
+        let manager = Arc::new(replay_manager());
+        let feed = ReplayFeed::new(vec![book(0, 1, 100), book(10, 2, 101)], 100.0).unwrap();
+
+        assert_eq!(feed.run(manager.clone()).await.unwrap(), 2);
+        let served = manager.get_order_book("BTCUSDT").await.unwrap();
+        assert_eq!(served.last_update_id, 2);
+    }
+
+    #[test]
+    fn test_rejects_non_positive_speed() {
+        assert!(ReplayFeed::new(Vec::new(), 0.0).is_err());
+        assert!(ReplayFeed::new(Vec::new(), f64::NAN).is_err());
+    }
+}
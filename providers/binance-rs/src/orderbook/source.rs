@@ -0,0 +1,160 @@
+//! Snapshot sources feeding the order book manager
+//!
+//! [`OrderBookManager`](super::OrderBookManager) obtains full snapshots through a
+//! [`SnapshotSource`]:
+//! - [`RestSnapshotSource`]: live mode, REST depth snapshot (`/api/v3/depth`, or
+//!   `/fapi/v1/depth` for futures) kept current by the depth WebSocket stream (default)
+//! - [`ReplaySnapshotSource`]: replay mode, books are pushed by a
+//!   [`ReplayFeed`](super::replay::ReplayFeed) and never fetched or streamed
+
+use crate::binance::client::BinanceClient;
+use crate::binance::market::Market;
+use crate::orderbook::manager::{ManagerError, DEFAULT_SNAPSHOT_DEPTH, SNAPSHOT_DEPTHS};
+use crate::orderbook::types::OrderBook;
+use rust_decimal::Decimal;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::debug;
+
+/// Provider of full order book snapshots
+#[tonic::async_trait]
+pub trait SnapshotSource: Send + Sync {
+    /// Fetch a full snapshot for `symbol` (uppercase)
+    async fn fetch_snapshot(&self, symbol: &str) -> Result<OrderBook, ManagerError>;
+
+    /// Whether fetched books are kept current by depth WebSocket streams
+    ///
+    /// Live sources get WebSocket subscriptions, gap resyncs and the staleness
+    /// fallback; non-live books are served as last applied.
+    fn is_live(&self) -> bool {
+        true
+    }
+
+    /// Market whose depth streams keep live books current
+    fn market(&self) -> Market {
+        Market::Spot
+    }
+}
+
+/// Live snapshots from the Binance REST API
+pub struct RestSnapshotSource {
+    binance_client: Arc<BinanceClient>,
+
+    /// `limit` sent to the depth endpoint (one of [`SNAPSHOT_DEPTHS`])
+    depth: u32,
+
+    /// Market snapshots are fetched from (default: spot)
+    market: Market,
+}
+
+impl RestSnapshotSource {
+    /// Source requesting [`DEFAULT_SNAPSHOT_DEPTH`] levels per side
+    pub fn new(binance_client: Arc<BinanceClient>) -> Self {
+        Self {
+            binance_client,
+            depth: DEFAULT_SNAPSHOT_DEPTH,
+            market: Market::Spot,
+        }
+    }
+
+    /// Source requesting `depth` levels per side
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ManagerError::InvalidSnapshotDepth`] if `depth` is not one of
+    /// the limits Binance accepts ([`SNAPSHOT_DEPTHS`])
+    pub fn with_depth(
+        binance_client: Arc<BinanceClient>,
+        depth: u32,
+    ) -> Result<Self, ManagerError> {
+        if !SNAPSHOT_DEPTHS.contains(&depth) {
+            return Err(ManagerError::InvalidSnapshotDepth(depth));
+        }
+        Ok(Self {
+            binance_client,
+            depth,
+            market: Market::Spot,
+        })
+    }
+
+    /// Fetch snapshots (and stream updates) from `market` instead of spot
+    pub fn with_market(mut self, market: Market) -> Self {
+        self.market = market;
+        self
+    }
+
+    /// Levels per side requested for each snapshot
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+}
+
+#[tonic::async_trait]
+impl SnapshotSource for RestSnapshotSource {
+    async fn fetch_snapshot(&self, symbol: &str) -> Result<OrderBook, ManagerError> {
+        debug!(
+            symbol = %symbol,
+            market = ?self.market,
+            "Fetching order book snapshot from REST API"
+        );
+
+        let snapshot = self
+            .binance_client
+            .get_market_order_book(self.market, symbol, Some(self.depth))
+            .await
+            .map_err(|e| ManagerError::RestApiError(e.to_string()))?;
+
+        // Convert response to OrderBook
+        let mut order_book = OrderBook::new(symbol.to_string());
+        order_book.last_update_id = snapshot.last_update_id;
+        order_book.timestamp = chrono::Utc::now().timestamp_millis();
+
+        // Parse bids - Binance API returns Vec<(String, String)>
+        for (price_str, qty_str) in &snapshot.bids {
+            let price = Decimal::from_str(price_str)
+                .map_err(|e| ManagerError::RestApiError(format!("Invalid bid price: {}", e)))?;
+            let qty = Decimal::from_str(qty_str)
+                .map_err(|e| ManagerError::RestApiError(format!("Invalid bid qty: {}", e)))?;
+            order_book.bids.insert(price, qty);
+        }
+
+        // Parse asks - Binance API returns Vec<(String, String)>
+        for (price_str, qty_str) in &snapshot.asks {
+            let price = Decimal::from_str(price_str)
+                .map_err(|e| ManagerError::RestApiError(format!("Invalid ask price: {}", e)))?;
+            let qty = Decimal::from_str(qty_str)
+                .map_err(|e| ManagerError::RestApiError(format!("Invalid ask qty: {}", e)))?;
+            order_book.asks.insert(price, qty);
+        }
+
+        debug!(
+            symbol = %symbol,
+            bid_levels = order_book.bids.len(),
+            ask_levels = order_book.asks.len(),
+            "Fetched order book snapshot"
+        );
+
+        Ok(order_book)
+    }
+
+    fn market(&self) -> Market {
+        self.market
+    }
+}
+
+/// Replay mode: books only exist once a replayed snapshot has been applied
+pub struct ReplaySnapshotSource;
+
+#[tonic::async_trait]
+impl SnapshotSource for ReplaySnapshotSource {
+    async fn fetch_snapshot(&self, symbol: &str) -> Result<OrderBook, ManagerError> {
+        Err(ManagerError::SymbolNotFound(format!(
+            "{} (no replayed snapshot yet)",
+            symbol
+        )))
+    }
+
+    fn is_live(&self) -> bool {
+        false
+    }
+}
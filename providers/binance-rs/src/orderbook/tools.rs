@@ -1,13 +1,20 @@
 //! MCP tool handlers for order book depth analysis
 //!
-//! Provides three tools following progressive disclosure strategy:
+//! Provides tools following progressive disclosure strategy:
 //! - get_orderbook_metrics: L1 aggregated metrics (15% token cost)
 //! - get_orderbook_depth: L2 depth with compact encoding (50-100% token cost)
 //! - get_orderbook_health: Service health monitoring
+//! - get_orderbook_integrity: Depth update sequence gaps and resyncs per symbol
+//! - get_slippage_estimate: VWAP fill estimate for an arbitrary USD order size
+//! - get_slippage_curve: Slippage across a set of order sizes on both sides
+//! - subscribe_symbol / unsubscribe_symbol: Explicit control over tracked symbols
 
-use crate::orderbook::manager::{ManagerError, OrderBookManager};
+use crate::orderbook::manager::{ManagerError, OrderBookManager, MAX_CONCURRENT_SYMBOLS};
 use crate::orderbook::metrics;
-use crate::orderbook::types::{OrderBookDepth, OrderBookHealth, OrderBookMetrics};
+use crate::orderbook::types::{
+    OrderBook, OrderBookDepth, OrderBookHealth, OrderBookIntegrity, OrderBookMetrics,
+    SlippageCurve, SlippageEstimate, SubscriptionStatus,
+};
 use schemars::JsonSchema;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -19,6 +26,9 @@ pub enum OrderBookToolError {
     #[error("Symbol not found: {0}")]
     SymbolNotFound(String),
 
+    #[error("Symbol not tracked: {0}. Query or subscribe to it first")]
+    SymbolNotTracked(String),
+
     #[error("Symbol limit reached: cannot track more than 20 symbols")]
     SymbolLimitReached,
 
@@ -31,8 +41,29 @@ pub enum OrderBookToolError {
     #[error("Invalid levels parameter: {0}. Must be between 1 and 100")]
     InvalidLevels(usize),
 
+    #[error("Invalid bucket_bps: {0}. Must be greater than 0 and at most 1000")]
+    InvalidBucketWidth(f64),
+
+    #[error("Invalid wall_median_multiplier: {0}. Must be between 1.0 and 100.0")]
+    InvalidWallMultiplier(f64),
+
     #[error("Failed to calculate metrics: {0}")]
     MetricsCalculationFailed(String),
+
+    #[error("Invalid notional_usd: {0}. Must be a positive number")]
+    InvalidNotional(f64),
+
+    #[error("Invalid sizes_usd: {0} sizes given. Must be between 1 and 20")]
+    InvalidCurveSizes(usize),
+
+    #[error(
+        "Insufficient depth for {symbol}: requested ${requested_usd:.2}, only ${filled_usd:.2} could be filled"
+    )]
+    InsufficientDepth {
+        symbol: String,
+        requested_usd: f64,
+        filled_usd: f64,
+    },
 }
 
 impl From<ManagerError> for OrderBookToolError {
@@ -55,6 +86,31 @@ impl From<ManagerError> for OrderBookToolError {
                     message: e,
                 }
             }
+            e @ ManagerError::InvalidSnapshotDepth(_) => OrderBookToolError::InitializationFailed {
+                symbol: "unknown".to_string(),
+                message: e.to_string(),
+            },
+        }
+    }
+}
+
+impl From<OrderBookToolError> for crate::error::ProviderError {
+    fn from(err: OrderBookToolError) -> Self {
+        use crate::error::ProviderError;
+
+        match err {
+            OrderBookToolError::SymbolNotFound(_) | OrderBookToolError::SymbolNotTracked(_) => {
+                ProviderError::SymbolNotFound(err.to_string())
+            }
+            OrderBookToolError::RateLimitExceeded(_) => ProviderError::RateLimited(err.to_string()),
+            OrderBookToolError::InvalidLevels(_)
+            | OrderBookToolError::InvalidBucketWidth(_)
+            | OrderBookToolError::InvalidWallMultiplier(_)
+            | OrderBookToolError::InvalidNotional(_)
+            | OrderBookToolError::InvalidCurveSizes(_) => {
+                ProviderError::Validation(err.to_string())
+            }
+            _ => ProviderError::BinanceApi(err.to_string()),
         }
     }
 }
@@ -65,6 +121,20 @@ pub struct GetOrderBookMetricsParams {
     /// Trading pair symbol (e.g., "BTCUSDT")
     #[schemars(description = "Trading pair symbol (e.g., 'BTCUSDT', 'ETHUSDT')")]
     pub symbol: String,
+
+    /// Levels per side feeding volume, imbalance and microprice (1-100, default: 20)
+    #[schemars(
+        description = "Number of top levels per side used for bid/ask volume, imbalance ratio and microprice (1-100). Default: 20; use e.g. 5 for short-horizon signals"
+    )]
+    #[serde(default = "default_levels")]
+    pub depth_levels: usize,
+
+    /// Wall threshold as a multiple of the median level quantity (1.0-100.0, default: 2.0)
+    #[schemars(
+        description = "A level counts as a liquidity wall when its quantity exceeds this multiple of the median level quantity (1.0-100.0). Default: 2.0; raise it to report only larger walls"
+    )]
+    #[serde(default = "default_wall_median_multiplier")]
+    pub wall_median_multiplier: f64,
 }
 
 /// Parameters for get_orderbook_depth tool
@@ -80,12 +150,80 @@ pub struct GetOrderBookDepthParams {
     )]
     #[serde(default = "default_levels")]
     pub levels: usize,
+
+    /// Group adjacent levels into buckets this many basis points wide (0-1000]
+    #[schemars(
+        description = "Optional: group adjacent levels into price buckets this many basis points wide (of the best price), summing quantities. Omit for raw levels"
+    )]
+    #[serde(default)]
+    pub bucket_bps: Option<f64>,
 }
 
 fn default_levels() -> usize {
     20
 }
 
+fn default_wall_median_multiplier() -> f64 {
+    metrics::DEFAULT_WALL_MEDIAN_MULTIPLIER
+}
+
+/// Parameters for subscribe_symbol and unsubscribe_symbol tools
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct SymbolSubscriptionParams {
+    /// Trading pair symbol (e.g., "BTCUSDT")
+    #[schemars(description = "Trading pair symbol (e.g., 'BTCUSDT', 'ETHUSDT')")]
+    pub symbol: String,
+}
+
+/// Parameters for get_orderbook_integrity tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct GetOrderBookIntegrityParams {
+    /// Trading pair symbol (e.g., "BTCUSDT")
+    #[schemars(description = "Trading pair symbol (e.g., 'BTCUSDT', 'ETHUSDT')")]
+    pub symbol: String,
+}
+
+/// Order direction for slippage estimation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum OrderSide {
+    /// Market buy (consumes asks)
+    Buy,
+    /// Market sell (consumes bids)
+    Sell,
+}
+
+/// Parameters for get_slippage_estimate tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct GetSlippageEstimateParams {
+    /// Trading pair symbol (e.g., "BTCUSDT")
+    #[schemars(description = "Trading pair symbol (e.g., 'BTCUSDT', 'ETHUSDT')")]
+    pub symbol: String,
+
+    /// Order direction
+    #[schemars(description = "Order side: 'buy' (walks asks) or 'sell' (walks bids)")]
+    pub side: OrderSide,
+
+    /// Order size in USD (quote currency)
+    #[schemars(description = "Order size in USD, e.g. 100000 for a $100k order")]
+    pub notional_usd: f64,
+}
+
+/// Parameters for get_slippage_curve tool
+#[derive(Debug, Clone, Deserialize, JsonSchema)]
+pub struct GetSlippageCurveParams {
+    /// Trading pair symbol (e.g., "BTCUSDT")
+    #[schemars(description = "Trading pair symbol (e.g., 'BTCUSDT', 'ETHUSDT')")]
+    pub symbol: String,
+
+    /// Order sizes in USD (default: 1k, 10k, 100k, 1M)
+    #[schemars(
+        description = "Order sizes in USD to estimate slippage at (1-20 sizes). Default: [1000, 10000, 100000, 1000000]"
+    )]
+    #[serde(default)]
+    pub sizes_usd: Option<Vec<f64>>,
+}
+
 /// Get L1 aggregated metrics for quick spread assessment
 ///
 /// Provides lightweight analysis (15% token cost vs L2-full):
@@ -104,11 +242,25 @@ pub async fn get_orderbook_metrics(
     let symbol_upper = params.symbol.to_uppercase();
     info!(symbol = %symbol_upper, "Fetching order book metrics");
 
+    if !(1..=metrics::MAX_DEPTH_LEVELS).contains(&params.depth_levels) {
+        return Err(OrderBookToolError::InvalidLevels(params.depth_levels));
+    }
+    if !(1.0..=100.0).contains(&params.wall_median_multiplier) {
+        return Err(OrderBookToolError::InvalidWallMultiplier(
+            params.wall_median_multiplier,
+        ));
+    }
+
     // Get order book (lazy initialization on first request)
     let order_book = manager.get_order_book(&symbol_upper).await?;
 
     // Calculate metrics
-    let metrics = metrics::calculate_metrics(&order_book).ok_or_else(|| {
+    let metrics = metrics::calculate_metrics_at_depth(
+        &order_book,
+        params.depth_levels,
+        params.wall_median_multiplier,
+    )
+    .ok_or_else(|| {
         OrderBookToolError::MetricsCalculationFailed(format!(
             "Failed to calculate metrics for {}. Order book may be empty or invalid.",
             symbol_upper
@@ -127,7 +279,8 @@ pub async fn get_orderbook_metrics(
 
 /// Get L2 depth with compact integer encoding
 ///
-/// Token cost: 50% (L2-lite with 20 levels) or 100% (L2-full with 100 levels)
+/// Token cost: 50% (L2-lite with 20 levels) or 100% (L2-full with 100 levels);
+/// `bucket_bps` groups the top `levels` levels into fewer price buckets.
 ///
 /// Compact encoding:
 /// - price_scale = 100 (e.g., 67650.00 → 6765000)
@@ -147,9 +300,16 @@ pub async fn get_orderbook_depth(
         return Err(OrderBookToolError::InvalidLevels(levels));
     }
 
+    if let Some(bucket_bps) = params.bucket_bps {
+        if !(bucket_bps > 0.0 && bucket_bps <= metrics::MAX_BUCKET_BPS) {
+            return Err(OrderBookToolError::InvalidBucketWidth(bucket_bps));
+        }
+    }
+
     info!(
         symbol = %symbol_upper,
         levels,
+        bucket_bps = ?params.bucket_bps,
         "Fetching order book depth"
     );
 
@@ -157,7 +317,10 @@ pub async fn get_orderbook_depth(
     let order_book = manager.get_order_book(&symbol_upper).await?;
 
     // Extract depth with compact encoding
-    let depth = metrics::extract_depth(&order_book, levels);
+    let depth = match params.bucket_bps {
+        Some(bucket_bps) => metrics::extract_bucketed_depth(&order_book, levels, bucket_bps),
+        None => metrics::extract_depth(&order_book, levels),
+    };
 
     debug!(
         symbol = %symbol_upper,
@@ -195,9 +358,217 @@ pub async fn get_orderbook_health(
     Ok(health)
 }
 
+/// Report depth update sequence integrity for a tracked symbol
+///
+/// Returns the last update id, gaps detected, resyncs performed and whether the
+/// book is crossed right now. Fails with `SymbolNotTracked` instead of
+/// subscribing, so checking never uses up a symbol slot.
+///
+/// Latency: <50ms (no external API calls)
+pub async fn get_orderbook_integrity(
+    manager: Arc<OrderBookManager>,
+    params: GetOrderBookIntegrityParams,
+) -> Result<OrderBookIntegrity, OrderBookToolError> {
+    let symbol_upper = params.symbol.to_uppercase();
+
+    let integrity = manager
+        .get_integrity(&symbol_upper)
+        .await
+        .ok_or(OrderBookToolError::SymbolNotTracked(symbol_upper))?;
+
+    info!(
+        symbol = %integrity.symbol,
+        last_update_id = integrity.last_update_id,
+        gaps_detected = integrity.gaps_detected,
+        resyncs = integrity.resyncs,
+        crossed = integrity.crossed,
+        "Retrieved order book integrity"
+    );
+
+    Ok(integrity)
+}
+
+/// Start tracking a symbol now (REST snapshot + depth WebSocket)
+///
+/// Idempotent; fails with `SymbolLimitReached` when 20 other symbols are tracked.
+pub async fn subscribe_symbol(
+    manager: Arc<OrderBookManager>,
+    params: SymbolSubscriptionParams,
+) -> Result<SubscriptionStatus, OrderBookToolError> {
+    let symbol_upper = params.symbol.to_uppercase();
+    let already_subscribed = manager.is_subscribed(&symbol_upper).await;
+
+    manager.subscribe(&symbol_upper).await?;
+
+    let active_symbols = manager.active_symbol_count().await;
+    info!(symbol = %symbol_upper, active_symbols, "Subscribed order book");
+
+    Ok(SubscriptionStatus {
+        symbol: symbol_upper,
+        subscribed: true,
+        changed: !already_subscribed,
+        active_symbols,
+        max_symbols: MAX_CONCURRENT_SYMBOLS,
+    })
+}
+
+/// Stop tracking a symbol, closing its depth WebSocket and freeing its slot
+///
+/// Unsubscribing an untracked symbol is not an error (`changed` is false).
+pub async fn unsubscribe_symbol(
+    manager: Arc<OrderBookManager>,
+    params: SymbolSubscriptionParams,
+) -> Result<SubscriptionStatus, OrderBookToolError> {
+    let symbol_upper = params.symbol.to_uppercase();
+    let changed = manager.unsubscribe(&symbol_upper).await;
+
+    Ok(SubscriptionStatus {
+        symbol: symbol_upper,
+        subscribed: false,
+        changed,
+        active_symbols: manager.active_symbol_count().await,
+        max_symbols: MAX_CONCURRENT_SYMBOLS,
+    })
+}
+
+/// Estimate average fill price and slippage for a market order of a given USD size
+///
+/// Walks the cached book level by level (asks for buys, bids for sells) and
+/// returns the VWAP fill price and slippage in basis points versus the best price.
+/// Fails with `InsufficientDepth` if the visible book cannot absorb the order.
+///
+/// First request: 2-3s (lazy initialization)
+/// Subsequent requests: <200ms (cached data)
+pub async fn get_slippage_estimate(
+    manager: Arc<OrderBookManager>,
+    params: GetSlippageEstimateParams,
+) -> Result<SlippageEstimate, OrderBookToolError> {
+    let symbol_upper = params.symbol.to_uppercase();
+
+    if !params.notional_usd.is_finite() || params.notional_usd <= 0.0 {
+        return Err(OrderBookToolError::InvalidNotional(params.notional_usd));
+    }
+
+    info!(
+        symbol = %symbol_upper,
+        side = ?params.side,
+        notional_usd = params.notional_usd,
+        "Estimating slippage"
+    );
+
+    // Get order book (lazy initialization on first request)
+    let order_book = manager.get_order_book(&symbol_upper).await?;
+
+    let estimate = slippage_from_book(&order_book, params.side, params.notional_usd)?;
+
+    debug!(
+        symbol = %symbol_upper,
+        avg_price = estimate.avg_price,
+        slippage_bps = estimate.slippage_bps,
+        "Calculated slippage estimate"
+    );
+
+    Ok(estimate)
+}
+
+/// Estimate slippage across a set of order sizes on both sides of the book
+///
+/// Unlike [`get_slippage_estimate`], sizes the visible book cannot absorb are
+/// not an error: their points report the partial fill, showing where
+/// liquidity thins out.
+///
+/// First request: 2-3s (lazy initialization)
+/// Subsequent requests: <200ms (cached data)
+pub async fn get_slippage_curve(
+    manager: Arc<OrderBookManager>,
+    params: GetSlippageCurveParams,
+) -> Result<SlippageCurve, OrderBookToolError> {
+    let symbol_upper = params.symbol.to_uppercase();
+    let sizes_usd = curve_sizes(params.sizes_usd)?;
+
+    info!(
+        symbol = %symbol_upper,
+        sizes = sizes_usd.len(),
+        "Estimating slippage curve"
+    );
+
+    // Get order book (lazy initialization on first request)
+    let order_book = manager.get_order_book(&symbol_upper).await?;
+
+    Ok(slippage_curve_from_book(&order_book, &sizes_usd))
+}
+
+/// Requested curve sizes, validated and sorted ascending without duplicates
+fn curve_sizes(sizes_usd: Option<Vec<f64>>) -> Result<Vec<f64>, OrderBookToolError> {
+    let Some(mut sizes) = sizes_usd else {
+        return Ok(metrics::DEFAULT_SLIPPAGE_CURVE_USD.to_vec());
+    };
+
+    if sizes.is_empty() || sizes.len() > metrics::MAX_SLIPPAGE_CURVE_POINTS {
+        return Err(OrderBookToolError::InvalidCurveSizes(sizes.len()));
+    }
+    if let Some(&invalid) = sizes.iter().find(|size| !size.is_finite() || **size <= 0.0) {
+        return Err(OrderBookToolError::InvalidNotional(invalid));
+    }
+
+    sizes.sort_by(f64::total_cmp);
+    sizes.dedup();
+    Ok(sizes)
+}
+
+fn slippage_curve_from_book(order_book: &OrderBook, sizes_usd: &[f64]) -> SlippageCurve {
+    SlippageCurve {
+        symbol: order_book.symbol.clone(),
+        buy: metrics::slippage_curve(order_book, sizes_usd, false),
+        sell: metrics::slippage_curve(order_book, sizes_usd, true),
+        timestamp: order_book.timestamp,
+    }
+}
+
+/// Walk `order_book` for a `side` order of `notional_usd`, rejecting partial fills
+fn slippage_from_book(
+    order_book: &OrderBook,
+    side: OrderSide,
+    notional_usd: f64,
+) -> Result<SlippageEstimate, OrderBookToolError> {
+    let is_sell = side == OrderSide::Sell;
+
+    let estimate =
+        metrics::estimate_slippage(order_book, notional_usd, is_sell).ok_or_else(|| {
+            OrderBookToolError::InsufficientDepth {
+                symbol: order_book.symbol.clone(),
+                requested_usd: notional_usd,
+                filled_usd: 0.0,
+            }
+        })?;
+
+    // Allow for floating point drift when the last level fills the remainder exactly
+    if estimate.filled_usd + 1e-6 < notional_usd {
+        return Err(OrderBookToolError::InsufficientDepth {
+            symbol: order_book.symbol.clone(),
+            requested_usd: notional_usd,
+            filled_usd: estimate.filled_usd,
+        });
+    }
+
+    Ok(estimate)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rust_decimal::Decimal;
+    use std::str::FromStr;
+
+    /// Book with bids 99/98/97 and asks 101/102/103, 1.0 qty per level
+    fn synthetic_book() -> OrderBook {
+        let mut book = OrderBook::new("TESTUSDT".to_string());
+        for (bid, ask) in [("99", "101"), ("98", "102"), ("97", "103")] {
+            book.update_bid(Decimal::from_str(bid).unwrap(), Decimal::ONE);
+            book.update_ask(Decimal::from_str(ask).unwrap(), Decimal::ONE);
+        }
+        book
+    }
 
     #[test]
     fn test_default_levels() {
@@ -211,5 +582,106 @@ mod tests {
 
         let err = OrderBookToolError::InvalidLevels(101);
         assert!(err.to_string().contains("between 1 and 100"));
+
+        let err = OrderBookToolError::InvalidBucketWidth(0.0);
+        assert!(err.to_string().contains("bucket_bps"));
+    }
+
+    #[test]
+    fn test_slippage_buy_walks_asks() {
+        let book = synthetic_book();
+
+        // 101 + 102 = 203 USD consumes the first two ask levels exactly
+        let estimate = slippage_from_book(&book, OrderSide::Buy, 203.0).unwrap();
+        assert!((estimate.filled_qty - 2.0).abs() < 1e-9);
+        assert!((estimate.avg_price - 101.5).abs() < 1e-9);
+        // (101.5 - 101) / 101 * 10000 = 49.5 bps
+        assert!((estimate.slippage_bps - 49.504950495).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_slippage_sell_walks_bids() {
+        let book = synthetic_book();
+
+        // Fits entirely within the best bid level
+        let estimate = slippage_from_book(&book, OrderSide::Sell, 49.5).unwrap();
+        assert!((estimate.avg_price - 99.0).abs() < 1e-9);
+        assert!(estimate.slippage_bps.abs() < 1e-9);
+
+        // 99 + 98 = 197 USD consumes the first two bid levels
+        let estimate = slippage_from_book(&book, OrderSide::Sell, 197.0).unwrap();
+        assert!((estimate.avg_price - 98.5).abs() < 1e-9);
+        assert!(estimate.slippage_bps > 0.0);
+    }
+
+    #[test]
+    fn test_slippage_insufficient_depth() {
+        let book = synthetic_book();
+
+        // Total ask liquidity is 101 + 102 + 103 = 306 USD
+        let err = slippage_from_book(&book, OrderSide::Buy, 1_000.0).unwrap_err();
+        match &err {
+            OrderBookToolError::InsufficientDepth {
+                requested_usd,
+                filled_usd,
+                ..
+            } => {
+                assert_eq!(*requested_usd, 1_000.0);
+                assert!((filled_usd - 306.0).abs() < 1e-9);
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+        assert!(err.to_string().contains("306.00"));
+
+        let empty = OrderBook::new("TESTUSDT".to_string());
+        assert!(slippage_from_book(&empty, OrderSide::Sell, 10.0).is_err());
+    }
+
+    #[test]
+    fn test_slippage_curve_is_non_decreasing_with_size() {
+        // Ask levels thin out and spread apart further from the touch
+        let mut book = OrderBook::new("TESTUSDT".to_string());
+        for (i, qty) in ["50", "20", "5", "1"].iter().enumerate() {
+            let offset = Decimal::from(1 << i);
+            let qty = Decimal::from_str(qty).unwrap();
+            book.update_bid(Decimal::from(100) - offset, qty);
+            book.update_ask(Decimal::from(100) + offset, qty);
+        }
+
+        let sizes = curve_sizes(Some(vec![100_000.0, 1_000.0, 5_000.0, 1_000.0, 7_500.0])).unwrap();
+        assert_eq!(sizes, [1_000.0, 5_000.0, 7_500.0, 100_000.0]);
+
+        let curve = slippage_curve_from_book(&book, &sizes);
+        for side in [&curve.buy, &curve.sell] {
+            assert_eq!(side.len(), sizes.len());
+            for pair in side.windows(2) {
+                assert!(pair[0].target_usd < pair[1].target_usd);
+                assert!(
+                    pair[1].slippage_bps >= pair[0].slippage_bps,
+                    "{} bps at ${} after {} bps at ${}",
+                    pair[1].slippage_bps,
+                    pair[1].target_usd,
+                    pair[0].slippage_bps,
+                    pair[0].target_usd
+                );
+            }
+            // The top level absorbs $1k, $100k exceeds the whole side
+            assert!(side[0].slippage_bps.abs() < 1e-9);
+            assert!(side[3].filled_usd < side[3].target_usd);
+        }
+        assert!(curve.buy[2].slippage_bps > curve.buy[1].slippage_bps);
+
+        assert_eq!(
+            curve_sizes(None).unwrap(),
+            metrics::DEFAULT_SLIPPAGE_CURVE_USD
+        );
+        assert!(matches!(
+            curve_sizes(Some(vec![])),
+            Err(OrderBookToolError::InvalidCurveSizes(0))
+        ));
+        assert!(matches!(
+            curve_sizes(Some(vec![1_000.0, -5.0])),
+            Err(OrderBookToolError::InvalidNotional(_))
+        ));
     }
 }
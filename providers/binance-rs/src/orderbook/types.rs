@@ -94,15 +94,19 @@ pub struct OrderBookMetrics {
     /// Mid price: (best_bid + best_ask) / 2 (basis for spread calculation)
     pub mid_price: f64,
 
-    /// Sum of top 20 bid level quantities (buy-side liquidity depth)
+    /// Sum of top `depth_levels` bid level quantities (buy-side liquidity depth)
     pub bid_volume: f64,
 
-    /// Sum of top 20 ask level quantities (sell-side liquidity depth)
+    /// Sum of top `depth_levels` ask level quantities (sell-side liquidity depth)
     pub ask_volume: f64,
 
     /// Bid/ask volume ratio (bid_volume / ask_volume). >1 = more buy pressure
     pub imbalance_ratio: f64,
 
+    /// Levels per side feeding bid/ask volume, imbalance and microprice (default 20)
+    #[serde(default = "default_depth_levels")]
+    pub depth_levels: usize,
+
     /// Highest bid price (string for decimal precision)
     pub best_bid: String,
 
@@ -122,6 +126,10 @@ pub struct OrderBookMetrics {
     pub slippage_estimates: SlippageEstimates,
 }
 
+fn default_depth_levels() -> usize {
+    crate::orderbook::metrics::DEFAULT_DEPTH_LEVELS
+}
+
 /// Container for bid and ask walls (support/resistance zones)
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct Walls {
@@ -141,6 +149,9 @@ pub struct Wall {
     /// Quantity at this level (string for decimal precision)
     pub qty: String,
 
+    /// Quantity as a multiple of the median level quantity (e.g. 4.5 = 4.5x median)
+    pub median_multiple: f64,
+
     /// Side of the order book
     pub side: WallSide,
 }
@@ -196,6 +207,26 @@ pub struct SlippageEstimate {
     pub filled_usd: f64,
 }
 
+/// Slippage across increasing order sizes on both sides of the book
+///
+/// Sizes beyond the visible depth keep their point, with `filled_usd` below
+/// `target_usd` and the slippage of consuming the whole side, so the curve
+/// shows where liquidity runs out.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SlippageCurve {
+    /// Trading pair symbol (uppercased)
+    pub symbol: String,
+
+    /// Market buy estimates (walking asks), smallest size first
+    pub buy: Vec<SlippageEstimate>,
+
+    /// Market sell estimates (walking bids), smallest size first
+    pub sell: Vec<SlippageEstimate>,
+
+    /// Book time (milliseconds since Unix epoch)
+    pub timestamp: i64,
+}
+
 /// L2 depth data with compact integer encoding for token efficiency
 ///
 /// Reduces JSON size by ~40% using scaled integers instead of full decimal strings.
@@ -221,6 +252,10 @@ pub struct OrderBookDepth {
 
     /// Ask levels as [scaled_price, scaled_qty] tuples (sorted ascending by price)
     pub asks: Vec<[i64; 2]>,
+
+    /// Bucket width in basis points when levels were grouped (absent for raw levels)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bucket_bps: Option<f64>,
 }
 
 /// Service health status for order book tracking
@@ -246,10 +281,81 @@ pub struct OrderBookHealth {
 
     /// Human-readable error message if status != 'ok'
     pub reason: Option<String>,
+
+    /// Depth WebSocket reconnect attempts across all symbols since startup
+    #[serde(default)]
+    pub websocket_reconnects: u64,
+
+    /// Per-symbol WebSocket connection details (sorted by symbol)
+    #[serde(default)]
+    pub streams: Vec<StreamHealth>,
+}
+
+/// WebSocket connection details for one tracked symbol
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct StreamHealth {
+    /// Trading pair symbol
+    pub symbol: String,
+
+    /// Whether the depth stream is currently connected
+    pub connected: bool,
+
+    /// Reconnect attempts for this stream
+    pub reconnects: u64,
+
+    /// Last successful connect time (milliseconds since Unix epoch), if any
+    pub last_connected_at: Option<i64>,
+}
+
+/// Depth update sequence integrity for one tracked symbol
+///
+/// Counters cover the time since the symbol started being tracked; a stale
+/// refresh keeps them, unsubscribing resets them.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct OrderBookIntegrity {
+    /// Trading pair symbol
+    pub symbol: String,
+
+    /// Binance `lastUpdateId` of the current book
+    pub last_update_id: i64,
+
+    /// Depth updates rejected because their first update id skipped ahead
+    pub gaps_detected: u64,
+
+    /// REST snapshot resyncs performed after a gap or crossed book
+    pub resyncs: u64,
+
+    /// Whether the current book is crossed (best ask at or below best bid)
+    pub crossed: bool,
+
+    /// A gap or crossed book was detected; the next read resyncs
+    pub resync_pending: bool,
+
+    /// Check time (milliseconds since Unix epoch)
+    pub timestamp: i64,
+}
+
+/// Outcome of a subscribe/unsubscribe call
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SubscriptionStatus {
+    /// Trading pair symbol (uppercased)
+    pub symbol: String,
+
+    /// Whether the symbol is tracked after the call
+    pub subscribed: bool,
+
+    /// Whether the call started or stopped tracking (false if already in that state)
+    pub changed: bool,
+
+    /// Symbols tracked after the call
+    pub active_symbols: usize,
+
+    /// Maximum symbols that can be tracked at once
+    pub max_symbols: usize,
 }
 
 /// Health status levels
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum HealthStatus {
     /// All systems operational
@@ -1,20 +1,63 @@
 //! WebSocket client for Binance depth streams
 //!
-//! Connects to `<symbol>@depth@100ms` streams for real-time order book updates.
-//! Implements exponential backoff reconnection strategy with auto-recovery.
+//! Connects to `<symbol>@depth@100ms` streams for real-time order book updates,
+//! on the spot or USDⓈ-M futures stream host depending on the [`Market`].
+//! Implements jittered exponential backoff reconnection with auto-recovery and
+//! per-stream reconnect statistics.
 
+use crate::binance::backoff::JitteredBackoff;
+use crate::binance::market::Market;
+use crate::binance::parse_errors::ParseErrorSampler;
 use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::time::{sleep, Duration};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{debug, error, info, warn};
 
-/// Binance WebSocket base URL for streams
-const BINANCE_WS_URL: &str = "wss://stream.binance.com:9443/ws";
+/// Initial reconnection backoff ceiling (1 second)
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
 
 /// Maximum reconnection delay (30 seconds)
-const MAX_RECONNECT_DELAY_SECS: u64 = 30;
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(30);
+
+/// Connection statistics for a single depth stream
+///
+/// Shared between the WebSocket task (writer) and the manager's health check (reader).
+#[derive(Debug, Default)]
+pub struct StreamConnectionStats {
+    /// Reconnect attempts for this stream since it was created
+    reconnects: AtomicU64,
+
+    /// Last successful connect time (milliseconds since epoch, 0 = never)
+    last_connected_at: AtomicI64,
+}
+
+impl StreamConnectionStats {
+    /// Reconnect attempts for this stream
+    pub fn reconnects(&self) -> u64 {
+        self.reconnects.load(Ordering::Relaxed)
+    }
+
+    /// Last successful connect time in milliseconds since epoch, if any
+    pub fn last_connected_at(&self) -> Option<i64> {
+        match self.last_connected_at.load(Ordering::Relaxed) {
+            0 => None,
+            ts => Some(ts),
+        }
+    }
+
+    fn record_connected(&self) {
+        self.last_connected_at
+            .store(chrono::Utc::now().timestamp_millis(), Ordering::Relaxed);
+    }
+
+    fn record_reconnect(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+    }
+}
 
 /// Depth update event from Binance WebSocket
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -34,6 +77,10 @@ pub struct DepthUpdateEvent {
     #[serde(rename = "u")]
     pub final_update_id: i64,
 
+    /// Final update ID of the previous event (futures streams only)
+    #[serde(rename = "pu", default, skip_serializing_if = "Option::is_none")]
+    pub prev_final_update_id: Option<i64>,
+
     #[serde(rename = "b")]
     pub bids: Vec<[String; 2]>, // [price, qty]
 
@@ -44,7 +91,13 @@ pub struct DepthUpdateEvent {
 /// WebSocket client for a single symbol's depth stream
 pub struct DepthWebSocketClient {
     symbol: String,
+    /// Selects the stream host (spot or futures)
+    market: Market,
     update_sender: mpsc::UnboundedSender<DepthUpdateEvent>,
+    /// Shared counter incremented on every reconnect attempt
+    reconnect_counter: Arc<AtomicU64>,
+    /// Reconnect count and last connect time for this stream only
+    stats: Arc<StreamConnectionStats>,
 }
 
 impl DepthWebSocketClient {
@@ -52,54 +105,71 @@ impl DepthWebSocketClient {
     ///
     /// Returns a client handle and a receiver channel for depth updates.
     /// The client spawns a background task that manages the WebSocket connection.
-    pub fn new(symbol: String) -> (Self, mpsc::UnboundedReceiver<DepthUpdateEvent>) {
+    /// `market` selects the stream host; `reconnect_counter` is shared across symbols
+    /// so reconnects can be exported as a metric.
+    pub fn new(
+        symbol: String,
+        market: Market,
+        reconnect_counter: Arc<AtomicU64>,
+    ) -> (Self, mpsc::UnboundedReceiver<DepthUpdateEvent>) {
         let (update_sender, update_receiver) = mpsc::unbounded_channel();
 
         let client = Self {
             symbol,
+            market,
             update_sender,
+            reconnect_counter,
+            stats: Arc::new(StreamConnectionStats::default()),
         };
 
         (client, update_receiver)
     }
 
+    /// Connection statistics for this stream (take before calling [`start`](Self::start))
+    pub fn stats(&self) -> Arc<StreamConnectionStats> {
+        Arc::clone(&self.stats)
+    }
+
     /// Start the WebSocket client with automatic reconnection
     ///
     /// Spawns a background task that:
     /// 1. Connects to Binance depth stream
     /// 2. Processes incoming depth updates
-    /// 3. Handles disconnections with exponential backoff (1s, 2s, 4s, 8s, max 30s)
+    /// 3. Handles disconnections with full-jitter exponential backoff (random delay
+    ///    below a ceiling of 1s, 2s, 4s, ... max 30s) so symbols don't reconnect in lockstep
     /// 4. Logs connection status changes at INFO level
     pub fn start(self) -> tokio::task::JoinHandle<()> {
         tokio::spawn(async move {
-            let mut retry_count = 0;
+            let mut backoff = JitteredBackoff::new(INITIAL_RECONNECT_DELAY, MAX_RECONNECT_DELAY);
+            let mut parse_errors = ParseErrorSampler::new();
 
             loop {
-                match self.connect_and_process().await {
+                let connected_before = self.stats.last_connected_at();
+
+                match self.connect_and_process(&mut parse_errors).await {
                     Ok(()) => {
                         info!(symbol = %self.symbol, "WebSocket connection closed normally");
                         break;
                     }
                     Err(e) => {
-                        // Calculate exponential backoff delay
-                        let delay_secs =
-                            std::cmp::min(2_u64.pow(retry_count), MAX_RECONNECT_DELAY_SECS);
+                        // A connection was established this round: start over from the
+                        // initial ceiling instead of continuing to back off
+                        if self.stats.last_connected_at() != connected_before {
+                            backoff.reset();
+                        }
+                        let delay = backoff.next_delay();
 
                         warn!(
                             symbol = %self.symbol,
                             error = %e,
-                            retry_count,
-                            delay_secs,
-                            "WebSocket connection failed, retrying with exponential backoff"
+                            reconnects = self.stats.reconnects(),
+                            delay_ms = delay.as_millis() as u64,
+                            "WebSocket connection failed, retrying with jittered backoff"
                         );
 
-                        sleep(Duration::from_secs(delay_secs)).await;
-                        retry_count += 1;
-
-                        // Reset retry count after successful connection (if we get far enough)
-                        if retry_count > 10 {
-                            retry_count = 0;
-                        }
+                        sleep(delay).await;
+                        self.reconnect_counter.fetch_add(1, Ordering::Relaxed);
+                        self.stats.record_reconnect();
                     }
                 }
             }
@@ -107,14 +177,18 @@ impl DepthWebSocketClient {
     }
 
     /// Connect to WebSocket and process messages until disconnection
-    async fn connect_and_process(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    async fn connect_and_process(
+        &self,
+        parse_errors: &mut ParseErrorSampler,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         let stream_name = format!("{}@depth@100ms", self.symbol.to_lowercase());
-        let url = format!("{}/{}", BINANCE_WS_URL, stream_name);
+        let url = self.market.stream_url(&stream_name);
 
         info!(symbol = %self.symbol, url = %url, "Connecting to Binance depth stream");
 
         let (ws_stream, _) = connect_async(&url).await?;
         info!(symbol = %self.symbol, "WebSocket connected successfully");
+        self.stats.record_connected();
 
         let (mut write, mut read) = ws_stream.split();
 
@@ -151,7 +225,7 @@ impl DepthWebSocketClient {
                             }
                         }
                         Err(e) => {
-                            error!(symbol = %self.symbol, error = %e, text = %text, "Failed to parse depth update");
+                            parse_errors.log(&stream_name, &e, &text);
                         }
                     }
                 }
@@ -216,6 +290,7 @@ mod tests {
         assert_eq!(update.symbol, "BTCUSDT");
         assert_eq!(update.first_update_id, 1000);
         assert_eq!(update.final_update_id, 1005);
+        assert_eq!(update.prev_final_update_id, None);
         assert_eq!(update.bids.len(), 2);
         assert_eq!(update.asks.len(), 2);
         assert_eq!(update.bids[0][0], "67650.00");
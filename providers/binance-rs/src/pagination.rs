@@ -0,0 +1,123 @@
+//! Pagination envelope for list-returning tools
+//!
+//! List tools answer with `{ items, count, truncated, next_cursor }` instead of
+//! a bare array, so a client can tell a complete result from one capped by a
+//! limit. When `truncated` is true and `next_cursor` is set, passing the cursor
+//! back as the tool's `cursor` argument continues after the last item.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// One page of a list tool's results
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Page<T> {
+    /// Results, in the order documented by the tool
+    pub items: Vec<T>,
+    /// Number of items in this page
+    pub count: usize,
+    /// A limit capped the results; more items may be available
+    pub truncated: bool,
+    /// Opaque cursor for the next page, set when `truncated` and the tool can
+    /// continue from here
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    /// Page with explicit truncation metadata
+    pub fn new(items: Vec<T>, truncated: bool, next_cursor: Option<String>) -> Self {
+        Self {
+            count: items.len(),
+            items,
+            truncated,
+            next_cursor,
+        }
+    }
+
+    /// Every result, nothing capped
+    pub fn complete(items: Vec<T>) -> Self {
+        Self::new(items, false, None)
+    }
+
+    /// Page fetched with `limit`, continuing at `cursor_after(last item)`
+    ///
+    /// Upstream APIs don't say whether more data exists, so a full page is
+    /// reported as truncated; the next page may turn out empty.
+    pub fn from_limited(
+        items: Vec<T>,
+        limit: usize,
+        cursor_after: impl FnOnce(&T) -> String,
+    ) -> Self {
+        let next_cursor = match items.last() {
+            Some(last) if items.len() >= limit => Some(cursor_after(last)),
+            _ => None,
+        };
+        Self::new(items, next_cursor.is_some(), next_cursor)
+    }
+
+    /// Items `[offset, offset + limit)` of a complete in-memory list
+    ///
+    /// The cursor is the offset of the next page.
+    pub fn from_offset(items: Vec<T>, offset: usize, limit: usize) -> Self {
+        let end = offset.saturating_add(limit);
+        let truncated = items.len() > end;
+        let page = items.into_iter().skip(offset).take(limit).collect();
+        Self::new(page, truncated, truncated.then(|| end.to_string()))
+    }
+}
+
+/// Parse an offset cursor produced by [`Page::from_offset`]
+pub fn parse_offset_cursor(cursor: &str) -> Option<usize> {
+    cursor.trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_caps_results_with_cursor() {
+        let capped = Page::from_limited(vec![10, 11, 12], 3, |last| (last + 1).to_string());
+        assert_eq!(capped.count, 3);
+        assert!(capped.truncated);
+        assert_eq!(capped.next_cursor.as_deref(), Some("13"));
+
+        let short = Page::from_limited(vec![10, 11], 3, |last| (last + 1).to_string());
+        assert!(!short.truncated);
+        assert_eq!(short.next_cursor, None);
+
+        let empty = Page::from_limited(Vec::<i64>::new(), 0, |last| last.to_string());
+        assert!(!empty.truncated);
+    }
+
+    #[test]
+    fn test_offset_pages_walk_the_list() {
+        let first = Page::from_offset((0..5).collect(), 0, 2);
+        assert_eq!(first.items, vec![0, 1]);
+        assert!(first.truncated);
+        assert_eq!(first.next_cursor.as_deref(), Some("2"));
+
+        // Follow next_cursor to the end: every item exactly once, in order
+        let mut seen = first.items;
+        let mut cursor = first.next_cursor;
+        let mut last = None;
+        while let Some(next) = cursor {
+            let offset = parse_offset_cursor(&next).unwrap();
+            let page = Page::from_offset((0..5).collect(), offset, 2);
+            seen.extend(&page.items);
+            cursor = page.next_cursor.clone();
+            last = Some(page);
+        }
+        assert_eq!(seen, vec![0, 1, 2, 3, 4]);
+
+        let last = last.unwrap();
+        assert_eq!(last.items, vec![4]);
+        assert_eq!(last.count, 1);
+        assert!(!last.truncated);
+
+        let json = serde_json::to_value(Page::complete(vec!["a"])).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"items": ["a"], "count": 1, "truncated": false, "next_cursor": null})
+        );
+    }
+}
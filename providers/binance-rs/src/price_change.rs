@@ -0,0 +1,195 @@
+//! Price change over trailing windows from klines
+//!
+//! The 24hr ticker is the only change metric Binance reports directly. For the
+//! other windows the change is derived from candles: the open of the first
+//! candle in the window against the close of the latest, still forming, candle
+//! (the last traded price), with the high and low across all of them.
+
+use crate::error::McpError;
+use crate::market_data::MarketDataSource;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+/// Trailing window for [`fetch_price_change`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceWindow {
+    OneHour,
+    FourHours,
+    TwelveHours,
+    OneDay,
+    SevenDays,
+}
+
+impl PriceWindow {
+    /// Every supported window, shortest first
+    pub const ALL: [PriceWindow; 5] = [
+        PriceWindow::OneHour,
+        PriceWindow::FourHours,
+        PriceWindow::TwelveHours,
+        PriceWindow::OneDay,
+        PriceWindow::SevenDays,
+    ];
+
+    /// Parse a window label ("1h", "4h", "12h", "1d", "7d")
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|window| window.label().eq_ignore_ascii_case(raw.trim()))
+    }
+
+    /// Label used in tool arguments and report rows
+    pub fn label(&self) -> &'static str {
+        match self {
+            PriceWindow::OneHour => "1h",
+            PriceWindow::FourHours => "4h",
+            PriceWindow::TwelveHours => "12h",
+            PriceWindow::OneDay => "1d",
+            PriceWindow::SevenDays => "7d",
+        }
+    }
+
+    /// Kline interval and candle count covering the window
+    ///
+    /// The last candle is the one still forming, so the window opens between
+    /// one interval short of and exactly its nominal length ago.
+    pub(crate) fn klines(&self) -> (&'static str, u32) {
+        match self {
+            PriceWindow::OneHour => ("1m", 60),
+            PriceWindow::FourHours => ("5m", 48),
+            PriceWindow::TwelveHours => ("15m", 48),
+            PriceWindow::OneDay => ("30m", 48),
+            PriceWindow::SevenDays => ("2h", 84),
+        }
+    }
+}
+
+/// Price change of a symbol over one window
+#[derive(Debug, Clone, PartialEq, Serialize, JsonSchema)]
+pub struct PriceChange {
+    pub symbol: String,
+    /// Window label ("1h", "4h", "12h", "1d" or "7d")
+    pub window: String,
+    /// Open time of the first candle in the window (ms since epoch)
+    pub open_time: i64,
+    /// Price at the start of the window
+    pub open_price: f64,
+    /// Last traded price
+    pub current_price: f64,
+    /// Highest price in the window
+    pub high_price: f64,
+    /// Lowest price in the window
+    pub low_price: f64,
+    /// `current_price - open_price`
+    pub price_change: f64,
+    /// Change relative to `open_price`, in percent
+    pub price_change_percent: f64,
+}
+
+/// Price change across `klines` (Binance array format, oldest first)
+///
+/// Returns None when there are no candles, a candle is malformed or the window
+/// opened at a zero price.
+pub fn price_change_from_klines(
+    symbol: &str,
+    window: PriceWindow,
+    klines: &[serde_json::Value],
+) -> Option<PriceChange> {
+    // [open_time, open, high, low, close, ...] with prices as strings
+    let field = |kline: &serde_json::Value, index: usize| -> Option<f64> {
+        kline.get(index)?.as_str()?.parse().ok()
+    };
+
+    let first = klines.first()?;
+    let open_time = first.get(0)?.as_i64()?;
+    let open_price = field(first, 1)?;
+    let current_price = field(klines.last()?, 4)?;
+    if open_price <= 0.0 {
+        return None;
+    }
+
+    let mut high_price = f64::NEG_INFINITY;
+    let mut low_price = f64::INFINITY;
+    for kline in klines {
+        high_price = high_price.max(field(kline, 2)?);
+        low_price = low_price.min(field(kline, 3)?);
+    }
+
+    let price_change = current_price - open_price;
+    Some(PriceChange {
+        symbol: symbol.to_string(),
+        window: window.label().to_string(),
+        open_time,
+        open_price,
+        current_price,
+        high_price,
+        low_price,
+        price_change,
+        price_change_percent: price_change / open_price * 100.0,
+    })
+}
+
+/// Fetch the klines covering `window` and compute the price change
+///
+/// # Returns
+/// * `Ok(None)` - The exchange returned no usable candles (e.g. newly listed symbol)
+pub async fn fetch_price_change<S: MarketDataSource + ?Sized>(
+    source: &S,
+    symbol: &str,
+    window: PriceWindow,
+) -> Result<Option<PriceChange>, McpError> {
+    let (interval, limit) = window.klines();
+    let klines = source.get_klines(symbol, interval, Some(limit)).await?;
+    Ok(price_change_from_klines(symbol, window, &klines))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kline(open_time: i64, open: &str, high: &str, low: &str, close: &str) -> serde_json::Value {
+        serde_json::json!([
+            open_time,
+            open,
+            high,
+            low,
+            close,
+            "10.0",
+            open_time + 3_599_999,
+            "1000.0",
+            42,
+            "5.0",
+            "500.0",
+            "0"
+        ])
+    }
+
+    #[test]
+    fn test_change_from_two_klines() {
+        let klines = vec![
+            kline(1_700_000_000_000, "100.00", "104.00", "99.00", "103.00"),
+            kline(1_700_003_600_000, "103.00", "106.00", "97.50", "105.00"),
+        ];
+
+        let change = price_change_from_klines("BTCUSDT", PriceWindow::OneHour, &klines).unwrap();
+
+        assert_eq!(change.window, "1h");
+        assert_eq!(change.open_time, 1_700_000_000_000);
+        assert_eq!(change.open_price, 100.0);
+        assert_eq!(change.current_price, 105.0);
+        assert_eq!(change.high_price, 106.0);
+        assert_eq!(change.low_price, 97.5);
+        assert!((change.price_change - 5.0).abs() < 1e-9);
+        assert!((change.price_change_percent - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_unusable_klines_and_window_labels() {
+        assert!(price_change_from_klines("BTCUSDT", PriceWindow::OneDay, &[]).is_none());
+        let zero_open = vec![kline(0, "0", "1", "0", "1")];
+        assert!(price_change_from_klines("BTCUSDT", PriceWindow::OneDay, &zero_open).is_none());
+
+        assert_eq!(PriceWindow::parse("4H"), Some(PriceWindow::FourHours));
+        assert_eq!(PriceWindow::parse("7d"), Some(PriceWindow::SevenDays));
+        assert_eq!(PriceWindow::parse("2h"), None);
+    }
+}
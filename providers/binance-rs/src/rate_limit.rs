@@ -0,0 +1,125 @@
+//! Per-client tool call rate limiting
+//!
+//! Every MCP client (HTTP session or SSE connection) gets its own
+//! [`TokenBucket`], so a single client spamming expensive tools such as
+//! `binance.generate_market_report` can't drain the Binance request weight the
+//! other clients share.
+
+use std::time::{Duration, Instant};
+
+/// Tool calls per minute allowed per client when `MCP_TOOL_RATE_PER_MIN` is unset
+pub const DEFAULT_TOOL_RATE_PER_MIN: u32 = 60;
+
+/// Load the per-client tool call rate from `MCP_TOOL_RATE_PER_MIN`
+///
+/// ## Environment Variables
+///
+/// - `MCP_TOOL_RATE_PER_MIN`: Tool calls per minute per client (default: 60,
+///   `0` disables the limit)
+///
+/// # Errors
+///
+/// Returns error if the variable is set but not a non-negative integer
+pub fn tool_rate_from_env() -> Result<Option<u32>, Box<dyn std::error::Error>> {
+    parse_tool_rate(std::env::var("MCP_TOOL_RATE_PER_MIN").ok().as_deref())
+}
+
+/// Parse a raw `MCP_TOOL_RATE_PER_MIN` value (`None` or blank = default, `0` = unlimited)
+pub fn parse_tool_rate(raw: Option<&str>) -> Result<Option<u32>, Box<dyn std::error::Error>> {
+    match raw.map(str::trim) {
+        None | Some("") => Ok(Some(DEFAULT_TOOL_RATE_PER_MIN)),
+        Some(value) => match value.parse::<u32>() {
+            Ok(0) => Ok(None),
+            Ok(rate) => Ok(Some(rate)),
+            Err(_) => Err(format!(
+                "MCP_TOOL_RATE_PER_MIN must be a non-negative integer, got '{}'",
+                value
+            )
+            .into()),
+        },
+    }
+}
+
+/// Token bucket holding up to one minute's worth of calls
+///
+/// Starts full and refills continuously at `rate_per_min / 60` tokens per
+/// second, so a client may burst `rate_per_min` calls and then sustains the rate.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    tokens: f64,
+    refill_per_sec: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Bucket allowing `rate_per_min` calls per minute (clamped to at least 1)
+    pub fn per_minute(rate_per_min: u32) -> Self {
+        let capacity = rate_per_min.max(1) as f64;
+        Self {
+            capacity,
+            tokens: capacity,
+            refill_per_sec: capacity / 60.0,
+            last_refill: Instant::now(),
+        }
+    }
+
+    /// Take one token, or return how long until the next one is available
+    pub fn try_acquire(&mut self) -> Result<(), Duration> {
+        self.try_acquire_at(Instant::now())
+    }
+
+    fn try_acquire_at(&mut self, now: Instant) -> Result<(), Duration> {
+        let elapsed = now
+            .saturating_duration_since(self.last_refill)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / self.refill_per_sec,
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_burst_then_refills() {
+        let mut bucket = TokenBucket::per_minute(3);
+        let start = bucket.last_refill;
+
+        for _ in 0..3 {
+            assert!(bucket.try_acquire_at(start).is_ok());
+        }
+        let wait = bucket.try_acquire_at(start).unwrap_err();
+        assert_eq!(wait, Duration::from_secs(20));
+
+        // One token every 20s at 3/min
+        assert!(bucket
+            .try_acquire_at(start + Duration::from_secs(10))
+            .is_err());
+        assert!(bucket
+            .try_acquire_at(start + Duration::from_secs(20))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_tool_rate_values_parsed() {
+        assert_eq!(
+            parse_tool_rate(None).unwrap(),
+            Some(DEFAULT_TOOL_RATE_PER_MIN)
+        );
+        assert_eq!(parse_tool_rate(Some(" 10 ")).unwrap(), Some(10));
+        assert_eq!(parse_tool_rate(Some("0")).unwrap(), None);
+        assert!(parse_tool_rate(Some("-1")).is_err());
+        assert!(parse_tool_rate(Some("fast")).is_err());
+    }
+}
@@ -1,5 +1,7 @@
 // Markdown formatting utilities for report generation
 
+use super::FreshnessThresholds;
+
 /// Build a markdown table from headers and rows
 pub fn build_table(headers: &[&str], rows: &[Vec<String>]) -> String {
     let mut table = String::new();
@@ -160,6 +162,239 @@ pub fn format_timestamp(millis: i64) -> String {
     }
 }
 
+/// Freshness indicator for a data age
+///
+/// - 🟢 Fresh: below `fresh_ms`
+/// - 🟡 Recent: below `recent_ms`
+/// - 🟠 Aging: below `stale_ms`
+/// - 🔴 Stale: `stale_ms` or older
+pub fn freshness_label(age_ms: i32, thresholds: &FreshnessThresholds) -> &'static str {
+    let age_ms = i64::from(age_ms);
+    if age_ms < i64::from(thresholds.fresh_ms) {
+        "🟢 Fresh"
+    } else if age_ms < i64::from(thresholds.recent_ms) {
+        "🟡 Recent"
+    } else if age_ms < i64::from(thresholds.stale_ms) {
+        "🟠 Aging"
+    } else {
+        "🔴 Stale"
+    }
+}
+
+/// Classify wall strength relative to the detection threshold
+///
+/// Bands scale with the median level size instead of absolute base units, so a
+/// "Strong" wall means the same thing on a thin altcoin book as on BTCUSDT:
+/// - 💪 Strong: >= 4x the detection threshold
+/// - 🔷 Moderate: >= 2x the detection threshold
+/// - 🔹 Weak: just above the threshold
+///
+/// # Arguments
+/// * `median_multiple` - Wall size as a multiple of the median level size
+/// * `wall_multiplier` - Detection threshold as a multiple of the median
+pub fn wall_strength(median_multiple: f64, wall_multiplier: f64) -> &'static str {
+    let threshold = wall_multiplier.max(f64::EPSILON);
+    let relative = median_multiple / threshold;
+    if relative >= 4.0 {
+        "💪 Strong"
+    } else if relative >= 2.0 {
+        "🔷 Moderate"
+    } else {
+        "🔹 Weak"
+    }
+}
+
+/// Status glyphs that prefix a label, rendered as `[LABEL]` in plain text
+const LABEL_GLYPHS: [char; 12] = [
+    '🟢', '🟡', '🟠', '🔴', '⚪', '✅', '❌', '⚠', '🔄', '💪', '🔷', '🔹',
+];
+
+/// Standalone glyphs and typography with a fixed ASCII replacement
+const GLYPH_REPLACEMENTS: [(char, &str); 16] = [
+    ('📈', "[UP]"),
+    ('📉', "[DOWN]"),
+    ('➡', "[FLAT]"),
+    ('⬆', "[UP]"),
+    ('⬇', "[DOWN]"),
+    ('↗', "[UP]"),
+    ('↘', "[DOWN]"),
+    ('→', "->"),
+    ('≥', ">="),
+    ('≤', "<="),
+    ('Ⓢ', "S"),
+    ('—', "-"),
+    ('–', "-"),
+    ('±', "+/-"),
+    ('×', "x"),
+    ('•', "-"),
+];
+
+/// Rewrite emoji indicators as plain ASCII text
+///
+/// - A status glyph followed by a label becomes the bracketed label in upper
+///   case: `🟢 Buy Pressure` -> `[BUY PRESSURE]`, `⚠️ Crossed` -> `[CROSSED]`
+/// - A status glyph before markup or another glyph is dropped:
+///   `⚠️ **WARNING:**` -> `**WARNING:**`
+/// - Trend arrows become `[UP]` / `[DOWN]` / `[FLAT]`, and typographic symbols
+///   their ASCII spelling (`≥` -> `>=`)
+///
+/// Any other non-ASCII character is removed, so the result is pure ASCII.
+pub fn plain_text_indicators(markdown: &str) -> String {
+    let mut plain = String::with_capacity(markdown.len());
+    let mut chars = markdown.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c.is_ascii() {
+            plain.push(c);
+        } else if LABEL_GLYPHS.contains(&c) {
+            // Emoji presentation selector of e.g. ⚠️
+            chars.next_if_eq(&'\u{FE0F}');
+            if chars.peek() != Some(&' ') {
+                continue;
+            }
+            chars.next();
+            let label: String = chars
+                .clone()
+                .take_while(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-'))
+                .collect();
+            let label = label.trim_end();
+            if !label.is_empty() {
+                plain.push_str(&format!("[{}]", label.to_uppercase()));
+                chars.nth(label.len() - 1);
+            }
+        } else if let Some((_, ascii)) = GLYPH_REPLACEMENTS.iter().find(|(glyph, _)| *glyph == c) {
+            plain.push_str(ascii);
+        }
+    }
+
+    plain
+}
+
+/// Quote assets recognised by [`split_symbol`], longest first so that e.g.
+/// `BTCFDUSD` splits on `FDUSD` rather than falling through to a shorter suffix
+const KNOWN_QUOTE_ASSETS: [&str; 10] = [
+    "FDUSD", "USDT", "USDC", "BUSD", "TUSD", "EUR", "BTC", "ETH", "BNB", "TRY",
+];
+
+/// USD-pegged quote assets rendered with a `$` prefix
+const USD_QUOTE_ASSETS: [&str; 5] = ["FDUSD", "USDT", "USDC", "BUSD", "TUSD"];
+
+/// Split a trading pair into `(base, quote)` assets
+///
+/// Matches known quote suffixes (USDT, BUSD, BTC, ETH, ...). Symbols with no
+/// recognised quote fall back to `(symbol, "")`, which renders unlabelled values.
+///
+/// Examples:
+/// - "ETHUSDT" -> ("ETH", "USDT")
+/// - "SOLBTC" -> ("SOL", "BTC")
+/// - "FDUSDUSDT" -> ("FDUSD", "USDT")
+pub fn split_symbol(symbol: &str) -> (&str, &str) {
+    KNOWN_QUOTE_ASSETS
+        .iter()
+        .find_map(|quote| {
+            symbol
+                .strip_suffix(quote)
+                .filter(|base| !base.is_empty())
+                .map(|base| (base, *quote))
+        })
+        .unwrap_or((symbol, ""))
+}
+
+/// Whether `quote` is a USD stablecoin (prices shown as `$1 234.56`)
+pub fn is_usd_quote(quote: &str) -> bool {
+    USD_QUOTE_ASSETS.contains(&quote)
+}
+
+/// Default number of price decimals for a quote asset
+///
+/// USD quotes use cents; BTC-quoted pairs need satoshi precision.
+pub fn quote_price_decimals(quote: &str) -> usize {
+    match quote {
+        q if is_usd_quote(q) => 2,
+        "BTC" => 8,
+        "ETH" | "BNB" => 6,
+        _ => 4,
+    }
+}
+
+/// Number of price decimals implied by an exchange tick size
+///
+/// Counts significant fractional digits, so Binance's padded strings work:
+/// "0.01000000" -> 2, "0.0001" -> 4, "1.00000000" -> 0.
+pub fn price_decimals_for_tick(tick: &str) -> usize {
+    tick.trim()
+        .split_once('.')
+        .map(|(_, fraction)| fraction.trim_end_matches('0').len())
+        .unwrap_or(0)
+}
+
+/// Format a price string labelled with its quote asset
+///
+/// USD quotes get a `$` prefix ("$113 559.99"), others a suffix ("0.00231400 BTC").
+pub fn format_quote_price(price: &str, quote: &str, decimals: usize) -> String {
+    label_quote(format_price(price, decimals), quote)
+}
+
+/// Format an f64 price labelled with its quote asset (see [`format_quote_price`])
+pub fn format_quote_price_f64(price: f64, quote: &str, decimals: usize) -> String {
+    label_quote(format_price_f64(price, decimals), quote)
+}
+
+fn label_quote(amount: String, quote: &str) -> String {
+    if is_usd_quote(quote) {
+        format!("${}", amount)
+    } else if quote.is_empty() {
+        amount
+    } else {
+        format!("{} {}", amount, quote)
+    }
+}
+
+/// Format a base-asset quantity with its asset label (e.g. "1.2500 ETH")
+pub fn format_base_qty(qty: f64, base: &str) -> String {
+    format!("{:.4} {}", qty, base)
+}
+
+/// Quote-asset notional of a base-asset `size` at `mid_price`
+pub fn quote_notional(size: f64, mid_price: f64) -> f64 {
+    size * mid_price
+}
+
+/// Approximate quote notional label (e.g. "~$97 500", "~0.03450000 BTC")
+///
+/// USD quotes drop cents from $1 000 up; other quotes use their price decimals.
+pub fn format_notional(notional: f64, quote: &str) -> String {
+    let decimals = if is_usd_quote(quote) && notional.abs() >= 1_000.0 {
+        0
+    } else {
+        quote_price_decimals(quote)
+    };
+    format!(
+        "~{}",
+        label_quote(format_price_f64(notional, decimals), quote)
+    )
+}
+
+/// Base-asset size followed by its quote notional at `mid_price`
+///
+/// "1.5 BTC (~$97 500)"; just "1.5 BTC" when `mid_price` is `None` or not
+/// positive, the size doesn't parse, or the quote asset is unknown.
+pub fn format_size_with_notional(
+    size: &str,
+    base: &str,
+    quote: &str,
+    mid_price: Option<f64>,
+) -> String {
+    let notional = mid_price
+        .filter(|mid| *mid > 0.0 && !quote.is_empty())
+        .zip(size.parse::<f64>().ok())
+        .map(|(mid, size)| quote_notional(size, mid));
+    match notional {
+        Some(notional) => format!("{} {} ({})", size, base, format_notional(notional, quote)),
+        None => format!("{} {}", size, base),
+    }
+}
+
 /// Format a DateTime<Utc> as human-readable string
 pub fn format_datetime(dt: chrono::DateTime<chrono::Utc>) -> String {
     dt.format("%Y-%m-%d %H:%M:%S UTC").to_string()
@@ -169,6 +404,41 @@ pub fn format_datetime(dt: chrono::DateTime<chrono::Utc>) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_notional_is_size_times_mid() {
+        let mid = 65_000.125;
+        for size in [1.5, 0.00012, 37.25] {
+            let expected = size * mid;
+            assert!((quote_notional(size, mid) - expected).abs() < 1e-9);
+
+            // The rendered amount rounds to whole dollars from $1 000 up
+            let label = format_size_with_notional(&size.to_string(), "BTC", "USDT", Some(mid));
+            let shown: f64 = label
+                .split("(~$")
+                .nth(1)
+                .unwrap()
+                .trim_end_matches(')')
+                .replace(' ', "")
+                .parse()
+                .unwrap();
+            let tolerance = if expected >= 1_000.0 { 0.5 } else { 0.005 };
+            assert!((shown - expected).abs() <= tolerance, "{}", label);
+        }
+
+        assert_eq!(
+            format_size_with_notional("1.5", "BTC", "USDT", Some(65_000.0)),
+            "1.5 BTC (~$97 500)"
+        );
+        assert_eq!(
+            format_size_with_notional("2", "ETH", "BTC", Some(0.05)),
+            "2 ETH (~0.10000000 BTC)"
+        );
+        assert_eq!(
+            format_size_with_notional("1.5", "BTC", "USDT", None),
+            "1.5 BTC"
+        );
+    }
+
     #[test]
     fn test_build_table() {
         let headers = vec!["Name", "Value"];
@@ -182,6 +452,27 @@ mod tests {
         assert!(table.contains("| Price | $42,000 |"));
     }
 
+    #[test]
+    fn test_split_symbol() {
+        assert_eq!(split_symbol("SOLBTC"), ("SOL", "BTC"));
+        assert_eq!(split_symbol("ETHUSDT"), ("ETH", "USDT"));
+        assert_eq!(split_symbol("FDUSDUSDT"), ("FDUSD", "USDT"));
+        assert_eq!(split_symbol("BTCFDUSD"), ("BTC", "FDUSD"));
+        // No recognised quote: fall back to the whole symbol, unlabelled
+        assert_eq!(split_symbol("ABCXYZ"), ("ABCXYZ", ""));
+        // A bare quote asset is not split into an empty base
+        assert_eq!(split_symbol("USDT"), ("USDT", ""));
+    }
+
+    #[test]
+    fn test_format_quote_price() {
+        assert_eq!(format_quote_price("113559.99", "USDT", 2), "$113 559.99");
+        assert_eq!(format_quote_price("0.002314", "BTC", 8), "0.00231400 BTC");
+        assert_eq!(format_quote_price_f64(12.5, "", 1), "12.5");
+        assert_eq!(quote_price_decimals("BTC"), 8);
+        assert_eq!(quote_price_decimals("FDUSD"), 2);
+    }
+
     #[test]
     fn test_build_list() {
         let items = vec!["First".to_string(), "Second".to_string()];
@@ -193,6 +484,54 @@ mod tests {
         assert!(ordered.contains("1. First"));
     }
 
+    #[test]
+    fn test_wall_strength_relative_to_median() {
+        // 2x threshold: Moderate from 4x median, Strong from 8x median
+        assert_eq!(wall_strength(3.0, 2.0), "🔹 Weak");
+        assert_eq!(wall_strength(4.0, 2.0), "🔷 Moderate");
+        assert_eq!(wall_strength(8.0, 2.0), "💪 Strong");
+
+        // Same size relative to median is weaker under a stricter threshold
+        assert_eq!(wall_strength(8.0, 5.0), "🔹 Weak");
+    }
+
+    #[test]
+    fn test_freshness_label_boundaries() {
+        let defaults = FreshnessThresholds::default();
+        assert_eq!(freshness_label(-5, &defaults), "🟢 Fresh");
+        assert_eq!(freshness_label(999, &defaults), "🟢 Fresh");
+        assert_eq!(freshness_label(1000, &defaults), "🟡 Recent");
+        assert_eq!(freshness_label(4999, &defaults), "🟡 Recent");
+        assert_eq!(freshness_label(5000, &defaults), "🟠 Aging");
+        assert_eq!(freshness_label(29_999, &defaults), "🟠 Aging");
+        assert_eq!(freshness_label(30_000, &defaults), "🔴 Stale");
+
+        let slow = FreshnessThresholds {
+            fresh_ms: 10_000,
+            recent_ms: 60_000,
+            stale_ms: 300_000,
+        };
+        assert_eq!(freshness_label(30_000, &slow), "🟡 Recent");
+        assert_eq!(freshness_label(300_000, &slow), "🔴 Stale");
+    }
+
+    #[test]
+    fn test_plain_text_indicators() {
+        assert_eq!(
+            plain_text_indicators("| Data Age | 🟢 Fresh |\n"),
+            "| Data Age | [FRESH] |\n"
+        );
+        assert_eq!(
+            plain_text_indicators("0.5 bps ⚠️ Crossed, 🟢 Positive (buying pressure)"),
+            "0.5 bps [CROSSED], [POSITIVE] (buying pressure)"
+        );
+        assert_eq!(
+            plain_text_indicators("⚠️ **WARNING:** 📈 2.5% | 🟢⬆️ | ≥1.2"),
+            "**WARNING:** [UP] 2.5% | [UP] | >=1.2"
+        );
+        assert!(plain_text_indicators("USDⓈ-M ✨ done 🔴").is_ascii());
+    }
+
     #[test]
     fn test_build_section_header() {
         assert_eq!(build_section_header("Title", 2), "## Title\n\n");
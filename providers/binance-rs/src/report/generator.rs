@@ -1,31 +1,78 @@
 // Report generator - main orchestrator for creating market intelligence reports
 
+use super::formatter;
 use super::sections;
-use super::{MarketReport, ReportCache, ReportOptions};
+use super::util;
+use super::{
+    MarketReport, ReportCache, ReportOptions, ReportPreset, ReportSection,
+    DEFAULT_ANALYTICS_TIMEOUT_MS,
+};
 use crate::binance::BinanceClient;
+use crate::error::McpError;
+use crate::market_data::MarketDataSource;
 use crate::orderbook::metrics;
 use crate::orderbook::OrderBookManager;
-use std::sync::Arc;
+use crate::price_change::{fetch_price_change, price_change_from_klines, PriceChange, PriceWindow};
+use crate::timestamp::Timestamp;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
+use tokio::sync::{mpsc, Semaphore};
+use tokio_util::sync::CancellationToken;
+
+/// Window for the VWAP row in the price overview section (1 hour)
+#[cfg(feature = "orderbook_analytics")]
+const REPORT_VWAP_WINDOW_SECS: u32 = 3600;
+
+/// Trailing windows shown as extra change rows in the price overview section
+const REPORT_PRICE_CHANGE_WINDOWS: [PriceWindow; 3] = [
+    PriceWindow::OneHour,
+    PriceWindow::FourHours,
+    PriceWindow::SevenDays,
+];
+
+/// Bucket size for the open interest history and long/short ratio
+const DERIVATIVES_PERIOD: &str = "1h";
+/// Open interest buckets fetched for the change row (24h of 1h buckets)
+const DERIVATIVES_OI_HISTORY_LIMIT: u32 = 25;
 
 /// Main service for generating market intelligence reports
-pub struct ReportGenerator {
-    binance_client: Arc<BinanceClient>,
+///
+/// Generic over the exchange REST client (see [`MarketDataSource`]); defaults to
+/// [`BinanceClient`].
+pub struct ReportGenerator<S: MarketDataSource = BinanceClient> {
+    market_data: Arc<S>,
     orderbook_manager: Arc<OrderBookManager>,
     cache: Arc<ReportCache>,
     #[cfg(feature = "orderbook_analytics")]
     analytics_storage: Option<Arc<crate::orderbook::analytics::SnapshotStorage>>,
     #[cfg(feature = "orderbook_analytics")]
     trade_storage: Option<Arc<crate::orderbook::analytics::TradeStorage>>,
+    /// Analytics storage failed to open: analytics sections report it unavailable
+    /// instead of rendering placeholders
+    #[cfg(feature = "orderbook_analytics")]
+    analytics_unavailable: bool,
+    /// Reports built from fresh data (cache hits excluded)
+    generations: AtomicU64,
+    /// Price display decimals per symbol, derived from exchange-info tick sizes
+    price_decimals: Mutex<HashMap<String, usize>>,
+    /// Fresh reports are also written under this directory for audit
+    archive_dir: Option<PathBuf>,
+    /// Caps analytics queries running at once across all reports
+    #[cfg_attr(not(feature = "orderbook_analytics"), allow(dead_code))]
+    analytics_permits: Arc<Semaphore>,
 }
 
-impl ReportGenerator {
+impl<S: MarketDataSource> ReportGenerator<S> {
     /// Creates a new report generator with dependency injection.
     ///
     /// # Arguments
-    /// * `binance_client` - Shared Binance API client for market data fetching
+    /// * `market_data` - Shared exchange client for REST market data (e.g. [`BinanceClient`])
     /// * `orderbook_manager` - Shared order book manager for WebSocket-powered data
-    /// * `cache_ttl_secs` - Cache time-to-live in seconds (typically 60s)
+    /// * `cache_ttl_secs` - Cache time-to-live in seconds (typically 60s, see
+    ///   [`cache_ttl_from_env`](super::cache_ttl_from_env))
     ///
     /// # Example
     /// ```no_run
@@ -39,46 +86,87 @@ impl ReportGenerator {
     /// let generator = ReportGenerator::new(client, orderbook, 60);
     /// ```
     pub fn new(
-        binance_client: Arc<BinanceClient>,
+        market_data: Arc<S>,
         orderbook_manager: Arc<OrderBookManager>,
         cache_ttl_secs: u64,
     ) -> Self {
         Self {
-            binance_client,
+            market_data,
             orderbook_manager,
             cache: Arc::new(ReportCache::new(cache_ttl_secs)),
             #[cfg(feature = "orderbook_analytics")]
             analytics_storage: None,
             #[cfg(feature = "orderbook_analytics")]
             trade_storage: None,
+            #[cfg(feature = "orderbook_analytics")]
+            analytics_unavailable: false,
+            generations: AtomicU64::new(0),
+            price_decimals: Mutex::new(HashMap::new()),
+            archive_dir: None,
+            analytics_permits: Arc::new(Semaphore::new(util::DEFAULT_ANALYTICS_CONCURRENCY)),
         }
     }
 
     /// Creates a new report generator with analytics storage (Feature 019)
     ///
     /// # Arguments
-    /// * `binance_client` - Shared Binance API client for market data fetching
+    /// * `market_data` - Shared exchange client for REST market data
     /// * `orderbook_manager` - Shared order book manager for WebSocket-powered data
     /// * `cache_ttl_secs` - Cache time-to-live in seconds (typically 60s)
     /// * `analytics_storage` - Optional snapshot storage for advanced analytics
     /// * `trade_storage` - Optional trade storage for volume profile and flow analysis
     #[cfg(feature = "orderbook_analytics")]
     pub fn new_with_analytics(
-        binance_client: Arc<BinanceClient>,
+        market_data: Arc<S>,
         orderbook_manager: Arc<OrderBookManager>,
         cache_ttl_secs: u64,
         analytics_storage: Arc<crate::orderbook::analytics::SnapshotStorage>,
-        trade_storage: Arc<crate::orderbook::analytics::TradeStorage>,
+        trade_storage: Option<Arc<crate::orderbook::analytics::TradeStorage>>,
     ) -> Self {
         Self {
-            binance_client,
+            market_data,
             orderbook_manager,
             cache: Arc::new(ReportCache::new(cache_ttl_secs)),
             analytics_storage: Some(analytics_storage),
-            trade_storage: Some(trade_storage),
+            trade_storage,
+            analytics_unavailable: false,
+            generations: AtomicU64::new(0),
+            price_decimals: Mutex::new(HashMap::new()),
+            archive_dir: None,
+            analytics_permits: Arc::new(Semaphore::new(util::DEFAULT_ANALYTICS_CONCURRENCY)),
         }
     }
 
+    /// Marks analytics storage as unavailable (it failed to open at startup)
+    ///
+    /// Microstructure, anomaly and health sections then fail with
+    /// `DataSourceUnavailable` rather than showing their no-analytics placeholders.
+    #[cfg(feature = "orderbook_analytics")]
+    pub fn with_analytics_unavailable(mut self) -> Self {
+        self.analytics_unavailable = true;
+        self
+    }
+
+    /// Archives every freshly generated report to `{dir}/{SYMBOL}/{generated_at}.md`
+    /// (see [`archive_dir_from_env`](super::archive_dir_from_env))
+    ///
+    /// Cache hits are not written again. The write runs on a spawned task, so it
+    /// never delays the report; failures are logged and otherwise ignored.
+    pub fn with_archive_dir(mut self, archive_dir: Option<PathBuf>) -> Self {
+        self.archive_dir = archive_dir;
+        self
+    }
+
+    /// Limits analytics queries running at once across all reports to `limit`
+    /// (min 1; see [`analytics_concurrency_from_env`](super::analytics_concurrency_from_env))
+    ///
+    /// Queries beyond the limit wait for a permit, and that wait counts against
+    /// the section's `analytics_timeout_ms`.
+    pub fn with_analytics_concurrency(mut self, limit: usize) -> Self {
+        self.analytics_permits = Arc::new(Semaphore::new(limit.max(1)));
+        self
+    }
+
     /// Generates a comprehensive market intelligence report for the specified symbol.
     ///
     /// This is the primary method for Feature 018. It orchestrates data fetching from
@@ -88,23 +176,33 @@ impl ReportGenerator {
     /// # Arguments
     /// * `symbol` - Trading pair (e.g., "BTCUSDT", "ETHUSDT")
     /// * `options` - Report customization options (sections, volume window, depth)
+    /// * `cancel` - Checked between sections; cancelling it (e.g. when the client
+    ///   disconnects) stops generation before the next section is built
     ///
     /// # Returns
     /// * `Ok(MarketReport)` - Complete report with markdown content and metadata
-    /// * `Err(String)` - Validation error if options are invalid
+    /// * `Err(String)` - Validation error if options are invalid, or generation
+    ///   was cancelled (nothing is cached or archived)
     ///
     /// # Behavior
     /// 1. Validates the provided options
     /// 2. Checks cache for existing report matching symbol + options
     /// 3. If cache miss, fetches data from Binance API and WebSocket streams in parallel
     /// 4. Builds requested report sections (or all sections if unspecified)
-    /// 5. Caches the generated report for 60 seconds
+    /// 5. Caches the generated report for the configured TTL
     /// 6. Returns the report with generation metadata
     ///
+    /// With `options.no_cache` steps 2 and 5 are skipped: the report is always
+    /// freshly generated and never stored.
+    ///
+    /// With `options.max_total_ms` set, generation stops at the deadline: the
+    /// sections finished so far are returned with `partial` set and the rest
+    /// listed in `failed_sections`. Partial reports are not cached.
+    ///
     /// # Performance
     /// - **Cache hit**: <3ms (cached report returned with original metadata)
     /// - **Cache miss**: <500ms (parallel data fetch + report generation)
-    /// - **Cache TTL**: 60 seconds
+    /// - **Cache TTL**: `REPORT_CACHE_TTL_SECS` (default 60 seconds)
     ///
     /// # Graceful Degradation
     /// If individual data sources fail, the corresponding sections will show
@@ -113,9 +211,14 @@ impl ReportGenerator {
     /// # Example
     /// ```no_run
     /// # use binance_provider::report::{ReportGenerator, ReportOptions};
+    /// # use tokio_util::sync::CancellationToken;
     /// # async fn example(generator: &ReportGenerator) -> Result<(), String> {
+    /// let cancel = CancellationToken::new();
+    ///
     /// // Full report with all sections
-    /// let report = generator.generate_report("BTCUSDT", ReportOptions::default()).await?;
+    /// let report = generator
+    ///     .generate_report("BTCUSDT", ReportOptions::default(), &cancel)
+    ///     .await?;
     /// println!("{}", report.markdown_content);
     ///
     /// // Custom report with specific sections
@@ -126,8 +229,9 @@ impl ReportGenerator {
     ///     ]),
     ///     volume_window_hours: Some(48),
     ///     orderbook_levels: Some(50),
+    ///     ..Default::default()
     /// };
-    /// let custom_report = generator.generate_report("ETHUSDT", options).await?;
+    /// let custom_report = generator.generate_report("ETHUSDT", options, &cancel).await?;
     /// # Ok(())
     /// # }
     /// ```
@@ -135,6 +239,7 @@ impl ReportGenerator {
         &self,
         symbol: &str,
         options: ReportOptions,
+        cancel: &CancellationToken,
     ) -> Result<MarketReport, String> {
         let start_time = Instant::now();
         let symbol_upper = symbol.to_uppercase();
@@ -147,173 +252,919 @@ impl ReportGenerator {
         let cache_key = options.to_cache_key(&symbol_upper);
 
         // Check cache (P1 fix: preserve metadata)
-        if let Some(cached_report) = self.cache.get(&cache_key) {
-            // P1 fix: Return cached report with ALL original metadata preserved
-            // This ensures generation_time_ms matches the footer inside markdown_content
-            // and allows consumers to reason about actual generation cost vs. cache hits
-            return Ok(cached_report);
+        if !options.no_cache {
+            if let Some(cached_report) = self.cache.get(&cache_key) {
+                // P1 fix: Return cached report with ALL original metadata preserved
+                // This ensures generation_time_ms matches the footer inside markdown_content
+                // and allows consumers to reason about actual generation cost vs. cache hits
+                return Ok(cached_report);
+            }
         }
 
-        // Fetch all data sources in parallel
-        let ticker_fut = self.binance_client.get_24hr_ticker(&symbol_upper);
-        let orderbook_fut = self.orderbook_manager.get_order_book(&symbol_upper);
-
-        let (ticker_result, orderbook_result) = tokio::join!(ticker_fut, orderbook_fut);
+        let mut built = Vec::new();
+        let (now_ms, data_age_ms, unfinished) = self
+            .build_sections_with_deadline(&symbol_upper, &options, cancel, |key, section| {
+                built.push((key, section))
+            })
+            .await
+            .ok_or_else(|| format!("Report generation for {} cancelled", symbol_upper))?;
+        let report = Self::assemble_report(
+            &symbol_upper,
+            now_ms,
+            data_age_ms,
+            built,
+            &unfinished,
+            start_time,
+            &options,
+        );
+        self.archive_report(&report);
 
-        // Calculate data age
-        let now_ms = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis() as i64;
+        // Cache result (P0 fix: use cache_key that includes options); a partial
+        // report would otherwise be served in place of a complete one
+        if !options.no_cache && !report.partial {
+            self.cache.set(cache_key, report.clone());
+        }
 
-        let data_age_ms = 500; // Placeholder for actual age calculation
+        Ok(report)
+    }
 
-        // Build sections
-        let ticker_data = ticker_result.ok();
-        let orderbook_data = orderbook_result.ok();
-        let orderbook_metrics = orderbook_data
-            .as_ref()
-            .and_then(|ob| metrics::calculate_metrics(ob));
+    /// Streaming variant of [`generate_report`](Self::generate_report)
+    ///
+    /// Sections are sent over the returned channel as soon as each one is built, in
+    /// report order: the header first (before any data is fetched), then price,
+    /// order book, liquidity and the analytics-backed sections, and finally a
+    /// `footer` section with the generation metadata. Concatenating the rendered
+    /// sections yields the same markdown as the blocking API. Sections whose data
+    /// source fails or exceeds `analytics_timeout_ms` are still sent with their
+    /// degraded placeholder content.
+    ///
+    /// The cache is not consulted, but the assembled report is cached once the
+    /// stream completes so a following `generate_report` call is a cache hit
+    /// (unless `options.no_cache` is set).
+    ///
+    /// Cancelling `cancel` stops the task before the next section: the channel
+    /// is closed without a footer and nothing is cached or archived. Reaching
+    /// `options.max_total_ms` instead sends a `deadline_exceeded` notice in place
+    /// of the unfinished sections, then the footer; the partial report is not
+    /// cached.
+    ///
+    /// # Returns
+    /// * `Ok(receiver)` - Channel closed after the footer is sent (or on cancellation)
+    /// * `Err(String)` - Validation error if options are invalid
+    pub fn generate_report_stream(
+        self: &Arc<Self>,
+        symbol: &str,
+        options: ReportOptions,
+        cancel: CancellationToken,
+    ) -> Result<mpsc::UnboundedReceiver<ReportSection>, String> {
+        options.validate()?;
 
-        let mut failed_sections = Vec::new();
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let generator = Arc::clone(self);
+        let symbol_upper = symbol.to_uppercase();
 
-        // Build all sections first
-        let header = sections::build_report_header(&symbol_upper, now_ms, data_age_ms);
-        let price = sections::build_price_overview_section(ticker_data.as_ref());
-        let orderbook = sections::build_orderbook_metrics_section(orderbook_metrics.as_ref());
-        let volume_hours = options.volume_window_hours.unwrap_or(24);
+        tokio::spawn(async move {
+            let start_time = Instant::now();
+            let mut built = Vec::new();
+            let Some((now_ms, data_age_ms, unfinished)) = generator
+                .build_sections_with_deadline(&symbol_upper, &options, &cancel, |key, section| {
+                    // A dropped receiver only stops streaming; the report is still cached
+                    let _ = sender.send(section.clone());
+                    built.push((key, section));
+                })
+                .await
+            else {
+                return;
+            };
 
-        // Feature 019 T052: Use async liquidity section when analytics storage available
-        // CROSSED FIX: Pass live orderbook_metrics to avoid historical snapshot mismatches
-        #[cfg(feature = "orderbook_analytics")]
-        let liquidity = if let (Some(storage), Some(trades)) = (&self.analytics_storage, &self.trade_storage) {
-            sections::build_liquidity_analysis_section_async(
-                storage,
-                trades,
+            let report = Self::assemble_report(
                 &symbol_upper,
-                volume_hours,
-                chrono::Utc::now(),
-                orderbook_metrics.as_ref(), // CROSSED FIX: Use live metrics for walls
-            )
-            .await
-        } else {
-            sections::build_liquidity_analysis_section(orderbook_metrics.as_ref(), volume_hours)
+                now_ms,
+                data_age_ms,
+                built,
+                &unfinished,
+                start_time,
+                &options,
+            );
+            generator.archive_report(&report);
+            let footer = ReportSection {
+                name: "footer".to_string(),
+                title: "Report Metadata".to_string(),
+                content: Ok(sections::build_report_footer(
+                    report.generation_time_ms as i32,
+                    false,
+                    options.footer_attribution.as_deref(),
+                    options.show_build_info,
+                )),
+                data_age_ms: None,
+            };
+            let _ = sender.send(if options.emoji {
+                footer
+            } else {
+                footer.without_emoji()
+            });
+
+            if !options.no_cache && !report.partial {
+                generator
+                    .cache
+                    .set(options.to_cache_key(&symbol_upper), report);
+            }
+        });
+
+        Ok(receiver)
+    }
+
+    /// Write `report` to the archive directory in the background (no-op when unset)
+    fn archive_report(&self, report: &MarketReport) {
+        let Some(archive_dir) = &self.archive_dir else {
+            return;
         };
+        let symbol_dir = archive_dir.join(&report.symbol);
+        let path = symbol_dir.join(format!("{}.md", report.generated_at));
+        let content = report.markdown_content.clone();
 
-        #[cfg(not(feature = "orderbook_analytics"))]
-        let liquidity = sections::build_liquidity_analysis_section(orderbook_metrics.as_ref(), volume_hours);
+        tokio::spawn(async move {
+            let result = async {
+                tokio::fs::create_dir_all(&symbol_dir).await?;
+                tokio::fs::write(&path, content).await
+            }
+            .await;
+            match result {
+                Ok(()) => tracing::debug!("Archived report to {}", path.display()),
+                Err(e) => tracing::warn!("Failed to archive report to {}: {}", path.display(), e),
+            }
+        });
+    }
 
-        // Feature 019 T052: Use async order flow section when analytics storage available
-        #[cfg(feature = "orderbook_analytics")]
-        let microstructure = if let Some(storage) = &self.analytics_storage {
-            sections::build_microstructure_section_async(storage, &symbol_upper, chrono::Utc::now())
-                .await
-        } else {
-            sections::build_microstructure_section()
+    /// VWAP over the last hour of stored trades for the price overview row
+    ///
+    /// Returns None without trade storage, when no trades were recorded, or
+    /// when the storage read (run on the blocking pool under the analytics
+    /// concurrency limit) fails or exceeds `timeout_ms`.
+    #[cfg(feature = "orderbook_analytics")]
+    async fn trailing_vwap(
+        &self,
+        symbol: &str,
+        timeout_ms: u64,
+    ) -> Option<(rust_decimal::Decimal, u32)> {
+        use crate::orderbook::analytics::vwap::{compute_vwap, TradeWindow};
+
+        let trade_storage = Arc::clone(self.trade_storage.as_ref()?);
+        let window = TradeWindow::trailing(
+            chrono::Utc::now().timestamp_millis(),
+            std::time::Duration::from_secs(REPORT_VWAP_WINDOW_SECS as u64),
+        );
+        let (start_ms, end_ms) = (window.start_ms, window.end_ms);
+
+        let query_symbol = symbol.to_string();
+        let query = util::spawn_blocking_limited(&self.analytics_permits, move || {
+            trade_storage.query_trades(&query_symbol, start_ms, end_ms)
+        });
+        let trades = util::timeout_analytics(
+            async {
+                query
+                    .await
+                    .map_err(anyhow::Error::from)
+                    .and_then(|trades| trades)
+            },
+            "query_trades",
+            symbol,
+            timeout_ms,
+        )
+        .await
+        .ok()?;
+        compute_vwap(&trades, window)
+            .ok()
+            .map(|vwap| (vwap, REPORT_VWAP_WINDOW_SECS))
+    }
+
+    /// Derivatives sentiment section, or None for symbols without a futures contract
+    ///
+    /// Each futures request is bounded by `timeout_ms`; a failed or timed-out open
+    /// interest request renders the section's placeholder.
+    async fn derivatives_sentiment(&self, symbol: &str, timeout_ms: u64) -> Option<ReportSection> {
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+        let (open_interest, history, long_short) = tokio::join!(
+            tokio::time::timeout(timeout, self.market_data.get_open_interest(symbol)),
+            tokio::time::timeout(
+                timeout,
+                self.market_data.get_open_interest_hist(
+                    symbol,
+                    DERIVATIVES_PERIOD,
+                    Some(DERIVATIVES_OI_HISTORY_LIMIT)
+                )
+            ),
+            tokio::time::timeout(
+                timeout,
+                self.market_data
+                    .get_long_short_ratio(symbol, DERIVATIVES_PERIOD)
+            ),
+        );
+
+        let open_interest = match open_interest {
+            Ok(Ok(open_interest)) => Some(open_interest),
+            Ok(Err(McpError::SymbolNotFound(_))) => {
+                tracing::debug!(
+                    "{} has no futures contract, skipping derivatives section",
+                    symbol
+                );
+                return None;
+            }
+            Ok(Err(e)) => {
+                tracing::warn!("Open interest unavailable for {}: {}", symbol, e);
+                None
+            }
+            Err(_) => {
+                tracing::warn!(
+                    "Open interest request timed out for {} ({}ms)",
+                    symbol,
+                    timeout_ms
+                );
+                None
+            }
         };
+        let history = history.ok().and_then(Result::ok).unwrap_or_default();
+        let long_short = long_short.ok().and_then(Result::ok).unwrap_or_default();
 
-        #[cfg(not(feature = "orderbook_analytics"))]
-        let microstructure = sections::build_microstructure_section();
+        Some(sections::build_derivatives_sentiment_section(
+            symbol,
+            open_interest.as_ref(),
+            &history,
+            &long_short,
+            DERIVATIVES_PERIOD,
+        ))
+    }
 
-        // Feature 019 T052: Use async anomaly detection when analytics storage available
-        #[cfg(feature = "orderbook_analytics")]
-        let anomalies = if let Some(storage) = &self.analytics_storage {
-            sections::build_anomalies_section_async(
-                storage,
-                &symbol_upper,
-                chrono::Utc::now(),
+    /// Price display decimals for `symbol`, from its exchange-info tick size
+    ///
+    /// Tick sizes rarely change, so successful lookups are cached for the life of
+    /// the generator. When exchange info fails or exceeds `timeout_ms` the quote
+    /// asset default is used and the lookup is retried on the next report.
+    async fn price_decimals(&self, symbol: &str, timeout_ms: u64) -> usize {
+        if let Some(decimals) = self.price_decimals.lock().unwrap().get(symbol) {
+            return *decimals;
+        }
+
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+        match tokio::time::timeout(timeout, self.market_data.get_exchange_info(Some(symbol))).await
+        {
+            Ok(Ok(info)) => {
+                if let Some(tick) = info
+                    .symbols
+                    .iter()
+                    .find(|s| s.symbol == symbol)
+                    .and_then(|s| s.tick_size())
+                {
+                    let decimals = formatter::price_decimals_for_tick(tick);
+                    self.price_decimals
+                        .lock()
+                        .unwrap()
+                        .insert(symbol.to_string(), decimals);
+                    return decimals;
+                }
+                tracing::debug!("No PRICE_FILTER tick size for {}", symbol);
+            }
+            Ok(Err(e)) => tracing::debug!("Exchange info unavailable for {}: {}", symbol, e),
+            Err(_) => tracing::debug!(
+                "Exchange info request timed out for {} ({}ms)",
+                symbol,
+                timeout_ms
+            ),
+        }
+
+        formatter::quote_price_decimals(formatter::split_symbol(symbol).1)
+    }
+
+    /// Price changes over [`REPORT_PRICE_CHANGE_WINDOWS`], shortest first
+    ///
+    /// Windows whose klines fail or exceed `timeout_ms` are left out.
+    async fn price_changes(&self, symbol: &str, timeout_ms: u64) -> Vec<PriceChange> {
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+        let results = futures::future::join_all(REPORT_PRICE_CHANGE_WINDOWS.map(|window| {
+            tokio::time::timeout(
+                timeout,
+                fetch_price_change(self.market_data.as_ref(), symbol, window),
             )
-            .await
-        } else {
-            sections::build_anomalies_section(Some(now_ms))
+        }))
+        .await;
+
+        let mut changes = Vec::new();
+        for (result, window) in results.into_iter().zip(REPORT_PRICE_CHANGE_WINDOWS) {
+            let change = match result {
+                Ok(Ok(change)) => change,
+                Ok(Err(e)) => {
+                    tracing::debug!(
+                        "{} change unavailable for {}: {}",
+                        window.label(),
+                        symbol,
+                        e
+                    );
+                    self.stored_price_change(symbol, window, timeout_ms).await
+                }
+                Err(_) => {
+                    tracing::debug!(
+                        "{} change request timed out for {} ({}ms)",
+                        window.label(),
+                        symbol,
+                        timeout_ms
+                    );
+                    self.stored_price_change(symbol, window, timeout_ms).await
+                }
+            };
+            changes.extend(change);
+        }
+        changes
+    }
+
+    /// Price change over `window` from candles synthesized out of stored trades
+    ///
+    /// Fallback for when klines can't be fetched. Returns None without trade
+    /// storage, or when the stored history doesn't reach back to the start of
+    /// the window (the trade stream started later or was down). The storage
+    /// read runs on the blocking pool under the analytics concurrency limit and
+    /// is bounded by `timeout_ms`.
+    #[cfg(feature = "orderbook_analytics")]
+    async fn stored_price_change(
+        &self,
+        symbol: &str,
+        window: PriceWindow,
+        timeout_ms: u64,
+    ) -> Option<PriceChange> {
+        use crate::orderbook::analytics::candles;
+
+        let trade_storage = Arc::clone(self.trade_storage.as_ref()?);
+        let (interval, limit) = window.klines();
+        let interval_ms = candles::interval_ms(interval)?;
+        let end_ms = chrono::Utc::now().timestamp_millis();
+        // Open of the oldest candle Binance would have returned
+        let start_ms = end_ms - end_ms.rem_euclid(interval_ms) - interval_ms * (limit as i64 - 1);
+
+        let query_symbol = symbol.to_string();
+        let query = util::spawn_blocking_limited(&self.analytics_permits, move || {
+            trade_storage.query_trades(&query_symbol, start_ms, end_ms)
+        });
+        let stored = util::timeout_analytics(
+            async {
+                query
+                    .await
+                    .map_err(anyhow::Error::from)
+                    .and_then(|trades| trades)
+            },
+            "query_trades",
+            symbol,
+            timeout_ms,
+        )
+        .await
+        .ok()?;
+
+        // Batches are keyed by flush time, so the first one can hold trades
+        // from before the window
+        let trades: Vec<_> = stored
+            .into_iter()
+            .filter(|trade| (start_ms..=end_ms).contains(&trade.timestamp))
+            .collect();
+        let klines = candles::aggregate_klines(&trades, interval).ok()?;
+        if klines.first()?.get(0)?.as_i64()? != start_ms {
+            return None;
+        }
+
+        tracing::debug!(
+            "{} change for {} synthesized from {} stored trades",
+            window.label(),
+            symbol,
+            trades.len()
+        );
+        price_change_from_klines(symbol, window, &klines)
+    }
+
+    #[cfg(not(feature = "orderbook_analytics"))]
+    async fn stored_price_change(
+        &self,
+        _symbol: &str,
+        _window: PriceWindow,
+        _timeout_ms: u64,
+    ) -> Option<PriceChange> {
+        None
+    }
+
+    /// Recent trades section from the last `count` aggregated trades
+    ///
+    /// The request is bounded by `timeout_ms`; a failed or timed-out request
+    /// renders the section's placeholder.
+    async fn recent_trades(
+        &self,
+        symbol: &str,
+        count: u32,
+        price_decimals: usize,
+        timeout_ms: u64,
+    ) -> ReportSection {
+        let timeout = std::time::Duration::from_millis(timeout_ms);
+        let trades = match tokio::time::timeout(
+            timeout,
+            self.market_data
+                .get_agg_trades(symbol, Some(count), None, None, None),
+        )
+        .await
+        {
+            Ok(Ok(trades)) => Some(trades),
+            Ok(Err(e)) => {
+                tracing::warn!("Recent trades unavailable for {}: {}", symbol, e);
+                None
+            }
+            Err(_) => {
+                tracing::warn!(
+                    "Recent trades request timed out for {} ({}ms)",
+                    symbol,
+                    timeout_ms
+                );
+                None
+            }
         };
 
-        #[cfg(not(feature = "orderbook_analytics"))]
-        let anomalies = sections::build_anomalies_section(Some(now_ms));
+        sections::build_recent_trades_section(symbol, trades.as_deref(), price_decimals)
+    }
 
-        // Feature 019 T052: Use async health section when analytics storage available
-        #[cfg(feature = "orderbook_analytics")]
-        let health = if let Some(storage) = &self.analytics_storage {
-            sections::build_health_section_async(storage, &symbol_upper, chrono::Utc::now())
-                .await
-        } else {
-            sections::build_health_section()
+    /// [`build_sections`](Self::build_sections) bounded by `options.max_total_ms`
+    ///
+    /// When the deadline passes first, the sections already emitted are kept and
+    /// a deadline notice is emitted in place of the rest. Requests still in
+    /// flight are dropped.
+    ///
+    /// # Returns
+    /// `(generated_at_ms, data_age_ms, unfinished)` where `unfinished` lists the
+    /// selected sections not built before the deadline (empty when the report
+    /// completed), or None when generation was cancelled
+    async fn build_sections_with_deadline<F>(
+        &self,
+        symbol_upper: &str,
+        options: &ReportOptions,
+        cancel: &CancellationToken,
+        mut emit: F,
+    ) -> Option<(i64, i32, Vec<&'static str>)>
+    where
+        F: FnMut(&'static str, ReportSection),
+    {
+        let now_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as i64;
+
+        let Some(max_total_ms) = options.max_total_ms else {
+            let data_age_ms = self
+                .build_sections(symbol_upper, options, now_ms, cancel, emit)
+                .await?;
+            return Some((now_ms, data_age_ms, Vec::new()));
         };
 
-        #[cfg(not(feature = "orderbook_analytics"))]
-        let health = sections::build_health_section();
-        let data_health = sections::build_data_health_section(data_age_ms);
+        let mut last_emitted = None;
+        let mut header_age_ms = None;
+        let built = tokio::time::timeout(
+            std::time::Duration::from_millis(max_total_ms),
+            self.build_sections(symbol_upper, options, now_ms, cancel, |key, section| {
+                if key == "header" {
+                    header_age_ms = section.data_age_ms;
+                } else {
+                    last_emitted = Some(key);
+                }
+                emit(key, section);
+            }),
+        )
+        .await;
 
-        // P1 fix: Honor ReportOptions.include_sections
-        let should_include_section = |section_name: &str| -> bool {
-            match &options.include_sections {
-                None => true,                          // Include all
-                Some(list) if list.is_empty() => true, // Include all
-                Some(list) => list.contains(&section_name.to_string()),
+        match built {
+            Ok(data_age_ms) => Some((now_ms, data_age_ms?, Vec::new())),
+            Err(_) => {
+                // Sections are built in report order, so everything selected
+                // after the last one emitted is unfinished
+                let planned = options.planned_sections();
+                let next = last_emitted
+                    .and_then(|key| planned.iter().position(|name| *name == key))
+                    .map_or(0, |index| index + 1);
+                let unfinished = planned[next..].to_vec();
+                tracing::warn!(
+                    "Report for {} hit its {}ms deadline; unfinished sections: {}",
+                    symbol_upper,
+                    max_total_ms,
+                    unfinished.join(", ")
+                );
+
+                let notice = sections::build_deadline_exceeded_section(max_total_ms, &unfinished);
+                emit(
+                    "deadline_exceeded",
+                    if options.emoji {
+                        notice
+                    } else {
+                        notice.without_emoji()
+                    },
+                );
+                Some((now_ms, header_age_ms.unwrap_or_default(), unfinished))
             }
+        }
+    }
+
+    /// Build the sections selected by `options`, passing each to `emit` as soon as it
+    /// is ready (header first, in report order)
+    ///
+    /// `emit` receives the `include_sections` key along with the section, with
+    /// emoji already rewritten as plain text unless `options.emoji` is set.
+    /// `cancel` is checked before each section is built; requests already in
+    /// flight finish, but no further data is fetched once it is cancelled.
+    ///
+    /// # Returns
+    /// `data_age_ms` used for the report metadata, or None when generation was
+    /// cancelled
+    async fn build_sections<F>(
+        &self,
+        symbol_upper: &str,
+        options: &ReportOptions,
+        now_ms: i64,
+        cancel: &CancellationToken,
+        mut emit: F,
+    ) -> Option<i32>
+    where
+        F: FnMut(&'static str, ReportSection),
+    {
+        self.generations.fetch_add(1, Ordering::Relaxed);
+
+        let cancelled = |next_section: &str| {
+            let cancelled = cancel.is_cancelled();
+            if cancelled {
+                tracing::debug!(
+                    "Report for {} cancelled before {}",
+                    symbol_upper,
+                    next_section
+                );
+            }
+            cancelled
         };
 
-        // Collect failed sections (only for included sections)
-        let all_sections = vec![
-            ("price_overview", &price),
-            ("orderbook_metrics", &orderbook),
-            ("liquidity_analysis", &liquidity),
-            ("market_anomalies", &anomalies),
-            ("microstructure_health", &health),
-        ];
+        let mut emit = |key: &'static str, section: ReportSection| {
+            emit(
+                key,
+                if options.emoji {
+                    section
+                } else {
+                    section.without_emoji()
+                },
+            )
+        };
 
-        for (name, section) in &all_sections {
-            if should_include_section(name) && section.content.is_err() {
-                failed_sections.push(section.name.clone());
+        // Quick reports skip every request beyond the ticker and order book
+        let quick = options.preset == Some(ReportPreset::Quick);
+
+        // P1 fix: Honor ReportOptions.include_sections
+        let should_include_section = |section_name: &str| options.includes_section(section_name);
+
+        let data_age_ms = 500; // Placeholder for actual age calculation
+        let freshness = options.freshness();
+
+        // Header always included, and sent before any data is fetched
+        emit(
+            "header",
+            sections::build_report_header(symbol_upper, now_ms, data_age_ms, &freshness),
+        );
+
+        let analytics_timeout_ms = options
+            .analytics_timeout_ms
+            .unwrap_or(DEFAULT_ANALYTICS_TIMEOUT_MS);
+
+        if cancelled("data fetch") {
+            return None;
+        }
+
+        // Fetch all data sources in parallel
+        let ticker_fut = self.market_data.get_24hr_ticker(symbol_upper);
+        let orderbook_fut = self.orderbook_manager.get_order_book(symbol_upper);
+        let decimals_fut = self.price_decimals(symbol_upper, analytics_timeout_ms);
+        let changes_fut = async {
+            if should_include_section("price_overview") && !quick {
+                self.price_changes(symbol_upper, analytics_timeout_ms).await
+            } else {
+                Vec::new()
             }
+        };
+
+        let (ticker_result, orderbook_result, price_decimals, price_changes) =
+            tokio::join!(ticker_fut, orderbook_fut, decimals_fut, changes_fut);
+
+        // An unlisted symbol would fail every section the same way
+        if let Err(McpError::SymbolNotFound(reason)) = &ticker_result {
+            emit(
+                "symbol_not_found",
+                sections::build_symbol_not_found_section(symbol_upper, reason),
+            );
+            return Some(data_age_ms);
         }
 
-        // Assemble markdown (P1 fix: filter by include_sections)
-        let mut markdown = String::new();
-        markdown.push_str(&header.render()); // Header always included
+        let ticker_data = ticker_result.ok();
+        let orderbook_data = orderbook_result.ok();
+        let wall_multiplier = options
+            .wall_multiplier
+            .unwrap_or(metrics::DEFAULT_WALL_MEDIAN_MULTIPLIER);
+        let depth_levels = options
+            .orderbook_levels
+            .map(|levels| levels as usize)
+            .unwrap_or(metrics::DEFAULT_DEPTH_LEVELS);
+        let orderbook_metrics = orderbook_data
+            .as_ref()
+            .and_then(|ob| metrics::calculate_metrics_at_depth(ob, depth_levels, wall_multiplier));
 
+        if cancelled("price_overview") {
+            return None;
+        }
         if should_include_section("price_overview") {
-            markdown.push_str(&price.render());
+            #[cfg(feature = "orderbook_analytics")]
+            let vwap = if quick {
+                None
+            } else {
+                self.trailing_vwap(symbol_upper, analytics_timeout_ms).await
+            };
+            #[cfg(not(feature = "orderbook_analytics"))]
+            let vwap = None;
+
+            emit(
+                "price_overview",
+                sections::build_price_overview_section_with_vwap(
+                    ticker_data.as_ref(),
+                    vwap,
+                    &price_changes,
+                    price_decimals,
+                ),
+            );
+        }
+        if cancelled("orderbook_metrics") {
+            return None;
         }
         if should_include_section("orderbook_metrics") {
-            markdown.push_str(&orderbook.render());
+            emit(
+                "orderbook_metrics",
+                sections::build_orderbook_metrics_section(
+                    orderbook_metrics.as_ref(),
+                    price_decimals,
+                    options.show_notional,
+                ),
+            );
+        }
+
+        let volume_hours = options.volume_window_hours.unwrap_or(24);
+        let max_walls = options.max_walls_per_side.unwrap_or(5) as usize;
+
+        if cancelled("liquidity_analysis") {
+            return None;
         }
         if should_include_section("liquidity_analysis") {
-            markdown.push_str(&liquidity.render());
+            // Feature 019 T052: Use async liquidity section when analytics storage available
+            // CROSSED FIX: Pass live orderbook_metrics to avoid historical snapshot mismatches
+            #[cfg(feature = "orderbook_analytics")]
+            let liquidity = if let (Some(storage), Some(trades)) =
+                (&self.analytics_storage, &self.trade_storage)
+            {
+                sections::build_liquidity_analysis_section_async(
+                    storage,
+                    trades,
+                    symbol_upper,
+                    volume_hours,
+                    chrono::Utc::now(),
+                    orderbook_metrics.as_ref(), // CROSSED FIX: Use live metrics for walls
+                    wall_multiplier,
+                    max_walls,
+                    price_decimals,
+                    options.show_notional,
+                    analytics_timeout_ms,
+                    &self.analytics_permits,
+                )
+                .await
+            } else {
+                sections::build_liquidity_analysis_section(
+                    orderbook_metrics.as_ref(),
+                    volume_hours,
+                    wall_multiplier,
+                    max_walls,
+                    price_decimals,
+                    options.show_notional,
+                )
+            };
+
+            #[cfg(not(feature = "orderbook_analytics"))]
+            let liquidity = sections::build_liquidity_analysis_section(
+                orderbook_metrics.as_ref(),
+                volume_hours,
+                wall_multiplier,
+                max_walls,
+                price_decimals,
+                options.show_notional,
+            );
+
+            emit("liquidity_analysis", liquidity);
+        }
+
+        if cancelled("market_microstructure") {
+            return None;
         }
         if should_include_section("market_microstructure") {
-            markdown.push_str(&microstructure.render());
+            // Feature 019 T052: Use async order flow section when analytics storage available
+            #[cfg(feature = "orderbook_analytics")]
+            let microstructure = if let Some(storage) = &self.analytics_storage {
+                sections::build_microstructure_section_async(
+                    storage,
+                    symbol_upper,
+                    chrono::Utc::now(),
+                    analytics_timeout_ms,
+                    &self.analytics_permits,
+                )
+                .await
+            } else if self.analytics_unavailable {
+                sections::build_storage_unavailable_section(
+                    "market_microstructure",
+                    "Market Microstructure",
+                )
+            } else {
+                sections::build_microstructure_section()
+            };
+
+            #[cfg(not(feature = "orderbook_analytics"))]
+            let microstructure = sections::build_microstructure_section();
+
+            emit("market_microstructure", microstructure);
+        }
+
+        if cancelled("market_anomalies") {
+            return None;
         }
         if should_include_section("market_anomalies") {
-            markdown.push_str(&anomalies.render());
+            // Feature 019 T052: Use async anomaly detection when analytics storage available
+            #[cfg(feature = "orderbook_analytics")]
+            let anomalies = if let Some(storage) = &self.analytics_storage {
+                sections::build_anomalies_section_async(
+                    storage,
+                    symbol_upper,
+                    chrono::Utc::now(),
+                    analytics_timeout_ms,
+                    &self.analytics_permits,
+                )
+                .await
+            } else if self.analytics_unavailable {
+                sections::build_storage_unavailable_section("market_anomalies", "Market Anomalies")
+            } else {
+                sections::build_anomalies_section(Some(now_ms))
+            };
+
+            #[cfg(not(feature = "orderbook_analytics"))]
+            let anomalies = sections::build_anomalies_section(Some(now_ms));
+
+            emit("market_anomalies", anomalies);
+        }
+
+        if cancelled("microstructure_health") {
+            return None;
         }
         if should_include_section("microstructure_health") {
-            markdown.push_str(&health.render());
+            // Feature 019 T052: Use async health section when analytics storage available
+            #[cfg(feature = "orderbook_analytics")]
+            let health = if let Some(storage) = &self.analytics_storage {
+                // Stored snapshots keep 20 levels per side; score live depth over
+                // no more than that
+                let snapshot_levels =
+                    crate::orderbook::analytics::storage::snapshot::SNAPSHOT_LEVELS;
+                let shallow_metrics;
+                let health_metrics = if depth_levels > snapshot_levels {
+                    shallow_metrics = orderbook_data.as_ref().and_then(|ob| {
+                        metrics::calculate_metrics_at_depth(ob, snapshot_levels, wall_multiplier)
+                    });
+                    shallow_metrics.as_ref()
+                } else {
+                    orderbook_metrics.as_ref()
+                };
+                sections::build_health_section_async(
+                    storage,
+                    symbol_upper,
+                    health_metrics,
+                    chrono::Utc::now(),
+                    analytics_timeout_ms,
+                    &self.analytics_permits,
+                )
+                .await
+            } else if self.analytics_unavailable {
+                sections::build_storage_unavailable_section(
+                    "microstructure_health",
+                    "Microstructure Health",
+                )
+            } else {
+                sections::build_health_section()
+            };
+
+            #[cfg(not(feature = "orderbook_analytics"))]
+            let health = sections::build_health_section();
+
+            emit("microstructure_health", health);
+        }
+
+        if cancelled("derivatives_sentiment") {
+            return None;
+        }
+        if should_include_section("derivatives_sentiment") {
+            if let Some(derivatives) = self
+                .derivatives_sentiment(symbol_upper, analytics_timeout_ms)
+                .await
+            {
+                emit("derivatives_sentiment", derivatives);
+            }
+        }
+
+        if cancelled("recent_trades") {
+            return None;
+        }
+        if should_include_section("recent_trades") {
+            let count = options.recent_trades_count.unwrap_or(20);
+            emit(
+                "recent_trades",
+                self.recent_trades(symbol_upper, count, price_decimals, analytics_timeout_ms)
+                    .await,
+            );
+        }
+
+        if cancelled("data_health") {
+            return None;
         }
         if should_include_section("data_health") {
-            markdown.push_str(&data_health.render());
+            emit(
+                "data_health",
+                sections::build_data_health_section(data_age_ms, &freshness),
+            );
+        }
+
+        Some(data_age_ms)
+    }
+
+    /// Assemble built sections (keyed by `include_sections` name) into a report
+    ///
+    /// Sections in `unfinished` (cut off by `max_total_ms`) are reported as
+    /// failed and mark the report partial.
+    fn assemble_report(
+        symbol_upper: &str,
+        now_ms: i64,
+        data_age_ms: i32,
+        built: Vec<(&'static str, ReportSection)>,
+        unfinished: &[&str],
+        start_time: Instant,
+        options: &ReportOptions,
+    ) -> MarketReport {
+        // Sections whose failure is reported in `failed_sections`
+        const TRACKED_SECTIONS: [&str; 7] = [
+            "price_overview",
+            "orderbook_metrics",
+            "liquidity_analysis",
+            "market_anomalies",
+            "microstructure_health",
+            "derivatives_sentiment",
+            "recent_trades",
+        ];
+
+        let mut markdown = String::new();
+        let mut failed_sections = Vec::new();
+        for (key, section) in &built {
+            if TRACKED_SECTIONS.contains(key) && section.content.is_err() {
+                failed_sections.push(section.name.clone());
+            }
+            markdown.push_str(&section.render());
         }
+        failed_sections.extend(unfinished.iter().map(|name| name.to_string()));
 
         let generation_time_ms = start_time.elapsed().as_millis() as i32;
 
         // T043: Add footer to fresh report
-        let footer = sections::build_report_footer(generation_time_ms, false);
-        markdown.push_str(&footer);
+        let footer = sections::build_report_footer(
+            generation_time_ms,
+            false,
+            options.footer_attribution.as_deref(),
+            options.show_build_info,
+        );
+        if options.emoji {
+            markdown.push_str(&footer);
+        } else {
+            markdown.push_str(&formatter::plain_text_indicators(&footer));
+        }
 
-        // Build complete report
-        let report = MarketReport {
+        MarketReport {
             markdown_content: markdown,
-            symbol: symbol_upper.clone(),
+            symbol: symbol_upper.to_string(),
             generated_at: now_ms,
+            generated_at_ts: Timestamp::from_millis(now_ms),
             data_age_ms,
             failed_sections,
             generation_time_ms: generation_time_ms as u64,
-        };
+            partial: !unfinished.is_empty(),
+        }
+    }
 
-        // Cache result (P0 fix: use cache_key that includes options)
-        self.cache.set(cache_key, report.clone());
+    /// Returns cumulative report cache `(hits, misses)` for metrics export.
+    pub fn cache_stats(&self) -> (u64, u64) {
+        self.cache.stats()
+    }
 
-        Ok(report)
+    /// Number of reports built from fresh data since startup (cache hits excluded)
+    pub fn generation_count(&self) -> u64 {
+        self.generations.load(Ordering::Relaxed)
     }
 
     /// Invalidates all cached reports for a symbol across all option combinations.
@@ -342,3 +1193,569 @@ impl ReportGenerator {
         self.cache.invalidate(symbol);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Generator whose REST calls fail fast (connection refused on a local port)
+    fn offline_generator() -> Arc<ReportGenerator> {
+        Arc::new(unshared_offline_generator())
+    }
+
+    fn unshared_offline_generator() -> ReportGenerator {
+        let mut client = BinanceClient::new();
+        client.base_url = "http://127.0.0.1:1".to_string();
+        client.futures_base_url = "http://127.0.0.1:1".to_string();
+        let client = Arc::new(client);
+        let manager = Arc::new(OrderBookManager::new(client.clone()));
+        ReportGenerator::new(client, manager, 60)
+    }
+
+    #[tokio::test]
+    async fn test_stream_sends_header_first_and_degraded_sections() {
+        let generator = offline_generator();
+        let mut receiver = generator
+            .generate_report_stream(
+                "btcusdt",
+                ReportOptions::default(),
+                CancellationToken::new(),
+            )
+            .unwrap();
+
+        let mut streamed = Vec::new();
+        while let Some(section) = receiver.recv().await {
+            streamed.push(section);
+        }
+        let names: Vec<&str> = streamed.iter().map(|s| s.name.as_str()).collect();
+
+        assert_eq!(names.first(), Some(&"header"));
+        assert_eq!(names.last(), Some(&"footer"));
+        let position = |name: &str| names.iter().position(|n| *n == name).unwrap();
+        assert!(position("header") < position("price_overview"));
+        assert!(position("price_overview") < position("liquidity_analysis"));
+        assert!(position("liquidity_analysis") < position("anomalies"));
+
+        // Unreachable data sources still produce their placeholder section
+        let price = &streamed[position("price_overview")];
+        assert!(price.content.is_err());
+        assert!(price.render().contains("[Data Unavailable]"));
+
+        // The assembled report was cached when the stream completed
+        let report = generator
+            .generate_report(
+                "BTCUSDT",
+                ReportOptions::default(),
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(generator.cache_stats().0, 1);
+        assert!(report
+            .failed_sections
+            .contains(&"price_overview".to_string()));
+
+        // Unreachable futures API degrades the section instead of skipping it
+        let derivatives = &streamed[position("derivatives_sentiment")];
+        assert!(derivatives.render().contains("[Data Unavailable]"));
+        assert!(report
+            .failed_sections
+            .contains(&"derivatives_sentiment".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_no_cache_always_generates_fresh_report() {
+        let generator = offline_generator();
+        let options = ReportOptions {
+            include_sections: Some(vec!["price_overview".to_string()]),
+            no_cache: true,
+            ..Default::default()
+        };
+
+        let first = generator
+            .generate_report("BTCUSDT", options.clone(), &CancellationToken::new())
+            .await
+            .unwrap();
+        let second = generator
+            .generate_report("BTCUSDT", options.clone(), &CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(generator.generation_count(), 2);
+        assert_eq!(generator.cache_stats(), (0, 0));
+        assert!(first.markdown_content.contains("Fresh Generation"));
+        assert!(second.markdown_content.contains("Fresh Generation"));
+
+        // Nothing was stored: a cached request still has to generate
+        let cached_options = ReportOptions {
+            no_cache: false,
+            ..options
+        };
+        generator
+            .generate_report("BTCUSDT", cached_options.clone(), &CancellationToken::new())
+            .await
+            .unwrap();
+        generator
+            .generate_report("BTCUSDT", cached_options, &CancellationToken::new())
+            .await
+            .unwrap();
+        assert_eq!(generator.generation_count(), 3);
+        assert_eq!(generator.cache_stats().0, 1);
+    }
+
+    #[tokio::test]
+    async fn test_fresh_reports_archived_but_cache_hits_not() {
+        let archive = tempfile::tempdir().unwrap();
+        let generator =
+            unshared_offline_generator().with_archive_dir(Some(archive.path().to_path_buf()));
+        let options = ReportOptions {
+            include_sections: Some(vec!["price_overview".to_string()]),
+            ..Default::default()
+        };
+
+        let report = generator
+            .generate_report("btcusdt", options.clone(), &CancellationToken::new())
+            .await
+            .unwrap();
+        let path = archive
+            .path()
+            .join("BTCUSDT")
+            .join(format!("{}.md", report.generated_at));
+
+        // The write runs on a spawned task
+        let mut archived = None;
+        for _ in 0..100 {
+            if let Ok(content) = tokio::fs::read_to_string(&path).await {
+                archived = Some(content);
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(archived.as_deref(), Some(report.markdown_content.as_str()));
+
+        // A cache hit returns the same report without writing another file
+        generator
+            .generate_report("BTCUSDT", options, &CancellationToken::new())
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        let files = std::fs::read_dir(archive.path().join("BTCUSDT"))
+            .unwrap()
+            .count();
+        assert_eq!(generator.generation_count(), 1);
+        assert_eq!(files, 1);
+    }
+
+    /// Market data served from memory, with only the required trait methods
+    /// plus futures open interest, which just counts calls
+    struct MockMarketData {
+        /// None answers like Binance for an unlisted symbol
+        ticker: Option<crate::binance::types::Ticker24hr>,
+        /// Kline and futures requests (everything beyond ticker and order book)
+        extra_calls: std::sync::atomic::AtomicUsize,
+        /// Futures requests stall for this long before answering
+        futures_delay: Option<std::time::Duration>,
+    }
+
+    impl MockMarketData {
+        fn new(ticker: crate::binance::types::Ticker24hr) -> Self {
+            Self {
+                ticker: Some(ticker),
+                extra_calls: std::sync::atomic::AtomicUsize::new(0),
+                futures_delay: None,
+            }
+        }
+
+        fn unlisted() -> Self {
+            Self {
+                ticker: None,
+                extra_calls: std::sync::atomic::AtomicUsize::new(0),
+                futures_delay: None,
+            }
+        }
+
+        fn with_futures_delay(mut self, delay: std::time::Duration) -> Self {
+            self.futures_delay = Some(delay);
+            self
+        }
+
+        fn extra_calls(&self) -> usize {
+            self.extra_calls.load(Ordering::SeqCst)
+        }
+    }
+
+    #[tonic::async_trait]
+    impl MarketDataSource for MockMarketData {
+        async fn get_24hr_ticker(
+            &self,
+            _symbol: &str,
+        ) -> Result<crate::binance::types::Ticker24hr, McpError> {
+            self.ticker
+                .clone()
+                .ok_or_else(|| McpError::SymbolNotFound("Invalid symbol.".to_string()))
+        }
+
+        async fn get_order_book(
+            &self,
+            _symbol: &str,
+            _limit: Option<u32>,
+        ) -> Result<crate::binance::types::OrderBook, McpError> {
+            Ok(crate::binance::types::OrderBook {
+                last_update_id: 1,
+                bids: vec![("100.00".to_string(), "2.0".to_string())],
+                asks: vec![("100.10".to_string(), "1.5".to_string())],
+            })
+        }
+
+        async fn get_klines(
+            &self,
+            _symbol: &str,
+            _interval: &str,
+            _limit: Option<u32>,
+        ) -> Result<crate::binance::types::KlineData, McpError> {
+            self.extra_calls.fetch_add(1, Ordering::SeqCst);
+            Ok(Vec::new())
+        }
+
+        async fn get_recent_trades(
+            &self,
+            _symbol: &str,
+            _limit: Option<u32>,
+        ) -> Result<Vec<crate::binance::types::Trade>, McpError> {
+            Ok(Vec::new())
+        }
+
+        async fn get_open_interest(
+            &self,
+            symbol: &str,
+        ) -> Result<crate::binance::types::OpenInterest, McpError> {
+            self.extra_calls.fetch_add(1, Ordering::SeqCst);
+            if let Some(delay) = self.futures_delay {
+                tokio::time::sleep(delay).await;
+            }
+            Err(McpError::SymbolNotFound(symbol.to_string()))
+        }
+    }
+
+    fn eth_ticker() -> crate::binance::types::Ticker24hr {
+        crate::binance::types::Ticker24hr {
+            symbol: "ETHUSDT".to_string(),
+            price_change: "50.00".to_string(),
+            price_change_percent: "2.43".to_string(),
+            weighted_avg_price: "2080.00".to_string(),
+            prev_close_price: "2055.00".to_string(),
+            last_price: "2105.00".to_string(),
+            last_qty: "0.5".to_string(),
+            bid_price: "2104.90".to_string(),
+            ask_price: "2105.10".to_string(),
+            open_price: "2055.00".to_string(),
+            high_price: "2120.00".to_string(),
+            low_price: "2040.00".to_string(),
+            volume: "1000".to_string(),
+            quote_volume: "2080000".to_string(),
+            open_time: 1_700_000_000_000,
+            close_time: 1_700_086_400_000,
+            first_id: 1,
+            last_id: 1000,
+            count: 1000,
+        }
+    }
+
+    /// Offline generator over `market_data` with a replay (never fetched) order book
+    fn mock_generator(market_data: Arc<MockMarketData>) -> Arc<ReportGenerator<MockMarketData>> {
+        let manager = Arc::new(OrderBookManager::with_source(Arc::new(
+            crate::orderbook::source::ReplaySnapshotSource,
+        )));
+        Arc::new(ReportGenerator::new(market_data, manager, 60))
+    }
+
+    #[tokio::test]
+    async fn test_mock_market_data_drives_report_without_network() {
+        // Replay books are never fetched, so the order book side stays offline too
+        let generator = mock_generator(Arc::new(MockMarketData::new(eth_ticker())));
+
+        let report = generator
+            .generate_report(
+                "ethusdt",
+                ReportOptions {
+                    include_sections: Some(vec![
+                        "price_overview".to_string(),
+                        "derivatives_sentiment".to_string(),
+                    ]),
+                    ..Default::default()
+                },
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+
+        assert!(!report
+            .failed_sections
+            .contains(&"price_overview".to_string()));
+        assert!(report.markdown_content.contains("Price Overview"));
+        assert!(report.markdown_content.contains("2.43%"));
+        // Without futures data the derivatives section is omitted, not degraded
+        assert!(!report.markdown_content.contains("Derivatives"));
+        assert!(!report
+            .failed_sections
+            .contains(&"derivatives_sentiment".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_unlisted_symbol_reports_not_found_instead_of_failures() {
+        let generator = mock_generator(Arc::new(MockMarketData::unlisted()));
+
+        let report = generator
+            .generate_report(
+                "NOPEUSDT",
+                ReportOptions::default(),
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+
+        assert!(report.failed_sections.is_empty());
+        assert!(report
+            .markdown_content
+            .contains("Symbol not found or not trading"));
+        assert!(!report.markdown_content.contains("Price Overview"));
+        assert!(!report.markdown_content.contains("Data Unavailable"));
+    }
+
+    #[tokio::test]
+    async fn test_deadline_returns_partial_report() {
+        // Derivatives stall well past the deadline, inside their own timeout
+        let market_data =
+            MockMarketData::new(eth_ticker()).with_futures_delay(std::time::Duration::from_secs(5));
+        let generator = mock_generator(Arc::new(market_data));
+        let options = ReportOptions {
+            include_sections: Some(vec![
+                "price_overview".to_string(),
+                "derivatives_sentiment".to_string(),
+                "data_health".to_string(),
+            ]),
+            analytics_timeout_ms: Some(10_000),
+            max_total_ms: Some(200),
+            ..Default::default()
+        };
+
+        let start = Instant::now();
+        let report = generator
+            .generate_report("ETHUSDT", options.clone(), &CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert!(start.elapsed() < std::time::Duration::from_secs(2));
+        assert!(report.partial);
+        assert_eq!(
+            report.failed_sections,
+            ["derivatives_sentiment", "data_health"]
+        );
+        assert!(report.markdown_content.contains("Price Overview"));
+        assert!(report.markdown_content.contains("Deadline exceeded"));
+        assert!(!report.markdown_content.contains("Data Health"));
+
+        // Partial reports are not cached
+        let (hits, _) = generator.cache_stats();
+        let again = generator
+            .generate_report("ETHUSDT", options, &CancellationToken::new())
+            .await
+            .unwrap();
+        assert!(again.partial);
+        assert_eq!(generator.cache_stats().0, hits);
+    }
+
+    #[cfg(feature = "orderbook_analytics")]
+    #[tokio::test]
+    async fn test_stored_trades_back_price_change_fallback() {
+        use crate::orderbook::analytics::{trade_storage::AggTrade, SnapshotStorage, TradeStorage};
+
+        // Keep the 1h window's start fixed for the duration of the test
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        if now_ms.rem_euclid(60_000) > 55_000 {
+            tokio::time::sleep(std::time::Duration::from_secs(6)).await;
+        }
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let start_ms = now_ms - now_ms.rem_euclid(60_000) - 59 * 60_000;
+
+        let dir = tempfile::tempdir().unwrap();
+        let storage = Arc::new(SnapshotStorage::new(dir.path()).unwrap());
+        let trades = Arc::new(TradeStorage::new(storage.db().unwrap()));
+        let trade = |timestamp: i64, price: &str| AggTrade {
+            price: price.to_string(),
+            quantity: "1".to_string(),
+            timestamp,
+            trade_id: timestamp,
+            buyer_is_maker: false,
+        };
+        // The first batch in the window was flushed after the window opened and
+        // still holds a trade from before it
+        trades
+            .store_batch(
+                "ETHUSDT",
+                start_ms + 10_000,
+                vec![
+                    trade(start_ms - 20_000, "90"),
+                    trade(start_ms + 5_000, "100"),
+                ],
+            )
+            .unwrap();
+        trades
+            .store_batch("ETHUSDT", now_ms, vec![trade(now_ms - 1_000, "110")])
+            .unwrap();
+
+        let manager = Arc::new(OrderBookManager::with_source(Arc::new(
+            crate::orderbook::source::ReplaySnapshotSource,
+        )));
+        let generator = ReportGenerator::new_with_analytics(
+            Arc::new(MockMarketData::new(eth_ticker())),
+            manager,
+            60,
+            storage,
+            Some(trades),
+        );
+
+        let change = generator
+            .stored_price_change("ETHUSDT", PriceWindow::OneHour, 1000)
+            .await
+            .expect("fallback from stored trades");
+        assert_eq!(change.open_price, 100.0);
+        assert_eq!(change.current_price, 110.0);
+        assert_eq!(change.low_price, 100.0);
+    }
+
+    #[tokio::test]
+    async fn test_quick_preset_builds_l1_sections_without_extra_requests() {
+        let market_data = Arc::new(MockMarketData::new(eth_ticker()));
+        let generator = mock_generator(Arc::clone(&market_data));
+        let options = ReportOptions {
+            preset: Some(ReportPreset::Quick),
+            ..Default::default()
+        };
+
+        let mut receiver = generator
+            .generate_report_stream("ETHUSDT", options.clone(), CancellationToken::new())
+            .unwrap();
+        let mut names = Vec::new();
+        while let Some(section) = receiver.recv().await {
+            names.push(section.name);
+        }
+        assert_eq!(
+            names,
+            ["header", "price_overview", "orderbook_metrics", "footer"]
+        );
+        assert_eq!(market_data.extra_calls(), 0);
+
+        let report = generator
+            .generate_report(
+                "ETHUSDT",
+                ReportOptions {
+                    no_cache: true,
+                    ..options
+                },
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+        assert!(report.markdown_content.contains("Price Overview"));
+        assert!(!report.markdown_content.contains("Liquidity Analysis"));
+        assert_eq!(market_data.extra_calls(), 0);
+
+        // The full report does fetch klines and futures data
+        generator
+            .generate_report(
+                "ETHUSDT",
+                ReportOptions::default(),
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+        assert!(market_data.extra_calls() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_preset_cannot_be_combined_with_sections() {
+        let options = ReportOptions {
+            include_sections: Some(vec!["price_overview".to_string()]),
+            preset: Some(ReportPreset::Quick),
+            ..Default::default()
+        };
+        assert!(options.validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_stream_rejects_invalid_options() {
+        let options = ReportOptions {
+            volume_window_hours: Some(0),
+            ..Default::default()
+        };
+        assert!(offline_generator()
+            .generate_report_stream("BTCUSDT", options, CancellationToken::new())
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_before_analytics_skips_remaining_sections() {
+        let market_data = Arc::new(MockMarketData::new(eth_ticker()));
+        let generator = mock_generator(Arc::clone(&market_data));
+        let cancel = CancellationToken::new();
+
+        // The client goes away once the liquidity section has been sent
+        let mut emitted = Vec::new();
+        let mut calls_at_cancel = 0;
+        let result = generator
+            .build_sections("ETHUSDT", &ReportOptions::default(), &cancel, |key, _| {
+                emitted.push(key);
+                if key == "liquidity_analysis" {
+                    calls_at_cancel = market_data.extra_calls();
+                    cancel.cancel();
+                }
+            })
+            .await;
+
+        assert_eq!(result, None);
+        assert_eq!(
+            emitted,
+            [
+                "header",
+                "price_overview",
+                "orderbook_metrics",
+                "liquidity_analysis"
+            ]
+        );
+        // No futures request was made for the skipped derivatives section
+        assert_eq!(market_data.extra_calls(), calls_at_cancel);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_report_is_not_cached() {
+        let generator = mock_generator(Arc::new(MockMarketData::new(eth_ticker())));
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        assert!(generator
+            .generate_report("ETHUSDT", ReportOptions::default(), &cancel)
+            .await
+            .is_err());
+
+        // The stream stops after the header and never sends a footer
+        let mut receiver = generator
+            .generate_report_stream("ETHUSDT", ReportOptions::default(), cancel)
+            .unwrap();
+        let mut names = Vec::new();
+        while let Some(section) = receiver.recv().await {
+            names.push(section.name);
+        }
+        assert_eq!(names, ["header"]);
+
+        generator
+            .generate_report(
+                "ETHUSDT",
+                ReportOptions::default(),
+                &CancellationToken::new(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(generator.cache_stats().0, 0);
+    }
+}
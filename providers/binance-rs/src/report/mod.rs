@@ -11,33 +11,280 @@ pub mod util;
 // Re-export main types
 pub use generator::ReportGenerator;
 
+use crate::timestamp::Timestamp;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Mutex;
 use std::time::{Duration, Instant};
 
+/// Default report cache TTL in seconds
+pub const DEFAULT_CACHE_TTL_SECS: u64 = 60;
+
+/// Default per-call timeout for analytics-backed sections, in milliseconds
+pub const DEFAULT_ANALYTICS_TIMEOUT_MS: u64 = 3000;
+
+/// Report cache TTL from `REPORT_CACHE_TTL_SECS` (default: 60, 0 disables caching)
+///
+/// # Errors
+///
+/// Returns error if the variable is set but not a non-negative integer
+pub fn cache_ttl_from_env() -> Result<u64, Box<dyn std::error::Error>> {
+    cache_ttl_from_value(std::env::var("REPORT_CACHE_TTL_SECS").ok().as_deref())
+}
+
+/// Parse a raw `REPORT_CACHE_TTL_SECS` value (`None` = unset, use the default)
+pub fn cache_ttl_from_value(raw: Option<&str>) -> Result<u64, Box<dyn std::error::Error>> {
+    match raw {
+        Some(raw) => raw.trim().parse::<u64>().map_err(|_| {
+            format!(
+                "REPORT_CACHE_TTL_SECS must be a non-negative integer, got '{}'",
+                raw
+            )
+            .into()
+        }),
+        None => Ok(DEFAULT_CACHE_TTL_SECS),
+    }
+}
+
+/// Concurrent analytics query limit from `REPORT_ANALYTICS_CONCURRENCY`
+/// (default: [`util::DEFAULT_ANALYTICS_CONCURRENCY`])
+///
+/// # Errors
+///
+/// Returns error if the variable is set but not a positive integer
+pub fn analytics_concurrency_from_env() -> Result<usize, Box<dyn std::error::Error>> {
+    analytics_concurrency_from_value(
+        std::env::var("REPORT_ANALYTICS_CONCURRENCY")
+            .ok()
+            .as_deref(),
+    )
+}
+
+/// Parse a raw `REPORT_ANALYTICS_CONCURRENCY` value (`None` = unset, use the default)
+pub fn analytics_concurrency_from_value(
+    raw: Option<&str>,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    match raw {
+        Some(raw) => match raw.trim().parse::<usize>() {
+            Ok(limit) if limit > 0 => Ok(limit),
+            _ => Err(format!(
+                "REPORT_ANALYTICS_CONCURRENCY must be a positive integer, got '{}'",
+                raw
+            )
+            .into()),
+        },
+        None => Ok(util::DEFAULT_ANALYTICS_CONCURRENCY),
+    }
+}
+
+/// Directory freshly generated reports are archived to, from `REPORT_ARCHIVE_DIR`
+///
+/// Unset or blank disables archiving.
+pub fn archive_dir_from_env() -> Option<std::path::PathBuf> {
+    archive_dir_from_value(std::env::var("REPORT_ARCHIVE_DIR").ok().as_deref())
+}
+
+/// Parse a raw `REPORT_ARCHIVE_DIR` value (`None` or blank = archiving disabled)
+pub fn archive_dir_from_value(raw: Option<&str>) -> Option<std::path::PathBuf> {
+    raw.map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(std::path::PathBuf::from)
+}
+
+/// Every section name accepted in [`ReportOptions::include_sections`], in report order
+///
+/// The header and footer are always rendered and cannot be selected.
+pub const REPORT_SECTIONS: &[&str] = &[
+    "price_overview",
+    "orderbook_metrics",
+    "liquidity_analysis",
+    "market_microstructure",
+    "market_anomalies",
+    "microstructure_health",
+    "derivatives_sentiment",
+    "recent_trades",
+    "data_health",
+];
+
+/// Sections that only render a placeholder without the `orderbook_analytics` feature
+pub const ANALYTICS_SECTIONS: &[&str] = &[
+    "market_microstructure",
+    "market_anomalies",
+    "microstructure_health",
+];
+
+/// Predefined section selections for [`ReportOptions::preset`]
+///
+/// | Preset  | Sections                                           |
+/// |---------|----------------------------------------------------|
+/// | `full`  | Every default section (same as omitting the preset) |
+/// | `quick` | Header, price overview and order book metrics       |
+///
+/// `quick` also skips the windowed price change rows (kline requests), the
+/// analytics VWAP and every analytics or futures request, so a warm report
+/// only needs the 24hr ticker and the cached order book (<100ms).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportPreset {
+    Full,
+    Quick,
+}
+
+impl ReportPreset {
+    /// Name used in tool arguments and cache keys
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReportPreset::Full => "full",
+            ReportPreset::Quick => "quick",
+        }
+    }
+
+    /// Sections built for this preset (None = every default section)
+    pub fn sections(&self) -> Option<&'static [&'static str]> {
+        match self {
+            ReportPreset::Full => None,
+            ReportPreset::Quick => Some(&["price_overview", "orderbook_metrics"]),
+        }
+    }
+}
+
+/// Data age boundaries (milliseconds) for the freshness indicators
+///
+/// Ages below `fresh_ms` are fresh, below `recent_ms` recent, below `stale_ms`
+/// aging, and anything older stale. The header, the data health section and
+/// [`formatter::freshness_label`] all read the same thresholds. Fields left out
+/// of a request keep their defaults (1000 / 5000 / 30000).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FreshnessThresholds {
+    pub fresh_ms: u32,
+    pub recent_ms: u32,
+    pub stale_ms: u32,
+}
+
+impl Default for FreshnessThresholds {
+    fn default() -> Self {
+        Self {
+            fresh_ms: 1000,
+            recent_ms: 5000,
+            stale_ms: 30_000,
+        }
+    }
+}
+
 /// Configuration options for report generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReportOptions {
     /// List of section names to include in the report.
-    /// If None or empty, all sections are included.
+    /// If None or empty, all sections are included except opt-in ones
+    /// (`recent_trades`), which must be listed explicitly.
     pub include_sections: Option<Vec<String>>,
 
+    /// Predefined section selection; cannot be combined with `include_sections`.
+    /// Default: None (all sections)
+    #[serde(default)]
+    pub preset: Option<ReportPreset>,
+
     /// Time window in hours for volume profile calculation.
     /// Default: 24 hours, Valid range: 1-168 (1 hour to 7 days)
     pub volume_window_hours: Option<u32>,
 
-    /// Number of order book levels to include in depth analysis.
+    /// Number of order book levels per side feeding bid/ask volume, imbalance
+    /// and microprice in the order book metrics section.
     /// Default: 20 levels, Valid range: 1-100
     pub orderbook_levels: Option<u32>,
+
+    /// Per-call timeout in milliseconds for analytics-backed sections.
+    /// Default: 3000ms, Valid range: 100-10000
+    pub analytics_timeout_ms: Option<u64>,
+
+    /// Overall deadline in milliseconds for generating the whole report; when
+    /// it passes, the sections finished so far are returned with `partial` set
+    /// and the unfinished ones listed in `failed_sections`.
+    /// Default: None (no deadline), Valid range: 100-60000
+    #[serde(default)]
+    pub max_total_ms: Option<u64>,
+
+    /// Liquidity wall threshold as a multiple of the median level size.
+    /// Default: 2.0, Valid range: 1.0-100.0
+    pub wall_multiplier: Option<f64>,
+
+    /// Largest liquidity walls listed per side in the liquidity section.
+    /// Default: 5, Valid range: 1-20
+    pub max_walls_per_side: Option<u32>,
+
+    /// Aggregated trades listed in the opt-in `recent_trades` section.
+    /// Default: 20, Valid range: 1-100
+    pub recent_trades_count: Option<u32>,
+
+    /// Skip both the cache lookup and the cache write for this request.
+    /// Default: false
+    #[serde(default)]
+    pub no_cache: bool,
+
+    /// Decorate the markdown with emoji indicators (🟢, 📈, ...). When false,
+    /// they are rendered as plain ASCII text such as `[FRESH]` or `[BUY PRESSURE]`.
+    /// Default: true
+    #[serde(default = "default_emoji")]
+    pub emoji: bool,
+
+    /// Data age boundaries for the fresh/recent/aging/stale indicators; raise
+    /// them for slow symbols that would otherwise always read as stale.
+    /// Default: 1000 / 5000 / 30000 ms, each 1-3600000 and strictly increasing
+    #[serde(default)]
+    pub freshness_thresholds: Option<FreshnessThresholds>,
+
+    /// Attribution line closing the footer, for white-label deployments; an
+    /// empty string omits it.
+    /// Default: None ("Generated by ForgeTrade MCP Market Data Provider"), at
+    /// most 200 characters on a single line
+    #[serde(default)]
+    pub footer_attribution: Option<String>,
+
+    /// Include the build configuration (enabled features) block in the footer.
+    /// Default: true
+    #[serde(default = "default_show_build_info")]
+    pub show_build_info: bool,
+
+    /// Follow base-asset sizes in wall tables and slippage estimates with their
+    /// approximate quote notional at the mid price, e.g. "1.5 BTC (~$97 500)".
+    /// Default: true
+    #[serde(default = "default_show_notional")]
+    pub show_notional: bool,
+}
+
+fn default_emoji() -> bool {
+    true
+}
+
+fn default_show_build_info() -> bool {
+    true
+}
+
+fn default_show_notional() -> bool {
+    true
 }
 
 impl Default for ReportOptions {
     fn default() -> Self {
         Self {
             include_sections: None, // All sections
+            preset: None,
             volume_window_hours: Some(24),
             orderbook_levels: Some(20),
+            analytics_timeout_ms: Some(DEFAULT_ANALYTICS_TIMEOUT_MS),
+            max_total_ms: None,
+            wall_multiplier: Some(2.0),
+            max_walls_per_side: Some(5),
+            recent_trades_count: Some(20),
+            no_cache: false,
+            emoji: true,
+            freshness_thresholds: None,
+            footer_attribution: None,
+            show_build_info: true,
+            show_notional: true,
         }
     }
 }
@@ -48,6 +295,18 @@ impl ReportOptions {
     /// # Validation Rules
     /// - `volume_window_hours`: Must be between 1 and 168 (1 hour to 7 days)
     /// - `orderbook_levels`: Must be between 1 and 100
+    /// - `analytics_timeout_ms`: Must be between 100 and 10000
+    /// - `max_total_ms`: Must be between 100 and 60000
+    /// - `wall_multiplier`: Must be between 1.0 and 100.0
+    /// - `max_walls_per_side`: Must be between 1 and 20
+    /// - `recent_trades_count`: Must be between 1 and 100
+    /// - `freshness_thresholds`: Each between 1 and 3600000 ms, with
+    ///   `fresh_ms < recent_ms < stale_ms`
+    /// - `footer_attribution`: At most 200 characters, without line breaks
+    /// - `preset`: Cannot be combined with a non-empty `include_sections`
+    /// - `include_sections`: Every name must be in [`REPORT_SECTIONS`]; analytics
+    ///   sections requested without the `orderbook_analytics` feature are logged
+    ///   as a warning (they still render a placeholder)
     ///
     /// # Returns
     /// - `Ok(())` if all options are valid
@@ -83,9 +342,158 @@ impl ReportOptions {
             }
         }
 
+        if let Some(timeout_ms) = self.analytics_timeout_ms {
+            if !(100..=10_000).contains(&timeout_ms) {
+                return Err(format!(
+                    "analytics_timeout_ms must be between 100 and 10000, got {}",
+                    timeout_ms
+                ));
+            }
+        }
+
+        if let Some(max_total_ms) = self.max_total_ms {
+            if !(100..=60_000).contains(&max_total_ms) {
+                return Err(format!(
+                    "max_total_ms must be between 100 and 60000, got {}",
+                    max_total_ms
+                ));
+            }
+        }
+
+        if let Some(multiplier) = self.wall_multiplier {
+            if !(1.0..=100.0).contains(&multiplier) {
+                return Err(format!(
+                    "wall_multiplier must be between 1.0 and 100.0, got {}",
+                    multiplier
+                ));
+            }
+        }
+
+        if let Some(max_walls) = self.max_walls_per_side {
+            if !(1..=20).contains(&max_walls) {
+                return Err(format!(
+                    "max_walls_per_side must be between 1 and 20, got {}",
+                    max_walls
+                ));
+            }
+        }
+
+        if let Some(count) = self.recent_trades_count {
+            if !(1..=100).contains(&count) {
+                return Err(format!(
+                    "recent_trades_count must be between 1 and 100, got {}",
+                    count
+                ));
+            }
+        }
+
+        if let Some(thresholds) = self.freshness_thresholds {
+            let FreshnessThresholds {
+                fresh_ms,
+                recent_ms,
+                stale_ms,
+            } = thresholds;
+            if fresh_ms < 1 || stale_ms > 3_600_000 {
+                return Err(format!(
+                    "freshness_thresholds must be between 1 and 3600000 ms, got {}/{}/{}",
+                    fresh_ms, recent_ms, stale_ms
+                ));
+            }
+            if fresh_ms >= recent_ms || recent_ms >= stale_ms {
+                return Err(format!(
+                    "freshness_thresholds must satisfy fresh_ms < recent_ms < stale_ms, got {}/{}/{}",
+                    fresh_ms, recent_ms, stale_ms
+                ));
+            }
+        }
+
+        if let Some(attribution) = &self.footer_attribution {
+            if attribution.chars().count() > 200 || attribution.contains(['\n', '\r']) {
+                return Err(
+                    "footer_attribution must be a single line of at most 200 characters"
+                        .to_string(),
+                );
+            }
+        }
+
+        if let Some(sections) = &self.include_sections {
+            let unknown: Vec<&str> = sections
+                .iter()
+                .map(String::as_str)
+                .filter(|name| !REPORT_SECTIONS.contains(name))
+                .collect();
+            if !unknown.is_empty() {
+                return Err(format!(
+                    "unknown include_sections: {} (valid: {})",
+                    unknown.join(", "),
+                    REPORT_SECTIONS.join(", ")
+                ));
+            }
+        }
+
+        let unavailable = self.sections_missing_feature(cfg!(feature = "orderbook_analytics"));
+        if !unavailable.is_empty() {
+            tracing::warn!(
+                "include_sections {} need the orderbook_analytics feature, which this build lacks; they will only show a placeholder",
+                unavailable.join(", ")
+            );
+        }
+
+        if let (Some(preset), Some(sections)) = (self.preset, &self.include_sections) {
+            if !sections.is_empty() {
+                return Err(format!(
+                    "preset '{}' cannot be combined with include_sections",
+                    preset.name()
+                ));
+            }
+        }
+
         Ok(())
     }
 
+    /// Requested `include_sections` that need the `orderbook_analytics` feature
+    /// when `analytics_enabled` is false (empty otherwise)
+    pub fn sections_missing_feature(&self, analytics_enabled: bool) -> Vec<&str> {
+        if analytics_enabled {
+            return Vec::new();
+        }
+        self.include_sections
+            .iter()
+            .flatten()
+            .map(String::as_str)
+            .filter(|name| ANALYTICS_SECTIONS.contains(name))
+            .collect()
+    }
+
+    /// Whether the section named `section_name` is built for these options
+    ///
+    /// A preset selects its own sections; otherwise an empty or missing
+    /// `include_sections` selects every section except the opt-in
+    /// `recent_trades`, which is only built when listed explicitly.
+    pub fn includes_section(&self, section_name: &str) -> bool {
+        let listed = |list: &Vec<String>| list.iter().any(|s| s == section_name);
+        if section_name == "recent_trades" {
+            return self.include_sections.as_ref().is_some_and(listed);
+        }
+        if let Some(sections) = self.preset.and_then(|preset| preset.sections()) {
+            return sections.contains(&section_name);
+        }
+        match &self.include_sections {
+            None => true,
+            Some(list) if list.is_empty() => true,
+            Some(list) => listed(list),
+        }
+    }
+
+    /// Sections built for these options, in report order (header excluded)
+    pub fn planned_sections(&self) -> Vec<&'static str> {
+        REPORT_SECTIONS
+            .iter()
+            .copied()
+            .filter(|name| self.includes_section(name))
+            .collect()
+    }
+
     /// Generates a deterministic cache key suffix from the report options.
     ///
     /// This method creates a unique string representation of the options that is used
@@ -93,12 +501,22 @@ impl ReportOptions {
     /// ensuring cached reports are isolated by their configuration.
     ///
     /// # Cache Key Format
-    /// `"sections:{sections};volume:{hours};levels:{levels}"`
+    /// `"sections:{sections};preset:{preset};volume:{hours};levels:{levels};timeout:{ms};walls:{multiplier};max_walls:{count};trades:{trades};emoji:{emoji};freshness:{fresh}/{recent}/{stale};notional:{notional};build_info:{build_info};footer:{attribution}"`
     ///
     /// Where:
     /// - `sections`: Sorted comma-separated list of section names, or "all"
+    /// - `preset`: Preset name, or "none"
     /// - `hours`: Volume window in hours (default: 24)
     /// - `levels`: Order book depth levels (default: 20)
+    /// - `ms`: Analytics timeout in milliseconds (default: 3000)
+    /// - `multiplier`: Wall threshold multiple of median level size (default: 2)
+    /// - `count`: Walls listed per side (default: 5)
+    /// - `trades`: Trades listed in the recent trades section (default: 20)
+    /// - `emoji`: Whether emoji indicators are rendered (default: true)
+    /// - `fresh`/`recent`/`stale`: Freshness thresholds in ms (default: 1000/5000/30000)
+    /// - `notional`: Whether sizes show their quote notional (default: true)
+    /// - `build_info`: Whether the footer lists the build configuration (default: true)
+    /// - `attribution`: Custom footer attribution (quoted), or "default"
     ///
     /// # Example
     /// ```
@@ -106,13 +524,35 @@ impl ReportOptions {
     ///
     /// let options = ReportOptions {
     ///     include_sections: Some(vec!["price_overview".to_string(), "liquidity_analysis".to_string()]),
+    ///     preset: None,
     ///     volume_window_hours: Some(48),
     ///     orderbook_levels: Some(50),
+    ///     analytics_timeout_ms: Some(2500),
+    ///     max_total_ms: None,
+    ///     wall_multiplier: Some(5.0),
+    ///     max_walls_per_side: Some(10),
+    ///     recent_trades_count: Some(50),
+    ///     no_cache: false,
+    ///     emoji: false,
+    ///     freshness_thresholds: None,
+    ///     footer_attribution: Some("Acme Research".to_string()),
+    ///     show_build_info: false,
+    ///     show_notional: false,
     /// };
     /// let suffix = options.to_cache_key_suffix();
     /// assert!(suffix.contains("sections:liquidity_analysis,price_overview"));
+    /// assert!(suffix.contains("preset:none"));
     /// assert!(suffix.contains("volume:48"));
     /// assert!(suffix.contains("levels:50"));
+    /// assert!(suffix.contains("timeout:2500"));
+    /// assert!(suffix.contains("walls:5"));
+    /// assert!(suffix.contains("max_walls:10"));
+    /// assert!(suffix.contains("trades:50"));
+    /// assert!(suffix.contains("emoji:false"));
+    /// assert!(suffix.contains("freshness:1000/5000/30000"));
+    /// assert!(suffix.contains("notional:false"));
+    /// assert!(suffix.contains("build_info:false"));
+    /// assert!(suffix.ends_with("footer:\"Acme Research\""));
     /// ```
     ///
     /// # Implementation Note
@@ -129,27 +569,61 @@ impl ReportOptions {
             }
         };
 
+        let preset = self.preset.map_or("none", |preset| preset.name());
+
         // Use default values if None
         let volume_hours = self.volume_window_hours.unwrap_or(24);
         let ob_levels = self.orderbook_levels.unwrap_or(20);
+        let timeout_ms = self.analytics_timeout_ms.unwrap_or(DEFAULT_ANALYTICS_TIMEOUT_MS);
+        let wall_multiplier = self.wall_multiplier.unwrap_or(2.0);
+        let max_walls = self.max_walls_per_side.unwrap_or(5);
+        let recent_trades = self.recent_trades_count.unwrap_or(20);
+        let freshness = self.freshness();
+        // Quoted so a custom attribution of "default" gets its own key
+        let footer = self
+            .footer_attribution
+            .as_ref()
+            .map_or("default".to_string(), |attribution| {
+                format!("{:?}", attribution)
+            });
 
         // Create deterministic cache key suffix
         format!(
-            "sections:{};volume:{};levels:{}",
-            sections_key, volume_hours, ob_levels
+            "sections:{};preset:{};volume:{};levels:{};timeout:{};walls:{};max_walls:{};trades:{};emoji:{};freshness:{}/{}/{};notional:{};build_info:{};footer:{}",
+            sections_key,
+            preset,
+            volume_hours,
+            ob_levels,
+            timeout_ms,
+            wall_multiplier,
+            max_walls,
+            recent_trades,
+            self.emoji,
+            freshness.fresh_ms,
+            freshness.recent_ms,
+            freshness.stale_ms,
+            self.show_notional,
+            self.show_build_info,
+            footer
         )
     }
 
+    /// Freshness thresholds to apply (the defaults when unset)
+    pub fn freshness(&self) -> FreshnessThresholds {
+        self.freshness_thresholds.unwrap_or_default()
+    }
+
     /// Generates a complete cache key by combining symbol and options.
     ///
     /// This is a convenience method that combines the symbol with the options suffix
     /// to create a fully-qualified cache key.
     ///
     /// # Arguments
-    /// * `symbol` - The trading pair symbol (e.g., "BTCUSDT")
+    /// * `symbol` - The trading pair symbol (e.g., "BTCUSDT"), in any case
     ///
     /// # Returns
-    /// A cache key in the format: `"{SYMBOL}:{options_suffix}"`
+    /// A cache key in the format: `"{SYMBOL}:{options_suffix}"`, with the symbol
+    /// upper-cased so [`ReportCache::invalidate`] matches it
     ///
     /// # Example
     /// ```
@@ -160,12 +634,12 @@ impl ReportOptions {
     /// assert!(key.starts_with("BTCUSDT:"));
     /// ```
     pub fn to_cache_key(&self, symbol: &str) -> String {
-        format!("{}:{}", symbol, self.to_cache_key_suffix())
+        format!("{}:{}", symbol.to_uppercase(), self.to_cache_key_suffix())
     }
 }
 
 /// The complete generated market intelligence report
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct MarketReport {
     /// The complete markdown-formatted report
     pub markdown_content: String,
@@ -176,6 +650,9 @@ pub struct MarketReport {
     /// Unix timestamp (milliseconds) when report was generated
     pub generated_at: i64,
 
+    /// `generated_at` as both RFC 3339 and epoch milliseconds
+    pub generated_at_ts: Timestamp,
+
     /// Age of the oldest data source in milliseconds
     pub data_age_ms: i32,
 
@@ -184,11 +661,17 @@ pub struct MarketReport {
 
     /// Report generation duration in milliseconds
     pub generation_time_ms: u64,
+
+    /// `max_total_ms` passed before every section finished; the unfinished
+    /// sections are listed in `failed_sections`
+    #[serde(default)]
+    pub partial: bool,
 }
 
-/// Internal representation of a report section
+/// A rendered report section (also the unit yielded by
+/// [`ReportGenerator::generate_report_stream`])
 #[derive(Debug, Clone)]
-pub(crate) struct ReportSection {
+pub struct ReportSection {
     pub name: String,
     pub title: String,
     pub content: Result<String, SectionError>,
@@ -203,6 +686,17 @@ impl ReportSection {
         }
     }
 
+    /// This section with emoji indicators rewritten as plain text
+    /// (see [`formatter::plain_text_indicators`])
+    pub fn without_emoji(self) -> Self {
+        Self {
+            content: self
+                .content
+                .map(|markdown| formatter::plain_text_indicators(&markdown)),
+            ..self
+        }
+    }
+
     fn render_error(&self, err: &SectionError) -> String {
         format!(
             "## {}\n\n**[Data Unavailable]**\n\n{}\n\n",
@@ -214,7 +708,7 @@ impl ReportSection {
 
 /// Errors that can occur when building a section
 #[derive(Debug, Clone)]
-pub(crate) enum SectionError {
+pub enum SectionError {
     DataSourceUnavailable(String),
     RateLimitExceeded,
     FeatureNotEnabled(String),
@@ -250,6 +744,8 @@ impl SectionError {
 pub struct ReportCache {
     cache: Mutex<HashMap<String, (MarketReport, Instant)>>,
     ttl: Duration,
+    hits: AtomicU64,
+    misses: AtomicU64,
 }
 
 impl ReportCache {
@@ -268,6 +764,8 @@ impl ReportCache {
         Self {
             cache: Mutex::new(HashMap::new()),
             ttl: Duration::from_secs(ttl_secs),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
         }
     }
 
@@ -295,16 +793,29 @@ impl ReportCache {
     /// }
     /// ```
     pub fn get(&self, symbol: &str) -> Option<MarketReport> {
+        let key = normalize_cache_key(symbol);
         let mut cache = self.cache.lock().unwrap();
-        if let Some((report, timestamp)) = cache.get(symbol) {
+        if let Some((report, timestamp)) = cache.get(&key) {
             if timestamp.elapsed() < self.ttl {
+                self.hits.fetch_add(1, Ordering::Relaxed);
                 return Some(report.clone());
             }
-            cache.remove(symbol);
+            cache.remove(&key);
         }
+        self.misses.fetch_add(1, Ordering::Relaxed);
         None
     }
 
+    /// Returns cumulative `(hits, misses)` counts for [`ReportCache::get`].
+    ///
+    /// Expired entries count as misses.
+    pub fn stats(&self) -> (u64, u64) {
+        (
+            self.hits.load(Ordering::Relaxed),
+            self.misses.load(Ordering::Relaxed),
+        )
+    }
+
     /// Stores a report in the cache with the current timestamp.
     ///
     /// If an entry with the same key already exists, it will be replaced.
@@ -319,21 +830,24 @@ impl ReportCache {
     /// # Example
     /// ```
     /// use binance_provider::report::{ReportCache, MarketReport};
+    /// use binance_provider::timestamp::Timestamp;
     ///
     /// let cache = ReportCache::new(60);
     /// let report = MarketReport {
     ///     markdown_content: "# Report".to_string(),
     ///     symbol: "BTCUSDT".to_string(),
     ///     generated_at: 1729780000000,
+    ///     generated_at_ts: Timestamp::from_millis(1729780000000),
     ///     data_age_ms: 100,
     ///     failed_sections: vec![],
     ///     generation_time_ms: 245,
+    ///     partial: false,
     /// };
     /// cache.set("BTCUSDT:sections:all;volume:24;levels:20".to_string(), report);
     /// ```
     pub fn set(&self, symbol: String, report: MarketReport) {
         let mut cache = self.cache.lock().unwrap();
-        cache.insert(symbol, (report, Instant::now()));
+        cache.insert(normalize_cache_key(&symbol), (report, Instant::now()));
     }
 
     /// Invalidates all cached reports for a symbol across all option combinations.
@@ -361,3 +875,15 @@ impl ReportCache {
         cache.retain(|key, _| !key.starts_with(&symbol_prefix));
     }
 }
+
+/// Upper-case the symbol part of a cache key (everything before the first `:`)
+///
+/// Keys stored by [`ReportCache::set`] and looked up by [`ReportCache::get`] go
+/// through this, so a report cached as `btcusdt:...` is still found and cleared
+/// by [`ReportCache::invalidate`]`("BTCUSDT")`. The options suffix is left as is.
+fn normalize_cache_key(key: &str) -> String {
+    match key.split_once(':') {
+        Some((symbol, suffix)) => format!("{}:{}", symbol.to_uppercase(), suffix),
+        None => key.to_uppercase(),
+    }
+}
@@ -3,12 +3,20 @@
 // Each function builds a specific section of the market intelligence report.
 // Sections return Result<String, SectionError> for graceful degradation.
 
-use super::{ReportSection, SectionError};
+use super::{FreshnessThresholds, ReportSection, SectionError};
+
+/// Decimals added to the tick precision for values between ticks (mid price, microprice)
+pub const SUB_TICK_DECIMALS: usize = 3;
 
 /// Build report header section with metadata
 ///
 /// Includes: Symbol, generation timestamp, data age indicator
-pub fn build_report_header(symbol: &str, generated_at: i64, data_age_ms: i32) -> ReportSection {
+pub fn build_report_header(
+    symbol: &str,
+    generated_at: i64,
+    data_age_ms: i32,
+    thresholds: &FreshnessThresholds,
+) -> ReportSection {
     use super::formatter;
 
     let mut content = String::new();
@@ -20,13 +28,7 @@ pub fn build_report_header(symbol: &str, generated_at: i64, data_age_ms: i32) ->
     ));
 
     // Metadata table
-    let freshness_indicator = if data_age_ms < 1000 {
-        "🟢 Fresh"
-    } else if data_age_ms < 5000 {
-        "🟡 Recent"
-    } else {
-        "🔴 Stale"
-    };
+    let freshness_indicator = formatter::freshness_label(data_age_ms, thresholds);
 
     let headers = vec!["Metric", "Value"];
     let rows = vec![
@@ -55,8 +57,26 @@ pub fn build_report_header(symbol: &str, generated_at: i64, data_age_ms: i32) ->
 /// Build price overview section
 ///
 /// Includes: Current price, 24h change, 24h high/low, volume
+/// Prices are shown with `price_decimals` decimals.
 pub fn build_price_overview_section(
     ticker: Option<&crate::binance::types::Ticker24hr>,
+    price_decimals: usize,
+) -> ReportSection {
+    build_price_overview_section_with_vwap(ticker, None, &[], price_decimals)
+}
+
+/// Build price overview section with extra VWAP and windowed change rows
+///
+/// # Arguments
+/// * `ticker` - 24hr ticker statistics
+/// * `vwap` - VWAP from stored trades and its window in seconds (row omitted when None)
+/// * `price_changes` - Changes over other windows, one row each before the 24h change
+/// * `price_decimals` - Decimals for displayed prices
+pub fn build_price_overview_section_with_vwap(
+    ticker: Option<&crate::binance::types::Ticker24hr>,
+    vwap: Option<(rust_decimal::Decimal, u32)>,
+    price_changes: &[crate::price_change::PriceChange],
+    price_decimals: usize,
 ) -> ReportSection {
     use super::formatter;
 
@@ -66,46 +86,64 @@ pub fn build_price_overview_section(
 
             // Parse values
             let price_change_pct: f64 = t.price_change_percent.parse().unwrap_or(0.0);
-            let trend_indicator = if price_change_pct > 0.0 {
-                "📈"
-            } else if price_change_pct < 0.0 {
-                "📉"
-            } else {
-                "➡️"
-            };
 
             // Format LTP timestamp
             let ltp_time = chrono::DateTime::from_timestamp_millis(t.close_time)
                 .map(|dt| dt.format("%Y-%m-%d %H:%M:%S UTC").to_string())
                 .unwrap_or_else(|| "Unknown".to_string());
 
-            // Build price table (format prices with 2 decimals and thousand separators)
+            // Label prices with the quote asset and sizes with the base asset
+            let (base, quote) = formatter::split_symbol(&t.symbol);
+            let decimals = price_decimals;
+
             let headers = vec!["Metric", "Value"];
-            let rows = vec![
-                vec!["Last Trade Price (LTP)".to_string(), format!("${}", formatter::format_price(&t.last_price, 2))],
+            let mut rows = vec![
+                vec!["Last Trade Price (LTP)".to_string(), formatter::format_quote_price(&t.last_price, quote, decimals)],
                 vec!["LTP Time".to_string(), ltp_time],
+            ];
+            for change in price_changes {
+                rows.push(vec![
+                    format!("{} Change", change.window),
+                    format!(
+                        "{} {:+.2}%",
+                        trend_indicator(change.price_change_percent),
+                        change.price_change_percent
+                    ),
+                ]);
+            }
+            rows.extend([
                 vec![
                     "24h Change".to_string(),
-                    format!("{} {}%", trend_indicator, t.price_change_percent),
+                    format!("{} {}%", trend_indicator(price_change_pct), t.price_change_percent),
                 ],
-                vec!["24h High".to_string(), format!("${}", formatter::format_price(&t.high_price, 2))],
-                vec!["24h Low".to_string(), format!("${}", formatter::format_price(&t.low_price, 2))],
+                vec!["24h High".to_string(), formatter::format_quote_price(&t.high_price, quote, decimals)],
+                vec!["24h Low".to_string(), formatter::format_quote_price(&t.low_price, quote, decimals)],
                 vec![
                     "24h Volume".to_string(),
-                    format!("{} {}", t.volume, t.symbol.trim_end_matches("USDT")),
+                    format!("{} {}", t.volume, base),
                 ],
                 vec![
                     "24h Quote Volume".to_string(),
                     {
                         let quote_vol: f64 = t.quote_volume.parse().unwrap_or(0.0);
-                        formatter::format_large_usd(quote_vol)
+                        if formatter::is_usd_quote(quote) {
+                            formatter::format_large_usd(quote_vol)
+                        } else {
+                            formatter::format_quote_price_f64(quote_vol, quote, 4)
+                        }
                     },
                 ],
                 vec![
                     "Weighted Avg Price".to_string(),
-                    format!("${}", formatter::format_price(&t.weighted_avg_price, 2)),
+                    formatter::format_quote_price(&t.weighted_avg_price, quote, decimals),
                 ],
-            ];
+            ]);
+            if let Some((vwap, window_secs)) = vwap {
+                rows.push(vec![
+                    format!("VWAP ({})", format_window(window_secs)),
+                    formatter::format_quote_price(&vwap.to_string(), quote, decimals),
+                ]);
+            }
 
             section.push_str(&formatter::build_table(&headers, &rows));
             section.push('\n');
@@ -123,11 +161,39 @@ pub fn build_price_overview_section(
     }
 }
 
+/// Direction emoji for a percentage change
+fn trend_indicator(change_pct: f64) -> &'static str {
+    if change_pct > 0.0 {
+        "📈"
+    } else if change_pct < 0.0 {
+        "📉"
+    } else {
+        "➡️"
+    }
+}
+
+/// Compact window label: "1h", "15m" or "90s"
+fn format_window(window_secs: u32) -> String {
+    if window_secs % 3600 == 0 {
+        format!("{}h", window_secs / 3600)
+    } else if window_secs % 60 == 0 {
+        format!("{}m", window_secs / 60)
+    } else {
+        format!("{}s", window_secs)
+    }
+}
+
 /// Build order book metrics section
 ///
-/// Includes: Spread (bps), microprice, bid/ask volume, imbalance ratio
+/// Includes: Spread (bps), microprice, bid/ask volume, imbalance ratio and
+/// slippage estimates
+/// Prices use `price_decimals`; mid price and microprice fall between ticks
+/// and get [`SUB_TICK_DECIMALS`] more. With `show_notional`, slippage fill
+/// sizes are followed by their quote notional at the mid price.
 pub fn build_orderbook_metrics_section(
     metrics: Option<&crate::orderbook::types::OrderBookMetrics>,
+    price_decimals: usize,
+    show_notional: bool,
 ) -> ReportSection {
     use super::formatter;
 
@@ -138,10 +204,14 @@ pub fn build_orderbook_metrics_section(
             // BLOCKER FIX: Detect crossed orderbook (ask < bid)
             let is_crossed = m.spread_bps < 0.0;
 
-            // Calculate spread in $ and m-bps (milli-basis points)
+            let (base, quote) = formatter::split_symbol(&m.symbol);
+            let decimals = price_decimals;
+
+            // Calculate spread in quote units and m-bps (milli-basis points)
             let best_bid_f64: f64 = m.best_bid.parse().unwrap_or(0.0);
             let best_ask_f64: f64 = m.best_ask.parse().unwrap_or(0.0);
-            let spread_usd = best_ask_f64 - best_bid_f64;
+            let spread_quote = best_ask_f64 - best_bid_f64;
+            let spread_label = formatter::format_quote_price_f64(spread_quote, quote, decimals);
             let spread_mbps = m.spread_bps * 1000.0; // Convert bps to m-bps
 
             // Spread formatting with crossed detection
@@ -149,12 +219,12 @@ pub fn build_orderbook_metrics_section(
                 // Crossed orderbook - show warning and actual spread with 4 decimals
                 format!("{:.4} bps ⚠️ Crossed", m.spread_bps)
             } else if m.spread_bps < 10.0 {
-                // Tight spread - show in m-bps and $ for microstructure analysis
-                format!("{:.2} m-bps (${:.2}) 🟢 Tight", spread_mbps, spread_usd)
+                // Tight spread - show in m-bps and quote units for microstructure analysis
+                format!("{:.2} m-bps ({}) 🟢 Tight", spread_mbps, spread_label)
             } else if m.spread_bps < 50.0 {
-                format!("{:.4} bps (${:.2}) 🟡 Moderate", m.spread_bps, spread_usd)
+                format!("{:.4} bps ({}) 🟡 Moderate", m.spread_bps, spread_label)
             } else {
-                format!("{:.4} bps (${:.2}) 🔴 Wide", m.spread_bps, spread_usd)
+                format!("{:.4} bps ({}) 🔴 Wide", m.spread_bps, spread_label)
             };
 
             // Imbalance indicator
@@ -166,28 +236,28 @@ pub fn build_orderbook_metrics_section(
                 "🟡 Balanced"
             };
 
-            // Build metrics table (format prices with 2 decimals and thousand separators for BTCUSDT)
+            // Build metrics table (prices in quote asset, sizes in base asset)
             let headers = vec!["Metric", "Value"];
             let rows = vec![
-                vec!["Best Bid".to_string(), format!("${}", formatter::format_price(&m.best_bid, 2))],
-                vec!["Best Bid Size".to_string(), format!("{:.4} BTC", m.best_bid_size)],
-                vec!["Best Ask".to_string(), format!("${}", formatter::format_price(&m.best_ask, 2))],
-                vec!["Best Ask Size".to_string(), format!("{:.4} BTC", m.best_ask_size)],
+                vec!["Best Bid".to_string(), formatter::format_quote_price(&m.best_bid, quote, decimals)],
+                vec!["Best Bid Size".to_string(), formatter::format_base_qty(m.best_bid_size, base)],
+                vec!["Best Ask".to_string(), formatter::format_quote_price(&m.best_ask, quote, decimals)],
+                vec!["Best Ask Size".to_string(), formatter::format_base_qty(m.best_ask_size, base)],
                 vec![
                     "Spread".to_string(),
                     spread_formatted,
                 ],
-                // Show Mid Price with 5 decimals to match microprice precision and prove spread basis
-                vec!["Mid Price".to_string(), format!("${}", formatter::format_price_f64(m.mid_price, 5))],
-                // P0 Fix: Increase microprice precision to 5 decimals to avoid rounding artifacts
-                vec!["Microprice".to_string(), format!("${:.5}", m.microprice)],
+                // Show Mid Price at microprice precision to prove spread basis
+                vec!["Mid Price".to_string(), formatter::format_quote_price_f64(m.mid_price, quote, decimals + SUB_TICK_DECIMALS)],
+                // P0 Fix: Sub-tick microprice precision avoids rounding artifacts
+                vec!["Microprice".to_string(), formatter::format_quote_price_f64(m.microprice, quote, decimals + SUB_TICK_DECIMALS)],
                 vec![
-                    "Bid Volume (Top 20)".to_string(),
-                    format!("{:.4} BTC", m.bid_volume),
+                    format!("Bid Volume (Top {})", m.depth_levels),
+                    formatter::format_base_qty(m.bid_volume, base),
                 ],
                 vec![
-                    "Ask Volume (Top 20)".to_string(),
-                    format!("{:.4} BTC", m.ask_volume),
+                    format!("Ask Volume (Top {})", m.depth_levels),
+                    formatter::format_base_qty(m.ask_volume, base),
                 ],
                 vec![
                     "Imbalance Ratio".to_string(),
@@ -198,6 +268,47 @@ pub fn build_orderbook_metrics_section(
             section.push_str(&formatter::build_table(&headers, &rows));
             section.push('\n');
 
+            // Slippage for standard order sizes, walking the book from the touch
+            let estimates = &m.slippage_estimates;
+            let slippage_rows: Vec<Vec<String>> = [
+                ("Buy", &estimates.buy_10k_usd),
+                ("Buy", &estimates.buy_25k_usd),
+                ("Buy", &estimates.buy_50k_usd),
+                ("Sell", &estimates.sell_10k_usd),
+                ("Sell", &estimates.sell_25k_usd),
+                ("Sell", &estimates.sell_50k_usd),
+            ]
+            .into_iter()
+            .filter_map(|(side, estimate)| {
+                let estimate = estimate.as_ref()?;
+                Some(vec![
+                    format!(
+                        "{} {}",
+                        side,
+                        formatter::format_quote_price_f64(estimate.target_usd, quote, 0)
+                    ),
+                    formatter::format_quote_price_f64(
+                        estimate.avg_price,
+                        quote,
+                        decimals + SUB_TICK_DECIMALS,
+                    ),
+                    format!("{:.2} bps", estimate.slippage_bps),
+                    formatter::format_size_with_notional(
+                        &format!("{:.4}", estimate.filled_qty),
+                        base,
+                        quote,
+                        show_notional.then_some(m.mid_price),
+                    ),
+                ])
+            })
+            .collect();
+            if !slippage_rows.is_empty() {
+                section.push_str("### Slippage Estimates\n\n");
+                let headers = vec!["Order", "Avg Fill Price", "Slippage", "Filled"];
+                section.push_str(&formatter::build_table(&headers, &slippage_rows));
+                section.push('\n');
+            }
+
             // Add verification notice
             section.push_str("### Verification Against Binance API\n\n");
 
@@ -211,14 +322,14 @@ pub fn build_orderbook_metrics_section(
                  |--------|-------|--------|\n\
                  | Last Update ID | {} | ✅ Tracked |\n\
                  | Event Time | {} | ✅ Timestamped |\n\
-                 | Best Bid | ${} | ✅ Live |\n\
-                 | Best Ask | ${} | ✅ Live |\n\
-                 | Spread | {:.2} m-bps (${:.2}) | ✅ Valid |\n\
+                 | Best Bid | {} | ✅ Live |\n\
+                 | Best Ask | {} | ✅ Live |\n\
+                 | Spread | {:.2} m-bps ({}) | ✅ Valid |\n\
                  | Data Age | Fresh (<500ms) | ✅ Real-time |\n\n",
                 m.last_update_id, event_time,
-                formatter::format_price(&m.best_bid, 2),
-                formatter::format_price(&m.best_ask, 2),
-                spread_mbps, spread_usd
+                formatter::format_quote_price(&m.best_bid, quote, decimals),
+                formatter::format_quote_price(&m.best_ask, quote, decimals),
+                spread_mbps, spread_label
             ));
             section.push_str("*OrderBook data sourced directly from Binance WebSocket depth streams with REST API fallback. Update ID ensures snapshot consistency.*\n\n");
 
@@ -239,46 +350,49 @@ pub fn build_orderbook_metrics_section(
 ///
 /// Includes: Major walls, volume profile (POC/VAH/VAL), liquidity vacuums
 /// Enhanced with better formatting, visual indicators, and volume profile (T033-T037)
+/// Walls are listed largest first, at most `max_walls` per side, with prices
+/// shown at `price_decimals` and, when `show_notional` is set, sizes followed by
+/// their quote notional at the mid price.
 pub fn build_liquidity_analysis_section(
     metrics: Option<&crate::orderbook::types::OrderBookMetrics>,
     volume_window_hours: u32,
+    wall_multiplier: f64,
+    max_walls: usize,
+    price_decimals: usize,
+    show_notional: bool,
 ) -> ReportSection {
     use super::formatter;
 
     let content = match metrics {
         Some(m) => {
             let mut section = formatter::build_section_header("Liquidity Analysis", 2);
+            let (base, quote) = formatter::split_symbol(&m.symbol);
+            let decimals = price_decimals;
+            let notional_mid = show_notional.then_some(m.mid_price);
 
             // T033: Enhanced walls table with better formatting and visual indicators
             section.push_str("### Liquidity Walls\n\n");
 
             if !m.walls.bids.is_empty() || !m.walls.asks.is_empty() {
-                // T037: Visual indicators for wall strength
-                let get_wall_strength = |qty: f64| -> &'static str {
-                    if qty > 100.0 {
-                        "💪 Strong"
-                    } else if qty > 50.0 {
-                        "🔷 Moderate"
-                    } else {
-                        "🔹 Weak"
-                    }
-                };
 
                 // Buy walls table
                 if !m.walls.bids.is_empty() {
                     section.push_str("**Buy Walls (Support Levels):**\n\n");
                     let headers = vec!["Price", "Volume", "Strength", "Type"];
-                    let rows: Vec<Vec<String>> = m
-                        .walls
-                        .bids
-                        .iter()
-                        .take(5)
+                    let rows: Vec<Vec<String>> = largest_walls(&m.walls.bids, max_walls)
+                        .into_iter()
                         .map(|w| {
-                            let qty: f64 = w.qty.parse().unwrap_or(0.0);
+                            // T037: Visual indicators for wall strength (relative to median)
                             vec![
-                                format!("${}", w.price),
-                                format!("{:.4} units", w.qty),
-                                get_wall_strength(qty).to_string(),
+                                formatter::format_quote_price(&w.price, quote, decimals),
+                                formatter::format_size_with_notional(
+                                    &w.qty,
+                                    base,
+                                    quote,
+                                    notional_mid,
+                                ),
+                                formatter::wall_strength(w.median_multiple, wall_multiplier)
+                                    .to_string(),
                                 "🟢 Support".to_string(),
                             ]
                         })
@@ -291,17 +405,20 @@ pub fn build_liquidity_analysis_section(
                 if !m.walls.asks.is_empty() {
                     section.push_str("**Sell Walls (Resistance Levels):**\n\n");
                     let headers = vec!["Price", "Volume", "Strength", "Type"];
-                    let rows: Vec<Vec<String>> = m
-                        .walls
-                        .asks
-                        .iter()
-                        .take(5)
+                    let rows: Vec<Vec<String>> = largest_walls(&m.walls.asks, max_walls)
+                        .into_iter()
                         .map(|w| {
-                            let qty: f64 = w.qty.parse().unwrap_or(0.0);
+                            // T037: Visual indicators for wall strength (relative to median)
                             vec![
-                                format!("${}", w.price),
-                                format!("{:.4} units", w.qty),
-                                get_wall_strength(qty).to_string(),
+                                formatter::format_quote_price(&w.price, quote, decimals),
+                                formatter::format_size_with_notional(
+                                    &w.qty,
+                                    base,
+                                    quote,
+                                    notional_mid,
+                                ),
+                                formatter::wall_strength(w.median_multiple, wall_multiplier)
+                                    .to_string(),
                                 "🔴 Resistance".to_string(),
                             ]
                         })
@@ -310,8 +427,10 @@ pub fn build_liquidity_analysis_section(
                     section.push('\n');
                 }
             } else {
-                section
-                    .push_str("*No significant liquidity walls detected (volume < 2x median)*\n\n");
+                section.push_str(&format!(
+                    "*No significant liquidity walls detected (volume < {}x median)*\n\n",
+                    wall_multiplier
+                ));
             }
 
             // T034: Volume profile visualization with POC/VAH/VAL (placeholder for future implementation)
@@ -371,6 +490,18 @@ pub fn build_liquidity_analysis_section(
     }
 }
 
+/// Up to `max_walls` walls of one side, largest quantity first
+fn largest_walls(
+    walls: &[crate::orderbook::types::Wall],
+    max_walls: usize,
+) -> Vec<&crate::orderbook::types::Wall> {
+    let qty = |wall: &crate::orderbook::types::Wall| wall.qty.parse::<f64>().unwrap_or(0.0);
+    let mut sorted: Vec<_> = walls.iter().collect();
+    sorted.sort_by(|a, b| qty(b).total_cmp(&qty(a)));
+    sorted.truncate(max_walls);
+    sorted
+}
+
 /// Build liquidity analysis section with full analytics (Feature 019 - US1)
 ///
 /// Provides comprehensive liquidity analysis:
@@ -384,6 +515,13 @@ pub fn build_liquidity_analysis_section(
 /// * `symbol` - Trading pair symbol
 /// * `volume_window_hours` - Time window for volume profile (1-168 hours)
 /// * `generated_at` - Report generation timestamp
+/// * `orderbook_metrics` - Live L1 metrics (source of liquidity walls)
+/// * `wall_multiplier` - Wall threshold used for `orderbook_metrics`, for strength bands
+/// * `max_walls` - Walls listed per side, largest first
+/// * `price_decimals` - Decimals for displayed prices
+/// * `show_notional` - Follow wall sizes with their quote notional at the mid price
+/// * `analytics_timeout_ms` - Per-call analytics timeout (from `ReportOptions`)
+/// * `analytics_permits` - Generator-wide limit on concurrent analytics queries
 ///
 /// # Returns
 /// ReportSection with volume profile, walls, and vacuums
@@ -395,6 +533,12 @@ pub async fn build_liquidity_analysis_section_async(
     volume_window_hours: u32,
     generated_at: chrono::DateTime<chrono::Utc>,
     orderbook_metrics: Option<&crate::orderbook::types::OrderBookMetrics>, // CROSSED FIX: Use live metrics for walls
+    wall_multiplier: f64,
+    max_walls: usize,
+    price_decimals: usize,
+    show_notional: bool,
+    analytics_timeout_ms: u64,
+    analytics_permits: &std::sync::Arc<tokio::sync::Semaphore>,
 ) -> ReportSection {
     use super::{formatter, util};
     use crate::orderbook::analytics::tools::{
@@ -427,7 +571,7 @@ pub async fn build_liquidity_analysis_section_async(
         async {
             // Spawn blocking to avoid blocking Tokio runtime on RocksDB I/O
             let query_start = std::time::Instant::now();
-            let trades_result = tokio::task::spawn_blocking(move || {
+            let trades_result = util::spawn_blocking_limited(analytics_permits, move || {
                 trade_storage_clone.query_trades(&symbol_for_trades, start_time_ms, end_time_ms)
             })
             .await;
@@ -472,10 +616,16 @@ pub async fn build_liquidity_analysis_section_async(
                                 symbol: symbol.to_string(),
                                 duration_hours: actual_window_hours, // Use reduced window
                                 tick_size: None,
+                                value_area_pct: None,
+                                max_bins: None,
+                                poc_basis: Default::default(),
+                                // Thin markets still get a profile, flagged low_confidence
+                                min_trades: Some(crate::orderbook::analytics::profile::MIN_TRADES_FLOOR),
                             },
                         ),
                         "get_volume_profile",
                         symbol,
+                        analytics_timeout_ms,
                     )
                     .await;
 
@@ -493,22 +643,26 @@ pub async fn build_liquidity_analysis_section_async(
         },
         // Liquidity vacuums
         util::timeout_analytics(
-            get_liquidity_vacuums(
-                storage.clone(),
-                GetLiquidityVacuumsParams {
-                    symbol: symbol.to_string(),
-                    duration_hours: (volume_window_hours as u32).min(24),
-                },
+            util::limit_analytics(
+                analytics_permits,
+                get_liquidity_vacuums(
+                    storage.clone(),
+                    GetLiquidityVacuumsParams {
+                        symbol: symbol.to_string(),
+                        duration_hours: (volume_window_hours as u32).min(24),
+                    },
+                ),
             ),
             "get_liquidity_vacuums",
             symbol,
+            analytics_timeout_ms,
         ),
         // CROSSED FIX: Use live orderbook_metrics.walls instead of historical snapshot
         async {
             match orderbook_metrics {
                 Some(metrics) => {
-                    // Convert Wall structs to tuple format (price, qty, side)
-                    let mut walls: Vec<(rust_decimal::Decimal, rust_decimal::Decimal, String)> = Vec::new();
+                    // Convert Wall structs to tuple format (price, qty, side, median multiple)
+                    let mut walls: Vec<(rust_decimal::Decimal, rust_decimal::Decimal, String, f64)> = Vec::new();
 
                     // Add bid walls
                     for wall in &metrics.walls.bids {
@@ -516,7 +670,7 @@ pub async fn build_liquidity_analysis_section_async(
                             wall.price.parse::<rust_decimal::Decimal>(),
                             wall.qty.parse::<rust_decimal::Decimal>()
                         ) {
-                            walls.push((price, qty, "bid".to_string()));
+                            walls.push((price, qty, "bid".to_string(), wall.median_multiple));
                         }
                     }
 
@@ -526,7 +680,7 @@ pub async fn build_liquidity_analysis_section_async(
                             wall.price.parse::<rust_decimal::Decimal>(),
                             wall.qty.parse::<rust_decimal::Decimal>()
                         ) {
-                            walls.push((price, qty, "ask".to_string()));
+                            walls.push((price, qty, "ask".to_string(), wall.median_multiple));
                         }
                     }
 
@@ -539,6 +693,12 @@ pub async fn build_liquidity_analysis_section_async(
         },
     );
 
+    let (base, quote) = formatter::split_symbol(symbol);
+    let decimals = price_decimals;
+    let notional_mid = orderbook_metrics
+        .filter(|_| show_notional)
+        .map(|metrics| metrics.mid_price);
+
     // BLOCKER FIX: Use actual window instead of requested to match reality
     content.push_str(&format!("### Volume Profile (last {}h)\n\n", actual_window_hours));
 
@@ -548,31 +708,40 @@ pub async fn build_liquidity_analysis_section_async(
             let rows = vec![
                 vec![
                     "POC".to_string(),
-                    format!("${}", formatter::format_price(&profile.point_of_control.to_string(), 2)),
+                    formatter::format_quote_price(&profile.point_of_control.to_string(), quote, decimals),
                     "Point of Control (highest volume)".to_string(),
                 ],
                 vec![
                     "VAH".to_string(),
-                    format!("${}", formatter::format_price(&profile.value_area_high.to_string(), 2)),
+                    formatter::format_quote_price(&profile.value_area_high.to_string(), quote, decimals),
                     "Value Area High (top of 70% volume)".to_string(),
                 ],
                 vec![
                     "VAL".to_string(),
-                    format!("${}", formatter::format_price(&profile.value_area_low.to_string(), 2)),
+                    formatter::format_quote_price(&profile.value_area_low.to_string(), quote, decimals),
                     "Value Area Low (bottom of 70% volume)".to_string(),
                 ],
                 vec![
                     "Total Volume".to_string(),
-                    format!("{}", profile.total_volume),
+                    format!("{} {}", profile.total_volume, base),
                     format!("Across {} bins", profile.bin_count),
                 ],
             ];
             content.push_str(&formatter::build_table(&headers, &rows));
             content.push_str("\n");
 
+            if profile.low_confidence {
+                content.push_str(&format!(
+                    "\n⚠️ *Low confidence: built from only {} trades (1000+ recommended). \
+                    Levels may shift as more trades arrive.*\n",
+                    profile.trade_count
+                ));
+            }
+
             // P0 Fix: Add data source disclaimer for Volume Profile
             // Check if volume is suspiciously low (may indicate incomplete data)
-            let total_vol_f64: f64 = profile.total_volume.to_string().parse().unwrap_or(0.0);
+            let total_vol_f64 =
+                rust_decimal::prelude::ToPrimitive::to_f64(&profile.total_volume).unwrap_or(0.0);
 
             // For BTCUSDT, expect roughly ~420 BTC/hour in normal conditions (based on 10k BTC/24h)
             let expected_min_volume = (actual_window_hours as f64) * 420.0;
@@ -583,15 +752,15 @@ pub async fn build_liquidity_analysis_section_async(
 
             if is_sampled {
                 content.push_str(&format!(
-                    "Coverage: ~{:.1}% ({:.1} BTC of expected ~{:.0} BTC for {}h window). \
+                    "Coverage: ~{:.1}% ({:.1} {} of expected ~{:.0} {} for {}h window). \
                     Representative for distribution shape, but not absolute volumes. \
                     For full historical data, REST API backfill required.*\n\n",
-                    coverage_pct, total_vol_f64, expected_min_volume, actual_window_hours
+                    coverage_pct, total_vol_f64, base, expected_min_volume, base, actual_window_hours
                 ));
             } else {
                 content.push_str(&format!(
-                    "Coverage: ~{:.1}% ({:.1} BTC). Profile includes majority of market activity.*\n\n",
-                    coverage_pct, total_vol_f64
+                    "Coverage: ~{:.1}% ({:.1} {}). Profile includes majority of market activity.*\n\n",
+                    coverage_pct, total_vol_f64, base
                 ));
             }
         }
@@ -608,32 +777,23 @@ pub async fn build_liquidity_analysis_section_async(
 
     match walls_result {
         Ok(walls) if !walls.is_empty() => {
-            let get_wall_strength = |qty: rust_decimal::Decimal| -> &'static str {
-                let qty_f64: f64 = qty.to_string().parse().unwrap_or(0.0);
-                if qty_f64 > 100.0 {
-                    "💪 Strong"
-                } else if qty_f64 > 50.0 {
-                    "🔷 Moderate"
-                } else {
-                    "🔹 Weak"
-                }
-            };
-
             // Group walls by side
-            let mut bid_walls: Vec<_> = walls.iter().filter(|(_, _, side)| *side == "bid").collect();
-            let mut ask_walls: Vec<_> = walls.iter().filter(|(_, _, side)| *side == "ask").collect();
+            let mut bid_walls: Vec<_> = walls.iter().filter(|(_, _, side, _)| *side == "bid").collect();
+            let mut ask_walls: Vec<_> = walls.iter().filter(|(_, _, side, _)| *side == "ask").collect();
+            bid_walls.sort_by_key(|(_, qty, _, _)| std::cmp::Reverse(*qty));
+            ask_walls.sort_by_key(|(_, qty, _, _)| std::cmp::Reverse(*qty));
 
             if !bid_walls.is_empty() {
                 content.push_str("**Buy Walls (Support Levels):**\n\n");
                 let headers = vec!["Price", "Volume", "Strength", "Type"];
                 let rows: Vec<Vec<String>> = bid_walls
                     .iter()
-                    .take(5)
-                    .map(|(price, qty, _)| {
+                    .take(max_walls)
+                    .map(|(price, qty, _, median_multiple)| {
                         vec![
-                            format!("${}", formatter::format_price(&price.to_string(), 2)),
-                            format!("{}", qty),
-                            get_wall_strength(*qty).to_string(),
+                            formatter::format_quote_price(&price.to_string(), quote, decimals),
+                            formatter::format_size_with_notional(&qty.to_string(), base, quote, notional_mid),
+                            formatter::wall_strength(*median_multiple, wall_multiplier).to_string(),
                             "🟢 Support".to_string(),
                         ]
                     })
@@ -647,12 +807,12 @@ pub async fn build_liquidity_analysis_section_async(
                 let headers = vec!["Price", "Volume", "Strength", "Type"];
                 let rows: Vec<Vec<String>> = ask_walls
                     .iter()
-                    .take(5)
-                    .map(|(price, qty, _)| {
+                    .take(max_walls)
+                    .map(|(price, qty, _, median_multiple)| {
                         vec![
-                            format!("${}", formatter::format_price(&price.to_string(), 2)),
-                            format!("{}", qty),
-                            get_wall_strength(*qty).to_string(),
+                            formatter::format_quote_price(&price.to_string(), quote, decimals),
+                            formatter::format_size_with_notional(&qty.to_string(), base, quote, notional_mid),
+                            formatter::wall_strength(*median_multiple, wall_multiplier).to_string(),
                             "🔴 Resistance".to_string(),
                         ]
                     })
@@ -662,7 +822,10 @@ pub async fn build_liquidity_analysis_section_async(
             }
         }
         Ok(_) => {
-            content.push_str("*No significant liquidity walls detected*\n\n");
+            content.push_str(&format!(
+                "*No significant liquidity walls detected (volume < {}x median)*\n\n",
+                wall_multiplier
+            ));
         }
         Err(util::TimeoutError::Exceeded) => {
             content.push_str("**[Data Unavailable: timeout]**\n\n");
@@ -708,9 +871,9 @@ pub async fn build_liquidity_analysis_section_async(
                     };
 
                     vec![
-                        format!("${} - ${}",
-                            formatter::format_price(&v.price_range_low.to_string(), 2),
-                            formatter::format_price(&v.price_range_high.to_string(), 2)
+                        format!("{} - {}",
+                            formatter::format_quote_price(&v.price_range_low.to_string(), quote, decimals),
+                            formatter::format_quote_price(&v.price_range_high.to_string(), quote, decimals)
                         ),
                         format!("{:.1} bps", width_bps),
                         format!("{:.1}%", v.volume_deficit_pct),
@@ -779,11 +942,14 @@ pub fn build_microstructure_section() -> ReportSection {
 /// - Ask flow rate (orders/sec)
 /// - Net flow (bid - ask)
 /// - Flow direction indicator
+/// - Absorption events (levels absorbing flow without price movement)
 ///
 /// # Arguments
 /// * `storage` - Analytics snapshot storage for historical data
 /// * `symbol` - Trading pair symbol
 /// * `generated_at` - Report generation timestamp for data age calculation
+/// * `analytics_timeout_ms` - Per-call analytics timeout (from `ReportOptions`)
+/// * `analytics_permits` - Generator-wide limit on concurrent analytics queries
 ///
 /// # Returns
 /// ReportSection with order flow metrics and trading signals
@@ -792,21 +958,28 @@ pub async fn build_microstructure_section_async(
     storage: &std::sync::Arc<crate::orderbook::analytics::SnapshotStorage>,
     symbol: &str,
     generated_at: chrono::DateTime<chrono::Utc>,
+    analytics_timeout_ms: u64,
+    analytics_permits: &tokio::sync::Semaphore,
 ) -> ReportSection {
     use super::{formatter, util};
     use crate::orderbook::analytics::tools::{get_order_flow, GetOrderFlowParams};
 
     // T041-T042: Call order flow calculation with timeout (FR-020)
     let flow_result = util::timeout_analytics(
-        get_order_flow(
-            storage.clone(),
-            GetOrderFlowParams {
-                symbol: symbol.to_string(),
-                window_duration_secs: 60, // FR-012: 60-second window
-            },
+        util::limit_analytics(
+            analytics_permits,
+            get_order_flow(
+                storage.clone(),
+                GetOrderFlowParams {
+                    symbol: symbol.to_string(),
+                    window_duration_secs: 60, // FR-012: 60-second window
+                    delta_half_life_secs: None,
+                },
+            ),
         ),
         "get_order_flow",
         symbol,
+        analytics_timeout_ms,
     )
     .await;
 
@@ -878,6 +1051,11 @@ pub async fn build_microstructure_section_async(
                     format!("{:+.2}", flow.cumulative_delta),
                     "Running buy/sell volume difference".to_string(),
                 ],
+                vec![
+                    "Flow Score".to_string(),
+                    format!("{:+.0} / 100", flow.flow_score),
+                    "40% order count, 60% volume".to_string(),
+                ],
             ];
 
             content.push_str(&formatter::build_table(&headers, &rows));
@@ -896,6 +1074,37 @@ pub async fn build_microstructure_section_async(
                 ));
             }
 
+            // Levels absorbing flow without price movement
+            content.push_str("### Absorption\n\n");
+            if flow.absorption_events.is_empty() {
+                content.push_str("No absorption detected in this window.\n\n");
+            } else {
+                let headers = vec!["Side", "Price", "Absorbed Volume", "Hits", "Likely Entity"];
+                let rows: Vec<Vec<String>> = flow
+                    .absorption_events
+                    .iter()
+                    .map(|event| {
+                        let side = match event.direction {
+                            crate::orderbook::analytics::types::Direction::Accumulation => {
+                                "🟢 Bid (accumulation)"
+                            }
+                            crate::orderbook::analytics::types::Direction::Distribution => {
+                                "🔴 Ask (distribution)"
+                            }
+                        };
+                        vec![
+                            side.to_string(),
+                            event.price_level.to_string(),
+                            event.absorbed_volume.normalize().to_string(),
+                            event.refill_count.to_string(),
+                            format!("{:?}", event.suspected_entity_type),
+                        ]
+                    })
+                    .collect();
+                content.push_str(&formatter::build_table(&headers, &rows));
+                content.push('\n');
+            }
+
             // T045: Trading signal based on flow direction
             content.push_str("### Trading Signal\n\n");
             content.push_str(&format!("**Recommendation:** {}\n\n", signal));
@@ -910,9 +1119,10 @@ pub async fn build_microstructure_section_async(
         Err(util::TimeoutError::Exceeded) => {
             // FR-013: Graceful degradation on timeout
             content.push_str("⚠️ **[Data Unavailable: timeout]**\n\n");
-            content.push_str(
-                "Order flow calculation exceeded 1-second timeout. This may indicate high system load.\n\n",
-            );
+            content.push_str(&format!(
+                "Order flow calculation exceeded {}ms timeout. This may indicate high system load.\n\n",
+                analytics_timeout_ms
+            ));
         }
         Err(util::TimeoutError::Analytics(e)) => {
             // FR-013: Graceful degradation on analytics error
@@ -1030,6 +1240,8 @@ pub fn build_anomalies_section(timestamp: Option<i64>) -> ReportSection {
 /// * `storage` - Analytics snapshot storage for historical data
 /// * `symbol` - Trading pair symbol
 /// * `generated_at` - Report generation timestamp for data age calculation
+/// * `analytics_timeout_ms` - Per-call analytics timeout (from `ReportOptions`)
+/// * `analytics_permits` - Generator-wide limit on concurrent analytics queries
 ///
 /// # Returns
 /// ReportSection with anomaly detections or "No anomalies detected" message
@@ -1038,15 +1250,21 @@ pub async fn build_anomalies_section_async(
     storage: &std::sync::Arc<crate::orderbook::analytics::SnapshotStorage>,
     symbol: &str,
     generated_at: chrono::DateTime<chrono::Utc>,
+    analytics_timeout_ms: u64,
+    analytics_permits: &tokio::sync::Semaphore,
 ) -> ReportSection {
     use super::{formatter, util};
-    use crate::orderbook::analytics::tools::detect_market_anomalies;
+    use crate::orderbook::analytics::tools::{detect_market_anomalies, DetectAnomaliesParams};
 
     // T019-T021: Call anomaly detection with timeout (FR-020)
     let anomalies_result = util::timeout_analytics(
-        detect_market_anomalies(storage.clone(), symbol),
+        util::limit_analytics(
+            analytics_permits,
+            detect_market_anomalies(storage.clone(), DetectAnomaliesParams::new(symbol)),
+        ),
         "detect_market_anomalies",
         symbol,
+        analytics_timeout_ms,
     )
     .await;
 
@@ -1153,9 +1371,10 @@ pub async fn build_anomalies_section_async(
         Err(util::TimeoutError::Exceeded) => {
             // FR-013: Graceful degradation on timeout
             content.push_str("⚠️ **[Data Unavailable: timeout]**\n\n");
-            content.push_str(
-                "Anomaly detection exceeded 1-second timeout. This may indicate high system load.\n\n",
-            );
+            content.push_str(&format!(
+                "Anomaly detection exceeded {}ms timeout. This may indicate high system load.\n\n",
+                analytics_timeout_ms
+            ));
         }
         Err(util::TimeoutError::Analytics(e)) => {
             // FR-013: Graceful degradation on analytics error
@@ -1231,7 +1450,10 @@ pub fn build_health_section() -> ReportSection {
 /// # Arguments
 /// * `storage` - Analytics snapshot storage for historical data
 /// * `symbol` - Trading pair symbol
+/// * `live_metrics` - Live order book metrics used for the liquidity depth score
 /// * `generated_at` - Report generation timestamp for data age calculation
+/// * `analytics_timeout_ms` - Per-call analytics timeout (from `ReportOptions`)
+/// * `analytics_permits` - Generator-wide limit on concurrent analytics queries
 ///
 /// # Returns
 /// ReportSection with composite health score and component breakdowns
@@ -1239,16 +1461,23 @@ pub fn build_health_section() -> ReportSection {
 pub async fn build_health_section_async(
     storage: &std::sync::Arc<crate::orderbook::analytics::SnapshotStorage>,
     symbol: &str,
+    live_metrics: Option<&crate::orderbook::types::OrderBookMetrics>,
     generated_at: chrono::DateTime<chrono::Utc>,
+    analytics_timeout_ms: u64,
+    analytics_permits: &tokio::sync::Semaphore,
 ) -> ReportSection {
     use super::{formatter, util};
     use crate::orderbook::analytics::tools::get_microstructure_health;
 
     // T031-T032: Call health calculation with timeout (FR-020)
     let health_result = util::timeout_analytics(
-        get_microstructure_health(storage.clone(), symbol),
+        util::limit_analytics(
+            analytics_permits,
+            get_microstructure_health(storage.clone(), symbol, live_metrics),
+        ),
         "get_microstructure_health",
         symbol,
+        analytics_timeout_ms,
     )
     .await;
 
@@ -1320,9 +1549,10 @@ pub async fn build_health_section_async(
         Err(util::TimeoutError::Exceeded) => {
             // FR-013: Graceful degradation on timeout
             content.push_str("⚠️ **[Data Unavailable: timeout]**\n\n");
-            content.push_str(
-                "Health calculation exceeded 1-second timeout. This may indicate high system load.\n\n",
-            );
+            content.push_str(&format!(
+                "Health calculation exceeded {}ms timeout. This may indicate high system load.\n\n",
+                analytics_timeout_ms
+            ));
         }
         Err(util::TimeoutError::Analytics(e)) => {
             // FR-013: Graceful degradation on analytics error
@@ -1364,19 +1594,316 @@ pub fn build_health_section() -> ReportSection {
     }
 }
 
+/// Build the only data section of a report for a symbol Binance doesn't list
+///
+/// Replaces every other section, which would all fail on the same missing
+/// symbol.
+///
+/// # Arguments
+/// * `symbol` - Trading pair as requested
+/// * `reason` - Binance's error message
+pub fn build_symbol_not_found_section(symbol: &str, reason: &str) -> ReportSection {
+    use super::formatter;
+
+    let mut content = formatter::build_section_header("Symbol Not Found", 2);
+    content.push_str(&format!(
+        "⚠️ **Symbol not found or not trading**: `{}` is not listed on Binance spot \
+         or is no longer trading ({}). Check the spelling and quote asset, e.g. \
+         `BTCUSDT`.\n\n",
+        symbol, reason
+    ));
+
+    ReportSection {
+        name: "symbol_not_found".to_string(),
+        title: "Symbol Not Found".to_string(),
+        content: Ok(content),
+        data_age_ms: None,
+    }
+}
+
+/// Build the notice closing a report cut short by `max_total_ms`
+///
+/// # Arguments
+/// * `max_total_ms` - The overall deadline that passed
+/// * `unfinished` - Sections not generated in time, in report order
+pub fn build_deadline_exceeded_section(max_total_ms: u64, unfinished: &[&str]) -> ReportSection {
+    use super::formatter;
+
+    let mut content = formatter::build_section_header("Report Incomplete", 2);
+    content.push_str(&format!(
+        "⚠️ **Deadline exceeded**: generation stopped after the {} ms limit \
+         (`max_total_ms`).",
+        max_total_ms
+    ));
+    if !unfinished.is_empty() {
+        content.push_str(&format!(
+            " Not generated: {}.",
+            unfinished
+                .iter()
+                .map(|name| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    content.push_str("\n\n");
+
+    ReportSection {
+        name: "deadline_exceeded".to_string(),
+        title: "Report Incomplete".to_string(),
+        content: Ok(content),
+        data_age_ms: None,
+    }
+}
+
+/// Build an analytics section whose storage failed to open at startup
+#[cfg(feature = "orderbook_analytics")]
+pub fn build_storage_unavailable_section(name: &str, title: &str) -> ReportSection {
+    ReportSection {
+        name: name.to_string(),
+        title: title.to_string(),
+        content: Err(SectionError::DataSourceUnavailable(
+            "analytics storage".to_string(),
+        )),
+        data_age_ms: None,
+    }
+}
+
+/// Long/short ratio above which the crowd is read as bullish
+const LONG_SHORT_BULLISH: f64 = 1.1;
+/// Long/short ratio below which the crowd is read as bearish
+const LONG_SHORT_BEARISH: f64 = 0.9;
+
+/// Build derivatives sentiment section from USDⓈ-M perpetual data
+///
+/// Includes: Open interest, its change over the history window, and the global
+/// long/short account ratio with a bullish/bearish indicator.
+///
+/// # Arguments
+/// * `symbol` - Trading pair (used for the base asset label)
+/// * `open_interest` - Current open interest (None = futures data unavailable)
+/// * `oi_history` - Open interest buckets, oldest first (change row shows n/a when empty)
+/// * `long_short` - Long/short ratio buckets, oldest first (latest one is rendered)
+/// * `period` - Bucket size the history and ratio were requested with
+pub fn build_derivatives_sentiment_section(
+    symbol: &str,
+    open_interest: Option<&crate::binance::types::OpenInterest>,
+    oi_history: &[crate::binance::types::OpenInterestHist],
+    long_short: &[crate::binance::types::LongShortRatio],
+    period: &str,
+) -> ReportSection {
+    use super::formatter;
+
+    let Some(oi) = open_interest else {
+        return ReportSection {
+            name: "derivatives_sentiment".to_string(),
+            title: "Derivatives Sentiment".to_string(),
+            content: Err(SectionError::DataSourceUnavailable(
+                "Futures market".to_string(),
+            )),
+            data_age_ms: None,
+        };
+    };
+
+    let (base, _) = formatter::split_symbol(symbol);
+    let current: f64 = oi.open_interest.parse().unwrap_or(0.0);
+
+    let mut section = formatter::build_section_header("Derivatives Sentiment", 2);
+
+    let headers = vec!["Metric", "Value"];
+    let mut rows = vec![vec![
+        "Open Interest".to_string(),
+        formatter::format_base_qty(current, base),
+    ]];
+
+    let oi_change = oi_history.first().and_then(|oldest| {
+        let previous: f64 = oldest.sum_open_interest.parse().ok()?;
+        (previous > 0.0).then(|| {
+            let hours = (oi.time - oldest.timestamp).max(0) / 3_600_000;
+            let delta = current - previous;
+            (hours, delta, delta / previous * 100.0)
+        })
+    });
+    rows.push(match oi_change {
+        Some((hours, delta, pct)) => vec![
+            format!("OI Change ({}h)", hours),
+            format!(
+                "{:+.2}% ({}{})",
+                pct,
+                if delta >= 0.0 { "+" } else { "-" },
+                formatter::format_base_qty(delta.abs(), base)
+            ),
+        ],
+        None => vec!["OI Change".to_string(), "n/a".to_string()],
+    });
+
+    if let Some(latest) = long_short.last() {
+        let ratio: f64 = latest.long_short_ratio.parse().unwrap_or(0.0);
+        let indicator = if ratio >= LONG_SHORT_BULLISH {
+            "🟢 Bullish"
+        } else if ratio <= LONG_SHORT_BEARISH {
+            "🔴 Bearish"
+        } else {
+            "⚪ Neutral"
+        };
+        let trend = match long_short.iter().rev().nth(1) {
+            Some(previous) => {
+                let previous: f64 = previous.long_short_ratio.parse().unwrap_or(ratio);
+                if ratio > previous {
+                    " ⬆️"
+                } else if ratio < previous {
+                    " ⬇️"
+                } else {
+                    ""
+                }
+            }
+            None => "",
+        };
+        let share = |raw: &str| raw.parse::<f64>().unwrap_or(0.0) * 100.0;
+
+        rows.push(vec![
+            "Long/Short Ratio".to_string(),
+            format!("{:.4}{} ({})", ratio, trend, indicator),
+        ]);
+        rows.push(vec![
+            "Long Accounts".to_string(),
+            formatter::format_percentage(share(&latest.long_account)),
+        ]);
+        rows.push(vec![
+            "Short Accounts".to_string(),
+            formatter::format_percentage(share(&latest.short_account)),
+        ]);
+    } else {
+        rows.push(vec!["Long/Short Ratio".to_string(), "n/a".to_string()]);
+    }
+
+    section.push_str(&formatter::build_table(&headers, &rows));
+    section.push('\n');
+    section.push_str(&format!(
+        "*USDⓈ-M perpetual data as of {}; long/short ratio over {} buckets \
+        (≥{} bullish, ≤{} bearish).*\n\n",
+        formatter::format_timestamp(oi.time),
+        period,
+        LONG_SHORT_BULLISH,
+        LONG_SHORT_BEARISH
+    ));
+
+    ReportSection {
+        name: "derivatives_sentiment".to_string(),
+        title: "Derivatives Sentiment".to_string(),
+        content: Ok(section),
+        data_age_ms: None,
+    }
+}
+
+/// Build the recent trades (time and sales) section
+///
+/// Lists aggregated trades newest first with running buy/sell volume totals
+/// accumulated from the oldest listed trade, so the top row carries the totals
+/// for the whole tape. A trade whose buyer was the maker was initiated by the
+/// seller and is shown as a sell.
+///
+/// # Arguments
+/// * `symbol` - Trading pair (used for the base/quote labels)
+/// * `trades` - Aggregated trades, oldest first (None = trade data unavailable)
+/// * `price_decimals` - Decimals for trade prices
+pub fn build_recent_trades_section(
+    symbol: &str,
+    trades: Option<&[crate::binance::types::AggTradeResponse]>,
+    price_decimals: usize,
+) -> ReportSection {
+    use super::formatter;
+
+    let Some(trades) = trades else {
+        return ReportSection {
+            name: "recent_trades".to_string(),
+            title: "Recent Trades".to_string(),
+            content: Err(SectionError::DataSourceUnavailable(
+                "recent trades".to_string(),
+            )),
+            data_age_ms: None,
+        };
+    };
+
+    let (base, quote) = formatter::split_symbol(symbol);
+    let decimals = price_decimals;
+    let mut section = formatter::build_section_header("Recent Trades", 2);
+
+    if trades.is_empty() {
+        section.push_str("*No recent trades*\n\n");
+    } else {
+        let mut buy_volume = 0.0;
+        let mut sell_volume = 0.0;
+        let mut rows: Vec<Vec<String>> = trades
+            .iter()
+            .map(|trade| {
+                let qty: f64 = trade.qty.parse().unwrap_or(0.0);
+                let side = if trade.is_buyer_maker {
+                    sell_volume += qty;
+                    "🔴 Sell"
+                } else {
+                    buy_volume += qty;
+                    "🟢 Buy"
+                };
+                let time = chrono::DateTime::from_timestamp_millis(trade.time)
+                    .map(|dt| dt.format("%H:%M:%S%.3f").to_string())
+                    .unwrap_or_else(|| trade.time.to_string());
+                vec![
+                    time,
+                    side.to_string(),
+                    formatter::format_quote_price(&trade.price, quote, decimals),
+                    format!("{} {}", trade.qty, base),
+                    formatter::format_base_qty(buy_volume, base),
+                    formatter::format_base_qty(sell_volume, base),
+                ]
+            })
+            .collect();
+        rows.reverse();
+
+        let headers = vec!["Time (UTC)", "Side", "Price", "Size", "Buy Vol", "Sell Vol"];
+        section.push_str(&formatter::build_table(&headers, &rows));
+        section.push('\n');
+
+        let total = buy_volume + sell_volume;
+        let buy_share = if total > 0.0 {
+            buy_volume / total * 100.0
+        } else {
+            0.0
+        };
+        section.push_str(&format!(
+            "**Tape:** {} bought / {} sold ({} buys, net {}{})\n\n",
+            formatter::format_base_qty(buy_volume, base),
+            formatter::format_base_qty(sell_volume, base),
+            formatter::format_percentage(buy_share),
+            if buy_volume >= sell_volume { "+" } else { "-" },
+            formatter::format_base_qty((buy_volume - sell_volume).abs(), base)
+        ));
+    }
+
+    ReportSection {
+        name: "recent_trades".to_string(),
+        title: "Recent Trades".to_string(),
+        content: Ok(section),
+        data_age_ms: None,
+    }
+}
+
 /// Build data health status section
 ///
 /// Includes: Websocket connectivity, last update age, overall status
 /// Enhanced with degradation warnings and additional metrics (T041-T042)
-pub fn build_data_health_section(data_age_ms: i32) -> ReportSection {
+pub fn build_data_health_section(
+    data_age_ms: i32,
+    thresholds: &FreshnessThresholds,
+) -> ReportSection {
     use super::formatter;
 
     let mut section = formatter::build_section_header("Data Health Status", 2);
 
     // Determine overall health status (T039: Visual indicators)
-    let (status, status_emoji) = if data_age_ms < 1000 {
+    let age_ms = i64::from(data_age_ms);
+    let (status, status_emoji) = if age_ms < i64::from(thresholds.fresh_ms) {
         ("✅ Healthy", "🟢")
-    } else if data_age_ms < 5000 {
+    } else if age_ms < i64::from(thresholds.recent_ms) {
         ("⚠️ Degraded", "🟡")
     } else {
         ("❌ Critical", "🔴")
@@ -1388,21 +1915,21 @@ pub fn build_data_health_section(data_age_ms: i32) -> ReportSection {
     ));
 
     // T042: Add degradation warnings when data age exceeds thresholds
-    if data_age_ms > 30000 {
-        section.push_str("⚠️ **CRITICAL WARNING:** Data is severely stale (>30s). Market conditions may have changed significantly.\n\n");
-    } else if data_age_ms > 5000 {
-        section.push_str("⚠️ **WARNING:** Data freshness degraded (>5s). Consider refreshing for real-time trading decisions.\n\n");
+    if age_ms >= i64::from(thresholds.stale_ms) {
+        section.push_str(&format!(
+            "⚠️ **CRITICAL WARNING:** Data is severely stale (>={} ms). Market conditions may have changed significantly.\n\n",
+            thresholds.stale_ms
+        ));
+    } else if age_ms >= i64::from(thresholds.recent_ms) {
+        section.push_str(&format!(
+            "⚠️ **WARNING:** Data freshness degraded (>={} ms). Consider refreshing for real-time trading decisions.\n\n",
+            thresholds.recent_ms
+        ));
     }
 
     // Status table
     let headers = vec!["Component", "Status", "Details"];
-    let freshness_status = if data_age_ms < 1000 {
-        "🟢 Fresh"
-    } else if data_age_ms < 5000 {
-        "🟡 Acceptable"
-    } else {
-        "🔴 Stale"
-    };
+    let freshness_status = formatter::freshness_label(data_age_ms, thresholds);
 
     let rows = vec![
         vec![
@@ -1444,10 +1971,20 @@ pub fn build_data_health_section(data_age_ms: i32) -> ReportSection {
     }
 }
 
+/// Footer attribution when `footer_attribution` is unset
+pub const DEFAULT_FOOTER_ATTRIBUTION: &str = "Generated by ForgeTrade MCP Market Data Provider";
+
 /// Build report footer with generation metadata
 ///
-/// Includes: Generation time, feature build info, cache status (T043)
-pub fn build_report_footer(generation_time_ms: i32, was_cached: bool) -> String {
+/// Includes: Generation time, cache status, feature build info (unless
+/// `show_build_info` is false) and the attribution line (T043). `attribution`
+/// replaces [`DEFAULT_FOOTER_ATTRIBUTION`]; an empty one omits the line.
+pub fn build_report_footer(
+    generation_time_ms: i32,
+    was_cached: bool,
+    attribution: Option<&str>,
+    show_build_info: bool,
+) -> String {
     use super::formatter;
 
     let mut footer = String::new();
@@ -1476,27 +2013,32 @@ pub fn build_report_footer(generation_time_ms: i32, was_cached: bool) -> String
     footer.push('\n');
 
     // Feature build info
-    footer.push_str("**Build Configuration:**\n");
-    let mut features = Vec::new();
+    if show_build_info {
+        footer.push_str("**Build Configuration:**\n");
+        let mut features = Vec::new();
 
-    #[cfg(feature = "orderbook")]
-    features.push("✅ OrderBook Analysis".to_string());
+        #[cfg(feature = "orderbook")]
+        features.push("✅ OrderBook Analysis".to_string());
 
-    #[cfg(feature = "orderbook_analytics")]
-    features.push("✅ Advanced Analytics (Anomalies, Health)".to_string());
+        #[cfg(feature = "orderbook_analytics")]
+        features.push("✅ Advanced Analytics (Anomalies, Health)".to_string());
 
-    #[cfg(not(feature = "orderbook_analytics"))]
-    features.push("⚠️ Advanced Analytics (Disabled)".to_string());
+        #[cfg(not(feature = "orderbook_analytics"))]
+        features.push("⚠️ Advanced Analytics (Disabled)".to_string());
 
-    if features.is_empty() {
-        features.push("⚠️ No advanced features enabled".to_string());
-    }
+        if features.is_empty() {
+            features.push("⚠️ No advanced features enabled".to_string());
+        }
 
-    footer.push_str(&formatter::build_list(&features, false));
-    footer.push('\n');
+        footer.push_str(&formatter::build_list(&features, false));
+        footer.push('\n');
+    }
 
-    footer.push_str("*Generated by ForgeTrade MCP Market Data Provider*\n");
-    footer.push('\n');
+    let attribution = attribution.unwrap_or(DEFAULT_FOOTER_ATTRIBUTION).trim();
+    if !attribution.is_empty() {
+        footer.push_str(&format!("*{}*\n", attribution));
+        footer.push('\n');
+    }
 
     footer
 }
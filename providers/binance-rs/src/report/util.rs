@@ -1,17 +1,23 @@
 //! Utility functions for report generation
 //!
 //! This module provides shared utilities for analytics integration including
-//! timeout enforcement and error logging.
+//! timeout enforcement, concurrency limiting and error logging.
 
 use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::Semaphore;
 use tokio::time::{timeout, Duration};
 
+/// Analytics queries allowed to run at once across all reports when
+/// `REPORT_ANALYTICS_CONCURRENCY` is unset
+pub const DEFAULT_ANALYTICS_CONCURRENCY: usize = 4;
+
 /// Error types for analytics timeout wrapper
 #[derive(Debug)]
 pub enum TimeoutError {
     /// Analytics function returned an error
     Analytics(String),
-    /// Function exceeded 1-second timeout
+    /// Function exceeded the configured timeout
     Exceeded,
 }
 
@@ -19,19 +25,20 @@ impl std::fmt::Display for TimeoutError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             TimeoutError::Analytics(msg) => write!(f, "Analytics error: {}", msg),
-            TimeoutError::Exceeded => write!(f, "Analytics function exceeded 1s timeout"),
+            TimeoutError::Exceeded => write!(f, "Analytics function exceeded timeout"),
         }
     }
 }
 
 impl std::error::Error for TimeoutError {}
 
-/// Wraps an analytics function with 1-second timeout and comprehensive error logging
+/// Wraps an analytics function with a timeout and comprehensive error logging
 ///
 /// # Purpose
-/// Implements FR-020 (1s timeout enforcement) and FR-021 (parallel execution support).
+/// Implements FR-020 (timeout enforcement) and FR-021 (parallel execution support).
 /// Each analytics function in report generation is wrapped with this utility to ensure:
-/// - No single analytics function blocks report generation >1 second
+/// - No single analytics function blocks report generation beyond `timeout_ms`
+///   (`ReportOptions::analytics_timeout_ms`, default 3000ms)
 /// - Timeout failures are logged for operators while sections degrade gracefully
 /// - Parallel execution via tokio::join! respects per-function timeout limits
 ///
@@ -39,19 +46,21 @@ impl std::error::Error for TimeoutError {}
 /// - `future`: The async analytics function to execute with timeout
 /// - `function_name`: Name of the analytics function for logging (e.g., "generate_volume_profile")
 /// - `symbol`: Trading pair symbol for context logging
+/// - `timeout_ms`: Maximum time to wait for the function, in milliseconds
 ///
 /// # Returns
 /// - `Ok(T)`: Analytics function succeeded within timeout
 /// - `Err(TimeoutError::Analytics)`: Function returned error (logged)
-/// - `Err(TimeoutError::Exceeded)`: Function exceeded 1s timeout (logged)
+/// - `Err(TimeoutError::Exceeded)`: Function exceeded `timeout_ms` (logged)
 ///
 /// # Example
 /// ```rust,ignore
 /// // Example usage (requires analytics context)
 /// let result = timeout_analytics(
-///     generate_volume_profile(symbol, trades, 24, None),
+///     generate_volume_profile(symbol, trades, 24, None, None, None, PocBasis::Volume, None),
 ///     "generate_volume_profile",
-///     "BTCUSDT"
+///     "BTCUSDT",
+///     1000,
 /// ).await;
 ///
 /// match result {
@@ -70,12 +79,13 @@ pub async fn timeout_analytics<T, E, F>(
     future: F,
     function_name: &str,
     symbol: &str,
+    timeout_ms: u64,
 ) -> Result<T, TimeoutError>
 where
     F: Future<Output = Result<T, E>>,
     E: std::fmt::Display,
 {
-    match timeout(Duration::from_secs(3), future).await {
+    match timeout(Duration::from_millis(timeout_ms), future).await {
         Ok(Ok(result)) => {
             // FR-018: Log successful execution at debug level
             tracing::debug!(
@@ -100,7 +110,7 @@ where
             tracing::warn!(
                 symbol = %symbol,
                 function = %function_name,
-                timeout_ms = 1000,
+                timeout_ms,
                 "Analytics function exceeded timeout, section will degrade gracefully"
             );
             Err(TimeoutError::Exceeded)
@@ -108,6 +118,45 @@ where
     }
 }
 
+/// Runs an analytics query once a permit from `permits` is available
+///
+/// The generator shares one semaphore across every report, so concurrent
+/// reports cannot queue more RocksDB queries onto the `spawn_blocking` pool
+/// than it has permits. Wrap the query *inside* [`timeout_analytics`] so time
+/// spent waiting for a permit counts against the section's timeout.
+///
+/// `future` must be lazy (e.g. an `async fn` call): a task it has already
+/// spawned runs regardless of the permit. Use [`spawn_blocking_limited`] for a
+/// blocking closure.
+pub async fn limit_analytics<F: Future>(permits: &Semaphore, future: F) -> F::Output {
+    // The semaphore is never closed; if it were, run unlimited rather than fail
+    let _permit = permits.acquire().await.ok();
+    future.await
+}
+
+/// Runs blocking `query` on the `spawn_blocking` pool once a permit from
+/// `permits` is available
+///
+/// The permit moves into the blocking task and is held until `query` returns,
+/// even if the caller stops waiting (e.g. on a timeout), so abandoned queries
+/// still count against the limit while they run.
+pub async fn spawn_blocking_limited<T, F>(
+    permits: &Arc<Semaphore>,
+    query: F,
+) -> Result<T, tokio::task::JoinError>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    // The semaphore is never closed; if it were, run unlimited rather than fail
+    let permit = Arc::clone(permits).acquire_owned().await.ok();
+    tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        query()
+    })
+    .await
+}
+
 /// Log error for report section rendering
 ///
 /// Implements FR-018 error logging requirements:
@@ -152,23 +201,19 @@ pub fn calculate_data_age_ms(
 
 /// Get data freshness indicator based on age
 ///
-/// Maps data age to visual indicator for FR-015 compliance.
+/// Maps data age to visual indicator for FR-015 compliance, using the default
+/// [`FreshnessThresholds`](super::FreshnessThresholds).
 ///
 /// # Arguments
 /// - `age_ms`: Data age in milliseconds
 ///
 /// # Returns
-/// - 🟢 Fresh (0-1s)
+/// - 🟢 Fresh (<1s)
 /// - 🟡 Recent (1-5s)
 /// - 🟠 Aging (5-30s)
-/// - 🔴 Stale (>30s)
+/// - 🔴 Stale (>=30s)
 pub fn data_age_indicator(age_ms: i32) -> &'static str {
-    match age_ms {
-        0..=1000 => "🟢 Fresh",
-        1001..=5000 => "🟡 Recent",
-        5001..=30000 => "🟠 Aging",
-        _ => "🔴 Stale",
-    }
+    super::formatter::freshness_label(age_ms, &super::FreshnessThresholds::default())
 }
 
 #[cfg(test)]
@@ -198,7 +243,7 @@ mod tests {
             Ok("success".to_string())
         }
 
-        let result = timeout_analytics(mock_analytics(), "mock_analytics", "BTCUSDT").await;
+        let result = timeout_analytics(mock_analytics(), "mock_analytics", "BTCUSDT", 1000).await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), "success");
     }
@@ -209,7 +254,7 @@ mod tests {
             Err("calculation failed".to_string())
         }
 
-        let result = timeout_analytics(mock_analytics(), "mock_analytics", "BTCUSDT").await;
+        let result = timeout_analytics(mock_analytics(), "mock_analytics", "BTCUSDT", 1000).await;
         assert!(matches!(result, Err(TimeoutError::Analytics(_))));
     }
 
@@ -220,7 +265,75 @@ mod tests {
             Ok("too late".to_string())
         }
 
-        let result = timeout_analytics(slow_analytics(), "slow_analytics", "BTCUSDT").await;
+        let result = timeout_analytics(slow_analytics(), "slow_analytics", "BTCUSDT", 1000).await;
+        assert!(matches!(result, Err(TimeoutError::Exceeded)));
+    }
+
+    #[tokio::test]
+    async fn test_limit_analytics_caps_concurrent_queries() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let permits = Semaphore::new(3);
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+
+        let query = || async {
+            let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+            max_running.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            running.fetch_sub(1, Ordering::SeqCst);
+            Ok::<_, String>(())
+        };
+
+        let results = futures::future::join_all((0..12).map(|_| {
+            timeout_analytics(
+                limit_analytics(&permits, query()),
+                "mock_analytics",
+                "BTCUSDT",
+                5000,
+            )
+        }))
+        .await;
+
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(max_running.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_blocking_limited_caps_blocking_queries() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let permits = Arc::new(Semaphore::new(3));
+        let running = Arc::new(AtomicUsize::new(0));
+        let max_running = Arc::new(AtomicUsize::new(0));
+
+        let results = futures::future::join_all((0..12).map(|_| {
+            let running = Arc::clone(&running);
+            let max_running = Arc::clone(&max_running);
+            spawn_blocking_limited(&permits, move || {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(now, Ordering::SeqCst);
+                std::thread::sleep(std::time::Duration::from_millis(20));
+                running.fetch_sub(1, Ordering::SeqCst);
+            })
+        }))
+        .await;
+
+        assert!(results.iter().all(Result::is_ok));
+        assert_eq!(max_running.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_analytics_respects_configured_timeout() {
+        async fn slow_analytics() -> Result<String, String> {
+            tokio::time::sleep(Duration::from_millis(300)).await;
+            Ok("done".to_string())
+        }
+
+        let result = timeout_analytics(slow_analytics(), "slow_analytics", "BTCUSDT", 100).await;
         assert!(matches!(result, Err(TimeoutError::Exceeded)));
+
+        let result = timeout_analytics(slow_analytics(), "slow_analytics", "BTCUSDT", 2000).await;
+        assert_eq!(result.unwrap(), "done");
     }
 }
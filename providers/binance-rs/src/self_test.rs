@@ -0,0 +1,357 @@
+//! End-to-end liveness self-test
+//!
+//! Unlike [`check_health`](crate::health::check_health), which only reports the
+//! state components are already in, the self-test actively exercises each one
+//! for a canary symbol: a REST ticker request, a depth WebSocket subscription,
+//! a storage write/read round trip and a quick report. Checks run concurrently,
+//! each under its own timeout, so one slow or failing component never hides
+//! the result of the others. Served as the `binance.self_test` tool for
+//! deployment smoke tests.
+
+use crate::binance::BinanceClient;
+use futures::future::BoxFuture;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "orderbook")]
+use crate::orderbook::OrderBookManager;
+
+/// Timeout for the REST ticker request
+pub const REST_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Timeout for the depth WebSocket to report connected
+pub const WEBSOCKET_CHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Timeout for the storage write/read round trip
+pub const STORAGE_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Timeout for generating the quick report
+pub const REPORT_CHECK_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Outcome of one sub-check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Pass,
+    Fail,
+    /// Component not enabled in this build or not initialized
+    Skipped,
+}
+
+/// Overall self-test outcome
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SelfTestStatus {
+    /// Every check that ran passed
+    Pass,
+    /// Some checks passed and some failed
+    Partial,
+    /// Every check that ran failed
+    Fail,
+}
+
+/// Result of one component check
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ComponentCheck {
+    /// Component name ("rest", "websocket", "storage" or "report")
+    pub component: String,
+    pub status: CheckStatus,
+    /// Wall-clock time the check took (0 when skipped)
+    pub duration_ms: u64,
+    /// What was verified, or why the check failed or was skipped
+    pub detail: String,
+}
+
+/// Per-component self-test results for a canary symbol
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SelfTestReport {
+    pub symbol: String,
+    pub status: SelfTestStatus,
+    /// Checks in run order
+    pub checks: Vec<ComponentCheck>,
+    /// Wall-clock time of the whole self-test
+    pub duration_ms: u64,
+    /// Completion timestamp (milliseconds since epoch)
+    pub timestamp: i64,
+}
+
+/// A named check: `Ok(detail)` passes, `Err(reason)` fails, `None` is skipped
+pub type Check<'a> = (
+    &'static str,
+    Duration,
+    Option<BoxFuture<'a, Result<String, String>>>,
+);
+
+/// Run `checks` concurrently, each bounded by its own timeout
+///
+/// A check that errors or times out is recorded as failed without affecting
+/// the others.
+pub async fn run_checks(symbol: &str, checks: Vec<Check<'_>>) -> SelfTestReport {
+    let started = Instant::now();
+
+    let results = futures::future::join_all(checks.into_iter().map(
+        |(component, timeout, check)| async move {
+            let Some(check) = check else {
+                return ComponentCheck {
+                    component: component.to_string(),
+                    status: CheckStatus::Skipped,
+                    duration_ms: 0,
+                    detail: "Not enabled".to_string(),
+                };
+            };
+
+            let check_started = Instant::now();
+            let (status, detail) = match tokio::time::timeout(timeout, check).await {
+                Ok(Ok(detail)) => (CheckStatus::Pass, detail),
+                Ok(Err(reason)) => (CheckStatus::Fail, reason),
+                Err(_) => (
+                    CheckStatus::Fail,
+                    format!("Timed out after {}ms", timeout.as_millis()),
+                ),
+            };
+            ComponentCheck {
+                component: component.to_string(),
+                status,
+                duration_ms: check_started.elapsed().as_millis() as u64,
+                detail,
+            }
+        },
+    ))
+    .await;
+
+    let ran = results
+        .iter()
+        .filter(|check| check.status != CheckStatus::Skipped)
+        .count();
+    let failed = results
+        .iter()
+        .filter(|check| check.status == CheckStatus::Fail)
+        .count();
+    let status = if failed == 0 {
+        SelfTestStatus::Pass
+    } else if failed < ran {
+        SelfTestStatus::Partial
+    } else {
+        SelfTestStatus::Fail
+    };
+
+    SelfTestReport {
+        symbol: symbol.to_string(),
+        status,
+        checks: results,
+        duration_ms: started.elapsed().as_millis() as u64,
+        timestamp: chrono::Utc::now().timestamp_millis(),
+    }
+}
+
+/// Exercise REST, WebSocket, storage and report generation for `symbol`
+///
+/// Components that are not enabled in this build or were not initialized are
+/// reported as skipped.
+pub async fn run_self_test(
+    client: &BinanceClient,
+    #[cfg(feature = "orderbook")] orderbook_manager: Option<&OrderBookManager>,
+    #[cfg(feature = "orderbook_analytics")] analytics_storage: Option<
+        &crate::orderbook::analytics::SnapshotStorage,
+    >,
+    #[cfg(feature = "orderbook")] report_generator: Option<&crate::report::ReportGenerator>,
+    symbol: &str,
+) -> SelfTestReport {
+    let rest: BoxFuture<'_, Result<String, String>> = Box::pin(async move {
+        let ticker = client
+            .get_24hr_ticker(symbol)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(format!("24hr ticker last price {}", ticker.last_price))
+    });
+
+    #[cfg(feature = "orderbook")]
+    let websocket = orderbook_manager.map(|manager| {
+        Box::pin(check_websocket(manager, symbol)) as BoxFuture<'_, Result<String, String>>
+    });
+    #[cfg(not(feature = "orderbook"))]
+    let websocket = None;
+
+    #[cfg(feature = "orderbook_analytics")]
+    let storage = analytics_storage.map(|storage| {
+        Box::pin(check_storage(storage, symbol)) as BoxFuture<'_, Result<String, String>>
+    });
+    #[cfg(not(feature = "orderbook_analytics"))]
+    let storage = None;
+
+    #[cfg(feature = "orderbook")]
+    let report = report_generator.map(|generator| {
+        Box::pin(check_report(generator, symbol)) as BoxFuture<'_, Result<String, String>>
+    });
+    #[cfg(not(feature = "orderbook"))]
+    let report = None;
+
+    run_checks(
+        symbol,
+        vec![
+            ("rest", REST_CHECK_TIMEOUT, Some(rest)),
+            ("websocket", WEBSOCKET_CHECK_TIMEOUT, websocket),
+            ("storage", STORAGE_CHECK_TIMEOUT, storage),
+            ("report", REPORT_CHECK_TIMEOUT, report),
+        ],
+    )
+    .await
+}
+
+/// Subscribe to `symbol` and wait until its depth WebSocket reports connected
+#[cfg(feature = "orderbook")]
+async fn check_websocket(manager: &OrderBookManager, symbol: &str) -> Result<String, String> {
+    manager.subscribe(symbol).await.map_err(|e| e.to_string())?;
+
+    loop {
+        let health = manager.get_health().await;
+        if let Some(stream) = health
+            .streams
+            .iter()
+            .find(|stream| stream.symbol == symbol && stream.connected)
+        {
+            return Ok(format!(
+                "Depth stream connected ({} reconnects)",
+                stream.reconnects
+            ));
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+/// Write a probe value, read it back and delete it
+#[cfg(feature = "orderbook_analytics")]
+async fn check_storage(
+    storage: &crate::orderbook::analytics::SnapshotStorage,
+    symbol: &str,
+) -> Result<String, String> {
+    let probe_symbol = format!("selftest-{}", symbol);
+    let timestamp_ms = chrono::Utc::now().timestamp_millis();
+    let value = timestamp_ms.to_be_bytes();
+
+    storage
+        .put(&probe_symbol, timestamp_ms, &value)
+        .await
+        .map_err(|e| format!("Write failed: {}", e))?;
+    let read = storage
+        .get(&probe_symbol, timestamp_ms)
+        .await
+        .map_err(|e| format!("Read failed: {}", e))?;
+    storage
+        .delete(&probe_symbol, timestamp_ms)
+        .await
+        .map_err(|e| format!("Cleanup failed: {}", e))?;
+
+    match read {
+        Some(read) if read == value => Ok("Write/read round trip succeeded".to_string()),
+        Some(_) => Err("Read back a different value than written".to_string()),
+        None => Err("Probe value missing after write".to_string()),
+    }
+}
+
+/// Generate an uncached quick report
+#[cfg(feature = "orderbook")]
+async fn check_report(
+    generator: &crate::report::ReportGenerator,
+    symbol: &str,
+) -> Result<String, String> {
+    use crate::report::{ReportOptions, ReportPreset};
+
+    let options = ReportOptions {
+        include_sections: None,
+        preset: Some(ReportPreset::Quick),
+        no_cache: true,
+        ..Default::default()
+    };
+    let report = generator
+        .generate_report(symbol, options, &tokio_util::sync::CancellationToken::new())
+        .await?;
+
+    if report.failed_sections.is_empty() {
+        Ok(format!(
+            "Quick report generated in {}ms",
+            report.generation_time_ms
+        ))
+    } else {
+        Err(format!(
+            "Report sections failed: {}",
+            report.failed_sections.join(", ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check<'a>(
+        result: Result<&'a str, &'a str>,
+        delay: Duration,
+    ) -> Option<BoxFuture<'a, Result<String, String>>> {
+        Some(Box::pin(async move {
+            tokio::time::sleep(delay).await;
+            result.map(str::to_string).map_err(str::to_string)
+        }))
+    }
+
+    #[tokio::test]
+    async fn test_failed_check_reports_partial_failure() {
+        let timeout = Duration::from_millis(200);
+        let report = run_checks(
+            "BTCUSDT",
+            vec![
+                ("rest", timeout, check(Ok("ticker ok"), Duration::ZERO)),
+                (
+                    "websocket",
+                    timeout,
+                    check(Err("connection refused"), Duration::ZERO),
+                ),
+                (
+                    "storage",
+                    timeout,
+                    check(Ok("round trip ok"), Duration::from_secs(5)),
+                ),
+                ("report", timeout, None),
+            ],
+        )
+        .await;
+
+        assert_eq!(report.status, SelfTestStatus::Partial);
+        let statuses: Vec<CheckStatus> = report.checks.iter().map(|c| c.status).collect();
+        assert_eq!(
+            statuses,
+            [
+                CheckStatus::Pass,
+                CheckStatus::Fail,
+                CheckStatus::Fail,
+                CheckStatus::Skipped
+            ]
+        );
+        assert_eq!(report.checks[1].detail, "connection refused");
+        assert!(report.checks[2].detail.starts_with("Timed out after 200ms"));
+        // The slow check was cut off by its own timeout, not the others'
+        assert!(report.duration_ms < 5000);
+    }
+
+    #[tokio::test]
+    async fn test_overall_status() {
+        let timeout = Duration::from_secs(1);
+        let passing = run_checks(
+            "BTCUSDT",
+            vec![
+                ("rest", timeout, check(Ok("ok"), Duration::ZERO)),
+                ("report", timeout, None),
+            ],
+        )
+        .await;
+        assert_eq!(passing.status, SelfTestStatus::Pass);
+
+        let failing = run_checks(
+            "BTCUSDT",
+            vec![("rest", timeout, check(Err("down"), Duration::ZERO))],
+        )
+        .await;
+        assert_eq!(failing.status, SelfTestStatus::Fail);
+    }
+}
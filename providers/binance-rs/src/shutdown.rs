@@ -0,0 +1,105 @@
+//! Process shutdown signals
+//!
+//! The gRPC, HTTP and SSE servers shut down gracefully on Ctrl+C (SIGINT) and,
+//! on Unix, on SIGTERM, which container orchestrators send before killing the
+//! process. Either one stops the servers and lets persistence tasks flush
+//! buffered trades and snapshots.
+
+use std::future::Future;
+use tokio::sync::broadcast;
+use tokio::task::JoinHandle;
+
+/// Wait for Ctrl+C or, on Unix, SIGTERM
+///
+/// # Returns
+/// Name of the signal received, for logging
+///
+/// # Errors
+/// Fails if a signal handler cannot be installed
+pub async fn shutdown_signal() -> std::io::Result<&'static str> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut terminate = signal(SignalKind::terminate())?;
+        first_signal(tokio::signal::ctrl_c(), async move {
+            terminate.recv().await;
+        })
+        .await
+    }
+
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await?;
+        Ok("Ctrl+C")
+    }
+}
+
+/// Resolve with whichever of `interrupt` (Ctrl+C) or `terminate` (SIGTERM)
+/// completes first
+pub async fn first_signal(
+    interrupt: impl Future<Output = std::io::Result<()>>,
+    terminate: impl Future<Output = ()>,
+) -> std::io::Result<&'static str> {
+    tokio::select! {
+        result = interrupt => result.map(|()| "Ctrl+C"),
+        () = terminate => Ok("SIGTERM"),
+    }
+}
+
+/// Broadcast on `shutdown_tx` once `signal` (e.g. [`shutdown_signal`]) resolves
+///
+/// A signal handler that fails to install is logged and never triggers
+/// shutdown.
+pub fn spawn_shutdown_listener<F>(signal: F, shutdown_tx: broadcast::Sender<()>) -> JoinHandle<()>
+where
+    F: Future<Output = std::io::Result<&'static str>> + Send + 'static,
+{
+    tokio::spawn(async move {
+        match signal.await {
+            Ok(name) => {
+                tracing::info!("Received shutdown signal ({})", name);
+                let _ = shutdown_tx.send(());
+            }
+            Err(err) => {
+                tracing::error!("Failed to listen for shutdown signal: {}", err);
+            }
+        }
+    })
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_terminate_triggers_shutdown() {
+        let (shutdown_tx, mut shutdown_rx) = broadcast::channel::<()>(1);
+        let (terminate_tx, terminate_rx) = tokio::sync::oneshot::channel::<()>();
+
+        // Ctrl+C never arrives; the simulated SIGTERM does
+        let signal = first_signal(std::future::pending(), async move {
+            terminate_rx.await.ok();
+        });
+        let listener = spawn_shutdown_listener(signal, shutdown_tx);
+
+        assert!(shutdown_rx.try_recv().is_err());
+        terminate_tx.send(()).unwrap();
+
+        tokio::time::timeout(Duration::from_secs(1), shutdown_rx.recv())
+            .await
+            .expect("shutdown not broadcast")
+            .unwrap();
+        listener.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_first_signal_names_the_source() {
+        let interrupt = first_signal(std::future::ready(Ok(())), std::future::pending());
+        assert_eq!(interrupt.await.unwrap(), "Ctrl+C");
+
+        let terminate = first_signal(std::future::pending(), async {});
+        assert_eq!(terminate.await.unwrap(), "SIGTERM");
+    }
+}
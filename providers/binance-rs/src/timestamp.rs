@@ -0,0 +1,121 @@
+//! Timestamps rendered both as RFC 3339 and as epoch milliseconds
+//!
+//! Tool outputs mix epoch milliseconds (`generated_at`) and RFC 3339 strings
+//! (`DateTime<Utc>` fields), and which one parses easily depends on the client
+//! language. Each of those fields has a `*_ts` companion of type [`Timestamp`],
+//! serialized as `{"iso": "2025-01-18T00:00:00.123Z", "ms": 1737158400123}`. The
+//! original fields are kept so existing clients keep working.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// An instant with millisecond precision, serialized as `{"iso", "ms"}`
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+)]
+#[serde(into = "TimestampRepr", try_from = "TimestampRepr")]
+pub struct Timestamp(DateTime<Utc>);
+
+/// Wire format of [`Timestamp`]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+struct TimestampRepr {
+    /// RFC 3339 / ISO-8601 in UTC with millisecond precision
+    iso: String,
+    /// Milliseconds since the Unix epoch
+    ms: i64,
+}
+
+impl Timestamp {
+    /// Current time, truncated to milliseconds
+    pub fn now() -> Self {
+        Utc::now().into()
+    }
+
+    /// Timestamp from epoch milliseconds, saturating outside chrono's range
+    pub fn from_millis(ms: i64) -> Self {
+        Self(DateTime::from_timestamp_millis(ms).unwrap_or(if ms < 0 {
+            DateTime::<Utc>::MIN_UTC
+        } else {
+            DateTime::<Utc>::MAX_UTC
+        }))
+    }
+
+    /// Milliseconds since the Unix epoch
+    pub fn as_millis(&self) -> i64 {
+        self.0.timestamp_millis()
+    }
+
+    /// RFC 3339 string in UTC with millisecond precision
+    pub fn to_rfc3339(&self) -> String {
+        self.0.to_rfc3339_opts(SecondsFormat::Millis, true)
+    }
+}
+
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(datetime: DateTime<Utc>) -> Self {
+        // Truncate so both representations name the same instant
+        Self::from_millis(datetime.timestamp_millis())
+    }
+}
+
+impl From<Timestamp> for DateTime<Utc> {
+    fn from(timestamp: Timestamp) -> Self {
+        timestamp.0
+    }
+}
+
+impl From<Timestamp> for TimestampRepr {
+    fn from(timestamp: Timestamp) -> Self {
+        Self {
+            iso: timestamp.to_rfc3339(),
+            ms: timestamp.as_millis(),
+        }
+    }
+}
+
+impl TryFrom<TimestampRepr> for Timestamp {
+    type Error = String;
+
+    fn try_from(repr: TimestampRepr) -> Result<Self, Self::Error> {
+        let timestamp = Self::from_millis(repr.ms);
+        let iso = DateTime::parse_from_rfc3339(&repr.iso)
+            .map_err(|e| format!("invalid iso timestamp '{}': {}", repr.iso, e))?;
+        if iso.timestamp_millis() != repr.ms {
+            return Err(format!(
+                "iso timestamp '{}' does not match ms {}",
+                repr.iso, repr.ms
+            ));
+        }
+        Ok(timestamp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serializes_consistent_iso_and_ms() {
+        let datetime = DateTime::parse_from_rfc3339("2025-01-18T00:00:00.123456Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let timestamp = Timestamp::from(datetime);
+
+        let json = serde_json::to_value(timestamp).unwrap();
+        assert_eq!(json["iso"], "2025-01-18T00:00:00.123Z");
+        assert_eq!(json["ms"], 1737158400123i64);
+
+        let iso = DateTime::parse_from_rfc3339(json["iso"].as_str().unwrap()).unwrap();
+        assert_eq!(iso.timestamp_millis(), json["ms"].as_i64().unwrap());
+
+        let round_trip: Timestamp = serde_json::from_value(json).unwrap();
+        assert_eq!(round_trip, timestamp);
+    }
+
+    #[test]
+    fn test_rejects_mismatched_representations() {
+        let json = serde_json::json!({"iso": "2025-01-18T00:00:00.123Z", "ms": 0});
+        assert!(serde_json::from_value::<Timestamp>(json).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+//! Correlation id and request logging middleware
+//!
+//! Every request gets an `X-Correlation-Id`: the client's value when it is a
+//! short printable token, otherwise a freshly generated UUID. The id is written
+//! back into the request headers (so handlers forward it as the gRPC
+//! `InvokeRequest.correlation_id`), recorded on a `tracing` span covering the
+//! whole request, and returned in the response headers.
+
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use std::time::Instant;
+use tracing::Instrument;
+
+/// Header carrying the correlation id in both directions
+pub const CORRELATION_ID_HEADER: HeaderName = HeaderName::from_static("x-correlation-id");
+
+/// Longest client-supplied correlation id that is accepted as-is
+const MAX_CORRELATION_ID_LEN: usize = 128;
+
+/// Axum middleware: ensure a correlation id, trace the request under it and echo it
+pub async fn correlation_id_middleware(mut request: Request, next: Next) -> Response {
+    let correlation_id = request
+        .headers()
+        .get(&CORRELATION_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| is_valid_correlation_id(id))
+        .map(str::to_string)
+        .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+
+    let header_value =
+        HeaderValue::from_str(&correlation_id).expect("validated correlation id is a valid header");
+    request
+        .headers_mut()
+        .insert(CORRELATION_ID_HEADER, header_value.clone());
+
+    let span = tracing::info_span!(
+        "http_request",
+        correlation_id = %correlation_id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+
+    async move {
+        let started = Instant::now();
+        let mut response = next.run(request).await;
+
+        tracing::info!(
+            status = response.status().as_u16(),
+            latency_ms = started.elapsed().as_millis() as u64,
+            "HTTP request completed"
+        );
+
+        response
+            .headers_mut()
+            .insert(CORRELATION_ID_HEADER, header_value);
+        response
+    }
+    .instrument(span)
+    .await
+}
+
+/// Whether a client-supplied id can be reused (non-empty, bounded, visible ASCII)
+fn is_valid_correlation_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_CORRELATION_ID_LEN && id.bytes().all(|b| b.is_ascii_graphic())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correlation_id_validation() {
+        assert!(is_valid_correlation_id("req-42"));
+        assert!(is_valid_correlation_id(&uuid::Uuid::new_v4().to_string()));
+        assert!(!is_valid_correlation_id(""));
+        assert!(!is_valid_correlation_id("has space"));
+        assert!(!is_valid_correlation_id(
+            &"x".repeat(MAX_CORRELATION_ID_LEN + 1)
+        ));
+    }
+}
@@ -0,0 +1,203 @@
+//! CORS policy for the HTTP transport
+//!
+//! Browsers may only call the MCP endpoint from origins listed in
+//! `MCP_CORS_ORIGINS`. When the variable is unset every origin is allowed
+//! (convenient for local development) and a warning is logged at startup.
+//! Methods and headers are always limited to what MCP clients need: the
+//! session and correlation id headers can be both sent and read.
+
+use super::correlation::CORRELATION_ID_HEADER;
+use axum::http::{header, HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+
+/// Session header sent by clients and returned by `initialize`
+const MCP_SESSION_HEADER: HeaderName = HeaderName::from_static("mcp-session-id");
+
+/// Methods used by the transport (POST /mcp, GET /metrics and /health)
+const ALLOWED_METHODS: [Method; 2] = [Method::GET, Method::POST];
+
+/// Cross-origin policy for the HTTP transport
+///
+/// ## Environment Variables
+///
+/// - `MCP_CORS_ORIGINS`: Comma-separated allowed origins, e.g.
+///   `https://app.example.com,http://localhost:5173` (default: any origin)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CorsConfig {
+    /// Allowed origins (None = any origin)
+    pub allowed_origins: Option<Vec<HeaderValue>>,
+}
+
+impl CorsConfig {
+    /// Load the CORS policy from `MCP_CORS_ORIGINS`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if an origin is not a valid `http(s)://host[:port]` value
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_value(std::env::var("MCP_CORS_ORIGINS").ok().as_deref())
+    }
+
+    /// Build the policy from a raw `MCP_CORS_ORIGINS` value (`None` = unset)
+    ///
+    /// Blank values and blank entries are ignored; an empty result or a `*`
+    /// entry allows any origin.
+    pub fn from_value(raw: Option<&str>) -> Result<Self, Box<dyn std::error::Error>> {
+        let mut origins: Vec<HeaderValue> = Vec::new();
+        for entry in raw
+            .unwrap_or("")
+            .split(',')
+            .map(str::trim)
+            .filter(|e| !e.is_empty())
+        {
+            if entry == "*" {
+                return Ok(Self::default());
+            }
+
+            // Browsers send the origin without a trailing slash or path
+            let origin = entry.trim_end_matches('/');
+            let has_scheme = origin.starts_with("http://") || origin.starts_with("https://");
+            let host = origin.split_once("://").map(|(_, host)| host).unwrap_or("");
+            if !has_scheme || host.is_empty() || host.contains('/') {
+                return Err(format!(
+                    "Invalid MCP_CORS_ORIGINS entry '{}': expected scheme://host[:port]",
+                    entry
+                )
+                .into());
+            }
+
+            let value = HeaderValue::from_str(origin)
+                .map_err(|e| format!("Invalid MCP_CORS_ORIGINS entry '{}': {}", entry, e))?;
+            if !origins.contains(&value) {
+                origins.push(value);
+            }
+        }
+
+        Ok(Self {
+            allowed_origins: (!origins.is_empty()).then_some(origins),
+        })
+    }
+
+    /// Build the `tower_http` layer for this policy
+    pub fn layer(&self) -> CorsLayer {
+        let allow_origin = match &self.allowed_origins {
+            Some(origins) => AllowOrigin::list(origins.clone()),
+            None => AllowOrigin::from(Any),
+        };
+
+        CorsLayer::new()
+            .allow_origin(allow_origin)
+            .allow_methods(ALLOWED_METHODS)
+            .allow_headers([
+                header::CONTENT_TYPE,
+                MCP_SESSION_HEADER,
+                CORRELATION_ID_HEADER,
+            ])
+            .expose_headers([MCP_SESSION_HEADER, CORRELATION_ID_HEADER])
+    }
+
+    /// Log the effective policy (warns when every origin is allowed)
+    pub fn log_policy(&self) {
+        match &self.allowed_origins {
+            Some(origins) => {
+                let origins: Vec<&str> = origins.iter().filter_map(|o| o.to_str().ok()).collect();
+                tracing::info!("CORS allowed origins: {}", origins.join(", "));
+            }
+            None => tracing::warn!(
+                "MCP_CORS_ORIGINS is not set: allowing requests from any origin. \
+                 Set it to a comma-separated origin list before exposing the server to browsers"
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unset_or_wildcard_allows_any() {
+        assert_eq!(CorsConfig::from_value(None).unwrap().allowed_origins, None);
+        assert_eq!(
+            CorsConfig::from_value(Some(" , ")).unwrap().allowed_origins,
+            None
+        );
+        assert_eq!(
+            CorsConfig::from_value(Some("https://a.example,*"))
+                .unwrap()
+                .allowed_origins,
+            None
+        );
+    }
+
+    #[test]
+    fn test_parses_and_normalizes_origins() {
+        let config = CorsConfig::from_value(Some(
+            " https://app.example.com/ ,http://localhost:5173,https://app.example.com",
+        ))
+        .unwrap();
+        assert_eq!(
+            config.allowed_origins.unwrap(),
+            vec![
+                HeaderValue::from_static("https://app.example.com"),
+                HeaderValue::from_static("http://localhost:5173"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_origins() {
+        assert!(CorsConfig::from_value(Some("app.example.com")).is_err());
+        assert!(CorsConfig::from_value(Some("https://")).is_err());
+        assert!(CorsConfig::from_value(Some("https://app.example.com/path")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_browsers_may_send_and_read_correlation_id() {
+        use axum::body::Body;
+        use axum::http::Request;
+        use tower::ServiceExt;
+
+        let router = axum::Router::new()
+            .route("/mcp", axum::routing::post(|| async { "ok" }))
+            .layer(CorsConfig::default().layer());
+
+        let preflight = router
+            .clone()
+            .oneshot(
+                Request::options("/mcp")
+                    .header(header::ORIGIN, "https://app.example.com")
+                    .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                    .header(
+                        header::ACCESS_CONTROL_REQUEST_HEADERS,
+                        "content-type,mcp-session-id,x-correlation-id",
+                    )
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let allowed = preflight.headers()[header::ACCESS_CONTROL_ALLOW_HEADERS]
+            .to_str()
+            .unwrap()
+            .to_lowercase();
+        assert!(allowed.contains("x-correlation-id"), "{}", allowed);
+        assert!(allowed.contains("mcp-session-id"), "{}", allowed);
+
+        let response = router
+            .oneshot(
+                Request::post("/mcp")
+                    .header(header::ORIGIN, "https://app.example.com")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let exposed = response.headers()[header::ACCESS_CONTROL_EXPOSE_HEADERS]
+            .to_str()
+            .unwrap()
+            .to_lowercase();
+        assert!(exposed.contains("x-correlation-id"), "{}", exposed);
+        assert!(exposed.contains("mcp-session-id"), "{}", exposed);
+    }
+}
@@ -30,6 +30,12 @@ pub enum HttpTransportError {
     #[error("Invalid parameters: {0}")]
     InvalidParams(String),
 
+    #[error("Request body exceeds {0} bytes")]
+    PayloadTooLarge(usize),
+
+    #[error("Request timed out after {0}ms")]
+    RequestTimeout(u64),
+
     #[error("Internal server error: {0}")]
     Internal(String),
 
@@ -53,6 +59,9 @@ impl HttpTransportError {
             HttpTransportError::Session(SessionError::InvalidSessionId) => {
                 JsonRpcError::session_missing()
             }
+            HttpTransportError::Session(SessionError::ToolRateLimited(retry_after_secs)) => {
+                JsonRpcError::tool_rate_limited(*retry_after_secs)
+            }
             HttpTransportError::JsonParse(_) => JsonRpcError::parse_error(),
             HttpTransportError::InvalidRequest(msg) => {
                 JsonRpcError::new(-32600, format!("Invalid Request: {}", msg))
@@ -63,26 +72,36 @@ impl HttpTransportError {
             HttpTransportError::InvalidParams(msg) => {
                 JsonRpcError::new(-32602, format!("Invalid params: {}", msg))
             }
+            HttpTransportError::PayloadTooLarge(max_body_bytes) => {
+                JsonRpcError::payload_too_large(*max_body_bytes)
+            }
+            HttpTransportError::RequestTimeout(timeout_ms) => {
+                JsonRpcError::request_timeout(*timeout_ms)
+            }
             HttpTransportError::Internal(msg) => {
                 JsonRpcError::new(-32603, format!("Internal error: {}", msg))
             }
-            HttpTransportError::Provider(err) => {
-                JsonRpcError::new(-32603, format!("Provider error: {}", err))
-            }
+            HttpTransportError::Provider(err) => JsonRpcError::with_data(
+                -32603,
+                format!("Provider error: {}", err),
+                serde_json::json!({ "error_code": err.error_code() }),
+            ),
         }
     }
 
     /// Get HTTP status code for error
     pub fn status_code(&self) -> StatusCode {
         match self {
-            HttpTransportError::Session(SessionError::SessionLimitExceeded(_)) => {
-                StatusCode::TOO_MANY_REQUESTS
-            }
+            HttpTransportError::Session(
+                SessionError::SessionLimitExceeded(_) | SessionError::ToolRateLimited(_),
+            ) => StatusCode::TOO_MANY_REQUESTS,
             HttpTransportError::Session(_) => StatusCode::UNAUTHORIZED,
             HttpTransportError::JsonParse(_) => StatusCode::BAD_REQUEST,
             HttpTransportError::InvalidRequest(_) => StatusCode::BAD_REQUEST,
             HttpTransportError::MethodNotFound(_) => StatusCode::NOT_FOUND,
             HttpTransportError::InvalidParams(_) => StatusCode::BAD_REQUEST,
+            HttpTransportError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            HttpTransportError::RequestTimeout(_) => StatusCode::REQUEST_TIMEOUT,
             HttpTransportError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
             HttpTransportError::Provider(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
@@ -145,4 +164,18 @@ mod tests {
         assert!(jsonrpc_err.data.is_some());
         assert_eq!(err.status_code(), StatusCode::TOO_MANY_REQUESTS);
     }
+
+    #[test]
+    fn test_provider_error_includes_error_code() {
+        let err = HttpTransportError::Provider(crate::error::ProviderError::RateLimited(
+            "weight exceeded".to_string(),
+        ));
+
+        let jsonrpc_err = err.to_jsonrpc_error();
+        assert_eq!(jsonrpc_err.code, -32603);
+        assert_eq!(
+            jsonrpc_err.data,
+            Some(serde_json::json!({ "error_code": "RATE_LIMITED" }))
+        );
+    }
 }
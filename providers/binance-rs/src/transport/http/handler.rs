@@ -1,19 +1,28 @@
 //! HTTP request handlers for MCP JSON-RPC endpoints
 //!
 //! Implements handlers for:
-//! - POST /mcp: Main JSON-RPC endpoint
+//! - POST /mcp: Main JSON-RPC endpoint (single requests or batches)
 //!   - initialize: Create session
 //!   - tools/list: List all available tools
 //!   - tools/call: Execute a tool
+//! - GET /metrics: Prometheus scrape endpoint
+//! - GET /health: Liveness/readiness probe
+//!
+//! JSON-RPC responses are JSON unless the client sends `Accept: application/msgpack`,
+//! in which case the same response object is encoded as MessagePack.
 
 use axum::{
     extract::State,
-    http::{HeaderMap, StatusCode},
+    http::{header, HeaderMap, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
-use std::collections::HashMap;
-use std::sync::Arc;
+use futures::future::{self, BoxFuture};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 use super::error::{HttpTransportError, Result};
@@ -21,11 +30,18 @@ use super::jsonrpc::{
     InitializeResult, JsonRpcRequest, JsonRpcResponse, ServerCapabilities, ServerInfo,
     ToolsCapability,
 };
+use super::metrics::{Metrics, ScrapeSnapshot, PROMETHEUS_CONTENT_TYPE, UNKNOWN_TOOL_LABEL};
 use super::session::SessionStore;
 use crate::binance::client::BinanceClient;
 use crate::grpc::capabilities::CapabilityBuilder;
 use crate::pb::{InvokeRequest, Json as PbJson};
 
+/// Content type of MessagePack-encoded JSON-RPC responses
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+/// Most requests accepted in one JSON-RPC batch
+pub const MAX_BATCH_REQUESTS: usize = 50;
+
 /// Shared application state
 #[derive(Clone)]
 pub struct AppState {
@@ -35,6 +51,9 @@ pub struct AppState {
     /// Binance API client
     pub binance_client: BinanceClient,
 
+    /// Prometheus counters shared across handlers
+    pub metrics: Arc<Metrics>,
+
     /// OrderBook manager (optional)
     #[cfg(feature = "orderbook")]
     pub orderbook_manager: Option<Arc<crate::orderbook::OrderBookManager>>,
@@ -57,16 +76,127 @@ pub struct AppState {
 /// POST /mcp
 /// Content-Type: application/json
 /// Mcp-Session-Id: <uuid> (optional for initialize)
+/// Accept: application/msgpack (optional, binary response body)
+///
+/// A JSON array body is handled as a batch (see [`handle_batch`]) and answered
+/// with an array of responses, or `202 Accepted` with no body when it only
+/// holds notifications.
 pub async fn handle_jsonrpc(
     State(state): State<AppState>,
     headers: HeaderMap,
-    Json(request): Json<JsonRpcRequest>,
+    Json(body): Json<serde_json::Value>,
 ) -> Result<Response> {
-    tracing::debug!(method = %request.method, "Received JSON-RPC request");
-
     // Extract session ID from headers (if present)
     let session_id = extract_session_id(&headers)?;
 
+    if let serde_json::Value::Array(batch) = body {
+        return match handle_batch(state, &headers, session_id, batch).await? {
+            Some(responses) => Ok(encode_response(&headers, &responses)),
+            None => Ok(StatusCode::ACCEPTED.into_response()),
+        };
+    }
+
+    let request: JsonRpcRequest = serde_json::from_value(body)
+        .map_err(|e| HttpTransportError::InvalidRequest(e.to_string()))?;
+    let response = dispatch(state, &headers, session_id, request).await?;
+    Ok(encode_response(&headers, &response))
+}
+
+/// Process a JSON-RPC batch, answering in request order
+///
+/// Requests between `initialize` calls run concurrently. Each `initialize` runs
+/// on its own, and when the batch carries no `Mcp-Session-Id` header the session
+/// it creates is used for the requests after it, so `initialize` + `tools/call`
+/// fit in one round trip. Failures become error entries; notifications get no
+/// entry. Returns None when nothing is left to answer.
+async fn handle_batch(
+    state: AppState,
+    headers: &HeaderMap,
+    mut session_id: Option<Uuid>,
+    batch: Vec<serde_json::Value>,
+) -> Result<Option<Vec<JsonRpcResponse>>> {
+    if batch.is_empty() {
+        return Err(HttpTransportError::InvalidRequest(
+            "empty batch".to_string(),
+        ));
+    }
+    if batch.len() > MAX_BATCH_REQUESTS {
+        return Err(HttpTransportError::InvalidRequest(format!(
+            "batch of {} requests exceeds the limit of {}",
+            batch.len(),
+            MAX_BATCH_REQUESTS
+        )));
+    }
+
+    let mut responses = Vec::with_capacity(batch.len());
+    let mut pending: Vec<BoxFuture<'_, Option<JsonRpcResponse>>> = Vec::new();
+
+    for entry in batch {
+        let request = match serde_json::from_value::<JsonRpcRequest>(entry) {
+            Ok(request) => request,
+            Err(e) => {
+                let error = HttpTransportError::InvalidRequest(e.to_string()).to_jsonrpc_error();
+                let response = JsonRpcResponse::error(error, serde_json::json!(null));
+                pending.push(Box::pin(future::ready(Some(response))));
+                continue;
+            }
+        };
+
+        if request.method == "initialize" {
+            responses.extend(
+                future::join_all(pending.drain(..))
+                    .await
+                    .into_iter()
+                    .flatten(),
+            );
+            let response = answer(state.clone(), headers, session_id, request).await;
+            if session_id.is_none() {
+                session_id = response
+                    .as_ref()
+                    .and_then(|response| response.result.as_ref())
+                    .and_then(|result| result["sessionId"].as_str())
+                    .and_then(|sid| Uuid::parse_str(sid).ok());
+            }
+            responses.extend(response);
+        } else {
+            pending.push(Box::pin(answer(
+                state.clone(),
+                headers,
+                session_id,
+                request,
+            )));
+        }
+    }
+    responses.extend(future::join_all(pending).await.into_iter().flatten());
+
+    Ok((!responses.is_empty()).then_some(responses))
+}
+
+/// Dispatch one batch entry, converting errors into error responses
+///
+/// Returns None for notifications (no `id`), whatever the outcome.
+async fn answer(
+    state: AppState,
+    headers: &HeaderMap,
+    session_id: Option<Uuid>,
+    request: JsonRpcRequest,
+) -> Option<JsonRpcResponse> {
+    let id = request.id.clone();
+    let result = dispatch(state, headers, session_id, request).await;
+    let id = id?;
+
+    Some(result.unwrap_or_else(|e| JsonRpcResponse::error(e.to_jsonrpc_error(), id)))
+}
+
+/// Route one JSON-RPC request to its method handler
+async fn dispatch(
+    state: AppState,
+    headers: &HeaderMap,
+    session_id: Option<Uuid>,
+    request: JsonRpcRequest,
+) -> Result<JsonRpcResponse> {
+    tracing::debug!(method = %request.method, "Received JSON-RPC request");
+
     // Route to appropriate handler based on method
     let response = match request.method.as_str() {
         "initialize" => handle_initialize(state, request).await?,
@@ -82,22 +212,46 @@ pub async fn handle_jsonrpc(
             handle_tools_list(state, request).await?
         }
         "tools/call" => {
-            // Validate session
+            // Validate session and spend one call from its budget
             if let Some(sid) = session_id {
                 state.sessions.validate_session(sid)?;
+                state.sessions.acquire_tool_call(sid)?;
             } else {
                 return Err(HttpTransportError::Session(
                     super::session::SessionError::InvalidSessionId,
                 ));
             }
-            handle_tools_call(state, request).await?
+            let correlation_id = headers
+                .get(&super::correlation::CORRELATION_ID_HEADER)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
+            handle_tools_call(state, request, correlation_id).await?
         }
         _ => {
             return Err(HttpTransportError::MethodNotFound(request.method.clone()));
         }
     };
 
-    Ok((StatusCode::OK, Json(response)).into_response())
+    Ok(response)
+}
+
+/// Encode a JSON-RPC response body as JSON, or MessagePack when the client accepts it
+fn encode_response<T: Serialize>(headers: &HeaderMap, body: &T) -> Response {
+    if !accepts_msgpack(headers) {
+        return (StatusCode::OK, Json(body)).into_response();
+    }
+
+    match rmp_serde::to_vec_named(body) {
+        Ok(body) => (
+            StatusCode::OK,
+            [(header::CONTENT_TYPE, MSGPACK_CONTENT_TYPE)],
+            body,
+        )
+            .into_response(),
+        Err(e) => {
+            HttpTransportError::Internal(format!("MessagePack encoding: {}", e)).into_response()
+        }
+    }
 }
 
 /// Handle initialize method
@@ -141,7 +295,8 @@ async fn handle_initialize(state: AppState, request: JsonRpcRequest) -> Result<J
 
 /// Handle tools/list method
 ///
-/// Returns all 21 available tools with their JSON schemas
+/// Returns the tools enabled by the compiled feature set (the same
+/// `CapabilityBuilder` output as gRPC `ListCapabilities`) with their JSON schemas
 async fn handle_tools_list(_state: AppState, request: JsonRpcRequest) -> Result<JsonRpcResponse> {
     tracing::debug!("Listing all available tools");
 
@@ -163,11 +318,19 @@ async fn handle_tools_list(_state: AppState, request: JsonRpcRequest) -> Result<
                 .and_then(|schema| serde_json::from_slice(&schema.value).ok())
                 .unwrap_or(serde_json::json!({}));
 
-            serde_json::json!({
+            let mut entry = serde_json::json!({
                 "name": tool.name,
                 "description": tool.description,
                 "inputSchema": input_schema
-            })
+            });
+            let output_schema: Option<serde_json::Value> = tool
+                .output_schema
+                .as_ref()
+                .and_then(|schema| serde_json::from_slice(&schema.value).ok());
+            if let Some(output_schema) = output_schema {
+                entry["outputSchema"] = output_schema;
+            }
+            entry
         })
         .collect();
 
@@ -179,8 +342,17 @@ async fn handle_tools_list(_state: AppState, request: JsonRpcRequest) -> Result<
 
 /// Handle tools/call method
 ///
-/// Routes tool invocations to the appropriate handler
-async fn handle_tools_call(state: AppState, request: JsonRpcRequest) -> Result<JsonRpcResponse> {
+/// Routes tool invocations to the appropriate handler. `correlation_id` (from the
+/// `X-Correlation-Id` header) is forwarded to the tool; without it the JSON-RPC id is used.
+///
+/// The tool runs on its own task with a cancellation token tied to the request:
+/// hyper drops the handler future when the client disconnects, which cancels
+/// the token so report generation stops before its next section.
+async fn handle_tools_call(
+    state: AppState,
+    request: JsonRpcRequest,
+    correlation_id: Option<String>,
+) -> Result<JsonRpcResponse> {
     // Extract parameters
     let params = request
         .params
@@ -196,6 +368,7 @@ async fn handle_tools_call(state: AppState, request: JsonRpcRequest) -> Result<J
         .unwrap_or(serde_json::json!({}));
 
     tracing::debug!(tool_name = %tool_name, "Calling tool");
+    let call_started = Instant::now();
 
     // Convert to gRPC InvokeRequest format
     let invoke_request = InvokeRequest {
@@ -203,47 +376,58 @@ async fn handle_tools_call(state: AppState, request: JsonRpcRequest) -> Result<J
         payload: Some(PbJson {
             value: serde_json::to_string(&tool_args)?.into_bytes(),
         }),
-        correlation_id: request
-            .id
-            .as_ref()
-            .and_then(|v| v.as_str())
-            .unwrap_or("")
-            .to_string(),
+        correlation_id: correlation_id.unwrap_or_else(|| {
+            request
+                .id
+                .as_ref()
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string()
+        }),
     };
 
     // Route to tool handler
-    #[cfg(all(feature = "orderbook", feature = "orderbook_analytics"))]
-    let response = crate::grpc::tools::route_tool(
-        &state.binance_client,
-        state.orderbook_manager.clone(),
-        state.analytics_storage.clone(),
-        state.trade_storage.clone(),
-        state.report_generator.clone(),
-        &invoke_request,
-    )
-    .await?;
-
-    #[cfg(all(feature = "orderbook", not(feature = "orderbook_analytics")))]
-    let response = crate::grpc::tools::route_tool(
-        &state.binance_client,
-        state.orderbook_manager.clone(),
-        None,
-        None,
-        state.report_generator.clone(),
-        &invoke_request,
-    )
-    .await?;
-
-    #[cfg(not(feature = "orderbook"))]
-    let response = crate::grpc::tools::route_tool(
-        &state.binance_client,
-        None,
-        None,
-        None,
-        None,
-        &invoke_request,
-    )
-    .await?;
+    let task_state = state.clone();
+    let outcome = spawn_cancellable(move |cancel| async move {
+        #[cfg(feature = "orderbook")]
+        let (orderbook_manager, report_generator) = (
+            task_state.orderbook_manager.clone(),
+            task_state.report_generator.clone(),
+        );
+        #[cfg(not(feature = "orderbook"))]
+        let (orderbook_manager, report_generator) = (None, None);
+
+        #[cfg(feature = "orderbook_analytics")]
+        let (analytics_storage, trade_storage) = (
+            task_state.analytics_storage.clone(),
+            task_state.trade_storage.clone(),
+        );
+        #[cfg(not(feature = "orderbook_analytics"))]
+        let (analytics_storage, trade_storage) = (None, None);
+
+        crate::grpc::tools::route_tool(
+            &task_state.binance_client,
+            orderbook_manager,
+            analytics_storage,
+            trade_storage,
+            report_generator,
+            &invoke_request,
+            &cancel,
+        )
+        .await
+    })
+    .await;
+
+    // Recorded once routing is done, so failed calls are counted and timed too
+    state
+        .metrics
+        .record_tool_invocation(tool_metric_label(tool_name));
+    if tool_name == "binance.generate_market_report" {
+        state
+            .metrics
+            .observe_report_latency(call_started.elapsed().as_millis() as u64);
+    }
+    let response = outcome??;
 
     // Convert response to JSON
     let result_json = if let Some(result_pb) = response.result {
@@ -266,6 +450,111 @@ async fn handle_tools_call(state: AppState, request: JsonRpcRequest) -> Result<J
     ))
 }
 
+/// Label for a tool's invocation counter: the name if the tool is listed,
+/// otherwise [`UNKNOWN_TOOL_LABEL`] so client-supplied names can't grow the
+/// metric's label set
+fn tool_metric_label(tool_name: &str) -> &str {
+    static LISTED_TOOLS: OnceLock<HashSet<String>> = OnceLock::new();
+    let listed = LISTED_TOOLS.get_or_init(|| {
+        CapabilityBuilder::new()
+            .build()
+            .map(|capabilities| capabilities.tools.into_iter().map(|t| t.name).collect())
+            .unwrap_or_default()
+    });
+
+    if listed.contains(tool_name) {
+        tool_name
+    } else {
+        UNKNOWN_TOOL_LABEL
+    }
+}
+
+/// Run `work` on its own task, cancelling its token if the caller goes away
+///
+/// Dropping the returned future (as hyper does when the client disconnects)
+/// cancels the token; the task keeps running until it next checks the token.
+async fn spawn_cancellable<T, F, Fut>(work: F) -> Result<T>
+where
+    F: FnOnce(CancellationToken) -> Fut,
+    Fut: std::future::Future<Output = T> + Send + 'static,
+    T: Send + 'static,
+{
+    let cancel = CancellationToken::new();
+    let _disconnect_guard = cancel.clone().drop_guard();
+
+    tokio::spawn(work(cancel))
+        .await
+        .map_err(|e| HttpTransportError::Internal(format!("Tool task failed: {}", e)))
+}
+
+/// Prometheus metrics endpoint
+///
+/// GET /metrics
+/// Returns counters in text exposition format; no session required.
+pub async fn handle_metrics(State(state): State<AppState>) -> Response {
+    #[allow(unused_mut)]
+    let mut snapshot = ScrapeSnapshot::default();
+
+    #[cfg(feature = "orderbook")]
+    {
+        if let Some(generator) = &state.report_generator {
+            let (hits, misses) = generator.cache_stats();
+            snapshot.report_cache_hits = hits;
+            snapshot.report_cache_misses = misses;
+        }
+        if let Some(manager) = &state.orderbook_manager {
+            snapshot.active_orderbook_symbols = manager.get_health().await.orderbook_symbols_active;
+            snapshot.websocket_reconnects = manager.websocket_reconnect_count();
+        }
+    }
+
+    (
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, PROMETHEUS_CONTENT_TYPE)],
+        state.metrics.render(&snapshot),
+    )
+        .into_response()
+}
+
+/// Health probe endpoint
+///
+/// GET /health
+/// Returns `ServerHealth` (including the open session count) as JSON with 200 when
+/// ready, or 503 when the orderbook tracks symbols but has zero connected WebSockets.
+pub async fn handle_health(State(state): State<AppState>) -> Response {
+    let mut health = crate::health::check_health(
+        &state.binance_client,
+        #[cfg(feature = "orderbook")]
+        state.orderbook_manager.as_deref(),
+        #[cfg(feature = "orderbook_analytics")]
+        state.analytics_storage.as_deref(),
+    )
+    .await;
+    health.active_sessions = Some(state.sessions.session_count());
+
+    let status = if health.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(health)).into_response()
+}
+
+/// Whether the `Accept` header asks for MessagePack (JSON stays the default)
+fn accepts_msgpack(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|media_type| media_type.split(';').next().unwrap_or("").trim())
+        .any(|media_type| {
+            media_type.eq_ignore_ascii_case(MSGPACK_CONTENT_TYPE)
+                || media_type.eq_ignore_ascii_case("application/x-msgpack")
+        })
+}
+
 /// Extract session ID from Mcp-Session-Id header
 fn extract_session_id(headers: &HeaderMap) -> Result<Option<Uuid>> {
     if let Some(header_value) = headers.get("mcp-session-id") {
@@ -313,4 +602,52 @@ mod tests {
         let result = extract_session_id(&headers);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_accepts_msgpack() {
+        let mut headers = HeaderMap::new();
+        assert!(!accepts_msgpack(&headers));
+
+        headers.insert(header::ACCEPT, "application/json".parse().unwrap());
+        assert!(!accepts_msgpack(&headers));
+
+        headers.insert(
+            header::ACCEPT,
+            "application/json;q=0.5, Application/MsgPack"
+                .parse()
+                .unwrap(),
+        );
+        assert!(accepts_msgpack(&headers));
+    }
+
+    #[tokio::test]
+    async fn test_dropped_request_cancels_spawned_work() {
+        let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+        let (stopped_tx, stopped_rx) = tokio::sync::oneshot::channel();
+
+        let call = spawn_cancellable(move |cancel| async move {
+            let _ = started_tx.send(());
+            cancel.cancelled().await;
+            let _ = stopped_tx.send(());
+        });
+
+        // Drop the handler future mid-call, as hyper does on disconnect
+        tokio::select! {
+            _ = call => panic!("work should wait for cancellation"),
+            _ = started_rx => {}
+        }
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), stopped_rx)
+            .await
+            .expect("spawned work was not cancelled")
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_spawn_cancellable_returns_result() {
+        let value = spawn_cancellable(|cancel| async move { cancel.is_cancelled() })
+            .await
+            .unwrap();
+        assert!(!value);
+    }
 }
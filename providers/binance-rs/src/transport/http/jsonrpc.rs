@@ -149,12 +149,42 @@ impl JsonRpcError {
         Self::new(-32001, "Session expired or invalid")
     }
 
-    /// Session limit exceeded (-32000)
+    /// Session limit reached (-32000); existing sessions are kept
     pub fn session_limit_exceeded(max: usize) -> Self {
         Self::with_data(
             -32000,
-            "Session limit exceeded",
-            serde_json::json!({ "max_sessions": max }),
+            format!("Session limit reached: maximum {} concurrent sessions", max),
+            serde_json::json!({ "error_code": "SESSION_LIMIT_REACHED", "max_sessions": max }),
+        )
+    }
+
+    /// Session exceeded its tool call rate (-32003)
+    pub fn tool_rate_limited(retry_after_secs: u64) -> Self {
+        Self::with_data(
+            -32003,
+            format!(
+                "Tool call rate limit exceeded; retry in {}s",
+                retry_after_secs
+            ),
+            serde_json::json!({ "error_code": "RATE_LIMITED", "retry_after_secs": retry_after_secs }),
+        )
+    }
+
+    /// Request body over the configured size limit (-32004)
+    pub fn payload_too_large(max_body_bytes: usize) -> Self {
+        Self::with_data(
+            -32004,
+            format!("Request body exceeds {} bytes", max_body_bytes),
+            serde_json::json!({ "error_code": "PAYLOAD_TOO_LARGE", "max_body_bytes": max_body_bytes }),
+        )
+    }
+
+    /// Request not answered within the configured timeout (-32005)
+    pub fn request_timeout(timeout_ms: u64) -> Self {
+        Self::with_data(
+            -32005,
+            format!("Request timed out after {}ms", timeout_ms),
+            serde_json::json!({ "error_code": "REQUEST_TIMEOUT", "timeout_ms": timeout_ms }),
         )
     }
 }
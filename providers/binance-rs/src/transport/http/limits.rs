@@ -0,0 +1,141 @@
+//! Request body size limit and per-request timeout for the HTTP transport
+//!
+//! A request body larger than `MCP_MAX_BODY_BYTES` is rejected with HTTP 413
+//! and a request still unanswered after `MCP_REQUEST_TIMEOUT_SECS` with HTTP
+//! 408. Both carry a JSON-RPC error body so clients can handle them like any
+//! other transport error.
+
+use axum::{
+    extract::{Request, State},
+    http::StatusCode,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    Router,
+};
+use std::time::Duration;
+use tower_http::limit::RequestBodyLimitLayer;
+use tower_http::timeout::TimeoutLayer;
+
+use super::error::HttpTransportError;
+use super::parse_positive;
+
+/// Default maximum request body size in bytes (1 MiB)
+pub const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Default per-request timeout in seconds
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Body size and timeout limits applied to every route
+///
+/// ## Environment Variables
+///
+/// - `MCP_MAX_BODY_BYTES`: Largest accepted request body (default: 1048576)
+/// - `MCP_REQUEST_TIMEOUT_SECS`: Time allowed to answer a request (default: 30)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    /// Largest accepted request body in bytes
+    pub max_body_bytes: usize,
+
+    /// Time allowed to read the request and produce a response
+    pub timeout: Duration,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            timeout: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+        }
+    }
+}
+
+impl RequestLimits {
+    /// Load limits from `MCP_MAX_BODY_BYTES` and `MCP_REQUEST_TIMEOUT_SECS`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a variable is set but not a positive integer
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_values(
+            std::env::var("MCP_MAX_BODY_BYTES").ok().as_deref(),
+            std::env::var("MCP_REQUEST_TIMEOUT_SECS").ok().as_deref(),
+        )
+    }
+
+    /// Build the limits from raw variable values (`None` = unset, use the default)
+    pub fn from_values(
+        max_body_bytes: Option<&str>,
+        timeout_secs: Option<&str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let defaults = Self::default();
+
+        let max_body_bytes = match max_body_bytes {
+            Some(raw) => parse_positive("MCP_MAX_BODY_BYTES", raw)? as usize,
+            None => defaults.max_body_bytes,
+        };
+        let timeout = match timeout_secs {
+            Some(raw) => Duration::from_secs(parse_positive("MCP_REQUEST_TIMEOUT_SECS", raw)?),
+            None => defaults.timeout,
+        };
+
+        Ok(Self {
+            max_body_bytes,
+            timeout,
+        })
+    }
+
+    /// Wrap `router` with the body limit and timeout layers
+    ///
+    /// The bare 413/408 responses produced by those layers (and by body
+    /// extractors hitting the limit) are replaced with JSON-RPC error responses.
+    pub fn apply<S>(&self, router: Router<S>) -> Router<S>
+    where
+        S: Clone + Send + Sync + 'static,
+    {
+        router
+            .layer(RequestBodyLimitLayer::new(self.max_body_bytes))
+            .layer(TimeoutLayer::with_status_code(
+                StatusCode::REQUEST_TIMEOUT,
+                self.timeout,
+            ))
+            .layer(middleware::from_fn_with_state(*self, jsonrpc_limit_errors))
+    }
+}
+
+/// Replace limit rejections with JSON-RPC error bodies
+async fn jsonrpc_limit_errors(
+    State(limits): State<RequestLimits>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let response = next.run(request).await;
+    match response.status() {
+        StatusCode::PAYLOAD_TOO_LARGE => {
+            HttpTransportError::PayloadTooLarge(limits.max_body_bytes).into_response()
+        }
+        StatusCode::REQUEST_TIMEOUT => {
+            HttpTransportError::RequestTimeout(limits.timeout.as_millis() as u64).into_response()
+        }
+        _ => response,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_values_defaults_and_overrides() {
+        assert_eq!(
+            RequestLimits::from_values(None, None).unwrap(),
+            RequestLimits::default()
+        );
+
+        let limits = RequestLimits::from_values(Some("2048"), Some(" 5 ")).unwrap();
+        assert_eq!(limits.max_body_bytes, 2048);
+        assert_eq!(limits.timeout, Duration::from_secs(5));
+
+        assert!(RequestLimits::from_values(Some("0"), None).is_err());
+        assert!(RequestLimits::from_values(None, Some("soon")).is_err());
+    }
+}
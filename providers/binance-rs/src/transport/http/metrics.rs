@@ -0,0 +1,224 @@
+//! Prometheus metrics for the HTTP transport
+//!
+//! Exposes `GET /metrics` in the Prometheus text exposition format (v0.0.4).
+//! Counters owned by the transport (tool invocations, report latency) live in
+//! [`Metrics`] and are incremented by the request handlers. Values owned by other
+//! components (report cache, orderbook manager) are sampled at scrape time.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Upper bounds (milliseconds) of the report generation latency histogram buckets
+pub const REPORT_LATENCY_BUCKETS_MS: [u64; 8] = [50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000];
+
+/// Tool label recorded for calls naming a tool that isn't listed
+pub const UNKNOWN_TOOL_LABEL: &str = "unknown";
+
+/// Content type for the Prometheus text exposition format
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Transport-level counters shared across handlers via `Arc<Metrics>`
+#[derive(Debug, Default)]
+pub struct Metrics {
+    /// Tool invocations keyed by tool name (or `UNKNOWN_TOOL_LABEL`)
+    tool_invocations: RwLock<HashMap<String, AtomicU64>>,
+
+    /// Cumulative histogram bucket counts (one per `REPORT_LATENCY_BUCKETS_MS` entry)
+    report_latency_buckets: [AtomicU64; REPORT_LATENCY_BUCKETS_MS.len()],
+
+    /// Sum of observed report latencies in milliseconds
+    report_latency_sum_ms: AtomicU64,
+
+    /// Number of observed report generations
+    report_latency_count: AtomicU64,
+}
+
+/// Values sampled from other components at scrape time
+#[derive(Debug, Clone, Default)]
+pub struct ScrapeSnapshot {
+    /// Report cache hits since startup
+    pub report_cache_hits: u64,
+    /// Report cache misses since startup
+    pub report_cache_misses: u64,
+    /// Symbols currently tracked by the orderbook manager
+    pub active_orderbook_symbols: usize,
+    /// Depth WebSocket reconnect attempts since startup
+    pub websocket_reconnects: u64,
+}
+
+impl Metrics {
+    /// Create an empty metrics registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Increment the invocation counter for a tool
+    pub fn record_tool_invocation(&self, tool_name: &str) {
+        {
+            let counters = self.tool_invocations.read().unwrap();
+            if let Some(counter) = counters.get(tool_name) {
+                counter.fetch_add(1, Ordering::Relaxed);
+                return;
+            }
+        }
+
+        let mut counters = self.tool_invocations.write().unwrap();
+        counters
+            .entry(tool_name.to_string())
+            .or_default()
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record one report generation latency observation
+    pub fn observe_report_latency(&self, latency_ms: u64) {
+        for (bucket, upper_bound) in self
+            .report_latency_buckets
+            .iter()
+            .zip(REPORT_LATENCY_BUCKETS_MS)
+        {
+            if latency_ms <= upper_bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.report_latency_sum_ms
+            .fetch_add(latency_ms, Ordering::Relaxed);
+        self.report_latency_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Render all metrics in Prometheus text exposition format
+    pub fn render(&self, snapshot: &ScrapeSnapshot) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP mcp_tool_invocations_total Tool invocations by tool name\n");
+        out.push_str("# TYPE mcp_tool_invocations_total counter\n");
+        {
+            let counters = self.tool_invocations.read().unwrap();
+            let mut names: Vec<&String> = counters.keys().collect();
+            names.sort();
+            for name in names {
+                let _ = writeln!(
+                    out,
+                    "mcp_tool_invocations_total{{tool=\"{}\"}} {}",
+                    escape_label(name),
+                    counters[name].load(Ordering::Relaxed)
+                );
+            }
+        }
+
+        write_single(
+            &mut out,
+            "mcp_report_cache_hits_total",
+            "counter",
+            "Report cache hits",
+            snapshot.report_cache_hits,
+        );
+        write_single(
+            &mut out,
+            "mcp_report_cache_misses_total",
+            "counter",
+            "Report cache misses",
+            snapshot.report_cache_misses,
+        );
+        write_single(
+            &mut out,
+            "mcp_orderbook_active_symbols",
+            "gauge",
+            "Symbols currently tracked by the orderbook manager",
+            snapshot.active_orderbook_symbols as u64,
+        );
+        write_single(
+            &mut out,
+            "mcp_websocket_reconnects_total",
+            "counter",
+            "Depth WebSocket reconnect attempts",
+            snapshot.websocket_reconnects,
+        );
+
+        out.push_str(
+            "# HELP mcp_report_generation_duration_seconds Market report generation latency\n",
+        );
+        out.push_str("# TYPE mcp_report_generation_duration_seconds histogram\n");
+        for (bucket, upper_bound) in self
+            .report_latency_buckets
+            .iter()
+            .zip(REPORT_LATENCY_BUCKETS_MS)
+        {
+            let _ = writeln!(
+                out,
+                "mcp_report_generation_duration_seconds_bucket{{le=\"{}\"}} {}",
+                upper_bound as f64 / 1000.0,
+                bucket.load(Ordering::Relaxed)
+            );
+        }
+        let count = self.report_latency_count.load(Ordering::Relaxed);
+        let _ = writeln!(
+            out,
+            "mcp_report_generation_duration_seconds_bucket{{le=\"+Inf\"}} {}",
+            count
+        );
+        let _ = writeln!(
+            out,
+            "mcp_report_generation_duration_seconds_sum {}",
+            self.report_latency_sum_ms.load(Ordering::Relaxed) as f64 / 1000.0
+        );
+        let _ = writeln!(
+            out,
+            "mcp_report_generation_duration_seconds_count {}",
+            count
+        );
+
+        out
+    }
+}
+
+/// Write a HELP/TYPE header and a single unlabelled sample
+fn write_single(out: &mut String, name: &str, kind: &str, help: &str, value: u64) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    let _ = writeln!(out, "{} {}", name, value);
+}
+
+/// Escape a label value per the exposition format (backslash, quote, newline)
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tool_invocation_counter() {
+        let metrics = Metrics::new();
+        metrics.record_tool_invocation("binance.generate_market_report");
+        metrics.record_tool_invocation("binance.generate_market_report");
+
+        let output = metrics.render(&ScrapeSnapshot::default());
+        assert!(output
+            .contains("mcp_tool_invocations_total{tool=\"binance.generate_market_report\"} 2"));
+    }
+
+    #[test]
+    fn test_latency_histogram_is_cumulative() {
+        let metrics = Metrics::new();
+        metrics.observe_report_latency(80);
+        metrics.observe_report_latency(3_000);
+
+        let output = metrics.render(&ScrapeSnapshot::default());
+        assert!(output.contains("mcp_report_generation_duration_seconds_bucket{le=\"0.05\"} 0"));
+        assert!(output.contains("mcp_report_generation_duration_seconds_bucket{le=\"0.1\"} 1"));
+        assert!(output.contains("mcp_report_generation_duration_seconds_bucket{le=\"5\"} 2"));
+        assert!(output.contains("mcp_report_generation_duration_seconds_bucket{le=\"+Inf\"} 2"));
+        assert!(output.contains("mcp_report_generation_duration_seconds_count 2"));
+    }
+
+    #[test]
+    fn test_escape_label() {
+        assert_eq!(escape_label("a\"b\\c"), "a\\\"b\\\\c");
+    }
+}
@@ -1,20 +1,67 @@
 //! HTTP transport for MCP using Axum
 //!
 //! Provides streamable HTTP transport with JSON-RPC 2.0 protocol.
-//! Session management with 30-minute timeout and 50 concurrent session limit.
+//! Session management with a configurable idle timeout and concurrent session limit
+//! (`MCP_SESSION_TTL_SECS`, `MCP_MAX_SESSIONS`; defaults 30 minutes / 50 sessions).
+//! Prometheus metrics are served at `GET /metrics` and a health probe at `GET /health`.
+//! Every response carries an `X-Correlation-Id` header (see [`correlation`]).
+//! `POST /mcp` responses are MessagePack-encoded for `Accept: application/msgpack`,
+//! and a JSON array body is handled as a JSON-RPC batch.
+//! Request bodies and handling time are bounded (see [`limits`]).
 
+pub mod correlation;
+pub mod cors;
 pub mod error;
 pub mod handler;
 pub mod jsonrpc;
+pub mod limits;
+pub mod metrics;
 pub mod session;
 
-use axum::{routing::post, Router};
+use axum::{
+    middleware,
+    routing::{get, post},
+    Router,
+};
 use std::net::SocketAddr;
 use std::sync::Arc;
-use tower_http::cors::{Any, CorsLayer};
 
-use handler::{handle_jsonrpc, AppState};
-use session::SessionStore;
+use correlation::correlation_id_middleware;
+use cors::CorsConfig;
+use handler::{handle_health, handle_jsonrpc, handle_metrics, AppState};
+use limits::RequestLimits;
+use metrics::Metrics;
+use session::{SessionConfig, SessionStore};
+
+/// Build the Axum router for the HTTP transport with the default (any-origin) CORS policy
+///
+/// Split out from `start_http_server` so tests can drive routes in-process.
+pub fn build_router(state: AppState) -> Router {
+    build_router_with_cors(state, &CorsConfig::default())
+}
+
+/// Build the Axum router with an explicit CORS policy and default request limits
+pub fn build_router_with_cors(state: AppState, cors: &CorsConfig) -> Router {
+    build_router_with_config(state, cors, &RequestLimits::default())
+}
+
+/// Build the Axum router with explicit CORS policy and request limits
+pub fn build_router_with_config(
+    state: AppState,
+    cors: &CorsConfig,
+    limits: &RequestLimits,
+) -> Router {
+    let routes = Router::new()
+        .route("/mcp", post(handle_jsonrpc))
+        .route("/metrics", get(handle_metrics))
+        .route("/health", get(handle_health));
+
+    limits
+        .apply(routes)
+        .layer(middleware::from_fn(correlation_id_middleware))
+        .layer(cors.layer())
+        .with_state(state)
+}
 
 /// Start HTTP server with MCP JSON-RPC endpoint
 ///
@@ -31,12 +78,25 @@ use session::SessionStore;
 ///   - initialize: Create session
 ///   - tools/list: List available tools
 ///   - tools/call: Execute tool
+///   - `Accept: application/msgpack` returns the response as MessagePack
+/// - GET /metrics: Prometheus text exposition
+/// - GET /health: Server health (200 healthy, 503 when all WebSockets are down)
 ///
 /// # CORS
-/// Configured to allow all origins (*) for development.
-/// In production, should be restricted to specific origins.
+/// Origins are restricted to `MCP_CORS_ORIGINS` (comma-separated). When unset,
+/// all origins are allowed and a warning is logged.
+///
+/// # Sessions
+/// Limits come from `MCP_MAX_SESSIONS` and `MCP_SESSION_TTL_SECS`; expired
+/// sessions are evicted by a background sweep. `tools/call` is limited to
+/// `MCP_TOOL_RATE_PER_MIN` calls per session (HTTP 429 past the limit).
+///
+/// # Limits
+/// Bodies over `MCP_MAX_BODY_BYTES` (default 1 MiB) get HTTP 413 and requests
+/// running past `MCP_REQUEST_TIMEOUT_SECS` (default 30s) HTTP 408, both with a
+/// JSON-RPC error body.
 pub async fn start_http_server(
-    port: u16,
+    addr: SocketAddr,
     binance_client: crate::binance::client::BinanceClient,
     #[cfg(feature = "orderbook")] orderbook_manager: Option<
         Arc<crate::orderbook::OrderBookManager>,
@@ -51,13 +111,17 @@ pub async fn start_http_server(
 ) -> Result<(), Box<dyn std::error::Error>> {
     tracing::info!("Initializing HTTP MCP server...");
 
-    // Create session store (max 50 concurrent sessions)
-    let sessions = SessionStore::new(50);
+    // Create session store and evict expired sessions in the background
+    let session_config = SessionConfig::from_env()?;
+    let tool_rate = crate::rate_limit::tool_rate_from_env()?;
+    let sessions = SessionStore::with_config(session_config).with_tool_rate(tool_rate);
+    sessions.spawn_sweeper(session_config.sweep_interval());
 
     // Build application state
     let state = AppState {
         sessions,
         binance_client,
+        metrics: Arc::new(Metrics::new()),
         #[cfg(feature = "orderbook")]
         orderbook_manager,
         #[cfg(feature = "orderbook_analytics")]
@@ -68,66 +132,55 @@ pub async fn start_http_server(
         report_generator,
     };
 
-    // Configure CORS
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
-
     // Build router
-    let app = Router::new()
-        .route("/mcp", post(handle_jsonrpc))
-        .layer(cors)
-        .with_state(state);
-
-    // Bind to address
-    let addr: SocketAddr = format!("0.0.0.0:{}", port).parse()?;
+    let cors = CorsConfig::from_env()?;
+    cors.log_policy();
+    let limits = RequestLimits::from_env()?;
+    let app = build_router_with_config(state, &cors, &limits);
 
     tracing::info!("HTTP MCP server listening on {}", addr);
     tracing::info!("Endpoint: POST http://{}:{}/mcp", addr.ip(), addr.port());
+    tracing::info!("Metrics: GET http://{}:{}/metrics", addr.ip(), addr.port());
+    tracing::info!("Health: GET http://{}:{}/health", addr.ip(), addr.port());
 
-    #[cfg(feature = "orderbook_analytics")]
-    {
-        tracing::info!("  - 21 tools (16 base + 5 analytics)");
-    }
-
-    #[cfg(all(feature = "orderbook", not(feature = "orderbook_analytics")))]
-    {
-        tracing::info!("  - 16 tools (market data, account, orders, orderbook L1/L2)");
-    }
-
-    #[cfg(not(feature = "orderbook"))]
-    {
-        tracing::info!("  - 13 tools (market data, account, orders)");
-    }
+    let tool_count = crate::grpc::capabilities::CapabilityBuilder::new()
+        .build()
+        .map(|capabilities| capabilities.tools.len())
+        .unwrap_or(0);
+    tracing::info!("  - {} tools enabled (JSON-RPC tools/list)", tool_count);
 
     tracing::info!("Session management:");
-    tracing::info!("  - Max concurrent sessions: 50");
-    tracing::info!("  - Session timeout: 30 minutes");
+    tracing::info!(
+        "  - Max concurrent sessions: {}",
+        session_config.max_sessions
+    );
+    tracing::info!(
+        "  - Session timeout: {} seconds",
+        session_config.ttl.as_secs()
+    );
+    match tool_rate {
+        Some(rate) => tracing::info!("  - Tool calls per session: {}/min", rate),
+        None => tracing::info!("  - Tool calls per session: unlimited"),
+    }
     tracing::info!("  - Header: Mcp-Session-Id (UUID)");
+    tracing::info!(
+        "Request limits: {} byte bodies, {}s timeout",
+        limits.max_body_bytes,
+        limits.timeout.as_secs()
+    );
 
     // Start server with graceful shutdown
     let listener = tokio::net::TcpListener::bind(addr).await?;
 
     // Create graceful shutdown handler
-    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
-
-    // Spawn shutdown signal handler
-    tokio::spawn(async move {
-        match tokio::signal::ctrl_c().await {
-            Ok(()) => {
-                tracing::info!("Received shutdown signal (Ctrl+C)");
-                let _ = shutdown_tx.send(());
-            }
-            Err(err) => {
-                tracing::error!("Failed to listen for shutdown signal: {}", err);
-            }
-        }
-    });
+    let (shutdown_tx, mut shutdown_rx) = tokio::sync::broadcast::channel::<()>(1);
+
+    // Spawn shutdown signal handler (Ctrl+C, or SIGTERM on Unix)
+    crate::shutdown::spawn_shutdown_listener(crate::shutdown::shutdown_signal(), shutdown_tx);
 
     axum::serve(listener, app)
-        .with_graceful_shutdown(async {
-            shutdown_rx.await.ok();
+        .with_graceful_shutdown(async move {
+            shutdown_rx.recv().await.ok();
             tracing::info!("Shutting down HTTP server...");
         })
         .await?;
@@ -135,3 +188,503 @@ pub async fn start_http_server(
     tracing::info!("Server stopped");
     Ok(())
 }
+
+/// Parse the raw value of environment variable `name` as a positive integer
+///
+/// Shared by the session and request limit configs so every `MCP_*` count and
+/// duration follows the same rules (surrounding whitespace allowed, zero rejected).
+fn parse_positive(name: &str, raw: &str) -> Result<u64, Box<dyn std::error::Error>> {
+    match raw.trim().parse::<u64>() {
+        Ok(value) if value > 0 => Ok(value),
+        _ => Err(format!("{} must be a positive integer, got '{}'", name, raw).into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::{to_bytes, Body};
+    use axum::http::{Request, StatusCode};
+    use tower::ServiceExt;
+
+    fn test_state() -> AppState {
+        AppState {
+            sessions: SessionStore::new(50),
+            binance_client: crate::binance::client::BinanceClient::new(),
+            metrics: Arc::new(Metrics::new()),
+            #[cfg(feature = "orderbook")]
+            orderbook_manager: None,
+            #[cfg(feature = "orderbook_analytics")]
+            analytics_storage: None,
+            #[cfg(feature = "orderbook_analytics")]
+            trade_storage: None,
+            #[cfg(feature = "orderbook")]
+            report_generator: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_metrics_route_exposition_format() {
+        let state = test_state();
+        state
+            .metrics
+            .record_tool_invocation("binance.generate_market_report");
+        state.metrics.observe_report_latency(120);
+
+        let response = build_router(state)
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let content_type = response.headers()["content-type"].to_str().unwrap();
+        assert!(content_type.starts_with("text/plain; version=0.0.4"));
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+
+        // Every sample line must be `<name>[{labels}] <float>`
+        let mut samples = 0;
+        for line in text
+            .lines()
+            .filter(|l| !l.is_empty() && !l.starts_with('#'))
+        {
+            let (series, value) = line.rsplit_once(' ').expect("sample has a value");
+            let name = series.split('{').next().unwrap();
+            assert!(
+                name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'),
+                "invalid metric name: {}",
+                name
+            );
+            value.parse::<f64>().expect("sample value is numeric");
+            samples += 1;
+        }
+        assert!(samples > 0);
+        assert!(
+            text.contains("mcp_tool_invocations_total{tool=\"binance.generate_market_report\"} 1")
+        );
+        assert!(text.contains("mcp_report_generation_duration_seconds_count 1"));
+    }
+
+    #[tokio::test]
+    async fn test_correlation_id_is_echoed_or_generated() {
+        let router = build_router(test_state());
+
+        let response = router
+            .clone()
+            .oneshot(
+                Request::get("/health")
+                    .header("x-correlation-id", "client-req-123")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["x-correlation-id"], "client-req-123");
+
+        let response = router
+            .oneshot(Request::get("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let generated = response.headers()["x-correlation-id"].to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(generated).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_cors_allowlist_rejects_other_origins() {
+        let cors = CorsConfig::from_value(Some("https://app.example.com")).unwrap();
+        let router = build_router_with_cors(test_state(), &cors);
+
+        let preflight = |origin: &'static str| {
+            Request::builder()
+                .method("OPTIONS")
+                .uri("/mcp")
+                .header("origin", origin)
+                .header("access-control-request-method", "POST")
+                .header(
+                    "access-control-request-headers",
+                    "content-type,mcp-session-id",
+                )
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let allowed = router
+            .clone()
+            .oneshot(preflight("https://app.example.com"))
+            .await
+            .unwrap();
+        assert_eq!(
+            allowed.headers()["access-control-allow-origin"],
+            "https://app.example.com"
+        );
+        let allowed_methods = allowed.headers()["access-control-allow-methods"]
+            .to_str()
+            .unwrap();
+        assert!(allowed_methods.contains("POST"));
+        assert!(!allowed_methods.contains("DELETE"));
+
+        let denied = router
+            .oneshot(preflight("https://evil.example.com"))
+            .await
+            .unwrap();
+        assert!(denied
+            .headers()
+            .get("access-control-allow-origin")
+            .is_none());
+    }
+
+    async fn post_jsonrpc(
+        state: &AppState,
+        body: serde_json::Value,
+        session_id: Option<&str>,
+    ) -> (StatusCode, serde_json::Value) {
+        let mut request = Request::post("/mcp").header("content-type", "application/json");
+        if let Some(session_id) = session_id {
+            request = request.header("mcp-session-id", session_id);
+        }
+        let response = build_router(state.clone())
+            .oneshot(request.body(Body::from(body.to_string())).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_msgpack_accept_header_round_trips_response() {
+        let state = test_state();
+        let (_, init) = post_jsonrpc(
+            &state,
+            serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+            None,
+        )
+        .await;
+        let session_id = init["result"]["sessionId"].as_str().unwrap().to_string();
+        let tools_list = serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"});
+
+        let response = build_router(state.clone())
+            .oneshot(
+                Request::post("/mcp")
+                    .header("content-type", "application/json")
+                    .header("accept", "application/msgpack")
+                    .header("mcp-session-id", &session_id)
+                    .body(Body::from(tools_list.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()["content-type"],
+            handler::MSGPACK_CONTENT_TYPE
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let decoded: jsonrpc::JsonRpcResponse = rmp_serde::from_slice(&body).unwrap();
+
+        // Same response as the JSON default, in fewer bytes
+        let (_, json) = post_jsonrpc(&state, tools_list, Some(&session_id)).await;
+        assert_eq!(serde_json::to_value(&decoded).unwrap(), json);
+        assert!(body.len() < json.to_string().len());
+    }
+
+    #[cfg(feature = "orderbook")]
+    #[tokio::test]
+    async fn test_tools_list_includes_feature_gated_tools() {
+        let state = test_state();
+        let (_, init) = post_jsonrpc(
+            &state,
+            serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+            None,
+        )
+        .await;
+        let session_id = init["result"]["sessionId"].as_str().unwrap().to_string();
+
+        let (status, body) = post_jsonrpc(
+            &state,
+            serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+            Some(&session_id),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+        let tools = body["result"]["tools"].as_array().unwrap();
+        let report = tools
+            .iter()
+            .find(|t| t["name"] == "binance.generate_market_report")
+            .expect("report tool listed with orderbook feature");
+        assert_eq!(report["inputSchema"]["type"], "object");
+        assert!(report["inputSchema"]["properties"]["symbol"].is_object());
+        assert!(tools
+            .iter()
+            .any(|t| t["name"] == "binance.get_slippage_estimate"));
+        assert!(!tools.iter().any(|t| t["name"] == "binance.orderbook_l1"));
+    }
+
+    async fn get_health(state: AppState) -> (StatusCode, serde_json::Value) {
+        let response = build_router(state)
+            .oneshot(Request::get("/health").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_session_limit_reached_keeps_existing_sessions() {
+        let mut state = test_state();
+        state.sessions = SessionStore::new(2);
+        let initialize =
+            serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}});
+
+        let mut session_ids = Vec::new();
+        for _ in 0..2 {
+            let (status, body) = post_jsonrpc(&state, initialize.clone(), None).await;
+            assert_eq!(status, StatusCode::OK);
+            session_ids.push(body["result"]["sessionId"].as_str().unwrap().to_string());
+        }
+
+        let (status, body) = post_jsonrpc(&state, initialize, None).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body["error"]["code"], -32000);
+        assert_eq!(body["error"]["data"]["error_code"], "SESSION_LIMIT_REACHED");
+        assert_eq!(body["error"]["data"]["max_sessions"], 2);
+
+        for session_id in &session_ids {
+            let (status, _) = post_jsonrpc(
+                &state,
+                serde_json::json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}),
+                Some(session_id),
+            )
+            .await;
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        let (_, health) = get_health(state).await;
+        assert_eq!(health["active_sessions"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_tool_calls_past_rate_limit_rejected() {
+        let mut state = test_state();
+        state.sessions = SessionStore::new(50).with_tool_rate(Some(1));
+        let (_, init) = post_jsonrpc(
+            &state,
+            serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+            None,
+        )
+        .await;
+        let session_id = init["result"]["sessionId"].as_str().unwrap().to_string();
+        // Unknown tool: answered locally, but still spends a call
+        let call = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "tools/call",
+            "params": {"name": "binance.unknown_tool", "arguments": {}}
+        });
+
+        let (status, _) = post_jsonrpc(&state, call.clone(), Some(&session_id)).await;
+        assert_ne!(status, StatusCode::TOO_MANY_REQUESTS);
+
+        let (status, body) = post_jsonrpc(&state, call, Some(&session_id)).await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(body["error"]["code"], -32003);
+        assert_eq!(body["error"]["data"]["error_code"], "RATE_LIMITED");
+        assert_eq!(body["error"]["data"]["retry_after_secs"], 60);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_tools_counted_under_one_label() {
+        let state = test_state();
+        let (_, init) = post_jsonrpc(
+            &state,
+            serde_json::json!({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}}),
+            None,
+        )
+        .await;
+        let session_id = init["result"]["sessionId"].as_str().unwrap().to_string();
+        for name in ["binance.made_up", "binance.also_made_up"] {
+            let call = serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 2,
+                "method": "tools/call",
+                "params": {"name": name, "arguments": {}}
+            });
+            post_jsonrpc(&state, call, Some(&session_id)).await;
+        }
+
+        let response = build_router(state)
+            .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let text = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(text.contains("mcp_tool_invocations_total{tool=\"unknown\"} 2"));
+        assert!(!text.contains("made_up"));
+    }
+
+    #[tokio::test]
+    async fn test_batch_answers_in_order_and_skips_notifications() {
+        let state = test_state();
+        // No session header: tools/list uses the session created by initialize
+        let batch = serde_json::json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}},
+            {"jsonrpc": "2.0", "method": "notifications/initialized"},
+            {"jsonrpc": "2.0", "id": "list", "method": "tools/list"}
+        ]);
+
+        let (status, body) = post_jsonrpc(&state, batch, None).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let responses = body.as_array().unwrap();
+        assert_eq!(responses.len(), 2);
+        assert_eq!(responses[0]["id"], 1);
+        assert!(responses[0]["result"]["sessionId"].is_string());
+        assert_eq!(responses[1]["id"], "list");
+        assert!(responses[1]["result"]["tools"].is_array());
+    }
+
+    #[tokio::test]
+    async fn test_batch_entry_errors_do_not_fail_batch() {
+        let state = test_state();
+        let batch = serde_json::json!([
+            {"jsonrpc": "2.0", "id": 1, "method": "tools/list"},
+            {"id": 2},
+            {"jsonrpc": "2.0", "id": 3, "method": "resources/list"}
+        ]);
+
+        let (status, body) = post_jsonrpc(&state, batch, None).await;
+
+        assert_eq!(status, StatusCode::OK);
+        let responses = body.as_array().unwrap();
+        assert_eq!(responses.len(), 3);
+        assert_eq!(responses[0]["error"]["code"], -32002);
+        assert_eq!(responses[1]["error"]["code"], -32600);
+        assert_eq!(responses[2]["id"], 3);
+        assert_eq!(responses[2]["error"]["code"], -32601);
+
+        let (status, _) = post_jsonrpc(&state, serde_json::json!([]), None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_health_route_reports_open_circuit() {
+        use crate::binance::circuit::UpstreamFailure;
+
+        let state = test_state();
+        for _ in 0..5 {
+            state
+                .binance_client
+                .circuit
+                .record_failure(UpstreamFailure::Unavailable, None);
+        }
+
+        let (status, body) = get_health(state).await;
+
+        // Cached data can still be served, so the probe stays ready
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "degraded");
+        assert_eq!(body["upstream"]["state"], "open");
+        assert_eq!(body["upstream"]["consecutive_failures"], 5);
+    }
+
+    #[cfg(feature = "orderbook")]
+    fn state_with_manager() -> (AppState, Arc<crate::orderbook::OrderBookManager>) {
+        let manager = Arc::new(crate::orderbook::OrderBookManager::new(Arc::new(
+            crate::binance::client::BinanceClient::new(),
+        )));
+        let mut state = test_state();
+        state.orderbook_manager = Some(manager.clone());
+        (state, manager)
+    }
+
+    #[cfg(feature = "orderbook")]
+    #[tokio::test]
+    async fn test_health_route_ok_without_subscriptions() {
+        let (state, _manager) = state_with_manager();
+
+        let (status, body) = get_health(state).await;
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["status"], "ok");
+        assert_eq!(body["orderbook"]["orderbook_symbols_active"], 0);
+        assert!(body["uptime_secs"].is_u64());
+    }
+
+    #[cfg(feature = "orderbook")]
+    #[tokio::test]
+    async fn test_health_route_unavailable_when_all_websockets_down() {
+        let (state, manager) = state_with_manager();
+        manager.insert_test_state("BTCUSDT", false).await;
+        manager.insert_test_state("ETHUSDT", false).await;
+
+        let (status, body) = get_health(state).await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["status"], "error");
+        assert_eq!(body["websocket_connected"], false);
+        assert_eq!(body["reason"], "All WebSocket connections down");
+    }
+
+    #[tokio::test]
+    async fn test_oversized_body_returns_jsonrpc_413() {
+        let limits = RequestLimits {
+            max_body_bytes: 256,
+            ..Default::default()
+        };
+        let router = build_router_with_config(test_state(), &CorsConfig::default(), &limits);
+        let padding = "x".repeat(1024);
+        let body = serde_json::json!({
+            "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"padding": padding}
+        });
+
+        let response = router
+            .oneshot(
+                Request::post("/mcp")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(response.headers().contains_key("x-correlation-id"));
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["jsonrpc"], "2.0");
+        assert_eq!(body["error"]["code"], -32004);
+        assert_eq!(body["error"]["data"]["max_body_bytes"], 256);
+    }
+
+    #[tokio::test]
+    async fn test_slow_handler_returns_jsonrpc_408() {
+        let limits = RequestLimits {
+            timeout: std::time::Duration::from_millis(50),
+            ..Default::default()
+        };
+        let router = limits.apply(Router::new().route(
+            "/slow",
+            post(|| async {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+                "done"
+            }),
+        ));
+
+        let response = router
+            .oneshot(Request::post("/slow").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], -32005);
+        assert_eq!(body["error"]["data"]["error_code"], "REQUEST_TIMEOUT");
+        assert_eq!(body["error"]["data"]["timeout_ms"], 50);
+    }
+}
@@ -1,16 +1,105 @@
 //! HTTP session management for MCP transport
 //!
 //! Manages stateful HTTP sessions with:
-//! - 30-minute idle timeout
-//! - 50 concurrent session limit
+//! - Idle timeout (default 30 minutes, `MCP_SESSION_TTL_SECS`)
+//! - Concurrent session limit (default 50, `MCP_MAX_SESSIONS`)
 //! - UUID-based session identification
-//! - Automatic cleanup of expired sessions
+//! - Per-session tool call rate limit (default 60/min, `MCP_TOOL_RATE_PER_MIN`)
+//! - Periodic sweep of expired sessions (see [`SessionStore::spawn_sweeper`])
 
+use super::parse_positive;
+use crate::rate_limit::TokenBucket;
 use chrono::{DateTime, Duration, Utc};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
 use uuid::Uuid;
 
+/// Default maximum number of concurrent sessions
+pub const DEFAULT_MAX_SESSIONS: usize = 50;
+
+/// Default idle timeout in seconds (30 minutes)
+pub const DEFAULT_SESSION_TTL_SECS: u64 = 30 * 60;
+
+/// Upper bound for `MCP_SESSION_TTL_SECS` (7 days)
+const MAX_SESSION_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Longest interval between expiry sweeps
+const MAX_SWEEP_INTERVAL_SECS: u64 = 60;
+
+/// Session limits for the HTTP transport
+///
+/// ## Environment Variables
+///
+/// - `MCP_MAX_SESSIONS`: Maximum concurrent sessions (default: 50)
+/// - `MCP_SESSION_TTL_SECS`: Idle timeout in seconds (default: 1800, max: 604800)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SessionConfig {
+    /// Maximum concurrent sessions
+    pub max_sessions: usize,
+
+    /// Idle timeout; each validated request extends the session by this much
+    pub ttl: std::time::Duration,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            max_sessions: DEFAULT_MAX_SESSIONS,
+            ttl: std::time::Duration::from_secs(DEFAULT_SESSION_TTL_SECS),
+        }
+    }
+}
+
+impl SessionConfig {
+    /// Load session limits from `MCP_MAX_SESSIONS` and `MCP_SESSION_TTL_SECS`
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a variable is set but not a positive integer, or the TTL
+    /// exceeds 7 days
+    pub fn from_env() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::from_values(
+            std::env::var("MCP_MAX_SESSIONS").ok().as_deref(),
+            std::env::var("MCP_SESSION_TTL_SECS").ok().as_deref(),
+        )
+    }
+
+    /// Build the config from raw variable values (`None` = unset, use the default)
+    pub fn from_values(
+        max_sessions: Option<&str>,
+        ttl_secs: Option<&str>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let defaults = Self::default();
+
+        let max_sessions = match max_sessions {
+            Some(raw) => parse_positive("MCP_MAX_SESSIONS", raw)? as usize,
+            None => defaults.max_sessions,
+        };
+        let ttl = match ttl_secs {
+            Some(raw) => {
+                let secs = parse_positive("MCP_SESSION_TTL_SECS", raw)?;
+                if secs > MAX_SESSION_TTL_SECS {
+                    return Err(format!(
+                        "MCP_SESSION_TTL_SECS must be at most {}, got {}",
+                        MAX_SESSION_TTL_SECS, secs
+                    )
+                    .into());
+                }
+                std::time::Duration::from_secs(secs)
+            }
+            None => defaults.ttl,
+        };
+
+        Ok(Self { max_sessions, ttl })
+    }
+
+    /// How often the background sweeper runs: the TTL, capped at one minute
+    pub fn sweep_interval(&self) -> std::time::Duration {
+        self.ttl
+            .min(std::time::Duration::from_secs(MAX_SWEEP_INTERVAL_SECS))
+    }
+}
+
 /// Session metadata for HTTP transport
 #[derive(Debug, Clone)]
 pub struct StreamableHttpSession {
@@ -26,22 +115,40 @@ pub struct StreamableHttpSession {
     /// Last activity timestamp (updated on each request)
     pub last_activity: DateTime<Utc>,
 
-    /// Session expiration timestamp (created_at + 30 minutes)
+    /// Session expiration timestamp (last_activity + idle timeout)
     pub expires_at: DateTime<Utc>,
+
+    /// Idle timeout applied on every touch
+    pub idle_timeout: Duration,
+
+    /// Tool call budget (None = unlimited)
+    pub tool_bucket: Option<TokenBucket>,
 }
 
 impl StreamableHttpSession {
-    /// Create a new session
+    /// Create a new session with the default 30-minute idle timeout
     pub fn new(client_metadata: HashMap<String, String>) -> Self {
+        Self::with_idle_timeout(
+            client_metadata,
+            Duration::seconds(DEFAULT_SESSION_TTL_SECS as i64),
+        )
+    }
+
+    /// Create a new session that expires after `idle_timeout` without activity
+    pub fn with_idle_timeout(
+        client_metadata: HashMap<String, String>,
+        idle_timeout: Duration,
+    ) -> Self {
         let now = Utc::now();
-        let expires_at = now + Duration::minutes(30);
 
         Self {
             session_id: Uuid::new_v4(),
             client_metadata,
             created_at: now,
             last_activity: now,
-            expires_at,
+            expires_at: now + idle_timeout,
+            idle_timeout,
+            tool_bucket: None,
         }
     }
 
@@ -54,7 +161,7 @@ impl StreamableHttpSession {
     pub fn touch(&mut self) {
         let now = Utc::now();
         self.last_activity = now;
-        self.expires_at = now + Duration::minutes(30);
+        self.expires_at = now + self.idle_timeout;
     }
 }
 
@@ -66,17 +173,40 @@ pub struct SessionStore {
 
     /// Maximum concurrent sessions (default: 50)
     max_sessions: usize,
+
+    /// Idle timeout for new sessions (default: 30 minutes)
+    ttl: Duration,
+
+    /// Tool calls per minute per session (None = unlimited)
+    tool_rate_per_min: Option<u32>,
 }
 
 impl SessionStore {
-    /// Create a new session store
+    /// Create a new session store with the default 30-minute idle timeout
     pub fn new(max_sessions: usize) -> Self {
+        Self::with_config(SessionConfig {
+            max_sessions,
+            ..SessionConfig::default()
+        })
+    }
+
+    /// Create a session store from explicit limits
+    pub fn with_config(config: SessionConfig) -> Self {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
-            max_sessions,
+            max_sessions: config.max_sessions,
+            ttl: Duration::from_std(config.ttl)
+                .unwrap_or_else(|_| Duration::seconds(DEFAULT_SESSION_TTL_SECS as i64)),
+            tool_rate_per_min: None,
         }
     }
 
+    /// Limit each new session to `rate_per_min` tool calls per minute (None = unlimited)
+    pub fn with_tool_rate(mut self, rate_per_min: Option<u32>) -> Self {
+        self.tool_rate_per_min = rate_per_min;
+        self
+    }
+
     /// Create a new session and store it
     ///
     /// # Arguments
@@ -86,19 +216,25 @@ impl SessionStore {
     /// Session ID if successful, or error if session limit reached
     ///
     /// # Errors
-    /// - `SessionLimitExceeded` if max_sessions reached (default: 50)
+    /// - `SessionLimitExceeded` if max_sessions live sessions exist (default: 50);
+    ///   existing sessions are never replaced
     pub fn create_session(
         &self,
         client_metadata: HashMap<String, String>,
     ) -> Result<Uuid, SessionError> {
         let mut sessions = self.sessions.write().unwrap();
 
-        // Check session limit
+        // Expired sessions must not hold slots until the next sweep
+        if sessions.len() >= self.max_sessions {
+            let now = Utc::now();
+            sessions.retain(|_, session| session.expires_at >= now);
+        }
         if sessions.len() >= self.max_sessions {
             return Err(SessionError::SessionLimitExceeded(self.max_sessions));
         }
 
-        let session = StreamableHttpSession::new(client_metadata);
+        let mut session = StreamableHttpSession::with_idle_timeout(client_metadata, self.ttl);
+        session.tool_bucket = self.tool_rate_per_min.map(TokenBucket::per_minute);
         let session_id = session.session_id;
 
         sessions.insert(session_id, session);
@@ -116,7 +252,7 @@ impl SessionStore {
     ///
     /// # Errors
     /// - `SessionNotFound` if session ID doesn't exist
-    /// - `SessionExpired` if session exceeded 30-minute timeout
+    /// - `SessionExpired` if session exceeded its idle timeout
     pub fn validate_session(&self, session_id: Uuid) -> Result<(), SessionError> {
         let mut sessions = self.sessions.write().unwrap();
 
@@ -134,17 +270,36 @@ impl SessionStore {
         }
     }
 
+    /// Spend one tool call from the session's budget
+    ///
+    /// # Errors
+    /// - `SessionNotFound` if session ID doesn't exist
+    /// - `ToolRateLimited` if the session used up its tool calls for now
+    pub fn acquire_tool_call(&self, session_id: Uuid) -> Result<(), SessionError> {
+        let mut sessions = self.sessions.write().unwrap();
+        let session = sessions
+            .get_mut(&session_id)
+            .ok_or(SessionError::SessionNotFound(session_id))?;
+
+        match session.tool_bucket.as_mut().map(TokenBucket::try_acquire) {
+            Some(Err(retry_after)) => Err(SessionError::ToolRateLimited(
+                retry_after.as_secs_f64().ceil() as u64,
+            )),
+            _ => Ok(()),
+        }
+    }
+
     /// Get session metadata (read-only)
     pub fn get_session(&self, session_id: Uuid) -> Option<StreamableHttpSession> {
         let sessions = self.sessions.read().unwrap();
         sessions.get(&session_id).cloned()
     }
 
-    /// Remove expired sessions (should be called periodically)
+    /// Remove expired sessions (run periodically by [`SessionStore::spawn_sweeper`])
     ///
     /// # Returns
     /// Number of sessions removed
-    pub fn cleanup_expired_sessions(&self) -> usize {
+    pub fn sweep_expired(&self) -> usize {
         let mut sessions = self.sessions.write().unwrap();
         let now = Utc::now();
 
@@ -166,11 +321,36 @@ impl SessionStore {
         let sessions = self.sessions.read().unwrap();
         sessions.len()
     }
+
+    /// Maximum concurrent sessions
+    pub fn max_sessions(&self) -> usize {
+        self.max_sessions
+    }
+
+    /// Spawn a background task calling [`SessionStore::sweep_expired`] every `interval`
+    pub fn spawn_sweeper(&self, interval: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        let store = self.clone();
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let removed = store.sweep_expired();
+                if removed > 0 {
+                    tracing::debug!(
+                        removed,
+                        active = store.session_count(),
+                        "Swept expired HTTP sessions"
+                    );
+                }
+            }
+        })
+    }
 }
 
 impl Default for SessionStore {
     fn default() -> Self {
-        Self::new(50)
+        Self::with_config(SessionConfig::default())
     }
 }
 
@@ -188,6 +368,9 @@ pub enum SessionError {
 
     #[error("Invalid session ID format")]
     InvalidSessionId,
+
+    #[error("Tool call rate limit exceeded; retry in {0}s")]
+    ToolRateLimited(u64),
 }
 
 #[cfg(test)]
@@ -249,4 +432,95 @@ mod tests {
         let new_expiry = store.get_session(session_id).unwrap().expires_at;
         assert!(new_expiry > original_expiry);
     }
+
+    #[test]
+    fn test_sweep_expired_removes_only_expired_sessions() {
+        let store = SessionStore::with_config(SessionConfig {
+            max_sessions: 10,
+            ttl: std::time::Duration::from_millis(20),
+        });
+        store.create_session(HashMap::new()).unwrap();
+        store.create_session(HashMap::new()).unwrap();
+
+        assert_eq!(store.sweep_expired(), 0);
+        std::thread::sleep(std::time::Duration::from_millis(40));
+
+        let live = SessionStore::new(10);
+        let live_id = live.create_session(HashMap::new()).unwrap();
+        assert_eq!(live.sweep_expired(), 0);
+        assert!(live.get_session(live_id).is_some());
+
+        assert_eq!(store.sweep_expired(), 2);
+        assert_eq!(store.session_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_sweeper_task_evicts_expired_sessions() {
+        let store = SessionStore::with_config(SessionConfig {
+            max_sessions: 10,
+            ttl: std::time::Duration::from_millis(20),
+        });
+        let session_id = store.create_session(HashMap::new()).unwrap();
+
+        let sweeper = store.spawn_sweeper(std::time::Duration::from_millis(10));
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        sweeper.abort();
+
+        assert_eq!(store.session_count(), 0);
+        assert!(store.get_session(session_id).is_none());
+    }
+
+    #[test]
+    fn test_expired_sessions_free_slots_at_cap() {
+        let store = SessionStore::with_config(SessionConfig {
+            max_sessions: 1,
+            ttl: std::time::Duration::from_millis(20),
+        });
+        store.create_session(HashMap::new()).unwrap();
+        assert!(store.create_session(HashMap::new()).is_err());
+
+        std::thread::sleep(std::time::Duration::from_millis(40));
+        assert!(store.create_session(HashMap::new()).is_ok());
+        assert_eq!(store.session_count(), 1);
+    }
+
+    #[test]
+    fn test_tool_calls_limited_per_session() {
+        let store = SessionStore::new(10).with_tool_rate(Some(2));
+        let limited = store.create_session(HashMap::new()).unwrap();
+        let other = store.create_session(HashMap::new()).unwrap();
+
+        store.acquire_tool_call(limited).unwrap();
+        store.acquire_tool_call(limited).unwrap();
+        assert!(matches!(
+            store.acquire_tool_call(limited),
+            Err(SessionError::ToolRateLimited(30))
+        ));
+
+        // Budgets are per session
+        assert!(store.acquire_tool_call(other).is_ok());
+
+        let unlimited = SessionStore::new(10);
+        let session_id = unlimited.create_session(HashMap::new()).unwrap();
+        for _ in 0..100 {
+            unlimited.acquire_tool_call(session_id).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_session_config_from_values() {
+        assert_eq!(
+            SessionConfig::from_values(None, None).unwrap(),
+            SessionConfig::default()
+        );
+
+        let config = SessionConfig::from_values(Some("5"), Some("90")).unwrap();
+        assert_eq!(config.max_sessions, 5);
+        assert_eq!(config.ttl, std::time::Duration::from_secs(90));
+        assert_eq!(config.sweep_interval(), std::time::Duration::from_secs(60));
+
+        assert!(SessionConfig::from_values(Some("0"), None).is_err());
+        assert!(SessionConfig::from_values(None, Some("soon")).is_err());
+        assert!(SessionConfig::from_values(None, Some("604801")).is_err());
+    }
 }
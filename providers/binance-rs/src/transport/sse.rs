@@ -7,3 +7,101 @@ pub use rmcp::transport::sse_server::{SseServer, SseServerConfig};
 
 // Re-export CancellationToken for convenience (required by SseServerConfig)
 pub use tokio_util::sync::CancellationToken;
+
+#[cfg(feature = "orderbook")]
+use crate::orderbook::OrderBookManager;
+#[cfg(feature = "orderbook")]
+use std::sync::Arc;
+
+/// Pre-subscribe `symbols` in the background so SSE clients start on warm books
+///
+/// Symbols are subscribed one at a time (each costs a REST snapshot). Failures,
+/// including the manager's symbol limit, are logged and skipped. The task stops
+/// as soon as `ct` is cancelled, also in the middle of a subscription.
+///
+/// # Returns
+/// Handle resolving to the symbols that were subscribed
+#[cfg(feature = "orderbook")]
+pub fn spawn_presubscribe(
+    manager: Arc<OrderBookManager>,
+    symbols: Vec<String>,
+    ct: CancellationToken,
+) -> tokio::task::JoinHandle<Vec<String>> {
+    tokio::spawn(async move {
+        let mut subscribed = Vec::new();
+        for symbol in symbols {
+            tokio::select! {
+                biased;
+                _ = ct.cancelled() => {
+                    tracing::info!("SSE pre-subscription cancelled");
+                    break;
+                }
+                result = manager.subscribe(&symbol) => match result {
+                    Ok(()) => {
+                        tracing::info!("Pre-subscribed to {} for SSE clients", symbol);
+                        subscribed.push(symbol);
+                    }
+                    Err(e) => tracing::error!("Failed to pre-subscribe to {}: {}", symbol, e),
+                },
+            }
+        }
+        subscribed
+    })
+}
+
+#[cfg(all(test, feature = "orderbook"))]
+mod tests {
+    use super::*;
+    use crate::config::analytics::parse_symbol_list;
+    use crate::orderbook::manager::ManagerError;
+    use crate::orderbook::source::SnapshotSource;
+    use crate::orderbook::OrderBook;
+    use std::sync::Mutex;
+
+    /// Non-live source recording every symbol it is asked for
+    #[derive(Default)]
+    struct RecordingSource {
+        fetched: Mutex<Vec<String>>,
+    }
+
+    #[tonic::async_trait]
+    impl SnapshotSource for RecordingSource {
+        async fn fetch_snapshot(&self, symbol: &str) -> Result<OrderBook, ManagerError> {
+            self.fetched.lock().unwrap().push(symbol.to_string());
+            Ok(OrderBook::new(symbol.to_string()))
+        }
+
+        fn is_live(&self) -> bool {
+            false
+        }
+    }
+
+    #[tokio::test]
+    async fn test_parsed_symbols_are_subscribed() {
+        let source = Arc::new(RecordingSource::default());
+        let manager = Arc::new(OrderBookManager::with_source(source.clone()));
+        let symbols = parse_symbol_list("btcusdt, ETHUSDT,,btcusdt,solusdt").unwrap();
+
+        let subscribed = spawn_presubscribe(manager, symbols, CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(subscribed, vec!["BTCUSDT", "ETHUSDT", "SOLUSDT"]);
+        assert_eq!(*source.fetched.lock().unwrap(), subscribed);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_token_stops_presubscription() {
+        let source = Arc::new(RecordingSource::default());
+        let manager = Arc::new(OrderBookManager::with_source(source.clone()));
+        let ct = CancellationToken::new();
+        ct.cancel();
+
+        let subscribed = spawn_presubscribe(manager, vec!["BTCUSDT".to_string()], ct)
+            .await
+            .unwrap();
+
+        assert!(subscribed.is_empty());
+        assert!(source.fetched.lock().unwrap().is_empty());
+    }
+}
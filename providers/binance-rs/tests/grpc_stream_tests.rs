@@ -0,0 +1,84 @@
+// Integration tests for the gRPC Stream RPC event forwarding
+
+use binance_provider::binance::websocket::TickerUpdate;
+use binance_provider::grpc::stream::{forward_events, StreamEventKind, EVENT_SOURCE};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::broadcast;
+use tokio_stream::StreamExt;
+
+fn sample_ticker() -> TickerUpdate {
+    serde_json::from_str(
+        r#"{
+            "e": "24hrTicker",
+            "E": 1699999999123,
+            "s": "BTCUSDT",
+            "p": "100.00",
+            "P": "0.50",
+            "w": "45000.50",
+            "c": "45100.00",
+            "Q": "0.001",
+            "o": "45000.00",
+            "h": "45200.00",
+            "l": "44900.00",
+            "v": "1000.5",
+            "q": "45000000.00"
+        }"#,
+    )
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_stream_receives_cloud_event_from_broadcast() {
+    let (tx, rx) = broadcast::channel(16);
+    let mut stream = forward_events(
+        rx,
+        "btcusdt@ticker".to_string(),
+        StreamEventKind::Ticker,
+        || {},
+    );
+
+    tx.send(sample_ticker()).unwrap();
+
+    let event = tokio::time::timeout(Duration::from_secs(1), stream.next())
+        .await
+        .expect("event within timeout")
+        .expect("stream open")
+        .expect("event is Ok");
+
+    assert_eq!(event.specversion, "1.0");
+    assert_eq!(event.source, EVENT_SOURCE);
+    assert_eq!(event.r#type, "binance.ticker.updated");
+    assert_eq!(event.id, "btcusdt@ticker-1");
+
+    let data: serde_json::Value = serde_json::from_slice(&event.data.unwrap().value).unwrap();
+    assert_eq!(data["s"], "BTCUSDT");
+    assert_eq!(data["c"], "45100.00");
+}
+
+#[tokio::test]
+async fn test_stream_releases_subscription_on_client_drop() {
+    let (tx, rx) = broadcast::channel::<TickerUpdate>(16);
+    let released = Arc::new(AtomicBool::new(false));
+    let released_flag = released.clone();
+
+    let stream = forward_events(
+        rx,
+        "btcusdt@ticker".to_string(),
+        StreamEventKind::Ticker,
+        move || released_flag.store(true, Ordering::SeqCst),
+    );
+    assert_eq!(tx.receiver_count(), 1);
+
+    drop(stream);
+
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while !released.load(Ordering::SeqCst) {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("forwarder released subscription");
+    assert_eq!(tx.receiver_count(), 0);
+}
@@ -1,6 +1,7 @@
 // Unit tests for report caching functionality
 
-use binance_provider::report::{MarketReport, ReportCache};
+use binance_provider::report::{MarketReport, ReportCache, ReportOptions};
+use binance_provider::timestamp::Timestamp;
 use std::thread;
 use std::time::Duration;
 
@@ -13,9 +14,11 @@ mod tests {
             markdown_content: format!("# Report for {}", symbol),
             symbol: symbol.to_string(),
             generated_at: 1729780000000,
+            generated_at_ts: Timestamp::from_millis(1729780000000),
             data_age_ms: 100,
             failed_sections: vec![],
             generation_time_ms: 245,
+            partial: false,
         }
     }
 
@@ -96,6 +99,25 @@ mod tests {
         assert!(cache.get(&key3).is_some(), "ETHUSDT should remain cached");
     }
 
+    #[test]
+    fn test_cache_invalidate_ignores_symbol_case() {
+        let cache = ReportCache::new(60);
+        let options = ReportOptions::default();
+
+        let lowercase_key = format!("btcusdt:{}", options.to_cache_key_suffix());
+        cache.set(lowercase_key.clone(), create_test_report("BTCUSDT"));
+        assert!(cache.get(&options.to_cache_key("BTCUSDT")).is_some());
+        assert!(options.to_cache_key("btcusdt").starts_with("BTCUSDT:"));
+
+        cache.invalidate("BTCUSDT");
+
+        assert!(
+            cache.get(&lowercase_key).is_none(),
+            "Lowercase-cached report should be invalidated"
+        );
+        assert!(cache.get(&options.to_cache_key("BTCUSDT")).is_none());
+    }
+
     #[test]
     fn test_cache_miss() {
         let cache = ReportCache::new(60);
@@ -174,9 +196,11 @@ mod tests {
             markdown_content: "# Report v1".to_string(),
             symbol: "BTCUSDT".to_string(),
             generated_at: 1000,
+            generated_at_ts: Timestamp::from_millis(1000),
             data_age_ms: 100,
             failed_sections: vec![],
             generation_time_ms: 200,
+            partial: false,
         };
         cache.set(cache_key.clone(), report1);
 
@@ -185,9 +209,11 @@ mod tests {
             markdown_content: "# Report v2".to_string(),
             symbol: "BTCUSDT".to_string(),
             generated_at: 2000,
+            generated_at_ts: Timestamp::from_millis(2000),
             data_age_ms: 50,
             failed_sections: vec![],
             generation_time_ms: 150,
+            partial: false,
         };
         cache.set(cache_key.clone(), report2);
 
@@ -197,4 +223,24 @@ mod tests {
         assert!(cached.markdown_content.contains("v2"));
         assert_eq!(cached.generation_time_ms, 150);
     }
+
+    #[test]
+    fn test_cache_key_suffix_includes_analytics_timeout() {
+        let default_options = ReportOptions::default();
+        let slow_storage = ReportOptions {
+            analytics_timeout_ms: Some(5000),
+            ..Default::default()
+        };
+
+        assert!(default_options
+            .to_cache_key_suffix()
+            .contains(";timeout:3000;"));
+        assert!(slow_storage
+            .to_cache_key_suffix()
+            .contains(";timeout:5000;"));
+        assert_ne!(
+            default_options.to_cache_key_suffix(),
+            slow_storage.to_cache_key_suffix()
+        );
+    }
 }
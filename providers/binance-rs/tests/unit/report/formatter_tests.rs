@@ -139,4 +139,17 @@ mod tests {
         assert_eq!(unordered, "- Only item\n");
         assert_eq!(ordered, "1. Only item\n");
     }
+
+    #[test]
+    fn test_price_decimals_for_tick() {
+        assert_eq!(price_decimals_for_tick("0.01"), 2);
+        assert_eq!(price_decimals_for_tick("0.0001"), 4);
+        assert_eq!(price_decimals_for_tick("1"), 0);
+
+        // Binance pads tick sizes to 8 decimals
+        assert_eq!(price_decimals_for_tick("0.01000000"), 2);
+        assert_eq!(price_decimals_for_tick("0.00000100"), 6);
+        assert_eq!(price_decimals_for_tick("1.00000000"), 0);
+        assert_eq!(price_decimals_for_tick("10.00000000"), 0);
+    }
 }
@@ -18,13 +18,167 @@ mod tests {
 
     #[test]
     fn test_build_orderbook_metrics_section() {
-        // TODO: Test orderbook metrics section
-        // Verify spread, microprice, imbalance calculations
+        use binance_provider::orderbook::metrics::calculate_metrics;
+        use binance_provider::orderbook::OrderBook;
+        use binance_provider::report::sections::build_orderbook_metrics_section;
+        use std::str::FromStr;
+
+        // ETHBTC trades in 0.00001 BTC ticks
+        let mut book = OrderBook::new("ETHBTC".to_string());
+        book.update_bid(
+            rust_decimal::Decimal::from_str("0.05123").unwrap(),
+            rust_decimal::Decimal::from(4),
+        );
+        book.update_ask(
+            rust_decimal::Decimal::from_str("0.05124").unwrap(),
+            rust_decimal::Decimal::from(4),
+        );
+        let metrics = calculate_metrics(&book).unwrap();
+
+        let content = build_orderbook_metrics_section(Some(&metrics), 5, true)
+            .content
+            .unwrap();
+        assert!(content.contains("| Best Bid | 0.05123 BTC |"));
+        assert!(content.contains("| Best Ask | 0.05124 BTC |"));
+        // Mid price falls between ticks and gets the extra sub-tick decimals
+        assert!(content.contains("| Mid Price | 0.05123500 BTC |"));
+
+        assert!(build_orderbook_metrics_section(None, 5, true)
+            .content
+            .is_err());
+    }
+
+    #[test]
+    fn test_sections_without_emoji_are_ascii() {
+        use binance_provider::orderbook::metrics::calculate_metrics;
+        use binance_provider::orderbook::OrderBook;
+        use binance_provider::report::sections::{
+            build_data_health_section, build_orderbook_metrics_section, build_report_header,
+        };
+        use binance_provider::report::FreshnessThresholds;
+        use rust_decimal::Decimal;
+
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        book.update_bid(Decimal::from(50_000), Decimal::from(8));
+        book.update_ask(Decimal::from(50_001), Decimal::from(2));
+        let metrics = calculate_metrics(&book).unwrap();
+
+        let metrics_section = build_orderbook_metrics_section(Some(&metrics), 2, true);
+        assert!(!metrics_section.render().is_ascii());
+
+        let plain = metrics_section.without_emoji().render();
+        assert!(plain.is_ascii(), "{}", plain);
+        assert!(plain.contains("[BUY PRESSURE]"));
+        assert!(plain.contains("[TIGHT]"));
+
+        let thresholds = FreshnessThresholds::default();
+        let header =
+            build_report_header("BTCUSDT", 1_700_000_000_000, 200, &thresholds).without_emoji();
+        let health = build_data_health_section(200, &thresholds).without_emoji();
+        for section in [header, health] {
+            let plain = section.render();
+            assert!(plain.is_ascii(), "{}", plain);
+        }
     }
 
     #[test]
     fn test_build_liquidity_analysis_section() {
-        // TODO: Test liquidity section with walls and vacuums
+        use binance_provider::orderbook::metrics::calculate_metrics;
+        use binance_provider::orderbook::OrderBook;
+        use binance_provider::report::sections::build_liquidity_analysis_section;
+        use rust_decimal::Decimal;
+
+        // Walls grow with depth, so book order lists the smallest first
+        let mut book = OrderBook::new("BTCUSDT".to_string());
+        for i in 0..20u32 {
+            let qty = match i {
+                2 => 10,
+                8 => 30,
+                15 => 20,
+                _ => 1,
+            };
+            book.update_bid(Decimal::from(50_000 - i), Decimal::from(qty));
+            book.update_ask(Decimal::from(50_001 + i), Decimal::from(1));
+        }
+        let metrics = calculate_metrics(&book).unwrap();
+        assert_eq!(metrics.walls.bids.len(), 3);
+
+        let section = build_liquidity_analysis_section(Some(&metrics), 24, 2.0, 2, 2, false);
+        let content = section.content.unwrap();
+        let largest = content.find("| 30 BTC |").expect("largest wall listed");
+        let second = content.find("| 20 BTC |").expect("second wall listed");
+        assert!(largest < second);
+        assert!(
+            !content.contains("| 10 BTC |"),
+            "walls beyond the cap are hidden"
+        );
+
+        // Sizes followed by their notional at the 50 000.5 mid
+        let section = build_liquidity_analysis_section(Some(&metrics), 24, 2.0, 2, 2, true);
+        assert!(section
+            .content
+            .unwrap()
+            .contains("| 30 BTC (~$1 500 015) |"));
+    }
+
+    #[test]
+    fn test_build_recent_trades_section() {
+        use binance_provider::binance::types::AggTradeResponse;
+        use binance_provider::report::sections::build_recent_trades_section;
+
+        let trade = |id: i64, qty: &str, is_buyer_maker: bool| AggTradeResponse {
+            agg_trade_id: id,
+            price: "50000.00".to_string(),
+            qty: qty.to_string(),
+            first_trade_id: id,
+            last_trade_id: id,
+            time: 1_700_000_000_000 + id * 250,
+            is_buyer_maker,
+            is_best_match: true,
+        };
+        let trades = vec![
+            trade(1, "1.5", false),
+            trade(2, "0.5", true),
+            trade(3, "2.0", false),
+            trade(4, "1.0", true),
+        ];
+
+        let section = build_recent_trades_section("BTCUSDT", Some(&trades), 2);
+        let content = section.content.unwrap();
+        assert_eq!(content.matches("🟢 Buy").count(), 2);
+        assert_eq!(content.matches("🔴 Sell").count(), 2);
+        assert!(content.contains(
+            "**Tape:** 3.5000 BTC bought / 1.5000 BTC sold (70.00% buys, net +2.0000 BTC)"
+        ));
+
+        // Newest trade first, carrying the running totals of the whole tape
+        let newest = content
+            .lines()
+            .find(|line| line.contains("🔴 Sell"))
+            .unwrap();
+        assert!(newest.contains("| 1.0 BTC | 3.5000 BTC | 1.5000 BTC |"));
+
+        let unavailable = build_recent_trades_section("BTCUSDT", None, 2);
+        assert!(unavailable.content.is_err());
+    }
+
+    #[test]
+    fn test_build_report_footer_customization() {
+        use binance_provider::report::sections::{build_report_footer, DEFAULT_FOOTER_ATTRIBUTION};
+
+        let default = build_report_footer(120, false, None, true);
+        assert!(default.contains(DEFAULT_FOOTER_ATTRIBUTION));
+        assert!(default.contains("**Build Configuration:**"));
+
+        let white_label = build_report_footer(120, false, Some("Acme Research Desk"), false);
+        assert!(white_label.contains("*Acme Research Desk*"));
+        assert!(!white_label.contains("ForgeTrade"));
+        assert!(!white_label.contains("Build Configuration"));
+        assert!(white_label.contains("| Generation Time | 120 ms |"));
+
+        let bare = build_report_footer(120, false, Some(""), true);
+        assert!(!bare.contains(DEFAULT_FOOTER_ATTRIBUTION));
+        assert!(bare.contains("**Build Configuration:**"));
     }
 
     #[test]
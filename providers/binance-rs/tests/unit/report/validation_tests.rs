@@ -2,7 +2,7 @@
 
 #[cfg(test)]
 mod tests {
-    // TODO: Import ReportOptions
+    use binance_provider::report::{FreshnessThresholds, ReportOptions, REPORT_SECTIONS};
 
     #[test]
     fn test_default_options() {
@@ -48,4 +48,95 @@ mod tests {
     fn test_validate_all_valid() {
         // TODO: Test validation passes with all valid options
     }
+
+    fn with_sections(sections: &[&str]) -> ReportOptions {
+        ReportOptions {
+            include_sections: Some(sections.iter().map(|s| s.to_string()).collect()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_validate_known_sections() {
+        assert!(
+            with_sections(&["price_overview", "recent_trades", "data_health"])
+                .validate()
+                .is_ok()
+        );
+        assert!(with_sections(REPORT_SECTIONS).validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_unknown_section_is_named() {
+        let err = with_sections(&["price_overview", "sentiment", "orderbook"])
+            .validate()
+            .unwrap_err();
+        assert!(
+            err.starts_with("unknown include_sections: sentiment, orderbook ("),
+            "{}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_analytics_section_without_feature() {
+        let options = with_sections(&["price_overview", "market_anomalies"]);
+
+        // Still valid (only warned about), but reported as missing its feature
+        assert!(options.validate().is_ok());
+        assert_eq!(
+            options.sections_missing_feature(false),
+            vec!["market_anomalies"]
+        );
+        assert!(options.sections_missing_feature(true).is_empty());
+    }
+
+    #[test]
+    fn test_validate_freshness_thresholds() {
+        let with_thresholds = |fresh_ms, recent_ms, stale_ms| ReportOptions {
+            freshness_thresholds: Some(FreshnessThresholds {
+                fresh_ms,
+                recent_ms,
+                stale_ms,
+            }),
+            ..Default::default()
+        };
+
+        assert!(with_thresholds(10_000, 60_000, 300_000).validate().is_ok());
+        assert!(with_thresholds(0, 5000, 30_000).validate().is_err());
+        assert!(with_thresholds(5000, 5000, 30_000).validate().is_err());
+        assert!(with_thresholds(1000, 5000, 3_600_001).validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_max_total_ms() {
+        let with_deadline = |max_total_ms| ReportOptions {
+            max_total_ms: Some(max_total_ms),
+            ..Default::default()
+        };
+
+        assert!(with_deadline(100).validate().is_ok());
+        assert!(with_deadline(60_000).validate().is_ok());
+        assert!(with_deadline(99).validate().is_err());
+        assert!(with_deadline(60_001).validate().is_err());
+    }
+
+    #[test]
+    fn test_planned_sections_follow_selection() {
+        let all = ReportOptions::default().planned_sections();
+        assert!(!all.contains(&"recent_trades"));
+        assert_eq!(all.len(), REPORT_SECTIONS.len() - 1);
+
+        let listed = ReportOptions {
+            include_sections: Some(vec![
+                "recent_trades".to_string(),
+                "price_overview".to_string(),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            listed.planned_sections(),
+            ["price_overview", "recent_trades"]
+        );
+    }
 }